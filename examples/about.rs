@@ -0,0 +1,8 @@
+use rui::*;
+
+fn main() {
+    button("About", |cx| cx.show_about(about_info!()))
+        .padding(Auto)
+        .command("Help:About", None, |cx| cx.show_about(about_info!()))
+        .run()
+}
@@ -8,15 +8,22 @@ fn main() {
     state(
         || "task not started".to_string(),
         |s, cx| {
-            hstack((
-                button("press to begin", move |_| {
-                    spawn(move || {
-                        on_main(move |cx| cx[s] = "task started".into());
-                        sleep(Duration::from_secs(2));
-                        on_main(move |cx| cx[s] = "task complete".into());
-                    });
+            vstack((
+                hstack((
+                    button("press to begin", move |_| {
+                        spawn(move || {
+                            on_main(move |cx| cx[s] = "task started".into());
+                            sleep(Duration::from_secs(2));
+                            on_main(move |cx| cx[s] = "task complete".into());
+                        });
+                    }),
+                    text(&cx[s]),
+                )),
+                // The same without a thread of our own.
+                button("press to run a future", move |cx| {
+                    cx[s] = "future started".into();
+                    cx.spawn_into(s, async { "future complete".to_string() });
                 }),
-                text(&cx[s]),
             ))
         },
     )
@@ -0,0 +1,42 @@
+use rui::*;
+
+fn main() {
+    state(
+        || 0.5,
+        |frequency, cx| {
+            let f = cx[frequency];
+            vstack((
+                // Only rebuilt when the frequency or the size changes.
+                cached_canvas((f * 1000.0) as i32, move |_, rect, list| {
+                    let mut path = CanvasPath::new();
+                    for i in 0..2000 {
+                        let t = i as f32 / 2000.0;
+                        let p = [
+                            t * rect.width(),
+                            (0.5 + 0.4 * (t * f * 200.0).sin()) * rect.height(),
+                        ];
+                        if i == 0 {
+                            path.move_to(p);
+                        } else {
+                            path.line_to(p);
+                        }
+                    }
+                    list.stroke_path(&path, 1.0, AZURE_HIGHLIGHT);
+
+                    let dot = CanvasPath::circle(rect.center(), 20.0);
+                    list.fill_path(
+                        &dot,
+                        CanvasPaint::LinearGradient {
+                            start: rect.center() - LocalVector::new(20.0, 20.0),
+                            end: rect.center() + LocalVector::new(20.0, 20.0),
+                            inner: AZURE_HIGHLIGHT,
+                            outer: RED_HIGHLIGHT,
+                        },
+                    );
+                }),
+                hslider(frequency).padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
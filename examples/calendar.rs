@@ -0,0 +1,43 @@
+use rui::*;
+
+fn main() {
+    let today = CalendarDate::today();
+    let events = vec![
+        CalendarEvent::new(today, "Standup").time(9 * 60 + 30, 9 * 60 + 45),
+        CalendarEvent::new(today, "Design review")
+            .time(11 * 60, 12 * 60)
+            .color(GREEN_HIGHLIGHT),
+        CalendarEvent::new(today, "Lunch").time(12 * 60, 13 * 60),
+        CalendarEvent::new(today, "Release").color(RED_HIGHLIGHT_DARK),
+        CalendarEvent::new(today.add_days(2), "Offsite"),
+    ];
+
+    state(
+        || CalendarLayout::Month,
+        move |layout, cx| {
+            let (events, shown) = (events.clone(), cx[layout]);
+            vstack((
+                hstack((
+                    button("Month", move |cx| cx[layout] = CalendarLayout::Month),
+                    button("Week", move |cx| cx[layout] = CalendarLayout::Week),
+                )),
+                state(
+                    move || events.clone(),
+                    move |events, _| {
+                        calendar(events)
+                            .layout(shown)
+                            .on_create(move |cx, date, minute| {
+                                let event = CalendarEvent::new(date, "New event");
+                                cx[events].push(match minute {
+                                    Some(m) => event.time(m, m + 60),
+                                    None => event,
+                                });
+                            })
+                            .on_event(|_, i| println!("event {}", i))
+                    },
+                ),
+            ))
+        },
+    )
+    .run()
+}
@@ -0,0 +1,25 @@
+use rui::*;
+
+fn main() {
+    let sizes = vec!["Small".to_string(), "Medium".into(), "Large".into()];
+    let mut table = GridTable::new()
+        .column("Task", CellKind::Text)
+        .column("Hours", CellKind::Number)
+        .column("Done", CellKind::Checkbox)
+        .column("Size", CellKind::Dropdown(sizes));
+    for i in 0..100 {
+        table = table.row(vec![
+            CellValue::Text(format!("Task {}", i + 1)),
+            CellValue::Number((i % 8) as f64),
+            CellValue::Bool(i % 3 == 0),
+            CellValue::Text("Small".into()),
+        ]);
+    }
+
+    state(
+        move || table.clone(),
+        |table, _| data_grid(table).frozen_columns(1),
+    )
+    .padding(Auto)
+    .run()
+}
@@ -0,0 +1,32 @@
+use rui::*;
+
+const OLD: &str = "fn main() {
+    let name = \"world\";
+    println!(\"hello {}\", name);
+}
+";
+
+const NEW: &str = "fn main() {
+    let name = \"rui\";
+    println!(\"hello {}\", name);
+    println!(\"goodbye\");
+}
+";
+
+fn main() {
+    state(
+        || false,
+        |unified, cx| {
+            let mode = if cx[unified] {
+                DiffMode::Unified
+            } else {
+                DiffMode::SideBySide
+            };
+            vstack((
+                hstack((toggle(unified), text("Unified").padding(Auto))),
+                diff_view(OLD, NEW).mode(mode),
+            ))
+        },
+    )
+    .run()
+}
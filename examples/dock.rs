@@ -0,0 +1,28 @@
+use rui::*;
+
+fn panel(name: &str) -> AnyView {
+    match name {
+        "Editor" => any_view(state(
+            || "fn main() {}".to_string(),
+            |source, _| text_editor(source).padding(Auto),
+        )),
+        "Console" => any_view(text("$ cargo run").padding(Auto)),
+        _ => any_view(text(name).padding(Auto)),
+    }
+}
+
+fn main() {
+    state(
+        || {
+            let mut layout = DockLayout::new();
+            layout.dock("Files", DockArea::Left);
+            layout.dock("Editor", DockArea::Center);
+            layout.dock("Outline", DockArea::Right);
+            layout.dock("Console", DockArea::Bottom);
+            layout.dock("Problems", DockArea::Bottom);
+            layout
+        },
+        |layout, _| dock_space(layout, panel),
+    )
+    .run()
+}
@@ -0,0 +1,34 @@
+use rui::*;
+
+fn swatch() -> impl View {
+    canvas(|_, rect, vger| {
+        let colors = [RED_HIGHLIGHT, AZURE_HIGHLIGHT, GREEN_HIGHLIGHT];
+        for (i, color) in colors.iter().enumerate() {
+            let c = rect.center() + LocalVector::new(i as f32 * 20.0 - 20.0, 0.0);
+            let paint = vger.color_paint(*color);
+            vger.fill_circle(c, 25.0, paint);
+        }
+    })
+    .size([120.0, 80.0])
+    .padding(Auto)
+}
+
+// Inverts the colors, which come premultiplied by alpha.
+const INVERT: &str = "
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let c = sample_source(uv);
+    return vec4<f32>(vec3<f32>(c.a) - c.rgb, c.a);
+}
+";
+
+fn main() {
+    vstack((
+        hstack((swatch(), swatch().blur(8.0))),
+        hstack((swatch().brightness(0.5), swatch().saturation(0.0))),
+        hstack((
+            swatch().shader(INVERT, &[]),
+            text("Blurred text").padding(Auto).blur(1.5),
+        )),
+    ))
+    .run()
+}
@@ -0,0 +1,26 @@
+use rui::*;
+
+fn main() {
+    state(
+        || 0.4,
+        |value, cx| {
+            vstack((
+                hstack((
+                    map(
+                        cx[value] * 120.0,
+                        |_, _| (),
+                        |speed, _| {
+                            gauge(speed, 0.0..120.0)
+                                .ticks(6, 3)
+                                .zone(90.0..120.0, RED_HIGHLIGHT)
+                                .padding(Auto)
+                        },
+                    ),
+                    radial_progress(value).padding(Auto),
+                )),
+                hslider(value).padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
@@ -0,0 +1,23 @@
+// Run with `cargo run --example global_hotkey --features global-hotkeys`.
+use rui::*;
+
+fn main() {
+    state(
+        || 0,
+        |count, cx| {
+            vstack((
+                format!("hotkey pressed {} times", cx[count]).padding(Auto),
+                button("register Ctrl+Shift+K", move |cx| {
+                    let mods = KeyboardModifiers {
+                        shift: true,
+                        control: true,
+                        ..Default::default()
+                    };
+                    cx.register_global_hotkey((mods, HotKey::KeyK), move |cx| cx[count] += 1);
+                })
+                .padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
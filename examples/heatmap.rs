@@ -0,0 +1,31 @@
+use rui::*;
+
+fn main() {
+    // Made-up activity by day and hour.
+    let matrix: Vec<Vec<f32>> = (0..7)
+        .map(|day| {
+            (0..24)
+                .map(|hour| {
+                    let work = if (9..18).contains(&hour) && day < 5 {
+                        6.0
+                    } else {
+                        0.0
+                    };
+                    work + ((day * 7 + hour * 13) % 5) as f32
+                })
+                .collect()
+        })
+        .collect();
+
+    heatmap(matrix, ColorScale::default())
+        .row_labels(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"])
+        .column_labels((0..24).map(|h| {
+            if h % 3 == 0 {
+                h.to_string()
+            } else {
+                String::new()
+            }
+        }))
+        .padding(Auto)
+        .run()
+}
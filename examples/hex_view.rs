@@ -0,0 +1,13 @@
+use rui::*;
+
+fn main() {
+    // This example's own executable, read as it's scrolled through.
+    let exe = std::env::current_exe().expect("no executable path");
+    let file = HexFile::open(exe).expect("couldn't open executable");
+
+    hex_view(file)
+        .editable(true)
+        .on_edit(|_, offset, byte| println!("{:08X}: {:02X}", offset, byte))
+        .padding(Auto)
+        .run()
+}
@@ -0,0 +1,22 @@
+use rui::*;
+
+fn main() {
+    vstack((
+        hstack((
+            icon(Icon::Search).color(MEDIUM_GRAY),
+            text("Search"),
+            spacer(),
+            icon(Icon::Close).icon_size(12.0),
+        ))
+        .padding(Auto),
+        list(Icon::ALL.to_vec(), |&i| {
+            hstack((
+                icon(i).icon_size(24.0).color(AZURE_HIGHLIGHT),
+                text(&format!("{:?}", i)),
+                spacer(),
+            ))
+            .padding(Auto)
+        }),
+    ))
+    .run()
+}
@@ -0,0 +1,29 @@
+use rui::*;
+
+fn main() {
+    // A gradient, so there's something to show without an image file.
+    let (width, height) = (64, 32);
+    let mut rgba = vec![];
+    for y in 0..height {
+        for x in 0..width {
+            rgba.extend_from_slice(&[(x * 4) as u8, (y * 8) as u8, 160, 255]);
+        }
+    }
+    let pixels = std::sync::Arc::new(RgbaImage {
+        width,
+        height,
+        rgba,
+    });
+
+    hstack((
+        image(pixels.clone()).padding(Auto),
+        image(pixels.clone())
+            .content_mode(ContentMode::Fill)
+            .corner_radius(16.0)
+            .padding(Auto),
+        image(pixels)
+            .content_mode(ContentMode::Stretch)
+            .padding(Auto),
+    ))
+    .run()
+}
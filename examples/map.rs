@@ -0,0 +1,38 @@
+use rui::*;
+
+fn main() {
+    let markers = vec![
+        MapMarker::new(LatLng::new(51.5081, -0.0759), "Tower of London"),
+        MapMarker::new(LatLng::new(51.5007, -0.1246), "Big Ben").color(AZURE_HIGHLIGHT),
+        MapMarker::new(LatLng::new(51.5194, -0.1270), "British Museum").color(GREEN_HIGHLIGHT),
+    ];
+
+    state(
+        || None,
+        move |selected: StateHandle<Option<usize>>, cx| {
+            let markers = markers.clone();
+            let title = match cx[selected] {
+                Some(i) => markers[i].title.clone(),
+                None => "Tap a marker".into(),
+            };
+            vstack((
+                text(&title).padding(Auto),
+                state(
+                    || LatLng::new(51.5072, -0.1000),
+                    move |center, _| {
+                        let markers = markers.clone();
+                        state(
+                            || 13.0,
+                            move |zoom, _| {
+                                map_view(center, zoom)
+                                    .markers(markers.clone())
+                                    .on_marker(move |cx, i| cx[selected] = Some(i))
+                            },
+                        )
+                    },
+                ),
+            ))
+        },
+    )
+    .run()
+}
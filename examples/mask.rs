@@ -0,0 +1,51 @@
+use rui::*;
+
+/// Fills its bounds with a gradient.
+fn gradient() -> impl View {
+    canvas(|_, rect, vger| {
+        let (a, b) = (AZURE_HIGHLIGHT, RED_HIGHLIGHT);
+        let paint = vger.linear_gradient(rect.min(), rect.max(), a, b, 0.0);
+        vger.fill_rect(rect, 0.0, paint);
+    })
+}
+
+/// Bars lit up to `value`, masking the gradient.
+fn meter(value: f32) -> impl View {
+    gradient().size([240.0, 24.0]).mask(move || {
+        canvas(move |_, rect, vger| {
+            let paint = vger.color_paint(Color::WHITE);
+            let bars = 12;
+            let width = rect.width() / bars as f32;
+            for i in 0..((value * bars as f32) as usize) {
+                let bar = LocalRect::new(
+                    [i as f32 * width + 2.0, 0.0].into(),
+                    [width - 4.0, rect.height()].into(),
+                );
+                vger.fill_rect(bar, 2.0, paint);
+            }
+        })
+    })
+}
+
+fn main() {
+    state(
+        || 0.6,
+        |value, cx| {
+            let hero = Paint::Gradient {
+                start: [0.0, 0.0].into(),
+                end: [200.0, 0.0].into(),
+                inner_color: AZURE_HIGHLIGHT,
+                outer_color: RED_HIGHLIGHT,
+            };
+            vstack((
+                text("Hero header")
+                    .font_size(40)
+                    .foreground(hero)
+                    .padding(Auto),
+                meter(cx[value]).padding(Auto),
+                hslider(value).padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
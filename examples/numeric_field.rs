@@ -0,0 +1,18 @@
+use rui::*;
+
+fn main() {
+    state(
+        || 1.0,
+        |volume, cx| {
+            vstack((
+                numeric_field(volume)
+                    .range(0.0..=11.0)
+                    .step(0.5)
+                    .decimals(1)
+                    .padding(Auto),
+                format!("volume: {}", cx[volume]).padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
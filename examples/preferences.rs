@@ -0,0 +1,23 @@
+use rui::*;
+
+// Press Cmd+, (Ctrl+, on Windows and Linux) to open preferences.
+
+fn main() {
+    zstack((
+        text("Press Cmd+, for preferences").padding(Auto),
+        preferences_window(vec![
+            (
+                "General",
+                any_view(state(|| true, |s, _| hstack((text("Autosave"), toggle(s))))),
+            ),
+            (
+                "Appearance",
+                any_view(state(
+                    || 0.5,
+                    |s, _| vstack((text("Contrast"), hslider(s))).size([300.0, 80.0]),
+                )),
+            ),
+        ]),
+    ))
+    .run()
+}
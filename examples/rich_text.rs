@@ -0,0 +1,15 @@
+use rui::*;
+
+fn main() {
+    let text = AttributedText::new("Rich text can be big, bold, red, struck out or a link.")
+        .size(17..20, 32)
+        .weight(22..26, FontWeight::Bold)
+        .color(28..31, RED_HIGHLIGHT)
+        .strikethrough(33..43)
+        .link(49..53, "https://github.com/audulus/rui");
+
+    rich_text(text)
+        .on_link(|_, url| println!("open {}", url))
+        .padding(Auto)
+        .run()
+}
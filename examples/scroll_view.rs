@@ -0,0 +1,14 @@
+use rui::*;
+
+fn main() {
+    state(LocalOffset::zero, |position, _| {
+        vstack((
+            button("Back to top", move |cx| cx[position] = LocalOffset::zero()),
+            scroll_view(list((0usize..100).collect(), |i| {
+                text(&format!("row {}", i)).padding(Auto)
+            }))
+            .scroll_position(position),
+        ))
+    })
+    .run()
+}
@@ -0,0 +1,26 @@
+use rui::*;
+
+fn row(name: &str, values: Vec<f32>) -> impl View {
+    let last = values.last().copied().unwrap_or_default();
+    hstack((
+        text(name).size([60.0, 16.0]),
+        sparkline(values)
+            .area(true)
+            .min_max(true)
+            .size([120.0, 20.0]),
+        format!("{:.1}", last).size([40.0, 16.0]),
+    ))
+    .padding(Auto)
+}
+
+fn main() {
+    vstack((
+        row("CPU", vec![12.0, 30.0, 22.0, 48.0, 41.0, 65.0, 52.0, 38.0]),
+        row(
+            "Memory",
+            vec![40.0, 41.0, 41.5, 43.0, 44.0, 44.0, 47.5, 48.0],
+        ),
+        row("Network", vec![2.0, 9.0, 1.0, 0.5, 7.0, 3.0, 11.0, 4.0]),
+    ))
+    .run()
+}
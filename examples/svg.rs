@@ -0,0 +1,26 @@
+use rui::*;
+
+const STAR: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">
+    <defs>
+        <linearGradient id="g" x1="0" y1="0" x2="0" y2="1">
+            <stop offset="0" stop-color="#ffd54f"/>
+            <stop offset="1" stop-color="#ff8f00"/>
+        </linearGradient>
+    </defs>
+    <path d="M12 2l3 7h7l-5.5 4.5 2 7.5-6.5-4.5-6.5 4.5 2-7.5L2 9h7z"
+        fill="url(#g)" stroke="#5d4037" stroke-width="1"/>
+</svg>"##;
+
+fn main() {
+    hstack((
+        svg(STAR).size([16.0, 16.0]),
+        svg(STAR).size([32.0, 32.0]),
+        svg(STAR).size([64.0, 64.0]),
+        svg(STAR).tint(AZURE_HIGHLIGHT).size([64.0, 64.0]),
+        svg(STAR)
+            .content_mode(ContentMode::Fill)
+            .size([128.0, 48.0]),
+    ))
+    .padding(Auto)
+    .run()
+}
@@ -14,8 +14,32 @@ fn main() {
         )
         .padding(Auto),
         state(
-            move || lorem.to_string(),
-            |state, _| text_editor(state).padding(Auto),
+            || "fn main() {\n    println!(\"hello\");\n}".to_string(),
+            |state, _| {
+                state(Vec::<usize>::new, move |breakpoints, _| {
+                    text_editor(state)
+                        .language(CodeLanguage)
+                        .line_numbers(true)
+                        .gutter_icon(move |cx, line| {
+                            cx[breakpoints]
+                                .contains(&line)
+                                .then_some(GutterIcon::Breakpoint)
+                        })
+                        .on_gutter_tap(move |cx, line| {
+                            let breakpoints = &mut cx[breakpoints];
+                            match breakpoints.iter().position(|l| *l == line) {
+                                Some(i) => {
+                                    breakpoints.remove(i);
+                                }
+                                None => breakpoints.push(line),
+                            }
+                        })
+                        .line_changes(BaseTextDiff::new(
+                            "fn main() {\n    println!(\"hello\");\n}",
+                        ))
+                        .padding(Auto)
+                })
+            },
         )
         .background(
             rectangle()
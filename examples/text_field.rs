@@ -0,0 +1,31 @@
+use rui::*;
+
+fn main() {
+    state(String::new, |name, _| {
+        state(String::new, move |zip, cx| {
+            vstack((
+                text_field(name)
+                    .placeholder("Name")
+                    .clear_button(true)
+                    .on_submit(|_, name| println!("submitted {}", name))
+                    .padding(Auto),
+                text_field(zip)
+                    .placeholder("Zip code")
+                    .max_length(5)
+                    .on_editing_changed(|_, editing| println!("editing: {}", editing))
+                    .padding(Auto),
+                state(String::new, |phone, _| {
+                    text_field(phone).input_mask("(###) ###-####").padding(Auto)
+                }),
+                state(String::new, |password, _| {
+                    text_field(password)
+                        .placeholder("Password")
+                        .secure(true)
+                        .padding(Auto)
+                }),
+                format!("{} {}", cx[name], cx[zip]).padding(Auto),
+            ))
+        })
+    })
+    .run()
+}
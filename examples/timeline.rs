@@ -0,0 +1,30 @@
+use rui::*;
+
+fn main() {
+    let items = vec![
+        TimelineItem::new(0, 0.0, 4.0, "Titles"),
+        TimelineItem::new(0, 4.0, 12.0, "Interview"),
+        TimelineItem::new(1, 0.0, 16.0, "Music").color(GREEN_HIGHLIGHT),
+        TimelineItem::new(2, 6.0, 2.0, "Whoosh").color(RED_HIGHLIGHT_DARK),
+    ];
+
+    state(
+        || 2.0,
+        move |playhead, cx| {
+            let items = items.clone();
+            vstack((
+                format!("{:.2}s", cx[playhead]).padding(Auto),
+                state(
+                    move || items.clone(),
+                    move |items, _| {
+                        timeline(items)
+                            .lanes(&["Video", "Music", "Effects"])
+                            .snap(0.25)
+                            .playhead(playhead)
+                    },
+                ),
+            ))
+        },
+    )
+    .run()
+}
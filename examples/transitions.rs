@@ -0,0 +1,30 @@
+use rui::*;
+
+fn main() {
+    state(
+        || false,
+        |shown, cx| {
+            vstack((
+                button("Switch", move |cx| cx[shown] = !cx[shown]),
+                cond(
+                    cx[shown],
+                    circle()
+                        .color(AZURE_HIGHLIGHT)
+                        .transition(Transition::Scale),
+                    rectangle()
+                        .corner_radius(5.0)
+                        .color(RED_HIGHLIGHT)
+                        .transition(Transition::Slide),
+                )
+                .padding(Auto),
+                cond(
+                    cx[shown],
+                    text("Shown").transition(Transition::Fade),
+                    text("Hidden").transition(Transition::Fade),
+                )
+                .padding(Auto),
+            ))
+        },
+    )
+    .run()
+}
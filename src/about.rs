@@ -0,0 +1,353 @@
+use crate::*;
+
+pub(crate) const PANEL_WIDTH: f32 = 320.0;
+pub(crate) const PANEL_PADDING: f32 = 20.0;
+pub(crate) const BUTTON_HEIGHT: f32 = 28.0;
+const BUTTON_FONT_SIZE: u32 = 13;
+const MAX_CREDITS_HEIGHT: f32 = 240.0;
+
+/// What `Context::show_about` shows. Use `about_info!()` to fill it in from
+/// the app's Cargo metadata.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AboutInfo {
+    pub name: String,
+    pub version: String,
+
+    /// Shown large above the name. vger can't draw images yet, so this is
+    /// text, like an emoji or a monogram. Defaults to the name's initial.
+    pub icon: Option<String>,
+
+    /// Headings, bullet lists and paragraphs in Markdown. Emphasis, code
+    /// and links are shown as plain text.
+    pub credits_markdown: String,
+}
+
+/// An `AboutInfo` with the name, version and authors from the calling
+/// crate's Cargo.toml.
+///
+/// ```no_run
+/// # use rui::*;
+/// # let cx = &mut Context::new();
+/// cx.show_about(about_info!());
+/// ```
+#[macro_export]
+macro_rules! about_info {
+    () => {
+        $crate::AboutInfo {
+            name: env!("CARGO_PKG_NAME").into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            icon: None,
+            credits_markdown: $crate::authors_markdown(env!("CARGO_PKG_AUTHORS")),
+        }
+    };
+}
+
+/// Credits for Cargo's colon-separated author list.
+#[doc(hidden)]
+pub fn authors_markdown(authors: &str) -> String {
+    let authors: Vec<&str> = authors.split(':').filter(|a| !a.is_empty()).collect();
+    if authors.is_empty() {
+        return String::new();
+    }
+    let mut md = String::from("# Authors\n\n");
+    for author in authors {
+        // Drop email addresses.
+        let name = match author.find('<') {
+            Some(i) => author[..i].trim(),
+            None => author.trim(),
+        };
+        md += &format!("- {}\n", name);
+    }
+    md
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Block {
+    Heading(String),
+    Bullet(String),
+    Paragraph(String),
+}
+
+/// Removes emphasis and code markers, and keeps just the text of links.
+fn plain(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(i) = rest.find('[') {
+        out += &rest[..i];
+        let link = &rest[i + 1..];
+        match (link.find("]("), link.find(')')) {
+            (Some(close), Some(end)) if close < end => {
+                out += &link[..close];
+                rest = &link[end + 1..];
+            }
+            _ => {
+                out.push('[');
+                rest = link;
+            }
+        }
+    }
+    out += rest;
+    out.replace("**", "").replace(['*', '`'], "")
+}
+
+fn parse_markdown(md: &str) -> Vec<Block> {
+    let mut credits = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+
+    fn end_paragraph(paragraph: &mut Vec<&str>, credits: &mut Vec<Block>) {
+        if !paragraph.is_empty() {
+            credits.push(Block::Paragraph(plain(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    }
+
+    for line in md.lines().map(|l| l.trim()) {
+        if line.starts_with('#') {
+            end_paragraph(&mut paragraph, &mut credits);
+            credits.push(Block::Heading(plain(line.trim_start_matches('#').trim())));
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            end_paragraph(&mut paragraph, &mut credits);
+            credits.push(Block::Bullet(plain(item)));
+        } else if line.is_empty() {
+            end_paragraph(&mut paragraph, &mut credits);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    end_paragraph(&mut paragraph, &mut credits);
+    credits
+}
+
+/// A line of text in a panel.
+pub(crate) struct PanelItem {
+    pub(crate) text: String,
+    pub(crate) size: u32,
+    pub(crate) color: Color,
+    pub(crate) centered: bool,
+    /// Space above.
+    pub(crate) gap: f32,
+}
+
+/// Lines for some Markdown text.
+pub(crate) fn markdown_items(md: &str) -> Vec<PanelItem> {
+    parse_markdown(md)
+        .into_iter()
+        .map(|block| match block {
+            Block::Heading(text) => PanelItem {
+                text,
+                size: 14,
+                color: TEXT_COLOR,
+                centered: false,
+                gap: 12.0,
+            },
+            Block::Bullet(text) => PanelItem {
+                text: format!("• {}", text),
+                size: 12,
+                color: TEXT_COLOR,
+                centered: false,
+                gap: 2.0,
+            },
+            Block::Paragraph(text) => PanelItem {
+                text,
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: false,
+                gap: 8.0,
+            },
+        })
+        .collect()
+}
+
+/// Total height of `items`, wrapped to `width`.
+pub(crate) fn items_height(vger: &mut Vger, items: &[PanelItem], width: f32) -> f32 {
+    items
+        .iter()
+        .map(|item| {
+            item.gap
+                + vger
+                    .text_bounds(&item.text, item.size, Some(width))
+                    .height()
+        })
+        .sum()
+}
+
+/// Dims a window of `size` and draws a panel's background at `frame`.
+pub(crate) fn draw_panel(vger: &mut Vger, size: LocalSize, frame: LocalRect) {
+    let paint = vger.color_paint(BLACK.alpha(0.4));
+    vger.fill_rect(LocalRect::from_size(size), 0.0, paint);
+    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+    vger.fill_rect(frame, 8.0, paint);
+    let paint = vger.color_paint(MEDIUM_GRAY);
+    vger.stroke_rect(frame.min(), frame.max(), 8.0, 1.0, paint);
+}
+
+/// The About panel shown by `Context::show_about`.
+pub(crate) struct AboutPanel {
+    info: AboutInfo,
+
+    /// Where the panel was last drawn, in window coordinates.
+    pub(crate) frame: LocalRect,
+}
+
+impl AboutPanel {
+    pub(crate) fn new(info: AboutInfo) -> Self {
+        Self {
+            info,
+            frame: LocalRect::zero(),
+        }
+    }
+
+    fn items(&self) -> (Vec<PanelItem>, Vec<PanelItem>) {
+        let icon = match &self.info.icon {
+            Some(icon) => icon.clone(),
+            None => self
+                .info
+                .name
+                .chars()
+                .next()
+                .map(|c| c.to_uppercase().to_string())
+                .unwrap_or_default(),
+        };
+        let header = vec![
+            PanelItem {
+                text: icon,
+                size: 48,
+                color: TEXT_COLOR,
+                centered: true,
+                gap: 0.0,
+            },
+            PanelItem {
+                text: self.info.name.clone(),
+                size: 18,
+                color: TEXT_COLOR,
+                centered: true,
+                gap: 12.0,
+            },
+            PanelItem {
+                text: format!("Version {}", self.info.version),
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: true,
+                gap: 4.0,
+            },
+        ];
+        let credits = markdown_items(&self.info.credits_markdown);
+        (header, credits)
+    }
+
+    /// Draws the panel centered in a window of `size`, over a dimmed UI.
+    pub(crate) fn draw(&mut self, vger: &mut Vger, size: LocalSize) {
+        let width = PANEL_WIDTH - 2.0 * PANEL_PADDING;
+        let (header, credits) = self.items();
+
+        let header_height = items_height(vger, &header, width);
+        let credits_height = items_height(vger, &credits, width).min(MAX_CREDITS_HEIGHT);
+
+        let panel_size = LocalSize::new(
+            PANEL_WIDTH,
+            header_height + credits_height + 2.0 * PANEL_PADDING,
+        );
+        self.frame = LocalRect::new(
+            ((size - panel_size) / 2.0).to_vector().to_point(),
+            panel_size,
+        );
+
+        draw_panel(vger, size, self.frame);
+
+        // Lay out top to bottom.
+        let mut top = self.frame.max_y() - PANEL_PADDING;
+        for item in &header {
+            top = draw_item(vger, item, self.frame, top, width);
+        }
+
+        vger.save();
+        vger.scissor(LocalRect::new(
+            [self.frame.min_x(), top - credits_height].into(),
+            [PANEL_WIDTH, credits_height].into(),
+        ));
+        for item in &credits {
+            top = draw_item(vger, item, self.frame, top, width);
+        }
+        vger.restore();
+    }
+}
+
+/// Draws an item below `top` and returns the new top.
+pub(crate) fn draw_item(
+    vger: &mut Vger,
+    item: &PanelItem,
+    frame: LocalRect,
+    top: f32,
+    width: f32,
+) -> f32 {
+    let top = top - item.gap;
+    let bounds = vger.text_bounds(&item.text, item.size, Some(width));
+    let x = if item.centered {
+        frame.center().x - bounds.width() / 2.0
+    } else {
+        frame.min_x() + PANEL_PADDING
+    };
+    vger.save();
+    vger.translate([x - bounds.min_x(), top - bounds.max_y()]);
+    vger.text(&item.text, item.size, item.color, Some(width));
+    vger.restore();
+    top - bounds.height()
+}
+
+/// Draws a row of buttons along the bottom of a panel, right to left,
+/// with the first (the default) highlighted. Returns where they are.
+pub(crate) fn draw_buttons(vger: &mut Vger, frame: LocalRect, titles: &[&str]) -> Vec<LocalRect> {
+    let mut rects = vec![];
+    let mut right = frame.max_x() - PANEL_PADDING;
+    for (i, title) in titles.iter().enumerate() {
+        let bounds = vger.text_bounds(title, BUTTON_FONT_SIZE, None);
+        let rect = LocalRect::new(
+            [right - bounds.width() - 24.0, frame.min_y() + PANEL_PADDING].into(),
+            [bounds.width() + 24.0, BUTTON_HEIGHT].into(),
+        );
+        let color = if i == 0 {
+            AZURE_HIGHLIGHT
+        } else {
+            CONTROL_BACKGROUND
+        };
+        let paint = vger.color_paint(color);
+        vger.fill_rect(rect, 5.0, paint);
+        vger.save();
+        vger.translate(rect.center() - bounds.center());
+        vger.text(title, BUTTON_FONT_SIZE, TEXT_COLOR, None);
+        vger.restore();
+
+        rects.push(rect);
+        right = rect.min_x() - 8.0;
+    }
+    rects
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_markdown() {
+        let md = "# Thanks\n\nBuilt with **vger** and\n[winit](https://github.com/rust-windowing/winit).\n\n- Alice\n* `Bob`\n";
+        assert_eq!(
+            parse_markdown(md),
+            [
+                Block::Heading("Thanks".into()),
+                Block::Paragraph("Built with vger and winit.".into()),
+                Block::Bullet("Alice".into()),
+                Block::Bullet("Bob".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_authors_markdown() {
+        assert_eq!(
+            authors_markdown("Ann <ann@example.com>:Bo"),
+            "# Authors\n\n- Ann\n- Bo\n"
+        );
+        assert_eq!(authors_markdown(""), "");
+    }
+}
@@ -0,0 +1,260 @@
+use crate::*;
+
+const DEBUGGER_WIDTH: f32 = 260.0;
+const ROW_HEIGHT: f32 = 16.0;
+const SLIDER_HEIGHT: f32 = 24.0;
+
+/// How much slower animations run in slow motion.
+const SLOW_MOTION: f32 = 0.1;
+
+/// An animation which was moving as of the last animation frame.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ActiveAnimation {
+    pub(crate) name: &'static str,
+    /// How far through it is, for animations which know.
+    pub(crate) progress: Option<f32>,
+}
+
+/// The panel shown by `Context::show_animation_debugger`.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AnimDebugger {
+    paused: bool,
+    slow: bool,
+    /// Where the scrubber is, from 0 to 1, once it's been moved while
+    /// paused.
+    scrub: Option<f32>,
+    scrubbing: bool,
+
+    /// Where things were last drawn, in window coordinates.
+    frame: LocalRect,
+    /// Pause, then speed.
+    buttons: Vec<LocalRect>,
+    slider: LocalRect,
+}
+
+impl AnimDebugger {
+    /// How far along the slider `x` is.
+    fn slider_value(&self, x: f32) -> f32 {
+        ((x - self.slider.min_x()) / self.slider.width()).clamp(0.0, 1.0)
+    }
+
+    fn draw(&mut self, vger: &mut Vger, animations: &[ActiveAnimation]) {
+        let width = DEBUGGER_WIDTH - 2.0 * PANEL_PADDING;
+        let mut items = vec![PanelItem {
+            text: "Animations".into(),
+            size: 14,
+            color: TEXT_COLOR,
+            centered: false,
+            gap: 0.0,
+        }];
+        if animations.is_empty() {
+            items.push(PanelItem {
+                text: "None running".into(),
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: false,
+                gap: 8.0,
+            });
+        }
+        for anim in animations {
+            let text = match anim.progress {
+                Some(p) => format!("{}  {:.0}%", anim.name, p * 100.0),
+                None => anim.name.to_string(),
+            };
+            items.push(PanelItem {
+                text,
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: false,
+                gap: ROW_HEIGHT - 12.0,
+            });
+        }
+
+        let height =
+            items_height(vger, &items, width) + SLIDER_HEIGHT + BUTTON_HEIGHT + 3.0 * PANEL_PADDING;
+        self.frame = LocalRect::new(
+            [PANEL_PADDING, PANEL_PADDING].into(),
+            [DEBUGGER_WIDTH, height].into(),
+        );
+
+        let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR.alpha(0.95));
+        vger.fill_rect(self.frame, 8.0, paint);
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        vger.stroke_rect(self.frame.min(), self.frame.max(), 8.0, 1.0, paint);
+
+        let mut top = self.frame.max_y() - PANEL_PADDING;
+        for item in &items {
+            top = draw_item(vger, item, self.frame, top, width);
+        }
+
+        // The scrubber, above the buttons.
+        self.slider = LocalRect::new(
+            [
+                self.frame.min_x() + PANEL_PADDING,
+                self.frame.min_y() + 2.0 * PANEL_PADDING + BUTTON_HEIGHT,
+            ]
+            .into(),
+            [width, SLIDER_HEIGHT].into(),
+        );
+        let y = self.slider.center().y;
+        let color = if self.paused {
+            TEXT_COLOR
+        } else {
+            MEDIUM_GRAY.alpha(0.5)
+        };
+        let paint = vger.color_paint(color);
+        vger.stroke_segment(
+            [self.slider.min_x(), y],
+            [self.slider.max_x(), y],
+            2.0,
+            paint,
+        );
+        let x = self.slider.min_x() + self.scrub.unwrap_or(0.0) * self.slider.width();
+        vger.fill_circle([x, y], 6.0, paint);
+
+        let pause = if self.paused { "Play" } else { "Pause" };
+        let speed = if self.slow {
+            "Normal Speed"
+        } else {
+            "Slow (10%)"
+        };
+        self.buttons = draw_buttons(vger, self.frame, &[pause, speed]);
+    }
+}
+
+impl Context {
+    /// Shows or hides a panel listing the animations which are running,
+    /// with buttons to pause them or slow them down to 10%, and a slider
+    /// to scrub through them while paused. For fine-tuning motion.
+    pub fn show_animation_debugger(&mut self, show: bool) {
+        self.anim_debugger = if show {
+            Some(AnimDebugger::default())
+        } else {
+            None
+        };
+        self.set_dirty();
+    }
+
+    /// Lists an animation in the debugger, if it's showing. Called by
+    /// animating views on each animation frame while they're moving.
+    pub(crate) fn note_animation(&mut self, name: &'static str, progress: Option<f32>) {
+        if self.anim_debugger.is_some() {
+            self.active_animations
+                .push(ActiveAnimation { name, progress });
+        }
+    }
+
+    /// Where the debugger has scrubbed animations to, from 0 to 1.
+    /// Animations which know how far along they are jump there.
+    pub(crate) fn anim_scrub(&self) -> Option<f32> {
+        self.anim_debugger
+            .as_ref()
+            .filter(|d| d.paused)
+            .and_then(|d| d.scrub)
+    }
+
+    /// Slows down or stops the time between animation frames, as the
+    /// debugger says.
+    pub(crate) fn debug_anim_dt(&self, dt: f32) -> f32 {
+        match &self.anim_debugger {
+            Some(d) if d.paused => 0.0,
+            Some(d) if d.slow => dt * SLOW_MOTION,
+            _ => dt,
+        }
+    }
+
+    pub(crate) fn draw_anim_debugger(&mut self, vger: &mut Vger) {
+        if let Some(debugger) = &mut self.anim_debugger {
+            debugger.draw(vger, &self.active_animations);
+        }
+    }
+
+    /// Handles touches on the debugger. Returns true if it took the event.
+    pub(crate) fn process_anim_debugger(&mut self, event: &Event) -> bool {
+        let debugger = match &mut self.anim_debugger {
+            Some(debugger) => debugger,
+            None => return false,
+        };
+        let took = match event {
+            Event::TouchBegin { position, .. } if debugger.frame.contains(*position) => {
+                match debugger
+                    .buttons
+                    .iter()
+                    .position(|rect| rect.contains(*position))
+                {
+                    Some(0) => {
+                        debugger.paused = !debugger.paused;
+                        debugger.scrub = None;
+                    }
+                    Some(_) => debugger.slow = !debugger.slow,
+                    None => {
+                        if debugger.paused && debugger.slider.contains(*position) {
+                            debugger.scrubbing = true;
+                            debugger.scrub = Some(debugger.slider_value(position.x));
+                        }
+                    }
+                }
+                true
+            }
+            Event::TouchMove { position, .. } if debugger.scrubbing => {
+                debugger.scrub = Some(debugger.slider_value(position.x));
+                true
+            }
+            Event::TouchEnd { .. } if debugger.scrubbing => {
+                debugger.scrubbing = false;
+                true
+            }
+            _ => false,
+        };
+        if took {
+            self.set_dirty();
+        }
+        took
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_anim_debugger() {
+        let mut cx = Context::new();
+        assert_eq!(cx.debug_anim_dt(1.0), 1.0);
+        cx.note_animation("spring", None);
+        assert!(cx.active_animations.is_empty());
+
+        cx.show_animation_debugger(true);
+        let debugger = cx.anim_debugger.as_mut().unwrap();
+        debugger.frame = LocalRect::new([0.0, 0.0].into(), [200.0, 200.0].into());
+        debugger.buttons = vec![
+            LocalRect::new([100.0, 0.0].into(), [50.0, 20.0].into()),
+            LocalRect::new([0.0, 0.0].into(), [50.0, 20.0].into()),
+        ];
+        debugger.slider = LocalRect::new([0.0, 50.0].into(), [100.0, 20.0].into());
+
+        let tap = |cx: &mut Context, x: f32, y: f32| {
+            cx.process_anim_debugger(&Event::TouchBegin {
+                id: 0,
+                position: [x, y].into(),
+            })
+        };
+
+        assert!(tap(&mut cx, 10.0, 10.0));
+        assert_eq!(cx.debug_anim_dt(1.0), SLOW_MOTION);
+
+        assert!(tap(&mut cx, 110.0, 10.0));
+        assert_eq!(cx.debug_anim_dt(1.0), 0.0);
+
+        assert!(tap(&mut cx, 25.0, 60.0));
+        assert_eq!(cx.anim_scrub(), Some(0.25));
+
+        // Outside the panel.
+        cx.process_anim_debugger(&Event::TouchEnd {
+            id: 0,
+            position: [25.0, 60.0].into(),
+        });
+        assert!(!tap(&mut cx, 300.0, 10.0));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Somewhere asset files come from.
+pub trait AssetSource: Send + Sync {
+    /// Reads the file at `path`, which uses forward slashes, like
+    /// "icons/open.png".
+    fn read(&self, path: &str) -> Result<Vec<u8>, String>;
+}
+
+/// Files in a folder.
+pub struct FileSource {
+    root: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl AssetSource for FileSource {
+    fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        let file = self.root.join(path);
+        std::fs::read(&file).map_err(|err| format!("{}: {}", file.display(), err))
+    }
+}
+
+/// Files compiled into the app, with `include_bytes!`.
+///
+/// ```
+/// # use rui::*;
+/// let source = EmbeddedSource::new(&[("hello.txt", b"hello")]);
+/// ```
+pub struct EmbeddedSource {
+    files: HashMap<&'static str, &'static [u8]>,
+}
+
+impl EmbeddedSource {
+    pub fn new(files: &[(&'static str, &'static [u8])]) -> Self {
+        Self {
+            files: files.iter().copied().collect(),
+        }
+    }
+}
+
+impl AssetSource for EmbeddedSource {
+    fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        match self.files.get(path) {
+            Some(data) => Ok(data.to_vec()),
+            None => Err(format!("{} isn't embedded", path)),
+        }
+    }
+}
+
+/// A folder compiled into the app with `include_dir!`.
+#[cfg(feature = "include_dir")]
+impl AssetSource for include_dir::Dir<'static> {
+    fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        match self.get_file(path) {
+            Some(file) => Ok(file.contents().to_vec()),
+            None => Err(format!("{} isn't embedded", path)),
+        }
+    }
+}
+
+/// Files on a web server, under `base_url`.
+#[cfg(all(feature = "http-assets", not(target_arch = "wasm32")))]
+pub struct HttpSource {
+    base_url: String,
+}
+
+#[cfg(all(feature = "http-assets", not(target_arch = "wasm32")))]
+impl HttpSource {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+        }
+    }
+}
+
+#[cfg(all(feature = "http-assets", not(target_arch = "wasm32")))]
+impl AssetSource for HttpSource {
+    fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        let url = format!("{}/{}", self.base_url, path);
+        let response = ureq::get(&url)
+            .call()
+            .map_err(|err| format!("{}: {}", url, err))?;
+        let mut data = vec![];
+        std::io::Read::read_to_end(&mut response.into_reader(), &mut data)
+            .map_err(|err| format!("{}: {}", url, err))?;
+        Ok(data)
+    }
+}
+
+/// Where the app's images, fonts and other resources come from, in the
+/// environment. Sources are tried in order, so a release build can embed
+/// its assets while a development build reads them from disk, and views
+/// load them the same way.
+///
+/// ```no_run
+/// # use rui::*;
+/// static ICONS: &[(&str, &[u8])] = &[("open.png", &[])];
+///
+/// let assets = if cfg!(debug_assertions) {
+///     Assets::files("assets")
+/// } else {
+///     Assets::new().with(EmbeddedSource::new(ICONS))
+/// };
+/// rui(env(|assets: Assets, _| text("hello")).env(assets));
+/// ```
+#[derive(Clone)]
+pub struct Assets {
+    sources: Vec<Arc<dyn AssetSource>>,
+}
+
+impl Default for Assets {
+    /// The "assets" folder in the working directory.
+    fn default() -> Self {
+        Assets::files("assets")
+    }
+}
+
+impl Assets {
+    /// No sources.
+    pub fn new() -> Self {
+        Self { sources: vec![] }
+    }
+
+    /// Files in the folder at `root`.
+    pub fn files(root: impl Into<PathBuf>) -> Self {
+        Self::new().with(FileSource::new(root))
+    }
+
+    /// Adds a source, tried after the others.
+    pub fn with(mut self, source: impl AssetSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Reads the file at `path` from the first source which has it.
+    pub fn read(&self, path: &str) -> Result<Vec<u8>, String> {
+        let mut errors = vec![];
+        for source in &self.sources {
+            match source.read(path) {
+                Ok(data) => return Ok(data),
+                Err(err) => errors.push(err),
+            }
+        }
+        if errors.is_empty() {
+            Err(format!("no asset sources for {}", path))
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Reads and decodes the file at `path` in the background. See
+    /// `Context::load_asset`, which this uses with `path` as the key.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load<T: Send + Sync + 'static>(
+        &self,
+        cx: &mut Context,
+        path: &str,
+        decode: impl FnOnce(Vec<u8>) -> Result<T, String> + Send + 'static,
+    ) -> Asset<T> {
+        let assets = self.clone();
+        let file = path.to_string();
+        cx.load_asset(path, move || decode(assets.read(&file)?))
+    }
+}
+
+impl Context {
+    /// The `Assets` in the environment.
+    pub fn assets(&mut self) -> Assets {
+        self.init_env(&Assets::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_assets() {
+        let assets = Assets::new()
+            .with(EmbeddedSource::new(&[("a.txt", b"embedded")]))
+            .with(FileSource::new(env!("CARGO_MANIFEST_DIR")));
+
+        assert_eq!(assets.read("a.txt").unwrap(), b"embedded");
+        assert!(assets.read("Cargo.toml").is_ok());
+        assert!(assets.read("missing.txt").is_err());
+        assert!(Assets::new().read("a.txt").is_err());
+    }
+}
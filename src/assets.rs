@@ -0,0 +1,160 @@
+use crate::*;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Most decode threads to start.
+const MAX_DECODE_THREADS: usize = 4;
+
+type Decoded = Result<Arc<dyn Any + Send + Sync>, String>;
+type Job = Box<dyn FnOnce() + Send>;
+
+/// An asset requested with `Context::load_asset`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Asset<T> {
+    /// Being decoded on a background thread. The UI updates when it's done.
+    Loading,
+    Ready(Arc<T>),
+    Failed(String),
+}
+
+/// Decodes assets on background threads and keeps the results.
+#[derive(Default)]
+pub(crate) struct AssetPool {
+    /// Sends work to the decode threads, which start on first use.
+    jobs: Option<mpsc::Sender<Job>>,
+    /// Finished work, picked up by the next update.
+    done: Arc<Mutex<Vec<(String, Decoded)>>>,
+    in_flight: HashSet<String>,
+    pub(crate) cache: HashMap<String, Decoded>,
+}
+
+impl AssetPool {
+    fn start(&mut self) -> &mpsc::Sender<Job> {
+        self.jobs.get_or_insert_with(|| {
+            let (tx, rx) = mpsc::channel::<Job>();
+            let rx = Arc::new(Mutex::new(rx));
+            let threads = std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+                .min(MAX_DECODE_THREADS);
+            for _ in 0..threads {
+                let rx = rx.clone();
+                std::thread::spawn(move || loop {
+                    let job = rx.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        // The Context is gone.
+                        Err(_) => break,
+                    }
+                });
+            }
+            tx
+        })
+    }
+}
+
+impl Context {
+    /// Gets the asset named `key`, decoding it with `decode` on a background
+    /// thread the first time it's asked for, so decoding a folder of images
+    /// doesn't freeze the UI. Requests for an asset that's still decoding
+    /// share the one decode. Returns `Asset::Loading` until the result is
+    /// picked up, at the start of the next update.
+    ///
+    /// Outside of `image` and `svg`, with the `images` and `svg` features,
+    /// rui doesn't decode assets itself. Pass a function that calls a crate
+    /// like `image` or `ttf-parser`.
+    pub fn load_asset<T: Send + Sync + 'static>(
+        &mut self,
+        key: &str,
+        decode: impl FnOnce() -> Result<T, String> + Send + 'static,
+    ) -> Asset<T> {
+        if let Some(decoded) = self.assets.cache.get(key) {
+            return match decoded {
+                Ok(value) => match value.clone().downcast::<T>() {
+                    Ok(value) => Asset::Ready(value),
+                    Err(_) => Asset::Failed(format!("asset {:?} has a different type", key)),
+                },
+                Err(err) => Asset::Failed(err.clone()),
+            };
+        }
+
+        if self.assets.in_flight.insert(key.to_string()) {
+            let done = self.assets.done.clone();
+            let key = key.to_string();
+            let job = Box::new(move || {
+                let decoded = decode().map(|value| Arc::new(value) as Arc<dyn Any + Send + Sync>);
+                done.lock().unwrap().push((key, decoded));
+
+                // Wake up the event loop.
+                #[cfg(feature = "winit")]
+                on_main(|_| ());
+            });
+            if self.assets.start().send(job).is_err() {
+                println!("asset decode threads have stopped");
+            }
+        }
+        Asset::Loading
+    }
+
+    /// Forgets a decoded asset, so it's decoded again next time it's asked for.
+    pub fn evict_asset(&mut self, key: &str) {
+        if self.assets.cache.remove(key).is_some() {
+            self.set_dirty();
+        }
+    }
+
+    /// Moves finished decodes into the cache.
+    pub(crate) fn poll_assets(&mut self) {
+        let done = std::mem::take(&mut *self.assets.done.lock().unwrap());
+        if done.is_empty() {
+            return;
+        }
+        for (key, decoded) in done {
+            self.assets.in_flight.remove(&key);
+            self.assets.cache.insert(key, decoded);
+        }
+        self.set_dirty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_load_asset() {
+        let mut cx = Context::new();
+        let decodes = Arc::new(AtomicUsize::new(0));
+
+        let load = |cx: &mut Context| {
+            let decodes = decodes.clone();
+            cx.load_asset("a.png", move || {
+                decodes.fetch_add(1, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(20));
+                Ok(42u32)
+            })
+        };
+
+        assert_eq!(load(&mut cx), Asset::Loading);
+        assert_eq!(load(&mut cx), Asset::Loading);
+
+        let mut asset = Asset::Loading;
+        for _ in 0..200 {
+            cx.poll_assets();
+            asset = load(&mut cx);
+            if asset != Asset::Loading {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(asset, Asset::Ready(Arc::new(42)));
+        assert_eq!(decodes.load(Ordering::SeqCst), 1);
+
+        cx.evict_asset("a.png");
+        assert_eq!(cx.assets.cache.len(), 0);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::*;
+use std::ops::Range;
+
+/// How heavy text is drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FontWeight {
+    #[default]
+    Regular,
+    Bold,
+}
+
+/// How a run of `AttributedText` looks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextAttributes {
+    pub size: u32,
+    pub weight: FontWeight,
+    pub color: Color,
+    pub underline: bool,
+    pub strikethrough: bool,
+    /// Passed to `RichText::on_link` when the run is tapped.
+    pub link: Option<String>,
+}
+
+impl Default for TextAttributes {
+    fn default() -> Self {
+        Self {
+            size: Text::DEFAULT_SIZE,
+            weight: FontWeight::Regular,
+            color: TEXT_COLOR,
+            underline: false,
+            strikethrough: false,
+            link: None,
+        }
+    }
+}
+
+/// One attribute set over a range.
+#[derive(Clone, Debug, PartialEq)]
+enum Attribute {
+    Size(u32),
+    Weight(FontWeight),
+    Color(Color),
+    Underline,
+    Strikethrough,
+    Link(String),
+}
+
+impl Attribute {
+    fn apply(&self, attrs: &mut TextAttributes) {
+        match self {
+            Attribute::Size(size) => attrs.size = *size,
+            Attribute::Weight(weight) => attrs.weight = *weight,
+            Attribute::Color(color) => attrs.color = *color,
+            Attribute::Underline => attrs.underline = true,
+            Attribute::Strikethrough => attrs.strikethrough = true,
+            Attribute::Link(url) => attrs.link = Some(url.clone()),
+        }
+    }
+}
+
+/// Text with attributes set over ranges of it, for `rich_text`. Ranges
+/// are in bytes, like string slices, and where they overlap, the
+/// attribute set last wins.
+///
+/// ```
+/// # use rui::*;
+/// let t = AttributedText::new("Read the docs, now!")
+///     .link(9..13, "https://docs.rs/rui")
+///     .weight(15..18, FontWeight::Bold);
+/// assert_eq!(t.runs().len(), 5);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributedText {
+    text: String,
+    spans: Vec<(Range<usize>, Attribute)>,
+}
+
+impl AttributedText {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            spans: vec![],
+        }
+    }
+
+    /// The text, without attributes.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Panics if `range` doesn't start and end on characters of the text,
+    /// as slicing it would.
+    fn span(mut self, range: Range<usize>, attr: Attribute) -> Self {
+        assert!(
+            range.start <= range.end
+                && self.text.is_char_boundary(range.start)
+                && self.text.is_char_boundary(range.end),
+            "range {:?} isn't within the text's characters",
+            range
+        );
+        self.spans.push((range, attr));
+        self
+    }
+
+    pub fn size(self, range: Range<usize>, size: u32) -> Self {
+        self.span(range, Attribute::Size(size))
+    }
+
+    pub fn weight(self, range: Range<usize>, weight: FontWeight) -> Self {
+        self.span(range, Attribute::Weight(weight))
+    }
+
+    pub fn color(self, range: Range<usize>, color: Color) -> Self {
+        self.span(range, Attribute::Color(color))
+    }
+
+    pub fn underline(self, range: Range<usize>) -> Self {
+        self.span(range, Attribute::Underline)
+    }
+
+    pub fn strikethrough(self, range: Range<usize>) -> Self {
+        self.span(range, Attribute::Strikethrough)
+    }
+
+    /// Makes `range` a link to `url`, colored and underlined unless other
+    /// attributes set after this say otherwise.
+    pub fn link(self, range: Range<usize>, url: impl Into<String>) -> Self {
+        self.span(range.clone(), Attribute::Link(url.into()))
+            .span(range.clone(), Attribute::Color(AZURE_HIGHLIGHT))
+            .span(range, Attribute::Underline)
+    }
+
+    /// Adds `other` to the end, keeping its attributes.
+    pub fn append(mut self, other: AttributedText) -> Self {
+        let offset = self.text.len();
+        self.text.push_str(&other.text);
+        self.spans.extend(
+            other
+                .spans
+                .into_iter()
+                .map(|(r, attr)| (r.start + offset..r.end + offset, attr)),
+        );
+        self
+    }
+
+    /// Splits the text into runs which look the same throughout.
+    pub fn runs(&self) -> Vec<(Range<usize>, TextAttributes)> {
+        let mut bounds: Vec<usize> = vec![0, self.text.len()];
+        for (r, _) in &self.spans {
+            bounds.push(r.start);
+            bounds.push(r.end);
+        }
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut runs: Vec<(Range<usize>, TextAttributes)> = vec![];
+        for pair in bounds.windows(2) {
+            let range = pair[0]..pair[1];
+            let mut attrs = TextAttributes::default();
+            for (r, attr) in &self.spans {
+                if r.start <= range.start && range.end <= r.end {
+                    attr.apply(&mut attrs);
+                }
+            }
+            match runs.last_mut() {
+                Some((last, a)) if *a == attrs => last.end = range.end,
+                _ => runs.push((range, attrs)),
+            }
+        }
+        runs
+    }
+}
+
+impl From<&str> for AttributedText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for AttributedText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_runs() {
+        let t = AttributedText::new("big bold link")
+            .size(0..8, 24)
+            .weight(4..8, FontWeight::Bold)
+            .link(9..13, "rui://link")
+            .color(9..13, RED_HIGHLIGHT);
+        let runs = t.runs();
+        let ranges: Vec<_> = runs.iter().map(|(r, _)| r.clone()).collect();
+        assert_eq!(ranges, vec![0..4, 4..8, 8..9, 9..13]);
+
+        assert_eq!(runs[0].1.size, 24);
+        assert_eq!(runs[0].1.weight, FontWeight::Regular);
+        assert_eq!(runs[1].1.weight, FontWeight::Bold);
+        assert_eq!(runs[2].1, TextAttributes::default());
+
+        let link = &runs[3].1;
+        assert_eq!(link.link.as_deref(), Some("rui://link"));
+        assert!(link.underline);
+        // Set after the link's own color.
+        assert_eq!(link.color, RED_HIGHLIGHT);
+
+        assert!(AttributedText::new("").runs().is_empty());
+    }
+
+    #[test]
+    fn test_append() {
+        let t = AttributedText::new("a ").append(AttributedText::new("b").underline(0..1));
+        assert_eq!(t.text(), "a b");
+        let runs = t.runs();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].0, 2..3);
+        assert!(runs[1].1.underline);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_outside_chars() {
+        AttributedText::new("é").underline(0..1);
+    }
+}
@@ -52,3 +52,303 @@ pub const MEDIUM_GRAY: Color = Color {
 
 pub const GROOVES: Color = Color::hex_const("#252A2B");
 pub const GROOVES_DARK: Color = Color::hex_const("#0D0D0D");
+
+/// Minimum contrast ratios from the Web Content Accessibility Guidelines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WcagLevel {
+    /// Body text.
+    AA,
+    /// Text at least 18pt, or 14pt bold.
+    AALargeText,
+    /// Body text, enhanced.
+    AAA,
+    AAALargeText,
+}
+
+impl WcagLevel {
+    pub fn min_contrast(self) -> f32 {
+        match self {
+            WcagLevel::AA => 4.5,
+            WcagLevel::AALargeText => 3.0,
+            WcagLevel::AAA => 7.0,
+            WcagLevel::AAALargeText => 4.5,
+        }
+    }
+}
+
+fn to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Linear sRGB from OKLab, which may be out of gamut.
+fn oklab_to_linear(l: f32, a: f32, b: f32) -> [f32; 3] {
+    let l_ = (l + 0.396_337_8 * a + 0.215_803_8 * b).powi(3);
+    let m_ = (l - 0.105_561_3 * a - 0.063_854_17 * b).powi(3);
+    let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_97 * s_,
+        -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_4 * s_,
+        -0.004_196_086 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+    ]
+}
+
+/// More ways to make and adjust colors. Colors are sRGB, like hex colors.
+///
+/// ```
+/// # use rui::*;
+/// let accent = vger::color::Color::from_oklch(0.7, 0.15, 250.0, 1.0);
+/// let hover = accent.lighten(0.1);
+/// assert!(TEXT_COLOR.meets_wcag(BLACK, WcagLevel::AA));
+/// ```
+pub trait ColorExt: Sized {
+    /// Hue in degrees, saturation and lightness from 0 to 1.
+    fn from_hsl(h: f32, s: f32, l: f32, a: f32) -> Self;
+    fn to_hsl(&self) -> (f32, f32, f32);
+
+    /// Perceptual lightness from 0 to 1, chroma (about 0 to 0.37) and hue
+    /// in degrees. Chroma is reduced if the color can't be shown.
+    fn from_oklch(l: f32, c: f32, h: f32, a: f32) -> Self;
+    fn to_oklch(&self) -> (f32, f32, f32);
+
+    /// Adds `amount` to the perceptual lightness, keeping the hue.
+    fn lighten(&self, amount: f32) -> Self;
+    fn darken(&self, amount: f32) -> Self;
+
+    /// Relative luminance, as defined by WCAG.
+    fn luminance(&self) -> f32;
+
+    /// From 1, for the same luminance, to 21 for black on white. Alpha is
+    /// ignored.
+    fn contrast_ratio(&self, other: Self) -> f32;
+
+    fn meets_wcag(&self, other: Self, level: WcagLevel) -> bool {
+        self.contrast_ratio(other) >= level.min_contrast()
+    }
+
+    /// `n` shades of the same hue, from light to dark.
+    fn shades(&self, n: usize) -> Vec<Self>;
+}
+
+impl ColorExt for Color {
+    fn from_hsl(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = l - c / 2.0;
+        Color::new(r + m, g + m, b + m, a)
+    }
+
+    fn to_hsl(&self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return (0.0, 0.0, l);
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == self.r {
+            ((self.g - self.b) / d).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / d + 2.0
+        } else {
+            (self.r - self.g) / d + 4.0
+        };
+        (h * 60.0, s, l)
+    }
+
+    fn from_oklch(l: f32, c: f32, h: f32, a: f32) -> Self {
+        let (sin, cos) = h.to_radians().sin_cos();
+        let in_gamut = |rgb: &[f32; 3]| rgb.iter().all(|c| (-1e-4..=1.0 + 1e-4).contains(c));
+
+        let mut rgb = oklab_to_linear(l, c * cos, c * sin);
+        if !in_gamut(&rgb) {
+            // Find the most chroma that fits.
+            let (mut lo, mut hi) = (0.0, c);
+            for _ in 0..16 {
+                let mid = (lo + hi) / 2.0;
+                if in_gamut(&oklab_to_linear(l, mid * cos, mid * sin)) {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            rgb = oklab_to_linear(l, lo * cos, lo * sin);
+        }
+        let [r, g, b] = rgb.map(|c| from_linear(c.clamp(0.0, 1.0)));
+        Color::new(r, g, b, a)
+    }
+
+    fn to_oklch(&self) -> (f32, f32, f32) {
+        let (r, g, b) = (to_linear(self.r), to_linear(self.g), to_linear(self.b));
+        let l = (0.412_221_5 * r + 0.536_332_5 * g + 0.051_446 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_397 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_8 * g + 0.629_978_7 * b).cbrt();
+        let lightness = 0.210_454_3 * l + 0.793_617_8 * m - 0.004_072_047 * s;
+        let a = 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s;
+        let b = 0.025_904_04 * l + 0.782_771_8 * m - 0.808_675_8 * s;
+        let c = (a * a + b * b).sqrt();
+        let h = if c < 1e-4 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        };
+        (lightness, c, h)
+    }
+
+    fn lighten(&self, amount: f32) -> Self {
+        let (l, c, h) = self.to_oklch();
+        Color::from_oklch((l + amount).clamp(0.0, 1.0), c, h, self.a)
+    }
+
+    fn darken(&self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    fn luminance(&self) -> f32 {
+        0.2126 * to_linear(self.r) + 0.7152 * to_linear(self.g) + 0.0722 * to_linear(self.b)
+    }
+
+    fn contrast_ratio(&self, other: Self) -> f32 {
+        let (a, b) = (self.luminance(), other.luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    fn shades(&self, n: usize) -> Vec<Self> {
+        let (_, c, h) = self.to_oklch();
+        (0..n)
+            .map(|i| {
+                let t = if n > 1 {
+                    i as f32 / (n - 1) as f32
+                } else {
+                    0.5
+                };
+                Color::from_oklch(0.95 - 0.8 * t, c, h, self.a)
+            })
+            .collect()
+    }
+}
+
+/// Colors for an app, generated from one seed color, in the same roles as
+/// the built-in colors like `AZURE_HIGHLIGHT` and `CONTROL_BACKGROUND`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub accent: Color,
+    pub accent_dark: Color,
+    pub accent_background: Color,
+    pub background: Color,
+    pub control: Color,
+    pub text: Color,
+    /// For `elevation` shadows.
+    pub shadow: Color,
+}
+
+impl Default for Palette {
+    /// The built-in colors.
+    fn default() -> Self {
+        Self {
+            accent: AZURE_HIGHLIGHT,
+            accent_dark: AZURE_HIGHLIGHT_DARK,
+            accent_background: AZURE_HIGHLIGHT_BACKGROUND,
+            background: BLACK,
+            control: CONTROL_BACKGROUND,
+            text: TEXT_COLOR,
+            shadow: BLACK.alpha(0.5),
+        }
+    }
+}
+
+impl Palette {
+    /// A dark palette with `seed`'s hue. The accent is made light enough to
+    /// stand out, and the text meets WCAG AA on the background.
+    pub fn from_seed(seed: Color) -> Self {
+        let (l, c, h) = seed.to_oklch();
+        let accent = Color::from_oklch(l.max(0.7), c, h, 1.0);
+        Self {
+            accent,
+            accent_dark: accent.darken(0.15),
+            accent_background: Color::from_oklch(0.15, c * 0.3, h, 1.0),
+            background: Color::from_oklch(0.12, c.min(0.01), h, 1.0),
+            control: Color::from_oklch(0.25, c.min(0.01), h, 1.0),
+            text: Color::from_oklch(0.88, c.min(0.005), h, 1.0),
+            shadow: Color::from_oklch(0.05, c.min(0.02), h, 0.5),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn assert_close(a: Color, b: Color) {
+        let d = (a.r - b.r).abs() + (a.g - b.g).abs() + (a.b - b.b).abs();
+        assert!(d < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_color_spaces() {
+        let (h, s, l) = AZURE_HIGHLIGHT.to_hsl();
+        assert_close(Color::from_hsl(h, s, l, 1.0), AZURE_HIGHLIGHT);
+        assert_close(
+            Color::from_hsl(120.0, 1.0, 0.5, 1.0),
+            Color::new(0.0, 1.0, 0.0, 1.0),
+        );
+
+        for color in [RED_HIGHLIGHT, GROOVES, Color::WHITE, BLACK] {
+            let (l, c, h) = color.to_oklch();
+            assert_close(Color::from_oklch(l, c, h, 1.0), color);
+        }
+        let (l, c, _) = Color::WHITE.to_oklch();
+        assert!((l - 1.0).abs() < 1e-3 && c < 1e-3);
+
+        // Out of gamut colors are brought in.
+        let c = Color::from_oklch(0.9, 0.4, 30.0, 1.0);
+        assert!([c.r, c.g, c.b].iter().all(|c| (0.0..=1.0).contains(c)));
+
+        assert!(AZURE_HIGHLIGHT.lighten(0.1).luminance() > AZURE_HIGHLIGHT.luminance());
+        assert!(AZURE_HIGHLIGHT.darken(0.1).luminance() < AZURE_HIGHLIGHT.luminance());
+    }
+
+    #[test]
+    fn test_contrast() {
+        assert!((BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 0.01);
+        assert_eq!(BLACK.contrast_ratio(BLACK), 1.0);
+        assert!(TEXT_COLOR.meets_wcag(BUTTON_BACKGROUND_COLOR, WcagLevel::AAA));
+        assert!(!MEDIUM_GRAY.meets_wcag(Color::WHITE, WcagLevel::AA));
+    }
+
+    #[test]
+    fn test_palette() {
+        let palette = Palette::from_seed(RED_HIGHLIGHT_DARK);
+        assert!(palette.text.meets_wcag(palette.background, WcagLevel::AA));
+        assert!(palette.text.meets_wcag(palette.control, WcagLevel::AA));
+        assert!(palette.accent.luminance() > palette.accent_dark.luminance());
+
+        let shades = AZURE_HIGHLIGHT.shades(5);
+        assert_eq!(shades.len(), 5);
+        assert!(shades
+            .windows(2)
+            .all(|w| w[0].luminance() > w[1].luminance()));
+    }
+}
@@ -3,8 +3,13 @@ use euclid::*;
 use std::any::Any;
 use std::any::TypeId;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::iter::FromIterator;
 use std::ops;
+use std::str::FromStr;
+use std::time::Duration;
+
+use instant::Instant;
 
 pub type LocalSpace = vger::defs::LocalSpace;
 pub type WorldSpace = vger::defs::WorldSpace;
@@ -34,12 +39,110 @@ pub(crate) struct LayoutBox {
 pub(crate) struct StateHolder {
     pub state: Box<dyn Any>,
     pub dirty: bool,
+    /// Updates since the view was last in the UI.
+    pub orphaned: u32,
+    /// Set by `named_state`, for debugging.
+    pub name: Option<&'static str>,
+    pub type_name: &'static str,
+    /// Frame in which the state was created or last changed.
+    pub modified: u64,
+}
+
+impl StateHolder {
+    pub(crate) fn new<S: 'static>(value: S, frame: u64) -> Self {
+        Self {
+            state: Box::new(value),
+            dirty: false,
+            orphaned: 0,
+            name: None,
+            type_name: std::any::type_name::<S>(),
+            modified: frame,
+        }
+    }
+}
+
+/// A state slot, from `Context::dump_state`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateInfo {
+    pub id: ViewId,
+    /// The name given to `named_state`.
+    pub name: Option<&'static str>,
+    pub type_name: &'static str,
+    /// The frame in which the state last changed. See `Context::frame`.
+    pub modified_frame: u64,
+}
+
+impl Display for StateInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name {
+            Some(name) => write!(f, "{} ({}, id {})", name, self.type_name, self.id.id),
+            None => write!(f, "{} (id {})", self.type_name, self.id.id),
+        }
+    }
+}
+
+/// Why the UI was updated, when `Context::explain_updates` is on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdateExplanation {
+    pub frame: u64,
+    /// State that changed. Empty if something else, like a window resize or
+    /// an animation, caused the update.
+    pub changed: Vec<StateInfo>,
+    /// The `state` views whose contents were laid out again.
+    pub relaid_out: Vec<StateInfo>,
+}
+
+/// When the state of views that have left the UI is dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateRetention {
+    /// At the next update.
+    Immediate,
+
+    /// Once the view has been gone for this many updates, so state survives
+    /// views that are briefly removed, like rows scrolled out of a list.
+    Updates(u32),
+
+    /// Never. Use `Context::purge_state` to drop it.
+    Never,
+}
+
+impl Default for StateRetention {
+    fn default() -> Self {
+        StateRetention::Immediate
+    }
 }
 
 pub(crate) type StateMap = HashMap<ViewId, StateHolder>;
 
 pub(crate) type EnvMap = HashMap<TypeId, Box<dyn Any>>;
 
+/// How often animations run while the window is inactive and power saving is on.
+pub const POWER_SAVER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Animation interval while the window is active.
+const ANIM_INTERVAL: Duration = Duration::from_micros(16_667);
+
+/// Identifies a callback registered with `Context::on_idle`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdleId(usize);
+
+struct IdleHandler {
+    id: IdleId,
+    duration: Duration,
+    fired: bool,
+    func: std::rc::Rc<dyn Fn(&mut Context)>,
+}
+
+/// Identifies a hotkey registered with `Context::register_global_hotkey`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlobalHotKeyId(usize);
+
+struct GlobalHotKeyHandler {
+    id: GlobalHotKeyId,
+    keys: KeyChord,
+    func: std::rc::Rc<dyn Fn(&mut Context)>,
+}
+
 pub struct RenderInfo<'a> {
     pub device: &'a wgpu::Device,
     pub surface: &'a wgpu::Surface,
@@ -51,10 +154,10 @@ pub struct RenderInfo<'a> {
 /// shouldn't have to interact with it directly.
 pub struct Context {
     /// Layout information for all views.
-    layout: HashMap<IdPath, LayoutBox>,
+    pub(crate) layout: HashMap<IdPath, LayoutBox>,
 
     /// Allocated ViewIds.
-    view_ids: HashMap<IdPath, ViewId>,
+    pub(crate) view_ids: HashMap<IdPath, ViewId>,
 
     /// Next allocated id.
     next_id: ViewId,
@@ -74,15 +177,74 @@ pub struct Context {
     /// Keyboard modifiers state.
     pub key_mods: KeyboardModifiers,
 
+    /// How command hotkeys are matched against the keyboard layout.
+    pub shortcut_matching: ShortcutMatching,
+
+    /// When state of views that are no longer shown is dropped.
+    pub state_retention: StateRetention,
+
+    /// Log which state changes caused each update, and which views were
+    /// laid out again as a result. See `last_update_explanation`.
+    pub explain_updates: bool,
+
+    /// `state` views laid out in this update, when explaining updates.
+    pub(crate) relaid_out: Vec<ViewId>,
+
+    last_update_explanation: Option<UpdateExplanation>,
+
     /// The view that has the keyboard focus.
     pub(crate) focused_id: Option<ViewId>,
 
+    /// Where the focused text view's caret was drawn, so input methods
+    /// can place their candidate windows. `None` when nothing is taking
+    /// text input.
+    pub(crate) text_input_area: Option<WorldRect>,
+
+    /// The About panel, if showing.
+    about: Option<AboutPanel>,
+
+    /// Asks whether to send the last crash report, if showing.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) crash_sheet: Option<CrashSheet>,
+
+    /// The name of the document with unsaved changes, if any, and the
+    /// sheet asking whether to save it before closing.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) edited_document: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) close_sheet: Option<CloseSheet>,
+    /// The user chose Save on the close sheet.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) save_before_close: bool,
+
+    /// The "Update available" sheet, if showing, and what to do when the
+    /// user chooses to install.
+    #[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+    pub(crate) update_sheet: Option<UpdateSheet>,
+    #[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+    pub(crate) update_handler: Option<UpdateHandler>,
+
+    /// Files and URLs to open, for `Event::OpenFiles` and `Event::OpenUrl`.
+    #[cfg_attr(
+        not(all(feature = "winit", not(target_arch = "wasm32"))),
+        allow(dead_code)
+    )]
+    pub(crate) open_files: Vec<std::path::PathBuf>,
+    #[cfg_attr(
+        not(all(feature = "winit", not(target_arch = "wasm32"))),
+        allow(dead_code)
+    )]
+    pub(crate) open_urls: Vec<String>,
+
     /// The current title of the window
     pub window_title: String,
 
     /// Are we fullscreen?
     pub fullscreen: bool,
 
+    /// What's drawn behind the UI.
+    pub window_background: WindowBackground,
+
     /// User state created by `state`.
     pub(crate) state_map: StateMap,
 
@@ -104,6 +266,9 @@ pub struct Context {
     /// A stack of ids for states to get parent dependencies.
     pub(crate) id_stack: Vec<ViewId>,
 
+    /// Is `gc` finding dependencies, rather than state in use?
+    pub(crate) collecting_deps: bool,
+
     /// Previous window size.
     window_size: Size2D<f32, WorldSpace>,
 
@@ -120,6 +285,171 @@ pub struct Context {
 
     /// Value of grab_cursor before processing event.
     pub(crate) prev_grab_cursor: bool,
+
+    /// When we last received user input.
+    last_input: Instant,
+
+    /// Callbacks registered with `on_idle`.
+    idle_handlers: Vec<IdleHandler>,
+
+    /// Next idle callback id.
+    next_idle_id: usize,
+
+    /// Callbacks registered with `after` and `every`.
+    pub(crate) timers: Vec<Timer>,
+
+    /// Next timer id.
+    pub(crate) next_timer_id: usize,
+
+    /// Copy and paste.
+    pub pasteboard: Pasteboard,
+
+    /// Fonts loaded for `Font::named`.
+    pub fonts: Fonts,
+
+    /// Decoded images and their textures.
+    pub(crate) images: ImageCache,
+
+    /// Layers drawn for modifiers like `blur`, composited after drawing.
+    pub(crate) effects: Effects,
+
+    /// Where views drawn now are clipped to, so images drawn outside
+    /// vger can be clipped the same.
+    pub(crate) clip_rect: Option<WorldRect>,
+
+    /// The layer views drawn now go in, if any.
+    pub(crate) layer: Option<usize>,
+
+    /// Where the window is, in points.
+    pub(crate) window_position: Option<[f32; 2]>,
+
+    /// Window size and position to restore, for the event loop.
+    pub(crate) pending_window: Option<WindowSpec>,
+
+    /// Changes from `set_window_size` and friends, for the event loop.
+    #[cfg_attr(not(feature = "winit"), allow(dead_code))]
+    pub(crate) window_changes: Vec<WindowChange>,
+
+    /// Dock layouts and panel state as of the last layout.
+    workspace: WorkspaceSpec,
+
+    /// Restored dock layouts and panel state which haven't appeared yet.
+    pending_workspace: WorkspaceSpec,
+
+    /// Drag out of the window started by a `drag_out` view.
+    pub(crate) drag_out: Option<DragOutRequest>,
+
+    /// Drag between views started by a `draggable` view.
+    pub(crate) drag: Option<ActiveDrag>,
+
+    /// Touches as seen by the gesture modifiers.
+    pub(crate) gestures: Gestures,
+
+    /// Set by `transition` views, so `cond` knows to animate.
+    pub(crate) transition_seen: bool,
+
+    /// Where views with each `matched_geometry` id are.
+    pub(crate) matched_geometry: HashMap<String, MatchedFrame>,
+
+    /// Shown by `show_animation_debugger`.
+    pub(crate) anim_debugger: Option<AnimDebugger>,
+
+    /// Animations which moved in the last animation frame, for the
+    /// debugger.
+    pub(crate) active_animations: Vec<ActiveAnimation>,
+
+    /// Callbacks registered with `register_global_hotkey`.
+    global_hotkeys: Vec<GlobalHotKeyHandler>,
+
+    /// Next global hotkey id.
+    next_global_hotkey_id: usize,
+
+    /// Set when global hotkeys need to be registered with the OS again.
+    pub(crate) global_hotkeys_changed: bool,
+
+    /// Throttle animations and redraws while the window is inactive.
+    pub power_saver: bool,
+
+    /// Does the window have keyboard focus?
+    pub(crate) window_focused: bool,
+
+    /// Is the window hidden from view?
+    pub(crate) window_occluded: bool,
+
+    /// When animations were last run.
+    last_anim: Option<Instant>,
+
+    /// Time since the previous animation tick, in seconds.
+    pub(crate) anim_dt: f32,
+
+    /// Did the last update change anything?
+    animating: bool,
+
+    /// Was a redraw deferred by the power saver?
+    redraw_pending: bool,
+
+    /// When we last redrew.
+    last_redraw: Option<Instant>,
+
+    /// Redraw at this time, for things like blinking carets.
+    redraw_at: Option<Instant>,
+
+    /// Device pixels per point.
+    scale_factor: f32,
+
+    /// How full vger's glyph atlas was at the last render.
+    pub(crate) glyph_atlas_usage: f32,
+
+    /// Clear the glyph atlas at the next render.
+    pub(crate) trim_glyphs: bool,
+
+    /// Frames rendered so far.
+    frame: u64,
+
+    /// Callbacks registered with `watch_path`.
+    #[cfg(all(feature = "file-watch", not(target_arch = "wasm32")))]
+    pub(crate) file_watches: Vec<FileWatch>,
+    #[cfg(all(feature = "file-watch", not(target_arch = "wasm32")))]
+    pub(crate) next_file_watch_id: usize,
+
+    /// Processes started with `run_command`.
+    #[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+    pub(crate) processes: Vec<Process>,
+    #[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+    pub(crate) next_process_id: usize,
+
+    /// Connections opened with `subscribe_ws` and `subscribe_sse`.
+    #[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+    pub(crate) subscriptions: Vec<Subscription>,
+    #[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+    pub(crate) next_subscription_id: usize,
+
+    /// MIDI inputs, once connected, and callbacks from `on_midi`.
+    #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+    pub(crate) midi_inputs: Option<Vec<midir::MidiInputConnection<()>>>,
+    pub(crate) midi_handlers: Vec<std::rc::Rc<dyn Fn(&mut Context, &MidiMessage)>>,
+    /// MIDI messages waiting to be sent to views.
+    #[cfg_attr(
+        not(all(feature = "winit", not(target_arch = "wasm32"))),
+        allow(dead_code)
+    )]
+    pub(crate) midi_events: Vec<MidiMessage>,
+    /// Whether MIDI learn is on, and the control to map.
+    pub(crate) midi_learning: bool,
+    pub(crate) midi_learn_target: Option<ViewId>,
+
+    /// Values registered with the `param` modifier, rebuilt by each update.
+    pub(crate) params: HashMap<String, std::rc::Rc<dyn ParamBinding>>,
+    #[cfg(all(feature = "osc", not(target_arch = "wasm32")))]
+    pub(crate) osc: Option<OscBridge>,
+
+    /// Parameter automation being recorded or played.
+    pub(crate) recording: Option<Recording>,
+    pub(crate) playback: Option<Playback>,
+
+    /// Images and other assets, decoded in the background.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) assets: AssetPool,
 }
 
 impl Default for Context {
@@ -139,9 +469,31 @@ impl Context {
             previous_position: [LocalPoint::zero(); 16],
             mouse_button: None,
             key_mods: Default::default(),
+            shortcut_matching: Default::default(),
+            state_retention: Default::default(),
+            explain_updates: false,
+            relaid_out: vec![],
+            last_update_explanation: None,
             focused_id: None,
+            text_input_area: None,
+            about: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            crash_sheet: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            edited_document: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            close_sheet: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            save_before_close: false,
+            #[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+            update_sheet: None,
+            #[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+            update_handler: None,
+            open_files: vec![],
+            open_urls: vec![],
             window_title: "rui".into(),
             fullscreen: false,
+            window_background: WindowBackground::default(),
             state_map: HashMap::new(),
             dirty: false,
             enable_dirty: true,
@@ -149,12 +501,77 @@ impl Context {
             dirty_region: Region::EMPTY,
             deps: HashMap::new(),
             id_stack: vec![],
+            collecting_deps: false,
             window_size: Size2D::default(),
             root_offset: LocalOffset::zero(),
             render_dirty: false,
             access_node_classes: accesskit::NodeClassSet::default(),
             grab_cursor: false,
             prev_grab_cursor: false,
+            last_input: Instant::now(),
+            idle_handlers: vec![],
+            next_idle_id: 0,
+            timers: vec![],
+            next_timer_id: 0,
+            pasteboard: Default::default(),
+            fonts: Default::default(),
+            images: Default::default(),
+            effects: Default::default(),
+            clip_rect: None,
+            layer: None,
+            window_position: None,
+            pending_window: None,
+            window_changes: vec![],
+            workspace: Default::default(),
+            pending_workspace: Default::default(),
+            drag_out: None,
+            drag: None,
+            gestures: Default::default(),
+            transition_seen: false,
+            matched_geometry: HashMap::new(),
+            anim_debugger: None,
+            active_animations: vec![],
+            global_hotkeys: vec![],
+            next_global_hotkey_id: 0,
+            global_hotkeys_changed: false,
+            power_saver: true,
+            window_focused: true,
+            window_occluded: false,
+            last_anim: None,
+            anim_dt: 1.0 / 60.0,
+            animating: false,
+            redraw_pending: false,
+            last_redraw: None,
+            redraw_at: None,
+            scale_factor: 1.0,
+            glyph_atlas_usage: 0.0,
+            trim_glyphs: false,
+            frame: 0,
+            #[cfg(all(feature = "file-watch", not(target_arch = "wasm32")))]
+            file_watches: vec![],
+            #[cfg(all(feature = "file-watch", not(target_arch = "wasm32")))]
+            next_file_watch_id: 0,
+            #[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+            processes: vec![],
+            #[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+            next_process_id: 0,
+            #[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+            subscriptions: vec![],
+            #[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+            next_subscription_id: 0,
+            #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+            midi_inputs: None,
+            midi_handlers: vec![],
+            midi_events: vec![],
+            midi_learning: false,
+            midi_learn_target: None,
+            params: HashMap::new(),
+            #[cfg(all(feature = "osc", not(target_arch = "wasm32")))]
+            osc: None,
+            recording: None,
+            playback: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            assets: Default::default(),
         }
     }
 
@@ -174,18 +591,63 @@ impl Context {
 
         let mut path = vec![0];
 
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_assets();
+
+        self.run_idle_handlers();
+        self.run_timers();
+
         // Run any animations.
-        let mut actions = vec![];
-        view.process(&Event::Anim, &mut path, self, &mut actions);
-        assert!(path.len() == 1);
+        let now = Instant::now();
+        let interval = self.anim_interval();
+        let anim_due = match self.last_anim {
+            Some(last) => now.duration_since(last) >= interval,
+            None => true,
+        };
+        if anim_due {
+            self.anim_dt = match self.last_anim {
+                Some(last) => now
+                    .duration_since(last)
+                    .min(interval.max(POWER_SAVER_INTERVAL)),
+                None => interval,
+            }
+            .as_secs_f32();
+            self.anim_dt = self.debug_anim_dt(self.anim_dt);
+            self.last_anim = Some(now);
+            self.tick_timeline(self.anim_dt);
+            self.active_animations.clear();
+            let mut actions = vec![];
+            view.process(&Event::Anim, &mut path, self, &mut actions);
+            assert!(path.len() == 1);
+
+            // Keep ticking while animations are changing state.
+            self.animating = self.dirty || self.playback.is_some();
+        }
 
         if self.dirty {
+            let changed: Vec<ViewId> = if self.explain_updates {
+                self.relaid_out.clear();
+                self.state_map
+                    .iter()
+                    .filter(|(_, holder)| holder.dirty)
+                    .map(|(id, _)| *id)
+                    .collect()
+            } else {
+                vec![]
+            };
+
             // Clean up state and layout.
             let mut keep = vec![];
+            self.params.clear();
             view.gc(&mut path, self, &mut keep);
             assert!(path.len() == 1);
+
+            self.record_param_changes();
+
+            #[cfg(all(feature = "osc", not(target_arch = "wasm32")))]
+            self.send_osc_updates();
             let keep_set = HashSet::<ViewId>::from_iter(keep);
-            self.state_map.retain(|k, _| keep_set.contains(k));
+            self.collect_state(&keep_set);
 
             let mut new_layout = self.layout.clone();
             new_layout.retain(|k, _| keep_set.contains(&self.view_id(k)));
@@ -197,6 +659,9 @@ impl Context {
             view.access(&mut path, self, &mut nodes);
             assert_eq!(path.len(), 1);
 
+            #[cfg(not(target_arch = "wasm32"))]
+            self.record_ui(&nodes);
+
             if nodes != *access_nodes {
                 println!("access nodes:");
                 for (id, node) in &nodes {
@@ -223,6 +688,10 @@ impl Context {
             );
             assert_eq!(path.len(), 1);
 
+            if self.explain_updates {
+                self.explain_update(&changed);
+            }
+
             // Get dirty rectangles.
             view.dirty(&mut path, LocalToWorld::identity(), self);
 
@@ -256,7 +725,17 @@ impl Context {
             }
         };
 
+        if self.trim_glyphs {
+            vger.glyph_cache.clear();
+            self.trim_glyphs = false;
+        }
         vger.begin(window_size.width, window_size.height, scale);
+        self.effects.begin(window_size, scale);
+        self.scale_factor = scale;
+        self.frame += 1;
+
+        // Only updates are explained.
+        self.relaid_out.clear();
 
         let mut path = vec![0];
         // Disable dirtying the state during layout and rendering
@@ -277,7 +756,55 @@ impl Context {
         self.root_offset = ((local_window_size - sz) / 2.0).into();
 
         vger.translate(self.root_offset);
+        self.text_input_area = None;
+        self.clip_rect = None;
+        self.layer = None;
+        // Only a `window_background` drawn this frame keeps it.
+        self.window_background = WindowBackground::default();
         view.draw(&mut path, &mut DrawArgs { cx: self, vger });
+
+        if let Some(rect) = self.drag.as_ref().map(|drag| drag.preview_rect()) {
+            let colors = self.theme().colors;
+            draw_drag_preview(vger, rect, &colors);
+        }
+
+        if let Some(about) = &mut self.about {
+            vger.save();
+            vger.translate(-self.root_offset);
+            about.draw(vger, local_window_size);
+            vger.restore();
+        }
+
+        #[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+        if let Some(sheet) = &mut self.update_sheet {
+            vger.save();
+            vger.translate(-self.root_offset);
+            sheet.draw(vger, local_window_size);
+            vger.restore();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(sheet) = &mut self.crash_sheet {
+            vger.save();
+            vger.translate(-self.root_offset);
+            sheet.draw(vger, local_window_size);
+            vger.restore();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(sheet) = &mut self.close_sheet {
+            vger.save();
+            vger.translate(-self.root_offset);
+            sheet.draw(vger, local_window_size);
+            vger.restore();
+        }
+
+        if self.anim_debugger.is_some() {
+            vger.save();
+            vger.translate(-self.root_offset);
+            self.draw_anim_debugger(vger);
+            vger.restore();
+        }
         self.enable_dirty = true;
 
         if self.render_dirty {
@@ -305,7 +832,7 @@ impl Context {
                 view: &texture_view,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    load: wgpu::LoadOp::Clear(self.window_background.clear_color()),
                     store: wgpu::StoreOp::Store,
                 },
             })],
@@ -313,20 +840,116 @@ impl Context {
         };
 
         vger.encode(&desc);
+        self.glyph_atlas_usage = vger.glyph_cache.usage();
+        let draws = self.images.prepare(&render_info, scale);
+        let window_draws: Vec<ImageDraw> = draws
+            .iter()
+            .filter(|d| d.layer.is_none())
+            .copied()
+            .collect();
+        let pixels = (config.width, config.height);
+        self.images.render(
+            &render_info,
+            &window_draws,
+            &texture_view,
+            window_size,
+            scale,
+            pixels,
+        );
+        self.effects.render(
+            &render_info,
+            &self.images,
+            &draws,
+            &texture_view,
+            window_size,
+            scale,
+        );
 
         frame.present();
     }
 
     /// Process a UI event.
     pub fn process(&mut self, view: &impl View, event: &Event) {
+        if !matches!(event, Event::Anim) {
+            self.input_received();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.log_event(event);
+            if self.process_crash_sheet(event) {
+                return;
+            }
+            if self.process_close_sheet(event) {
+                return;
+            }
+        }
+
+        #[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+        if self.process_update_sheet(event) {
+            return;
+        }
+
+        if self.process_anim_debugger(event) {
+            return;
+        }
+
+        // The About panel is modal.
+        if let Some(about) = &self.about {
+            match event {
+                Event::TouchBegin { position, .. } => {
+                    if !about.frame.contains(*position) {
+                        self.hide_about();
+                    }
+                    return;
+                }
+                Event::Key {
+                    key: Key::Escape, ..
+                } => {
+                    self.hide_about();
+                    return;
+                }
+                Event::TouchMove { .. }
+                | Event::Hover { .. }
+                | Event::DropHover { .. }
+                | Event::DropFiles { .. }
+                | Event::Scroll { .. }
+                | Event::TouchEnd { .. }
+                | Event::Key { .. }
+                | Event::KeyUp { .. }
+                | Event::TextInput(_) => return,
+                _ => (),
+            }
+        }
+
+        let event = event.offset(-self.root_offset);
+        self.gestures.track(&event);
+
         let mut actions = vec![];
         let mut path = vec![0];
-        view.process(
-            &event.offset(-self.root_offset),
-            &mut path,
-            self,
-            &mut actions,
-        );
+        view.process(&event, &mut path, self, &mut actions);
+
+        // The drag preview follows its touch, and the drag ends with it.
+        let (mut moved, mut ended) = (false, false);
+        if let Some(drag) = &mut self.drag {
+            match event {
+                Event::TouchMove { id, position, .. } if *id == drag.touch => {
+                    drag.position = *position - self.root_offset;
+                    moved = true;
+                }
+                Event::TouchEnd { id, .. } if *id == drag.touch => ended = true,
+                Event::Key {
+                    key: Key::Escape, ..
+                } => ended = true,
+                _ => (),
+            }
+        }
+        if ended {
+            self.drag = None;
+        }
+        if moved || ended {
+            self.set_dirty();
+        }
 
         for action in actions {
             if !action.is::<()>() {
@@ -335,6 +958,298 @@ impl Context {
         }
     }
 
+    /// Shows an About panel over the UI until the user clicks outside it or
+    /// presses Escape. See `about_info!`.
+    pub fn show_about(&mut self, info: AboutInfo) {
+        self.about = Some(AboutPanel::new(info));
+        self.set_dirty();
+    }
+
+    pub fn hide_about(&mut self) {
+        if self.about.take().is_some() {
+            self.set_dirty();
+        }
+    }
+
+    /// Calls `f` once the user hasn't interacted with the window for `duration`.
+    ///
+    /// The callback fires once per idle period and is re-armed by the next input.
+    pub fn on_idle(&mut self, duration: Duration, f: impl Fn(&mut Context) + 'static) -> IdleId {
+        let id = IdleId(self.next_idle_id);
+        self.next_idle_id += 1;
+        self.idle_handlers.push(IdleHandler {
+            id,
+            duration,
+            fired: false,
+            func: std::rc::Rc::new(f),
+        });
+        id
+    }
+
+    /// Removes a callback registered with `on_idle`.
+    pub fn remove_idle(&mut self, id: IdleId) {
+        self.idle_handlers.retain(|h| h.id != id);
+    }
+
+    /// How long since the user last interacted with the window.
+    pub fn idle_time(&self) -> Duration {
+        Instant::now().duration_since(self.last_input)
+    }
+
+    /// The system clipboard, or an in-app one without the `clipboard`
+    /// feature. Usable from any callback.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// button("Copy", |cx| cx.clipboard().set_text("hello"));
+    /// ```
+    pub fn clipboard(&mut self) -> &mut Pasteboard {
+        &mut self.pasteboard
+    }
+
+    /// Calls `f` when `keys` are pressed, even while another app is focused.
+    ///
+    /// Requires the `global-hotkeys` feature. Without it the hotkey is
+    /// recorded but never fires.
+    pub fn register_global_hotkey(
+        &mut self,
+        keys: impl Into<KeyChord>,
+        f: impl Fn(&mut Context) + 'static,
+    ) -> GlobalHotKeyId {
+        let id = GlobalHotKeyId(self.next_global_hotkey_id);
+        self.next_global_hotkey_id += 1;
+        self.global_hotkeys.push(GlobalHotKeyHandler {
+            id,
+            keys: keys.into(),
+            func: std::rc::Rc::new(f),
+        });
+        self.global_hotkeys_changed = true;
+        id
+    }
+
+    /// Removes a hotkey registered with `register_global_hotkey`.
+    pub fn unregister_global_hotkey(&mut self, id: GlobalHotKeyId) {
+        self.global_hotkeys.retain(|h| h.id != id);
+        self.global_hotkeys_changed = true;
+    }
+
+    /// Key chords which currently have global hotkey callbacks.
+    pub(crate) fn global_hotkey_chords(&self) -> Vec<KeyChord> {
+        let mut chords: Vec<KeyChord> = vec![];
+        for handler in &self.global_hotkeys {
+            if !chords.contains(&handler.keys) {
+                chords.push(handler.keys);
+            }
+        }
+        chords
+    }
+
+    pub(crate) fn fire_global_hotkey(&mut self, keys: KeyChord) {
+        let funcs: Vec<_> = self
+            .global_hotkeys
+            .iter()
+            .filter(|h| h.keys == keys)
+            .map(|h| h.func.clone())
+            .collect();
+        for f in funcs {
+            f(self);
+        }
+    }
+
+    /// Captures the arrangement of the app, for restoring on next launch.
+    pub fn save_workspace(&self) -> WorkspaceSpec {
+        let mut spec = self.workspace.clone();
+        spec.windows = vec![WindowSpec {
+            title: self.window_title.clone(),
+            position: self.window_position,
+            size: [self.window_size.width, self.window_size.height],
+            fullscreen: self.fullscreen,
+        }];
+        spec
+    }
+
+    /// Restores an arrangement from `save_workspace`. Docks and panels
+    /// which aren't showing yet are restored when they appear.
+    pub fn restore_workspace(&mut self, spec: WorkspaceSpec) {
+        self.pending_window = spec.windows.into_iter().next();
+        self.pending_workspace.docks.extend(spec.docks);
+        self.pending_workspace.panels.extend(spec.panels);
+        self.set_dirty();
+    }
+
+    /// Applies a saved dock layout, then records the current one.
+    pub(crate) fn sync_workspace_dock(&mut self, key: &str, layout: impl Binding<DockLayout>) {
+        if let Some(saved) = self.pending_workspace.docks.remove(key) {
+            *layout.get_mut(self) = saved;
+        }
+        let current = layout.get(self).clone();
+        self.workspace.docks.insert(key.into(), current);
+    }
+
+    /// Applies saved panel state, then records the current state.
+    pub(crate) fn sync_workspace_panel<T: FromStr + Display + 'static>(
+        &mut self,
+        key: &str,
+        binding: impl Binding<T>,
+    ) {
+        if let Some(saved) = self.pending_workspace.panels.remove(key) {
+            if let Ok(value) = saved.parse() {
+                *binding.get_mut(self) = value;
+            }
+        }
+        let current = binding.get(self).to_string();
+        self.workspace.panels.insert(key.into(), current);
+    }
+
+    fn input_received(&mut self) {
+        self.last_input = Instant::now();
+        for handler in &mut self.idle_handlers {
+            handler.fired = false;
+        }
+    }
+
+    fn run_idle_handlers(&mut self) {
+        let idle_time = self.idle_time();
+        let mut due = vec![];
+        for handler in &mut self.idle_handlers {
+            if !handler.fired && idle_time >= handler.duration {
+                handler.fired = true;
+                due.push(handler.func.clone());
+            }
+        }
+        for f in due {
+            f(self);
+        }
+    }
+
+    /// Is the window unfocused or hidden, with power saving on?
+    fn saving_power(&self) -> bool {
+        self.power_saver && (!self.window_focused || self.window_occluded)
+    }
+
+    fn anim_interval(&self) -> Duration {
+        if self.saving_power() {
+            POWER_SAVER_INTERVAL
+        } else {
+            ANIM_INTERVAL
+        }
+    }
+
+    /// Called by the event loop when the window gains or loses focus.
+    pub fn set_window_focused(&mut self, focused: bool) {
+        self.window_focused = focused;
+        if focused {
+            self.input_received();
+        }
+    }
+
+    /// Called by the event loop when the window is hidden or revealed.
+    pub fn set_window_occluded(&mut self, occluded: bool) {
+        if occluded != self.window_occluded {
+            self.window_occluded = occluded;
+
+            // Let views that pause work while hidden know.
+            self.set_dirty();
+
+            // Whatever is on screen may be stale.
+            if !occluded {
+                self.redraw_pending = true;
+            }
+        }
+    }
+
+    /// Is the window hidden behind other windows or minimized?
+    ///
+    /// Use this to pause expensive work, like video decoding, while
+    /// nothing can be seen. Redrawing stops automatically.
+    pub fn window_occluded(&self) -> bool {
+        self.window_occluded
+    }
+
+    /// Should the event loop redraw now? `changed` is the result of `update`.
+    ///
+    /// Redraws are skipped while the window is hidden. When saving power, they're
+    /// also throttled while it's unfocused. Skipped redraws happen later.
+    pub fn should_redraw(&mut self, changed: bool) -> bool {
+        let now = Instant::now();
+        if let Some(t) = self.redraw_at {
+            if now >= t {
+                self.redraw_at = None;
+                self.redraw_pending = true;
+            }
+        }
+
+        self.redraw_pending |= changed;
+        if !self.redraw_pending {
+            return false;
+        }
+
+        // Nothing to see while hidden.
+        if self.window_occluded {
+            return false;
+        }
+
+        if self.power_saver && !self.window_focused {
+            if let Some(last) = self.last_redraw {
+                if now.duration_since(last) < POWER_SAVER_INTERVAL {
+                    return false;
+                }
+            }
+        }
+
+        self.redraw_pending = false;
+        self.last_redraw = Some(now);
+        true
+    }
+
+    /// Redraws the window at (or shortly after) `time`, without changing any state.
+    /// Useful for time-based drawing, like blinking.
+    pub fn request_redraw_at(&mut self, time: Instant) {
+        self.redraw_at = Some(match self.redraw_at {
+            Some(t) => t.min(time),
+            None => time,
+        });
+    }
+
+    /// Device pixels per point, as of the last render.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// When the event loop should wake up next, if there's anything to do.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let mut deadline: Option<Instant> = None;
+        let mut wake_at = |t: Instant| {
+            deadline = Some(match deadline {
+                Some(d) => d.min(t),
+                None => t,
+            })
+        };
+
+        for handler in &self.idle_handlers {
+            if !handler.fired {
+                wake_at(self.last_input + handler.duration);
+            }
+        }
+
+        if let Some(t) = self.redraw_at {
+            wake_at(t);
+        }
+
+        if let Some(t) = self.next_timer() {
+            wake_at(t);
+        }
+
+        // Animations stop entirely while hidden.
+        if (self.animating || self.redraw_pending) && !self.window_occluded {
+            if let Some(last) = self.last_anim {
+                wake_at(last + self.anim_interval());
+            }
+        }
+
+        deadline
+    }
+
     /// Get menu commands.
     pub fn commands(&mut self, view: &impl View, cmds: &mut Vec<CommandInfo>) {
         let mut path = vec![0];
@@ -353,6 +1268,17 @@ impl Context {
         }
     }
 
+    /// Narrows where images are clipped to by `rect`, in window points.
+    /// Returns what it was, to restore after drawing.
+    pub(crate) fn clip_to(&mut self, rect: WorldRect) -> Option<WorldRect> {
+        let old = self.clip_rect;
+        self.clip_rect = Some(match old {
+            Some(clip) => clip.intersection(&rect).unwrap_or_else(WorldRect::zero),
+            None => rect,
+        });
+        old
+    }
+
     pub(crate) fn get_layout(&self, path: &IdPath) -> LayoutBox {
         match self.layout.get(path) {
             Some(b) => *b,
@@ -384,6 +1310,101 @@ impl Context {
         }
     }
 
+    /// Drops state of views not in `keep`, according to `state_retention`.
+    fn collect_state(&mut self, keep: &HashSet<ViewId>) {
+        let limit = match self.state_retention {
+            StateRetention::Immediate => 0,
+            StateRetention::Updates(n) => n,
+            StateRetention::Never => u32::MAX,
+        };
+        self.state_map.retain(|id, holder| {
+            if keep.contains(id) {
+                holder.orphaned = 0;
+                true
+            } else {
+                holder.orphaned = holder.orphaned.saturating_add(1);
+                holder.orphaned <= limit
+            }
+        });
+    }
+
+    /// Drops the state of all views inside the `state` view which made
+    /// `handle`, keeping `handle`'s own value. Use this to free the state
+    /// of a large list that's being replaced, when `state_retention` keeps
+    /// orphaned state around.
+    pub fn purge_state<S>(&mut self, handle: StateHandle<S>) {
+        let prefix = match self.view_ids.iter().find(|(_, id)| **id == handle.id) {
+            Some((path, _)) => path.clone(),
+            None => return,
+        };
+        let inside: HashSet<ViewId> = self
+            .view_ids
+            .iter()
+            .filter(|(path, _)| path.len() > prefix.len() && path.starts_with(&prefix))
+            .map(|(_, id)| *id)
+            .collect();
+        self.state_map.retain(|id, _| !inside.contains(id));
+        self.set_dirty();
+    }
+
+    /// The number of frames rendered so far.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    fn state_info(&self, id: ViewId) -> Option<StateInfo> {
+        self.state_map.get(&id).map(|holder| StateInfo {
+            id,
+            name: holder.name,
+            type_name: holder.type_name,
+            modified_frame: holder.modified,
+        })
+    }
+
+    /// Lists all state, most recently changed first, to find which state
+    /// is causing relayouts. Name state with `named_state` to tell it apart.
+    pub fn dump_state(&self) -> Vec<StateInfo> {
+        let mut infos: Vec<StateInfo> = self
+            .state_map
+            .keys()
+            .filter_map(|id| self.state_info(*id))
+            .collect();
+        infos.sort_by_key(|info| (std::cmp::Reverse(info.modified_frame), info.id.id));
+        infos
+    }
+
+    fn explain_update(&mut self, changed: &[ViewId]) {
+        let explanation = UpdateExplanation {
+            frame: self.frame,
+            changed: changed
+                .iter()
+                .filter_map(|id| self.state_info(*id))
+                .collect(),
+            relaid_out: self
+                .relaid_out
+                .iter()
+                .filter_map(|id| self.state_info(*id))
+                .collect(),
+        };
+
+        println!("update in frame {}:", explanation.frame);
+        if explanation.changed.is_empty() {
+            println!("  no state changed");
+        }
+        for info in &explanation.changed {
+            println!("  changed: {}", info);
+        }
+        for info in &explanation.relaid_out {
+            println!("  laid out: {}", info);
+        }
+        self.last_update_explanation = Some(explanation);
+    }
+
+    /// Why the UI was last updated, if `explain_updates` is on.
+    pub fn last_update_explanation(&self) -> Option<&UpdateExplanation> {
+        self.last_update_explanation.as_ref()
+    }
+
     pub(crate) fn set_dirty(&mut self) {
         if self.enable_dirty {
             self.dirty = true
@@ -398,13 +1419,8 @@ impl Context {
     }
 
     pub(crate) fn set_state<S: 'static>(&mut self, id: ViewId, value: S) {
-        self.state_map.insert(
-            id,
-            StateHolder {
-                state: Box::new(value),
-                dirty: false,
-            },
-        );
+        self.state_map
+            .insert(id, StateHolder::new(value, self.frame));
     }
 
     pub(crate) fn is_dirty(&self, id: ViewId) -> bool {
@@ -412,10 +1428,10 @@ impl Context {
     }
 
     pub(crate) fn init_state<S: 'static, D: Fn() -> S + 'static>(&mut self, id: ViewId, func: &D) {
-        self.state_map.entry(id).or_insert_with(|| StateHolder {
-            state: Box::new((func)()),
-            dirty: false,
-        });
+        let frame = self.frame;
+        self.state_map
+            .entry(id)
+            .or_insert_with(|| StateHolder::new((func)(), frame));
     }
 
     pub(crate) fn init_env<S: Clone + 'static, D: Fn() -> S + 'static>(&mut self, func: &D) -> S {
@@ -452,6 +1468,7 @@ impl Context {
 
         let holder = self.state_map.get_mut(&id.id).unwrap();
         holder.dirty = true;
+        holder.modified = self.frame;
         holder.state.downcast_mut::<S>().unwrap()
     }
 }
@@ -475,3 +1492,99 @@ where
         self.get_mut(index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_idle() {
+        let mut cx = Context::new();
+        let s = std::rc::Rc::new(std::cell::Cell::new(0));
+        let s2 = s.clone();
+        cx.on_idle(Duration::ZERO, move |_| s2.set(s2.get() + 1));
+
+        cx.run_idle_handlers();
+        assert_eq!(s.get(), 1);
+
+        // Only fires once per idle period.
+        cx.run_idle_handlers();
+        assert_eq!(s.get(), 1);
+
+        cx.input_received();
+        cx.run_idle_handlers();
+        assert_eq!(s.get(), 2);
+    }
+
+    #[test]
+    fn test_power_saver() {
+        let mut cx = Context::new();
+        assert!(cx.should_redraw(true));
+
+        cx.set_window_occluded(true);
+        assert!(!cx.should_redraw(true));
+
+        cx.set_window_occluded(false);
+        assert!(cx.should_redraw(false));
+    }
+
+    #[test]
+    fn test_occluded() {
+        let mut cx = Context::new();
+        cx.power_saver = false;
+        cx.set_window_occluded(true);
+        assert!(cx.window_occluded());
+        assert!(!cx.should_redraw(true));
+        assert!(cx.next_deadline().is_none());
+
+        // Revealing the window always redraws.
+        cx.set_window_occluded(false);
+        assert!(cx.should_redraw(false));
+    }
+
+    #[test]
+    fn test_state_retention() {
+        let mut cx = Context::new();
+        cx.state_retention = StateRetention::Updates(1);
+        let list = cx.view_id(&vec![0]);
+        let row = cx.view_id(&vec![0, 1]);
+        cx.init_state(list, &|| 0);
+        cx.init_state(row, &|| 0);
+
+        let keep = HashSet::from_iter([list]);
+        cx.collect_state(&keep);
+        assert!(cx.state_map.contains_key(&row));
+        cx.collect_state(&keep);
+        assert!(!cx.state_map.contains_key(&row));
+
+        cx.state_retention = StateRetention::Never;
+        cx.init_state(row, &|| 0);
+        cx.collect_state(&keep);
+        assert!(cx.state_map.contains_key(&row));
+
+        cx.purge_state(StateHandle::<i32>::new(list));
+        assert!(cx.state_map.contains_key(&list));
+        assert!(!cx.state_map.contains_key(&row));
+    }
+
+    #[test]
+    fn test_dump_state() {
+        let mut cx = Context::new();
+        let a = cx.view_id(&vec![0]);
+        let b = cx.view_id(&vec![0, 1]);
+        cx.init_state(a, &|| 1.0f32);
+        cx.init_state(b, &String::new);
+        cx.state_map.get_mut(&b).unwrap().name = Some("title");
+
+        cx.frame = 3;
+        cx[StateHandle::<f32>::new(a)] = 2.0;
+
+        let dump = cx.dump_state();
+        assert_eq!(dump[0].id, a);
+        assert_eq!(dump[0].type_name, "f32");
+        assert_eq!(dump[0].modified_frame, 3);
+        assert_eq!(dump[1].name, Some("title"));
+        assert_eq!(dump[1].modified_frame, 0);
+    }
+}
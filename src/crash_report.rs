@@ -0,0 +1,382 @@
+use crate::*;
+use lazy_static::lazy_static;
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// How many recent events go in a report.
+const EVENT_LOG_LEN: usize = 64;
+
+/// A report written when the app panicked, found at the next launch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrashReport {
+    /// The panic message and location, a backtrace, the views on screen,
+    /// recent events and state.
+    pub text: String,
+}
+
+type SendReport = Box<dyn Fn(&CrashReport) + Send>;
+
+/// What the panic hook needs to know about the UI. Kept up to date by
+/// `Context` while a reporter is installed.
+struct Recorder {
+    path: PathBuf,
+    values: HashMap<TypeId, fn(&dyn Any) -> String>,
+    views: String,
+    state: Vec<String>,
+    events: VecDeque<String>,
+    pending: Option<(CrashReport, SendReport)>,
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref RECORDER: Mutex<Option<Recorder>> = Mutex::new(None);
+}
+
+fn debug_value<S: Debug + 'static>(value: &dyn Any) -> String {
+    format!("{:?}", value.downcast_ref::<S>().unwrap())
+}
+
+/// Where apps usually keep their data.
+pub(crate) fn data_dir(app_id: &str) -> PathBuf {
+    let home = || PathBuf::from(std::env::var_os("HOME").unwrap_or_default());
+    let base = if cfg!(target_os = "macos") {
+        home().join("Library/Application Support")
+    } else if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("APPDATA").unwrap_or_default())
+    } else {
+        match std::env::var_os("XDG_DATA_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => home().join(".local/share"),
+        }
+    };
+    base.join(app_id)
+}
+
+/// Installs a panic hook which writes a crash report, so it can be sent
+/// the next time the app runs.
+///
+/// Reports list the keys of view state, but not its values, which may
+/// hold the user's data. Use `include_state` to opt in for types that
+/// are safe to send.
+///
+/// ```no_run
+/// # use rui::*;
+/// CrashReporter::new("com.example.editor")
+///     .include_state::<bool>()
+///     .ask_to_send(|report| println!("{}", report.text))
+///     .install();
+/// ```
+pub struct CrashReporter {
+    app_id: String,
+    values: HashMap<TypeId, fn(&dyn Any) -> String>,
+    send: Option<SendReport>,
+}
+
+impl CrashReporter {
+    /// `app_id` names the app, like "com.example.editor". Reports are kept
+    /// in a folder of that name in the user's application data.
+    pub fn new(app_id: &str) -> Self {
+        Self {
+            app_id: app_id.into(),
+            values: HashMap::new(),
+            send: None,
+        }
+    }
+
+    /// Includes the values of view state of type `S` in reports.
+    pub fn include_state<S: Debug + 'static>(mut self) -> Self {
+        self.values.insert(
+            TypeId::of::<S>(),
+            debug_value::<S> as fn(&dyn Any) -> String,
+        );
+        self
+    }
+
+    /// If the app crashed last time, asks the user whether to send the
+    /// report, and calls `send` if they agree.
+    pub fn ask_to_send(mut self, send: impl Fn(&CrashReport) + Send + 'static) -> Self {
+        self.send = Some(Box::new(send));
+        self
+    }
+
+    /// Installs the panic hook. Returns the report from the last crash, if
+    /// any, which is then removed. Call this first thing in `main`.
+    pub fn install(self) -> Option<CrashReport> {
+        let dir = data_dir(&self.app_id);
+        if let Err(err) = std::fs::create_dir_all(&dir) {
+            println!("unable to create crash report folder: {}", err);
+        }
+        let path = dir.join("crash-report.txt");
+
+        let last = std::fs::read_to_string(&path)
+            .ok()
+            .map(|text| CrashReport { text });
+        if last.is_some() {
+            let _ = std::fs::remove_file(&path);
+        }
+
+        *RECORDER.lock().unwrap() = Some(Recorder {
+            path,
+            values: self.values,
+            views: String::new(),
+            state: vec![],
+            events: VecDeque::new(),
+            pending: last.clone().zip(self.send),
+        });
+        INSTALLED.store(true, Ordering::Relaxed);
+
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            write_report(&self.app_id, info);
+            previous(info);
+        }));
+
+        last
+    }
+}
+
+/// `info` is the panic's message and location.
+fn write_report(app_id: &str, info: &dyn std::fmt::Display) {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let thread = std::thread::current();
+    let mut text = format!(
+        "app: {}\ntime: {}\nthread '{}' {}\n\nbacktrace:\n{}\n",
+        app_id,
+        time,
+        thread.name().unwrap_or("<unnamed>"),
+        info,
+        std::backtrace::Backtrace::force_capture()
+    );
+
+    // The panic may have happened while we held the lock.
+    let recorder = match RECORDER.try_lock() {
+        Ok(recorder) => recorder,
+        Err(_) => return println!("unable to write crash report"),
+    };
+    let recorder = match &*recorder {
+        Some(recorder) => recorder,
+        None => return,
+    };
+    text += &format!("\nviews:\n{}", recorder.views);
+    text += "\nevents:\n";
+    for event in &recorder.events {
+        text += &format!("  {}\n", event);
+    }
+    text += "\nstate:\n";
+    for state in &recorder.state {
+        text += &format!("  {}\n", state);
+    }
+
+    if let Err(err) = std::fs::write(&recorder.path, text) {
+        println!("unable to write crash report: {}", err);
+    }
+}
+
+/// Indented roles and names of the accessibility tree, which stands in for
+/// the view hierarchy.
+fn view_summary(nodes: &[(accesskit::NodeId, accesskit::Node)]) -> String {
+    let by_id: HashMap<_, _> = nodes.iter().map(|(id, node)| (*id, node)).collect();
+    let is_child: std::collections::HashSet<_> = nodes
+        .iter()
+        .flat_map(|(_, node)| node.children().iter().copied())
+        .collect();
+
+    fn add(
+        summary: &mut String,
+        id: accesskit::NodeId,
+        by_id: &HashMap<accesskit::NodeId, &accesskit::Node>,
+        depth: usize,
+    ) {
+        if let Some(node) = by_id.get(&id) {
+            *summary += &"  ".repeat(depth + 1);
+            *summary += &format!("{:?}", node.role());
+            if let Some(name) = node.name() {
+                *summary += &format!(" {:?}", name);
+            }
+            summary.push('\n');
+            for child in node.children() {
+                add(summary, *child, by_id, depth + 1);
+            }
+        }
+    }
+
+    let mut summary = String::new();
+    for (id, _) in nodes.iter().filter(|(id, _)| !is_child.contains(id)) {
+        add(&mut summary, *id, &by_id, 0);
+    }
+    summary
+}
+
+impl Context {
+    /// Remembers an event for crash reports.
+    pub(crate) fn log_event(&self, event: &Event) {
+        if !INSTALLED.load(Ordering::Relaxed) || matches!(event, Event::Anim) {
+            return;
+        }
+        if let Some(recorder) = &mut *RECORDER.lock().unwrap() {
+            if recorder.events.len() == EVENT_LOG_LEN {
+                recorder.events.pop_front();
+            }
+            recorder.events.push_back(format!("{:?}", event));
+        }
+    }
+
+    /// Records the views and state for crash reports, after they change.
+    pub(crate) fn record_ui(&mut self, nodes: &[(accesskit::NodeId, accesskit::Node)]) {
+        if !INSTALLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut lock = RECORDER.lock().unwrap();
+        let recorder = match &mut *lock {
+            Some(recorder) => recorder,
+            None => return,
+        };
+        recorder.views = view_summary(nodes);
+        recorder.state = self
+            .state_map
+            .iter()
+            .map(|(id, holder)| {
+                let key = match holder.name {
+                    Some(name) => format!("{} ({}, {})", name, holder.type_name, id.id),
+                    None => format!("{} ({})", holder.type_name, id.id),
+                };
+                let value = holder.state.as_ref();
+                match recorder.values.get(&value.type_id()) {
+                    Some(format) => format!("{}: {}", key, format(value)),
+                    None => key,
+                }
+            })
+            .collect();
+        recorder.state.sort();
+
+        if self.crash_sheet.is_none() {
+            if let Some((report, send)) = recorder.pending.take() {
+                self.crash_sheet = Some(CrashSheet {
+                    report,
+                    send,
+                    buttons: vec![],
+                });
+            }
+        }
+    }
+
+    /// Handles events while the crash report sheet is showing. Returns
+    /// true if the event was for the sheet.
+    pub(crate) fn process_crash_sheet(&mut self, event: &Event) -> bool {
+        let sheet = match &self.crash_sheet {
+            Some(sheet) => sheet,
+            None => return false,
+        };
+        match event {
+            Event::TouchBegin { position, .. } => {
+                match sheet
+                    .buttons
+                    .iter()
+                    .position(|rect| rect.contains(*position))
+                {
+                    Some(0) => {
+                        (sheet.send)(&sheet.report);
+                        self.crash_sheet = None;
+                    }
+                    Some(_) => self.crash_sheet = None,
+                    None => (),
+                }
+                self.set_dirty();
+                true
+            }
+            Event::TouchMove { .. }
+            | Event::Hover { .. }
+            | Event::DropHover { .. }
+            | Event::DropFiles { .. }
+            | Event::Scroll { .. }
+            | Event::TouchEnd { .. }
+            | Event::Key { .. }
+            | Event::KeyUp { .. }
+            | Event::TextInput(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Asks whether to send the last crash report.
+pub(crate) struct CrashSheet {
+    report: CrashReport,
+    send: SendReport,
+    /// "Send" then "Don't Send".
+    buttons: Vec<LocalRect>,
+}
+
+impl CrashSheet {
+    pub(crate) fn draw(&mut self, vger: &mut Vger, size: LocalSize) {
+        let width = PANEL_WIDTH - 2.0 * PANEL_PADDING;
+        let items = [
+            PanelItem {
+                text: "The app quit unexpectedly".into(),
+                size: 18,
+                color: TEXT_COLOR,
+                centered: false,
+                gap: 0.0,
+            },
+            PanelItem {
+                text: "Send a crash report to help fix the problem? It lists what was on screen and your recent input, but not your documents.".into(),
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: false,
+                gap: 6.0,
+            },
+        ];
+
+        let panel_size = LocalSize::new(
+            PANEL_WIDTH,
+            items_height(vger, &items, width) + BUTTON_HEIGHT + 3.0 * PANEL_PADDING,
+        );
+        let frame = LocalRect::new(
+            ((size - panel_size) / 2.0).to_vector().to_point(),
+            panel_size,
+        );
+        draw_panel(vger, size, frame);
+
+        let mut top = frame.max_y() - PANEL_PADDING;
+        for item in &items {
+            top = draw_item(vger, item, frame, top, width);
+        }
+        self.buttons = draw_buttons(vger, frame, &["Send", "Don't Send"]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_view_summary() {
+        let label_id = ViewId { id: 2 }.access_id();
+        let mut classes = accesskit::NodeClassSet::default();
+        let mut label = accesskit::NodeBuilder::new(accesskit::Role::LabelText);
+        label.set_name("hello");
+        let mut window = accesskit::NodeBuilder::new(accesskit::Role::Window);
+        window.set_children(vec![label_id]);
+
+        let nodes = [
+            (label_id, label.build(&mut classes)),
+            (ViewId { id: 1 }.access_id(), window.build(&mut classes)),
+        ];
+        assert_eq!(view_summary(&nodes), "  Window\n    LabelText \"hello\"\n");
+    }
+
+    #[test]
+    fn test_state_values() {
+        let format = debug_value::<(i32, bool)>;
+        assert_eq!(format(&(3, true)), "(3, true)");
+    }
+}
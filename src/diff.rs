@@ -0,0 +1,81 @@
+use std::ops::Range;
+
+/// A run of items which are the same in two sequences, or which differ.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DiffBlock {
+    pub old: Range<usize>,
+    pub new: Range<usize>,
+    /// Were items removed or added, or are these the same on both sides?
+    pub changed: bool,
+}
+
+/// Splits `old` and `new` into runs which match and runs which differ,
+/// through their longest common subsequence.
+pub(crate) fn diff_blocks<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffBlock> {
+    let (n, m) = (old.len(), new.len());
+
+    // How many items match from old[i..] and new[j..].
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut blocks: Vec<DiffBlock> = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        let same = i < n && j < m && old[i] == new[j];
+        match blocks.last_mut() {
+            Some(block) if block.changed != same => {}
+            _ => blocks.push(DiffBlock {
+                old: i..i,
+                new: j..j,
+                changed: !same,
+            }),
+        }
+        let block = blocks.last_mut().unwrap();
+        if same {
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            i += 1;
+        } else {
+            j += 1;
+        }
+        block.old.end = i;
+        block.new.end = j;
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_diff_blocks() {
+        let old: Vec<char> = "abcd".chars().collect();
+        let new: Vec<char> = "aXcdY".chars().collect();
+        let blocks = diff_blocks(&old, &new);
+        let ranges: Vec<_> = blocks
+            .iter()
+            .map(|b| (b.old.clone(), b.new.clone(), b.changed))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (0..1, 0..1, false),
+                (1..2, 1..2, true),
+                (2..4, 2..4, false),
+                (4..4, 4..5, true),
+            ]
+        );
+        assert!(diff_blocks::<char>(&[], &[]).is_empty());
+    }
+}
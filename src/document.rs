@@ -0,0 +1,439 @@
+use crate::*;
+use std::any::Any;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How often unsaved changes are written to the recovery file.
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+type LoadFn<T> = Rc<dyn Fn(&Path) -> io::Result<T>>;
+type SaveFn<T> = Rc<dyn Fn(&T, &Path) -> io::Result<()>>;
+
+/// A value the user edits and saves to a file, which knows whether it has
+/// unsaved changes. Keep one in `state` and show it with the `document`
+/// modifier, which puts its name in the window title, autosaves it and
+/// asks to save it before the window closes.
+///
+/// ```no_run
+/// # use rui::*;
+/// state(
+///     || {
+///         Document::new(
+///             String::new(),
+///             |path| std::fs::read_to_string(path),
+///             |text, path| std::fs::write(path, text),
+///         )
+///         .autosave("com.example.notes")
+///     },
+///     |doc, _| text_editor(bind(doc, DocumentValue)).document(doc),
+/// );
+/// ```
+pub struct Document<T> {
+    /// What the user is editing.
+    pub value: T,
+    /// As of the last load or save.
+    saved: T,
+    path: Option<PathBuf>,
+    /// Where unsaved changes are autosaved, if anywhere.
+    recovery: Option<PathBuf>,
+    last_autosave: Instant,
+    load: LoadFn<T>,
+    save: SaveFn<T>,
+}
+
+impl<T: Clone + PartialEq + 'static> Document<T> {
+    /// An untitled document, read and written by `load` and `save`.
+    pub fn new(
+        value: T,
+        load: impl Fn(&Path) -> io::Result<T> + 'static,
+        save: impl Fn(&T, &Path) -> io::Result<()> + 'static,
+    ) -> Self {
+        Self {
+            saved: value.clone(),
+            value,
+            path: None,
+            recovery: None,
+            last_autosave: Instant::now(),
+            load: Rc::new(load),
+            save: Rc::new(save),
+        }
+    }
+
+    /// Writes unsaved changes every `AUTOSAVE_INTERVAL` to a recovery file
+    /// in the app's data directory, so they survive a crash. See `recover`.
+    pub fn autosave(self, app_id: &str) -> Self {
+        Self {
+            recovery: Some(data_dir(app_id).join("Recovery")),
+            ..self
+        }
+    }
+
+    /// Does it differ from the file?
+    pub fn is_dirty(&self) -> bool {
+        self.value != self.saved
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The file name, or "Untitled".
+    pub fn name(&self) -> String {
+        self.path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map_or("Untitled".into(), |name| name.to_string_lossy().into())
+    }
+
+    /// Loads the document from `path`.
+    pub fn open(&mut self, path: impl Into<PathBuf>) -> io::Result<()> {
+        let path = path.into();
+        self.value = (self.load)(&path)?;
+        self.saved = self.value.clone();
+        self.path = Some(path);
+        Ok(())
+    }
+
+    /// Saves to the file it came from. Untitled documents need `save_as`.
+    pub fn save(&mut self) -> io::Result<()> {
+        let path = self.path.clone().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "untitled documents need save_as")
+        })?;
+        self.save_as(path)
+    }
+
+    pub fn save_as(&mut self, path: impl Into<PathBuf>) -> io::Result<()> {
+        let path = path.into();
+        (self.save)(&self.value, &path)?;
+        self.saved = self.value.clone();
+        self.path = Some(path);
+        if let Some(recovery) = self.recovery_path() {
+            let _ = std::fs::remove_file(recovery);
+        }
+        Ok(())
+    }
+
+    /// Loads changes autosaved before a crash, if there are any. The
+    /// document stays dirty until it's saved.
+    pub fn recover(&mut self) -> io::Result<bool> {
+        match self.recovery_path() {
+            Some(recovery) if recovery.exists() => {
+                self.value = (self.load)(&recovery)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn recovery_path(&self) -> Option<PathBuf> {
+        let dir = self.recovery.as_ref()?;
+        Some(dir.join(self.name()))
+    }
+
+    /// When unsaved changes should next be written to the recovery file.
+    fn autosave_due(&self) -> Option<Instant> {
+        if self.recovery.is_some() && self.is_dirty() {
+            Some(self.last_autosave + AUTOSAVE_INTERVAL)
+        } else {
+            None
+        }
+    }
+
+    fn write_recovery(&mut self) {
+        self.last_autosave = Instant::now();
+        if let Some(recovery) = self.recovery_path() {
+            let written = std::fs::create_dir_all(recovery.parent().unwrap())
+                .and_then(|_| (self.save)(&self.value, &recovery));
+            if let Err(err) = written {
+                println!("error autosaving {:?}: {}", recovery, err);
+            }
+        }
+    }
+}
+
+/// Lens to a document's value, for `bind`.
+#[derive(Clone, Copy)]
+pub struct DocumentValue;
+
+impl<T: 'static> Lens<Document<T>, T> for DocumentValue {
+    fn focus<'a>(&self, data: &'a Document<T>) -> &'a T {
+        &data.value
+    }
+    fn focus_mut<'a>(&self, data: &'a mut Document<T>) -> &'a mut T {
+        &mut data.value
+    }
+}
+
+/// Struct for the `document` modifier.
+pub struct DocumentView<V, B, T> {
+    child: V,
+    document: B,
+    phantom: std::marker::PhantomData<T>,
+}
+
+impl<V, B, T> DocumentView<V, B, T>
+where
+    V: View,
+    B: Binding<Document<T>>,
+    T: Clone + PartialEq + 'static,
+{
+    pub fn new(child: V, document: B) -> Self {
+        Self {
+            child,
+            document,
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<V, B, T> View for DocumentView<V, B, T>
+where
+    V: View,
+    B: Binding<Document<T>>,
+    T: Clone + PartialEq + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            if let Some(due) = self.document.get(cx).autosave_due() {
+                if Instant::now() >= due {
+                    self.document.get_mut(cx).write_recovery();
+                } else {
+                    cx.request_redraw_at(due);
+                }
+            }
+        }
+
+        // Chosen from the sheet shown when closing with unsaved changes.
+        if cx.save_before_close {
+            cx.save_before_close = false;
+            match self.document.get_mut(cx).save() {
+                Ok(()) => cx.close_window(),
+                Err(err) => println!("error saving before closing: {}", err),
+            }
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        let doc = self.document.get(args.cx);
+        let name = doc.name();
+        let edited = doc.is_dirty();
+        // macOS shows unsaved changes in the close button instead.
+        let title = if edited && !cfg!(target_os = "macos") {
+            format!("\u{2022} {}", name)
+        } else {
+            name.clone()
+        };
+        let cx = &mut args.cx;
+        if cx.window_title != title {
+            cx.window_title = title;
+        }
+        cx.edited_document = if edited { Some(name) } else { None };
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.child.hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B, T> private::Sealed for DocumentView<V, B, T> {}
+
+/// Asks whether to save a document before its window closes.
+pub(crate) struct CloseSheet {
+    name: String,
+    /// "Save", "Don't Save" then "Cancel".
+    buttons: Vec<LocalRect>,
+}
+
+impl CloseSheet {
+    pub(crate) fn draw(&mut self, vger: &mut Vger, size: LocalSize) {
+        let width = PANEL_WIDTH - 2.0 * PANEL_PADDING;
+        let items = [
+            PanelItem {
+                text: format!("Save changes to \"{}\" before closing?", self.name),
+                size: 18,
+                color: TEXT_COLOR,
+                centered: false,
+                gap: 0.0,
+            },
+            PanelItem {
+                text: "Your changes will be lost if you don't save them.".into(),
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: false,
+                gap: 6.0,
+            },
+        ];
+
+        let panel_size = LocalSize::new(
+            PANEL_WIDTH,
+            items_height(vger, &items, width) + BUTTON_HEIGHT + 3.0 * PANEL_PADDING,
+        );
+        let frame = LocalRect::new(
+            ((size - panel_size) / 2.0).to_vector().to_point(),
+            panel_size,
+        );
+        draw_panel(vger, size, frame);
+
+        let mut top = frame.max_y() - PANEL_PADDING;
+        for item in &items {
+            top = draw_item(vger, item, frame, top, width);
+        }
+        self.buttons = draw_buttons(vger, frame, &["Save", "Don't Save", "Cancel"]);
+    }
+}
+
+impl Context {
+    /// Called when the user tries to close the window. Returns true if it
+    /// can close now, or asks about unsaved changes first.
+    pub(crate) fn close_requested(&mut self) -> bool {
+        match &self.edited_document {
+            Some(name) => {
+                self.close_sheet = Some(CloseSheet {
+                    name: name.clone(),
+                    buttons: vec![],
+                });
+                self.set_dirty();
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Handles events while asking about unsaved changes. Returns true if
+    /// the sheet took the event.
+    pub(crate) fn process_close_sheet(&mut self, event: &Event) -> bool {
+        let sheet = match &self.close_sheet {
+            Some(sheet) => sheet,
+            None => return false,
+        };
+        match event {
+            Event::TouchBegin { position, .. } => {
+                match sheet
+                    .buttons
+                    .iter()
+                    .position(|rect| rect.contains(*position))
+                {
+                    Some(0) => {
+                        self.close_sheet = None;
+                        self.save_before_close = true;
+                    }
+                    Some(1) => {
+                        self.close_sheet = None;
+                        self.close_window();
+                    }
+                    Some(_) => self.close_sheet = None,
+                    None => (),
+                }
+                self.set_dirty();
+                true
+            }
+            Event::Key {
+                key: Key::Escape, ..
+            } => {
+                self.close_sheet = None;
+                self.set_dirty();
+                true
+            }
+            Event::TouchMove { .. }
+            | Event::Hover { .. }
+            | Event::DropHover { .. }
+            | Event::DropFiles { .. }
+            | Event::Scroll { .. }
+            | Event::TouchEnd { .. }
+            | Event::Key { .. }
+            | Event::KeyUp { .. }
+            | Event::TextInput(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_document() {
+        let dir = std::env::temp_dir().join(format!("rui-document-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("notes.txt");
+        std::fs::write(&file, "hello").unwrap();
+
+        let mut doc = Document::new(
+            String::new(),
+            |path| std::fs::read_to_string(path),
+            |text, path| std::fs::write(path, text),
+        );
+        assert_eq!(doc.name(), "Untitled");
+        assert!(doc.save().is_err());
+
+        doc.open(&file).unwrap();
+        assert_eq!(doc.value, "hello");
+        assert!(!doc.is_dirty());
+
+        doc.value.push_str(" world");
+        assert!(doc.is_dirty());
+        doc.save().unwrap();
+        assert!(!doc.is_dirty());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "hello world");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,108 @@
+/// How `text_editor` edits a kind of text, such as a programming
+/// language: which brackets and quotes close themselves and which
+/// brackets are matched at the caret, and how new lines are indented.
+/// Set one with `TextEditorMods::language`.
+///
+/// The default methods suit C-like languages. Override them for others.
+pub trait EditorLanguage {
+    /// Opening and closing brackets. Typing an opening one inserts the
+    /// closing one too, or wraps the selection in the pair.
+    fn brackets(&self) -> &[(char, char)] {
+        &[('(', ')'), ('[', ']'), ('{', '}')]
+    }
+
+    /// Quotes which close themselves, except straight after a word, so
+    /// apostrophes work.
+    fn quotes(&self) -> &[char] {
+        &['"', '\'']
+    }
+
+    /// One level of indentation.
+    fn indent_unit(&self) -> &str {
+        "    "
+    }
+
+    /// The indentation for a line started after `line`, which is the text
+    /// before the caret on its line: the same as `line`, and a level
+    /// deeper after an opening bracket.
+    fn indent_after(&self, line: &str) -> String {
+        let mut indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        let last = line.trim_end().chars().last();
+        if self.brackets().iter().any(|(open, _)| Some(*open) == last) {
+            indent.push_str(self.indent_unit());
+        }
+        indent
+    }
+}
+
+/// `EditorLanguage` for C-like languages, using the defaults.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CodeLanguage;
+
+impl EditorLanguage for CodeLanguage {}
+
+/// The closing bracket for `open`, if it opens a pair.
+pub(crate) fn closing_bracket(language: &dyn EditorLanguage, open: char) -> Option<char> {
+    language
+        .brackets()
+        .iter()
+        .find(|(o, _)| *o == open)
+        .map(|(_, close)| *close)
+}
+
+/// Where the bracket matching the one at `i` is, counting nested pairs.
+pub(crate) fn matching_bracket(
+    language: &dyn EditorLanguage,
+    chars: &[char],
+    i: usize,
+) -> Option<usize> {
+    let c = *chars.get(i)?;
+    for &(open, close) in language.brackets() {
+        let mut depth = 0;
+        if c == open {
+            for (j, &d) in chars.iter().enumerate().skip(i) {
+                if d == open {
+                    depth += 1;
+                } else if d == close {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(j);
+                    }
+                }
+            }
+            return None;
+        }
+        if c == close {
+            for (j, &d) in chars[..=i].iter().enumerate().rev() {
+                if d == close {
+                    depth += 1;
+                } else if d == open {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(j);
+                    }
+                }
+            }
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_matching_bracket() {
+        let chars: Vec<char> = "f(a[0], (b))".chars().collect();
+        assert_eq!(matching_bracket(&CodeLanguage, &chars, 1), Some(11));
+        assert_eq!(matching_bracket(&CodeLanguage, &chars, 11), Some(1));
+        assert_eq!(matching_bracket(&CodeLanguage, &chars, 3), Some(5));
+        assert_eq!(matching_bracket(&CodeLanguage, &chars, 0), None);
+
+        assert_eq!(CodeLanguage.indent_after("    if x {"), "        ");
+        assert_eq!(CodeLanguage.indent_after("  x = 1;"), "  ");
+    }
+}
@@ -0,0 +1,717 @@
+use crate::*;
+use euclid::Size2D;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+/// How many numbers a `shader` effect's uniforms can hold.
+pub const MAX_EFFECT_UNIFORMS: usize = 16;
+
+/// Bindings, the vertex shader and `sample_source`, put before every effect's
+/// WGSL, which defines `effect`.
+const PRELUDE: &str = r#"
+struct Uniforms {
+    // Where the layer goes in what it's drawn into, from -1 to 1.
+    rect: vec4<f32>,
+    // Size of a pixel, from 0 to 1 across the layer.
+    texel: vec2<f32>,
+    // Pixels per point.
+    scale: f32,
+    values: array<vec4<f32>, 4>,
+};
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var source_texture: texture_2d<f32>;
+@group(0) @binding(2) var source_sampler: sampler;
+@group(0) @binding(3) var mask_texture: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+    );
+    let c = corners[i];
+    var out: VertexOutput;
+    out.position = vec4<f32>(mix(uniforms.rect.xy, uniforms.rect.zw, c), 0.0, 1.0);
+    out.uv = vec2<f32>(c.x, 1.0 - c.y);
+    return out;
+}
+
+// What the view drew at `uv`, from the top left, held at the edges. Colors
+// are premultiplied by alpha.
+fn sample_source(uv: vec2<f32>) -> vec4<f32> {
+    let p = clamp(uv, uniforms.texel * 0.5, vec2<f32>(1.0) - uniforms.texel * 0.5);
+    return textureSampleLevel(source_texture, source_sampler, p, 0.0);
+}
+"#;
+
+const FRAGMENT: &str = r#"
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let mask = textureSampleLevel(mask_texture, source_sampler, in.uv, 0.0).a;
+    return effect(in.uv) * mask;
+}
+"#;
+
+/// Leaves the layer as it is.
+const IDENTITY: &str = r#"
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    return sample_source(uv);
+}
+"#;
+
+/// A gaussian blur, `values[0].x` points across.
+const BLUR: &str = r#"
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let stride = uniforms.values[0].x * uniforms.scale / 4.0 * uniforms.texel;
+    var sum = vec4<f32>(0.0);
+    var total = 0.0;
+    for (var x = -4; x <= 4; x = x + 1) {
+        for (var y = -4; y <= 4; y = y + 1) {
+            let d = vec2<f32>(f32(x), f32(y));
+            let w = exp(-dot(d, d) / 8.0);
+            sum = sum + sample_source(uv + d * stride) * w;
+            total = total + w;
+        }
+    }
+    return sum / total;
+}
+"#;
+
+const BRIGHTNESS: &str = r#"
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let c = sample_source(uv);
+    return vec4<f32>(c.rgb * uniforms.values[0].x, c.a);
+}
+"#;
+
+const SATURATION: &str = r#"
+fn effect(uv: vec2<f32>) -> vec4<f32> {
+    let c = sample_source(uv);
+    let luma = dot(c.rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    return vec4<f32>(mix(vec3<f32>(luma), c.rgb, uniforms.values[0].x), c.a);
+}
+"#;
+
+/// A post-process for the `blur`, `brightness`, `saturation` and
+/// `shader` modifiers: WGSL defining `effect`, and its uniforms.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Effect {
+    wgsl: Rc<str>,
+    uniforms: [f32; MAX_EFFECT_UNIFORMS],
+    /// Points around the view it spreads into.
+    pad: f32,
+}
+
+impl Effect {
+    pub(crate) fn new(wgsl: impl Into<Rc<str>>, uniforms: &[f32]) -> Self {
+        debug_assert!(
+            uniforms.len() <= MAX_EFFECT_UNIFORMS,
+            "effects have at most {} uniforms",
+            MAX_EFFECT_UNIFORMS
+        );
+        let mut values = [0.0; MAX_EFFECT_UNIFORMS];
+        for (value, u) in values.iter_mut().zip(uniforms) {
+            *value = *u;
+        }
+        Self {
+            wgsl: wgsl.into(),
+            uniforms: values,
+            pad: 0.0,
+        }
+    }
+
+    pub(crate) fn identity() -> Self {
+        Self::new(IDENTITY, &[])
+    }
+
+    pub(crate) fn blur(radius: f32) -> Self {
+        Self {
+            pad: radius.max(0.0),
+            ..Self::new(BLUR, &[radius])
+        }
+    }
+
+    pub(crate) fn brightness(amount: f32) -> Self {
+        Self::new(BRIGHTNESS, &[amount])
+    }
+
+    pub(crate) fn saturation(amount: f32) -> Self {
+        Self::new(SATURATION, &[amount])
+    }
+
+    /// The whole shader.
+    fn source(&self) -> String {
+        format!("{}{}{}", PRELUDE, self.wgsl, FRAGMENT)
+    }
+}
+
+/// Part of the window drawn into a texture of its own, with a vger of
+/// its own, to be composited once it's drawn.
+struct Layer {
+    /// What the layer's views were drawn with. Taken while they draw.
+    vger: Option<Vger>,
+    /// Where the layer is in its parent's points, snapped to pixels.
+    rect: WorldRect,
+    /// Where it's clipped to in its parent, in the same points.
+    clip: Option<WorldRect>,
+    /// The layer it's composited into, or `None` for the window.
+    parent: Option<usize>,
+    /// How it's composited, or `None` for a mask, which only other layers
+    /// read.
+    effect: Option<Effect>,
+    /// The layer whose alpha it's multiplied by.
+    mask: Option<usize>,
+}
+
+/// A texture a layer is drawn into, in pixels.
+struct LayerTexture {
+    view: wgpu::TextureView,
+    width: u32,
+    height: u32,
+}
+
+/// What's needed on the GPU, made on the first frame with layers.
+struct EffectGpu {
+    format: wgpu::TextureFormat,
+    layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    sampler: wgpu::Sampler,
+    /// Bound as the mask of layers without one.
+    white: wgpu::TextureView,
+    /// By shader. `None` when the shader didn't compile.
+    pipelines: HashMap<Rc<str>, Option<wgpu::RenderPipeline>>,
+    /// By layer, kept while layers stay the same size.
+    textures: Vec<LayerTexture>,
+}
+
+impl EffectGpu {
+    fn new(device: &wgpu::Device, queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("rui effect"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                texture_entry(1),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                texture_entry(3),
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("rui effect"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("rui effect"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let white = device.create_texture_with_data(
+            queue,
+            &wgpu::TextureDescriptor {
+                label: Some("rui effect mask"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            &[255; 4],
+        );
+        Self {
+            format,
+            layout,
+            pipeline_layout,
+            sampler,
+            white: white.create_view(&wgpu::TextureViewDescriptor::default()),
+            pipelines: HashMap::new(),
+            textures: vec![],
+        }
+    }
+
+    /// The pipeline for `effect`, compiling it the first time.
+    fn pipeline(
+        &mut self,
+        device: &wgpu::Device,
+        effect: &Effect,
+    ) -> Option<&wgpu::RenderPipeline> {
+        if !self.pipelines.contains_key(&effect.wgsl) {
+            #[cfg(not(target_arch = "wasm32"))]
+            device.push_error_scope(wgpu::ErrorFilter::Validation);
+            let source = effect.source();
+            let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: Some("rui effect"),
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+            });
+            let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("rui effect"),
+                layout: Some(&self.pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: "vs_main",
+                    buffers: &[],
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &module,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format: self.format,
+                        // Layers are drawn over transparent, so come out
+                        // premultiplied.
+                        blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+            });
+            // Bad WGSL from `shader` shouldn't take the app down.
+            #[cfg(not(target_arch = "wasm32"))]
+            let pipeline = match futures::executor::block_on(device.pop_error_scope()) {
+                Some(err) => {
+                    println!("shader effect failed to compile: {}", err);
+                    None
+                }
+                None => Some(pipeline),
+            };
+            #[cfg(target_arch = "wasm32")]
+            let pipeline = Some(pipeline);
+            self.pipelines.insert(effect.wgsl.clone(), pipeline);
+        }
+        self.pipelines[&effect.wgsl].as_ref()
+    }
+
+    /// Makes sure there's a texture of `width` by `height` pixels for the
+    /// layer at `index`.
+    fn texture(&mut self, device: &wgpu::Device, index: usize, width: u32, height: u32) {
+        let fits = self
+            .textures
+            .get(index)
+            .map_or(false, |t| (t.width, t.height) == (width, height));
+        if fits {
+            return;
+        }
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("rui layer"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let texture = LayerTexture {
+            view: texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            width,
+            height,
+        };
+        if index < self.textures.len() {
+            self.textures[index] = texture;
+        } else {
+            self.textures.push(texture);
+        }
+    }
+}
+
+/// The layers drawn this frame, kept by the `Context`, for effects and
+/// masks. Each is drawn by a vger of its own into a texture of its own.
+/// Once the window and its images are drawn, layers are composited into
+/// what they were drawn in, so like images, they show over the rest of
+/// it.
+#[derive(Default)]
+pub(crate) struct Effects {
+    /// What vgers for layers are made with. Set by the event loop.
+    device: Option<(Arc<wgpu::Device>, Arc<wgpu::Queue>, wgpu::TextureFormat)>,
+    layers: Vec<Layer>,
+    /// The vgers of last frame's layers, to draw with again.
+    spare: Vec<Vger>,
+    window_size: Size2D<f32, WorldSpace>,
+    scale: f32,
+    /// Whether we've said layers can't be drawn.
+    warned: bool,
+    gpu: Option<EffectGpu>,
+}
+
+impl Effects {
+    /// Lets layers be drawn, with vgers made with `device` and `queue`,
+    /// drawing in `format`.
+    pub(crate) fn set_device(
+        &mut self,
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        format: wgpu::TextureFormat,
+    ) {
+        self.device = Some((device, queue, format));
+        self.spare.clear();
+    }
+
+    /// Starts a frame drawn into a window `window_size` points across.
+    pub(crate) fn begin(&mut self, window_size: Size2D<f32, WorldSpace>, scale: f32) {
+        self.window_size = window_size;
+        self.scale = scale;
+    }
+
+    /// Where a layer for `rect` goes in `layer`, or the window, clipped to
+    /// `clip` but for `pad` points around it, and snapped to pixels.
+    fn layer_rect(
+        &mut self,
+        layer: Option<usize>,
+        rect: WorldRect,
+        clip: Option<WorldRect>,
+        pad: f32,
+    ) -> Option<WorldRect> {
+        if self.device.is_none() {
+            if !self.warned {
+                println!("no GPU to draw layers with, so effects and masks aren't applied");
+                self.warned = true;
+            }
+            return None;
+        }
+        let size = match layer {
+            Some(index) => self.layers[index].rect.size,
+            None => self.window_size,
+        };
+        let bounds = WorldRect::new(WorldPoint::zero(), size);
+        let visible = clip.unwrap_or(bounds).intersection(&bounds)?;
+        let rect = rect.intersection(&visible.inflate(pad, pad))?;
+        let s = self.scale;
+        let min = WorldPoint::new(
+            (rect.min_x() * s).floor() / s,
+            (rect.min_y() * s).floor() / s,
+        );
+        let max = WorldPoint::new((rect.max_x() * s).ceil() / s, (rect.max_y() * s).ceil() / s);
+        let rect = WorldRect::from_points([min, max]);
+        if rect.width() * s < 1.0 || rect.height() * s < 1.0 {
+            return None;
+        }
+        Some(rect)
+    }
+
+    /// Draws this frame's layers into their textures, then composites
+    /// them, innermost first, into `target`, which the window has been
+    /// drawn into. `draws` are this frame's images, some of which go in
+    /// layers.
+    pub(crate) fn render(
+        &mut self,
+        render_info: &RenderInfo,
+        images: &ImageCache,
+        draws: &[ImageDraw],
+        target: &wgpu::TextureView,
+        window_size: Size2D<f32, WorldSpace>,
+        scale: f32,
+    ) {
+        let mut layers = std::mem::take(&mut self.layers);
+        if layers.is_empty() {
+            return;
+        }
+
+        let (device, queue) = (render_info.device, render_info.queue);
+        let format = render_info.config.format;
+        if self.gpu.as_ref().map(|g| g.format) != Some(format) {
+            self.gpu = Some(EffectGpu::new(device, queue, format));
+        }
+        let gpu = self.gpu.as_mut().unwrap();
+
+        let pixels = |rect: WorldRect| {
+            (
+                (rect.width() * scale).round() as u32,
+                (rect.height() * scale).round() as u32,
+            )
+        };
+        for (index, layer) in layers.iter().enumerate() {
+            let (width, height) = pixels(layer.rect);
+            gpu.texture(device, index, width, height);
+        }
+        gpu.textures.truncate(layers.len());
+        for (index, layer) in layers.iter_mut().enumerate() {
+            let view = &gpu.textures[index].view;
+            if let Some(vger) = &mut layer.vger {
+                vger.encode(&wgpu::RenderPassDescriptor {
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    ..<_>::default()
+                });
+            }
+            let draws: Vec<ImageDraw> = draws
+                .iter()
+                .filter(|d| d.layer == Some(index))
+                .copied()
+                .collect();
+            images.render(
+                render_info,
+                &draws,
+                view,
+                layer.rect.size,
+                scale,
+                pixels(layer.rect),
+            );
+        }
+
+        // Composite children before their parents.
+        let depth = |mut index: usize| {
+            let mut depth = 0;
+            while let Some(parent) = layers[index].parent {
+                index = parent;
+                depth += 1;
+            }
+            depth
+        };
+        let mut order: Vec<(usize, usize)> = (0..layers.len())
+            .filter(|i| layers[*i].effect.is_some())
+            .map(|i| (depth(i), i))
+            .collect();
+        order.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("rui effects"),
+        });
+        for (_, index) in order {
+            let layer = &layers[index];
+            let effect = layer.effect.as_ref().unwrap();
+            let (size, target_pixels) = match layer.parent {
+                Some(parent) => (layers[parent].rect.size, pixels(layers[parent].rect)),
+                None => (
+                    window_size,
+                    (render_info.config.width, render_info.config.height),
+                ),
+            };
+            let (x, y, w, h) = match pixel_rect(layer.rect, layer.clip, size, scale, target_pixels)
+            {
+                Some(rect) => rect,
+                None => continue,
+            };
+            // Draw what was drawn, if the effect's shader didn't compile.
+            let effect = if gpu.pipeline(device, effect).is_some() {
+                effect.clone()
+            } else {
+                Effect::identity()
+            };
+            if gpu.pipeline(device, &effect).is_none() {
+                continue;
+            }
+
+            let (width, height) = pixels(layer.rect);
+            let mut uniforms = vec![
+                layer.rect.min_x() / size.width * 2.0 - 1.0,
+                layer.rect.min_y() / size.height * 2.0 - 1.0,
+                layer.rect.max_x() / size.width * 2.0 - 1.0,
+                layer.rect.max_y() / size.height * 2.0 - 1.0,
+                1.0 / width as f32,
+                1.0 / height as f32,
+                scale,
+                // Padding, as `values` starts on 16 bytes.
+                0.0,
+            ];
+            uniforms.extend_from_slice(&effect.uniforms);
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("rui effect uniforms"),
+                contents: &uniforms
+                    .iter()
+                    .flat_map(|f| f.to_ne_bytes())
+                    .collect::<Vec<_>>(),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+            let mask = match layer.mask {
+                Some(mask) => &gpu.textures[mask].view,
+                None => &gpu.white,
+            };
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("rui effect"),
+                layout: &gpu.layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&gpu.textures[index].view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(&gpu.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::TextureView(mask),
+                    },
+                ],
+            });
+
+            let view = match layer.parent {
+                Some(parent) => &gpu.textures[parent].view,
+                None => target,
+            };
+            let pipeline = gpu.pipelines[&effect.wgsl].as_ref().unwrap();
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("rui effect"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                ..<_>::default()
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.set_scissor_rect(x, y, w, h);
+            pass.draw(0..6, 0..1);
+        }
+        queue.submit(Some(encoder.finish()));
+
+        self.spare
+            .extend(layers.into_iter().filter_map(|layer| layer.vger));
+    }
+}
+
+/// Makes `vger`, just begun, draw like `xform` does, but from `origin`.
+fn set_transform(vger: &mut Vger, xform: LocalToWorld, origin: WorldPoint) {
+    vger.translate([xform.m31 - origin.x, xform.m32 - origin.y]);
+    vger.rotate(xform.m12.atan2(xform.m11));
+    let sx = xform.m11.hypot(xform.m12);
+    if sx > 0.0 {
+        vger.scale([sx, xform.determinant() / sx]);
+    }
+}
+
+/// Where a layer covering `rect`, in `vger`'s local points, with `pad`
+/// points around it, goes in the layer or window drawn in now. `None` when
+/// there's no GPU to draw it with, or it wouldn't be seen.
+pub(crate) fn layer_rect(
+    cx: &mut Context,
+    vger: &Vger,
+    rect: LocalRect,
+    pad: f32,
+) -> Option<WorldRect> {
+    let world = vger.current_transform().outer_transformed_rect(&rect);
+    cx.effects
+        .layer_rect(cx.layer, world.inflate(pad, pad), cx.clip_rect, pad)
+}
+
+/// Has `draw` draw into a new layer at `rect`, from `layer_rect`, from
+/// where `vger` is drawing now, and returns it.
+pub(crate) fn draw_into(
+    cx: &mut Context,
+    vger: &Vger,
+    rect: WorldRect,
+    effect: Option<Effect>,
+    draw: impl FnOnce(&mut Context, &mut Vger),
+) -> usize {
+    let fx = &mut cx.effects;
+    let mut layer_vger = match fx.spare.pop() {
+        Some(vger) => vger,
+        None => {
+            let (device, queue, format) = fx.device.clone().unwrap();
+            Vger::new(device, queue, format)
+        }
+    };
+    layer_vger.begin(rect.width(), rect.height(), fx.scale);
+    set_transform(&mut layer_vger, vger.current_transform(), rect.origin);
+
+    let index = fx.layers.len();
+    fx.layers.push(Layer {
+        vger: None,
+        rect,
+        clip: cx.clip_rect,
+        parent: cx.layer,
+        effect,
+        mask: None,
+    });
+
+    // Views inside draw in the layer's points.
+    let parent = cx.layer.replace(index);
+    let offset = rect.origin.to_vector();
+    let clip = cx.clip_rect.map(|clip| clip.translate(-offset));
+    let clip = std::mem::replace(&mut cx.clip_rect, clip);
+    draw(cx, &mut layer_vger);
+    cx.clip_rect = clip;
+    cx.layer = parent;
+
+    cx.effects.layers[index].vger = Some(layer_vger);
+    index
+}
+
+/// Has `draw` draw into a layer of its own covering `rect`, in `vger`'s
+/// local points, with `effect` applied once it's drawn. Where there's no
+/// layer to draw into, `draw` draws into `vger` without the effect.
+pub(crate) fn draw_layer(
+    cx: &mut Context,
+    vger: &mut Vger,
+    rect: LocalRect,
+    effect: Effect,
+    draw: impl FnOnce(&mut Context, &mut Vger),
+) {
+    match layer_rect(cx, vger, rect, effect.pad) {
+        Some(rect) => {
+            draw_into(cx, vger, rect, Some(effect), draw);
+        }
+        None => draw(cx, vger),
+    }
+}
+
+/// Has `draw` draw the mask of `layer`, which shows only where the mask
+/// is opaque.
+pub(crate) fn draw_mask(
+    cx: &mut Context,
+    vger: &Vger,
+    layer: usize,
+    draw: impl FnOnce(&mut Context, &mut Vger),
+) {
+    let rect = cx.effects.layers[layer].rect;
+    let mask = draw_into(cx, vger, rect, None, draw);
+    cx.effects.layers[layer].mask = Some(mask);
+}
@@ -25,11 +25,60 @@ pub enum Event {
         position: LocalPoint,
     },
 
+    /// Mouse moved over the window, whether or not a button is down.
+    Hover { position: LocalPoint },
+
+    /// Mouse left the window.
+    HoverLeave,
+
+    /// Mouse wheel or trackpad scroll, by how far to move the content in
+    /// points. Wheels which scroll by lines move `SCROLL_LINE_HEIGHT` per
+    /// line.
+    Scroll {
+        position: LocalPoint,
+        delta: LocalOffset,
+    },
+
     /// Menu command.
     Command(String),
 
-    /// Key press.
-    Key(Key),
+    /// Files to open, from the command line, the Finder or another launch
+    /// of the app. See `single_instance`.
+    OpenFiles(Vec<std::path::PathBuf>),
+
+    /// A URL to open, for a scheme registered with `register_url_scheme`.
+    OpenUrl(String),
+
+    /// Files dragged from another app are over the window. See `on_drop`.
+    DropHover {
+        position: LocalPoint,
+        paths: Vec<std::path::PathBuf>,
+    },
+
+    /// Dragged files left the window without being dropped.
+    DropHoverLeave,
+
+    /// Files dropped on the window.
+    DropFiles {
+        position: LocalPoint,
+        paths: Vec<std::path::PathBuf>,
+    },
+
+    /// Key press. Held keys repeat, with `repeat` set.
+    Key {
+        key: Key,
+        mods: KeyboardModifiers,
+        repeat: bool,
+    },
+
+    /// Key release.
+    KeyUp { key: Key, mods: KeyboardModifiers },
+
+    /// Text from an input method.
+    TextInput(TextInput),
+
+    /// A message from a MIDI input. See `Context::on_midi`.
+    Midi(MidiMessage),
 
     /// Animation.
     Anim,
@@ -42,12 +91,19 @@ impl Event {
             Event::TouchBegin { position, .. } => *position += offset,
             Event::TouchMove { position, .. } => *position += offset,
             Event::TouchEnd { position, .. } => *position += offset,
+            Event::Hover { position } => *position += offset,
+            Event::Scroll { position, .. } => *position += offset,
+            Event::DropHover { position, .. } => *position += offset,
+            Event::DropFiles { position, .. } => *position += offset,
             _ => (),
         }
         event
     }
 }
 
+/// Points scrolled per line, for mouse wheels which scroll by lines.
+pub const SCROLL_LINE_HEIGHT: f32 = 20.0;
+
 #[derive(Copy, Clone, Debug)]
 pub enum MouseButton {
     Left,
@@ -55,7 +111,22 @@ pub enum MouseButton {
     Center,
 }
 
-#[derive(Copy, Clone, Debug, Default)]
+/// Text from an input method: composed characters, e.g. CJK input or
+/// press-and-hold accents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TextInput {
+    /// Text being composed, shown in place until committed, and the
+    /// selected range within it in bytes. Empty text ends composition.
+    Compose {
+        text: String,
+        selection: Option<(usize, usize)>,
+    },
+
+    /// Text to insert, ending any composition.
+    Commit(String),
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct KeyboardModifiers {
     pub shift: bool,
     pub control: bool,
@@ -63,6 +134,28 @@ pub struct KeyboardModifiers {
     pub command: bool,
 }
 
+impl KeyboardModifiers {
+    /// Is the modifier for shortcuts held? Command on macOS, Control
+    /// elsewhere.
+    pub fn shortcut(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            self.command
+        } else {
+            self.control
+        }
+    }
+
+    /// Is the modifier for moving by words held? Option on macOS, Control
+    /// elsewhere.
+    pub fn word(&self) -> bool {
+        if cfg!(target_os = "macos") {
+            self.alt
+        } else {
+            self.control
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Key {
     Character(char),
@@ -123,4 +216,5 @@ pub enum HotKey {
     KeyX,
     KeyY,
     KeyZ,
+    Comma,
 }
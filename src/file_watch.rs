@@ -0,0 +1,149 @@
+use crate::*;
+use notify::Watcher;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Changes this close together are delivered together.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Longest to hold changes back while a file keeps changing.
+const MAX_DELAY: Duration = Duration::from_secs(1);
+
+/// Identifies a watch started with `Context::watch_path`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileWatchId(usize);
+
+pub(crate) struct FileWatch {
+    id: FileWatchId,
+    func: Rc<dyn Fn(&mut Context, &Path)>,
+    /// Stops watching when dropped.
+    _watcher: Option<notify::RecommendedWatcher>,
+}
+
+/// Collects changes until things are quiet, then hands them to the UI.
+fn debounce(id: FileWatchId, rx: mpsc::Receiver<PathBuf>) {
+    while let Ok(path) = rx.recv() {
+        let start = Instant::now();
+        let mut paths = vec![path];
+        while start.elapsed() < MAX_DELAY {
+            match rx.recv_timeout(DEBOUNCE) {
+                Ok(path) => {
+                    if !paths.contains(&path) {
+                        paths.push(path);
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+        on_main(move |cx| cx.files_changed(id, paths));
+    }
+}
+
+impl Context {
+    /// Calls `f` on the UI thread when the file at `path` changes, or when
+    /// anything in the folder at `path` does, with the path of what changed.
+    /// Bursts of changes, like an editor saving, are delivered once.
+    ///
+    /// Use this to reload stylesheets, translations or a document being
+    /// previewed.
+    pub fn watch_path(
+        &mut self,
+        path: impl AsRef<Path>,
+        f: impl Fn(&mut Context, &Path) + 'static,
+    ) -> FileWatchId {
+        let id = FileWatchId(self.next_file_watch_id);
+        self.next_file_watch_id += 1;
+
+        let (tx, rx) = mpsc::channel();
+        let watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) => {
+                    if !matches!(event.kind, notify::EventKind::Access(_)) {
+                        for path in event.paths {
+                            let _ = tx.send(path);
+                        }
+                    }
+                }
+                Err(err) => println!("error watching files: {}", err),
+            })
+            .and_then(|mut watcher| {
+                watcher.watch(path.as_ref(), notify::RecursiveMode::Recursive)?;
+                Ok(watcher)
+            });
+
+        let watcher = match watcher {
+            Ok(watcher) => {
+                // Ends when the watcher is dropped.
+                std::thread::spawn(move || debounce(id, rx));
+                Some(watcher)
+            }
+            Err(err) => {
+                println!("unable to watch {}: {}", path.as_ref().display(), err);
+                None
+            }
+        };
+
+        self.file_watches.push(FileWatch {
+            id,
+            func: Rc::new(f),
+            _watcher: watcher,
+        });
+        id
+    }
+
+    /// Stops a watch started with `watch_path`.
+    pub fn unwatch_path(&mut self, id: FileWatchId) {
+        self.file_watches.retain(|w| w.id != id);
+    }
+
+    fn files_changed(&mut self, id: FileWatchId, paths: Vec<PathBuf>) {
+        let func = match self.file_watches.iter().find(|w| w.id == id) {
+            Some(watch) => watch.func.clone(),
+            // Unwatched since.
+            None => return,
+        };
+        for path in paths {
+            func(self, &path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_debounce() {
+        let (tx, rx) = mpsc::channel();
+        for name in ["a.css", "a.css", "b.css"] {
+            tx.send(PathBuf::from(name)).unwrap();
+        }
+        drop(tx);
+        debounce(FileWatchId(0), rx);
+
+        let mut cx = Context::new();
+        let seen = Rc::new(std::cell::RefCell::new(vec![]));
+        let seen2 = seen.clone();
+        cx.file_watches.push(FileWatch {
+            id: FileWatchId(0),
+            func: Rc::new(move |_, path| seen2.borrow_mut().push(path.to_path_buf())),
+            _watcher: None,
+        });
+
+        // The changes were queued for the UI thread as one batch.
+        while let Some(f) = crate::winit_event_loop::GLOBAL_WORK_QUEUE
+            .lock()
+            .unwrap()
+            .pop_front()
+        {
+            f(&mut cx);
+        }
+        assert_eq!(
+            *seen.borrow(),
+            [PathBuf::from("a.css"), PathBuf::from("b.css")]
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::*;
+use std::ops::Range;
+
+/// What to find and what to replace it with. Edited by the panel
+/// `text_editor` shows with Cmd+F.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Find {
+    pub pattern: String,
+    pub replacement: String,
+    /// Treats the pattern as a regular expression, with `$1` and the like
+    /// in the replacement standing for its groups. Needs the `regex`
+    /// feature.
+    pub regex: bool,
+    pub case_sensitive: bool,
+}
+
+impl Find {
+    /// Char ranges of the matches in `text`, or why the pattern is no good.
+    pub fn matches(&self, text: &str) -> Result<Vec<Range<usize>>, String> {
+        if self.pattern.is_empty() {
+            Ok(vec![])
+        } else if self.regex {
+            self.regex_matches(text)
+        } else {
+            Ok(self.literal_matches(text))
+        }
+    }
+
+    /// Replaces the match at `range`, one of the `matches`.
+    pub fn replace(&self, text: &str, range: Range<usize>) -> Result<String, String> {
+        let bytes = byte_index(text, range.start)..byte_index(text, range.end);
+        let replacement = if self.regex {
+            self.regex_replacement(text, bytes.start)?
+        } else {
+            self.replacement.clone()
+        };
+        let mut t = text.to_string();
+        t.replace_range(bytes, &replacement);
+        Ok(t)
+    }
+
+    /// Replaces every match, returning the new text and how many there were.
+    pub fn replace_all(&self, text: &str) -> Result<(String, usize), String> {
+        let matches = self.matches(text)?;
+        if self.regex {
+            return Ok((self.regex_replace_all(text)?, matches.len()));
+        }
+        let mut t = text.to_string();
+        // From the end, so the ranges before stay put.
+        for range in matches.iter().rev() {
+            t = self.replace(&t, range.clone())?;
+        }
+        Ok((t, matches.len()))
+    }
+
+    fn literal_matches(&self, text: &str) -> Vec<Range<usize>> {
+        let same = |a: char, b: char| {
+            a == b || (!self.case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+        };
+        let text: Vec<char> = text.chars().collect();
+        let pattern: Vec<char> = self.pattern.chars().collect();
+        let mut matches = vec![];
+        let mut i = 0;
+        while i + pattern.len() <= text.len() {
+            if text[i..i + pattern.len()]
+                .iter()
+                .zip(&pattern)
+                .all(|(a, b)| same(*a, *b))
+            {
+                matches.push(i..i + pattern.len());
+                i += pattern.len();
+            } else {
+                i += 1;
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Find {
+    fn compile(&self) -> Result<regex::Regex, String> {
+        regex::RegexBuilder::new(&self.pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .map_err(|err| err.to_string())
+    }
+
+    fn regex_matches(&self, text: &str) -> Result<Vec<Range<usize>>, String> {
+        let char_index = |byte: usize| text[..byte].chars().count();
+        Ok(self
+            .compile()?
+            .find_iter(text)
+            // Empty matches can't be seen or replaced sensibly.
+            .filter(|m| !m.range().is_empty())
+            .map(|m| char_index(m.start())..char_index(m.end()))
+            .collect())
+    }
+
+    /// The replacement for the match starting at byte `start`, with its
+    /// groups filled in.
+    fn regex_replacement(&self, text: &str, start: usize) -> Result<String, String> {
+        let mut replacement = String::new();
+        if let Some(caps) = self
+            .compile()?
+            .captures_iter(text)
+            .find(|caps| caps.get(0).map_or(false, |m| m.start() == start))
+        {
+            caps.expand(&self.replacement, &mut replacement);
+        }
+        Ok(replacement)
+    }
+
+    fn regex_replace_all(&self, text: &str) -> Result<String, String> {
+        let re = self.compile()?;
+        // The same non-empty matches `regex_matches` finds.
+        let captures: Vec<_> = re
+            .captures_iter(text)
+            .filter(|caps| caps.get(0).map_or(false, |m| !m.range().is_empty()))
+            .collect();
+        let mut t = text.to_string();
+        // From the end, so the ranges before stay put.
+        for caps in captures.iter().rev() {
+            let mut replacement = String::new();
+            caps.expand(&self.replacement, &mut replacement);
+            t.replace_range(caps.get(0).unwrap().range(), &replacement);
+        }
+        Ok(t)
+    }
+}
+
+#[cfg(not(feature = "regex"))]
+const NO_REGEX: &str = "Regular expressions need the regex feature";
+
+#[cfg(not(feature = "regex"))]
+impl Find {
+    fn regex_matches(&self, _text: &str) -> Result<Vec<Range<usize>>, String> {
+        Err(NO_REGEX.into())
+    }
+
+    fn regex_replacement(&self, _text: &str, _start: usize) -> Result<String, String> {
+        Err(NO_REGEX.into())
+    }
+
+    fn regex_replace_all(&self, _text: &str) -> Result<String, String> {
+        Err(NO_REGEX.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_find_literal() {
+        let mut find = Find {
+            pattern: "ab".into(),
+            replacement: "é".into(),
+            ..Default::default()
+        };
+        let text = "ab Ab éab";
+        assert_eq!(find.matches(text), Ok(vec![0..2, 3..5, 7..9]));
+        assert_eq!(find.replace(text, 7..9), Ok("ab Ab éé".into()));
+        assert_eq!(find.replace_all(text), Ok(("é é éé".into(), 3)));
+
+        find.case_sensitive = true;
+        assert_eq!(find.matches(text), Ok(vec![0..2, 7..9]));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_find_regex() {
+        let find = Find {
+            pattern: r"(\w)(\d)".into(),
+            replacement: "$2$1".into(),
+            regex: true,
+            ..Default::default()
+        };
+        let text = "é1 b2";
+        assert_eq!(find.matches(text), Ok(vec![0..2, 3..5]));
+        assert_eq!(find.replace(text, 3..5), Ok("é1 2b".into()));
+        assert_eq!(find.replace_all(text), Ok(("1é 2b".into(), 2)));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_find_regex_empty_matches() {
+        let find = Find {
+            pattern: "x*".into(),
+            replacement: "-".into(),
+            regex: true,
+            ..Default::default()
+        };
+        let text = "axxb";
+        assert_eq!(find.matches(text), Ok(vec![1..3]));
+        assert_eq!(find.replace_all(text), Ok(("a-b".into(), 1)));
+    }
+}
@@ -0,0 +1,793 @@
+use crate::*;
+use euclid::{Angle, Point2D, Transform2D, UnknownUnit};
+use std::sync::Arc;
+use ttf_parser::{colr, name_id, GlyphId, OutlineBuilder, RasterImageFormat};
+
+/// Which font text is set in, and how big and heavy. Set it for a view and
+/// everything in it with the `font` modifier:
+///
+/// ```no_run
+/// # use rui::*;
+/// text("Hello").font(Font::named("Inter").size(14).weight(FontWeight::Bold));
+/// ```
+///
+/// Families are found in `Context::fonts`, so load them there first. A
+/// family that isn't loaded falls back to vger's built-in font, and bold
+/// without a bold face is drawn twice, slightly offset.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Font {
+    /// `None` for vger's built-in font.
+    pub family: Option<String>,
+    /// `None` to leave text at its own size.
+    pub size: Option<u32>,
+    pub weight: FontWeight,
+}
+
+impl Font {
+    pub fn named(family: impl Into<String>) -> Self {
+        Self {
+            family: Some(family.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn size(self, size: u32) -> Self {
+        Self {
+            size: Some(size),
+            ..self
+        }
+    }
+
+    pub fn weight(self, weight: FontWeight) -> Self {
+        Self { weight, ..self }
+    }
+}
+
+/// A TrueType or OpenType face loaded into `Fonts`.
+#[derive(Clone)]
+pub struct FontFace {
+    data: Arc<Vec<u8>>,
+    /// Within a font collection.
+    index: u32,
+    family: String,
+    weight: FontWeight,
+    italic: bool,
+}
+
+impl FontFace {
+    fn parse(data: Arc<Vec<u8>>, index: u32) -> Result<Self, String> {
+        let face = ttf_parser::Face::parse(&data, index).map_err(|err| err.to_string())?;
+        let names = face.names();
+        let name = |id| {
+            names
+                .into_iter()
+                .filter(|n| n.name_id == id)
+                .find_map(|n| n.to_string())
+        };
+        let family = name(name_id::TYPOGRAPHIC_FAMILY)
+            .or_else(|| name(name_id::FAMILY))
+            .ok_or("font has no family name")?;
+        let weight = if face.is_bold() || face.weight().to_number() >= 600 {
+            FontWeight::Bold
+        } else {
+            FontWeight::Regular
+        };
+        let italic = face.is_italic() || face.is_oblique();
+        Ok(Self {
+            data,
+            index,
+            family,
+            weight,
+            italic,
+        })
+    }
+
+    fn face(&self) -> ttf_parser::Face {
+        ttf_parser::Face::parse(&self.data, self.index).expect("face was parsed when loaded")
+    }
+
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    pub fn weight(&self) -> FontWeight {
+        self.weight
+    }
+
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.face().glyph_index(c).is_some()
+    }
+
+    /// Can `c` be drawn from this face? Glyphs only in PNG bitmaps, like
+    /// those of Apple Color Emoji, need the `images` feature.
+    fn can_draw(&self, c: char) -> bool {
+        let face = self.face();
+        match face.glyph_index(c) {
+            Some(id) => {
+                c.is_whitespace()
+                    || face.is_color_glyph(id)
+                    || face.glyph_bounding_box(id).is_some()
+                    || (cfg!(feature = "images") && png_glyph(&face, id, u16::MAX).is_some())
+            }
+            None => false,
+        }
+    }
+
+    fn same_face(&self, other: &FontFace) -> bool {
+        Arc::ptr_eq(&self.data, &other.data) && self.index == other.index
+    }
+
+    /// Places each glyph of `text` along the baseline, returning the glyphs
+    /// with their x positions, and the total advance.
+    fn layout(&self, text: &str, size: u32) -> (Vec<(GlyphId, f32)>, f32) {
+        let face = self.face();
+        let scale = size as f32 / face.units_per_em() as f32;
+        let kern = face.tables().kern;
+        let mut glyphs = vec![];
+        let mut x = 0.0;
+        let mut prev: Option<GlyphId> = None;
+        for c in text.chars().filter(|&c| !is_ignorable(c)) {
+            let id = face.glyph_index(c).unwrap_or(GlyphId(0));
+            if let (Some(prev), Some(kern)) = (prev, kern) {
+                let adjust = kern
+                    .subtables
+                    .into_iter()
+                    .filter(|s| s.horizontal && !s.variable)
+                    .find_map(|s| s.glyphs_kerning(prev, id))
+                    .unwrap_or(0);
+                x += adjust as f32 * scale;
+            }
+            glyphs.push((id, x));
+            x += face.glyph_hor_advance(id).unwrap_or(0) as f32 * scale;
+            prev = Some(id);
+        }
+        (glyphs, x)
+    }
+
+    /// The line box of `text`: its advance across, and from the descender
+    /// to the ascender, with the baseline at zero.
+    pub fn text_bounds(&self, text: &str, size: u32) -> LocalRect {
+        let (_, width) = self.layout(text, size);
+        let face = self.face();
+        let scale = size as f32 / face.units_per_em() as f32;
+        let descender = face.descender() as f32 * scale;
+        let ascender = face.ascender() as f32 * scale;
+        LocalRect::new(
+            [0.0, descender].into(),
+            [width, ascender - descender].into(),
+        )
+    }
+
+    /// Fills the glyph outlines of `text`, with the baseline starting at
+    /// the origin. Color glyphs, such as emoji, are drawn in their own
+    /// colors, except for parts meant to be `foreground`.
+    pub fn draw_text(
+        &self,
+        text: &str,
+        size: u32,
+        paint: PaintIndex,
+        foreground: Color,
+        vger: &mut Vger,
+    ) {
+        let (glyphs, _) = self.layout(text, size);
+        let face = self.face();
+        let scale = size as f32 / face.units_per_em() as f32;
+        for (id, x) in glyphs {
+            let xform: Transform2D<f32, UnknownUnit, LocalSpace> =
+                Transform2D::scale(scale, scale).then_translate([x, 0.0].into());
+            if face.is_color_glyph(id) {
+                let mut painter = ColorGlyphPainter {
+                    face: &face,
+                    vger: &mut *vger,
+                    xforms: vec![xform],
+                    outline: None,
+                    foreground,
+                };
+                let [r, g, b, a] = [foreground.r, foreground.g, foreground.b, foreground.a]
+                    .map(|v| (v * 255.0).round() as u8);
+                let foreground = ttf_parser::RgbaColor::new(r, g, b, a);
+                if face
+                    .paint_color_glyph(id, 0, foreground, &mut painter)
+                    .is_some()
+                {
+                    continue;
+                }
+            }
+            if fill_glyph(&face, id, xform, vger) {
+                vger.fill(paint);
+            }
+        }
+    }
+
+    /// Draws the glyphs of `text` which are only PNG bitmaps, which
+    /// `draw_text` leaves out, as images. They're drawn from the strike
+    /// nearest the text's size in pixels, and decoded on first use.
+    #[cfg(feature = "images")]
+    pub(crate) fn draw_bitmaps(&self, text: &str, size: u32, cx: &mut Context, vger: &Vger) {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let (glyphs, _) = self.layout(text, size);
+        let face = self.face();
+        let pixels = (size as f32 * cx.scale_factor()).round() as u16;
+        let xform = vger.current_transform();
+        let all = LocalRect::new(LocalPoint::zero(), [1.0, 1.0].into());
+        for (id, x) in glyphs {
+            if face.is_color_glyph(id) || face.glyph_bounding_box(id).is_some() {
+                continue;
+            }
+            let raster = match png_glyph(&face, id, pixels) {
+                Some(raster) => raster,
+                None => continue,
+            };
+            let mut hasher = DefaultHasher::new();
+            (Arc::as_ptr(&self.data) as usize, self.index, id.0).hash(&mut hasher);
+            raster.pixels_per_em.hash(&mut hasher);
+            let key = hasher.finish();
+            if cx.images.get(key).is_none() {
+                match decode_image(raster.data) {
+                    Ok(image) => cx.images.insert(key, Arc::new(image)),
+                    Err(_) => continue,
+                }
+            }
+            // Offsets are in the strike's pixels, to the bottom left.
+            let scale = size as f32 / raster.pixels_per_em as f32;
+            let rect = LocalRect::new(
+                [x + raster.x as f32 * scale, raster.y as f32 * scale].into(),
+                [raster.width as f32 * scale, raster.height as f32 * scale].into(),
+            );
+            let world = xform.outer_transformed_rect(&rect);
+            cx.images.draw(key, world, all, 0.0, cx.clip_rect, cx.layer);
+        }
+    }
+
+    #[cfg(not(feature = "images"))]
+    pub(crate) fn draw_bitmaps(&self, _text: &str, _size: u32, _cx: &mut Context, _vger: &Vger) {}
+}
+
+/// The PNG bitmap of glyph `id` in the strike closest to `pixels` per em,
+/// as in sbix and CBDT tables.
+fn png_glyph<'a>(
+    face: &ttf_parser::Face<'a>,
+    id: GlyphId,
+    pixels: u16,
+) -> Option<ttf_parser::RasterGlyphImage<'a>> {
+    face.glyph_raster_image(id, pixels)
+        .filter(|raster| matches!(raster.format, RasterImageFormat::PNG))
+}
+
+/// Adds the outline of glyph `id` to vger's path, returning whether it
+/// has one.
+fn fill_glyph(
+    face: &ttf_parser::Face,
+    id: GlyphId,
+    xform: Transform2D<f32, UnknownUnit, LocalSpace>,
+    vger: &mut Vger,
+) -> bool {
+    let mut path = GlyphPath {
+        vger,
+        xform,
+        start: LocalPoint::zero(),
+        last: LocalPoint::zero(),
+        empty: true,
+    };
+    face.outline_glyph(id, &mut path);
+    !path.empty
+}
+
+/// Draws the layers of a COLR glyph. vger can only clip to rectangles and
+/// doesn't blend layers, so clips and blend modes are left out, and
+/// gradients are drawn in the text's color.
+struct ColorGlyphPainter<'a, 'b> {
+    face: &'a ttf_parser::Face<'a>,
+    vger: &'b mut Vger,
+    /// From font units to points, with transforms from the glyph pushed on.
+    xforms: Vec<Transform2D<f32, UnknownUnit, LocalSpace>>,
+    outline: Option<GlyphId>,
+    foreground: Color,
+}
+
+impl<'a> ColorGlyphPainter<'a, '_> {
+    fn push(&mut self, xform: Transform2D<f32, UnknownUnit, UnknownUnit>) {
+        let current = *self.xforms.last().expect("base transform");
+        self.xforms.push(xform.then(&current));
+    }
+}
+
+impl<'a> colr::Painter<'a> for ColorGlyphPainter<'a, '_> {
+    fn outline_glyph(&mut self, glyph_id: GlyphId) {
+        self.outline = Some(glyph_id);
+    }
+
+    fn paint(&mut self, paint: colr::Paint<'a>) {
+        let color = match paint {
+            colr::Paint::Solid(c) => Color::new(
+                c.red as f32 / 255.0,
+                c.green as f32 / 255.0,
+                c.blue as f32 / 255.0,
+                c.alpha as f32 / 255.0,
+            ),
+            _ => self.foreground,
+        };
+        let xform = *self.xforms.last().expect("base transform");
+        if let Some(id) = self.outline {
+            if fill_glyph(self.face, id, xform, self.vger) {
+                let paint = self.vger.color_paint(color);
+                self.vger.fill(paint);
+            }
+        }
+    }
+
+    fn push_clip(&mut self) {}
+
+    fn push_clip_box(&mut self, _clipbox: colr::ClipBox) {}
+
+    fn pop_clip(&mut self) {}
+
+    fn push_layer(&mut self, _mode: colr::CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_translate(&mut self, tx: f32, ty: f32) {
+        self.push(Transform2D::translation(tx, ty));
+    }
+
+    fn push_scale(&mut self, sx: f32, sy: f32) {
+        self.push(Transform2D::scale(sx, sy));
+    }
+
+    /// Angles are in half turns.
+    fn push_rotate(&mut self, angle: f32) {
+        self.push(Transform2D::rotation(Angle::radians(
+            angle * std::f32::consts::PI,
+        )));
+    }
+
+    fn push_skew(&mut self, skew_x: f32, skew_y: f32) {
+        let (tx, ty) = (
+            (skew_x * std::f32::consts::PI).tan(),
+            (skew_y * std::f32::consts::PI).tan(),
+        );
+        self.push(Transform2D::new(1.0, ty, -tx, 1.0, 0.0, 0.0));
+    }
+
+    fn push_transform(&mut self, t: ttf_parser::Transform) {
+        self.push(Transform2D::new(t.a, t.b, t.c, t.d, t.e, t.f));
+    }
+
+    fn pop_transform(&mut self) {
+        if self.xforms.len() > 1 {
+            self.xforms.pop();
+        }
+    }
+}
+
+/// Splits the cubic from `p0` to `p3` in half and fits a quadratic to each
+/// half, which is close enough at text and icon sizes. Returns the control
+/// and end points of the two quadratics.
+pub(crate) fn split_cubic(
+    p0: LocalPoint,
+    p1: LocalPoint,
+    p2: LocalPoint,
+    p3: LocalPoint,
+) -> [(LocalPoint, LocalPoint); 2] {
+    let p01 = p0.lerp(p1, 0.5);
+    let p12 = p1.lerp(p2, 0.5);
+    let p23 = p2.lerp(p3, 0.5);
+    let p012 = p01.lerp(p12, 0.5);
+    let p123 = p12.lerp(p23, 0.5);
+    let mid = p012.lerp(p123, 0.5);
+    let fit = |a: LocalPoint, b: LocalPoint, c: LocalPoint, d: LocalPoint| {
+        let v = (b.to_vector() + c.to_vector()) * 0.75 - (a.to_vector() + d.to_vector()) * 0.25;
+        v.to_point()
+    };
+    [
+        (fit(p0, p01, p012, mid), mid),
+        (fit(mid, p123, p23, p3), p3),
+    ]
+}
+
+/// Feeds a glyph outline to vger's path fill, which only takes quadratic
+/// curves.
+struct GlyphPath<'a> {
+    vger: &'a mut Vger,
+    /// From font units to points.
+    xform: Transform2D<f32, UnknownUnit, LocalSpace>,
+    start: LocalPoint,
+    last: LocalPoint,
+    empty: bool,
+}
+
+impl GlyphPath<'_> {
+    fn point(&self, x: f32, y: f32) -> LocalPoint {
+        self.xform.transform_point(Point2D::new(x, y))
+    }
+}
+
+impl OutlineBuilder for GlyphPath<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.vger.move_to(p);
+        self.start = p;
+        self.last = p;
+        self.empty = false;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.vger.quad_to(self.last.lerp(p, 0.5), p);
+        self.last = p;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.vger.quad_to(self.point(x1, y1), p);
+        self.last = p;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let p3 = self.point(x, y);
+        for (control, end) in split_cubic(self.last, self.point(x1, y1), self.point(x2, y2), p3) {
+            self.vger.quad_to(control, end);
+        }
+        self.last = p3;
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            let (start, last) = (self.start, self.last);
+            self.vger.quad_to(last.lerp(start, 0.5), start);
+            self.last = start;
+        }
+    }
+}
+
+/// Zero-width characters which only change how their neighbors are
+/// shaped, such as emoji variation selectors and joiners. Without shaping,
+/// they're left out rather than drawn as boxes.
+fn is_ignorable(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200F}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{FE00}'..='\u{FE0F}'
+            | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Does vger's built-in font have `c`? It covers Latin and punctuation.
+fn vger_covers(c: char) -> bool {
+    (c as u32) < 0x250 || ('\u{2000}'..='\u{206F}').contains(&c)
+}
+
+/// Part of a string set in one face, or in vger's built-in font if `face`
+/// is `None`.
+#[derive(Clone)]
+pub(crate) struct FontRun {
+    pub text: String,
+    pub face: Option<FontFace>,
+}
+
+/// Fonts loaded for `Font::named`, kept in `Context::fonts`.
+///
+/// Characters missing from a font are looked for in the fallback
+/// families, in order, so text mixing scripts, or with emoji, shows
+/// instead of boxes. Emoji fonts with COLR color layers, like Segoe UI
+/// Emoji, are drawn in color. So are those with PNG bitmaps, like Apple
+/// Color Emoji, with the `images` feature.
+#[derive(Clone, Default)]
+pub struct Fonts {
+    faces: Vec<FontFace>,
+    fallbacks: Vec<String>,
+    /// Has `load_system_fallbacks` been called, or tried for text which
+    /// needed it?
+    system_fallbacks: bool,
+}
+
+impl Fonts {
+    /// Loads the faces of a TrueType or OpenType font, or font collection,
+    /// returning their family names.
+    pub fn load(&mut self, data: Vec<u8>) -> Result<Vec<String>, String> {
+        let data = Arc::new(data);
+        let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+        let mut families = vec![];
+        for index in 0..count {
+            let face = FontFace::parse(data.clone(), index)?;
+            if !families.contains(&face.family) {
+                families.push(face.family.clone());
+            }
+            self.faces.push(face);
+        }
+        Ok(families)
+    }
+
+    /// Loads a `.ttf`, `.otf` or `.ttc` file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_file(&mut self, path: impl AsRef<std::path::Path>) -> Result<Vec<String>, String> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        self.load(data)
+    }
+
+    /// Loads the faces of an installed font family, looking in the usual
+    /// font folders for files named after it, like `Inter-Bold.otf` for
+    /// "Inter".
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_system(&mut self, family: &str) -> Result<(), String> {
+        let mut files = vec![];
+        for dir in system_font_dirs() {
+            find_font_files(&dir, family, 3, &mut files);
+        }
+        let mut found = false;
+        for file in files {
+            let data = match std::fs::read(&file) {
+                Ok(data) => Arc::new(data),
+                Err(_) => continue,
+            };
+            let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+            for index in 0..count {
+                if let Ok(face) = FontFace::parse(data.clone(), index) {
+                    // Files for "Inter" also match "Inter Display".
+                    if face.family.eq_ignore_ascii_case(family) {
+                        self.faces.push(face);
+                        found = true;
+                    }
+                }
+            }
+        }
+        if found {
+            Ok(())
+        } else {
+            Err(format!("no installed font family {:?}", family))
+        }
+    }
+
+    /// Families loaded so far.
+    pub fn families(&self) -> Vec<String> {
+        let mut families: Vec<String> = vec![];
+        for face in &self.faces {
+            if !families.contains(&face.family) {
+                families.push(face.family.clone());
+            }
+        }
+        families
+    }
+
+    /// Families to find characters in which the font being drawn lacks.
+    pub fn fallbacks(&self) -> &[String] {
+        &self.fallbacks
+    }
+
+    pub fn set_fallbacks(&mut self, families: Vec<String>) {
+        self.fallbacks = families;
+    }
+
+    /// Loads whichever of the platform's emoji and wide-coverage fonts are
+    /// installed, and adds them to the fallbacks.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_system_fallbacks(&mut self) {
+        self.system_fallbacks = true;
+        let families: &[&str] = if cfg!(target_os = "macos") {
+            &[
+                "Apple Color Emoji",
+                "Apple Symbols",
+                "PingFang SC",
+                "Hiragino Sans",
+                "Apple SD Gothic Neo",
+                "Arial Unicode MS",
+            ]
+        } else if cfg!(target_os = "windows") {
+            &[
+                "Segoe UI Emoji",
+                "Segoe UI Symbol",
+                "Microsoft YaHei",
+                "Yu Gothic",
+                "Malgun Gothic",
+                "Nirmala UI",
+            ]
+        } else {
+            &[
+                "Noto Color Emoji",
+                "Twemoji",
+                "Noto Sans CJK SC",
+                "Noto Sans",
+                "DejaVu Sans",
+            ]
+        };
+        for family in families {
+            let loaded = self
+                .faces
+                .iter()
+                .any(|face| face.family.eq_ignore_ascii_case(family));
+            let listed = self
+                .fallbacks
+                .iter()
+                .any(|f| f.eq_ignore_ascii_case(family));
+            if !listed && (loaded || self.load_system(family).is_ok()) {
+                self.fallbacks.push(family.to_string());
+            }
+        }
+    }
+
+    /// Splits `text` into runs by the face each character is drawn from:
+    /// `font`'s if it has the character, or else the first fallback which
+    /// does. The first text needing a fallback loads the system's.
+    pub(crate) fn runs(&mut self, text: &str, font: &Font) -> Vec<FontRun> {
+        let covered = |fonts: &Fonts, c: char| match fonts.face(font) {
+            Some(face) => face.has_glyph(c),
+            None => vger_covers(c),
+        };
+        if !self.system_fallbacks && text.chars().any(|c| !is_ignorable(c) && !covered(self, c)) {
+            self.system_fallbacks = true;
+            #[cfg(not(target_arch = "wasm32"))]
+            self.load_system_fallbacks();
+        }
+
+        let primary = self.face(font);
+        let fallbacks: Vec<&FontFace> = self
+            .fallbacks
+            .iter()
+            .filter_map(|family| {
+                self.face(&Font {
+                    family: Some(family.clone()),
+                    size: None,
+                    weight: font.weight,
+                })
+            })
+            .collect();
+
+        let mut runs: Vec<FontRun> = vec![];
+        for c in text.chars().filter(|&c| !is_ignorable(c)) {
+            // Spaces go with what's before them.
+            if let Some(run) = runs.last_mut().filter(|_| c.is_whitespace()) {
+                run.text.push(c);
+                continue;
+            }
+            let face = if covered(self, c) {
+                primary
+            } else {
+                fallbacks
+                    .iter()
+                    .copied()
+                    .find(|face| face.can_draw(c))
+                    .or(primary)
+            };
+            match runs.last_mut() {
+                Some(run) if same_face(run.face.as_ref(), face) => run.text.push(c),
+                _ => runs.push(FontRun {
+                    text: c.to_string(),
+                    face: face.cloned(),
+                }),
+            }
+        }
+        runs
+    }
+
+    /// The loaded face closest to `font`: of its family, preferring its
+    /// weight, then upright faces.
+    pub fn face(&self, font: &Font) -> Option<&FontFace> {
+        let family = font.family.as_deref()?;
+        self.faces
+            .iter()
+            .filter(|face| face.family.eq_ignore_ascii_case(family))
+            .min_by_key(|face| (face.weight != font.weight, face.italic))
+    }
+}
+
+fn same_face(a: Option<&FontFace>, b: Option<&FontFace>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.same_face(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn system_font_dirs() -> Vec<std::path::PathBuf> {
+    let mut dirs: Vec<std::path::PathBuf> = if cfg!(target_os = "macos") {
+        vec!["/System/Library/Fonts".into(), "/Library/Fonts".into()]
+    } else if cfg!(target_os = "windows") {
+        let windir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".into());
+        vec![std::path::Path::new(&windir).join("Fonts")]
+    } else {
+        vec!["/usr/share/fonts".into(), "/usr/local/share/fonts".into()]
+    };
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    if let Some(home) = home.map(std::path::PathBuf::from) {
+        if cfg!(target_os = "macos") {
+            dirs.push(home.join("Library/Fonts"));
+        } else if cfg!(target_os = "windows") {
+            dirs.push(home.join("AppData\\Local\\Microsoft\\Windows\\Fonts"));
+        } else {
+            dirs.push(home.join(".local/share/fonts"));
+            dirs.push(home.join(".fonts"));
+        }
+    }
+    dirs
+}
+
+/// Does a font file's name look like it's of `family`? Spaces, dashes and
+/// case are ignored, so "Fira Code" matches `FiraCode-Bold.ttf`, and
+/// "PingFang SC" matches `PingFang.ttc`.
+fn font_file_matches(file_name: &str, family: &str) -> bool {
+    let squash = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect::<String>()
+    };
+    let (stem, ext) = match file_name.rsplit_once('.') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    ["ttf", "otf", "ttc"].contains(&ext.to_lowercase().as_str())
+        && (squash(stem).starts_with(&squash(family)) || squash(family).starts_with(&squash(stem)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn find_font_files(
+    dir: &std::path::Path,
+    family: &str,
+    depth: usize,
+    files: &mut Vec<std::path::PathBuf>,
+) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth > 0 {
+                find_font_files(&path, family, depth - 1, files);
+            }
+        } else if font_file_matches(&entry.file_name().to_string_lossy(), family) {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_font() {
+        let font = Font::named("Inter").size(14).weight(FontWeight::Bold);
+        assert_eq!(font.family.as_deref(), Some("Inter"));
+        assert_eq!(font.size, Some(14));
+        assert_eq!(font.weight, FontWeight::Bold);
+        assert_eq!(Font::default().family, None);
+    }
+
+    #[test]
+    fn test_font_file_matches() {
+        assert!(font_file_matches("Inter-Bold.otf", "Inter"));
+        assert!(font_file_matches("FiraCode-Regular.TTF", "Fira Code"));
+        assert!(!font_file_matches("Inter.woff2", "Inter"));
+        assert!(!font_file_matches("Roboto.ttf", "Inter"));
+        assert!(font_file_matches("PingFang.ttc", "PingFang SC"));
+    }
+
+    #[test]
+    fn test_runs_without_fallbacks() {
+        let mut fonts = Fonts {
+            system_fallbacks: true,
+            ..Default::default()
+        };
+        let runs = fonts.runs("caf\u{e9} \u{2764}\u{fe0f}", &Font::default());
+        // Nothing to fall back to, so it's all vger's, less the selector.
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].text, "caf\u{e9} \u{2764}");
+        assert!(runs[0].face.is_none());
+    }
+
+    #[test]
+    fn test_bad_font() {
+        let mut fonts = Fonts::default();
+        assert!(fonts.load(b"not a font".to_vec()).is_err());
+        assert!(fonts.families().is_empty());
+        assert!(fonts.face(&Font::named("Inter")).is_none());
+    }
+}
@@ -0,0 +1,207 @@
+use crate::*;
+use std::time::Duration;
+
+/// How far a touch can wander and still be a tap or a long press, in points.
+pub const TAP_SLOP: f32 = 8.0;
+
+/// Longest gap between taps that counts them together, e.g. as a
+/// double tap.
+pub const MULTI_TAP_INTERVAL: Duration = Duration::from_millis(400);
+
+/// How long a touch is held for `on_long_press` unless another duration
+/// is given. Touches held this long aren't taps.
+pub const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// A touch which stops moving this long before lifting has no velocity.
+const VELOCITY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// A touch, as tracked for gestures. Positions are relative to the root
+/// view, so only differences between them mean anything to views.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TrackedTouch {
+    pub down: bool,
+    pub start: Instant,
+    pub origin: LocalPoint,
+    pub position: LocalPoint,
+    /// Has it moved further than `TAP_SLOP` from where it started?
+    pub moved: bool,
+    /// In points per second.
+    pub velocity: LocalOffset,
+    last_move: Instant,
+}
+
+/// Recognizes taps and tracks touches for the gesture modifiers, before
+/// views see the events.
+#[derive(Default)]
+pub(crate) struct Gestures {
+    touches: [Option<TrackedTouch>; 16],
+    /// When and where the last tap was, and how many came in a row.
+    last_tap: Option<(Instant, LocalPoint, usize)>,
+    tap_count: usize,
+    /// Which touchscreen touch each id is given to. Id zero is always the
+    /// mouse.
+    touch_ids: [Option<u64>; 16],
+}
+
+impl Gestures {
+    pub fn track(&mut self, event: &Event) {
+        let now = Instant::now();
+        match event {
+            Event::TouchBegin { id, position } => {
+                self.touches[*id] = Some(TrackedTouch {
+                    down: true,
+                    start: now,
+                    origin: *position,
+                    position: *position,
+                    moved: false,
+                    velocity: LocalOffset::zero(),
+                    last_move: now,
+                });
+            }
+            Event::TouchMove { id, position, .. } => {
+                if let Some(touch) = &mut self.touches[*id] {
+                    let dt = now.duration_since(touch.last_move).as_secs_f32();
+                    if dt > 0.0 {
+                        // Smooth out jitter between events.
+                        let velocity = (*position - touch.position) / dt;
+                        touch.velocity = touch.velocity.lerp(velocity, 0.5);
+                    }
+                    touch.position = *position;
+                    touch.last_move = now;
+                    touch.moved |= (*position - touch.origin).length() > TAP_SLOP;
+                }
+            }
+            Event::TouchEnd { id, position } => {
+                self.tap_count = 0;
+                if let Some(touch) = &mut self.touches[*id] {
+                    touch.down = false;
+                    touch.position = *position;
+                    touch.moved |= (*position - touch.origin).length() > TAP_SLOP;
+                    if now.duration_since(touch.last_move) > VELOCITY_TIMEOUT {
+                        touch.velocity = LocalOffset::zero();
+                    }
+
+                    let held = now.duration_since(touch.start);
+                    if !touch.moved && held < LONG_PRESS_DURATION {
+                        let count = match self.last_tap {
+                            Some((time, at, count))
+                                if now.duration_since(time) < MULTI_TAP_INTERVAL
+                                    && (*position - at).length() < TAP_SLOP =>
+                            {
+                                count + 1
+                            }
+                            _ => 1,
+                        };
+                        self.last_tap = Some((now, *position, count));
+                        self.tap_count = count;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub fn touch(&self, id: usize) -> Option<&TrackedTouch> {
+        self.touches.get(id)?.as_ref()
+    }
+
+    /// The id views see for a touch the platform calls `device_id`,
+    /// giving it a free one if it's beginning.
+    pub fn touch_id(&mut self, device_id: u64, begin: bool) -> Option<usize> {
+        if let Some(id) = self.touch_ids.iter().position(|t| *t == Some(device_id)) {
+            return Some(id);
+        }
+        if !begin {
+            return None;
+        }
+        let id = (1..self.touch_ids.len()).find(|id| self.touch_ids[*id].is_none())?;
+        self.touch_ids[id] = Some(device_id);
+        Some(id)
+    }
+
+    /// Frees up the id of a touch which has ended.
+    pub fn release_touch_id(&mut self, id: usize) {
+        self.touch_ids[id] = None;
+    }
+}
+
+impl Context {
+    /// Taps in a row, counting the touch that just ended, or zero if it
+    /// wasn't a tap. Only meaningful while handling `Event::TouchEnd`.
+    pub fn tap_count(&self) -> usize {
+        self.gestures.tap_count
+    }
+
+    /// Is touch `id` held and moved further than `TAP_SLOP`, so it's
+    /// dragging something, selecting text or the like?
+    pub(crate) fn is_dragging(&self, id: usize) -> bool {
+        self.gestures.touch(id).map_or(false, |t| t.down && t.moved)
+    }
+
+    /// Ids and positions, relative to the root view, of the touches which
+    /// are down. Includes the mouse, as id zero, while a button is held.
+    pub fn active_touches(&self) -> Vec<(usize, LocalPoint)> {
+        self.gestures
+            .touches
+            .iter()
+            .enumerate()
+            .filter_map(|(id, t)| t.filter(|t| t.down).map(|t| (id, t.position)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_tap_count() {
+        let mut gestures = Gestures::default();
+        let tap = |gestures: &mut Gestures, x: f32| {
+            let position = [x, 0.0].into();
+            gestures.track(&Event::TouchBegin { id: 0, position });
+            gestures.track(&Event::TouchEnd { id: 0, position });
+            gestures.tap_count
+        };
+        assert_eq!(tap(&mut gestures, 0.0), 1);
+        assert_eq!(tap(&mut gestures, 1.0), 2);
+        // Too far away to count with the others.
+        assert_eq!(tap(&mut gestures, 100.0), 1);
+
+        // Dragging isn't tapping.
+        gestures.track(&Event::TouchBegin {
+            id: 0,
+            position: [0.0, 0.0].into(),
+        });
+        gestures.track(&Event::TouchMove {
+            id: 0,
+            position: [50.0, 0.0].into(),
+            delta: [50.0, 0.0].into(),
+        });
+        gestures.track(&Event::TouchEnd {
+            id: 0,
+            position: [50.0, 0.0].into(),
+        });
+        assert_eq!(gestures.tap_count, 0);
+        assert!(gestures.touch(0).unwrap().moved);
+    }
+
+    #[test]
+    fn test_touch_ids() {
+        let mut gestures = Gestures::default();
+        assert_eq!(gestures.touch_id(42, true), Some(1));
+        assert_eq!(gestures.touch_id(7, true), Some(2));
+        assert_eq!(gestures.touch_id(42, false), Some(1));
+        assert_eq!(gestures.touch_id(99, false), None);
+
+        gestures.release_touch_id(1);
+        assert_eq!(gestures.touch_id(8, true), Some(1));
+
+        for device_id in 100..113 {
+            assert!(gestures.touch_id(device_id, true).is_some());
+        }
+        // Out of ids.
+        assert_eq!(gestures.touch_id(200, true), None);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::*;
+use global_hotkey::{
+    hotkey::{Code, HotKey as GlobalHotKey, Modifiers},
+    GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Keeps the OS registrations in sync with `Context::register_global_hotkey`.
+///
+/// Must live on the main thread, alongside the event loop.
+#[derive(Default)]
+pub(crate) struct GlobalHotKeys {
+    manager: Option<GlobalHotKeyManager>,
+    registered: Arc<Mutex<HashMap<u32, KeyChord>>>,
+}
+
+impl GlobalHotKeys {
+    pub fn update(&mut self, cx: &mut Context) {
+        if !cx.global_hotkeys_changed {
+            return;
+        }
+        cx.global_hotkeys_changed = false;
+
+        let chords = cx.global_hotkey_chords();
+
+        if self.manager.is_none() {
+            if chords.is_empty() {
+                return;
+            }
+            match GlobalHotKeyManager::new() {
+                Ok(manager) => self.manager = Some(manager),
+                Err(err) => {
+                    println!("error creating global hotkey manager: {:?}", err);
+                    return;
+                }
+            }
+
+            // Events arrive on the main thread on macOS and Windows, but
+            // on a background thread on Linux, so go through on_main.
+            let registered = self.registered.clone();
+            GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+                if event.state == HotKeyState::Pressed {
+                    let keys = registered.lock().unwrap().get(&event.id).copied();
+                    if let Some(keys) = keys {
+                        on_main(move |cx| cx.fire_global_hotkey(keys));
+                    }
+                }
+            }));
+        }
+
+        let manager = self.manager.as_ref().unwrap();
+        let mut registered = self.registered.lock().unwrap();
+
+        registered.retain(|_, keys| {
+            if chords.contains(keys) {
+                true
+            } else {
+                if let Err(err) = manager.unregister(global_hotkey(*keys)) {
+                    println!("error unregistering global hotkey: {:?}", err);
+                }
+                false
+            }
+        });
+
+        for keys in chords {
+            let hotkey = global_hotkey(keys);
+            if registered.contains_key(&hotkey.id()) {
+                continue;
+            }
+            match manager.register(hotkey) {
+                Ok(()) => {
+                    registered.insert(hotkey.id(), keys);
+                }
+                Err(err) => println!("error registering global hotkey {:?}: {:?}", keys, err),
+            }
+        }
+    }
+}
+
+fn global_hotkey(keys: KeyChord) -> GlobalHotKey {
+    let mut mods = Modifiers::empty();
+    if keys.mods.shift {
+        mods |= Modifiers::SHIFT;
+    }
+    if keys.mods.control {
+        mods |= Modifiers::CONTROL;
+    }
+    if keys.mods.alt {
+        mods |= Modifiers::ALT;
+    }
+    if keys.mods.command {
+        mods |= Modifiers::SUPER;
+    }
+
+    let code = match keys.key {
+        HotKey::KeyA => Code::KeyA,
+        HotKey::KeyB => Code::KeyB,
+        HotKey::KeyC => Code::KeyC,
+        HotKey::KeyD => Code::KeyD,
+        HotKey::KeyE => Code::KeyE,
+        HotKey::KeyF => Code::KeyF,
+        HotKey::KeyG => Code::KeyG,
+        HotKey::KeyH => Code::KeyH,
+        HotKey::KeyI => Code::KeyI,
+        HotKey::KeyJ => Code::KeyJ,
+        HotKey::KeyK => Code::KeyK,
+        HotKey::KeyL => Code::KeyL,
+        HotKey::KeyM => Code::KeyM,
+        HotKey::KeyN => Code::KeyN,
+        HotKey::KeyO => Code::KeyO,
+        HotKey::KeyP => Code::KeyP,
+        HotKey::KeyQ => Code::KeyQ,
+        HotKey::KeyR => Code::KeyR,
+        HotKey::KeyS => Code::KeyS,
+        HotKey::KeyT => Code::KeyT,
+        HotKey::KeyU => Code::KeyU,
+        HotKey::KeyV => Code::KeyV,
+        HotKey::KeyW => Code::KeyW,
+        HotKey::KeyX => Code::KeyX,
+        HotKey::KeyY => Code::KeyY,
+        HotKey::KeyZ => Code::KeyZ,
+        HotKey::Comma => Code::Comma,
+    };
+
+    GlobalHotKey::new(Some(mods), code)
+}
@@ -0,0 +1,582 @@
+use crate::*;
+use euclid::Size2D;
+use std::collections::HashMap;
+use std::sync::Arc;
+use wgpu::util::DeviceExt;
+
+/// Frames an image can go undrawn before its texture is freed.
+const EVICT_AFTER_FRAMES: usize = 600;
+
+const SHADER: &str = r#"
+struct Uniforms {
+    size: vec2<f32>,
+    pad: vec2<f32>,
+};
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(1) @binding(0) var image: texture_2d<f32>;
+@group(1) @binding(1) var image_sampler: sampler;
+
+struct Instance {
+    @location(0) rect: vec4<f32>,
+    @location(1) uv: vec4<f32>,
+    @location(2) radius: f32,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) local: vec2<f32>,
+    @location(2) half_size: vec2<f32>,
+    @location(3) radius: f32,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32, instance: Instance) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+    );
+    let c = corners[i];
+    let p = instance.rect.xy + c * instance.rect.zw;
+    var out: VertexOutput;
+    out.position = vec4<f32>(p / uniforms.size * 2.0 - 1.0, 0.0, 1.0);
+    // Image rows go down from the top.
+    out.uv = instance.uv.xy + vec2<f32>(c.x, 1.0 - c.y) * instance.uv.zw;
+    out.local = (c - 0.5) * instance.rect.zw;
+    out.half_size = instance.rect.zw * 0.5;
+    out.radius = instance.radius;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(image, image_sampler, in.uv);
+    let q = abs(in.local) - in.half_size + in.radius;
+    let d = length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - in.radius;
+    return vec4<f32>(color.rgb, color.a * clamp(0.5 - d, 0.0, 1.0));
+}
+"#;
+
+/// An image drawn this frame, in the points of the window or the layer
+/// it's in.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ImageDraw {
+    key: u64,
+    rect: WorldRect,
+    /// The part of the image shown, from 0 to 1, from the top left.
+    uv: LocalRect,
+    radius: f32,
+    clip: Option<WorldRect>,
+    /// Whether `key` is a `wgpu_view`'s texture rather than an image.
+    surface: bool,
+    /// The effect layer it's drawn in, if any.
+    pub(crate) layer: Option<usize>,
+}
+
+/// Per-instance vertex data, matching `Instance` in the shader.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Instance {
+    rect: [f32; 4],
+    uv: [f32; 4],
+    radius: f32,
+}
+
+impl Instance {
+    fn bytes(instances: &[Instance]) -> Vec<u8> {
+        instances
+            .iter()
+            .flat_map(|i| {
+                i.rect
+                    .iter()
+                    .chain(i.uv.iter())
+                    .chain(std::iter::once(&i.radius))
+                    .flat_map(|f| f.to_ne_bytes())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+struct Texture {
+    bind_group: wgpu::BindGroup,
+}
+
+struct CachedImage {
+    image: Arc<RgbaImage>,
+    texture: Option<Texture>,
+    last_drawn: usize,
+}
+
+/// A texture a `wgpu_view` renders into, in pixels.
+struct Surface {
+    view: wgpu::TextureView,
+    bind_group: wgpu::BindGroup,
+    width: u32,
+    height: u32,
+    format: wgpu::TextureFormat,
+    last_drawn: usize,
+}
+
+struct Pipeline {
+    pipeline: wgpu::RenderPipeline,
+    uniforms: wgpu::Buffer,
+    uniform_bind_group: wgpu::BindGroup,
+    texture_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    format: wgpu::TextureFormat,
+}
+
+impl Pipeline {
+    fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("rui images"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let uniform_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("rui image uniforms"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("rui image texture"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("rui images"),
+            bind_group_layouts: &[&uniform_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("rui images"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<Instance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x4, 1 => Float32x4, 2 => Float32
+                    ],
+                }],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("rui image uniforms"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("rui image uniforms"),
+            layout: &uniform_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniforms.as_entire_binding(),
+            }],
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("rui images"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            pipeline,
+            uniforms,
+            uniform_bind_group,
+            texture_layout,
+            sampler,
+            format,
+        }
+    }
+
+    fn upload(&self, device: &wgpu::Device, queue: &wgpu::Queue, image: &RgbaImage) -> Texture {
+        let size = wgpu::Extent3d {
+            width: image.width.max(1) as u32,
+            height: image.height.max(1) as u32,
+            depth_or_array_layers: 1,
+        };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("rui image"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        if image.width > 0 && image.height > 0 {
+            queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &image.rgba,
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * size.width),
+                    rows_per_image: Some(size.height),
+                },
+                size,
+            );
+        }
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("rui image"),
+            layout: &self.texture_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+        Texture { bind_group }
+    }
+
+    fn surface(&self, device: &wgpu::Device, width: u32, height: u32, frame: usize) -> Surface {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("rui wgpu_view"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("rui wgpu_view"),
+            layout: &self.texture_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+        Surface {
+            view,
+            bind_group,
+            width,
+            height,
+            format: self.format,
+            last_drawn: frame,
+        }
+    }
+}
+
+/// The pixels of `rect`, in the points of a target `window_size` points
+/// and `pixels` across, clipped to `clip` and the target, as x, y, width
+/// and height down from the top left, like scissor rects take.
+pub(crate) fn pixel_rect(
+    rect: WorldRect,
+    clip: Option<WorldRect>,
+    window_size: Size2D<f32, WorldSpace>,
+    scale: f32,
+    pixels: (u32, u32),
+) -> Option<(u32, u32, u32, u32)> {
+    let window = WorldRect::new(WorldPoint::zero(), window_size);
+    let rect = rect
+        .intersection(&clip.unwrap_or(window))?
+        .intersection(&window)?;
+    let x = (rect.min_x() * scale).floor();
+    let y = ((window_size.height - rect.max_y()) * scale).floor();
+    let w = (rect.max_x() * scale).ceil() - x;
+    let h = ((window_size.height - rect.min_y()) * scale).ceil() - y;
+    let (max_w, max_h) = (pixels.0 as f32, pixels.1 as f32);
+    let (x, y) = (x.clamp(0.0, max_w), y.clamp(0.0, max_h));
+    let (w, h) = (w.min(max_w - x), h.min(max_h - y));
+    if w < 1.0 || h < 1.0 {
+        return None;
+    }
+    Some((x as u32, y as u32, w as u32, h as u32))
+}
+
+/// Decoded images and their textures, kept by the `Context` across
+/// frames. vger can't draw textures, so images are drawn in a pass of
+/// their own after the rest of the window. `wgpu_view`s render into
+/// textures which are drawn in the same pass.
+#[derive(Default)]
+pub(crate) struct ImageCache {
+    images: HashMap<u64, CachedImage>,
+    surfaces: HashMap<u64, Surface>,
+    draws: Vec<ImageDraw>,
+    /// The `wgpu_view`s drawn this frame, to render before the pass.
+    renders: Vec<(u64, WorldRect, RenderFn)>,
+    pipeline: Option<Pipeline>,
+    frame: usize,
+}
+
+impl ImageCache {
+    /// The image cached under `key`, if any.
+    pub(crate) fn get(&self, key: u64) -> Option<Arc<RgbaImage>> {
+        self.images.get(&key).map(|c| c.image.clone())
+    }
+
+    /// Keeps `image` under `key`, to be uploaded when it's first drawn.
+    pub(crate) fn insert(&mut self, key: u64, image: Arc<RgbaImage>) {
+        self.images.insert(
+            key,
+            CachedImage {
+                image,
+                texture: None,
+                last_drawn: self.frame,
+            },
+        );
+    }
+
+    /// Draws the image under `key` in `rect`, in the points of the window
+    /// or `layer`, showing the `uv` part of it, and clipped to `clip`.
+    pub(crate) fn draw(
+        &mut self,
+        key: u64,
+        rect: WorldRect,
+        uv: LocalRect,
+        radius: f32,
+        clip: Option<WorldRect>,
+        layer: Option<usize>,
+    ) {
+        if let Some(cached) = self.images.get_mut(&key) {
+            cached.last_drawn = self.frame;
+            self.draws.push(ImageDraw {
+                key,
+                rect,
+                uv,
+                radius,
+                clip,
+                surface: false,
+                layer,
+            });
+        }
+    }
+
+    /// Draws the texture of the `wgpu_view` under `key` in `rect`, in the
+    /// points of the window or `layer`, clipped to `clip`, after `render`
+    /// has drawn into it.
+    pub(crate) fn draw_surface(
+        &mut self,
+        key: u64,
+        rect: WorldRect,
+        clip: Option<WorldRect>,
+        layer: Option<usize>,
+        render: RenderFn,
+    ) {
+        self.renders.push((key, rect, render));
+        self.draws.push(ImageDraw {
+            key,
+            rect,
+            uv: LocalRect::new(LocalPoint::zero(), [1.0, 1.0].into()),
+            radius: 0.0,
+            clip,
+            surface: true,
+            layer,
+        });
+    }
+
+    /// Uploads this frame's images and renders its `wgpu_view`s, then
+    /// frees textures which haven't been drawn in a while. Returns what's
+    /// to be drawn, for `render`.
+    pub(crate) fn prepare(&mut self, render_info: &RenderInfo, scale: f32) -> Vec<ImageDraw> {
+        self.frame += 1;
+        let frame = self.frame;
+        self.images
+            .retain(|_, c| frame - c.last_drawn <= EVICT_AFTER_FRAMES);
+        self.surfaces
+            .retain(|_, s| frame - s.last_drawn <= EVICT_AFTER_FRAMES);
+        let draws = std::mem::take(&mut self.draws);
+        let renders = std::mem::take(&mut self.renders);
+        if draws.is_empty() {
+            return draws;
+        }
+
+        let (device, queue) = (render_info.device, render_info.queue);
+        let format = render_info.config.format;
+        if self.pipeline.as_ref().map(|p| p.format) != Some(format) {
+            self.pipeline = Some(Pipeline::new(device, format));
+            for cached in self.images.values_mut() {
+                cached.texture = None;
+            }
+            self.surfaces.clear();
+        }
+        let pipeline = self.pipeline.as_ref().unwrap();
+        for draw in &draws {
+            if let Some(cached) = self.images.get_mut(&draw.key) {
+                if cached.texture.is_none() {
+                    cached.texture = Some(pipeline.upload(device, queue, &cached.image));
+                }
+            }
+        }
+        for (key, rect, render) in renders {
+            let width = ((rect.width() * scale).round() as u32).max(1);
+            let height = ((rect.height() * scale).round() as u32).max(1);
+            let stale = self.surfaces.get(&key).map_or(true, |s| {
+                (s.width, s.height, s.format) != (width, height, format)
+            });
+            if stale {
+                self.surfaces
+                    .insert(key, pipeline.surface(device, width, height, frame));
+            }
+            let surface = self.surfaces.get_mut(&key).unwrap();
+            surface.last_drawn = frame;
+            render(
+                device,
+                queue,
+                &surface.view,
+                &WgpuViewport {
+                    rect,
+                    width,
+                    height,
+                    format,
+                    scale,
+                },
+            );
+        }
+        draws
+    }
+
+    /// Draws `draws`, from `prepare`, over what's in `target`, which is
+    /// `size` points and `pixels` across.
+    pub(crate) fn render(
+        &self,
+        render_info: &RenderInfo,
+        draws: &[ImageDraw],
+        target: &wgpu::TextureView,
+        size: Size2D<f32, WorldSpace>,
+        scale: f32,
+        pixels: (u32, u32),
+    ) {
+        let pipeline = match &self.pipeline {
+            Some(pipeline) if !draws.is_empty() => pipeline,
+            _ => return,
+        };
+        let (device, queue) = (render_info.device, render_info.queue);
+        let size_uniform = [size.width, size.height, 0.0, 0.0];
+        queue.write_buffer(
+            &pipeline.uniforms,
+            0,
+            &size_uniform
+                .iter()
+                .flat_map(|f| f.to_ne_bytes())
+                .collect::<Vec<_>>(),
+        );
+        let instances: Vec<Instance> = draws
+            .iter()
+            .map(|d| Instance {
+                rect: [
+                    d.rect.min_x(),
+                    d.rect.min_y(),
+                    d.rect.width(),
+                    d.rect.height(),
+                ],
+                uv: [d.uv.min_x(), d.uv.min_y(), d.uv.width(), d.uv.height()],
+                radius: d.radius.min(d.rect.width().min(d.rect.height()) / 2.0),
+            })
+            .collect();
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("rui image instances"),
+            contents: &Instance::bytes(&instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("rui images"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("rui images"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                ..<_>::default()
+            });
+            pass.set_pipeline(&pipeline.pipeline);
+            pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
+            pass.set_vertex_buffer(0, buffer.slice(..));
+            for (i, draw) in draws.iter().enumerate() {
+                let bind_group = if draw.surface {
+                    self.surfaces.get(&draw.key).map(|s| &s.bind_group)
+                } else {
+                    self.images
+                        .get(&draw.key)
+                        .and_then(|c| c.texture.as_ref())
+                        .map(|t| &t.bind_group)
+                };
+                let bind_group = match bind_group {
+                    Some(bind_group) => bind_group,
+                    None => continue,
+                };
+                let (x, y, w, h) = match pixel_rect(draw.rect, draw.clip, size, scale, pixels) {
+                    Some(rect) => rect,
+                    None => continue,
+                };
+                pass.set_scissor_rect(x, y, w, h);
+                pass.set_bind_group(1, bind_group, &[]);
+                pass.draw(0..6, i as u32..i as u32 + 1);
+            }
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
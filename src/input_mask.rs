@@ -0,0 +1,166 @@
+/// One position in an `InputMask`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MaskItem {
+    /// `#`: a digit.
+    Digit,
+
+    /// `A`: a letter.
+    Letter,
+
+    /// `*`: a letter or digit.
+    Alphanumeric,
+
+    /// Anything else is shown as-is. Use `\` to escape `#`, `A` and `*`.
+    Literal(char),
+}
+
+/// Input pattern for `text_field`, such as `"(###) ###-####"` for phone numbers.
+///
+/// `#` accepts a digit, `A` a letter and `*` either. Other characters are
+/// literals inserted automatically. The bound value only holds what
+/// the user typed, without the literals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputMask {
+    items: Vec<MaskItem>,
+}
+
+impl InputMask {
+    pub fn new(mask: &str) -> Self {
+        let mut items = vec![];
+        let mut chars = mask.chars();
+        while let Some(c) = chars.next() {
+            items.push(match c {
+                '#' => MaskItem::Digit,
+                'A' => MaskItem::Letter,
+                '*' => MaskItem::Alphanumeric,
+                '\\' => MaskItem::Literal(chars.next().unwrap_or('\\')),
+                c => MaskItem::Literal(c),
+            })
+        }
+        Self { items }
+    }
+
+    fn slots(&self) -> impl Iterator<Item = &MaskItem> {
+        self.items
+            .iter()
+            .filter(|item| !matches!(item, MaskItem::Literal(_)))
+    }
+
+    /// How many characters the user can enter.
+    pub fn capacity(&self) -> usize {
+        self.slots().count()
+    }
+
+    /// Can `c` be entered as the `index`th character of the raw value?
+    pub fn accepts(&self, index: usize, c: char) -> bool {
+        match self.slots().nth(index) {
+            Some(MaskItem::Digit) => c.is_ascii_digit(),
+            Some(MaskItem::Letter) => c.is_alphabetic(),
+            Some(MaskItem::Alphanumeric) => c.is_alphanumeric(),
+            _ => false,
+        }
+    }
+
+    /// Removes characters which don't fit the mask.
+    pub fn filter(&self, raw: &str) -> String {
+        let mut result = String::new();
+        for c in raw.chars() {
+            if self.accepts(result.chars().count(), c) {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    /// Inserts literals into the raw value for display. Literals are
+    /// shown up to the last character entered.
+    pub fn format(&self, raw: &str) -> String {
+        let mut result = String::new();
+        let mut pending = String::new();
+        let mut chars = raw.chars();
+        for item in &self.items {
+            match item {
+                MaskItem::Literal(c) => pending.push(*c),
+                _ => match chars.next() {
+                    Some(c) => {
+                        result.push_str(&pending);
+                        pending.clear();
+                        result.push(c);
+                    }
+                    None => break,
+                },
+            }
+        }
+        result
+    }
+
+    /// The mask with blanks for input, such as `"(___) ___-____"`.
+    pub fn template(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| match item {
+                MaskItem::Literal(c) => *c,
+                _ => '_',
+            })
+            .collect()
+    }
+
+    /// Where a cursor after `raw_index` raw characters goes in the formatted string.
+    pub fn display_index(&self, raw_index: usize) -> usize {
+        if raw_index == 0 {
+            return 0;
+        }
+        let mut slots = 0;
+        for (i, item) in self.items.iter().enumerate() {
+            if !matches!(item, MaskItem::Literal(_)) {
+                slots += 1;
+                if slots == raw_index {
+                    return i + 1;
+                }
+            }
+        }
+        self.items.len()
+    }
+}
+
+impl From<&str> for InputMask {
+    fn from(mask: &str) -> Self {
+        InputMask::new(mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_phone_mask() {
+        let mask = InputMask::new("(###) ###-####");
+        assert_eq!(mask.capacity(), 10);
+        assert!(mask.accepts(0, '5'));
+        assert!(!mask.accepts(0, 'x'));
+        assert!(!mask.accepts(10, '5'));
+
+        assert_eq!(mask.format(""), "");
+        assert_eq!(mask.format("5"), "(5");
+        assert_eq!(mask.format("5551"), "(555) 1");
+        assert_eq!(mask.format("5551234567"), "(555) 123-4567");
+        assert_eq!(mask.template(), "(___) ___-____");
+
+        assert_eq!(mask.display_index(0), 0);
+        assert_eq!(mask.display_index(3), 4);
+        assert_eq!(mask.display_index(4), 7);
+
+        assert_eq!(mask.filter("555-12x3"), "555123");
+    }
+
+    #[test]
+    fn test_escaped_mask() {
+        let mask = InputMask::new("\\#AA-**");
+        assert_eq!(mask.capacity(), 4);
+        assert!(mask.accepts(0, 'q'));
+        assert!(mask.accepts(2, '7'));
+        assert_eq!(mask.format("ab1c"), "#ab-1c");
+    }
+}
@@ -0,0 +1,257 @@
+use crate::*;
+
+/// How an animation speeds up and slows down between keyframes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+impl Easing {
+    /// Maps `t`, from 0 to 1, to how far along the animation is.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+        }
+    }
+}
+
+/// Interpolates between two values.
+pub(crate) fn lerp<T: Animatable>(a: T, b: T, t: f32) -> T {
+    let (a, b) = (a.components(), b.components());
+    let mut c = [0.0; 4];
+    for i in 0..4 {
+        c[i] = a[i] + (b[i] - a[i]) * t;
+    }
+    T::from_components(c)
+}
+
+/// A value to reach at a time, in seconds from the start of the
+/// animation, eased from the keyframe before.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+    pub easing: Easing,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(time: f32, value: T) -> Self {
+        Self {
+            time,
+            value,
+            easing: Easing::default(),
+        }
+    }
+
+    pub fn easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+}
+
+/// A multi-stage animation. Build one with `keyframes`, or with
+/// `Keyframes::new()` and `at`. Play it with `keyframe_animation` or the
+/// `keyframes` modifier.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Keyframes<T> {
+    /// In time order.
+    frames: Vec<Keyframe<T>>,
+    /// Index of the keyframe added last, for `ease`.
+    last_added: Option<usize>,
+    /// Stretches the keyframes to last this long.
+    length: Option<f32>,
+    repeats: bool,
+    autoreverses: bool,
+}
+
+impl<T: Animatable> Default for Keyframes<T> {
+    fn default() -> Self {
+        Self {
+            frames: vec![],
+            last_added: None,
+            length: None,
+            repeats: false,
+            autoreverses: false,
+        }
+    }
+}
+
+impl<T: Animatable> Keyframes<T> {
+    /// No keyframes yet; add them with `at`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a keyframe, reached linearly from the one before. Use `ease`
+    /// to change that.
+    pub fn at(mut self, time: f32, value: T) -> Self {
+        let i = self.frames.partition_point(|f| f.time <= time);
+        self.frames.insert(i, Keyframe::new(time, value));
+        self.last_added = Some(i);
+        self
+    }
+
+    /// Eases into the keyframe added last.
+    pub fn ease(mut self, easing: Easing) -> Self {
+        if let Some(i) = self.last_added {
+            self.frames[i].easing = easing;
+        }
+        self
+    }
+
+    /// Stretches the keyframes to last `seconds`, so their times can be
+    /// fractions from 0 to 1.
+    pub fn duration(self, seconds: f32) -> Self {
+        Self {
+            length: Some(seconds),
+            ..self
+        }
+    }
+
+    /// Plays over and over instead of stopping at the end.
+    pub fn repeating(self) -> Self {
+        Self {
+            repeats: true,
+            ..self
+        }
+    }
+
+    /// Plays backwards after playing forwards.
+    pub fn autoreverse(self) -> Self {
+        Self {
+            autoreverses: true,
+            ..self
+        }
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    /// How long it takes to reach the last keyframe, in seconds.
+    pub fn length(&self) -> f32 {
+        let last = self.frames.last().map_or(0.0, |f| f.time);
+        self.length.unwrap_or(last)
+    }
+
+    /// How long it takes to play through once, and back if it
+    /// autoreverses.
+    pub fn period(&self) -> f32 {
+        if self.autoreverses {
+            2.0 * self.length()
+        } else {
+            self.length()
+        }
+    }
+
+    /// The value `t` seconds into the animation.
+    pub fn value_at(&self, t: f32) -> T {
+        let last = match self.frames.last() {
+            Some(last) => last.time,
+            None => return T::from_components([0.0; 4]),
+        };
+        let (length, period) = (self.length(), self.period());
+        let mut t = if self.repeats && period > 0.0 {
+            t.rem_euclid(period)
+        } else {
+            t.min(period)
+        };
+        if t > length {
+            t = period - t;
+        }
+        // Back to keyframe time.
+        if length > 0.0 {
+            t *= last / length;
+        }
+
+        let i = self.frames.partition_point(|f| f.time <= t);
+        if i == 0 {
+            self.frames[0].value
+        } else if i == self.frames.len() {
+            self.frames[i - 1].value
+        } else {
+            let (a, b) = (&self.frames[i - 1], &self.frames[i]);
+            let u = (t - a.time) / (b.time - a.time);
+            lerp(a.value, b.value, b.easing.apply(u))
+        }
+    }
+}
+
+/// Builds a multi-stage animation from keyframes, in any order. There must
+/// be at least one.
+pub fn keyframes<T: Animatable>(frames: impl IntoIterator<Item = Keyframe<T>>) -> Keyframes<T> {
+    let mut frames: Vec<_> = frames.into_iter().collect();
+    assert!(!frames.is_empty(), "keyframes needs at least one keyframe");
+    frames.sort_by(|a, b| {
+        a.time
+            .partial_cmp(&b.time)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Keyframes {
+        frames,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_keyframes() {
+        let k = keyframes([
+            Keyframe::new(1.0, 10.0_f32).easing(Easing::EaseIn),
+            Keyframe::new(0.0, 0.0),
+            Keyframe::new(2.0, 0.0),
+        ]);
+        assert_eq!(k.length(), 2.0);
+        assert_eq!(k.value_at(-1.0), 0.0);
+        assert_eq!(k.value_at(0.5), 2.5);
+        assert_eq!(k.value_at(1.0), 10.0);
+        assert_eq!(k.value_at(1.5), 5.0);
+        assert_eq!(k.value_at(3.0), 0.0);
+
+        let k = k.repeating();
+        assert_eq!(k.value_at(2.5), 2.5);
+    }
+
+    #[test]
+    fn test_keyframes_builder() {
+        let k = Keyframes::new()
+            .at(0.0, 0.0_f32)
+            .at(1.0, 10.0)
+            .at(0.5, 5.0)
+            .ease(Easing::EaseIn)
+            .duration(4.0)
+            .autoreverse();
+        assert_eq!(k.period(), 8.0);
+        assert_eq!(k.value_at(1.0), 1.25);
+        assert_eq!(k.value_at(2.0), 5.0);
+        assert_eq!(k.value_at(4.0), 10.0);
+        // On the way back.
+        assert_eq!(k.value_at(6.0), 5.0);
+        assert_eq!(k.value_at(9.0), 0.0);
+
+        let k = k.repeating();
+        assert_eq!(k.value_at(10.0), 5.0);
+    }
+}
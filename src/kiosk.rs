@@ -0,0 +1,177 @@
+//! Exclusive fullscreen for `WindowConfig::kiosk`.
+
+use crate::*;
+use winit::{
+    event_loop::EventLoopWindowTarget,
+    monitor::MonitorHandle,
+    window::{CursorGrabMode, Fullscreen, Window},
+};
+
+/// The index in `modes` (with their bit depths) that best matches `wanted`:
+/// the largest, then fastest, then deepest.
+fn choose_video_mode(wanted: Option<VideoMode>, modes: &[(VideoMode, u16)]) -> Option<usize> {
+    modes
+        .iter()
+        .enumerate()
+        .filter(|(_, (mode, _))| match wanted {
+            Some(wanted) => {
+                mode.width == wanted.width
+                    && mode.height == wanted.height
+                    && wanted
+                        .refresh_rate
+                        .map_or(true, |hz| mode.refresh_rate == Some(hz))
+            }
+            None => true,
+        })
+        .max_by_key(|(_, (mode, depth))| {
+            (
+                mode.width as u64 * mode.height as u64,
+                mode.refresh_rate,
+                *depth,
+            )
+        })
+        .map(|(i, _)| i)
+}
+
+fn monitor<T>(target: &EventLoopWindowTarget<T>, index: Option<usize>) -> Option<MonitorHandle> {
+    match index {
+        Some(i) => {
+            let monitor = target.available_monitors().nth(i);
+            if monitor.is_none() {
+                println!("no monitor {}, using the primary monitor", i);
+            }
+            monitor.or_else(|| target.primary_monitor())
+        }
+        None => target.primary_monitor(),
+    }
+    .or_else(|| target.available_monitors().next())
+}
+
+/// Exclusive fullscreen on the configured monitor and video mode, or
+/// borderless fullscreen if there's no such mode.
+pub(crate) fn fullscreen<T>(
+    target: &EventLoopWindowTarget<T>,
+    config: &WindowConfig,
+) -> Fullscreen {
+    let monitor = match monitor(target, config.monitor) {
+        Some(monitor) => monitor,
+        None => return Fullscreen::Borderless(None),
+    };
+
+    let mut modes: Vec<_> = monitor.video_modes().collect();
+    let sizes: Vec<_> = modes
+        .iter()
+        .map(|mode| {
+            let size = mode.size();
+            let hz = (mode.refresh_rate_millihertz() + 500) / 1000;
+            (
+                VideoMode {
+                    width: size.width,
+                    height: size.height,
+                    refresh_rate: Some(hz),
+                },
+                mode.bit_depth(),
+            )
+        })
+        .collect();
+
+    match choose_video_mode(config.video_mode, &sizes) {
+        Some(i) => Fullscreen::Exclusive(modes.swap_remove(i)),
+        None => {
+            println!(
+                "video mode {:?} isn't available, using borderless fullscreen",
+                config.video_mode
+            );
+            Fullscreen::Borderless(Some(monitor))
+        }
+    }
+}
+
+/// Keeps the cursor on the kiosk's monitor and the screen awake.
+pub(crate) fn lock_down(window: &Window) {
+    if let Err(err) = window.set_cursor_grab(CursorGrabMode::Confined) {
+        println!("unable to confine cursor: {}", err);
+    }
+    inhibit_screen_saver();
+}
+
+/// Keeps the display on for the rest of the app's life.
+#[cfg(target_os = "windows")]
+fn inhibit_screen_saver() {
+    const ES_CONTINUOUS: u32 = 0x8000_0000;
+    const ES_SYSTEM_REQUIRED: u32 = 0x0000_0001;
+    const ES_DISPLAY_REQUIRED: u32 = 0x0000_0002;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetThreadExecutionState(flags: u32) -> u32;
+    }
+
+    // This lasts as long as the thread, which is the main thread.
+    if unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED) }
+        == 0
+    {
+        println!("unable to inhibit screen saver");
+    }
+}
+
+/// Keeps the display on for the rest of the app's life.
+#[cfg(target_os = "macos")]
+fn inhibit_screen_saver() {
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    const NS_ACTIVITY_IDLE_SYSTEM_SLEEP_DISABLED: u64 = 1 << 20;
+    const NS_ACTIVITY_IDLE_DISPLAY_SLEEP_DISABLED: u64 = 1 << 40;
+    const NS_UTF8_STRING_ENCODING: usize = 4;
+
+    unsafe {
+        let text = "Kiosk mode";
+        let reason: *mut Object = msg_send![class!(NSString), alloc];
+        let reason: *mut Object = msg_send![reason,
+            initWithBytes: text.as_ptr()
+            length: text.len()
+            encoding: NS_UTF8_STRING_ENCODING];
+        let options =
+            NS_ACTIVITY_IDLE_SYSTEM_SLEEP_DISABLED | NS_ACTIVITY_IDLE_DISPLAY_SLEEP_DISABLED;
+        let info: *mut Object = msg_send![class!(NSProcessInfo), processInfo];
+        let activity: *mut Object =
+            msg_send![info, beginActivityWithOptions: options reason: reason];
+        // The activity ends when it's released, so keep it forever.
+        let _: *mut Object = msg_send![activity, retain];
+    }
+}
+
+/// Linux desktops inhibit the screen saver over D-Bus, which we don't
+/// talk to yet.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn inhibit_screen_saver() {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_choose_video_mode() {
+        let modes = [
+            (VideoMode::new(1280, 720).refresh_rate(60), 32),
+            (VideoMode::new(1920, 1080).refresh_rate(60), 32),
+            (VideoMode::new(1920, 1080).refresh_rate(144), 24),
+            (VideoMode::new(1920, 1080).refresh_rate(144), 32),
+        ];
+        assert_eq!(choose_video_mode(None, &modes), Some(3));
+        assert_eq!(
+            choose_video_mode(Some(VideoMode::new(1920, 1080).refresh_rate(60)), &modes),
+            Some(1)
+        );
+        assert_eq!(
+            choose_video_mode(Some(VideoMode::new(1280, 720)), &modes),
+            Some(0)
+        );
+        assert_eq!(
+            choose_video_mode(Some(VideoMode::new(800, 600)), &modes),
+            None
+        );
+    }
+}
@@ -3,6 +3,10 @@
 pub use vger;
 use vger::color::*;
 pub use vger::{LineMetrics, PaintIndex, Vger};
+pub use wgpu;
+
+/// `std::time::Instant`, except on wasm where that isn't available.
+pub use instant::Instant;
 
 #[cfg(feature = "winit")]
 #[macro_use]
@@ -35,6 +39,21 @@ pub use views::*;
 mod paint;
 pub use paint::*;
 
+mod pixel_snap;
+pub use pixel_snap::*;
+
+mod gestures;
+pub use gestures::*;
+
+mod spring;
+pub use spring::*;
+
+mod keyframes;
+pub use keyframes::*;
+
+mod timers;
+pub use timers::*;
+
 mod modifiers;
 pub use modifiers::*;
 
@@ -47,12 +66,141 @@ pub use align::*;
 mod region;
 pub use region::*;
 
+mod input_mask;
+pub use input_mask::*;
+
+mod text_undo;
+pub use text_undo::*;
+
+mod find;
+pub use find::*;
+
+mod attributed_text;
+pub use attributed_text::*;
+
+mod fonts;
+pub use fonts::*;
+
+mod image_cache;
+pub(crate) use image_cache::*;
+
+mod effects;
+pub use effects::*;
+
+mod editor_language;
+pub use editor_language::*;
+
+mod diff;
+pub(crate) use diff::*;
+
+mod shortcut;
+pub use shortcut::*;
+
+mod pasteboard;
+pub use pasteboard::*;
+
+mod window_config;
+pub use window_config::*;
+
+mod workspace;
+pub use workspace::*;
+
+mod memory;
+pub use memory::*;
+
+mod midi;
+pub use midi::*;
+
+mod timeline;
+pub use timeline::*;
+
+mod theme;
+pub use theme::*;
+
+mod menubar;
+pub use menubar::*;
+
+mod about;
+pub use about::*;
+
+mod anim_debugger;
+pub use anim_debugger::*;
+
+mod open;
+pub use open::*;
+
+mod asset_source;
+pub use asset_source::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod assets;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use assets::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod crash_report;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crash_report::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod document;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use document::*;
+
 #[cfg(feature = "winit")]
 mod winit_event_loop;
 
 #[cfg(feature = "winit")]
 pub use winit_event_loop::*;
 
+#[cfg(all(feature = "global-hotkeys", not(target_arch = "wasm32")))]
+mod global_hotkeys;
+
+#[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+mod single_instance;
+
+#[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+pub use single_instance::*;
+
+#[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+mod process;
+
+#[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+mod kiosk;
+
+#[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+pub use process::*;
+
+#[cfg(all(feature = "winit", not(target_arch = "wasm32")))]
+mod tasks;
+
+#[cfg(all(feature = "tokio", feature = "winit", not(target_arch = "wasm32")))]
+mod tokio_runtime;
+
+#[cfg(all(feature = "file-watch", not(target_arch = "wasm32")))]
+mod file_watch;
+
+#[cfg(all(feature = "file-watch", not(target_arch = "wasm32")))]
+pub use file_watch::*;
+
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+mod subscribe;
+
+#[cfg(all(feature = "websocket", not(target_arch = "wasm32")))]
+pub use subscribe::*;
+
+#[cfg(all(feature = "osc", not(target_arch = "wasm32")))]
+mod osc;
+
+#[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+mod updater;
+
+#[cfg(all(feature = "updater", not(target_arch = "wasm32")))]
+pub use updater::*;
+
 // See https://rust-lang.github.io/api-guidelines/future-proofing.html
 pub(crate) mod private {
     pub trait Sealed {}
@@ -0,0 +1,125 @@
+use crate::*;
+use std::collections::HashSet;
+use std::mem::{size_of, size_of_val};
+
+/// How much memory the UI is using, from `Context::memory_report`.
+///
+/// Byte counts are estimates. State sizes don't include heap data the
+/// state owns, like a `String`'s characters.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryReport {
+    /// Values created by `state` and other stateful views.
+    pub state_entries: usize,
+    pub state_bytes: usize,
+
+    /// Cached view positions and sizes.
+    pub layout_entries: usize,
+    pub layout_bytes: usize,
+
+    /// Ids given to views, which are kept until `trim_caches`.
+    pub view_id_entries: usize,
+    pub view_id_bytes: usize,
+
+    /// Which views depend on which state.
+    pub dependency_entries: usize,
+
+    /// The texture vger renders glyphs from.
+    pub glyph_atlas_bytes: usize,
+
+    /// How much of the glyph atlas was in use at the last render, from 0 to 1.
+    pub glyph_atlas_usage: f32,
+
+    /// Decoded assets from `load_asset`, including failures.
+    pub asset_entries: usize,
+}
+
+impl MemoryReport {
+    pub fn total_bytes(&self) -> usize {
+        self.state_bytes + self.layout_bytes + self.view_id_bytes + self.glyph_atlas_bytes
+    }
+}
+
+fn path_bytes(path: &IdPath) -> usize {
+    size_of::<IdPath>() + path.capacity() * size_of::<u64>()
+}
+
+impl Context {
+    /// Counts state and cache entries and estimates their size, to find
+    /// what's growing in a long-running app.
+    pub fn memory_report(&self) -> MemoryReport {
+        let atlas_size = vger::atlas::Atlas::ATLAS_SIZE as usize;
+        MemoryReport {
+            state_entries: self.state_map.len(),
+            state_bytes: self
+                .state_map
+                .values()
+                .map(|holder| size_of::<(ViewId, StateHolder)>() + size_of_val(&*holder.state))
+                .sum(),
+            layout_entries: self.layout.len(),
+            layout_bytes: self
+                .layout
+                .keys()
+                .map(|path| path_bytes(path) + size_of::<LayoutBox>())
+                .sum(),
+            view_id_entries: self.view_ids.len(),
+            view_id_bytes: self
+                .view_ids
+                .keys()
+                .map(|path| path_bytes(path) + size_of::<ViewId>())
+                .sum(),
+            dependency_entries: self.deps.values().map(|deps| deps.len()).sum(),
+            // One byte per pixel.
+            glyph_atlas_bytes: atlas_size * atlas_size,
+            glyph_atlas_usage: self.glyph_atlas_usage,
+            #[cfg(not(target_arch = "wasm32"))]
+            asset_entries: self.assets.cache.len(),
+            #[cfg(target_arch = "wasm32")]
+            asset_entries: 0,
+        }
+    }
+
+    /// Frees what can be rebuilt: ids of views that keep no state, state
+    /// dependencies, decoded assets, and glyphs, which are drawn again as
+    /// needed. Costs a full layout, some glyph rendering and decoding
+    /// assets again.
+    pub fn trim_caches(&mut self) {
+        let mut keep: HashSet<ViewId> = self.state_map.keys().copied().collect();
+        keep.extend(self.touches.iter().copied());
+        keep.extend(self.focused_id);
+        self.view_ids.retain(|_, id| keep.contains(id));
+        self.view_ids.shrink_to_fit();
+
+        self.deps.clear();
+        self.deps.shrink_to_fit();
+        self.layout.shrink_to_fit();
+        self.state_map.shrink_to_fit();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.assets.cache.clear();
+
+        self.trim_glyphs = true;
+        self.set_dirty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_trim_caches() {
+        let mut cx = Context::new();
+        let stateful = cx.view_id(&vec![0, 1]);
+        cx.init_state(stateful, &|| 0.5f32);
+        cx.view_id(&vec![0, 2]);
+
+        let report = cx.memory_report();
+        assert_eq!(report.state_entries, 1);
+        assert_eq!(report.view_id_entries, 2);
+
+        cx.trim_caches();
+        assert_eq!(cx.memory_report().view_id_entries, 1);
+        assert_eq!(cx.view_id(&vec![0, 1]), stateful);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::*;
+
+/// Whose menu conventions to follow when building a menu bar.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MenuPlatform {
+    MacOS,
+    Windows,
+    Linux,
+}
+
+impl MenuPlatform {
+    /// The platform we're running on.
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            MenuPlatform::MacOS
+        } else if cfg!(target_os = "windows") {
+            MenuPlatform::Windows
+        } else {
+            MenuPlatform::Linux
+        }
+    }
+}
+
+/// What a top-level menu is for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MenuRole {
+    /// The macOS application menu, titled with the app's name.
+    App,
+    File,
+    Edit,
+    View,
+    /// Menus added by the app.
+    Custom,
+    /// The macOS Window menu, which also lists open windows.
+    Window,
+    /// The Help menu. On macOS the system adds a search field to it.
+    Help,
+}
+
+/// What a menu item does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MenuItemRole {
+    /// Sends `Event::Command` with this path.
+    Command(String),
+    /// Opens a submenu.
+    Submenu,
+    Separator,
+    /// The app's About command, moved to where the platform expects it.
+    About(String),
+    /// The app's Preferences (or Settings) command, moved to where the
+    /// platform expects it.
+    Preferences(String),
+    /// The macOS Services submenu, filled in by the system.
+    Services,
+    /// The search field at the top of the macOS Help menu.
+    HelpSearch,
+    Hide,
+    HideOthers,
+    ShowAll,
+    Minimize,
+    Zoom,
+    BringAllToFront,
+    Quit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MenuItem {
+    pub title: String,
+    pub key: Option<HotKey>,
+    pub role: MenuItemRole,
+    pub submenu: Vec<MenuItem>,
+}
+
+impl MenuItem {
+    fn new(title: &str, role: MenuItemRole) -> Self {
+        Self {
+            title: title.into(),
+            key: None,
+            role,
+            submenu: vec![],
+        }
+    }
+
+    fn separator() -> Self {
+        Self::new("", MenuItemRole::Separator)
+    }
+
+    fn hotkey(mut self, key: HotKey) -> Self {
+        self.key = Some(key);
+        self
+    }
+}
+
+/// A top-level menu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Menu {
+    pub title: String,
+    pub role: MenuRole,
+    pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+    fn new(title: &str, role: MenuRole) -> Self {
+        Self {
+            title: title.into(),
+            role,
+            items: vec![],
+        }
+    }
+}
+
+fn menu_role(title: &str) -> MenuRole {
+    match title {
+        "File" => MenuRole::File,
+        "Edit" => MenuRole::Edit,
+        "View" => MenuRole::View,
+        "Window" => MenuRole::Window,
+        "Help" => MenuRole::Help,
+        _ => MenuRole::Custom,
+    }
+}
+
+fn is_about(name: &str) -> bool {
+    name == "About" || name.starts_with("About ")
+}
+
+fn is_preferences(name: &str) -> bool {
+    name.starts_with("Preferences") || name.starts_with("Settings") || name == "Options"
+}
+
+/// Adds a command to `items`, creating submenus along `names`.
+fn insert(items: &mut Vec<MenuItem>, names: &[&str], cmd: &CommandInfo) {
+    match names {
+        [] => (),
+        [name] => items.push(MenuItem {
+            title: (*name).into(),
+            key: cmd.key,
+            role: MenuItemRole::Command(cmd.path.clone()),
+            submenu: vec![],
+        }),
+        [name, rest @ ..] => {
+            let index = match items
+                .iter()
+                .position(|item| item.role == MenuItemRole::Submenu && item.title == *name)
+            {
+                Some(index) => index,
+                None => {
+                    items.push(MenuItem::new(name, MenuItemRole::Submenu));
+                    items.len() - 1
+                }
+            };
+            insert(&mut items[index].submenu, rest, cmd);
+        }
+    }
+}
+
+fn menu<'a>(menus: &'a mut Vec<Menu>, title: &str) -> &'a mut Menu {
+    let index = match menus.iter().position(|m| m.title == title) {
+        Some(index) => index,
+        None => {
+            menus.push(Menu::new(title, menu_role(title)));
+            menus.len() - 1
+        }
+    };
+    &mut menus[index]
+}
+
+/// Appends items to the end of a menu, after a separator.
+fn append(menu: &mut Menu, items: Vec<MenuItem>) {
+    if !menu.items.is_empty() && !items.is_empty() {
+        menu.items.push(MenuItem::separator());
+    }
+    menu.items.extend(items);
+}
+
+/// Builds a menu bar from the commands gathered by `Context::commands`,
+/// following the platform's conventions.
+///
+/// Commands named like "File:New" go in the menu before the first colon.
+/// Commands named "About …" and "Preferences" (or "Settings") are moved
+/// wherever the platform expects them, whichever menu they were given in.
+/// On macOS this adds the application menu with Services, Hide and Quit,
+/// the Window menu, and a Help menu, which the system gives a search field.
+pub fn menu_bar(app_name: &str, commands: &[CommandInfo], platform: MenuPlatform) -> Vec<Menu> {
+    let mut menus: Vec<Menu> = vec![];
+    let mut about = None;
+    let mut preferences = None;
+
+    for cmd in commands {
+        let names: Vec<&str> = cmd.path.split(':').collect();
+        if names.len() < 2 {
+            println!(
+                "menu command {:?} needs a menu, like \"File:{}\"",
+                cmd.path, cmd.path
+            );
+            continue;
+        }
+        let name = names[names.len() - 1];
+        if names.len() == 2 && is_about(name) {
+            about = Some(MenuItem::new(name, MenuItemRole::About(cmd.path.clone())));
+            continue;
+        }
+        if names.len() == 2 && is_preferences(name) {
+            let mut item = MenuItem::new(name, MenuItemRole::Preferences(cmd.path.clone()));
+            item.key = cmd.key;
+            preferences = Some(item);
+            continue;
+        }
+        insert(&mut menu(&mut menus, names[0]).items, &names[1..], cmd);
+    }
+
+    match platform {
+        MenuPlatform::MacOS => {
+            let mut app = Menu::new(app_name, MenuRole::App);
+            if let Some(about) = about {
+                app.items.push(MenuItem {
+                    title: format!("About {}", app_name),
+                    ..about
+                });
+                app.items.push(MenuItem::separator());
+            }
+            if let Some(preferences) = preferences {
+                app.items.push(preferences);
+                app.items.push(MenuItem::separator());
+            }
+            app.items
+                .push(MenuItem::new("Services", MenuItemRole::Services));
+            app.items.push(MenuItem::separator());
+            app.items.push(
+                MenuItem::new(&format!("Hide {}", app_name), MenuItemRole::Hide)
+                    .hotkey(HotKey::KeyH),
+            );
+            app.items
+                .push(MenuItem::new("Hide Others", MenuItemRole::HideOthers));
+            app.items
+                .push(MenuItem::new("Show All", MenuItemRole::ShowAll));
+            app.items.push(MenuItem::separator());
+            app.items.push(
+                MenuItem::new(&format!("Quit {}", app_name), MenuItemRole::Quit)
+                    .hotkey(HotKey::KeyQ),
+            );
+            menus.insert(0, app);
+
+            let window_items = vec![
+                MenuItem::new("Minimize", MenuItemRole::Minimize).hotkey(HotKey::KeyM),
+                MenuItem::new("Zoom", MenuItemRole::Zoom),
+                MenuItem::separator(),
+                MenuItem::new("Bring All to Front", MenuItemRole::BringAllToFront),
+            ];
+            let window = menu(&mut menus, "Window");
+            let app_items = std::mem::replace(&mut window.items, window_items);
+            append(window, app_items);
+
+            let help = menu(&mut menus, "Help");
+            help.items
+                .insert(0, MenuItem::new("Search", MenuItemRole::HelpSearch));
+        }
+        MenuPlatform::Windows | MenuPlatform::Linux => {
+            // Windows apps keep options in Tools, GNOME and KDE apps in Edit.
+            if let Some(preferences) = preferences {
+                let title = if platform == MenuPlatform::Windows {
+                    "Tools"
+                } else {
+                    "Edit"
+                };
+                append(menu(&mut menus, title), vec![preferences]);
+            }
+
+            let file = menu(&mut menus, "File");
+            let title = if platform == MenuPlatform::Windows {
+                "Exit"
+            } else {
+                "Quit"
+            };
+            append(file, vec![MenuItem::new(title, MenuItemRole::Quit)]);
+
+            if let Some(about) = about {
+                append(menu(&mut menus, "Help"), vec![about]);
+            }
+        }
+    }
+
+    // Standard menus go in the usual order, custom ones in the order given.
+    menus.sort_by_key(|m| match m.role {
+        MenuRole::App => 0,
+        MenuRole::File => 1,
+        MenuRole::Edit => 2,
+        MenuRole::View => 3,
+        MenuRole::Custom => 4,
+        MenuRole::Window => 5,
+        MenuRole::Help => 6,
+    });
+
+    menus
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn cmd(path: &str) -> CommandInfo {
+        CommandInfo {
+            path: path.into(),
+            key: None,
+        }
+    }
+
+    fn titles(menus: &[Menu]) -> Vec<&str> {
+        menus.iter().map(|m| m.title.as_str()).collect()
+    }
+
+    #[test]
+    fn test_menu_bar() {
+        let commands = [
+            cmd("Custom:Submenu:One"),
+            cmd("Help:About Demo"),
+            cmd("File:New"),
+            cmd("Edit:Preferences"),
+            cmd("Custom:Submenu:Two"),
+        ];
+
+        let mac = menu_bar("Demo", &commands, MenuPlatform::MacOS);
+        assert_eq!(titles(&mac), ["Demo", "File", "Custom", "Window", "Help"]);
+        assert_eq!(
+            mac[0].items[0].role,
+            MenuItemRole::About("Help:About Demo".into())
+        );
+        assert_eq!(
+            mac[0].items[2].role,
+            MenuItemRole::Preferences("Edit:Preferences".into())
+        );
+        assert!(mac[0]
+            .items
+            .iter()
+            .any(|i| i.role == MenuItemRole::Services));
+        assert_eq!(mac[4].items[0].role, MenuItemRole::HelpSearch);
+        assert_eq!(mac[2].items.len(), 1);
+        assert_eq!(mac[2].items[0].submenu.len(), 2);
+
+        let win = menu_bar("Demo", &commands, MenuPlatform::Windows);
+        assert_eq!(titles(&win), ["File", "Custom", "Tools", "Help"]);
+        assert_eq!(win[0].items.last().unwrap().role, MenuItemRole::Quit);
+        assert!(matches!(
+            win[3].items.last().unwrap().role,
+            MenuItemRole::About(_)
+        ));
+
+        let linux = menu_bar("Demo", &commands, MenuPlatform::Linux);
+        assert_eq!(titles(&linux), ["File", "Edit", "Custom", "Help"]);
+        assert!(matches!(
+            linux[1].items[0].role,
+            MenuItemRole::Preferences(_)
+        ));
+    }
+}
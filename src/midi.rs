@@ -0,0 +1,187 @@
+use crate::*;
+use std::rc::Rc;
+
+/// A message from a MIDI controller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MidiMessage {
+    NoteOn {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    /// Also sent for a note on with zero velocity.
+    NoteOff {
+        channel: u8,
+        note: u8,
+        velocity: u8,
+    },
+    ControlChange {
+        channel: u8,
+        controller: u8,
+        value: u8,
+    },
+    /// Centered at 8192.
+    PitchBend {
+        channel: u8,
+        value: u16,
+    },
+    /// Anything else, as received.
+    Other(Vec<u8>),
+}
+
+impl MidiMessage {
+    pub fn parse(bytes: &[u8]) -> Self {
+        let (status, data1, data2) = match bytes {
+            [status, data1, data2, ..] => (*status, *data1, *data2),
+            _ => return MidiMessage::Other(bytes.to_vec()),
+        };
+        let channel = status & 0x0f;
+        match status & 0xf0 {
+            0x90 if data2 > 0 => MidiMessage::NoteOn {
+                channel,
+                note: data1,
+                velocity: data2,
+            },
+            0x80 | 0x90 => MidiMessage::NoteOff {
+                channel,
+                note: data1,
+                velocity: data2,
+            },
+            0xb0 => MidiMessage::ControlChange {
+                channel,
+                controller: data1,
+                value: data2,
+            },
+            0xe0 => MidiMessage::PitchBend {
+                channel,
+                value: (data1 as u16 & 0x7f) | ((data2 as u16 & 0x7f) << 7),
+            },
+            _ => MidiMessage::Other(bytes.to_vec()),
+        }
+    }
+}
+
+/// Connects to every MIDI input, delivering messages on the UI thread.
+/// Devices plugged in later aren't seen.
+#[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+fn connect_inputs() -> Vec<midir::MidiInputConnection<()>> {
+    let count = match midir::MidiInput::new("rui") {
+        Ok(input) => input.port_count(),
+        Err(err) => {
+            println!("unable to use MIDI: {}", err);
+            return vec![];
+        }
+    };
+    let mut connections = vec![];
+    for i in 0..count {
+        // Connecting consumes the MidiInput, so each port needs its own.
+        let input = match midir::MidiInput::new("rui") {
+            Ok(input) => input,
+            Err(_) => continue,
+        };
+        let port = match input.ports().get(i) {
+            Some(port) => port.clone(),
+            None => continue,
+        };
+        let name = input.port_name(&port).unwrap_or_default();
+        let connection = input.connect(
+            &port,
+            "rui",
+            |_, bytes, _| {
+                let message = MidiMessage::parse(bytes);
+                on_main(move |cx| cx.midi_received(message));
+            },
+            (),
+        );
+        match connection {
+            Ok(connection) => connections.push(connection),
+            Err(err) => println!("unable to connect to MIDI input {}: {}", name, err),
+        }
+    }
+    connections
+}
+
+impl Context {
+    /// Calls `f` on the UI thread with each message from any MIDI input.
+    /// Views get them too, as `Event::Midi`.
+    ///
+    /// Needs the `midi` feature.
+    pub fn on_midi(&mut self, f: impl Fn(&mut Context, &MidiMessage) + 'static) {
+        self.start_midi();
+        self.midi_handlers.push(Rc::new(f));
+    }
+
+    /// Connects to MIDI inputs, the first time it's called.
+    pub(crate) fn start_midi(&mut self) {
+        #[cfg(all(feature = "midi", not(target_arch = "wasm32")))]
+        if self.midi_inputs.is_none() {
+            self.midi_inputs = Some(connect_inputs());
+        }
+    }
+
+    /// Turns MIDI learn on or off. While it's on, controls with the
+    /// `midi_learn` modifier are outlined, and the next controller moved
+    /// is mapped to the last one touched.
+    pub fn set_midi_learn(&mut self, learn: bool) {
+        self.midi_learning = learn;
+        self.midi_learn_target = None;
+        self.set_dirty();
+    }
+
+    pub fn midi_learning(&self) -> bool {
+        self.midi_learning
+    }
+
+    #[cfg_attr(
+        not(all(feature = "midi", not(target_arch = "wasm32"))),
+        allow(dead_code)
+    )]
+    fn midi_received(&mut self, message: MidiMessage) {
+        for f in self.midi_handlers.clone() {
+            f(self, &message);
+        }
+        self.midi_events.push(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_midi() {
+        assert_eq!(
+            MidiMessage::parse(&[0x91, 60, 100]),
+            MidiMessage::NoteOn {
+                channel: 1,
+                note: 60,
+                velocity: 100
+            }
+        );
+        assert_eq!(
+            MidiMessage::parse(&[0x90, 60, 0]),
+            MidiMessage::NoteOff {
+                channel: 0,
+                note: 60,
+                velocity: 0
+            }
+        );
+        assert_eq!(
+            MidiMessage::parse(&[0xb2, 7, 127]),
+            MidiMessage::ControlChange {
+                channel: 2,
+                controller: 7,
+                value: 127
+            }
+        );
+        assert_eq!(
+            MidiMessage::parse(&[0xe0, 0, 0x40]),
+            MidiMessage::PitchBend {
+                channel: 0,
+                value: 8192
+            }
+        );
+        assert_eq!(MidiMessage::parse(&[0xf8]), MidiMessage::Other(vec![0xf8]));
+    }
+}
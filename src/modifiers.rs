@@ -1,6 +1,8 @@
 use crate::*;
 use accesskit::Role;
+use std::fmt::Display;
 use std::marker::PhantomData;
+use std::str::FromStr;
 
 /// Modifiers common to all views.
 pub trait Modifiers: View + Sized {
@@ -9,6 +11,18 @@ pub trait Modifiers: View + Sized {
         AnimView::new(self, func)
     }
 
+    /// Calls a closure every `interval` with context and the seconds since
+    /// it was last called, redrawing each time, even when nothing else is
+    /// happening. For games, visualizers and animated canvases. A zero
+    /// interval calls it every frame.
+    fn tick<F: Fn(&mut Context, f32) + 'static>(
+        self,
+        interval: std::time::Duration,
+        func: F,
+    ) -> TickView<Self, F> {
+        TickView::new(self, interval, func)
+    }
+
     /// Puts a view behind another. The background view inherits the size of the view.
     fn background<BG: View>(self, background: BG) -> Background<Self, BG> {
         Background::new(self, background)
@@ -65,6 +79,47 @@ pub trait Modifiers: View + Sized {
         )
     }
 
+    /// Drags content out of the window into other applications.
+    ///
+    /// Needs the `drag-out` feature, and is supported on macOS and Windows.
+    fn drag_out<F: Fn(&mut Context) -> DragPayload + 'static>(self, f: F) -> DragOut<Self, F> {
+        DragOut::new(self, f)
+    }
+
+    /// Lets the view be dragged onto a `drop_target` which takes `payload`'s
+    /// type.
+    fn draggable<T: Clone + 'static>(self, payload: T) -> Draggable<Self, T> {
+        Draggable::new(self, payload)
+    }
+
+    /// Calls a function with the payload of a `draggable` view dropped on
+    /// this one, if it's a `T`. The view is outlined while one is over it.
+    fn drop_target<T: Clone + 'static, A: 'static, F: Fn(&mut Context, T) -> A + 'static>(
+        self,
+        f: F,
+    ) -> DropTarget<Self, F, T> {
+        DropTarget::new(self, f)
+    }
+
+    /// Draws an image behind the view, stretched to its size with the
+    /// corners kept the same. See `image_slice`.
+    fn background_image_slice(
+        self,
+        image: impl Into<std::rc::Rc<RgbaImage>>,
+        insets: SliceInsets,
+    ) -> Background<Self, ImageSlice> {
+        self.background(image_slice(image, insets))
+    }
+
+    /// Calls a function when the mouse wheel or trackpad scrolls over the
+    /// view, with how far to move the content.
+    fn scroll<A: 'static, F: Fn(&mut Context, LocalOffset) -> A + 'static>(
+        self,
+        f: F,
+    ) -> ScrollGesture<Self, F> {
+        ScrollGesture::new(self, f)
+    }
+
     /// Calls a function in response to a mouse hovering.
     fn hover<A: 'static, F: Fn(&mut Context, bool) -> A + 'static>(
         self,
@@ -73,6 +128,15 @@ pub trait Modifiers: View + Sized {
         Hover::new(self, HoverFunc { f })
     }
 
+    /// Calls a function with the paths of files dropped on the view from
+    /// another app, outlining the view while they're dragged over it.
+    fn on_drop<A: 'static, F: Fn(&mut Context, Vec<std::path::PathBuf>) -> A + 'static>(
+        self,
+        f: F,
+    ) -> OnDrop<Self, F> {
+        OnDrop::new(self, f)
+    }
+
     /// Calls a function in response to a mouse hovering. Version which passes the position
     fn hover_p<A: 'static, F: Fn(&mut Context, LocalPoint) -> A + 'static>(
         self,
@@ -91,6 +155,16 @@ pub trait Modifiers: View + Sized {
         Flex::new(self)
     }
 
+    /// Saves and restores state with the workspace, under a stable `key`.
+    /// See `Context::save_workspace`.
+    fn workspace_state<T: FromStr + Display + 'static, B: Binding<T>>(
+        self,
+        key: &str,
+        binding: B,
+    ) -> WorkspaceState<Self, B, T> {
+        WorkspaceState::new(self, key, binding)
+    }
+
     /// Make the window full screen.
     fn fullscreen(self) -> FullscreenView<Self> {
         FullscreenView::new(self)
@@ -102,11 +176,23 @@ pub trait Modifiers: View + Sized {
         Geom::new(self, f)
     }
 
-    /// Responds to keyboard events
+    /// Responds to keyboard events. Held keys repeat; see `Event::Key`.
     fn key<F: Fn(&mut Context, Key) + 'static>(self, f: F) -> KeyView<Self, F> {
         KeyView::new(self, f)
     }
 
+    /// Responds to keys being released.
+    fn key_up<F: Fn(&mut Context, Key) + 'static>(self, f: F) -> KeyView<Self, F> {
+        KeyView::new_up(self, f)
+    }
+
+    /// Responds to text from input methods, like CJK input and
+    /// press-and-hold accents. Dictation and system text replacements,
+    /// which insert without composing, aren't delivered yet.
+    fn text_input<F: Fn(&mut Context, TextInput) + 'static>(self, f: F) -> TextInputView<Self, F> {
+        TextInputView::new(self, f)
+    }
+
     /// Applies an offset to the view in local space.
     fn offset<Off: Into<LocalOffset>>(self, offset: Off) -> Offset<Self> {
         Offset::new(self, offset.into())
@@ -132,6 +218,103 @@ pub trait Modifiers: View + Sized {
         Tap::new(self, TapAdapter { f })
     }
 
+    /// Calls a function after `count` taps in a row. Unlike `tap`, it
+    /// doesn't take the touch, so it can be combined with other gestures.
+    fn on_tap<A: 'static, F: Fn(&mut Context) -> A + 'static>(
+        self,
+        count: usize,
+        f: F,
+    ) -> Gesture<Self, TapRecognizer<F>> {
+        Gesture::new(self, TapRecognizer::new(count, f))
+    }
+
+    /// Calls a function on a double tap.
+    fn on_double_tap<A: 'static, F: Fn(&mut Context) -> A + 'static>(
+        self,
+        f: F,
+    ) -> Gesture<Self, TapRecognizer<F>> {
+        self.on_tap(2, f)
+    }
+
+    /// Calls a function once a touch has been held still for `duration`,
+    /// or `LONG_PRESS_DURATION` if none is given.
+    fn on_long_press<A: 'static, F: Fn(&mut Context) -> A + 'static>(
+        self,
+        duration: impl Into<Option<std::time::Duration>>,
+        f: F,
+    ) -> Gesture<Self, LongPressRecognizer<F>> {
+        let duration = duration.into().unwrap_or(LONG_PRESS_DURATION);
+        Gesture::new(self, LongPressRecognizer::new(duration, f))
+    }
+
+    /// Calls a function as a touch pans across the view, with the movement
+    /// since the last call and the velocity in points per second. The
+    /// velocity when it ends is for flinging.
+    fn on_pan<
+        A: 'static,
+        F: Fn(&mut Context, LocalOffset, LocalOffset, GestureState) -> A + 'static,
+    >(
+        self,
+        f: F,
+    ) -> Gesture<Self, PanRecognizer<F>> {
+        Gesture::new(self, PanRecognizer::new(f))
+    }
+
+    /// Calls a function as two touches pinch the view, with the scale and
+    /// rotation in radians since they went down.
+    fn on_pinch<A: 'static, F: Fn(&mut Context, f32, f32, GestureState) -> A + 'static>(
+        self,
+        f: F,
+    ) -> Gesture<Self, PinchRecognizer<F>> {
+        Gesture::new(self, PinchRecognizer::new(f))
+    }
+
+    /// Animates the view in and out when a `cond` switches to or away
+    /// from it.
+    fn transition(self, transition: Transition) -> TransitionView<Self> {
+        TransitionView::new(self, transition)
+    }
+
+    /// Plays keyframes into a binding, starting when the view appears.
+    fn keyframes<T: Animatable, B: Binding<T>>(
+        self,
+        binding: B,
+        keyframes: Keyframes<T>,
+    ) -> KeyframeDriver<Self, B, T> {
+        KeyframeDriver::new(self, binding, keyframes)
+    }
+
+    /// When a view with the same id goes away as this one appears, e.g.
+    /// a thumbnail and its detail view, this one moves and resizes from
+    /// where the other was. A `corner_radius` set outside eases too.
+    fn matched_geometry(self, id: &str) -> MatchedGeometry<Self> {
+        MatchedGeometry::new(self, id)
+    }
+
+    /// Shrinks the view slightly while it's pressed, springing back on
+    /// release.
+    fn press_scale(self) -> Interaction<Self> {
+        Interaction::press(self)
+    }
+
+    /// Like `offset`, but springs to a new offset when it changes, e.g. for
+    /// a toggle's thumb.
+    fn spring_offset<Off: Into<LocalOffset>>(self, offset: Off) -> Interaction<Self> {
+        Interaction::spring(self, offset.into())
+    }
+
+    /// Shakes the view side to side whenever `trigger` changes, to reject
+    /// input. Bump a counter on each error.
+    fn shake(self, trigger: u64) -> Interaction<Self> {
+        Interaction::shake(self, trigger)
+    }
+
+    /// Draws a checkmark over the view when `shown` becomes true, stroke by
+    /// stroke, to confirm success.
+    fn success_checkmark(self, shown: bool) -> Interaction<Self> {
+        Interaction::checkmark(self, shown)
+    }
+
     /// Version of `tap` which takes an action type instead
     /// of a function.
     fn tap_a<A: Clone + 'static>(self, action: A) -> Tap<Self, TapActionAdapter<A>> {
@@ -151,6 +334,23 @@ pub trait Modifiers: View + Sized {
         TitleView::new(self, title)
     }
 
+    /// Shows a `Document` in the window title, autosaves it, and asks to
+    /// save unsaved changes before the window closes.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn document<B: Binding<Document<T>>, T: Clone + PartialEq + 'static>(
+        self,
+        document: B,
+    ) -> DocumentView<Self, B, T> {
+        DocumentView::new(self, document)
+    }
+
+    /// Specify what's drawn behind the UI: a color, nothing, or a blurred
+    /// system material. See-through backgrounds need
+    /// `WindowConfig::transparent`.
+    fn window_background(self, background: WindowBackground) -> WindowBackgroundView<Self> {
+        WindowBackgroundView::new(self, background)
+    }
+
     /// Handle an action from a child view.
     fn handle<A: 'static, A2: 'static, F: Fn(&mut Context, &A) -> A2 + 'static>(
         self,
@@ -163,6 +363,138 @@ pub trait Modifiers: View + Sized {
     fn clip(self) -> Clip<Self> {
         Clip::new(self)
     }
+
+    /// Blurs the view, `radius` points across. Effects draw the view,
+    /// images and `wgpu_view`s included, into a texture of its own, which
+    /// is drawn over the window once the rest of it is, like images are.
+    /// Without a GPU, as in tests, views are drawn without their effects.
+    fn blur(self, radius: f32) -> EffectView<Self> {
+        EffectView::new(self, Effect::blur(radius))
+    }
+
+    /// Scales the brightness of the view. 1 leaves it as it is, and 0
+    /// makes it black. See `blur` for how effects are drawn.
+    fn brightness(self, amount: f32) -> EffectView<Self> {
+        EffectView::new(self, Effect::brightness(amount))
+    }
+
+    /// Scales the saturation of the view. 1 leaves it as it is, 0 makes it
+    /// gray and more makes it more vivid. See `blur` for how effects are
+    /// drawn.
+    fn saturation(self, amount: f32) -> EffectView<Self> {
+        EffectView::new(self, Effect::saturation(amount))
+    }
+
+    /// Post-processes the view with WGSL defining
+    /// `fn effect(uv: vec2<f32>) -> vec4<f32>`, the color at `uv`, from 0
+    /// to 1 across the view from the top left, premultiplied by alpha. It
+    /// can call `sample_source(uv)` for what the view drew, and read
+    /// `uniforms.values`, four `vec4<f32>`s holding up to
+    /// `MAX_EFFECT_UNIFORMS` of `uniforms`, `uniforms.texel`, the size of a
+    /// pixel in `uv`, and `uniforms.scale`, pixels per point. Shaders which
+    /// don't compile leave the view as it is. See `blur` for how effects
+    /// are drawn.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// let invert = "
+    ///     fn effect(uv: vec2<f32>) -> vec4<f32> {
+    ///         let c = sample_source(uv);
+    ///         return vec4<f32>(mix(c.rgb, c.a - c.rgb, uniforms.values[0].x), c.a);
+    ///     }
+    /// ";
+    /// text("Inverted").padding(Auto).shader(invert, &[1.0]);
+    /// ```
+    fn shader(self, wgsl: &str, uniforms: &[f32]) -> EffectView<Self> {
+        EffectView::new(self, Effect::new(wgsl, uniforms))
+    }
+
+    /// Shows the view only where `mask` is drawn, as opaque as it is. The
+    /// mask is laid out in the view's bounds, and isn't interactive. Like
+    /// effects, the view and mask are drawn into textures of their own, so
+    /// see `blur` for how they're drawn.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// canvas(|_, rect, vger| {
+    ///     let (a, b) = (AZURE_HIGHLIGHT, RED_HIGHLIGHT);
+    ///     let paint = vger.linear_gradient(rect.min(), rect.max(), a, b, 0.0);
+    ///     vger.fill_rect(rect, 0.0, paint);
+    /// })
+    /// .size([200.0, 60.0])
+    /// .mask(|| text("Hero").font_size(48));
+    /// ```
+    fn mask<M: View>(self, mask: impl Fn() -> M) -> MaskView<Self, M> {
+        MaskView::new(self, mask())
+    }
+
+    /// Makes an invalidation boundary: state changes inside never cause
+    /// views outside to be laid out again. Takes all the space offered, so
+    /// what's inside can't change the layout around it. Use this for costly
+    /// regions like an embedded preview.
+    fn isolate(self) -> Isolate<Self> {
+        Isolate::new(self)
+    }
+
+    /// Lets a MIDI controller set `value`, the 0 to 1 value of a knob or
+    /// slider, once mapped with MIDI learn. See `Context::set_midi_learn`.
+    ///
+    /// Needs the `midi` feature.
+    fn midi_learn<B: Binding<f32>>(self, value: B) -> MidiLearn<Self, B> {
+        MidiLearn::new(self, value)
+    }
+
+    /// Registers `value` at an address like "/synth/cutoff", so it can be
+    /// set remotely over OSC and automated with a `ParamTimeline`.
+    fn param<B: Binding<f32>>(self, address: &str, value: B) -> ParamView<Self, B> {
+        ParamView::new(self, address, value)
+    }
+
+    /// Draws a title bar above the app, for windows without decorations
+    /// (see `WindowConfig::decorations`), with `title` in the middle. It has
+    /// the platform's window buttons, moves the window when dragged, and
+    /// zooms it when double-clicked.
+    fn titlebar<T: View>(self, title: impl Fn() -> T) -> Titlebar<Self, T> {
+        Titlebar::new(self, title())
+    }
+
+    /// Rounds the corners of a border, shadow and `background` rectangle.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// text("card")
+    ///     .padding(Auto)
+    ///     .background(rectangle())
+    ///     .corner_radius(8.0)
+    ///     .border(1.0, vger::color::Color::gray(0.5))
+    ///     .elevation(2);
+    /// ```
+    fn corner_radius(self, radius: f32) -> Chrome<Self> {
+        Chrome::new(self).corner_radius(radius)
+    }
+
+    /// Strokes a border of `width` inside the edge of a view.
+    fn border(self, width: f32, color: Color) -> Chrome<Self> {
+        Chrome::new(self).border(width, color)
+    }
+
+    /// Casts a shadow under a view, in the theme's shadow color. Higher
+    /// levels look further raised.
+    fn elevation(self, level: u32) -> Chrome<Self> {
+        Chrome::new(self).elevation(level)
+    }
+
+    /// Turns off (or back on) snapping dividers and borders to whole
+    /// pixels, for views which animate them smoothly.
+    fn pixel_snap(self, snap: bool) -> SetenvView<Self, PixelSnap> {
+        self.env(PixelSnap(snap))
+    }
+
+    /// Sets text in this view and everything in it in `font`. Text sized
+    /// with `font_size` keeps its size.
+    fn font(self, font: Font) -> SetenvView<Self, Font> {
+        self.env(font)
+    }
 }
 
 impl<V: View> Modifiers for V {}
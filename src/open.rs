@@ -0,0 +1,287 @@
+use std::path::{Path, PathBuf};
+
+/// Is `arg` a URL, like "myapp://open?id=3"? Windows drive letters
+/// ("C:\notes.txt") aren't.
+fn is_url(arg: &str) -> bool {
+    match arg.find(':') {
+        Some(i) if i > 1 => {
+            let scheme = &arg[..i];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        _ => false,
+    }
+}
+
+/// Splits command-line arguments into files (made absolute) and URLs,
+/// which is how Windows and Linux pass documents and deep links to apps.
+/// Flags are skipped.
+pub(crate) fn command_line_opens<'a>(
+    cwd: &Path,
+    args: impl Iterator<Item = &'a str>,
+) -> (Vec<PathBuf>, Vec<String>) {
+    let mut files = vec![];
+    let mut urls = vec![];
+    for arg in args.filter(|arg| !arg.starts_with('-')) {
+        if let Some(path) = arg.strip_prefix("file://") {
+            files.push(PathBuf::from(path));
+        } else if is_url(arg) {
+            urls.push(arg.to_string());
+        } else {
+            files.push(cwd.join(arg));
+        }
+    }
+    (files, urls)
+}
+
+/// Makes the app open `scheme:` URLs, which arrive as `Event::OpenUrl`.
+/// `app_id` names the app, like "com.example.editor".
+///
+/// On Windows this registers the running executable for the current user.
+/// On Linux it installs a desktop entry for the running executable and makes
+/// it the default handler. macOS reads URL schemes from the app bundle's
+/// Info.plist instead (see `url_scheme_plist`), so this returns an error there.
+pub fn register_url_scheme(scheme: &str, app_id: &str) -> std::io::Result<()> {
+    let exe = std::env::current_exe()?;
+    register(scheme, app_id, &exe)
+}
+
+/// The `CFBundleURLTypes` entry to add to a macOS app's Info.plist so it
+/// opens `scheme:` URLs.
+pub fn url_scheme_plist(scheme: &str, app_id: &str) -> String {
+    format!(
+        "<key>CFBundleURLTypes</key>
+<array>
+    <dict>
+        <key>CFBundleURLName</key>
+        <string>{}</string>
+        <key>CFBundleURLSchemes</key>
+        <array>
+            <string>{}</string>
+        </array>
+    </dict>
+</array>
+",
+        app_id, scheme
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn register(_scheme: &str, _app_id: &str, _exe: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        "on macOS, add URL schemes to the app's Info.plist (see url_scheme_plist)",
+    ))
+}
+
+#[cfg(target_os = "windows")]
+fn reg_add(args: &[&str]) -> std::io::Result<()> {
+    let status = std::process::Command::new("reg")
+        .arg("add")
+        .args(args)
+        .arg("/f")
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("reg add {} failed", args[0]),
+        ))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn register(scheme: &str, app_id: &str, exe: &Path) -> std::io::Result<()> {
+    let key = format!("HKCU\\Software\\Classes\\{}", scheme);
+    reg_add(&[&key, "/ve", "/d", &format!("URL:{}", app_id)])?;
+    // An empty "URL Protocol" value marks the key as a URL scheme.
+    reg_add(&[&key, "/v", "URL Protocol", "/d", ""])?;
+    reg_add(&[
+        &format!("{}\\shell\\open\\command", key),
+        "/ve",
+        "/d",
+        &format!("\"{}\" \"%1\"", exe.display()),
+    ])
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn register(scheme: &str, app_id: &str, exe: &Path) -> std::io::Result<()> {
+    let data = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME").unwrap_or_default()).join(".local/share"),
+    };
+    let applications = data.join("applications");
+    std::fs::create_dir_all(&applications)?;
+
+    let desktop = format!("{}.desktop", app_id);
+    let mime = format!("x-scheme-handler/{}", scheme);
+    std::fs::write(
+        applications.join(&desktop),
+        format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec=\"{}\" %u\nNoDisplay=true\nMimeType={};\n",
+            app_id,
+            exe.display(),
+            mime
+        ),
+    )?;
+
+    let status = std::process::Command::new("xdg-mime")
+        .arg("default")
+        .args([desktop.as_str(), mime.as_str()])
+        .status()?;
+    if !status.success() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "xdg-mime default failed",
+        ));
+    }
+    Ok(())
+}
+
+/// Receives documents and URLs from Finder and other apps, which macOS
+/// sends as Apple events rather than arguments.
+#[cfg(all(target_os = "macos", feature = "winit"))]
+pub(crate) mod apple_events {
+    use crate::*;
+    use objc::declare::ClassDecl;
+    use objc::runtime::{Object, Sel};
+    use objc::{class, msg_send, sel, sel_impl};
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::path::PathBuf;
+
+    const fn four_cc(code: &[u8; 4]) -> u32 {
+        u32::from_be_bytes(*code)
+    }
+
+    const CORE_EVENT_CLASS: u32 = four_cc(b"aevt");
+    const OPEN_DOCUMENTS: u32 = four_cc(b"odoc");
+    const INTERNET_EVENT_CLASS: u32 = four_cc(b"GURL");
+    const GET_URL: u32 = four_cc(b"GURL");
+    const DIRECT_OBJECT: u32 = four_cc(b"----");
+
+    unsafe fn to_string(string: *mut Object) -> Option<String> {
+        if string.is_null() {
+            return None;
+        }
+        let utf8: *const c_char = msg_send![string, UTF8String];
+        if utf8.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(utf8).to_string_lossy().into_owned())
+    }
+
+    unsafe fn file_path(descriptor: *mut Object) -> Option<PathBuf> {
+        let url: *mut Object = msg_send![descriptor, fileURLValue];
+        if url.is_null() {
+            return None;
+        }
+        let path: *mut Object = msg_send![url, path];
+        to_string(path).map(PathBuf::from)
+    }
+
+    extern "C" fn open_documents(
+        _this: &Object,
+        _cmd: Sel,
+        event: *mut Object,
+        _reply: *mut Object,
+    ) {
+        let paths: Vec<PathBuf> = unsafe {
+            let files: *mut Object = msg_send![event, paramDescriptorForKeyword: DIRECT_OBJECT];
+            if files.is_null() {
+                return;
+            }
+            // A single file may not come in a list.
+            let count: isize = msg_send![files, numberOfItems];
+            if count > 0 {
+                (1..=count)
+                    .filter_map(|i| file_path(msg_send![files, descriptorAtIndex: i]))
+                    .collect()
+            } else {
+                file_path(files).into_iter().collect()
+            }
+        };
+        if !paths.is_empty() {
+            on_main(move |cx| cx.open_files.extend(paths));
+        }
+    }
+
+    extern "C" fn get_url(_this: &Object, _cmd: Sel, event: *mut Object, _reply: *mut Object) {
+        let url = unsafe {
+            let url: *mut Object = msg_send![event, paramDescriptorForKeyword: DIRECT_OBJECT];
+            if url.is_null() {
+                return;
+            }
+            to_string(msg_send![url, stringValue])
+        };
+        if let Some(url) = url {
+            on_main(move |cx| cx.open_urls.push(url));
+        }
+    }
+
+    /// Replaces AppKit's handlers, which would otherwise pass documents to
+    /// an app delegate method winit doesn't implement. Call once the app has
+    /// finished launching, since AppKit installs its own handlers then.
+    pub(crate) fn install() {
+        unsafe {
+            let mut decl = match ClassDecl::new("RuiAppleEventHandler", class!(NSObject)) {
+                Some(decl) => decl,
+                None => return,
+            };
+            decl.add_method(
+                sel!(openDocuments:withReplyEvent:),
+                open_documents as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+            );
+            decl.add_method(
+                sel!(getUrl:withReplyEvent:),
+                get_url as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+            );
+            let class = decl.register();
+            let handler: *mut Object = msg_send![class, new];
+
+            let manager: *mut Object =
+                msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+            let () = msg_send![manager,
+                setEventHandler: handler
+                andSelector: sel!(openDocuments:withReplyEvent:)
+                forEventClass: CORE_EVENT_CLASS
+                andEventID: OPEN_DOCUMENTS];
+            let () = msg_send![manager,
+                setEventHandler: handler
+                andSelector: sel!(getUrl:withReplyEvent:)
+                forEventClass: INTERNET_EVENT_CLASS
+                andEventID: GET_URL];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_command_line_opens() {
+        let cwd = PathBuf::from("/home/me");
+        let args = [
+            "--verbose",
+            "notes.txt",
+            "myapp://open?id=3",
+            "file:///tmp/a.txt",
+            "C:\\b.txt",
+        ];
+        let (files, urls) = command_line_opens(&cwd, args.iter().copied());
+        assert_eq!(
+            files,
+            [
+                cwd.join("notes.txt"),
+                PathBuf::from("/tmp/a.txt"),
+                cwd.join("C:\\b.txt")
+            ]
+        );
+        assert_eq!(urls, ["myapp://open?id=3"]);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::*;
+use rosc::{OscMessage, OscPacket, OscType};
+use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+/// Largest packet we'll receive.
+const MAX_PACKET: usize = 65536;
+
+pub(crate) struct OscBridge {
+    socket: UdpSocket,
+    /// Where to send changes.
+    clients: Vec<SocketAddr>,
+    /// Last value sent or received for each address, so we only send
+    /// changes and don't echo values back.
+    sent: HashMap<String, f32>,
+}
+
+/// Numeric messages in a packet, with bundles flattened.
+fn float_messages(packet: OscPacket, messages: &mut Vec<(String, f32)>) {
+    match packet {
+        OscPacket::Message(message) => {
+            let value = match message.args.first() {
+                Some(OscType::Float(v)) => *v,
+                Some(OscType::Double(v)) => *v as f32,
+                Some(OscType::Int(v)) => *v as f32,
+                Some(OscType::Long(v)) => *v as f32,
+                Some(OscType::Bool(v)) => *v as u8 as f32,
+                _ => return,
+            };
+            messages.push((message.addr, value));
+        }
+        OscPacket::Bundle(bundle) => {
+            for packet in bundle.content {
+                float_messages(packet, messages);
+            }
+        }
+    }
+}
+
+fn receive(socket: UdpSocket) {
+    let mut buf = vec![0; MAX_PACKET];
+    loop {
+        let len = match socket.recv_from(&mut buf) {
+            Ok((len, _)) => len,
+            Err(err) => {
+                println!("error receiving OSC: {}", err);
+                break;
+            }
+        };
+        match rosc::decoder::decode_udp(&buf[..len]) {
+            Ok((_, packet)) => {
+                let mut messages = vec![];
+                float_messages(packet, &mut messages);
+                if !messages.is_empty() {
+                    on_main(move |cx| cx.osc_received(messages));
+                }
+            }
+            Err(err) => println!("invalid OSC packet: {:?}", err),
+        }
+    }
+}
+
+impl Context {
+    /// Listens for OSC messages on `addr`, like "0.0.0.0:9000", so other
+    /// apps and controllers can set values registered with the `param`
+    /// modifier.
+    /// A message's first argument is the new value.
+    pub fn start_osc(&mut self, addr: impl ToSocketAddrs) {
+        let socket = match UdpSocket::bind(addr) {
+            Ok(socket) => socket,
+            Err(err) => {
+                println!("unable to listen for OSC: {}", err);
+                return;
+            }
+        };
+        match socket.try_clone() {
+            Ok(receiver) => {
+                std::thread::spawn(move || receive(receiver));
+            }
+            Err(err) => {
+                println!("unable to listen for OSC: {}", err);
+                return;
+            }
+        }
+        self.osc = Some(OscBridge {
+            socket,
+            clients: vec![],
+            sent: HashMap::new(),
+        });
+    }
+
+    /// Sends changes to values registered with the `param` modifier to `addr`,
+    /// like a DAW at "127.0.0.1:8000". Call after `start_osc`.
+    pub fn send_osc_to(&mut self, addr: impl ToSocketAddrs) {
+        let bridge = match &mut self.osc {
+            Some(bridge) => bridge,
+            None => {
+                println!("call start_osc before send_osc_to");
+                return;
+            }
+        };
+        match addr.to_socket_addrs() {
+            Ok(addrs) => bridge.clients.extend(addrs.take(1)),
+            Err(err) => println!("unable to send OSC: {}", err),
+        }
+    }
+
+    fn osc_received(&mut self, messages: Vec<(String, f32)>) {
+        for (address, value) in messages {
+            if self.set_param(&address, value) {
+                if let Some(bridge) = &mut self.osc {
+                    bridge.sent.insert(address, value);
+                }
+            }
+        }
+    }
+
+    /// Sends values which have changed since they were last sent.
+    pub(crate) fn send_osc_updates(&mut self) {
+        let bridge = match &self.osc {
+            Some(bridge) if !bridge.clients.is_empty() => bridge,
+            _ => return,
+        };
+        let changed: Vec<_> = self
+            .params()
+            .into_iter()
+            .filter(|(address, value)| bridge.sent.get(address) != Some(value))
+            .collect();
+
+        let bridge = self.osc.as_mut().unwrap();
+        for (address, value) in changed {
+            let packet = OscPacket::Message(OscMessage {
+                addr: address.clone(),
+                args: vec![OscType::Float(value)],
+            });
+            match rosc::encoder::encode(&packet) {
+                Ok(bytes) => {
+                    for client in &bridge.clients {
+                        if let Err(err) = bridge.socket.send_to(&bytes, client) {
+                            println!("unable to send OSC to {}: {}", client, err);
+                        }
+                    }
+                }
+                Err(err) => println!("unable to encode OSC: {:?}", err),
+            }
+            bridge.sent.insert(address, value);
+        }
+    }
+}
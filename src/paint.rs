@@ -27,4 +27,44 @@ impl Paint {
             } => vger.linear_gradient(*start, *end, *inner_color, *outer_color, 0.0),
         }
     }
+
+    /// The color the paint gives `pt`.
+    pub fn color_at(&self, pt: LocalPoint) -> Color {
+        match self {
+            Paint::Color(color) => *color,
+            Paint::Gradient {
+                start,
+                end,
+                inner_color,
+                outer_color,
+            } => {
+                let axis = *end - *start;
+                let len2 = axis.square_length();
+                if len2 == 0.0 {
+                    return *inner_color;
+                }
+                let t = (pt - *start).dot(axis) / len2;
+                inner_color.mix(*outer_color, t.clamp(0.0, 1.0))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_color_at() {
+        let paint = Paint::Gradient {
+            start: [0.0, 0.0].into(),
+            end: [10.0, 0.0].into(),
+            inner_color: Color::new(0.0, 0.0, 0.0, 1.0),
+            outer_color: Color::new(1.0, 1.0, 1.0, 1.0),
+        };
+        assert_eq!(paint.color_at([5.0, 3.0].into()).r, 0.5);
+        assert_eq!(paint.color_at([-5.0, 0.0].into()).r, 0.0);
+        assert_eq!(paint.color_at([20.0, 0.0].into()).r, 1.0);
+    }
 }
@@ -0,0 +1,356 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Image on the pasteboard, as 8-bit RGBA pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasteboardImage {
+    pub width: usize,
+    pub height: usize,
+    pub rgba: Vec<u8>,
+}
+
+/// What we last put on the pasteboard.
+#[derive(Default)]
+struct Contents {
+    text: Option<String>,
+    html: Option<String>,
+    image: Option<PasteboardImage>,
+    file_urls: Vec<PathBuf>,
+    /// App-defined types, keyed by type name.
+    custom: HashMap<String, Vec<u8>>,
+}
+
+/// Copy and paste.
+///
+/// With the `clipboard` feature this is the system clipboard. Otherwise
+/// contents are only shared within the app.
+///
+/// App-defined types never leave the process: they sit alongside whatever
+/// was last copied, and are dropped once another app changes the clipboard.
+#[derive(Default)]
+pub struct Pasteboard {
+    system: Option<system::System>,
+    system_checked: bool,
+    contents: Contents,
+    /// What was on the system clipboard when we last polled.
+    system_contents: Contents,
+    change_count: u64,
+    fingerprint: u64,
+}
+
+impl Pasteboard {
+    /// Bumped whenever the pasteboard changes, so views can poll for
+    /// changes (e.g. to enable a paste button).
+    pub fn change_count(&mut self) -> u64 {
+        self.poll();
+        self.change_count
+    }
+
+    pub fn text(&mut self) -> Option<String> {
+        self.poll();
+        match &self.system {
+            Some(_) => self.system_contents.text.clone(),
+            None => self.contents.text.clone(),
+        }
+    }
+
+    pub fn set_text(&mut self, text: &str) {
+        self.write(Contents {
+            text: Some(text.into()),
+            ..Default::default()
+        });
+    }
+
+    pub fn html(&mut self) -> Option<String> {
+        self.poll();
+        match &self.system {
+            Some(_) => self.system_contents.html.clone(),
+            None => self.contents.html.clone(),
+        }
+    }
+
+    /// Copies styled content. `alt_text` is pasted into apps which don't take HTML.
+    pub fn set_html(&mut self, html: &str, alt_text: &str) {
+        self.write(Contents {
+            text: Some(alt_text.into()),
+            html: Some(html.into()),
+            ..Default::default()
+        });
+    }
+
+    pub fn image(&mut self) -> Option<PasteboardImage> {
+        self.poll();
+        match &self.system {
+            Some(_) => self.system_contents.image.clone(),
+            None => self.contents.image.clone(),
+        }
+    }
+
+    pub fn set_image(&mut self, image: PasteboardImage) {
+        self.write(Contents {
+            image: Some(image),
+            ..Default::default()
+        });
+    }
+
+    /// Files copied in a file manager, or with `set_file_urls`.
+    pub fn file_urls(&mut self) -> Vec<PathBuf> {
+        self.poll();
+        if self.system_contents.file_urls.is_empty() {
+            self.contents.file_urls.clone()
+        } else {
+            self.system_contents.file_urls.clone()
+        }
+    }
+
+    /// Copies files. Other apps receive the paths as text.
+    pub fn set_file_urls(&mut self, paths: Vec<PathBuf>) {
+        let text = paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.write(Contents {
+            text: Some(text),
+            file_urls: paths,
+            ..Default::default()
+        });
+    }
+
+    /// Data of an app-defined type.
+    pub fn data(&mut self, ty: &str) -> Option<Vec<u8>> {
+        self.poll();
+        self.contents.custom.get(ty).cloned()
+    }
+
+    /// Adds data of an app-defined type to what's on the pasteboard.
+    ///
+    /// Set a plain text version first so other apps can paste something too.
+    pub fn set_data(&mut self, ty: &str, data: Vec<u8>) {
+        self.poll();
+        self.contents.custom.insert(ty.into(), data);
+        self.change_count += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.write(Contents::default());
+    }
+
+    fn write(&mut self, contents: Contents) {
+        self.connect();
+        if let Some(system) = &mut self.system {
+            if let Some(image) = &contents.image {
+                system.set_image(image);
+            } else if let Some(html) = &contents.html {
+                system.set_html(html, contents.text.as_deref().unwrap_or_default());
+            } else if let Some(text) = &contents.text {
+                system.set_text(text);
+            } else {
+                system.clear();
+            }
+        }
+        self.contents = contents;
+        self.change_count += 1;
+        self.read_system();
+        self.fingerprint = fingerprint(&self.system_contents);
+    }
+
+    /// Checks whether another app changed the system clipboard.
+    fn poll(&mut self) {
+        self.connect();
+        if self.system.is_some() {
+            self.read_system();
+            let fingerprint = fingerprint(&self.system_contents);
+            if fingerprint != self.fingerprint {
+                self.fingerprint = fingerprint;
+                self.contents = Contents::default();
+                self.change_count += 1;
+            }
+        }
+    }
+
+    /// Fetches everything on the system clipboard, so each poll asks the
+    /// OS only once.
+    fn read_system(&mut self) {
+        if let Some(system) = &mut self.system {
+            self.system_contents = Contents {
+                text: system.text(),
+                html: system.html(),
+                image: system.image(),
+                file_urls: system.file_urls(),
+                ..Default::default()
+            };
+        }
+    }
+
+    /// Opens the system clipboard on first use.
+    fn connect(&mut self) {
+        if !self.system_checked {
+            self.system_checked = true;
+            self.system = system::System::new();
+            self.read_system();
+            self.fingerprint = fingerprint(&self.system_contents);
+        }
+    }
+}
+
+/// Cheap summary of the system clipboard. Images are compared by size
+/// rather than pixel by pixel.
+fn fingerprint(contents: &Contents) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.text.hash(&mut hasher);
+    contents.html.hash(&mut hasher);
+    contents.file_urls.hash(&mut hasher);
+    contents
+        .image
+        .as_ref()
+        .map(|image| (image.width, image.height))
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32")))]
+mod system {
+    use super::PasteboardImage;
+    use std::borrow::Cow;
+    use std::path::PathBuf;
+
+    pub struct System(arboard::Clipboard);
+
+    impl System {
+        pub fn new() -> Option<Self> {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => Some(Self(clipboard)),
+                Err(err) => {
+                    println!("unable to open the system clipboard: {}", err);
+                    None
+                }
+            }
+        }
+
+        pub fn text(&mut self) -> Option<String> {
+            self.0.get_text().ok()
+        }
+
+        pub fn html(&mut self) -> Option<String> {
+            self.0.get().html().ok()
+        }
+
+        pub fn image(&mut self) -> Option<PasteboardImage> {
+            self.0.get_image().ok().map(|image| PasteboardImage {
+                width: image.width,
+                height: image.height,
+                rgba: image.bytes.into_owned(),
+            })
+        }
+
+        pub fn file_urls(&mut self) -> Vec<PathBuf> {
+            self.0.get().file_list().unwrap_or_default()
+        }
+
+        pub fn set_text(&mut self, text: &str) {
+            report(self.0.set_text(text));
+        }
+
+        pub fn set_html(&mut self, html: &str, alt_text: &str) {
+            report(self.0.set_html(html, Some(alt_text)));
+        }
+
+        pub fn set_image(&mut self, image: &PasteboardImage) {
+            report(self.0.set_image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: Cow::Borrowed(&image.rgba),
+            }));
+        }
+
+        pub fn clear(&mut self) {
+            report(self.0.clear());
+        }
+    }
+
+    fn report(result: Result<(), arboard::Error>) {
+        if let Err(err) = result {
+            println!("unable to write to the system clipboard: {}", err);
+        }
+    }
+}
+
+#[cfg(not(all(feature = "clipboard", not(target_arch = "wasm32"))))]
+mod system {
+    use super::PasteboardImage;
+    use std::path::PathBuf;
+
+    /// No system clipboard in this build.
+    pub enum System {}
+
+    impl System {
+        pub fn new() -> Option<Self> {
+            None
+        }
+
+        pub fn text(&mut self) -> Option<String> {
+            match *self {}
+        }
+
+        pub fn html(&mut self) -> Option<String> {
+            match *self {}
+        }
+
+        pub fn image(&mut self) -> Option<PasteboardImage> {
+            match *self {}
+        }
+
+        pub fn file_urls(&mut self) -> Vec<PathBuf> {
+            match *self {}
+        }
+
+        pub fn set_text(&mut self, _text: &str) {
+            match *self {}
+        }
+
+        pub fn set_html(&mut self, _html: &str, _alt_text: &str) {
+            match *self {}
+        }
+
+        pub fn set_image(&mut self, _image: &PasteboardImage) {
+            match *self {}
+        }
+
+        pub fn clear(&mut self) {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "clipboard")))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_pasteboard() {
+        let mut pb = Pasteboard::default();
+        assert_eq!(pb.change_count(), 0);
+        assert_eq!(pb.text(), None);
+
+        pb.set_html("<b>hi</b>", "hi");
+        assert_eq!(pb.change_count(), 1);
+        assert_eq!(pb.text().as_deref(), Some("hi"));
+        assert_eq!(pb.html().as_deref(), Some("<b>hi</b>"));
+
+        pb.set_data("com.example.shape", vec![1, 2, 3]);
+        assert_eq!(pb.change_count(), 2);
+        assert_eq!(pb.data("com.example.shape"), Some(vec![1, 2, 3]));
+        assert_eq!(pb.text().as_deref(), Some("hi"));
+
+        // Copying something else replaces everything.
+        pb.set_file_urls(vec!["/tmp/a.txt".into()]);
+        assert_eq!(pb.data("com.example.shape"), None);
+        assert_eq!(pb.html(), None);
+        assert_eq!(pb.file_urls(), vec![PathBuf::from("/tmp/a.txt")]);
+        assert_eq!(pb.text().as_deref(), Some("/tmp/a.txt"));
+    }
+}
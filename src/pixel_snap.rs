@@ -0,0 +1,71 @@
+use crate::*;
+
+/// Whether hairlines and borders are drawn on whole physical pixels, so
+/// they stay crisp at fractional scale factors. On by default; set it with
+/// the `pixel_snap` modifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelSnap(pub bool);
+
+impl Default for PixelSnap {
+    fn default() -> Self {
+        PixelSnap(true)
+    }
+}
+
+/// Rounds a length in points to whole pixels, at least one.
+pub(crate) fn snap_length(length: f32, scale: f32) -> f32 {
+    (length * scale).round().max(1.0) / scale
+}
+
+/// Moves the edges of `rect`, drawn with `xform`, onto physical pixels,
+/// keeping it at least a pixel across.
+pub(crate) fn snap_rect(rect: LocalRect, xform: LocalToWorld, scale: f32) -> LocalRect {
+    let inverse = match xform.inverse() {
+        Some(inverse) => inverse,
+        None => return rect,
+    };
+    let world = xform.outer_transformed_rect(&rect);
+    let px = |v: f32| (v * scale).round() / scale;
+    let min = WorldPoint::new(px(world.min_x()), px(world.min_y()));
+    let max = WorldPoint::new(
+        px(world.max_x()).max(min.x + 1.0 / scale),
+        px(world.max_y()).max(min.y + 1.0 / scale),
+    );
+    inverse.outer_transformed_rect(&WorldRect::new(min, (max - min).to_size()))
+}
+
+impl Context {
+    /// Snaps `rect` to physical pixels if `PixelSnap` is on, for views
+    /// which draw hairlines.
+    pub(crate) fn pixel_snap(&mut self, rect: LocalRect, xform: LocalToWorld) -> LocalRect {
+        if self.init_env(&PixelSnap::default).0 {
+            snap_rect(rect, xform, self.scale_factor())
+        } else {
+            rect
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_snap_rect() {
+        let xform = LocalToWorld::translation(0.3, 0.0);
+        let rect = LocalRect::new([10.0, 10.0].into(), [100.0, 0.5].into());
+
+        // At 1.5x, pixels are every 2/3 of a point.
+        let snapped = snap_rect(rect, xform, 1.5);
+        let world = xform.outer_transformed_rect(&snapped);
+        for v in [world.min_x(), world.max_x(), world.min_y(), world.max_y()] {
+            let px = v * 1.5;
+            assert!((px - px.round()).abs() < 1e-4, "{} isn't on a pixel", v);
+        }
+        assert!((world.height() - 2.0 / 3.0).abs() < 1e-4);
+
+        assert_eq!(snap_length(1.0, 1.25), 0.8);
+        assert_eq!(snap_length(0.1, 2.0), 0.5);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::*;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often to check whether a process has exited, once its output ends.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Identifies a process started with `Context::run_command`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ProcessId(usize);
+
+type LineFunc = Rc<dyn Fn(&mut Context, &str)>;
+type ExitFunc = Rc<dyn Fn(&mut Context, Result<ExitStatus, String>)>;
+
+pub(crate) struct Process {
+    id: ProcessId,
+    child: Option<Arc<Mutex<Child>>>,
+    on_stdout: Option<LineFunc>,
+    on_stderr: Option<LineFunc>,
+    on_exit: Option<ExitFunc>,
+}
+
+#[derive(Copy, Clone)]
+enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Calls `f` with each line, without the line ending. Invalid UTF-8 is
+/// replaced rather than ending the output.
+fn read_lines(reader: impl Read, mut f: impl FnMut(String)) {
+    let mut reader = BufReader::new(reader);
+    let mut buf = vec![];
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf);
+                f(line.trim_end_matches(&['\r', '\n'][..]).to_string());
+            }
+        }
+    }
+}
+
+/// A process started by `Context::run_command`. Add callbacks before
+/// returning to the event loop.
+pub struct RunningProcess<'a> {
+    cx: &'a mut Context,
+    id: ProcessId,
+}
+
+impl<'a> RunningProcess<'a> {
+    pub fn id(&self) -> ProcessId {
+        self.id
+    }
+
+    fn process(&mut self) -> &mut Process {
+        let id = self.id;
+        self.cx.processes.iter_mut().find(|p| p.id == id).unwrap()
+    }
+
+    /// Calls `f` with each line the process writes to its standard output.
+    pub fn on_stdout(mut self, f: impl Fn(&mut Context, &str) + 'static) -> Self {
+        self.process().on_stdout = Some(Rc::new(f));
+        self
+    }
+
+    /// Calls `f` with each line the process writes to its standard error.
+    pub fn on_stderr(mut self, f: impl Fn(&mut Context, &str) + 'static) -> Self {
+        self.process().on_stderr = Some(Rc::new(f));
+        self
+    }
+
+    /// Calls `f` after all output has been delivered and the process has
+    /// exited, or with an error if it couldn't be started.
+    pub fn on_exit(
+        mut self,
+        f: impl Fn(&mut Context, Result<ExitStatus, String>) + 'static,
+    ) -> Self {
+        self.process().on_exit = Some(Rc::new(f));
+        self
+    }
+}
+
+impl Context {
+    /// Starts `command`, delivering its output a line at a time on the UI
+    /// thread. Standard input is closed.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// # let cx = &mut Context::new();
+    /// let mut command = std::process::Command::new("cargo");
+    /// command.arg("build");
+    /// let build = cx
+    ///     .run_command(command)
+    ///     .on_stderr(|_, line| println!("{}", line))
+    ///     .on_exit(|_, status| println!("done: {:?}", status))
+    ///     .id();
+    /// cx.cancel_process(build);
+    /// ```
+    pub fn run_command(&mut self, mut command: Command) -> RunningProcess {
+        let id = ProcessId(self.next_process_id);
+        self.next_process_id += 1;
+
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+
+        let child = match child {
+            Ok(mut child) => {
+                let readers: Vec<_> = vec![
+                    (
+                        Stream::Stdout,
+                        child
+                            .stdout
+                            .take()
+                            .map(|r| Box::new(r) as Box<dyn Read + Send>),
+                    ),
+                    (
+                        Stream::Stderr,
+                        child
+                            .stderr
+                            .take()
+                            .map(|r| Box::new(r) as Box<dyn Read + Send>),
+                    ),
+                ]
+                .into_iter()
+                .filter_map(|(stream, reader)| reader.map(|reader| (stream, reader)))
+                .map(|(stream, reader)| {
+                    std::thread::spawn(move || {
+                        read_lines(reader, |line| {
+                            on_main(move |cx| cx.process_output(id, stream, &line))
+                        })
+                    })
+                })
+                .collect();
+
+                let child = Arc::new(Mutex::new(child));
+                let waiter = child.clone();
+                std::thread::spawn(move || {
+                    for reader in readers {
+                        let _ = reader.join();
+                    }
+                    // Don't hold the lock while waiting, so the process can be killed.
+                    let status = loop {
+                        match waiter.lock().unwrap().try_wait() {
+                            Ok(Some(status)) => break Ok(status),
+                            Ok(None) => (),
+                            Err(err) => break Err(err.to_string()),
+                        }
+                        std::thread::sleep(EXIT_POLL_INTERVAL);
+                    };
+                    on_main(move |cx| cx.process_exited(id, status));
+                });
+                Some(child)
+            }
+            Err(err) => {
+                let err = format!("unable to run {:?}: {}", command.get_program(), err);
+                on_main(move |cx| cx.process_exited(id, Err(err)));
+                None
+            }
+        };
+
+        self.processes.push(Process {
+            id,
+            child,
+            on_stdout: None,
+            on_stderr: None,
+            on_exit: None,
+        });
+        RunningProcess { cx: self, id }
+    }
+
+    /// Kills a process started with `run_command`. Its `on_exit` callback
+    /// is still called.
+    pub fn cancel_process(&mut self, id: ProcessId) {
+        if let Some(child) = self
+            .processes
+            .iter()
+            .find(|p| p.id == id)
+            .and_then(|p| p.child.as_ref())
+        {
+            if let Err(err) = child.lock().unwrap().kill() {
+                println!("unable to cancel process: {}", err);
+            }
+        }
+    }
+
+    fn process_output(&mut self, id: ProcessId, stream: Stream, line: &str) {
+        let func = self
+            .processes
+            .iter()
+            .find(|p| p.id == id)
+            .and_then(|p| match stream {
+                Stream::Stdout => p.on_stdout.clone(),
+                Stream::Stderr => p.on_stderr.clone(),
+            });
+        if let Some(f) = func {
+            f(self, line);
+        }
+    }
+
+    fn process_exited(&mut self, id: ProcessId, status: Result<ExitStatus, String>) {
+        if let Some(i) = self.processes.iter().position(|p| p.id == id) {
+            let process = self.processes.remove(i);
+            if let Some(f) = process.on_exit {
+                f(self, status);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_read_lines() {
+        let mut lines = vec![];
+        read_lines(&b"one\r\ntwo\n\xffthree"[..], |line| lines.push(line));
+        assert_eq!(lines, ["one", "two", "\u{fffd}three"]);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::*;
+
+/// How hotkeys for commands are matched against key presses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShortcutMatching {
+    /// Match the character the key produces in the current keyboard layout,
+    /// so Cmd+Z follows the Z key on AZERTY and Dvorak.
+    Character,
+
+    /// Match the key's position on a US QWERTY keyboard, whatever the layout.
+    /// Good for position-based bindings like WASD.
+    Physical,
+
+    /// Match by character, but fall back to the key's position when the layout
+    /// doesn't produce Latin letters (Cyrillic, Greek, ...). This is what macOS,
+    /// Windows and GTK do for menu shortcuts.
+    Auto,
+}
+
+impl Default for ShortcutMatching {
+    fn default() -> Self {
+        ShortcutMatching::Auto
+    }
+}
+
+impl ShortcutMatching {
+    /// Does a key press match `hotkey`? `key` is the key as interpreted by the
+    /// keyboard layout, `physical` the key at that position on a US layout.
+    pub fn matches(&self, hotkey: HotKey, key: Option<&Key>, physical: Option<HotKey>) -> bool {
+        let character = match key {
+            Some(Key::Character(c)) => HotKey::from_char(*c),
+            _ => None,
+        };
+        match self {
+            ShortcutMatching::Character => character == Some(hotkey),
+            ShortcutMatching::Physical => physical == Some(hotkey),
+            ShortcutMatching::Auto => match character {
+                Some(c) => c == hotkey,
+                None => physical == Some(hotkey),
+            },
+        }
+    }
+}
+
+impl HotKey {
+    /// The hotkey for a Latin letter (ignoring case) or comma.
+    pub fn from_char(c: char) -> Option<HotKey> {
+        use HotKey::*;
+        const KEYS: [HotKey; 26] = [
+            KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM, KeyN,
+            KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV, KeyW, KeyX, KeyY, KeyZ,
+        ];
+        let c = c.to_ascii_lowercase();
+        if c == ',' {
+            Some(Comma)
+        } else if c.is_ascii_lowercase() {
+            Some(KEYS[(c as u8 - b'a') as usize])
+        } else {
+            None
+        }
+    }
+
+    /// The key at a scancode's position on a US QWERTY keyboard.
+    ///
+    /// Scancodes are platform specific: virtual key codes on macOS,
+    /// and evdev (which matches PC set 1 for letters) elsewhere.
+    pub fn from_scancode(scancode: u32) -> Option<HotKey> {
+        use HotKey::*;
+
+        #[cfg(target_os = "macos")]
+        let key = match scancode {
+            0 => KeyA,
+            1 => KeyS,
+            2 => KeyD,
+            3 => KeyF,
+            4 => KeyH,
+            5 => KeyG,
+            6 => KeyZ,
+            7 => KeyX,
+            8 => KeyC,
+            9 => KeyV,
+            11 => KeyB,
+            12 => KeyQ,
+            13 => KeyW,
+            14 => KeyE,
+            15 => KeyR,
+            16 => KeyY,
+            17 => KeyT,
+            31 => KeyO,
+            32 => KeyU,
+            34 => KeyI,
+            35 => KeyP,
+            37 => KeyL,
+            38 => KeyJ,
+            40 => KeyK,
+            45 => KeyN,
+            46 => KeyM,
+            43 => Comma,
+            _ => return None,
+        };
+
+        #[cfg(not(target_os = "macos"))]
+        let key = match scancode {
+            16 => KeyQ,
+            17 => KeyW,
+            18 => KeyE,
+            19 => KeyR,
+            20 => KeyT,
+            21 => KeyY,
+            22 => KeyU,
+            23 => KeyI,
+            24 => KeyO,
+            25 => KeyP,
+            30 => KeyA,
+            31 => KeyS,
+            32 => KeyD,
+            33 => KeyF,
+            34 => KeyG,
+            35 => KeyH,
+            36 => KeyJ,
+            37 => KeyK,
+            38 => KeyL,
+            44 => KeyZ,
+            45 => KeyX,
+            46 => KeyC,
+            47 => KeyV,
+            48 => KeyB,
+            49 => KeyN,
+            50 => KeyM,
+            51 => Comma,
+            _ => return None,
+        };
+
+        Some(key)
+    }
+}
+
+/// A key pressed together with modifiers, like Cmd+Shift+S.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    pub mods: KeyboardModifiers,
+    pub key: HotKey,
+}
+
+impl KeyChord {
+    pub fn new(mods: KeyboardModifiers, key: HotKey) -> Self {
+        Self { mods, key }
+    }
+}
+
+impl From<HotKey> for KeyChord {
+    fn from(key: HotKey) -> Self {
+        Self {
+            mods: Default::default(),
+            key,
+        }
+    }
+}
+
+impl From<(KeyboardModifiers, HotKey)> for KeyChord {
+    fn from((mods, key): (KeyboardModifiers, HotKey)) -> Self {
+        Self { mods, key }
+    }
+}
+
+/// Finds the command whose hotkey matches a key press.
+pub(crate) fn find_shortcut<'a>(
+    commands: &'a [CommandInfo],
+    matching: ShortcutMatching,
+    key: Option<&Key>,
+    physical: Option<HotKey>,
+) -> Option<&'a CommandInfo> {
+    commands.iter().find(|cmd| match cmd.key {
+        Some(hotkey) => matching.matches(hotkey, key, physical),
+        None => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_shortcut_matching() {
+        // AZERTY: the key at QWERTY's Q position types 'a'.
+        let key = Key::Character('a');
+        let physical = Some(HotKey::KeyQ);
+
+        assert!(ShortcutMatching::Character.matches(HotKey::KeyA, Some(&key), physical));
+        assert!(ShortcutMatching::Physical.matches(HotKey::KeyQ, Some(&key), physical));
+        assert!(ShortcutMatching::Auto.matches(HotKey::KeyA, Some(&key), physical));
+        assert!(!ShortcutMatching::Auto.matches(HotKey::KeyQ, Some(&key), physical));
+
+        // Cyrillic layout: no Latin character, so Auto uses the position.
+        let key = Key::Character('с');
+        let physical = Some(HotKey::KeyC);
+        assert!(!ShortcutMatching::Character.matches(HotKey::KeyC, Some(&key), physical));
+        assert!(ShortcutMatching::Auto.matches(HotKey::KeyC, Some(&key), physical));
+        assert!(ShortcutMatching::Auto.matches(HotKey::KeyC, None, physical));
+    }
+
+    #[test]
+    fn test_find_shortcut() {
+        let commands = vec![
+            CommandInfo {
+                path: "File:New".into(),
+                key: Some(HotKey::KeyN),
+            },
+            CommandInfo {
+                path: "Edit:Undo".into(),
+                key: Some(HotKey::KeyZ),
+            },
+        ];
+        let cmd = find_shortcut(
+            &commands,
+            ShortcutMatching::Auto,
+            Some(&Key::Character('Z')),
+            HotKey::from_scancode(44),
+        );
+        assert_eq!(cmd.map(|c| c.path.as_str()), Some("Edit:Undo"));
+    }
+}
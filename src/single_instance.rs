@@ -0,0 +1,190 @@
+use crate::*;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener as Listener, UnixStream as Stream};
+
+#[cfg(not(unix))]
+use std::net::{TcpListener as Listener, TcpStream as Stream};
+
+/// Starts every message, so we don't act on stray connections.
+const MAGIC: &str = "rui-single-instance-1";
+
+/// Where the running instance listens. On Windows we use a loopback TCP
+/// port, written to this file with a token other users can't read.
+fn socket_path(app_id: &str) -> PathBuf {
+    let name: String = app_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // Each user gets their own instance, so keep out of each other's way.
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => PathBuf::from(dir).join(format!("{}.rui-instance", name)),
+        None => std::env::temp_dir().join(match user_id() {
+            Some(uid) => format!("{}.{}.rui-instance", name, uid),
+            None => format!("{}.rui-instance", name),
+        }),
+    }
+}
+
+/// Our uid, where /proc has it. The temporary directory is per-user on
+/// macOS and Windows anyway.
+#[cfg(unix)]
+fn user_id() -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata("/proc/self").ok().map(|m| m.uid())
+}
+
+#[cfg(not(unix))]
+fn user_id() -> Option<u32> {
+    None
+}
+
+/// The running instance and the token it expects. Only the socket's owner
+/// can connect to it, so there's no token.
+#[cfg(unix)]
+fn connect(path: &Path) -> Option<(Stream, String)> {
+    Some((Stream::connect(path).ok()?, String::new()))
+}
+
+/// Anyone can connect to a loopback port, so messages carry the token from
+/// the port file, which is in the user's own temporary directory.
+#[cfg(not(unix))]
+fn connect(path: &Path) -> Option<(Stream, String)> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let (port, token) = contents.trim().split_once(' ')?;
+    let port: u16 = port.parse().ok()?;
+    Some((
+        Stream::connect(("127.0.0.1", port)).ok()?,
+        token.to_string(),
+    ))
+}
+
+#[cfg(unix)]
+fn listen(path: &Path) -> std::io::Result<(Listener, String)> {
+    // Nobody answered, so the socket is left over from an instance that quit.
+    let _ = std::fs::remove_file(path);
+    Ok((Listener::bind(path)?, String::new()))
+}
+
+#[cfg(not(unix))]
+fn listen(path: &Path) -> std::io::Result<(Listener, String)> {
+    let listener = Listener::bind(("127.0.0.1", 0))?;
+    let token = random_token();
+    let port = listener.local_addr()?.port();
+    std::fs::write(path, format!("{} {}", port, token))?;
+    Ok((listener, token))
+}
+
+/// 128 bits from the OS, which seeds each `RandomState`.
+#[cfg(not(unix))]
+fn random_token() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    (0..2)
+        .map(|i| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u32(i);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
+/// Encodes the working directory and arguments, separated by NULs, which
+/// can't appear in either.
+fn encode(app_id: &str, token: &str, cwd: &Path, args: &[String]) -> Vec<u8> {
+    let mut fields = vec![
+        MAGIC.to_string(),
+        app_id.to_string(),
+        token.to_string(),
+        cwd.to_string_lossy().into_owned(),
+    ];
+    fields.extend(args.iter().cloned());
+    fields.join("\0").into_bytes()
+}
+
+/// The files and URLs named in a message from another instance.
+fn decode(app_id: &str, token: &str, message: &[u8]) -> Option<(Vec<PathBuf>, Vec<String>)> {
+    let message = std::str::from_utf8(message).ok()?;
+    let mut fields = message.split('\0');
+    if fields.next()? != MAGIC || fields.next()? != app_id || fields.next()? != token {
+        return None;
+    }
+    let cwd = PathBuf::from(fields.next()?);
+    Some(command_line_opens(&cwd, fields))
+}
+
+/// Makes sure only one instance of the app runs. Call this first thing
+/// in `main`, before building any UI.
+///
+/// If another instance with the same `app_id` (like "com.example.editor")
+/// is running, this sends it our command-line arguments and exits. That
+/// instance gets `Event::OpenFiles` and `Event::OpenUrl` for the files and
+/// URLs named, which is how "Open With" and links reach an app that's
+/// already open.
+pub fn single_instance(app_id: &str) {
+    let path = socket_path(app_id);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    if let Some((mut stream, token)) = connect(&path) {
+        match stream.write_all(&encode(app_id, &token, &cwd, &args)) {
+            Ok(()) => std::process::exit(0),
+            Err(err) => println!("unable to reach running instance: {}", err),
+        }
+    }
+
+    let (listener, token) = match listen(&path) {
+        Ok(listening) => listening,
+        Err(err) => {
+            println!("unable to listen for other instances: {}", err);
+            return;
+        }
+    };
+
+    let app_id = app_id.to_string();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut message = vec![];
+            match stream.and_then(|mut s| s.read_to_end(&mut message)) {
+                Ok(_) => {
+                    if let Some((files, urls)) = decode(&app_id, &token, &message) {
+                        on_main(move |cx| {
+                            cx.open_files.extend(files);
+                            cx.open_urls.extend(urls);
+                        });
+                    }
+                }
+                Err(err) => println!("error reading from other instance: {}", err),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        let cwd = PathBuf::from("/home/me");
+        let args = ["--new-window".to_string(), "notes.txt".to_string()];
+        let message = encode("com.example.app", "1234", &cwd, &args);
+
+        assert_eq!(
+            decode("com.example.app", "1234", &message),
+            Some((vec![cwd.join("notes.txt")], vec![]))
+        );
+        assert_eq!(decode("com.example.other", "1234", &message), None);
+        assert_eq!(decode("com.example.app", "5678", &message), None);
+        assert_eq!(decode("com.example.app", "", b"GET / HTTP/1.1"), None);
+    }
+}
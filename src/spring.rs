@@ -0,0 +1,181 @@
+use crate::*;
+
+/// Longest step taken when simulating a spring, in seconds. Longer frames
+/// are split up so stiff springs don't blow up.
+const MAX_STEP: f32 = 1.0 / 120.0;
+
+/// Values closer than this to where they're going, and moving slower, are
+/// at rest.
+const REST: f32 = 1e-3;
+
+/// Values which a `Spring` can animate, as up to four numbers.
+pub trait Animatable: Copy + PartialEq + 'static {
+    fn components(&self) -> [f32; 4];
+    fn from_components(c: [f32; 4]) -> Self;
+}
+
+impl Animatable for f32 {
+    fn components(&self) -> [f32; 4] {
+        [*self, 0.0, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        c[0]
+    }
+}
+
+impl<U: 'static> Animatable for euclid::Vector2D<f32, U> {
+    fn components(&self) -> [f32; 4] {
+        [self.x, self.y, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        Self::new(c[0], c[1])
+    }
+}
+
+impl<U: 'static> Animatable for euclid::Point2D<f32, U> {
+    fn components(&self) -> [f32; 4] {
+        [self.x, self.y, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        Self::new(c[0], c[1])
+    }
+}
+
+impl<U: 'static> Animatable for euclid::Size2D<f32, U> {
+    fn components(&self) -> [f32; 4] {
+        [self.width, self.height, 0.0, 0.0]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        Self::new(c[0], c[1])
+    }
+}
+
+impl Animatable for Color {
+    fn components(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+    fn from_components(c: [f32; 4]) -> Self {
+        Color::new(c[0], c[1], c[2], c[3])
+    }
+}
+
+/// A damped spring pulling a value towards a target.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+impl Default for Spring {
+    /// Slightly underdamped, so things settle with a small bounce.
+    fn default() -> Self {
+        Self {
+            stiffness: 400.0,
+            damping: 28.0,
+        }
+    }
+}
+
+impl Spring {
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Self { stiffness, damping }
+    }
+
+    /// A spring which settles as fast as it can without overshooting.
+    pub fn critically_damped(stiffness: f32) -> Self {
+        Self::new(stiffness, 2.0 * stiffness.sqrt())
+    }
+
+    /// Moves `value` along `dt` seconds towards `target`, returning the new
+    /// value and velocity. Snaps to the target once at rest.
+    pub fn step<T: Animatable>(&self, value: T, velocity: T, target: T, dt: f32) -> (T, T) {
+        let (mut x, mut v, t) = (
+            value.components(),
+            velocity.components(),
+            target.components(),
+        );
+        let steps = (dt / MAX_STEP).ceil().max(1.0);
+        let h = dt / steps;
+        for _ in 0..steps as usize {
+            for i in 0..4 {
+                v[i] += (self.stiffness * (t[i] - x[i]) - self.damping * v[i]) * h;
+                x[i] += v[i] * h;
+            }
+        }
+        let at_rest = (0..4).all(|i| (t[i] - x[i]).abs() < REST && v[i].abs() < REST);
+        if at_rest {
+            (target, T::from_components([0.0; 4]))
+        } else {
+            (T::from_components(x), T::from_components(v))
+        }
+    }
+}
+
+/// A value being animated by a spring. Changing the target mid-flight
+/// keeps the velocity, so the motion carries on smoothly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpringState<T> {
+    pub value: T,
+    pub velocity: T,
+    pub target: T,
+}
+
+impl<T: Animatable> SpringState<T> {
+    /// At rest at `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            velocity: T::from_components([0.0; 4]),
+            target: value,
+        }
+    }
+
+    pub fn is_moving(&self) -> bool {
+        self.value != self.target || self.velocity != T::from_components([0.0; 4])
+    }
+
+    /// Steps the animation. Returns false once there's nothing left to do.
+    pub fn step(&mut self, spring: &Spring, dt: f32) -> bool {
+        if !self.is_moving() {
+            return false;
+        }
+        let (value, velocity) = spring.step(self.value, self.velocity, self.target, dt);
+        self.value = value;
+        self.velocity = velocity;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_spring_settles() {
+        let spring = Spring::default();
+        let mut s = SpringState::new(0.0_f32);
+        s.target = 10.0;
+        for _ in 0..120 {
+            s.step(&spring, 1.0 / 60.0);
+        }
+        assert_eq!(s, SpringState::new(10.0));
+    }
+
+    #[test]
+    fn test_retarget_keeps_velocity() {
+        let spring = Spring::critically_damped(100.0);
+        let mut s = SpringState::new(LocalOffset::zero());
+        s.target = [100.0, 0.0].into();
+        s.step(&spring, 0.1);
+        let velocity = s.velocity;
+        assert!(velocity.x > 0.0);
+
+        // Heading back, it's still moving forwards at first.
+        s.target = LocalOffset::zero();
+        assert_eq!(s.velocity, velocity);
+        let x = s.value.x;
+        s.step(&spring, 1.0 / 120.0);
+        assert!(s.value.x > x);
+    }
+}
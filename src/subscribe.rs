@@ -0,0 +1,193 @@
+use crate::*;
+use std::io::{BufRead, BufReader};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// First wait before reconnecting. Doubles after each failure.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Identifies a subscription from `Context::subscribe_ws` or
+/// `Context::subscribe_sse`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(usize);
+
+pub(crate) struct Subscription {
+    id: SubscriptionId,
+    func: Rc<dyn Fn(&mut Context, &str)>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Collects the `data` lines of server-sent events.
+#[derive(Default)]
+struct SseParser {
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Returns an event's data when `line` ends it.
+    fn line(&mut self, line: &str) -> Option<String> {
+        if line.is_empty() {
+            if self.data.is_empty() {
+                return None;
+            }
+            let data = self.data.join("\n");
+            self.data.clear();
+            return Some(data);
+        }
+        if let Some(value) = line.strip_prefix("data") {
+            if value.is_empty() || value.starts_with(':') {
+                let value = value.strip_prefix(':').unwrap_or(value);
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+        }
+        // Comments and other fields are ignored.
+        None
+    }
+}
+
+/// Reads messages from a WebSocket until it closes.
+fn read_ws(
+    url: &str,
+    cancelled: &AtomicBool,
+    deliver: &mut dyn FnMut(String),
+) -> Result<(), String> {
+    let (mut socket, _) = tungstenite::connect(url).map_err(|err| err.to_string())?;
+    while !cancelled.load(Ordering::Relaxed) {
+        match socket.read().map_err(|err| err.to_string())? {
+            tungstenite::Message::Text(text) => deliver(text),
+            tungstenite::Message::Close(_) => break,
+            // Pings are answered by tungstenite.
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+/// Reads server-sent events until the stream ends.
+fn read_sse(
+    url: &str,
+    cancelled: &AtomicBool,
+    deliver: &mut dyn FnMut(String),
+) -> Result<(), String> {
+    let response = ureq::get(url)
+        .set("Accept", "text/event-stream")
+        .call()
+        .map_err(|err| err.to_string())?;
+    let mut parser = SseParser::default();
+    for line in BufReader::new(response.into_reader()).lines() {
+        if cancelled.load(Ordering::Relaxed) {
+            break;
+        }
+        if let Some(data) = parser.line(&line.map_err(|err| err.to_string())?) {
+            deliver(data);
+        }
+    }
+    Ok(())
+}
+
+type Reader = fn(&str, &AtomicBool, &mut dyn FnMut(String)) -> Result<(), String>;
+
+impl Context {
+    fn subscribe(
+        &mut self,
+        url: &str,
+        read: Reader,
+        f: impl Fn(&mut Context, &str) + 'static,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next_subscription_id);
+        self.next_subscription_id += 1;
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let url = url.to_string();
+        let stop = cancelled.clone();
+        std::thread::spawn(move || {
+            let mut backoff = MIN_BACKOFF;
+            while !stop.load(Ordering::Relaxed) {
+                let mut connected = false;
+                let result = read(&url, &stop, &mut |message| {
+                    connected = true;
+                    on_main(move |cx| cx.deliver_message(id, &message));
+                });
+                if let Err(err) = result {
+                    println!("subscription to {} failed: {}", url, err);
+                }
+                if connected {
+                    backoff = MIN_BACKOFF;
+                }
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                std::thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        });
+
+        self.subscriptions.push(Subscription {
+            id,
+            func: Rc::new(f),
+            cancelled,
+        });
+        id
+    }
+
+    /// Calls `f` on the UI thread with each text message from the WebSocket
+    /// at `url`, reconnecting with backoff when the connection drops.
+    pub fn subscribe_ws(
+        &mut self,
+        url: &str,
+        f: impl Fn(&mut Context, &str) + 'static,
+    ) -> SubscriptionId {
+        self.subscribe(url, read_ws, f)
+    }
+
+    /// Calls `f` on the UI thread with the data of each server-sent event
+    /// from `url`, reconnecting with backoff when the stream ends.
+    pub fn subscribe_sse(
+        &mut self,
+        url: &str,
+        f: impl Fn(&mut Context, &str) + 'static,
+    ) -> SubscriptionId {
+        self.subscribe(url, read_sse, f)
+    }
+
+    /// Stops delivering messages. The connection closes once the next
+    /// message arrives.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) {
+        self.subscriptions.retain(|s| s.id != id);
+    }
+
+    fn deliver_message(&mut self, id: SubscriptionId, message: &str) {
+        let func = self
+            .subscriptions
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.func.clone());
+        if let Some(f) = func {
+            f(self, message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_sse_parser() {
+        let mut parser = SseParser::default();
+        let stream = ": comment\nevent: tick\ndata: one\ndata:two\n\n\ndata\n\n";
+        let events: Vec<String> = stream.lines().filter_map(|l| parser.line(l)).collect();
+        assert_eq!(events, ["one\ntwo", ""]);
+    }
+}
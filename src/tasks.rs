@@ -0,0 +1,74 @@
+use crate::*;
+use futures::channel::mpsc::{unbounded, UnboundedSender};
+use futures::executor::block_on;
+use futures::future::{BoxFuture, FutureExt};
+use futures::StreamExt;
+use std::future::Future;
+use std::sync::Mutex;
+
+lazy_static! {
+    /// Sends futures to a background thread, which runs them all
+    /// concurrently.
+    static ref TASKS: Mutex<UnboundedSender<BoxFuture<'static, ()>>> = {
+        let (sender, receiver) = unbounded();
+        std::thread::Builder::new()
+            .name("rui tasks".into())
+            .spawn(move || block_on(receiver.for_each_concurrent(None, |task| task)))
+            .expect("couldn't start the task thread");
+        Mutex::new(sender)
+    };
+}
+
+impl Context {
+    /// Runs a future in the background. Futures share one thread, so
+    /// they shouldn't block it; use a thread for blocking work. Use
+    /// `on_main` from the future to update the UI, or `spawn_then`.
+    pub fn spawn(&mut self, future: impl Future<Output = ()> + Send + 'static) {
+        if TASKS
+            .lock()
+            .unwrap()
+            .unbounded_send(future.boxed())
+            .is_err()
+        {
+            println!("error spawning task: the task thread has stopped");
+        }
+    }
+
+    /// Runs a future in the background, then calls `f` with its output
+    /// on the UI thread.
+    pub fn spawn_then<T: Send + 'static>(
+        &mut self,
+        future: impl Future<Output = T> + Send + 'static,
+        f: impl FnOnce(&mut Context, T) + Send + 'static,
+    ) {
+        self.spawn(async move {
+            let value = future.await;
+            on_main(move |cx| f(cx, value));
+        })
+    }
+
+    /// Runs a future in the background and sets `binding` to its output,
+    /// such as a `state` the view shows.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// state(
+    ///     || "loading".to_string(),
+    ///     |s, cx| {
+    ///         hstack((
+    ///             button("load", move |cx| {
+    ///                 cx.spawn_into(s, async { "loaded".to_string() });
+    ///             }),
+    ///             text(&cx[s]),
+    ///         ))
+    ///     },
+    /// );
+    /// ```
+    pub fn spawn_into<T: Send + 'static, B: Binding<T> + Send>(
+        &mut self,
+        binding: B,
+        future: impl Future<Output = T> + Send + 'static,
+    ) {
+        self.spawn_then(future, move |cx, value| *binding.get_mut(cx) = value)
+    }
+}
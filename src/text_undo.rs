@@ -0,0 +1,168 @@
+use crate::*;
+
+/// Most edits a text view remembers.
+const UNDO_LIMIT: usize = 100;
+
+/// What an edit did, for deciding which edits undo together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EditKind {
+    /// Typed characters undo a word at a time.
+    Typing,
+    /// A run of Backspace or Delete undoes together.
+    Deleting,
+    /// Pastes, cuts and the like each undo on their own.
+    Other,
+}
+
+impl EditKind {
+    /// The kind of edit a key press makes.
+    pub fn for_key(key: &Key) -> Self {
+        match key {
+            Key::Character(_) | Key::Space => EditKind::Typing,
+            Key::Backspace | Key::Delete => EditKind::Deleting,
+            _ => EditKind::Other,
+        }
+    }
+}
+
+/// Undo or redo, from a shortcut.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum UndoAction {
+    Undo,
+    Redo,
+}
+
+/// The undo shortcuts: Cmd+Z to undo and Shift+Cmd+Z to redo, with Ctrl
+/// instead of Cmd and also Ctrl+Y to redo off macOS.
+pub(crate) fn undo_shortcut(key: &Key, mods: &KeyboardModifiers) -> Option<UndoAction> {
+    if !mods.shortcut() {
+        return None;
+    }
+    let hotkey = match key {
+        Key::Character(c) => HotKey::from_char(*c),
+        _ => None,
+    };
+    match hotkey {
+        Some(HotKey::KeyZ) if mods.shift => Some(UndoAction::Redo),
+        Some(HotKey::KeyZ) => Some(UndoAction::Undo),
+        Some(HotKey::KeyY) if !cfg!(target_os = "macos") => Some(UndoAction::Redo),
+        _ => None,
+    }
+}
+
+/// Text and cursor from before or after an edit.
+#[derive(Clone, Debug, PartialEq)]
+struct Snapshot {
+    text: String,
+    cursor: usize,
+}
+
+/// Undo and redo stacks for a text view. Each entry is how the text was
+/// before some edits, coalesced so typing undoes a word at a time.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TextUndo {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+    /// The last edit, and where it left the cursor, while the next can
+    /// join its entry.
+    open: Option<(EditKind, usize)>,
+}
+
+impl TextUndo {
+    /// Remembers an edit, given the text and cursor from before it and
+    /// where it left the cursor.
+    pub fn record(&mut self, kind: EditKind, text: &str, cursor: usize, new_cursor: usize) {
+        let joins = match self.open {
+            Some((open, at)) if open == kind && at == cursor => match kind {
+                // A new word starts after whitespace.
+                EditKind::Typing => !text
+                    .chars()
+                    .nth(cursor.wrapping_sub(1))
+                    .map_or(false, char::is_whitespace),
+                EditKind::Deleting => true,
+                EditKind::Other => false,
+            },
+            _ => false,
+        };
+        if !joins {
+            self.undo.push(Snapshot {
+                text: text.into(),
+                cursor,
+            });
+            if self.undo.len() > UNDO_LIMIT {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.open = Some((kind, new_cursor));
+    }
+
+    /// Undoes or redoes the last entry, given the text and cursor as they
+    /// are now. Returns them as they should be, if there was one.
+    pub fn apply(
+        &mut self,
+        action: UndoAction,
+        text: &str,
+        cursor: usize,
+    ) -> Option<(String, usize)> {
+        let (from, to) = match action {
+            UndoAction::Undo => (&mut self.undo, &mut self.redo),
+            UndoAction::Redo => (&mut self.redo, &mut self.undo),
+        };
+        let snapshot = from.pop()?;
+        to.push(Snapshot {
+            text: text.into(),
+            cursor,
+        });
+        self.open = None;
+        Some((snapshot.text, snapshot.cursor))
+    }
+}
+
+impl Context {
+    /// Is a text view focused? Undo shortcuts go to it instead of the
+    /// `Edit:Undo` and `Edit:Redo` commands while it is, like native
+    /// editors.
+    pub fn is_editing_text(&self) -> bool {
+        self.text_input_area.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_text_undo() {
+        let mut undo = TextUndo::default();
+        let mut text = String::new();
+        for c in "hi there".chars() {
+            let cursor = text.chars().count();
+            undo.record(EditKind::Typing, &text, cursor, cursor + 1);
+            text.push(c);
+        }
+        undo.record(EditKind::Deleting, &text, 8, 7);
+        text.pop();
+        undo.record(EditKind::Deleting, &text, 7, 6);
+        text.pop();
+        undo.record(EditKind::Other, &text, 6, 11);
+        text.push_str("ey you");
+
+        let mut undo_once = |text: &str| undo.apply(UndoAction::Undo, text, 0).unwrap().0;
+        // The paste, both deletes, then a word at a time.
+        let text = undo_once(&text);
+        assert_eq!(text, "hi the");
+        let text = undo_once(&text);
+        assert_eq!(text, "hi there");
+        let text = undo_once(&text);
+        assert_eq!(text, "hi ");
+        let text = undo_once(&text);
+        assert_eq!(text, "");
+
+        let (text, cursor) = undo.apply(UndoAction::Redo, &text, 0).unwrap();
+        assert_eq!((text.as_str(), cursor), ("hi ", 0));
+        assert!(undo.apply(UndoAction::Undo, "", 0).is_some());
+        assert!(undo.apply(UndoAction::Undo, "", 0).is_none());
+    }
+}
@@ -0,0 +1,225 @@
+use crate::*;
+use std::any::TypeId;
+
+/// Corner radii.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Radii {
+    pub small: f32,
+    pub medium: f32,
+    pub large: f32,
+}
+
+impl Default for Radii {
+    fn default() -> Self {
+        Self {
+            small: 4.0,
+            medium: 8.0,
+            large: 16.0,
+        }
+    }
+}
+
+/// Text sizes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FontSizes {
+    pub caption: u32,
+    pub body: u32,
+    pub title: u32,
+}
+
+impl Default for FontSizes {
+    fn default() -> Self {
+        Self {
+            caption: 14,
+            body: Text::DEFAULT_SIZE,
+            title: 28,
+        }
+    }
+}
+
+/// Design tokens for an app, in the environment. Read it with
+/// `env(|theme: Theme, cx| ...)`, and change it for the whole app with
+/// `Context::set_theme`, or load it from a file with `Context::load_theme`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub colors: Palette,
+    /// Spacing scale, from smallest to largest.
+    pub spacing: Vec<f32>,
+    pub radii: Radii,
+    pub font_sizes: FontSizes,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            colors: Palette::default(),
+            spacing: vec![4.0, 8.0, 16.0, 24.0, 32.0],
+            radii: Radii::default(),
+            font_sizes: FontSizes::default(),
+        }
+    }
+}
+
+impl Theme {
+    /// Step `i` of the spacing scale, or the largest step.
+    pub fn spacing(&self, i: usize) -> f32 {
+        self.spacing
+            .get(i)
+            .or_else(|| self.spacing.last())
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Parses a tokens file. Tokens which are left out keep their defaults,
+    /// and a `seed` color generates the colors, which can then be
+    /// overridden one at a time:
+    ///
+    /// ```json
+    /// {
+    ///     "colors": { "seed": "#FF0062", "text": "#FFFFFF" },
+    ///     "spacing": [4, 8, 12, 20],
+    ///     "radii": { "small": 2, "medium": 6, "large": 12 },
+    ///     "typography": { "caption": 12, "body": 16, "title": 24 }
+    /// }
+    /// ```
+    #[cfg(all(feature = "theme-file", not(target_arch = "wasm32")))]
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let tokens: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| err.to_string())?;
+        let mut theme = Theme::default();
+
+        let colors = &tokens["colors"];
+        let color = |name: &str| -> Result<Option<Color>, String> {
+            match colors[name].as_str() {
+                Some(hex) => Color::hex(hex)
+                    .map(Some)
+                    .map_err(|err| format!("colors.{}: {}", name, err)),
+                None => Ok(None),
+            }
+        };
+        if let Some(seed) = color("seed")? {
+            theme.colors = Palette::from_seed(seed);
+        }
+        for (name, field) in [
+            ("accent", &mut theme.colors.accent),
+            ("accent_dark", &mut theme.colors.accent_dark),
+            ("accent_background", &mut theme.colors.accent_background),
+            ("background", &mut theme.colors.background),
+            ("control", &mut theme.colors.control),
+            ("text", &mut theme.colors.text),
+            ("shadow", &mut theme.colors.shadow),
+        ] {
+            if let Some(c) = color(name)? {
+                *field = c;
+            }
+        }
+
+        if let Some(spacing) = tokens["spacing"].as_array() {
+            theme.spacing = spacing
+                .iter()
+                .map(|v| v.as_f64().map(|v| v as f32))
+                .collect::<Option<_>>()
+                .ok_or("spacing must be numbers")?;
+        }
+
+        let radii = &tokens["radii"];
+        for (name, field) in [
+            ("small", &mut theme.radii.small),
+            ("medium", &mut theme.radii.medium),
+            ("large", &mut theme.radii.large),
+        ] {
+            if let Some(v) = radii[name].as_f64() {
+                *field = v as f32;
+            }
+        }
+
+        let typography = &tokens["typography"];
+        for (name, field) in [
+            ("caption", &mut theme.font_sizes.caption),
+            ("body", &mut theme.font_sizes.body),
+            ("title", &mut theme.font_sizes.title),
+        ] {
+            if let Some(v) = typography[name].as_u64() {
+                *field = v as u32;
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+impl Context {
+    /// The app's `Theme`, unless a view sets another with `.env`.
+    pub fn theme(&mut self) -> Theme {
+        self.init_env(&Theme::default)
+    }
+
+    /// Changes the `Theme` for the whole app.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.env.insert(TypeId::of::<Theme>(), Box::new(theme));
+        // Everything may depend on it.
+        self.deps.clear();
+        self.set_dirty();
+    }
+
+    /// Sets the theme from a tokens file. See `Theme::from_json`.
+    #[cfg(all(feature = "theme-file", not(target_arch = "wasm32")))]
+    pub fn load_theme(&mut self, path: impl AsRef<std::path::Path>) {
+        let path = path.as_ref();
+        let theme = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|json| Theme::from_json(&json));
+        match theme {
+            Ok(theme) => self.set_theme(theme),
+            Err(err) => println!("unable to load theme {}: {}", path.display(), err),
+        }
+    }
+
+    /// Loads a tokens file, and loads it again whenever it's saved, so
+    /// designers can try changes while the app runs.
+    #[cfg(all(
+        feature = "theme-file",
+        feature = "file-watch",
+        not(target_arch = "wasm32")
+    ))]
+    pub fn watch_theme(&mut self, path: impl AsRef<std::path::Path>) -> FileWatchId {
+        let path = path.as_ref().to_path_buf();
+        self.load_theme(&path);
+        self.watch_path(path.clone(), move |cx, _| cx.load_theme(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_set_theme() {
+        let mut cx = Context::new();
+        assert_eq!(cx.theme(), Theme::default());
+
+        let theme = Theme {
+            spacing: vec![2.0],
+            ..Default::default()
+        };
+        cx.set_theme(theme.clone());
+        assert_eq!(cx.theme(), theme);
+        assert_eq!(theme.spacing(3), 2.0);
+    }
+
+    #[cfg(all(feature = "theme-file", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_theme_from_json() {
+        let theme = Theme::from_json(
+            r##"{ "colors": { "text": "#FFFFFF" }, "spacing": [1, 2], "typography": { "body": 16 } }"##,
+        )
+        .unwrap();
+        assert_eq!(theme.colors.text, Color::WHITE);
+        assert_eq!(theme.spacing, [1.0, 2.0]);
+        assert_eq!(theme.font_sizes.body, 16);
+        assert_eq!(theme.radii, Radii::default());
+
+        assert!(Theme::from_json(r#"{ "colors": { "text": "white" } }"#).is_err());
+    }
+}
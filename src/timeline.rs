@@ -0,0 +1,215 @@
+use crate::*;
+use instant::Instant;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A change to a parameter at a time in seconds.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamEvent {
+    pub time: f32,
+    pub address: String,
+    pub value: f32,
+}
+
+/// Changes to parameters registered with the `param` modifier over time,
+/// like an automation lane. Record one with `Context::record_params` and
+/// play it with `Context::play_timeline`.
+///
+/// Saved as text with `to_string` and loaded with `parse`, one event per
+/// line; with the `serde` feature it can be serialized too.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParamTimeline {
+    /// In time order.
+    pub events: Vec<ParamEvent>,
+}
+
+impl ParamTimeline {
+    /// Adds an event, keeping the events in time order.
+    pub fn add(&mut self, time: f32, address: &str, value: f32) {
+        let i = self.events.partition_point(|e| e.time <= time);
+        self.events.insert(
+            i,
+            ParamEvent {
+                time,
+                address: address.into(),
+                value,
+            },
+        );
+    }
+
+    /// Time of the last event.
+    pub fn duration(&self) -> f32 {
+        self.events.last().map_or(0.0, |e| e.time)
+    }
+
+    /// The value of each parameter at `time`, interpolating between events.
+    /// Parameters with no events yet are left out.
+    pub fn values_at(&self, time: f32) -> HashMap<String, f32> {
+        let mut values = HashMap::new();
+        let mut before: HashMap<&str, &ParamEvent> = HashMap::new();
+        for event in &self.events {
+            if event.time <= time {
+                values.insert(event.address.clone(), event.value);
+                before.insert(&event.address, event);
+            } else if let Some(prev) = before.remove(event.address.as_str()) {
+                let t = (time - prev.time) / (event.time - prev.time);
+                values.insert(
+                    event.address.clone(),
+                    prev.value + (event.value - prev.value) * t,
+                );
+            }
+        }
+        values
+    }
+}
+
+impl fmt::Display for ParamTimeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for event in &self.events {
+            writeln!(f, "{} {} {}", event.time, event.address, event.value)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ParamTimeline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut timeline = ParamTimeline::default();
+        for (n, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let event = match parts[..] {
+                [time, address, value] => time
+                    .parse()
+                    .ok()
+                    .zip(value.parse().ok())
+                    .map(|(time, value)| (time, address, value)),
+                _ => None,
+            };
+            match event {
+                Some((time, address, value)) => timeline.add(time, address, value),
+                None => return Err(format!("invalid timeline event on line {}", n + 1)),
+            }
+        }
+        Ok(timeline)
+    }
+}
+
+pub(crate) struct Recording {
+    start: Instant,
+    /// Values at the last update, to find changes.
+    last: HashMap<String, f32>,
+    timeline: ParamTimeline,
+}
+
+pub(crate) struct Playback {
+    time: f32,
+    timeline: ParamTimeline,
+}
+
+impl Context {
+    /// Starts recording changes to parameters registered with the `param`
+    /// modifier, starting with their current values.
+    pub fn record_params(&mut self) {
+        let mut timeline = ParamTimeline::default();
+        for (address, value) in self.params() {
+            timeline.add(0.0, &address, value);
+        }
+        self.recording = Some(Recording {
+            start: Instant::now(),
+            last: self.params().into_iter().collect(),
+            timeline,
+        });
+        self.set_dirty();
+    }
+
+    /// Stops recording, returning what was recorded.
+    pub fn stop_recording(&mut self) -> Option<ParamTimeline> {
+        self.recording.take().map(|r| r.timeline)
+    }
+
+    /// Sets parameters from `timeline` on each animation tick until it ends.
+    pub fn play_timeline(&mut self, timeline: ParamTimeline) {
+        self.playback = Some(Playback {
+            time: 0.0,
+            timeline,
+        });
+        self.set_dirty();
+    }
+
+    pub fn stop_playback(&mut self) {
+        self.playback = None;
+    }
+
+    pub fn is_playing_timeline(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Advances playback by `dt` seconds, on each animation tick.
+    pub(crate) fn tick_timeline(&mut self, dt: f32) {
+        let (values, done) = match &mut self.playback {
+            Some(playback) => {
+                playback.time += dt;
+                let time = playback.time;
+                (
+                    playback.timeline.values_at(time),
+                    time >= playback.timeline.duration(),
+                )
+            }
+            None => return,
+        };
+        for (address, value) in values {
+            self.set_param(&address, value);
+        }
+        if done {
+            self.playback = None;
+        }
+    }
+
+    /// Adds parameters which changed since the last update to the recording.
+    pub(crate) fn record_param_changes(&mut self) {
+        if self.recording.is_none() {
+            return;
+        }
+        let params = self.params();
+        let recording = self.recording.as_mut().unwrap();
+        let time = recording.start.elapsed().as_secs_f32();
+        for (address, value) in params {
+            if recording.last.get(&address) != Some(&value) {
+                recording.timeline.add(time, &address, value);
+                recording.last.insert(address, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_timeline() {
+        let mut timeline = ParamTimeline::default();
+        timeline.add(1.0, "/cutoff", 1.0);
+        timeline.add(0.0, "/cutoff", 0.0);
+        timeline.add(0.5, "/gain", 0.25);
+
+        assert_eq!(timeline.duration(), 1.0);
+        assert_eq!(timeline.values_at(0.25)["/cutoff"], 0.25);
+        assert!(!timeline.values_at(0.25).contains_key("/gain"));
+        assert_eq!(timeline.values_at(2.0)["/gain"], 0.25);
+
+        let text = timeline.to_string();
+        assert_eq!(text, "0 /cutoff 0\n0.5 /gain 0.25\n1 /cutoff 1\n");
+        assert_eq!(text.parse::<ParamTimeline>(), Ok(timeline));
+        assert!("0 /cutoff".parse::<ParamTimeline>().is_err());
+    }
+}
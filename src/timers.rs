@@ -0,0 +1,131 @@
+use crate::*;
+use std::time::Duration;
+
+/// Identifies a timer started with `Context::after` or `Context::every`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId(usize);
+
+pub(crate) struct Timer {
+    id: TimerId,
+    due: Instant,
+    /// How often it repeats, for `every`.
+    repeat: Option<Duration>,
+    /// Taken out while it's being called.
+    func: Option<Box<dyn FnMut(&mut Context)>>,
+}
+
+impl Context {
+    /// Calls `f` once, after `delay`, for debouncing, auto-save and the
+    /// like. The event loop wakes up for it, without a thread.
+    pub fn after(&mut self, delay: Duration, f: impl FnOnce(&mut Context) + 'static) -> TimerId {
+        let mut f = Some(f);
+        self.add_timer(delay, None, move |cx| {
+            if let Some(f) = f.take() {
+                f(cx)
+            }
+        })
+    }
+
+    /// Calls `f` every `interval` until the timer is cancelled.
+    pub fn every(&mut self, interval: Duration, f: impl FnMut(&mut Context) + 'static) -> TimerId {
+        self.add_timer(interval, Some(interval), f)
+    }
+
+    /// Stops a timer from `after` or `every`. Does nothing if it's already
+    /// gone.
+    pub fn cancel_timer(&mut self, id: TimerId) {
+        self.timers.retain(|t| t.id != id);
+    }
+
+    fn add_timer(
+        &mut self,
+        delay: Duration,
+        repeat: Option<Duration>,
+        f: impl FnMut(&mut Context) + 'static,
+    ) -> TimerId {
+        let id = TimerId(self.next_timer_id);
+        self.next_timer_id += 1;
+        self.timers.push(Timer {
+            id,
+            due: Instant::now() + delay,
+            repeat,
+            func: Some(Box::new(f)),
+        });
+        id
+    }
+
+    pub(crate) fn run_timers(&mut self) {
+        let now = Instant::now();
+        let due: Vec<TimerId> = self
+            .timers
+            .iter()
+            .filter(|t| t.due <= now)
+            .map(|t| t.id)
+            .collect();
+
+        for id in due {
+            // An earlier callback may have cancelled it.
+            let mut func = match self.timers.iter_mut().find(|t| t.id == id) {
+                Some(timer) => timer.func.take(),
+                None => continue,
+            };
+            if let Some(f) = &mut func {
+                f(self);
+            }
+
+            if let Some(i) = self.timers.iter().position(|t| t.id == id) {
+                match self.timers[i].repeat {
+                    Some(interval) => {
+                        let timer = &mut self.timers[i];
+                        timer.func = func;
+                        // Skip ticks missed while busy or asleep.
+                        let next = timer.due + interval;
+                        timer.due = if next > now { next } else { now + interval };
+                    }
+                    None => {
+                        self.timers.remove(i);
+                    }
+                }
+            }
+        }
+    }
+
+    /// When the next timer is due.
+    pub(crate) fn next_timer(&self) -> Option<Instant> {
+        self.timers.iter().map(|t| t.due).min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_timers() {
+        let mut cx = Context::new();
+        let count = Rc::new(Cell::new(0));
+
+        let c = count.clone();
+        cx.after(Duration::ZERO, move |_| c.set(c.get() + 1));
+        let c = count.clone();
+        let ticker = cx.every(Duration::ZERO, move |_| c.set(c.get() + 10));
+        let c = count.clone();
+        cx.after(Duration::from_secs(60), move |_| c.set(c.get() + 100));
+        assert!(cx.next_timer().is_some());
+
+        cx.run_timers();
+        assert_eq!(count.get(), 11);
+
+        // Only the repeating one again.
+        cx.run_timers();
+        assert_eq!(count.get(), 21);
+
+        cx.cancel_timer(ticker);
+        cx.run_timers();
+        assert_eq!(count.get(), 21);
+        assert_eq!(cx.timers.len(), 1);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::*;
+use std::future::Future;
+use std::sync::Mutex;
+use tokio::runtime::{Handle, Runtime, RuntimeFlavor};
+use tokio::task::JoinHandle;
+
+lazy_static! {
+    /// The runtime `rui` was started in, if it was started in one.
+    static ref HANDLE: Mutex<Option<Handle>> = Mutex::new(None);
+
+    /// Our own runtime, for when there isn't one already.
+    static ref RUNTIME: Runtime = tokio::runtime::Builder::new_multi_thread()
+        .thread_name("rui tokio")
+        .enable_all()
+        .build()
+        .expect("couldn't start a tokio runtime");
+}
+
+/// Remembers the runtime `rui` is running in, if any, so tasks spawned
+/// from the UI go there.
+pub(crate) fn enter() {
+    *HANDLE.lock().unwrap() = Handle::try_current().ok();
+}
+
+fn handle() -> Handle {
+    if let Ok(handle) = Handle::try_current() {
+        return handle;
+    }
+    match &*HANDLE.lock().unwrap() {
+        Some(handle) => handle.clone(),
+        None => RUNTIME.handle().clone(),
+    }
+}
+
+/// Waits for a future on this thread. If it's one of Tokio's workers, other
+/// tasks are moved off it first so they don't stall.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| futures::executor::block_on(future))
+        }
+        _ => futures::executor::block_on(future),
+    }
+}
+
+impl Context {
+    /// Runs a future on Tokio: the runtime `rui` was started in, such as
+    /// from `#[tokio::main]`, or else one of our own. Unlike `spawn`, the
+    /// future can use Tokio's I/O and timers. Use `on_main` from it to
+    /// update the UI.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// state(
+    ///     || 0,
+    ///     |count, _| {
+    ///         button("wait", move |cx| {
+    ///             cx.spawn_tokio(async move {
+    ///                 tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    ///                 on_main(move |cx| cx[count] += 1);
+    ///             });
+    ///         })
+    ///     },
+    /// );
+    /// ```
+    pub fn spawn_tokio<T: Send + 'static>(
+        &mut self,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> JoinHandle<T> {
+        handle().spawn(future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_spawn_tokio() {
+        let mut cx = Context::new();
+        let task = cx.spawn_tokio(async {
+            tokio::task::yield_now().await;
+            42
+        });
+        assert_eq!(block_on(task).unwrap(), 42);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::*;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+const MAX_NOTES_HEIGHT: f32 = 240.0;
+
+/// Where to look for new versions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UpdateFeed {
+    /// The URL of a Sparkle-style appcast: an RSS feed with an item for each
+    /// version, whose enclosure links to the download.
+    Appcast(String),
+
+    /// The latest release of a GitHub repository, like "audulus/rui".
+    GitHubReleases(String),
+}
+
+/// A newer version, found by `Context::check_for_updates`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub notes_markdown: String,
+    /// Where to download the update from.
+    pub url: String,
+}
+
+pub(crate) type UpdateHandler = Rc<dyn Fn(&mut Context, &UpdateInfo)>;
+
+/// The "Update available" sheet.
+pub(crate) struct UpdateSheet {
+    info: UpdateInfo,
+    current_version: String,
+    /// "Install and Restart" then "Later".
+    buttons: Vec<LocalRect>,
+}
+
+/// Compares dotted version numbers, ignoring a leading "v" and anything
+/// after the digits in each part, so "v1.10.0-beta" is newer than "1.9".
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| -> Vec<u64> {
+        v.trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|part| {
+                let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+                digits.parse().unwrap_or(0)
+            })
+            .collect()
+    };
+    let (a, b) = (parts(a), parts(b));
+    for i in 0..a.len().max(b.len()) {
+        let ord = a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+/// The value of `name="..."` in a tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+/// The text inside the first `<name>...</name>`.
+fn element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}", name);
+    let mut start = xml.find(&open)?;
+    start += xml[start..].find('>')? + 1;
+    let end = xml[start..].find(&format!("</{}>", name))? + start;
+    let text = xml[start..end].trim();
+    Some(
+        text.strip_prefix("<![CDATA[")
+            .and_then(|t| t.strip_suffix("]]>"))
+            .unwrap_or(text),
+    )
+}
+
+/// Release notes in appcasts are HTML. Keeps the structure Markdown can
+/// show and drops the rest.
+fn html_to_markdown(html: &str) -> String {
+    let mut md = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        md += &rest[..start];
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let tag = rest[start + 1..end].trim().to_ascii_lowercase();
+        let name = tag.split_whitespace().next().unwrap_or("");
+        md += match name {
+            "h1" | "h2" | "h3" | "h4" => "\n\n# ",
+            "li" => "\n- ",
+            "p" | "/p" | "/ul" | "/ol" | "/h1" | "/h2" | "/h3" | "/h4" => "\n\n",
+            "br" | "br/" => "\n",
+            _ => "",
+        };
+        rest = &rest[end + 1..];
+    }
+    md += rest;
+    md.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The newest version in an appcast.
+fn parse_appcast(xml: &str) -> Option<UpdateInfo> {
+    xml.split("<item>")
+        .skip(1)
+        .filter_map(|item| {
+            let item = &item[..item.find("</item>").unwrap_or(item.len())];
+            let enclosure = &item[item.find("<enclosure")?..];
+            let enclosure = &enclosure[..enclosure.find('>').unwrap_or(enclosure.len())];
+            let version = attribute(enclosure, "sparkle:shortVersionString")
+                .or_else(|| element(item, "sparkle:shortVersionString").map(String::from))
+                .or_else(|| attribute(enclosure, "sparkle:version"))
+                .or_else(|| element(item, "sparkle:version").map(String::from))?;
+            Some(UpdateInfo {
+                version,
+                notes_markdown: element(item, "description")
+                    .map(html_to_markdown)
+                    .unwrap_or_default(),
+                url: attribute(enclosure, "url")?,
+            })
+        })
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+}
+
+fn parse_github_release(json: &str) -> Option<UpdateInfo> {
+    let release: serde_json::Value = serde_json::from_str(json).ok()?;
+    Some(UpdateInfo {
+        version: release["tag_name"].as_str()?.to_string(),
+        notes_markdown: release["body"].as_str().unwrap_or("").to_string(),
+        url: release["html_url"].as_str()?.to_string(),
+    })
+}
+
+fn fetch(feed: &UpdateFeed) -> Result<Option<UpdateInfo>, String> {
+    let get = |url: &str| -> Result<String, String> {
+        ureq::get(url)
+            .call()
+            .map_err(|err| err.to_string())?
+            .into_string()
+            .map_err(|err| err.to_string())
+    };
+    Ok(match feed {
+        UpdateFeed::Appcast(url) => parse_appcast(&get(url)?),
+        UpdateFeed::GitHubReleases(repo) => parse_github_release(&get(&format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            repo
+        ))?),
+    })
+}
+
+/// Starts the app again with the same arguments, and exits this instance.
+/// Call this once an update is installed.
+pub fn relaunch() -> ! {
+    match std::env::current_exe() {
+        Ok(exe) => {
+            if let Err(err) = std::process::Command::new(exe)
+                .args(std::env::args_os().skip(1))
+                .spawn()
+            {
+                println!("unable to relaunch: {}", err);
+            }
+        }
+        Err(err) => println!("unable to relaunch: {}", err),
+    }
+    std::process::exit(0)
+}
+
+impl UpdateSheet {
+    pub(crate) fn draw(&mut self, vger: &mut Vger, size: LocalSize) {
+        let width = PANEL_WIDTH - 2.0 * PANEL_PADDING;
+        let header = [
+            PanelItem {
+                text: "Update Available".into(),
+                size: 18,
+                color: TEXT_COLOR,
+                centered: false,
+                gap: 0.0,
+            },
+            PanelItem {
+                text: format!(
+                    "Version {} is available. You have {}.",
+                    self.info.version, self.current_version
+                ),
+                size: 12,
+                color: MEDIUM_GRAY,
+                centered: false,
+                gap: 6.0,
+            },
+        ];
+        let notes = markdown_items(&self.info.notes_markdown);
+
+        let header_height = items_height(vger, &header, width);
+        let notes_height = items_height(vger, &notes, width).min(MAX_NOTES_HEIGHT);
+        let panel_size = LocalSize::new(
+            PANEL_WIDTH,
+            header_height + notes_height + BUTTON_HEIGHT + 3.0 * PANEL_PADDING,
+        );
+        let frame = LocalRect::new(
+            ((size - panel_size) / 2.0).to_vector().to_point(),
+            panel_size,
+        );
+        draw_panel(vger, size, frame);
+
+        let mut top = frame.max_y() - PANEL_PADDING;
+        for item in &header {
+            top = draw_item(vger, item, frame, top, width);
+        }
+
+        vger.save();
+        vger.scissor(LocalRect::new(
+            [frame.min_x(), top - notes_height].into(),
+            [PANEL_WIDTH, notes_height].into(),
+        ));
+        for item in &notes {
+            top = draw_item(vger, item, frame, top, width);
+        }
+        vger.restore();
+
+        self.buttons = draw_buttons(vger, frame, &["Install and Restart", "Later"]);
+    }
+}
+
+impl Context {
+    /// Checks `feed` for a version newer than `current_version` in the
+    /// background. If there is one, shows a sheet with its release notes.
+    /// Choosing "Install and Restart" calls `on_install`, which should
+    /// download and install the update, then call `relaunch`.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// # let cx = &mut Context::new();
+    /// cx.check_for_updates(
+    ///     UpdateFeed::GitHubReleases("audulus/rui".into()),
+    ///     env!("CARGO_PKG_VERSION"),
+    ///     |_, update| println!("download {}", update.url),
+    /// );
+    /// ```
+    pub fn check_for_updates(
+        &mut self,
+        feed: UpdateFeed,
+        current_version: &str,
+        on_install: impl Fn(&mut Context, &UpdateInfo) + 'static,
+    ) {
+        self.update_handler = Some(Rc::new(on_install));
+        let current_version = current_version.to_string();
+        std::thread::spawn(move || match fetch(&feed) {
+            Ok(Some(info)) => {
+                if compare_versions(&info.version, &current_version) == Ordering::Greater {
+                    on_main(move |cx| {
+                        cx.update_sheet = Some(UpdateSheet {
+                            info,
+                            current_version,
+                            buttons: vec![],
+                        });
+                        cx.set_dirty();
+                    });
+                }
+            }
+            Ok(None) => println!("no versions found in update feed {:?}", feed),
+            Err(err) => println!("error checking for updates: {}", err),
+        });
+    }
+
+    /// Handles events while the update sheet is showing. Returns true if
+    /// the event was for the sheet.
+    pub(crate) fn process_update_sheet(&mut self, event: &Event) -> bool {
+        let sheet = match &self.update_sheet {
+            Some(sheet) => sheet,
+            None => return false,
+        };
+        match event {
+            Event::TouchBegin { position, .. } => {
+                let button = sheet
+                    .buttons
+                    .iter()
+                    .position(|rect| rect.contains(*position));
+                match button {
+                    Some(0) => {
+                        let info = sheet.info.clone();
+                        self.update_sheet = None;
+                        if let Some(f) = self.update_handler.clone() {
+                            f(self, &info);
+                        }
+                    }
+                    Some(_) => self.update_sheet = None,
+                    None => (),
+                }
+                self.set_dirty();
+                true
+            }
+            Event::Key {
+                key: Key::Escape, ..
+            } => {
+                self.update_sheet = None;
+                self.set_dirty();
+                true
+            }
+            Event::TouchMove { .. }
+            | Event::Hover { .. }
+            | Event::DropHover { .. }
+            | Event::DropFiles { .. }
+            | Event::Scroll { .. }
+            | Event::TouchEnd { .. }
+            | Event::Key { .. }
+            | Event::KeyUp { .. }
+            | Event::TextInput(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("v1.10.0", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("0.6.1", "0.7.0-beta"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_parse_appcast() {
+        let xml = r#"<rss><channel>
+            <item>
+                <title>1.1</title>
+                <enclosure url="https://example.com/1.1.zip" sparkle:version="11" sparkle:shortVersionString="1.1"/>
+            </item>
+            <item>
+                <title>1.2</title>
+                <description><![CDATA[<h2>New</h2><ul><li>Faster &amp; smaller</li></ul>]]></description>
+                <enclosure url="https://example.com/1.2.zip" sparkle:version="12" sparkle:shortVersionString="1.2"/>
+            </item>
+        </channel></rss>"#;
+
+        let info = parse_appcast(xml).unwrap();
+        assert_eq!(info.version, "1.2");
+        assert_eq!(info.url, "https://example.com/1.2.zip");
+        assert_eq!(
+            markdown_items(&info.notes_markdown)
+                .iter()
+                .map(|item| item.text.as_str())
+                .collect::<Vec<_>>(),
+            ["New", "• Faster & smaller"]
+        );
+    }
+}
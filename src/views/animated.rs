@@ -0,0 +1,129 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for `animated`.
+pub struct Animated<T, F> {
+    target: T,
+    spring: Spring,
+    func: F,
+}
+
+impl<T, V, F> Animated<T, F>
+where
+    T: Animatable,
+    V: View,
+    F: Fn(T, &mut Context) -> V + 'static,
+{
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<SpringState<T>> {
+        let vid = cx.view_id(path);
+        let target = self.target;
+        cx.init_state(vid, &move || SpringState::new(target));
+        StateHandle::new(vid)
+    }
+
+    fn body(&self, path: &IdPath, cx: &mut Context) -> V {
+        let value = self.state(path, cx).get(cx).value;
+        (self.func)(value, cx)
+    }
+}
+
+impl<T, V, F> View for Animated<T, F>
+where
+    T: Animatable,
+    V: View,
+    F: Fn(T, &mut Context) -> V + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            let s = self.state(path, cx);
+            let mut next = *s.get(cx);
+            // Keeps the velocity if it's retargeted mid-flight.
+            next.target = self.target;
+            next.step(&self.spring, cx.anim_dt);
+
+            // Writing keeps animation frames coming, so stop once settled.
+            if next != *s.get(cx) {
+                cx[s] = next;
+                cx.note_animation("spring", None);
+            }
+        }
+
+        path.push(0);
+        self.body(path, cx).process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.body(path, args.cx).draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.body(path, args.cx).layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.body(path, cx).dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.body(path, cx).hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.body(path, cx).commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.body(path, cx).gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.body(path, cx).access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<T, F> private::Sealed for Animated<T, F> {}
+
+/// Springs a value towards `target` whenever it changes, building a view
+/// from the value as it goes. Works for numbers, points, offsets, sizes
+/// and colors.
+pub fn animated<T: Animatable, V: View, F: Fn(T, &mut Context) -> V + 'static>(
+    target: T,
+    spring: Spring,
+    f: F,
+) -> Animated<T, F> {
+    Animated {
+        target,
+        spring,
+        func: f,
+    }
+}
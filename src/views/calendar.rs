@@ -0,0 +1,987 @@
+use crate::*;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// A day in the Gregorian calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    pub year: i32,
+    /// From 1 to 12.
+    pub month: u32,
+    /// From 1.
+    pub day: u32,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl CalendarDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Today, in UTC.
+    pub fn today() -> Self {
+        let secs = instant::SystemTime::now()
+            .duration_since(instant::SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_days((secs / 86400) as i64)
+    }
+
+    pub fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            4 | 6 | 9 | 11 => 30,
+            2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+            2 => 28,
+            _ => 31,
+        }
+    }
+
+    /// Days since 1970-01-01.
+    fn days(self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let y = self.year as i64 - (self.month <= 2) as i64;
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let m = self.month as i64;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146097 + doe - 719468
+    }
+
+    fn from_days(days: i64) -> Self {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z - era * 146097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + (month <= 2) as i64) as i32;
+        Self { year, month, day }
+    }
+
+    pub fn add_days(self, days: i64) -> Self {
+        Self::from_days(self.days() + days)
+    }
+
+    /// Moves by `months`, keeping the day where the month is long enough.
+    pub fn add_months(self, months: i32) -> Self {
+        let m = self.year * 12 + self.month as i32 - 1 + months;
+        let (year, month) = (m.div_euclid(12), m.rem_euclid(12) as u32 + 1);
+        let day = self.day.min(Self::days_in_month(year, month));
+        Self { year, month, day }
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        Weekday::from_monday((self.days() + 3).rem_euclid(7) as u32)
+    }
+
+    /// The first day of the week this is in, for weeks starting on `start`.
+    pub fn start_of_week(self, start: Weekday) -> Self {
+        let back = (self.weekday().index() + 7 - start.index()) % 7;
+        self.add_days(-(back as i64))
+    }
+}
+
+/// A day of the week.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+/// Regions whose weeks start on Sunday.
+const SUNDAY_REGIONS: &[&str] = &[
+    "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CO", "DM", "DO", "ET", "GT", "GU", "HK",
+    "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO", "MT", "MX", "MZ",
+    "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV", "TH", "TT", "TW", "UM",
+    "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+];
+
+/// Regions whose weeks start on Saturday.
+const SATURDAY_REGIONS: &[&str] = &[
+    "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+];
+
+impl Weekday {
+    fn from_monday(n: u32) -> Self {
+        use Weekday::*;
+        [
+            Monday, Tuesday, Wednesday, Thursday, Friday, Saturday, Sunday,
+        ][n as usize % 7]
+    }
+
+    fn index(self) -> u32 {
+        self as u32
+    }
+
+    /// Like "Mon".
+    pub fn short_name(self) -> &'static str {
+        ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"][self as usize]
+    }
+
+    /// The day the user's weeks start on, going by the `LC_ALL`, `LC_TIME`
+    /// and `LANG` environment variables, or else Monday.
+    pub fn week_start() -> Self {
+        let locale = ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .unwrap_or_default();
+        Self::week_start_for_locale(&locale)
+    }
+
+    /// The day weeks start on in a locale such as `"en_US.UTF-8"`, going
+    /// by its region.
+    pub fn week_start_for_locale(locale: &str) -> Self {
+        let region = locale
+            .split(|c| c == '.' || c == '@')
+            .next()
+            .and_then(|s| s.split(|c| c == '_' || c == '-').nth(1))
+            .map(|s| s.to_uppercase());
+        match region.as_deref() {
+            Some(r) if SUNDAY_REGIONS.contains(&r) => Weekday::Sunday,
+            Some(r) if SATURDAY_REGIONS.contains(&r) => Weekday::Saturday,
+            _ => Weekday::Monday,
+        }
+    }
+}
+
+/// An event on a `calendar`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarEvent {
+    pub date: CalendarDate,
+    pub title: String,
+    pub color: Color,
+    /// Minutes after midnight, or `None` for all day.
+    pub time: Option<Range<u32>>,
+}
+
+impl CalendarEvent {
+    /// An all day event.
+    pub fn new(date: CalendarDate, title: &str) -> Self {
+        Self {
+            date,
+            title: title.into(),
+            color: AZURE_HIGHLIGHT_DARK,
+            time: None,
+        }
+    }
+
+    /// From `start` to `end` minutes after midnight.
+    pub fn time(mut self, start: u32, end: u32) -> Self {
+        self.time = Some(start..end.max(start));
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// Like "9:30" or "14:00".
+fn time_label(minute: u32) -> String {
+    format!("{}:{:02}", minute / 60, minute % 60)
+}
+
+/// Which days a `calendar` shows at once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CalendarLayout {
+    /// Six weeks, covering the month.
+    #[default]
+    Month,
+    /// Seven days, with times of day down the side.
+    Week,
+}
+
+const HEADER_HEIGHT: f32 = 22.0;
+const DAY_NUMBER_HEIGHT: f32 = 20.0;
+const CHIP_HEIGHT: f32 = 18.0;
+const CHIP_SPACING: f32 = 2.0;
+const ALL_DAY_HEIGHT: f32 = 2.0 * (CHIP_HEIGHT + CHIP_SPACING) + CHIP_SPACING;
+const TIME_WIDTH: f32 = 48.0;
+const HOUR_HEIGHT: f32 = 40.0;
+const POPOVER_WIDTH: f32 = 180.0;
+const FONT_SIZE: u32 = 12;
+
+/// An event drawn in `rect`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Chip {
+    rect: LocalRect,
+    event: usize,
+}
+
+/// A day, its events, and how many more there are than fit.
+#[derive(Clone, Debug, PartialEq)]
+struct DayCell {
+    date: CalendarDate,
+    rect: LocalRect,
+    chips: Vec<Chip>,
+    more: Option<(LocalRect, usize)>,
+}
+
+/// What's under a tap.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CalendarHit {
+    Event(usize),
+    More(CalendarDate),
+    /// A day, and in the week layout, the minute.
+    Day(CalendarDate, Option<u32>),
+}
+
+/// The events on `date`, all day ones first, then by time.
+fn events_on(events: &[CalendarEvent], date: CalendarDate) -> Vec<usize> {
+    let mut on: Vec<usize> = (0..events.len())
+        .filter(|&i| events[i].date == date)
+        .collect();
+    on.sort_by_key(|&i| events[i].time.as_ref().map(|t| t.start + 1).unwrap_or(0));
+    on
+}
+
+/// Stacks chips for `events` down from `top` in `rect`, as many as fit,
+/// ending with "+N more" if they don't all.
+fn stack_chips(date: CalendarDate, rect: LocalRect, top: f32, events: &[usize]) -> DayCell {
+    let slot = |i: usize| {
+        let y = top - CHIP_SPACING - i as f32 * (CHIP_HEIGHT + CHIP_SPACING);
+        LocalRect::new(
+            [rect.min_x() + 2.0, y - CHIP_HEIGHT].into(),
+            [rect.width() - 4.0, CHIP_HEIGHT].into(),
+        )
+    };
+    let room =
+        ((top - rect.min_y() - CHIP_SPACING) / (CHIP_HEIGHT + CHIP_SPACING)).max(0.0) as usize;
+    let shown = if events.len() <= room {
+        events.len()
+    } else {
+        room.saturating_sub(1)
+    };
+    DayCell {
+        date,
+        rect,
+        chips: events[..shown]
+            .iter()
+            .enumerate()
+            .map(|(i, &event)| Chip {
+                rect: slot(i),
+                event,
+            })
+            .collect(),
+        more: (shown < events.len()).then(|| (slot(shown), events.len() - shown)),
+    }
+}
+
+/// Where a `calendar` draws its days and events.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct CalendarGeometry {
+    /// Month days, or the week's all day row.
+    cells: Vec<DayCell>,
+    /// Events at times of day, in the week layout.
+    timed: Vec<Chip>,
+    /// The week's days, below the all day row.
+    columns: Vec<(CalendarDate, LocalRect)>,
+    /// The top of the times of day.
+    grid_top: f32,
+}
+
+impl CalendarGeometry {
+    fn month(
+        rect: LocalRect,
+        shown: CalendarDate,
+        start: Weekday,
+        events: &[CalendarEvent],
+    ) -> Self {
+        let first = CalendarDate::new(shown.year, shown.month, 1).start_of_week(start);
+        let height = rect.height() - HEADER_HEIGHT;
+        let (w, h) = (rect.width() / 7.0, height / 6.0);
+        let cells = (0..42)
+            .map(|i| {
+                let date = first.add_days(i as i64);
+                let (row, column) = ((i / 7) as f32, (i % 7) as f32);
+                let top = height - row * h;
+                let cell = LocalRect::new([column * w, top - h].into(), [w, h].into());
+                stack_chips(
+                    date,
+                    cell,
+                    top - DAY_NUMBER_HEIGHT,
+                    &events_on(events, date),
+                )
+            })
+            .collect();
+        Self {
+            cells,
+            ..Default::default()
+        }
+    }
+
+    fn week(
+        rect: LocalRect,
+        shown: CalendarDate,
+        start: Weekday,
+        events: &[CalendarEvent],
+        scroll_y: f32,
+    ) -> Self {
+        let first = shown.start_of_week(start);
+        let w = (rect.width() - TIME_WIDTH) / 7.0;
+        let all_day_top = rect.height() - HEADER_HEIGHT;
+        let grid_top = all_day_top - ALL_DAY_HEIGHT;
+        let mut geometry = Self {
+            grid_top,
+            ..Default::default()
+        };
+        for i in 0..7 {
+            let date = first.add_days(i);
+            let x = TIME_WIDTH + i as f32 * w;
+            let on = events_on(events, date);
+            let (all_day, timed): (Vec<usize>, Vec<usize>) =
+                on.into_iter().partition(|&e| events[e].time.is_none());
+            let cell = LocalRect::new([x, grid_top].into(), [w, ALL_DAY_HEIGHT].into());
+            geometry
+                .cells
+                .push(stack_chips(date, cell, all_day_top, &all_day));
+            geometry.columns.push((
+                date,
+                LocalRect::new([x, 0.0].into(), [w, grid_top.max(0.0)].into()),
+            ));
+
+            // Overlapping events share the column, side by side.
+            let mut lane_ends: Vec<u32> = vec![];
+            let mut lanes = vec![];
+            for &e in &timed {
+                let time = events[e].time.clone().unwrap_or(0..0);
+                let lane = match lane_ends.iter().position(|&end| end <= time.start) {
+                    Some(lane) => lane,
+                    None => {
+                        lane_ends.push(0);
+                        lane_ends.len() - 1
+                    }
+                };
+                lane_ends[lane] = time.end.max(time.start + 15);
+                lanes.push((e, lane, time));
+            }
+            let lane_width = w / lane_ends.len().max(1) as f32;
+            for (event, lane, time) in lanes {
+                let y = |minute: u32| grid_top + scroll_y - minute as f32 / 60.0 * HOUR_HEIGHT;
+                let (top, bottom) = (y(time.start), y(time.end.max(time.start + 15)));
+                geometry.timed.push(Chip {
+                    rect: LocalRect::new(
+                        [x + lane as f32 * lane_width + 1.0, bottom].into(),
+                        [lane_width - 2.0, top - bottom - 1.0].into(),
+                    ),
+                    event,
+                });
+            }
+        }
+        geometry
+    }
+
+    fn hit(&self, p: LocalPoint, scroll_y: f32) -> Option<CalendarHit> {
+        if p.y < self.grid_top {
+            if let Some(chip) = self.timed.iter().rev().find(|c| c.rect.contains(p)) {
+                return Some(CalendarHit::Event(chip.event));
+            }
+        }
+        for cell in &self.cells {
+            if let Some(chip) = cell.chips.iter().find(|c| c.rect.contains(p)) {
+                return Some(CalendarHit::Event(chip.event));
+            }
+            if let Some((rect, _)) = cell.more {
+                if rect.contains(p) {
+                    return Some(CalendarHit::More(cell.date));
+                }
+            }
+            if cell.rect.contains(p) {
+                return Some(CalendarHit::Day(cell.date, None));
+            }
+        }
+        self.columns
+            .iter()
+            .find(|(_, r)| r.contains(p))
+            .map(|(date, _)| {
+                let minute = (self.grid_top + scroll_y - p.y) / HOUR_HEIGHT * 60.0;
+                // To the half hour above.
+                let minute = ((minute / 30.0).floor() * 30.0).clamp(0.0, 23.5 * 60.0) as u32;
+                CalendarHit::Day(*date, Some(minute))
+            })
+    }
+}
+
+/// The "+N more" panel for `cell`, kept in `rect`, and where its events go.
+fn popover(rect: LocalRect, cell: &DayCell, count: usize) -> (LocalRect, Vec<LocalRect>) {
+    let height = HEADER_HEIGHT + count as f32 * (CHIP_HEIGHT + CHIP_SPACING) + 2.0 * CHIP_SPACING;
+    let width = POPOVER_WIDTH.max(cell.rect.width());
+    let x = cell.rect.min_x().min(rect.max_x() - width).max(0.0);
+    let top = cell.rect.max_y().max(height).min(rect.height());
+    let panel = LocalRect::new([x, top - height].into(), [width, height].into());
+    let rows = (0..count)
+        .map(|i| {
+            let y = top - HEADER_HEIGHT - CHIP_SPACING - i as f32 * (CHIP_HEIGHT + CHIP_SPACING);
+            LocalRect::new(
+                [x + 4.0, y - CHIP_HEIGHT].into(),
+                [width - 8.0, CHIP_HEIGHT].into(),
+            )
+        })
+        .collect();
+    (panel, rows)
+}
+
+/// The events listed under "+N more" for `date`: in the week layout, only
+/// the all day ones.
+fn more_events(events: &[CalendarEvent], date: CalendarDate, week: bool) -> Vec<usize> {
+    let mut on = events_on(events, date);
+    if week {
+        on.retain(|&e| events[e].time.is_none());
+    }
+    on
+}
+
+/// Options for `calendar`. Set them with `CalendarMods`.
+#[derive(Clone, Default)]
+pub struct CalendarOptions {
+    layout: CalendarLayout,
+    week_start: Option<Weekday>,
+    on_create: Option<Rc<dyn Fn(&mut Context, CalendarDate, Option<u32>)>>,
+    on_event: Option<Rc<dyn Fn(&mut Context, usize)>>,
+}
+
+pub trait CalendarMods: View + Sized {
+    /// Shows a month or a week.
+    fn layout(self, layout: CalendarLayout) -> Self;
+
+    /// Starts weeks on `day`, rather than as the user's locale does.
+    fn week_start(self, day: Weekday) -> Self;
+
+    /// Calls `f` with the day, and in the week layout the minute, when
+    /// somewhere without an event is tapped, to create one there.
+    fn on_create(self, f: impl Fn(&mut Context, CalendarDate, Option<u32>) + 'static) -> Self;
+
+    /// Calls `f` with the index of an event when it's tapped.
+    fn on_event(self, f: impl Fn(&mut Context, usize) + 'static) -> Self;
+}
+
+impl<F> CalendarMods for ModView<CalendarOptions, F>
+where
+    ModView<CalendarOptions, F>: View,
+{
+    fn layout(self, layout: CalendarLayout) -> Self {
+        let mut opts = self.value;
+        opts.layout = layout;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn week_start(self, day: Weekday) -> Self {
+        let mut opts = self.value;
+        opts.week_start = Some(day);
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_create(self, f: impl Fn(&mut Context, CalendarDate, Option<u32>) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_create = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_event(self, f: impl Fn(&mut Context, usize) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_event = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CalendarState {
+    /// A day in the month or week shown.
+    shown: CalendarDate,
+    today: CalendarDate,
+    /// The day whose "+N more" panel is open.
+    more: Option<CalendarDate>,
+    /// How far the week's times of day are scrolled down.
+    scroll_y: f32,
+    size: LocalSize,
+}
+
+impl CalendarState {
+    fn new() -> Self {
+        let today = CalendarDate::today();
+        Self {
+            shown: today,
+            today,
+            more: None,
+            // Start the working day at the top.
+            scroll_y: 8.0 * HOUR_HEIGHT,
+            size: LocalSize::zero(),
+        }
+    }
+
+    fn title(&self, layout: CalendarLayout, start: Weekday) -> String {
+        let month = |d: CalendarDate| MONTH_NAMES[d.month as usize - 1];
+        match layout {
+            CalendarLayout::Month => format!("{} {}", month(self.shown), self.shown.year),
+            CalendarLayout::Week => {
+                let (first, last) = {
+                    let first = self.shown.start_of_week(start);
+                    (first, first.add_days(6))
+                };
+                if first.month == last.month {
+                    format!(
+                        "{} {} – {}, {}",
+                        &month(first)[..3],
+                        first.day,
+                        last.day,
+                        last.year
+                    )
+                } else {
+                    format!(
+                        "{} {} – {} {}, {}",
+                        &month(first)[..3],
+                        first.day,
+                        &month(last)[..3],
+                        last.day,
+                        last.year
+                    )
+                }
+            }
+        }
+    }
+
+    fn step(&mut self, layout: CalendarLayout, by: i32) {
+        self.shown = match layout {
+            CalendarLayout::Month => self.shown.add_months(by),
+            CalendarLayout::Week => self.shown.add_days(7 * by as i64),
+        };
+        self.more = None;
+    }
+}
+
+fn draw_text(vger: &mut Vger, s: &str, x: f32, top: f32, color: Color) {
+    vger.save();
+    vger.translate([x, top - 3.0]);
+    vger.text(s, FONT_SIZE, color, None);
+    vger.restore();
+}
+
+fn draw_chip(vger: &mut Vger, chip: LocalRect, event: &CalendarEvent, with_time: bool) {
+    let paint = vger.color_paint(event.color);
+    vger.fill_rect(chip, 3.0, paint);
+    let label = match (&event.time, with_time) {
+        (Some(time), true) => format!("{} {}", time_label(time.start), event.title),
+        _ => event.title.clone(),
+    };
+    vger.save();
+    vger.scissor(chip.inflate(-2.0, 0.0));
+    draw_text(vger, &label, chip.min_x() + 4.0, chip.max_y(), TEXT_COLOR);
+    vger.restore();
+}
+
+fn geometry(
+    rect: LocalRect,
+    s: &CalendarState,
+    opts: &CalendarOptions,
+    start: Weekday,
+    events: &[CalendarEvent],
+) -> CalendarGeometry {
+    match opts.layout {
+        CalendarLayout::Month => CalendarGeometry::month(rect, s.shown, start, events),
+        CalendarLayout::Week => CalendarGeometry::week(rect, s.shown, start, events, s.scroll_y),
+    }
+}
+
+fn calendar_canvas(
+    events: impl Binding<Vec<CalendarEvent>>,
+    state: StateHandle<CalendarState>,
+    opts: CalendarOptions,
+    start: Weekday,
+) -> impl View {
+    let (tap_opts, scroll_layout) = (opts.clone(), opts.layout);
+    canvas(move |cx, rect, vger| {
+        cx[state].size = rect.size;
+        let s = cx[state].clone();
+        let events = events.get(cx);
+        let g = geometry(rect, &s, &opts, start, events);
+        let week = opts.layout == CalendarLayout::Week;
+        let grid = vger.color_paint(GROOVES);
+        let left = if week { TIME_WIDTH } else { 0.0 };
+        let w = (rect.width() - left) / 7.0;
+
+        // Times of day, scrolled, under the rest.
+        if week {
+            vger.save();
+            vger.scissor(LocalRect::new(
+                LocalPoint::zero(),
+                [rect.width(), g.grid_top.max(0.0)].into(),
+            ));
+            for hour in 0..24 {
+                let y = g.grid_top + s.scroll_y - hour as f32 * HOUR_HEIGHT;
+                vger.fill_rect(
+                    LocalRect::new([left, y].into(), [rect.width() - left, 1.0].into()),
+                    0.0,
+                    grid,
+                );
+                if hour > 0 {
+                    draw_text(vger, &time_label(hour * 60), 6.0, y + 7.0, MEDIUM_GRAY);
+                }
+            }
+            for chip in &g.timed {
+                draw_chip(vger, chip.rect, &events[chip.event], false);
+            }
+            vger.restore();
+        }
+
+        // Days.
+        for (i, cell) in g.cells.iter().enumerate() {
+            let outside = !week && cell.date.month != s.shown.month;
+            if outside {
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.fill_rect(cell.rect, 0.0, paint);
+            }
+            if !week {
+                let number = cell.date.day.to_string();
+                let color = if outside { MEDIUM_GRAY } else { TEXT_COLOR };
+                if cell.date == s.today {
+                    let paint = vger.color_paint(RED_HIGHLIGHT);
+                    let center = [cell.rect.min_x() + 14.0, cell.rect.max_y() - 11.0];
+                    vger.fill_circle(center, 9.0, paint);
+                }
+                let x = cell.rect.min_x() + if cell.date.day < 10 { 11.0 } else { 7.0 };
+                draw_text(vger, &number, x, cell.rect.max_y() - 2.0, color);
+            }
+            for chip in &cell.chips {
+                draw_chip(vger, chip.rect, &events[chip.event], !week);
+            }
+            if let Some((more, n)) = cell.more {
+                draw_text(
+                    vger,
+                    &format!("+{} more", n),
+                    more.min_x() + 4.0,
+                    more.max_y(),
+                    MEDIUM_GRAY,
+                );
+            }
+            let line = LocalRect::new(
+                [cell.rect.min_x(), cell.rect.min_y()].into(),
+                [1.0, cell.rect.height()].into(),
+            );
+            if i % 7 != 0 || week {
+                vger.fill_rect(line, 0.0, grid);
+            }
+            vger.fill_rect(
+                LocalRect::new(cell.rect.origin, [cell.rect.width(), 1.0].into()),
+                0.0,
+                grid,
+            );
+        }
+        for (date, column) in &g.columns {
+            vger.fill_rect(
+                LocalRect::new(column.origin, [1.0, column.height()].into()),
+                0.0,
+                grid,
+            );
+            if *date == s.today {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+                vger.fill_rect(
+                    LocalRect::new(
+                        [column.min_x(), rect.height() - HEADER_HEIGHT].into(),
+                        [column.width(), HEADER_HEIGHT].into(),
+                    ),
+                    0.0,
+                    paint,
+                );
+            }
+        }
+
+        // Names of the days.
+        for i in 0..7 {
+            let day = Weekday::from_monday(start.index() + i);
+            let x = left + i as f32 * w + 6.0;
+            let label = if week {
+                let date = s.shown.start_of_week(start).add_days(i as i64);
+                format!("{} {}", day.short_name(), date.day)
+            } else {
+                day.short_name().to_string()
+            };
+            draw_text(vger, &label, x, rect.height() - 3.0, MEDIUM_GRAY);
+        }
+        vger.fill_rect(
+            LocalRect::new(
+                [0.0, rect.height() - HEADER_HEIGHT].into(),
+                [rect.width(), 1.0].into(),
+            ),
+            0.0,
+            grid,
+        );
+
+        // The "+N more" panel, over everything.
+        if let Some(cell) = s
+            .more
+            .and_then(|date| g.cells.iter().find(|c| c.date == date))
+        {
+            let more = more_events(events, cell.date, week);
+            let (panel, rows) = popover(rect, cell, more.len());
+            let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+            vger.fill_rect(panel, 6.0, paint);
+            let border = vger.color_paint(GROOVES);
+            vger.stroke_rect(panel.min(), panel.max(), 6.0, 1.0, border);
+            let title = format!("{} {}", cell.date.weekday().short_name(), cell.date.day);
+            draw_text(
+                vger,
+                &title,
+                panel.min_x() + 8.0,
+                panel.max_y() - 2.0,
+                TEXT_COLOR,
+            );
+            for (row, &event) in rows.iter().zip(more.iter()) {
+                draw_chip(vger, *row, &events[event], true);
+            }
+        }
+    })
+    .clip()
+    .tap_p(move |cx, p, _| {
+        let opts = &tap_opts;
+        let s = cx[state].clone();
+        let rect = LocalRect::from_size(s.size);
+        let week = opts.layout == CalendarLayout::Week;
+        let (g, hit, more) = events.with(cx, |events| {
+            let g = geometry(rect, &s, opts, start, events);
+            let hit = g.hit(p, s.scroll_y);
+            let more = s.more.map(|date| more_events(events, date, week));
+            (g, hit, more)
+        });
+
+        // A tap on the "+N more" panel picks an event, and anywhere else
+        // closes it.
+        if let (Some(date), Some(more)) = (s.more, more) {
+            cx[state].more = None;
+            if let Some(cell) = g.cells.iter().find(|c| c.date == date) {
+                let (panel, rows) = popover(rect, cell, more.len());
+                if panel.contains(p) {
+                    let row = rows.iter().position(|r| r.contains(p));
+                    if let (Some(f), Some(row)) = (&opts.on_event, row) {
+                        f(cx, more[row]);
+                    }
+                    return;
+                }
+            }
+        }
+
+        match hit {
+            Some(CalendarHit::Event(event)) => {
+                if let Some(f) = &opts.on_event {
+                    f(cx, event);
+                }
+            }
+            Some(CalendarHit::More(date)) => cx[state].more = Some(date),
+            Some(CalendarHit::Day(date, minute)) => {
+                if let Some(f) = &opts.on_create {
+                    f(cx, date, minute);
+                }
+            }
+            None => (),
+        }
+    })
+    .scroll(move |cx, delta| {
+        if scroll_layout == CalendarLayout::Week {
+            let s = &mut cx[state];
+            let visible = s.size.height - HEADER_HEIGHT - ALL_DAY_HEIGHT;
+            let max = (24.0 * HOUR_HEIGHT - visible).max(0.0);
+            s.scroll_y = (s.scroll_y - delta.y).clamp(0.0, max);
+            s.more = None;
+        }
+    })
+}
+
+/// Shows `events` a month or a week at a time, with buttons to move
+/// between them. Days show as many events as fit, then "+N more", which
+/// opens a panel listing them all. Weeks start on the day the user's
+/// locale does, unless set with `CalendarMods::week_start`, and tapping a
+/// day calls `CalendarMods::on_create`.
+///
+/// ```no_run
+/// # use rui::*;
+/// let day = CalendarDate::new(2024, 3, 4);
+/// state(
+///     move || vec![CalendarEvent::new(day, "Launch").time(9 * 60, 10 * 60)],
+///     |events, _| {
+///         calendar(events).on_create(move |cx, date, minute| {
+///             let event = CalendarEvent::new(date, "New event");
+///             let event = match minute {
+///                 Some(m) => event.time(m, m + 60),
+///                 None => event,
+///             };
+///             cx[events].push(event);
+///         })
+///     },
+/// );
+/// ```
+pub fn calendar(events: impl Binding<Vec<CalendarEvent>>) -> impl CalendarMods {
+    modview(move |opts: CalendarOptions, _| {
+        let start = opts.week_start.unwrap_or_else(Weekday::week_start);
+        let layout = opts.layout;
+        state(CalendarState::new, move |state, cx| {
+            vstack((
+                hstack((
+                    text(&cx[state].title(layout, start)).padding(Auto),
+                    spacer(),
+                    button("Previous", move |cx| cx[state].step(layout, -1)),
+                    button("Today", move |cx| {
+                        let s = &mut cx[state];
+                        s.shown = s.today;
+                        s.more = None;
+                    }),
+                    button("Next", move |cx| cx[state].step(layout, 1)),
+                )),
+                calendar_canvas(events, state, opts.clone(), start),
+            ))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_dates() {
+        let d = CalendarDate::new(1970, 1, 1);
+        assert_eq!(d.days(), 0);
+        assert_eq!(d.weekday(), Weekday::Thursday);
+        assert_eq!(
+            CalendarDate::from_days(19786),
+            CalendarDate::new(2024, 3, 4)
+        );
+        assert_eq!(CalendarDate::new(2024, 3, 4).weekday(), Weekday::Monday);
+        assert_eq!(
+            CalendarDate::new(2024, 2, 28).add_days(2),
+            CalendarDate::new(2024, 3, 1)
+        );
+        assert_eq!(
+            CalendarDate::new(2023, 12, 31).add_days(1),
+            CalendarDate::new(2024, 1, 1)
+        );
+        assert_eq!(
+            CalendarDate::new(2024, 1, 31).add_months(1),
+            CalendarDate::new(2024, 2, 29)
+        );
+        assert_eq!(
+            CalendarDate::new(2024, 1, 15).add_months(-1),
+            CalendarDate::new(2023, 12, 15)
+        );
+        assert_eq!(CalendarDate::days_in_month(1900, 2), 28);
+        assert_eq!(CalendarDate::days_in_month(2000, 2), 29);
+    }
+
+    #[test]
+    fn test_week_start() {
+        assert_eq!(
+            Weekday::week_start_for_locale("en_US.UTF-8"),
+            Weekday::Sunday
+        );
+        assert_eq!(
+            Weekday::week_start_for_locale("de_DE.UTF-8"),
+            Weekday::Monday
+        );
+        assert_eq!(Weekday::week_start_for_locale("ar-EG"), Weekday::Saturday);
+        assert_eq!(Weekday::week_start_for_locale("C"), Weekday::Monday);
+
+        let d = CalendarDate::new(2024, 3, 6);
+        assert_eq!(
+            d.start_of_week(Weekday::Monday),
+            CalendarDate::new(2024, 3, 4)
+        );
+        assert_eq!(
+            d.start_of_week(Weekday::Sunday),
+            CalendarDate::new(2024, 3, 3)
+        );
+        assert_eq!(
+            d.start_of_week(Weekday::Saturday),
+            CalendarDate::new(2024, 3, 2)
+        );
+    }
+
+    #[test]
+    fn test_month_geometry() {
+        let day = CalendarDate::new(2024, 3, 4);
+        let events: Vec<CalendarEvent> = (0..5)
+            .map(|i| CalendarEvent::new(day, &i.to_string()))
+            .collect();
+        // Rows 100 high: room for three chips.
+        let rect = LocalRect::new(LocalPoint::zero(), [700.0, HEADER_HEIGHT + 600.0].into());
+        let g = CalendarGeometry::month(rect, day, Weekday::Monday, &events);
+
+        assert_eq!(g.cells[0].date, CalendarDate::new(2024, 2, 26));
+        let cell = &g.cells[7];
+        assert_eq!(cell.date, day);
+        assert_eq!(cell.chips.len(), 2);
+        let (more, n) = cell.more.unwrap();
+        assert_eq!(n, 3);
+
+        assert_eq!(
+            g.hit(cell.chips[1].rect.center(), 0.0),
+            Some(CalendarHit::Event(1))
+        );
+        assert_eq!(g.hit(more.center(), 0.0), Some(CalendarHit::More(day)));
+        assert_eq!(
+            g.hit([5.0, 5.0].into(), 0.0),
+            Some(CalendarHit::Day(CalendarDate::new(2024, 4, 1), None))
+        );
+    }
+
+    #[test]
+    fn test_week_geometry() {
+        let day = CalendarDate::new(2024, 3, 4);
+        let events = vec![
+            CalendarEvent::new(day, "a").time(9 * 60, 11 * 60),
+            CalendarEvent::new(day, "b").time(10 * 60, 12 * 60),
+        ];
+        let rect = LocalRect::new(LocalPoint::zero(), [TIME_WIDTH + 700.0, 600.0].into());
+        let g = CalendarGeometry::week(rect, day, Weekday::Monday, &events, 8.0 * HOUR_HEIGHT);
+
+        // Overlapping, so side by side.
+        assert_eq!(g.timed.len(), 2);
+        assert_eq!(g.timed[0].rect.width(), 48.0);
+        assert!(g.timed[1].rect.min_x() > g.timed[0].rect.max_x());
+
+        // Half past eight on Tuesday.
+        let y = g.grid_top - 0.5 * HOUR_HEIGHT - 1.0;
+        assert_eq!(
+            g.hit([TIME_WIDTH + 150.0, y].into(), 8.0 * HOUR_HEIGHT),
+            Some(CalendarHit::Day(
+                CalendarDate::new(2024, 3, 5),
+                Some(8 * 60 + 30)
+            ))
+        );
+    }
+}
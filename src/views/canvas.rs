@@ -1,4 +1,7 @@
 use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 
 /// Struct for `canvas`
 #[derive(Clone)]
@@ -45,8 +48,623 @@ where
 }
 
 /// Canvas for GPU drawing with Vger. See https://github.com/audulus/vger-rs.
+///
+/// Besides vger's own methods, `CanvasExt` fills and strokes `CanvasPath`s
+/// and places text, and `DrawList` records drawing to replay later, which
+/// `cached_canvas` uses to skip rebuilding it.
 pub fn canvas<F: Fn(&mut Context, LocalRect, &mut Vger) + 'static>(f: F) -> impl View {
     Canvas { func: f }
 }
 
 impl<F> private::Sealed for Canvas<F> {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Segment {
+    Move(LocalPoint),
+    Line(LocalPoint),
+    Quad(LocalPoint, LocalPoint),
+    /// Back to the last move.
+    Close,
+}
+
+/// A shape made of lines and curves, built once and filled or stroked
+/// any number of times. Cubic curves are kept as pairs of quadratics,
+/// which is what vger draws.
+///
+/// ```no_run
+/// # use rui::*;
+/// let mut arrow = CanvasPath::new();
+/// arrow.move_to([0.0, 10.0]).line_to([20.0, 10.0]).line_to([20.0, 20.0]);
+/// arrow.line_to([30.0, 5.0]).line_to([20.0, -10.0]).line_to([20.0, 0.0]);
+/// arrow.line_to([0.0, 0.0]).close();
+///
+/// canvas(move |_, _, vger| {
+///     let paint = vger.color_paint(AZURE_HIGHLIGHT);
+///     vger.fill_path(&arrow, paint);
+///     let paint = vger.color_paint(TEXT_COLOR);
+///     vger.stroke_path(&arrow, 1.0, paint);
+/// });
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CanvasPath {
+    pub(crate) segments: Vec<Segment>,
+    start: LocalPoint,
+    last: LocalPoint,
+}
+
+impl CanvasPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A rectangle.
+    pub fn rect(rect: LocalRect) -> Self {
+        let mut path = Self::new();
+        path.move_to(rect.min())
+            .line_to([rect.max_x(), rect.min_y()])
+            .line_to(rect.max())
+            .line_to([rect.min_x(), rect.max_y()])
+            .close();
+        path
+    }
+
+    /// A circle, from four quarter arcs.
+    pub fn circle(center: impl Into<LocalPoint>, radius: f32) -> Self {
+        let c = center.into();
+        // Control points for a cubic quarter circle.
+        let k = radius * 0.552_284_8;
+        let mut path = Self::new();
+        path.move_to([c.x + radius, c.y])
+            .cubic_to(
+                [c.x + radius, c.y + k],
+                [c.x + k, c.y + radius],
+                [c.x, c.y + radius],
+            )
+            .cubic_to(
+                [c.x - k, c.y + radius],
+                [c.x - radius, c.y + k],
+                [c.x - radius, c.y],
+            )
+            .cubic_to(
+                [c.x - radius, c.y - k],
+                [c.x - k, c.y - radius],
+                [c.x, c.y - radius],
+            )
+            .cubic_to(
+                [c.x + k, c.y - radius],
+                [c.x + radius, c.y - k],
+                [c.x + radius, c.y],
+            )
+            .close();
+        path
+    }
+
+    /// An arc from angle `start` to `end`, in radians counterclockwise from
+    /// the x axis, made of quadratics at most an eighth of a turn long.
+    pub fn arc(center: impl Into<LocalPoint>, radius: f32, start: f32, end: f32) -> Self {
+        let c = center.into();
+        let at = |angle: f32, r: f32| LocalPoint::new(c.x + r * angle.cos(), c.y + r * angle.sin());
+        let n = ((end - start).abs() / std::f32::consts::FRAC_PI_4)
+            .ceil()
+            .max(1.0) as usize;
+        let step = (end - start) / n as f32;
+        let mut path = Self::new();
+        path.move_to(at(start, radius));
+        for i in 0..n {
+            let a = start + step * i as f32;
+            // Where the tangents at both ends meet.
+            let control = at(a + step / 2.0, radius / (step / 2.0).cos());
+            path.quad_to(control, at(a + step, radius));
+        }
+        path
+    }
+
+    /// Starts a new outline at `p`.
+    pub fn move_to(&mut self, p: impl Into<LocalPoint>) -> &mut Self {
+        let p = p.into();
+        self.segments.push(Segment::Move(p));
+        self.start = p;
+        self.last = p;
+        self
+    }
+
+    pub fn line_to(&mut self, p: impl Into<LocalPoint>) -> &mut Self {
+        let p = p.into();
+        self.segments.push(Segment::Line(p));
+        self.last = p;
+        self
+    }
+
+    /// A quadratic curve to `p`, pulled toward `control`.
+    pub fn quad_to(
+        &mut self,
+        control: impl Into<LocalPoint>,
+        p: impl Into<LocalPoint>,
+    ) -> &mut Self {
+        let p = p.into();
+        self.segments.push(Segment::Quad(control.into(), p));
+        self.last = p;
+        self
+    }
+
+    /// A cubic curve to `p`, pulled toward `c1` then `c2`.
+    pub fn cubic_to(
+        &mut self,
+        c1: impl Into<LocalPoint>,
+        c2: impl Into<LocalPoint>,
+        p: impl Into<LocalPoint>,
+    ) -> &mut Self {
+        let p = p.into();
+        for (control, end) in split_cubic(self.last, c1.into(), c2.into(), p) {
+            self.segments.push(Segment::Quad(control, end));
+        }
+        self.last = p;
+        self
+    }
+
+    /// Goes back to where the outline started.
+    pub fn close(&mut self) -> &mut Self {
+        self.segments.push(Segment::Close);
+        self.last = self.start;
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The smallest rectangle around the points and control points.
+    pub fn bounds(&self) -> LocalRect {
+        let points = self.segments.iter().flat_map(|s| match *s {
+            Segment::Move(p) | Segment::Line(p) => vec![p],
+            Segment::Quad(c, p) => vec![c, p],
+            Segment::Close => vec![],
+        });
+        LocalRect::from_points(points)
+    }
+
+    /// The path with every point moved by `xform`.
+    pub fn transformed(&self, xform: &Transform2D<f32, LocalSpace, LocalSpace>) -> Self {
+        let f = |p: LocalPoint| xform.transform_point(p);
+        Self {
+            segments: self
+                .segments
+                .iter()
+                .map(|s| match *s {
+                    Segment::Move(p) => Segment::Move(f(p)),
+                    Segment::Line(p) => Segment::Line(f(p)),
+                    Segment::Quad(c, p) => Segment::Quad(f(c), f(p)),
+                    Segment::Close => Segment::Close,
+                })
+                .collect(),
+            start: f(self.start),
+            last: f(self.last),
+        }
+    }
+}
+
+/// A color or gradient to draw with, kept in a `DrawList`. vger's
+/// `PaintIndex`es only last a frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CanvasPaint {
+    Color(Color),
+    /// From `inner` at `start` to `outer` at `end`.
+    LinearGradient {
+        start: LocalPoint,
+        end: LocalPoint,
+        inner: Color,
+        outer: Color,
+    },
+}
+
+impl CanvasPaint {
+    /// Makes the paint for this frame.
+    pub fn paint(&self, vger: &mut Vger) -> PaintIndex {
+        match *self {
+            CanvasPaint::Color(color) => vger.color_paint(color),
+            CanvasPaint::LinearGradient {
+                start,
+                end,
+                inner,
+                outer,
+            } => vger.linear_gradient(start, end, inner, outer, 0.0),
+        }
+    }
+}
+
+impl From<Color> for CanvasPaint {
+    fn from(color: Color) -> Self {
+        CanvasPaint::Color(color)
+    }
+}
+
+/// Drawing with `CanvasPath`s and text, on top of vger's own methods.
+pub trait CanvasExt {
+    /// Fills `path`, closing any outlines left open.
+    fn fill_path(&mut self, path: &CanvasPath, paint: PaintIndex);
+
+    /// Strokes `path` with lines `width` wide.
+    fn stroke_path(&mut self, path: &CanvasPath, width: f32, paint: PaintIndex);
+
+    /// Draws `text` with its baseline starting at `p`.
+    fn text_at(&mut self, text: &str, p: impl Into<LocalPoint>, size: u32, color: Color);
+
+    /// Calls `f` between `save` and `restore`, so transforms and scissors
+    /// it sets are undone after.
+    fn with_save<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R;
+}
+
+impl CanvasExt for Vger {
+    fn fill_path(&mut self, path: &CanvasPath, paint: PaintIndex) {
+        if path.is_empty() {
+            return;
+        }
+        let (mut start, mut last) = (LocalPoint::zero(), LocalPoint::zero());
+        let line = |vger: &mut Vger, from: LocalPoint, to: LocalPoint| {
+            if from != to {
+                vger.quad_to(from.lerp(to, 0.5), to);
+            }
+        };
+        for segment in &path.segments {
+            match *segment {
+                Segment::Move(p) => {
+                    line(self, last, start);
+                    self.move_to(p);
+                    start = p;
+                    last = p;
+                }
+                Segment::Line(p) => {
+                    line(self, last, p);
+                    last = p;
+                }
+                Segment::Quad(c, p) => {
+                    self.quad_to(c, p);
+                    last = p;
+                }
+                Segment::Close => {
+                    line(self, last, start);
+                    last = start;
+                }
+            }
+        }
+        line(self, last, start);
+        self.fill(paint);
+    }
+
+    fn stroke_path(&mut self, path: &CanvasPath, width: f32, paint: PaintIndex) {
+        let (mut start, mut last) = (LocalPoint::zero(), LocalPoint::zero());
+        for segment in &path.segments {
+            match *segment {
+                Segment::Move(p) => start = p,
+                Segment::Line(p) => self.stroke_segment(last, p, width, paint),
+                Segment::Quad(c, p) => self.stroke_bezier(last, c, p, width, paint),
+                Segment::Close => self.stroke_segment(last, start, width, paint),
+            }
+            last = match *segment {
+                Segment::Move(p) | Segment::Line(p) | Segment::Quad(_, p) => p,
+                Segment::Close => start,
+            };
+        }
+    }
+
+    fn text_at(&mut self, text: &str, p: impl Into<LocalPoint>, size: u32, color: Color) {
+        let p = p.into();
+        self.save();
+        self.translate([p.x, p.y]);
+        self.text(text, size, color, None);
+        self.restore();
+    }
+
+    fn with_save<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.save();
+        let result = f(self);
+        self.restore();
+        result
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum DrawOp {
+    Save,
+    Restore,
+    Translate(LocalVector),
+    Scale(LocalVector),
+    Rotate(f32),
+    Scissor(LocalRect),
+    FillPath(CanvasPath, CanvasPaint),
+    StrokePath(CanvasPath, f32, CanvasPaint),
+    FillRect(LocalRect, f32, CanvasPaint),
+    FillCircle(LocalPoint, f32, CanvasPaint),
+    Text(String, LocalPoint, u32, Color),
+}
+
+/// Drawing recorded to replay with `draw`, so it can be built once and
+/// drawn every frame. `cached_canvas` keeps one for you.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DrawList {
+    ops: Vec<DrawOp>,
+}
+
+impl DrawList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Pushes the transform and scissor, to get back to with `restore`.
+    pub fn save(&mut self) -> &mut Self {
+        self.ops.push(DrawOp::Save);
+        self
+    }
+
+    pub fn restore(&mut self) -> &mut Self {
+        self.ops.push(DrawOp::Restore);
+        self
+    }
+
+    pub fn translate(&mut self, offset: impl Into<LocalVector>) -> &mut Self {
+        self.ops.push(DrawOp::Translate(offset.into()));
+        self
+    }
+
+    pub fn scale(&mut self, scale: impl Into<LocalVector>) -> &mut Self {
+        self.ops.push(DrawOp::Scale(scale.into()));
+        self
+    }
+
+    /// Rotates counterclockwise by `radians`.
+    pub fn rotate(&mut self, radians: f32) -> &mut Self {
+        self.ops.push(DrawOp::Rotate(radians));
+        self
+    }
+
+    /// Only draws inside `rect` until `restore`.
+    pub fn scissor(&mut self, rect: LocalRect) -> &mut Self {
+        self.ops.push(DrawOp::Scissor(rect));
+        self
+    }
+
+    pub fn fill_path(&mut self, path: &CanvasPath, paint: impl Into<CanvasPaint>) -> &mut Self {
+        self.ops.push(DrawOp::FillPath(path.clone(), paint.into()));
+        self
+    }
+
+    pub fn stroke_path(
+        &mut self,
+        path: &CanvasPath,
+        width: f32,
+        paint: impl Into<CanvasPaint>,
+    ) -> &mut Self {
+        self.ops
+            .push(DrawOp::StrokePath(path.clone(), width, paint.into()));
+        self
+    }
+
+    /// Fills `rect`, with corners rounded by `radius`.
+    pub fn fill_rect(
+        &mut self,
+        rect: LocalRect,
+        radius: f32,
+        paint: impl Into<CanvasPaint>,
+    ) -> &mut Self {
+        self.ops.push(DrawOp::FillRect(rect, radius, paint.into()));
+        self
+    }
+
+    pub fn fill_circle(
+        &mut self,
+        center: impl Into<LocalPoint>,
+        radius: f32,
+        paint: impl Into<CanvasPaint>,
+    ) -> &mut Self {
+        self.ops
+            .push(DrawOp::FillCircle(center.into(), radius, paint.into()));
+        self
+    }
+
+    /// Draws `text` with its baseline starting at `p`.
+    pub fn text(
+        &mut self,
+        text: &str,
+        p: impl Into<LocalPoint>,
+        size: u32,
+        color: Color,
+    ) -> &mut Self {
+        self.ops
+            .push(DrawOp::Text(text.into(), p.into(), size, color));
+        self
+    }
+
+    /// Replays the drawing, inside a `save` and `restore` of its own.
+    pub fn draw(&self, vger: &mut Vger) {
+        vger.save();
+        for op in &self.ops {
+            match op {
+                DrawOp::Save => vger.save(),
+                DrawOp::Restore => vger.restore(),
+                DrawOp::Translate(offset) => vger.translate(*offset),
+                DrawOp::Scale(scale) => vger.scale(*scale),
+                DrawOp::Rotate(radians) => vger.rotate(*radians),
+                DrawOp::Scissor(rect) => vger.scissor(*rect),
+                DrawOp::FillPath(path, paint) => {
+                    let paint = paint.paint(vger);
+                    vger.fill_path(path, paint);
+                }
+                DrawOp::StrokePath(path, width, paint) => {
+                    let paint = paint.paint(vger);
+                    vger.stroke_path(path, *width, paint);
+                }
+                DrawOp::FillRect(rect, radius, paint) => {
+                    let paint = paint.paint(vger);
+                    vger.fill_rect(*rect, *radius, paint);
+                }
+                DrawOp::FillCircle(center, radius, paint) => {
+                    let paint = paint.paint(vger);
+                    vger.fill_circle(*center, *radius, paint);
+                }
+                DrawOp::Text(text, p, size, color) => vger.text_at(text, *p, *size, *color),
+            }
+        }
+        vger.restore();
+    }
+}
+
+/// A `DrawList`, and the hash of what it was built from.
+struct CanvasCache {
+    hash: u64,
+    list: Rc<DrawList>,
+}
+
+/// Struct for `cached_canvas`.
+pub struct CachedCanvas<K, F> {
+    key: K,
+    func: F,
+}
+
+impl<K, F> View for CachedCanvas<K, F>
+where
+    K: Hash + 'static,
+    F: Fn(&mut Context, LocalRect, &mut DrawList) + 'static,
+{
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let mut hasher = DefaultHasher::new();
+        self.key.hash(&mut hasher);
+        (rect.width().to_bits(), rect.height().to_bits()).hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let id = args.cx.view_id(path);
+        args.cx.init_state(id, &|| CanvasCache {
+            hash: 0,
+            list: Default::default(),
+        });
+        let cache = StateHandle::<CanvasCache>::new(id);
+        let cached = &args.cx[cache];
+        let list = if cached.hash == hash && !cached.list.is_empty() {
+            cached.list.clone()
+        } else {
+            let mut list = DrawList::new();
+            (self.func)(args.cx, rect, &mut list);
+            let list = Rc::new(list);
+            args.cx[cache] = CanvasCache {
+                hash,
+                list: list.clone(),
+            };
+            list
+        };
+        list.draw(args.vger);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let rect = cx.get_layout(path).rect;
+
+        if rect.contains(pt) {
+            Some(cx.view_id(path))
+        } else {
+            None
+        }
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+    }
+}
+
+impl<K, F> private::Sealed for CachedCanvas<K, F> {}
+
+/// Like `canvas`, but records the drawing into a `DrawList` and replays it
+/// on later frames, only calling `f` again when `key`, the inputs to the
+/// drawing, or the size changes. Suits drawing that's costly to build,
+/// like plots of many points.
+///
+/// ```no_run
+/// # use rui::*;
+/// let points: Vec<f32> = (0..1000).map(|i| (i as f32 * 0.1).sin()).collect();
+/// cached_canvas(points.len(), move |_, rect, list| {
+///     let mut path = CanvasPath::new();
+///     for (i, y) in points.iter().enumerate() {
+///         let p = [
+///             i as f32 / points.len() as f32 * rect.width(),
+///             (y + 1.0) / 2.0 * rect.height(),
+///         ];
+///         if i == 0 {
+///             path.move_to(p);
+///         } else {
+///             path.line_to(p);
+///         }
+///     }
+///     list.stroke_path(&path, 1.0, AZURE_HIGHLIGHT);
+/// });
+/// ```
+pub fn cached_canvas<K, F>(key: K, f: F) -> CachedCanvas<K, F>
+where
+    K: Hash + 'static,
+    F: Fn(&mut Context, LocalRect, &mut DrawList) + 'static,
+{
+    CachedCanvas { key, func: f }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_canvas_path() {
+        let rect = LocalRect::new([1.0, 2.0].into(), [3.0, 4.0].into());
+        let path = CanvasPath::rect(rect);
+        assert_eq!(path.segments.len(), 5);
+        assert_eq!(path.bounds(), rect);
+
+        let circle = CanvasPath::circle([0.0, 0.0], 10.0);
+        // Four cubics, as eight quadratics.
+        assert_eq!(circle.segments.len(), 10);
+        let bounds = circle.bounds();
+        assert!((bounds.max_x() - 10.0).abs() < 1e-4);
+        assert!(bounds.min_y() < -10.0 + 1e-4);
+
+        let moved = path.transformed(&Transform2D::translation(1.0, 1.0));
+        assert_eq!(moved.bounds(), rect.translate([1.0, 1.0].into()));
+    }
+
+    #[test]
+    fn test_arc_path() {
+        let arc = CanvasPath::arc([0.0, 0.0], 10.0, 0.0, std::f32::consts::PI);
+        // A move and four eighths of a turn.
+        assert_eq!(arc.segments.len(), 5);
+        let bounds = arc.bounds();
+        assert!((bounds.min_x() + 10.0).abs() < 1e-4);
+        assert!((bounds.max_x() - 10.0).abs() < 1e-4);
+        assert!(bounds.min_y().abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_draw_list() {
+        let mut list = DrawList::new();
+        assert!(list.is_empty());
+        list.save()
+            .translate([10.0, 0.0])
+            .fill_rect(LocalRect::zero(), 2.0, RED_HIGHLIGHT)
+            .text("hi", [0.0, 0.0], 12, TEXT_COLOR)
+            .restore();
+        assert_eq!(list.ops.len(), 5);
+        assert_eq!(
+            list.ops[2],
+            DrawOp::FillRect(LocalRect::zero(), 2.0, CanvasPaint::Color(RED_HIGHLIGHT))
+        );
+    }
+}
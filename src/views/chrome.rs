@@ -0,0 +1,206 @@
+use crate::*;
+use std::any::Any;
+
+/// Corner radius in the environment, for rectangles which don't set their
+/// own. Set by the `corner_radius` modifier.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CornerRadius(pub f32);
+
+/// Layers drawn for an elevation shadow, since vger can't blur.
+const SHADOW_LAYERS: u32 = 4;
+
+/// Struct for the `corner_radius`, `border` and `elevation` modifiers.
+/// Calling another of them adjusts this one rather than adding a view.
+pub struct Chrome<V> {
+    child: V,
+    radius: Option<f32>,
+    border: Option<(f32, Color)>,
+    elevation: u32,
+}
+
+impl<V> Chrome<V>
+where
+    V: View,
+{
+    pub fn new(child: V) -> Self {
+        Self {
+            child,
+            radius: None,
+            border: None,
+            elevation: 0,
+        }
+    }
+
+    /// Rounds the border, the shadow, and rectangles inside, like a
+    /// `background` rectangle, which don't set their own corner radius.
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+
+    /// Strokes a border inside the edge.
+    pub fn border(mut self, width: f32, color: Color) -> Self {
+        self.border = Some((width, color));
+        self
+    }
+
+    /// Casts a shadow, which gets larger and softer with each level, in the
+    /// theme's shadow color.
+    pub fn elevation(mut self, level: u32) -> Self {
+        self.elevation = level;
+        self
+    }
+
+    /// Sets the corner radius in the environment while `f` runs.
+    fn with_radius<R>(&self, cx: &mut Context, f: impl FnOnce(&mut Context) -> R) -> R {
+        match self.radius {
+            Some(radius) => {
+                let old = cx.set_env(&CornerRadius(radius));
+                let result = f(cx);
+                cx.set_env(&old.unwrap_or_default());
+                result
+            }
+            None => f(cx),
+        }
+    }
+}
+
+impl<V> View for Chrome<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.with_radius(cx, |cx| self.child.process(event, path, cx, actions));
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let radius = match self.radius {
+            Some(radius) => radius,
+            None => args.cx.init_env(&CornerRadius::default).0,
+        };
+
+        if self.elevation > 0 {
+            let color = args.cx.theme().colors.shadow;
+            let level = self.elevation as f32;
+            let blur = 2.0 * level;
+            let alpha = color.a / SHADOW_LAYERS as f32;
+            let paint = args.vger.color_paint(color.alpha(alpha));
+            // Shadows fall below.
+            let rect = rect.translate([0.0, -level].into());
+            for i in 1..=SHADOW_LAYERS {
+                let spread = blur * i as f32 / SHADOW_LAYERS as f32;
+                args.vger
+                    .fill_rect(rect.inflate(spread, spread), radius + spread, paint);
+            }
+        }
+
+        path.push(0);
+        match self.radius {
+            Some(radius) => {
+                let old = args.cx.set_env(&CornerRadius(radius));
+                self.child.draw(path, args);
+                args.cx.set_env(&old.unwrap_or_default());
+            }
+            None => self.child.draw(path, args),
+        }
+        path.pop();
+
+        if let Some((width, color)) = self.border {
+            // Keep thin borders crisp by covering whole pixels.
+            let (rect, width) = if args.cx.init_env(&PixelSnap::default).0 {
+                let scale = args.cx.scale_factor();
+                let xform = args.vger.current_transform();
+                (snap_rect(rect, xform, scale), snap_length(width, scale))
+            } else {
+                (rect, width)
+            };
+            let inset = rect.inflate(-width / 2.0, -width / 2.0);
+            let paint = args.vger.color_paint(color);
+            args.vger.stroke_rect(
+                inset.min(),
+                inset.max(),
+                (radius - width / 2.0).max(0.0),
+                width,
+                paint,
+            );
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let offered = args.sz;
+        let text_bounds = &mut *args.text_bounds;
+        path.push(0);
+        let sz = self.with_radius(args.cx, |cx| {
+            self.child.layout(
+                path,
+                &mut LayoutArgs {
+                    sz: offered,
+                    cx,
+                    text_bounds,
+                },
+            )
+        });
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+
+    fn is_flexible(&self) -> bool {
+        self.child.is_flexible()
+    }
+}
+
+impl<V> private::Sealed for Chrome<V> {}
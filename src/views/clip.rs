@@ -39,9 +39,12 @@ where
 
         args.vger.save();
         args.vger.scissor(rect);
+        let world = args.vger.current_transform().outer_transformed_rect(&rect);
+        let clip = args.cx.clip_to(world);
         path.push(0);
         self.child.draw(path, args);
         path.pop();
+        args.cx.clip_rect = clip;
         args.vger.restore();
     }
 
@@ -8,6 +8,119 @@ pub struct Cond<V0, V1> {
     if_false: V1,
 }
 
+/// What `cond` remembers to animate switching between its views.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct CondState {
+    /// Which view was shown as of the last animation frame.
+    shown: Option<bool>,
+    /// Whether each view has a `transition`, true's first.
+    animated: [bool; 2],
+    /// The view being switched away from, kept until it has transitioned
+    /// out.
+    exiting: Option<bool>,
+    /// How far through switching, from 0 to 1.
+    progress: f32,
+}
+
+impl Default for CondState {
+    fn default() -> Self {
+        Self {
+            shown: None,
+            animated: [false; 2],
+            exiting: None,
+            progress: 1.0,
+        }
+    }
+}
+
+fn index(b: bool) -> usize {
+    if b {
+        0
+    } else {
+        1
+    }
+}
+
+impl<V0, V1> Cond<V0, V1>
+where
+    V0: View,
+    V1: View,
+{
+    fn state(&self, path: &IdPath, cx: &mut Context) -> CondState {
+        let vid = cx.view_id(path);
+        cx.init_state(vid, &CondState::default);
+        *StateHandle::<CondState>::new(vid).get(cx)
+    }
+
+    /// The view being switched away from, if it's still showing.
+    fn exiting(&self, s: &CondState) -> Option<bool> {
+        match s.shown {
+            // Switched since the last animation frame.
+            Some(shown) if shown != self.cond => Some(shown).filter(|b| s.animated[index(*b)]),
+            _ => s.exiting,
+        }
+    }
+
+    fn progress(&self, s: &CondState) -> f32 {
+        if s.shown == Some(!self.cond) {
+            0.0
+        } else {
+            s.progress
+        }
+    }
+
+    /// Steps switching along, and notes whether the shown view has a
+    /// transition.
+    fn animate(&self, path: &mut IdPath, cx: &mut Context, actions: &mut Vec<Box<dyn Any>>) {
+        let vid = cx.view_id(path);
+        let s = StateHandle::<CondState>::new(vid);
+        let mut next = *s.get(cx);
+
+        let outer = std::mem::replace(&mut cx.transition_seen, false);
+        if self.cond {
+            path.push(0);
+            self.if_true.process(&Event::Anim, path, cx, actions);
+            path.pop();
+        } else {
+            path.push(1);
+            self.if_false.process(&Event::Anim, path, cx, actions);
+            path.pop();
+        }
+        let seen = cx.transition_seen;
+        cx.transition_seen = outer || seen;
+        next.animated[index(self.cond)] = seen;
+
+        match next.shown {
+            Some(shown) if shown != self.cond => {
+                next.exiting = self.exiting(s.get(cx));
+                next.progress = if seen || next.exiting.is_some() {
+                    0.0
+                } else {
+                    1.0
+                };
+            }
+            _ if next.progress < 1.0 => {
+                next.progress = match cx.anim_scrub() {
+                    // Short of the end, which would drop the exiting view.
+                    Some(t) => t.min(0.99),
+                    None => (next.progress + cx.anim_dt / TRANSITION_DURATION).min(1.0),
+                };
+                if next.progress >= 1.0 {
+                    next.exiting = None;
+                }
+                cx.note_animation("transition", Some(next.progress));
+            }
+            _ => (),
+        }
+        next.shown = Some(self.cond);
+
+        // Writing keeps animation frames coming, so stop once done.
+        if next != *s.get(cx) {
+            cx[s] = next;
+        }
+    }
+}
+
 impl<V0, V1> View for Cond<V0, V1>
 where
     V0: View,
@@ -20,7 +133,10 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if self.cond {
+        self.state(path, cx);
+        if let Event::Anim = event {
+            self.animate(path, cx, actions);
+        } else if self.cond {
             path.push(0);
             self.if_true.process(event, path, cx, actions);
             path.pop();
@@ -32,18 +148,48 @@ where
     }
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
-        if self.cond {
-            path.push(0);
-            self.if_true.draw(path, args);
-            path.pop();
-        } else {
-            path.push(1);
-            self.if_false.draw(path, args);
-            path.pop();
+        let s = self.state(path, args.cx);
+        let progress = self.progress(&s);
+        let old = args.cx.init_env(&TransitionPhase::default);
+
+        let exiting = self.exiting(&s);
+        for b in exiting.into_iter().chain(Some(self.cond)) {
+            let shown = if b == self.cond {
+                progress
+            } else {
+                1.0 - progress
+            };
+            if shown < 1.0 {
+                args.cx.set_env(&TransitionPhase(Some(shown)));
+            }
+            if b {
+                path.push(0);
+                self.if_true.draw(path, args);
+                path.pop();
+            } else {
+                path.push(1);
+                self.if_false.draw(path, args);
+                path.pop();
+            }
+            args.cx.set_env(&old);
         }
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        // Lay out a view transitioning out too, so it can be drawn.
+        let s = self.state(path, args.cx);
+        if let Some(b) = self.exiting(&s) {
+            if b {
+                path.push(0);
+                self.if_true.layout(path, args);
+                path.pop();
+            } else {
+                path.push(1);
+                self.if_false.layout(path, args);
+                path.pop();
+            }
+        }
+
         if self.cond {
             path.push(0);
             let sz = self.if_true.layout(path, args);
@@ -84,11 +230,17 @@ where
     }
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
-        if self.cond {
+        map.push(cx.view_id(path));
+
+        // Keep the state of a view transitioning out until it's gone.
+        let s = self.state(path, cx);
+        let exiting = self.exiting(&s);
+        if self.cond || exiting == Some(true) {
             path.push(0);
             self.if_true.gc(path, cx, map);
             path.pop();
-        } else {
+        }
+        if !self.cond || exiting == Some(false) {
             path.push(1);
             self.if_false.gc(path, cx, map);
             path.pop();
@@ -117,7 +269,8 @@ where
 
 impl<V0, V1> private::Sealed for Cond<V0, V1> {}
 
-/// Switches between views according to a boolean.
+/// Switches between views according to a boolean. Views with a
+/// `transition` animate in and out.
 pub fn cond(cond: bool, if_true: impl View, if_false: impl View) -> impl View {
     Cond {
         cond,
@@ -125,3 +278,35 @@ pub fn cond(cond: bool, if_true: impl View, if_false: impl View) -> impl View {
         if_false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_cond_transition() {
+        let mut cx = Context::new();
+        let mut path = vec![0];
+        let mut actions = vec![];
+        let ui = |b| cond(b, rectangle().transition(Transition::Slide), rectangle());
+
+        ui(true).process(&Event::Anim, &mut path, &mut cx, &mut actions);
+        ui(false).process(&Event::Anim, &mut path, &mut cx, &mut actions);
+
+        // The true view is kept while it slides out.
+        let s = StateHandle::<CondState>::new(cx.view_id(&path));
+        assert_eq!(s.get(&cx).exiting, Some(true));
+        assert_eq!(s.get(&cx).progress, 0.0);
+
+        cx.anim_dt = TRANSITION_DURATION;
+        ui(false).process(&Event::Anim, &mut path, &mut cx, &mut actions);
+        assert_eq!(s.get(&cx).exiting, None);
+        assert_eq!(s.get(&cx).progress, 1.0);
+
+        // Switching back, there's nothing to slide out.
+        ui(true).process(&Event::Anim, &mut path, &mut cx, &mut actions);
+        assert_eq!(s.get(&cx).exiting, None);
+        assert_eq!(s.get(&cx).progress, 0.0);
+    }
+}
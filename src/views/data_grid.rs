@@ -0,0 +1,1098 @@
+use crate::*;
+use std::ops::Range;
+
+/// What a column of a `data_grid` holds, and so how its cells are edited.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellKind {
+    Text,
+    Number,
+    Checkbox,
+    /// Text chosen from a list.
+    Dropdown(Vec<String>),
+}
+
+/// The value of a `data_grid` cell.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CellValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl CellValue {
+    /// The value for an empty cell of `kind`.
+    pub fn empty(kind: &CellKind) -> Self {
+        match kind {
+            CellKind::Text | CellKind::Dropdown(_) => CellValue::Text(String::new()),
+            CellKind::Number => CellValue::Number(0.0),
+            CellKind::Checkbox => CellValue::Bool(false),
+        }
+    }
+
+    /// Reads a value for a cell of `kind`, as typed or pasted. Checkboxes
+    /// take words like "true" and "no", and dropdowns only their choices.
+    pub fn parse(kind: &CellKind, s: &str) -> Option<Self> {
+        let s = s.trim();
+        match kind {
+            CellKind::Text => Some(CellValue::Text(s.to_string())),
+            CellKind::Number => s.parse().ok().map(CellValue::Number),
+            CellKind::Checkbox => match s.to_lowercase().as_str() {
+                "true" | "yes" | "1" | "x" => Some(CellValue::Bool(true)),
+                "false" | "no" | "0" | "" => Some(CellValue::Bool(false)),
+                _ => None,
+            },
+            CellKind::Dropdown(choices) => choices
+                .iter()
+                .find(|c| c.eq_ignore_ascii_case(s))
+                .map(|c| CellValue::Text(c.clone())),
+        }
+    }
+}
+
+impl std::fmt::Display for CellValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CellValue::Text(s) => write!(f, "{}", s),
+            CellValue::Number(n) => write!(f, "{}", n),
+            CellValue::Bool(b) => write!(f, "{}", if *b { "TRUE" } else { "FALSE" }),
+        }
+    }
+}
+
+/// Rows of cells for `data_grid`, in columns of one `CellKind` each.
+pub trait GridModel {
+    fn row_count(&self) -> usize;
+    fn column_count(&self) -> usize;
+    fn column_title(&self, column: usize) -> String;
+    fn column_kind(&self, column: usize) -> CellKind;
+
+    fn column_width(&self, _column: usize) -> f32 {
+        100.0
+    }
+
+    fn cell(&self, row: usize, column: usize) -> CellValue;
+    fn set_cell(&mut self, row: usize, column: usize, value: CellValue);
+}
+
+/// A column of a `GridTable`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridColumn {
+    pub title: String,
+    pub kind: CellKind,
+    pub width: f32,
+}
+
+/// A `GridModel` which keeps its cells in memory.
+///
+/// ```
+/// # use rui::*;
+/// let table = GridTable::new()
+///     .column("Name", CellKind::Text)
+///     .column("Done", CellKind::Checkbox)
+///     .row(vec![CellValue::Text("Write docs".into()), CellValue::Bool(false)]);
+/// assert_eq!(table.cell(0, 1), CellValue::Bool(false));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GridTable {
+    pub columns: Vec<GridColumn>,
+    pub rows: Vec<Vec<CellValue>>,
+}
+
+impl GridTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn column(mut self, title: &str, kind: CellKind) -> Self {
+        self.columns.push(GridColumn {
+            title: title.into(),
+            kind,
+            width: 100.0,
+        });
+        self
+    }
+
+    pub fn row(mut self, cells: Vec<CellValue>) -> Self {
+        self.rows.push(cells);
+        self
+    }
+}
+
+impl GridModel for GridTable {
+    fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn column_count(&self) -> usize {
+        self.columns.len()
+    }
+
+    fn column_title(&self, column: usize) -> String {
+        self.columns[column].title.clone()
+    }
+
+    fn column_kind(&self, column: usize) -> CellKind {
+        self.columns[column].kind.clone()
+    }
+
+    fn column_width(&self, column: usize) -> f32 {
+        self.columns[column].width
+    }
+
+    /// Rows too short for a column have empty cells there.
+    fn cell(&self, row: usize, column: usize) -> CellValue {
+        match self.rows.get(row).and_then(|r| r.get(column)) {
+            Some(value) => value.clone(),
+            None => CellValue::empty(&self.columns[column].kind),
+        }
+    }
+
+    fn set_cell(&mut self, row: usize, column: usize, value: CellValue) {
+        let columns = &self.columns;
+        let cells = &mut self.rows[row];
+        while cells.len() <= column {
+            cells.push(CellValue::empty(&columns[cells.len()].kind));
+        }
+        cells[column] = value;
+    }
+}
+
+/// Selected rows and columns.
+type CellRange = (Range<usize>, Range<usize>);
+
+/// The selection as tab-separated values, a row per line, as spreadsheets
+/// copy and paste.
+fn to_tsv(model: &dyn GridModel, (rows, columns): CellRange) -> String {
+    rows.map(|row| {
+        columns
+            .clone()
+            .map(|column| {
+                model
+                    .cell(row, column)
+                    .to_string()
+                    .replace(['\t', '\n', '\r'], " ")
+            })
+            .collect::<Vec<_>>()
+            .join("\t")
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Pastes tab-separated values with their top left at `at`, returning
+/// what was pasted over. Values which don't suit a column are skipped, and
+/// those beyond the grid are dropped.
+fn paste_tsv(model: &mut dyn GridModel, at: (usize, usize), tsv: &str) -> CellRange {
+    let lines: Vec<&str> = tsv.trim_end_matches(['\n', '\r']).lines().collect();
+    let (rows, columns) = (model.row_count(), model.column_count());
+    let mut end = at;
+    for (r, line) in lines.iter().enumerate() {
+        let row = at.0 + r;
+        if row >= rows {
+            break;
+        }
+        for (c, field) in line.split('\t').enumerate() {
+            let column = at.1 + c;
+            if column >= columns {
+                break;
+            }
+            if let Some(value) = CellValue::parse(&model.column_kind(column), field) {
+                model.set_cell(row, column, value);
+            }
+            end = (end.0.max(row), end.1.max(column));
+        }
+    }
+    (at.0..end.0 + 1, at.1..end.1 + 1)
+}
+
+/// Copies the top row of the selection down through the rest of it.
+fn fill_down(model: &mut dyn GridModel, (rows, columns): CellRange) {
+    for column in columns {
+        let value = model.cell(rows.start, column);
+        for row in rows.start + 1..rows.end {
+            model.set_cell(row, column, value.clone());
+        }
+    }
+}
+
+const ROW_HEIGHT: f32 = 24.0;
+const HEADER_HEIGHT: f32 = 26.0;
+const ROW_NUMBER_WIDTH: f32 = 44.0;
+const FONT_SIZE: u32 = 14;
+const CELL_PADDING: f32 = 6.0;
+const MENU_ITEM_HEIGHT: f32 = 22.0;
+
+/// Where the columns of a `data_grid` are. The row numbers and frozen
+/// columns stay put while the rest scroll across.
+struct GridGeometry {
+    widths: Vec<f32>,
+    frozen: usize,
+    /// Right of the row numbers and frozen columns.
+    frozen_x: f32,
+}
+
+impl GridGeometry {
+    fn new(model: &dyn GridModel, frozen: usize) -> Self {
+        let widths: Vec<f32> = (0..model.column_count())
+            .map(|c| model.column_width(c))
+            .collect();
+        let frozen = frozen.min(widths.len());
+        let frozen_x = ROW_NUMBER_WIDTH + widths[..frozen].iter().sum::<f32>();
+        Self {
+            widths,
+            frozen,
+            frozen_x,
+        }
+    }
+
+    /// The left of `column`, scrolled across by `scroll_x` if it isn't
+    /// frozen.
+    fn column_x(&self, column: usize, scroll_x: f32) -> f32 {
+        let x = ROW_NUMBER_WIDTH + self.widths[..column].iter().sum::<f32>();
+        if column < self.frozen {
+            x
+        } else {
+            x - scroll_x
+        }
+    }
+
+    fn column_at(&self, x: f32, scroll_x: f32) -> Option<usize> {
+        (0..self.widths.len()).find(|&c| {
+            let left = self.column_x(c, scroll_x);
+            let visible = c < self.frozen || x >= self.frozen_x;
+            visible && (left..left + self.widths[c]).contains(&x)
+        })
+    }
+
+    fn content_width(&self) -> f32 {
+        ROW_NUMBER_WIDTH + self.widths.iter().sum::<f32>()
+    }
+}
+
+/// Options for `data_grid`. Set them with `DataGridMods`.
+#[derive(Clone, Copy, Default)]
+pub struct DataGridOptions {
+    frozen_columns: usize,
+}
+
+pub trait DataGridMods: View + Sized {
+    /// Keeps the first `columns` in place while the rest scroll across.
+    fn frozen_columns(self, columns: usize) -> Self;
+}
+
+impl<F> DataGridMods for ModView<DataGridOptions, F>
+where
+    ModView<DataGridOptions, F>: View,
+{
+    fn frozen_columns(self, columns: usize) -> Self {
+        let mut opts = self.value;
+        opts.frozen_columns = columns;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct DataGridState {
+    /// Row and column where the selection started.
+    anchor: (usize, usize),
+    /// Row and column of the current cell.
+    cursor: (usize, usize),
+    scroll: LocalOffset,
+    size: LocalSize,
+    /// Text typed into the current cell, while editing it.
+    edit: Option<String>,
+    /// The highlighted choice, while the current cell's dropdown is open.
+    menu: Option<usize>,
+}
+
+impl DataGridState {
+    fn selection(&self) -> CellRange {
+        let (a, c) = (self.anchor, self.cursor);
+        (
+            a.0.min(c.0)..a.0.max(c.0) + 1,
+            a.1.min(c.1)..a.1.max(c.1) + 1,
+        )
+    }
+
+    fn is_selected(&self, row: usize, column: usize) -> bool {
+        let (rows, columns) = self.selection();
+        rows.contains(&row) && columns.contains(&column)
+    }
+
+    fn move_to(&mut self, cell: (usize, usize), select: bool, geometry: &GridGeometry) {
+        self.cursor = cell;
+        if !select {
+            self.anchor = cell;
+        }
+        self.edit = None;
+        self.menu = None;
+        self.reveal(geometry);
+    }
+
+    /// Moves the cursor by `by` rows and columns, staying in the grid.
+    fn step(
+        &mut self,
+        by: (isize, isize),
+        select: bool,
+        model: &dyn GridModel,
+        geometry: &GridGeometry,
+    ) {
+        let clamp =
+            |v: usize, d: isize, n: usize| (v as isize + d).clamp(0, n as isize - 1) as usize;
+        if model.row_count() == 0 || model.column_count() == 0 {
+            return;
+        }
+        let cell = (
+            clamp(self.cursor.0, by.0, model.row_count()),
+            clamp(self.cursor.1, by.1, model.column_count()),
+        );
+        self.move_to(cell, select, geometry);
+    }
+
+    /// Scrolls so the cursor shows.
+    fn reveal(&mut self, geometry: &GridGeometry) {
+        let (row, column) = self.cursor;
+        let body = (self.size.height - HEADER_HEIGHT).max(ROW_HEIGHT);
+        let top = row as f32 * ROW_HEIGHT;
+        if top < self.scroll.y {
+            self.scroll.y = top;
+        } else if top + ROW_HEIGHT > self.scroll.y + body {
+            self.scroll.y = top + ROW_HEIGHT - body;
+        }
+        if column >= geometry.frozen && column < geometry.widths.len() {
+            let left = geometry.column_x(column, 0.0);
+            let right = left + geometry.widths[column];
+            if left - self.scroll.x < geometry.frozen_x {
+                self.scroll.x = left - geometry.frozen_x;
+            } else if right - self.scroll.x > self.size.width {
+                self.scroll.x = right - self.size.width;
+            }
+        }
+    }
+
+    fn scroll_by(&mut self, delta: LocalOffset, model: &dyn GridModel, geometry: &GridGeometry) {
+        let max_x = (geometry.content_width() - self.size.width).max(0.0);
+        let max_y =
+            (model.row_count() as f32 * ROW_HEIGHT - (self.size.height - HEADER_HEIGHT)).max(0.0);
+        self.scroll.x = (self.scroll.x - delta.x).clamp(0.0, max_x);
+        self.scroll.y = (self.scroll.y + delta.y).clamp(0.0, max_y);
+    }
+}
+
+/// Starts editing the current cell, as suits its kind: typing over text
+/// and numbers, toggling checkboxes and opening dropdowns.
+fn begin_edit<M: GridModel>(
+    cx: &mut Context,
+    model: impl Binding<M>,
+    state: StateHandle<DataGridState>,
+    typed: Option<char>,
+) {
+    let (row, column) = cx[state].cursor;
+    let cell = model.with(cx, |m| {
+        (row < m.row_count() && column < m.column_count())
+            .then(|| (m.column_kind(column), m.cell(row, column)))
+    });
+    let (kind, value) = match cell {
+        Some(cell) => cell,
+        None => return,
+    };
+    match kind {
+        CellKind::Text | CellKind::Number => {
+            cx[state].edit = Some(match typed {
+                Some(c) => c.to_string(),
+                None => value.to_string(),
+            });
+        }
+        CellKind::Checkbox => {
+            if typed.is_none() || typed == Some(' ') {
+                let checked = value == CellValue::Bool(true);
+                model.with_mut(cx, |m| m.set_cell(row, column, CellValue::Bool(!checked)));
+            }
+        }
+        CellKind::Dropdown(choices) => {
+            let current = choices
+                .iter()
+                .position(|c| CellValue::Text(c.clone()) == value);
+            cx[state].menu = Some(current.unwrap_or(0));
+        }
+    }
+}
+
+/// Stores what was typed into the current cell, if it suits the column.
+fn commit_edit<M: GridModel>(
+    cx: &mut Context,
+    model: impl Binding<M>,
+    state: StateHandle<DataGridState>,
+) {
+    let (row, column) = cx[state].cursor;
+    if let Some(text) = cx[state].edit.take() {
+        model.with_mut(cx, |m| {
+            if let Some(value) = CellValue::parse(&m.column_kind(column), &text) {
+                m.set_cell(row, column, value);
+            }
+        });
+    }
+}
+
+/// Draws `s` in `cell`, left aligned, or right aligned for numbers.
+fn draw_cell_text(vger: &mut Vger, s: &str, cell: LocalRect, right: bool, color: Color) {
+    let x = if right {
+        cell.max_x() - CELL_PADDING - vger.text_bounds(s, FONT_SIZE, None).width()
+    } else {
+        cell.min_x() + CELL_PADDING
+    };
+    vger.save();
+    vger.scissor(cell);
+    vger.translate([x, cell.max_y() - 4.0]);
+    vger.text(s, FONT_SIZE, color, None);
+    vger.restore();
+}
+
+fn draw_cell(
+    vger: &mut Vger,
+    kind: &CellKind,
+    value: &CellValue,
+    cell: LocalRect,
+    edit: Option<&str>,
+) {
+    if let Some(text) = edit {
+        let paint = vger.color_paint(BLACK);
+        vger.fill_rect(cell, 0.0, paint);
+        draw_cell_text(vger, text, cell, false, TEXT_COLOR);
+        let width = vger
+            .text_bounds(&format!("{}|", text), FONT_SIZE, None)
+            .width()
+            - vger.text_bounds("|", FONT_SIZE, None).width();
+        let caret = LocalRect::new(
+            [
+                cell.min_x() + CELL_PADDING + width + 1.0,
+                cell.min_y() + 4.0,
+            ]
+            .into(),
+            [1.5, cell.height() - 8.0].into(),
+        );
+        let paint = vger.color_paint(AZURE_HIGHLIGHT);
+        vger.fill_rect(caret, 0.0, paint);
+        return;
+    }
+    match (kind, value) {
+        (CellKind::Checkbox, CellValue::Bool(checked)) => {
+            let size = 12.0;
+            let center = cell.center();
+            let min = LocalPoint::new(center.x - size / 2.0, center.y - size / 2.0);
+            let paint = vger.color_paint(MEDIUM_GRAY);
+            vger.stroke_rect(min, min + LocalOffset::new(size, size), 2.0, 1.0, paint);
+            if *checked {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                let inner = LocalRect::new(
+                    min + LocalOffset::new(3.0, 3.0),
+                    [size - 6.0, size - 6.0].into(),
+                );
+                vger.fill_rect(inner, 1.0, paint);
+            }
+        }
+        (CellKind::Dropdown(_), value) => {
+            let mut text_cell = cell;
+            text_cell.size.width -= 14.0;
+            draw_cell_text(vger, &value.to_string(), text_cell, false, TEXT_COLOR);
+            // A small arrow pointing down.
+            let (x, y) = (cell.max_x() - 12.0, cell.center().y);
+            let paint = vger.color_paint(MEDIUM_GRAY);
+            vger.move_to([x, y + 2.0].into());
+            vger.quad_to([x + 3.0, y + 2.0].into(), [x + 6.0, y + 2.0].into());
+            vger.quad_to([x + 4.5, y].into(), [x + 3.0, y - 2.0].into());
+            vger.quad_to([x + 1.5, y].into(), [x, y + 2.0].into());
+            vger.fill(paint);
+        }
+        (_, value) => {
+            let right = matches!(value, CellValue::Number(_));
+            draw_cell_text(vger, &value.to_string(), cell, right, TEXT_COLOR);
+        }
+    }
+}
+
+fn grid_canvas<M: GridModel + 'static>(
+    model: impl Binding<M>,
+    state: StateHandle<DataGridState>,
+    opts: DataGridOptions,
+    has_focus: bool,
+) -> impl View {
+    canvas(move |cx, rect, vger| {
+        cx[state].size = rect.size;
+        let s = cx[state].clone();
+        let m = model.get(cx);
+        let geometry = GridGeometry::new(m, opts.frozen_columns);
+        let (rows, columns) = (m.row_count(), m.column_count());
+
+        let body_top = rect.height() - HEADER_HEIGHT;
+        let first = (s.scroll.y / ROW_HEIGHT) as usize;
+        let last = (((s.scroll.y + body_top) / ROW_HEIGHT).ceil() as usize).min(rows);
+        let row_rect = |row: usize| {
+            let top = body_top + s.scroll.y - row as f32 * ROW_HEIGHT;
+            (top - ROW_HEIGHT, top)
+        };
+        let cell_rect = |row: usize, column: usize| {
+            let (bottom, top) = row_rect(row);
+            let x = geometry.column_x(column, s.scroll.x);
+            LocalRect::new(
+                [x, bottom].into(),
+                [geometry.widths[column], top - bottom].into(),
+            )
+        };
+        let grid_paint = vger.color_paint(GROOVES);
+        let selected_paint = vger.color_paint(AZURE_HIGHLIGHT_BACKGROUND);
+        let header_paint = vger.color_paint(CONTROL_BACKGROUND);
+
+        // Scrolled columns first, then frozen ones, each clipped to its
+        // part of the body.
+        let scrolled = LocalRect::new(
+            [geometry.frozen_x, 0.0].into(),
+            [
+                (rect.width() - geometry.frozen_x).max(0.0),
+                body_top.max(0.0),
+            ]
+            .into(),
+        );
+        let frozen = LocalRect::new(
+            [0.0, 0.0].into(),
+            [geometry.frozen_x, body_top.max(0.0)].into(),
+        );
+        for (area, range) in [
+            (scrolled, geometry.frozen..columns),
+            (frozen, 0..geometry.frozen),
+        ] {
+            vger.save();
+            vger.scissor(area);
+            for column in range {
+                let kind = m.column_kind(column);
+                for row in first..last {
+                    let cell = cell_rect(row, column);
+                    if s.is_selected(row, column) {
+                        vger.fill_rect(cell, 0.0, selected_paint);
+                    }
+                    let editing = s.edit.as_deref().filter(|_| s.cursor == (row, column));
+                    draw_cell(vger, &kind, &m.cell(row, column), cell, editing);
+                    vger.fill_rect(
+                        LocalRect::new(
+                            [cell.max_x() - 1.0, cell.min_y()].into(),
+                            [1.0, cell.height()].into(),
+                        ),
+                        0.0,
+                        grid_paint,
+                    );
+                    vger.fill_rect(
+                        LocalRect::new(cell.origin, [cell.width(), 1.0].into()),
+                        0.0,
+                        grid_paint,
+                    );
+                }
+            }
+            vger.restore();
+        }
+
+        // Row numbers.
+        vger.save();
+        vger.scissor(LocalRect::new(
+            [0.0, 0.0].into(),
+            [ROW_NUMBER_WIDTH, body_top.max(0.0)].into(),
+        ));
+        for row in first..last {
+            let (bottom, top) = row_rect(row);
+            let cell = LocalRect::new(
+                [0.0, bottom].into(),
+                [ROW_NUMBER_WIDTH, top - bottom].into(),
+            );
+            vger.fill_rect(cell, 0.0, header_paint);
+            draw_cell_text(vger, &(row + 1).to_string(), cell, true, MEDIUM_GRAY);
+        }
+        vger.restore();
+
+        // The header row, which doesn't scroll down.
+        let header = LocalRect::new([0.0, body_top].into(), [rect.width(), HEADER_HEIGHT].into());
+        vger.fill_rect(header, 0.0, header_paint);
+        for (area, range) in [
+            (
+                LocalRect::new(
+                    [geometry.frozen_x, body_top].into(),
+                    [(rect.width() - geometry.frozen_x).max(0.0), HEADER_HEIGHT].into(),
+                ),
+                geometry.frozen..columns,
+            ),
+            (
+                LocalRect::new(
+                    [0.0, body_top].into(),
+                    [geometry.frozen_x, HEADER_HEIGHT].into(),
+                ),
+                0..geometry.frozen,
+            ),
+        ] {
+            vger.save();
+            vger.scissor(area);
+            for column in range {
+                let x = geometry.column_x(column, s.scroll.x);
+                let cell = LocalRect::new(
+                    [x, body_top].into(),
+                    [geometry.widths[column], HEADER_HEIGHT].into(),
+                );
+                draw_cell_text(vger, &m.column_title(column), cell, false, TEXT_COLOR);
+                vger.fill_rect(
+                    LocalRect::new(
+                        [cell.max_x() - 1.0, cell.min_y()].into(),
+                        [1.0, cell.height()].into(),
+                    ),
+                    0.0,
+                    grid_paint,
+                );
+            }
+            vger.restore();
+        }
+        vger.fill_rect(
+            LocalRect::new([0.0, body_top].into(), [rect.width(), 1.0].into()),
+            0.0,
+            grid_paint,
+        );
+        vger.fill_rect(
+            LocalRect::new(
+                [geometry.frozen_x - 1.0, 0.0].into(),
+                [1.0, rect.height()].into(),
+            ),
+            0.0,
+            grid_paint,
+        );
+
+        // The current cell, and its open dropdown.
+        let (row, column) = s.cursor;
+        if row < rows && column < columns && (first..last).contains(&row) {
+            let cell = cell_rect(row, column);
+            if has_focus {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                vger.save();
+                vger.scissor(if column < geometry.frozen {
+                    frozen
+                } else {
+                    scrolled
+                });
+                vger.stroke_rect(cell.min(), cell.max(), 0.0, 2.0, paint);
+                vger.restore();
+            }
+            if let (Some(highlighted), CellKind::Dropdown(choices)) =
+                (s.menu, m.column_kind(column))
+            {
+                let menu = LocalRect::new(
+                    [
+                        cell.min_x(),
+                        cell.min_y() - choices.len() as f32 * MENU_ITEM_HEIGHT,
+                    ]
+                    .into(),
+                    [
+                        cell.width().max(120.0),
+                        choices.len() as f32 * MENU_ITEM_HEIGHT,
+                    ]
+                    .into(),
+                );
+                let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+                vger.fill_rect(menu, 4.0, paint);
+                for (i, choice) in choices.iter().enumerate() {
+                    let top = cell.min_y() - i as f32 * MENU_ITEM_HEIGHT;
+                    let item = LocalRect::new(
+                        [menu.min_x(), top - MENU_ITEM_HEIGHT].into(),
+                        [menu.width(), MENU_ITEM_HEIGHT].into(),
+                    );
+                    if i == highlighted {
+                        let paint = vger.color_paint(AZURE_HIGHLIGHT_DARK);
+                        vger.fill_rect(item, 4.0, paint);
+                    }
+                    draw_cell_text(vger, choice, item, false, TEXT_COLOR);
+                }
+            }
+        }
+    })
+    .clip()
+    .drag_p(move |cx, p, gesture, _| {
+        let s = cx[state].clone();
+        let (geometry, rows, kind) = model.with(cx, |m| {
+            let geometry = GridGeometry::new(m, opts.frozen_columns);
+            let kind = (s.cursor.1 < m.column_count()).then(|| m.column_kind(s.cursor.1));
+            (geometry, m.row_count(), kind)
+        });
+        let from_top = s.size.height - p.y;
+
+        // A tap on an open dropdown chooses from it.
+        if let (Some(_), Some(CellKind::Dropdown(choices))) = (s.menu, &kind) {
+            if gesture == GestureState::Ended {
+                let cell_bottom = HEADER_HEIGHT + (s.cursor.0 + 1) as f32 * ROW_HEIGHT - s.scroll.y;
+                let item = ((from_top - cell_bottom) / MENU_ITEM_HEIGHT).floor();
+                if (0.0..choices.len() as f32).contains(&item) {
+                    let value = CellValue::Text(choices[item as usize].clone());
+                    let (row, column) = s.cursor;
+                    model.with_mut(cx, |m| m.set_cell(row, column, value));
+                }
+                cx[state].menu = None;
+            }
+            return;
+        }
+
+        if from_top < HEADER_HEIGHT || p.x < ROW_NUMBER_WIDTH {
+            return;
+        }
+        let row = ((from_top - HEADER_HEIGHT + s.scroll.y) / ROW_HEIGHT) as usize;
+        let column = geometry.column_at(p.x, s.scroll.x);
+        let (row, column) = match column {
+            Some(column) if row < rows => (row, column),
+            _ => return,
+        };
+        match gesture {
+            GestureState::Began => {
+                if s.edit.is_some() {
+                    commit_edit(cx, model, state);
+                }
+                let select = cx.key_mods.shift;
+                cx[state].move_to((row, column), select, &geometry);
+            }
+            GestureState::Changed => cx[state].move_to((row, column), true, &geometry),
+            GestureState::Ended => {
+                // Checkboxes toggle with a tap, other cells edit with two.
+                let tapped = s.anchor == (row, column) && s.cursor == (row, column);
+                let is_checkbox = model.with(cx, |m| m.column_kind(column) == CellKind::Checkbox);
+                if tapped && (is_checkbox || cx.tap_count() >= 2) {
+                    begin_edit(cx, model, state, None);
+                }
+            }
+        }
+    })
+    .scroll(move |cx, delta| {
+        let geometry = model.with(cx, |m| GridGeometry::new(m, opts.frozen_columns));
+        let rows = model.with(cx, |m| m.row_count());
+        let s = &mut cx[state];
+        let max_x = (geometry.content_width() - s.size.width).max(0.0);
+        let max_y = (rows as f32 * ROW_HEIGHT - (s.size.height - HEADER_HEIGHT)).max(0.0);
+        s.scroll.x = (s.scroll.x - delta.x).clamp(0.0, max_x);
+        s.scroll.y = (s.scroll.y + delta.y).clamp(0.0, max_y);
+    })
+}
+
+/// Handles a key while editing a cell or choosing from a dropdown.
+/// Returns false for keys which move around the grid instead.
+fn edit_key<M: GridModel>(
+    cx: &mut Context,
+    model: impl Binding<M>,
+    state: StateHandle<DataGridState>,
+    k: &Key,
+    geometry: &GridGeometry,
+) -> bool {
+    if let Some(highlighted) = cx[state].menu {
+        let (row, column) = cx[state].cursor;
+        let choices = match model.with(cx, |m| m.column_kind(column)) {
+            CellKind::Dropdown(choices) => choices,
+            _ => Vec::new(),
+        };
+        match k {
+            Key::ArrowUp => cx[state].menu = Some(highlighted.saturating_sub(1)),
+            Key::ArrowDown => {
+                cx[state].menu = Some((highlighted + 1).min(choices.len().saturating_sub(1)))
+            }
+            Key::Enter | Key::Space => {
+                if let Some(choice) = choices.get(highlighted) {
+                    let value = CellValue::Text(choice.clone());
+                    model.with_mut(cx, |m| m.set_cell(row, column, value));
+                }
+                cx[state].menu = None;
+            }
+            Key::Escape => cx[state].menu = None,
+            _ => (),
+        }
+        return true;
+    }
+
+    let text = match &mut cx[state].edit {
+        Some(text) => text,
+        None => return false,
+    };
+    match k {
+        Key::Character(c) => text.push(*c),
+        Key::Space => text.push(' '),
+        Key::Backspace => {
+            text.pop();
+        }
+        Key::Escape => cx[state].edit = None,
+        Key::Enter | Key::Tab => {
+            commit_edit(cx, model, state);
+            let by = if matches!(k, Key::Enter) {
+                (1, 0)
+            } else {
+                (0, 1)
+            };
+            let m = model.get(cx);
+            let mut s = cx[state].clone();
+            s.step(by, false, m, geometry);
+            cx[state] = s;
+        }
+        _ => (),
+    }
+    true
+}
+
+/// Shows the cells of a `GridModel` in rows and columns, like a
+/// spreadsheet. The header row and the row numbers stay in place while the
+/// cells scroll, as do the first columns, with
+/// `DataGridMods::frozen_columns`.
+///
+/// Double-tapping a cell, pressing Enter, or typing edits it, as suits its
+/// `CellKind`: text and numbers are typed in, checkboxes toggle, and
+/// dropdowns open a list of choices. Dragging or Shift with the arrow keys
+/// selects a range. With the shortcut key, C copies the range as
+/// tab-separated values, V pastes them, D fills the top row down through
+/// the range, and A selects everything.
+///
+/// ```no_run
+/// # use rui::*;
+/// let table = GridTable::new()
+///     .column("Task", CellKind::Text)
+///     .column("Hours", CellKind::Number)
+///     .column("Done", CellKind::Checkbox);
+/// state(move || table.clone(), |table, _| data_grid(table).frozen_columns(1));
+/// ```
+pub fn data_grid<M: GridModel + 'static>(model: impl Binding<M>) -> impl DataGridMods {
+    modview(move |opts: DataGridOptions, _| {
+        focus(move |has_focus| {
+            state(DataGridState::default, move |state, _| {
+                grid_canvas(model, state, opts, has_focus).key(move |cx, k| {
+                    if !has_focus {
+                        return;
+                    }
+                    let geometry = model.with(cx, |m| GridGeometry::new(m, opts.frozen_columns));
+                    if edit_key(cx, model, state, &k, &geometry) {
+                        return;
+                    }
+                    let select = cx.key_mods.shift;
+                    let hotkey = match &k {
+                        Key::Character(c) if cx.key_mods.shortcut() => HotKey::from_char(*c),
+                        _ => None,
+                    };
+                    let page =
+                        ((cx[state].size.height - HEADER_HEIGHT) / ROW_HEIGHT).max(1.0) as isize;
+                    let mut s = cx[state].clone();
+                    let step = |cx: &mut Context, s: &mut DataGridState, by| {
+                        s.step(by, select, model.get(cx), &geometry);
+                    };
+                    match (hotkey, &k) {
+                        (Some(HotKey::KeyC), _) => {
+                            let tsv = model.with(cx, |m| to_tsv(m, s.selection()));
+                            cx.clipboard().set_text(&tsv);
+                        }
+                        (Some(HotKey::KeyV), _) => {
+                            if let Some(tsv) = cx.clipboard().text() {
+                                let at = (s.selection().0.start, s.selection().1.start);
+                                let (rows, columns) =
+                                    model.with_mut(cx, |m| paste_tsv(m, at, &tsv));
+                                s.anchor = (rows.start, columns.start);
+                                s.cursor = (rows.end - 1, columns.end - 1);
+                            }
+                        }
+                        (Some(HotKey::KeyD), _) => {
+                            model.with_mut(cx, |m| fill_down(m, s.selection()));
+                        }
+                        (Some(HotKey::KeyA), _) => {
+                            let (rows, columns) =
+                                model.with(cx, |m| (m.row_count(), m.column_count()));
+                            s.anchor = (0, 0);
+                            s.cursor = (rows.saturating_sub(1), columns.saturating_sub(1));
+                        }
+                        (Some(_), _) => (),
+                        (None, Key::ArrowUp) => step(cx, &mut s, (-1, 0)),
+                        (None, Key::ArrowDown) => step(cx, &mut s, (1, 0)),
+                        (None, Key::ArrowLeft) => step(cx, &mut s, (0, -1)),
+                        (None, Key::ArrowRight) => step(cx, &mut s, (0, 1)),
+                        (None, Key::PageUp) => step(cx, &mut s, (-page, 0)),
+                        (None, Key::PageDown) => step(cx, &mut s, (page, 0)),
+                        (None, Key::Tab) => step(cx, &mut s, (0, 1)),
+                        (None, Key::Enter) => {
+                            cx[state] = s;
+                            begin_edit(cx, model, state, None);
+                            return;
+                        }
+                        (None, Key::Space) => {
+                            cx[state] = s;
+                            begin_edit(cx, model, state, Some(' '));
+                            return;
+                        }
+                        (None, Key::Character(c)) => {
+                            cx[state] = s;
+                            begin_edit(cx, model, state, Some(*c));
+                            return;
+                        }
+                        (None, Key::Delete) | (None, Key::Backspace) => {
+                            let (rows, columns) = s.selection();
+                            model.with_mut(cx, |m| {
+                                for column in columns {
+                                    let empty = CellValue::empty(&m.column_kind(column));
+                                    for row in rows.clone() {
+                                        if row < m.row_count() {
+                                            m.set_cell(row, column, empty.clone());
+                                        }
+                                    }
+                                }
+                            });
+                        }
+                        _ => (),
+                    }
+                    cx[state] = s;
+                })
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn table() -> GridTable {
+        GridTable::new()
+            .column("Name", CellKind::Text)
+            .column("Hours", CellKind::Number)
+            .column("Done", CellKind::Checkbox)
+            .column(
+                "Size",
+                CellKind::Dropdown(vec!["Small".into(), "Large".into()]),
+            )
+            .row(vec![
+                CellValue::Text("a".into()),
+                CellValue::Number(1.5),
+                CellValue::Bool(true),
+                CellValue::Text("Small".into()),
+            ])
+            .row(vec![CellValue::Text("b".into())])
+            .row(vec![])
+    }
+
+    #[test]
+    fn test_parse_cell() {
+        assert_eq!(
+            CellValue::parse(&CellKind::Number, " 2.5 "),
+            Some(CellValue::Number(2.5))
+        );
+        assert_eq!(CellValue::parse(&CellKind::Number, "two"), None);
+        assert_eq!(
+            CellValue::parse(&CellKind::Checkbox, "Yes"),
+            Some(CellValue::Bool(true))
+        );
+        assert_eq!(CellValue::parse(&CellKind::Checkbox, "maybe"), None);
+        let sizes = CellKind::Dropdown(vec!["Small".into(), "Large".into()]);
+        assert_eq!(
+            CellValue::parse(&sizes, "large"),
+            Some(CellValue::Text("Large".into()))
+        );
+        assert_eq!(CellValue::parse(&sizes, "Huge"), None);
+    }
+
+    #[test]
+    fn test_grid_tsv() {
+        let mut t = table();
+        assert_eq!(t.cell(1, 2), CellValue::Bool(false));
+        assert_eq!(to_tsv(&t, (0..2, 0..3)), "a\t1.5\tTRUE\nb\t0\tFALSE");
+
+        let pasted = paste_tsv(&mut t, (1, 1), "4\tx\tLarge\textra\nnope\tno\n\n");
+        assert_eq!(pasted, (1..3, 1..4));
+        assert_eq!(t.cell(1, 1), CellValue::Number(4.0));
+        assert_eq!(t.cell(1, 2), CellValue::Bool(true));
+        assert_eq!(t.cell(1, 3), CellValue::Text("Large".into()));
+        // Not a number, so left alone.
+        assert_eq!(t.cell(2, 1), CellValue::Number(0.0));
+        assert_eq!(t.cell(2, 2), CellValue::Bool(false));
+    }
+
+    #[test]
+    fn test_grid_fill_down() {
+        let mut t = table();
+        fill_down(&mut t, (0..3, 1..3));
+        for row in 1..3 {
+            assert_eq!(t.cell(row, 1), CellValue::Number(1.5));
+            assert_eq!(t.cell(row, 2), CellValue::Bool(true));
+        }
+        assert_eq!(t.cell(2, 0), CellValue::Text(String::new()));
+    }
+
+    #[test]
+    fn test_grid_geometry() {
+        let t = table();
+        let geometry = GridGeometry::new(&t, 1);
+        assert_eq!(geometry.frozen_x, ROW_NUMBER_WIDTH + 100.0);
+        assert_eq!(geometry.column_at(ROW_NUMBER_WIDTH + 10.0, 50.0), Some(0));
+        assert_eq!(geometry.column_at(ROW_NUMBER_WIDTH + 110.0, 50.0), Some(1));
+        // Scrolled under the frozen column.
+        assert_eq!(geometry.column_at(ROW_NUMBER_WIDTH + 90.0, 50.0), Some(0));
+
+        let mut s = DataGridState {
+            size: [300.0, HEADER_HEIGHT + 2.0 * ROW_HEIGHT].into(),
+            ..Default::default()
+        };
+        s.step((2, 3), false, &t, &geometry);
+        assert_eq!(s.cursor, (2, 3));
+        assert_eq!(s.scroll.y, ROW_HEIGHT);
+        assert_eq!(s.scroll.x, ROW_NUMBER_WIDTH + 400.0 - 300.0);
+        s.step((-5, 0), true, &t, &geometry);
+        assert_eq!(s.selection(), (0..3, 3..4));
+    }
+
+    #[test]
+    fn test_grid_events() {
+        let mut cx = Context::new();
+        let ui = state(table, |t, _| data_grid(t));
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [400.0, 200.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        // Drawing would record the size.
+        let grid = StateHandle::<DataGridState>::new(cx.view_id(&vec![0, 0, 0, 0]));
+        cx[grid].size = [400.0, 200.0].into();
+
+        // From the first cell to the second row and column. Rows go down
+        // from the top, under the header.
+        let cell = |row: f32, column: f32| -> LocalPoint {
+            [
+                ROW_NUMBER_WIDTH + 50.0 + column * 100.0,
+                200.0 - HEADER_HEIGHT - (row + 0.5) * ROW_HEIGHT,
+            ]
+            .into()
+        };
+        let events = [
+            Event::TouchBegin {
+                id: 0,
+                position: cell(0.0, 0.0),
+            },
+            Event::TouchMove {
+                id: 0,
+                position: cell(1.0, 1.0),
+                delta: cell(1.0, 1.0) - cell(0.0, 0.0),
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: cell(1.0, 1.0),
+            },
+            Event::Key {
+                key: Key::ArrowDown,
+                mods: KeyboardModifiers::default(),
+                repeat: false,
+            },
+        ];
+        let mut actions = vec![];
+        for (i, event) in events.iter().enumerate() {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+            if i == 2 {
+                assert_eq!(cx[grid].selection(), (0..2, 0..2));
+            }
+        }
+        assert_eq!(cx[grid].cursor, (2, 1));
+        assert_eq!(cx[grid].selection(), (2..3, 1..2));
+    }
+}
@@ -0,0 +1,653 @@
+use crate::*;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// How `diff_view` lays out the two texts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffMode {
+    /// The old text on the left and the new on the right, with changed
+    /// lines opposite each other.
+    SideBySide,
+    /// One column, with removed lines above the ones added in their place.
+    Unified,
+}
+
+/// A row of a `diff_view`. Lines are numbered from 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffRow {
+    Same {
+        old: usize,
+        new: usize,
+    },
+    Removed(usize),
+    Added(usize),
+    /// A removed line beside the one added in its place.
+    Changed {
+        old: usize,
+        new: usize,
+    },
+    /// Unchanged lines hidden until tapped. `block` indexes
+    /// `LineDiff::blocks`.
+    Collapsed {
+        block: usize,
+        lines: usize,
+    },
+}
+
+impl DiffRow {
+    fn changed(&self) -> bool {
+        matches!(
+            self,
+            DiffRow::Removed(_) | DiffRow::Added(_) | DiffRow::Changed { .. }
+        )
+    }
+}
+
+/// Two texts compared line by line.
+struct LineDiff {
+    old: Vec<String>,
+    new: Vec<String>,
+    blocks: Vec<DiffBlock>,
+}
+
+impl LineDiff {
+    fn new(old: &str, new: &str) -> Self {
+        let old: Vec<String> = old.lines().map(String::from).collect();
+        let new: Vec<String> = new.lines().map(String::from).collect();
+        let blocks = diff_blocks(&old, &new);
+        Self { old, new, blocks }
+    }
+
+    /// The rows to show, and the row each hunk starts at. Runs of
+    /// unchanged lines keep `context` lines next to the changes, and the
+    /// rest collapse unless their block is in `expanded`.
+    fn rows(
+        &self,
+        mode: DiffMode,
+        context: usize,
+        expanded: &[usize],
+    ) -> (Vec<DiffRow>, Vec<usize>) {
+        let mut rows = vec![];
+        let mut hunks = vec![];
+        let last = self.blocks.len().saturating_sub(1);
+        for (b, block) in self.blocks.iter().enumerate() {
+            if block.changed {
+                hunks.push(rows.len());
+                match mode {
+                    DiffMode::SideBySide => {
+                        let (old, new) = (block.old.len(), block.new.len());
+                        for k in 0..old.max(new) {
+                            rows.push(if k < old && k < new {
+                                DiffRow::Changed {
+                                    old: block.old.start + k,
+                                    new: block.new.start + k,
+                                }
+                            } else if k < old {
+                                DiffRow::Removed(block.old.start + k)
+                            } else {
+                                DiffRow::Added(block.new.start + k)
+                            });
+                        }
+                    }
+                    DiffMode::Unified => {
+                        rows.extend(block.old.clone().map(DiffRow::Removed));
+                        rows.extend(block.new.clone().map(DiffRow::Added));
+                    }
+                }
+                continue;
+            }
+
+            let same = |k: usize| DiffRow::Same {
+                old: block.old.start + k,
+                new: block.new.start + k,
+            };
+            let n = block.old.len();
+            let lead = if b == 0 { 0 } else { context };
+            let trail = if b == last { 0 } else { context };
+            // Hiding a single line would save nothing.
+            if expanded.contains(&b) || n <= lead + trail + 1 {
+                rows.extend((0..n).map(same));
+            } else {
+                rows.extend((0..lead).map(same));
+                rows.push(DiffRow::Collapsed {
+                    block: b,
+                    lines: n - lead - trail,
+                });
+                rows.extend((n - trail..n).map(same));
+            }
+        }
+        (rows, hunks)
+    }
+
+    /// The line opposite `line` in the same changed block, if there is
+    /// one. `old` says which text `line` is in.
+    fn partner(&self, line: usize, old: bool) -> Option<usize> {
+        self.blocks.iter().filter(|b| b.changed).find_map(|b| {
+            let (this, other) = if old {
+                (&b.old, &b.new)
+            } else {
+                (&b.new, &b.old)
+            };
+            let k = line
+                .checked_sub(this.start)
+                .filter(|_| this.contains(&line))?;
+            Some(other.start + k).filter(|i| other.contains(i))
+        })
+    }
+
+    /// Characters of a changed line which differ from the line opposite
+    /// it, as ranges of char indices.
+    fn char_changes(&self, line: usize, old: bool) -> Vec<Range<usize>> {
+        match self.partner(line, old) {
+            Some(other) => {
+                let (o, n) = if old { (line, other) } else { (other, line) };
+                let (removed, added) = intra_line(&self.old[o], &self.new[n]);
+                if old {
+                    removed
+                } else {
+                    added
+                }
+            }
+            None => vec![],
+        }
+    }
+}
+
+/// The characters removed from `old` and added in `new`, as ranges of
+/// char indices.
+fn intra_line(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let a: Vec<char> = old.chars().collect();
+    let b: Vec<char> = new.chars().collect();
+    let mut removed = vec![];
+    let mut added = vec![];
+    for block in diff_blocks(&a, &b).into_iter().filter(|b| b.changed) {
+        if !block.old.is_empty() {
+            removed.push(block.old);
+        }
+        if !block.new.is_empty() {
+            added.push(block.new);
+        }
+    }
+    (removed, added)
+}
+
+/// Options for `diff_view`. Set them with `DiffViewMods`.
+#[derive(Clone, Copy)]
+pub struct DiffViewOptions {
+    mode: DiffMode,
+    context: usize,
+}
+
+impl Default for DiffViewOptions {
+    fn default() -> Self {
+        Self {
+            mode: DiffMode::SideBySide,
+            context: 3,
+        }
+    }
+}
+
+pub trait DiffViewMods: View + Sized {
+    /// Shows the texts side by side, which it does by default, or in one
+    /// column.
+    fn mode(self, mode: DiffMode) -> Self;
+
+    /// How many unchanged lines to show around each change. The rest
+    /// collapse. It's 3 by default.
+    fn context_lines(self, lines: usize) -> Self;
+}
+
+impl<F> DiffViewMods for ModView<DiffViewOptions, F>
+where
+    ModView<DiffViewOptions, F>: View,
+{
+    fn mode(self, mode: DiffMode) -> Self {
+        let mut opts = self.value;
+        opts.mode = mode;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn context_lines(self, lines: usize) -> Self {
+        let mut opts = self.value;
+        opts.context = lines;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+/// View-model for `diff_view`.
+#[derive(Default)]
+struct DiffViewState {
+    /// The last diff, and the texts it was found for, so they aren't
+    /// compared again every frame.
+    diff: Option<(Rc<str>, Rc<str>, Rc<LineDiff>)>,
+    /// Runs of unchanged lines which were tapped to show them.
+    expanded: Vec<usize>,
+    /// Which hunk Next and Previous are on.
+    current: Option<usize>,
+    hunks: usize,
+    /// How far the rows are scrolled up, in points.
+    scroll: f32,
+    /// Of the canvas, and of all the rows, as last drawn.
+    height: f32,
+    content_height: f32,
+}
+
+impl DiffViewState {
+    /// Which hunk we're on, and how many there are.
+    fn status(&self) -> String {
+        match (self.current, self.hunks) {
+            (_, 0) => "No changes".into(),
+            (None, 1) => "1 change".into(),
+            (None, n) => format!("{} changes", n),
+            (Some(c), n) => format!("Change {} of {}", c % n + 1, n),
+        }
+    }
+}
+
+/// The diff of `old` and `new`, found again only when they change.
+fn line_diff(
+    cx: &mut Context,
+    state: StateHandle<DiffViewState>,
+    old: &Rc<str>,
+    new: &Rc<str>,
+) -> Rc<LineDiff> {
+    if let Some((o, n, diff)) = &cx[state].diff {
+        if o == old && n == new {
+            return diff.clone();
+        }
+    }
+    let diff = Rc::new(LineDiff::new(old, new));
+    cx[state].diff = Some((old.clone(), new.clone(), diff.clone()));
+    cx[state].expanded.clear();
+    cx[state].current = None;
+    diff
+}
+
+const ROW_HEIGHT: f32 = 20.0;
+const FONT_SIZE: u32 = 14;
+
+/// Moves to the next or previous hunk, and scrolls to it.
+fn step(
+    cx: &mut Context,
+    state: StateHandle<DiffViewState>,
+    old: &Rc<str>,
+    new: &Rc<str>,
+    opts: DiffViewOptions,
+    by: isize,
+) {
+    let diff = line_diff(cx, state, old, new);
+    let (rows, hunks) = diff.rows(opts.mode, opts.context, &cx[state].expanded);
+    if hunks.is_empty() {
+        return;
+    }
+    let count = hunks.len() as isize;
+    let current = match cx[state].current {
+        Some(c) => (c as isize + by).rem_euclid(count),
+        None if by > 0 => 0,
+        None => count - 1,
+    };
+    let current = current as usize;
+    cx[state].current = Some(current);
+
+    // Show a couple of lines above it.
+    let max = (rows.len() as f32 * ROW_HEIGHT - cx[state].height).max(0.0);
+    cx[state].scroll = (hunks[current].saturating_sub(2) as f32 * ROW_HEIGHT).min(max);
+}
+
+/// Draws `line` in `cell`, starting `indent` in. A changed line has
+/// `colors` for its background and for the chars in `changes`.
+fn draw_line(
+    vger: &mut Vger,
+    line: &str,
+    changes: &[Range<usize>],
+    colors: Option<(Color, Color)>,
+    cell: LocalRect,
+    indent: f32,
+) {
+    if let Some((background, _)) = colors {
+        let paint = vger.color_paint(background);
+        vger.fill_rect(cell, 0.0, paint);
+    }
+    vger.save();
+    vger.scissor(cell);
+    vger.translate([cell.min_x() + indent, cell.max_y() - 2.0]);
+    if let Some((_, highlight)) = colors {
+        let rects = vger.glyph_positions(line, FONT_SIZE, None);
+        let paint = vger.color_paint(highlight);
+        for range in changes {
+            for r in rects.get(range.clone()).unwrap_or(&[]) {
+                vger.fill_rect(*r, 0.0, paint);
+            }
+        }
+    }
+    vger.text(line, FONT_SIZE, TEXT_COLOR, None);
+    vger.restore();
+}
+
+/// Colors for a changed line, and for the changes within it.
+fn change_colors(old: bool) -> (Color, Color) {
+    let color = if old { RED_HIGHLIGHT } else { GREEN_HIGHLIGHT };
+    (color.alpha(0.15), color.alpha(0.4))
+}
+
+/// Draws a side-by-side row, with the old line in `left` and the new in
+/// `right`.
+fn draw_side_by_side(
+    vger: &mut Vger,
+    diff: &LineDiff,
+    row: DiffRow,
+    left: LocalRect,
+    right: LocalRect,
+    number_width: f32,
+) {
+    let (old, new) = match row {
+        DiffRow::Same { old, new } => (Some((old, false)), Some((new, false))),
+        DiffRow::Removed(old) => (Some((old, true)), None),
+        DiffRow::Added(new) => (None, Some((new, true))),
+        DiffRow::Changed { old, new } => (Some((old, true)), Some((new, true))),
+        DiffRow::Collapsed { .. } => return,
+    };
+    for (side, cell, is_old) in [(old, left, true), (new, right, false)] {
+        match side {
+            Some((line, changed)) => {
+                let text = if is_old {
+                    &diff.old[line]
+                } else {
+                    &diff.new[line]
+                };
+                let changes = diff.char_changes(line, is_old);
+                let colors = Some(change_colors(is_old)).filter(|_| changed);
+                draw_line(vger, text, &changes, colors, cell, number_width);
+                draw_line_number(
+                    vger,
+                    line + 1,
+                    cell.min_x() + number_width - 8.0,
+                    cell.max_y(),
+                );
+            }
+            // Nothing opposite a line which was only added or removed.
+            None => {
+                let paint = vger.color_paint(GROOVES);
+                vger.fill_rect(cell, 0.0, paint);
+            }
+        }
+    }
+}
+
+/// Draws a unified row in `cell`, numbering it in both texts.
+fn draw_unified(
+    vger: &mut Vger,
+    diff: &LineDiff,
+    row: DiffRow,
+    cell: LocalRect,
+    number_width: f32,
+) {
+    let indent = number_width * 2.0;
+    let (old, new) = match row {
+        DiffRow::Same { old, new } => {
+            draw_line(vger, &diff.new[new], &[], None, cell, indent);
+            (Some(old), Some(new))
+        }
+        DiffRow::Removed(old) => {
+            let changes = diff.char_changes(old, true);
+            let colors = Some(change_colors(true));
+            draw_line(vger, &diff.old[old], &changes, colors, cell, indent);
+            (Some(old), None)
+        }
+        DiffRow::Added(new) => {
+            let changes = diff.char_changes(new, false);
+            let colors = Some(change_colors(false));
+            draw_line(vger, &diff.new[new], &changes, colors, cell, indent);
+            (None, Some(new))
+        }
+        // Only side-by-side rows pair lines up.
+        DiffRow::Changed { .. } | DiffRow::Collapsed { .. } => return,
+    };
+    if let Some(old) = old {
+        draw_line_number(
+            vger,
+            old + 1,
+            cell.min_x() + number_width - 8.0,
+            cell.max_y(),
+        );
+    }
+    if let Some(new) = new {
+        draw_line_number(vger, new + 1, cell.min_x() + indent - 8.0, cell.max_y());
+    }
+}
+
+fn diff_canvas(
+    old: Rc<str>,
+    new: Rc<str>,
+    state: StateHandle<DiffViewState>,
+    opts: DiffViewOptions,
+) -> impl View {
+    let (tap_old, tap_new) = (old.clone(), new.clone());
+    canvas(move |cx, rect, vger| {
+        let diff = line_diff(cx, state, &old, &new);
+        let (rows, hunks) = diff.rows(opts.mode, opts.context, &cx[state].expanded);
+
+        let content_height = rows.len() as f32 * ROW_HEIGHT;
+        let scroll = cx[state]
+            .scroll
+            .min(content_height - rect.height())
+            .max(0.0);
+        if cx[state].scroll != scroll {
+            cx[state].scroll = scroll;
+        }
+        cx[state].height = rect.height();
+        cx[state].content_height = content_height;
+        cx[state].hunks = hunks.len();
+
+        // Rows of the hunk we're on, for a marker down the left.
+        let current = cx[state]
+            .current
+            .and_then(|c| hunks.get(c % hunks.len().max(1)))
+            .map(|&start| {
+                let len = rows[start..].iter().take_while(|r| r.changed()).count();
+                start..start + len
+            })
+            .unwrap_or(0..0);
+
+        let number_width = line_number_width(diff.old.len().max(diff.new.len()));
+        let first = (scroll / ROW_HEIGHT) as usize;
+        let last = (((scroll + rect.height()) / ROW_HEIGHT).ceil() as usize).min(rows.len());
+        for (i, row) in rows.iter().enumerate().take(last).skip(first) {
+            let top = rect.height() + scroll - i as f32 * ROW_HEIGHT;
+            let cell = |x: f32, width: f32| {
+                LocalRect::new([x, top - ROW_HEIGHT].into(), [width, ROW_HEIGHT].into())
+                    .intersection(&rect)
+                    .unwrap_or_else(LocalRect::zero)
+            };
+
+            if let DiffRow::Collapsed { lines, .. } = row {
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.fill_rect(cell(0.0, rect.width()), 0.0, paint);
+                vger.save();
+                vger.translate([number_width, top - 2.0]);
+                let label = format!("{} unchanged lines", lines);
+                vger.text(&label, FONT_SIZE, MEDIUM_GRAY, None);
+                vger.restore();
+                continue;
+            }
+
+            match opts.mode {
+                DiffMode::SideBySide => {
+                    let half = rect.width() / 2.0;
+                    let (left, right) = (cell(0.0, half), cell(half, half));
+                    draw_side_by_side(vger, &diff, *row, left, right, number_width);
+                }
+                DiffMode::Unified => {
+                    draw_unified(vger, &diff, *row, cell(0.0, rect.width()), number_width);
+                }
+            }
+
+            if current.contains(&i) {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                vger.fill_rect(cell(0.0, CHANGE_MARKER_WIDTH), 0.0, paint);
+            }
+        }
+    })
+    .clip()
+    .tap_p(move |cx, p, _| {
+        // Rows go down from the top of the canvas.
+        let row = (cx[state].height + cx[state].scroll - p.y) / ROW_HEIGHT;
+        let diff = line_diff(cx, state, &tap_old, &tap_new);
+        let (rows, _) = diff.rows(opts.mode, opts.context, &cx[state].expanded);
+        if let Some(DiffRow::Collapsed { block, .. }) = rows.get(row as usize) {
+            cx[state].expanded.push(*block);
+        }
+    })
+    .scroll(move |cx, delta| {
+        let max = (cx[state].content_height - cx[state].height).max(0.0);
+        cx[state].scroll = (cx[state].scroll + delta.y).clamp(0.0, max);
+    })
+}
+
+/// Compares two texts line by line, side by side or in one column, as
+/// set with `DiffViewMods::mode`. Removed and added lines are colored,
+/// and where a line was changed, so are the characters which differ.
+/// Long runs of unchanged lines collapse, and tapping one shows it.
+///
+/// Next and Previous above it step through the changes, as do F7 and
+/// Shift+F7, or Alt with the up and down arrows, when it has focus.
+///
+/// ```no_run
+/// # use rui::*;
+/// diff_view("a\nb\nc", "a\nB\nc").mode(DiffMode::Unified);
+/// ```
+pub fn diff_view(old: impl Into<String>, new: impl Into<String>) -> impl DiffViewMods {
+    let (old, new): (String, String) = (old.into(), new.into());
+    let (old, new): (Rc<str>, Rc<str>) = (old.into(), new.into());
+    modview(move |opts: DiffViewOptions, _| {
+        let (old, new) = (old.clone(), new.clone());
+        focus(move |has_focus| {
+            let (old, new) = (old.clone(), new.clone());
+            state(DiffViewState::default, move |state, cx| {
+                let (prev_old, prev_new) = (old.clone(), new.clone());
+                let (next_old, next_new) = (old.clone(), new.clone());
+                let (key_old, key_new) = (old.clone(), new.clone());
+                vstack((
+                    hstack((
+                        text(&cx[state].status()).padding(Auto),
+                        spacer(),
+                        button("Previous", move |cx| {
+                            step(cx, state, &prev_old, &prev_new, opts, -1)
+                        }),
+                        button("Next", move |cx| {
+                            step(cx, state, &next_old, &next_new, opts, 1)
+                        }),
+                    )),
+                    diff_canvas(old.clone(), new.clone(), state, opts).key(move |cx, k| {
+                        if !has_focus {
+                            return;
+                        }
+                        let by = match k {
+                            Key::F7 if cx.key_mods.shift => -1,
+                            Key::F7 => 1,
+                            Key::ArrowUp if cx.key_mods.alt => -1,
+                            Key::ArrowDown if cx.key_mods.alt => 1,
+                            _ => return,
+                        };
+                        step(cx, state, &key_old, &key_new, opts, by);
+                    }),
+                ))
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_diff_rows() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\nold\n9";
+        let new = "1\n2\n3\n4\n5\n6\n7\n8\nnew\nadded\n9";
+        let diff = LineDiff::new(old, new);
+
+        let (rows, hunks) = diff.rows(DiffMode::SideBySide, 2, &[]);
+        assert_eq!(
+            rows,
+            vec![
+                DiffRow::Collapsed { block: 0, lines: 6 },
+                DiffRow::Same { old: 6, new: 6 },
+                DiffRow::Same { old: 7, new: 7 },
+                DiffRow::Changed { old: 8, new: 8 },
+                DiffRow::Added(9),
+                DiffRow::Same { old: 9, new: 10 },
+            ]
+        );
+        assert_eq!(hunks, vec![3]);
+
+        let (rows, hunks) = diff.rows(DiffMode::Unified, 2, &[0]);
+        assert_eq!(rows.len(), 12);
+        assert_eq!(
+            rows[8..11],
+            [DiffRow::Removed(8), DiffRow::Added(8), DiffRow::Added(9)]
+        );
+        assert_eq!(hunks, vec![8]);
+
+        assert_eq!(diff.partner(8, true), Some(8));
+        assert_eq!(diff.partner(9, false), None);
+        assert_eq!(diff.partner(2, true), None);
+    }
+
+    #[test]
+    fn test_intra_line() {
+        assert_eq!(
+            intra_line("let x = 1;", "let y = 1;"),
+            (vec![4..5], vec![4..5])
+        );
+        assert_eq!(intra_line("ab", "abc"), (vec![], vec![2..3]));
+    }
+
+    #[test]
+    fn test_diff_events() {
+        let mut cx = Context::new();
+        let ui = diff_view(
+            "1\n2\n3\n4\n5\n6\n7\n8\nold\n9",
+            "1\n2\n3\n4\n5\n6\n7\n8\nnew\n9",
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [400.0, 400.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        // Drawing would record the height. The rows are below the buttons.
+        let diff = StateHandle::<DiffViewState>::new(cx.view_id(&vec![0, 0, 0]));
+        let offset = cx.get_layout(&vec![0, 0, 0, 0, 1]).offset;
+        let canvas = cx.get_layout(&vec![0, 0, 0, 0, 1, 0, 0, 0, 0]);
+        cx[diff].height = canvas.rect.height();
+
+        // The first row, which collapses the first five lines.
+        let position = LocalPoint::new(100.0, cx[diff].height - ROW_HEIGHT / 2.0) + offset;
+        let events = [
+            Event::TouchBegin { id: 0, position },
+            Event::TouchEnd { id: 0, position },
+            Event::Key {
+                key: Key::F7,
+                mods: KeyboardModifiers::default(),
+                repeat: false,
+            },
+        ];
+        let mut actions = vec![];
+        for event in &events {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+        assert_eq!(cx[diff].expanded, vec![0]);
+        assert_eq!(cx[diff].current, Some(0));
+    }
+}
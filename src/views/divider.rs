@@ -0,0 +1,38 @@
+use crate::*;
+
+/// Thickness of a divider, in points.
+const DIVIDER_THICKNESS: f32 = 1.0;
+
+#[derive(Clone)]
+pub struct Divider {}
+
+impl View for Divider {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let rect = args.cx.pixel_snap(rect, args.vger.current_transform());
+        let paint = args
+            .vger
+            .color_paint(args.cx.theme().colors.text.alpha(0.2));
+        args.vger.fill_rect(rect, 0.0, paint);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let sz = LocalSize::new(args.sz.width, DIVIDER_THICKNESS);
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+}
+
+impl private::Sealed for Divider {}
+
+/// A hairline across a vertical stack, snapped to whole pixels unless
+/// `pixel_snap(false)` is set.
+pub fn divider() -> Divider {
+    Divider {}
+}
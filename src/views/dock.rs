@@ -0,0 +1,1013 @@
+use crate::*;
+use std::any::Any;
+
+const TAB_BAR_HEIGHT: f32 = 24.0;
+const TAB_WIDTH: f32 = 110.0;
+const TAB_FONT_SIZE: u32 = 13;
+
+/// Half the width of the grab area around a splitter.
+const SPLITTER_GRAB: f32 = 4.0;
+
+/// Dropping a tab this close to an edge of the center area docks it there.
+const DROP_EDGE: f32 = 60.0;
+
+const MIN_DOCK_SIZE: f32 = 80.0;
+const DRAG_THRESHOLD: f32 = 4.0;
+const RESIZE_CORNER: f32 = 12.0;
+const DEFAULT_FLOATING_SIZE: [f32; 2] = [320.0, 240.0];
+
+/// One of the fixed areas of a `dock_space`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DockArea {
+    Left,
+    Right,
+    Bottom,
+    Center,
+}
+
+/// Panels tabbed together.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockTabs {
+    pub panels: Vec<String>,
+    /// Index of the panel being shown.
+    pub active: usize,
+}
+
+impl DockTabs {
+    pub fn active_panel(&self) -> Option<&str> {
+        self.panels.get(self.active).map(|p| p.as_str())
+    }
+
+    fn remove(&mut self, panel: &str) -> bool {
+        if let Some(i) = self.panels.iter().position(|p| p == panel) {
+            self.panels.remove(i);
+            if self.active > i || self.active >= self.panels.len() {
+                self.active = self.active.saturating_sub(1);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn add(&mut self, panel: &str) {
+        self.panels.push(panel.into());
+        self.active = self.panels.len() - 1;
+    }
+}
+
+/// Panels floating above the docks.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FloatingPanel {
+    pub tabs: DockTabs,
+    pub origin: [f32; 2],
+    pub size: [f32; 2],
+}
+
+impl FloatingPanel {
+    fn rect(&self) -> LocalRect {
+        LocalRect::new(self.origin.into(), self.size.into())
+    }
+}
+
+/// Arrangement of panels in a `dock_space`. Store it to restore
+/// the arrangement later; with the `serde` feature it can be serialized.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockLayout {
+    pub left: DockTabs,
+    pub right: DockTabs,
+    pub bottom: DockTabs,
+    pub center: DockTabs,
+    pub left_width: f32,
+    pub right_width: f32,
+    pub bottom_height: f32,
+    pub floating: Vec<FloatingPanel>,
+}
+
+impl Default for DockLayout {
+    fn default() -> Self {
+        Self {
+            left: Default::default(),
+            right: Default::default(),
+            bottom: Default::default(),
+            center: Default::default(),
+            left_width: 240.0,
+            right_width: 240.0,
+            bottom_height: 200.0,
+            floating: vec![],
+        }
+    }
+}
+
+impl DockLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves a panel into a dock area, adding it if it isn't in the layout.
+    pub fn dock(&mut self, panel: &str, area: DockArea) {
+        self.remove(panel);
+        self.area_mut(area).add(panel);
+    }
+
+    /// Moves a panel into its own floating panel.
+    pub fn float(&mut self, panel: &str, origin: [f32; 2], size: [f32; 2]) {
+        self.remove(panel);
+        let mut tabs = DockTabs::default();
+        tabs.add(panel);
+        self.floating.push(FloatingPanel { tabs, origin, size });
+    }
+
+    /// Takes a panel out of the layout.
+    pub fn remove(&mut self, panel: &str) {
+        for area in [
+            DockArea::Left,
+            DockArea::Right,
+            DockArea::Bottom,
+            DockArea::Center,
+        ] {
+            self.area_mut(area).remove(panel);
+        }
+        for floating in &mut self.floating {
+            floating.tabs.remove(panel);
+        }
+        self.floating.retain(|f| !f.tabs.panels.is_empty());
+    }
+
+    /// Where a panel is.
+    pub fn area_of(&self, panel: &str) -> Option<DockArea> {
+        [
+            DockArea::Left,
+            DockArea::Right,
+            DockArea::Bottom,
+            DockArea::Center,
+        ]
+        .into_iter()
+        .find(|area| self.area(*area).panels.iter().any(|p| p == panel))
+    }
+
+    pub fn area(&self, area: DockArea) -> &DockTabs {
+        match area {
+            DockArea::Left => &self.left,
+            DockArea::Right => &self.right,
+            DockArea::Bottom => &self.bottom,
+            DockArea::Center => &self.center,
+        }
+    }
+
+    pub fn area_mut(&mut self, area: DockArea) -> &mut DockTabs {
+        match area {
+            DockArea::Left => &mut self.left,
+            DockArea::Right => &mut self.right,
+            DockArea::Bottom => &mut self.bottom,
+            DockArea::Center => &mut self.center,
+        }
+    }
+
+    fn tabs(&self, slot: Slot) -> &DockTabs {
+        match slot {
+            Slot::Dock(area) => self.area(area),
+            Slot::Floating(i) => &self.floating[i].tabs,
+        }
+    }
+
+    fn tabs_mut(&mut self, slot: Slot) -> &mut DockTabs {
+        match slot {
+            Slot::Dock(area) => self.area_mut(area),
+            Slot::Floating(i) => &mut self.floating[i].tabs,
+        }
+    }
+
+    /// Every panel in the layout.
+    fn panels(&self) -> impl Iterator<Item = &String> {
+        self.left
+            .panels
+            .iter()
+            .chain(self.right.panels.iter())
+            .chain(self.bottom.panels.iter())
+            .chain(self.center.panels.iter())
+            .chain(self.floating.iter().flat_map(|f| f.tabs.panels.iter()))
+    }
+
+    fn drop_panel(&mut self, panel: &str, target: DropTarget) {
+        match target {
+            DropTarget::Dock(area) => self.dock(panel, area),
+            DropTarget::Floating(i) => {
+                // Indices shift if removing the panel empties a floating panel,
+                // so find the target again by one of its other panels.
+                let neighbor = self.floating[i]
+                    .tabs
+                    .panels
+                    .iter()
+                    .find(|p| *p != panel)
+                    .cloned();
+                self.remove(panel);
+                if let Some(f) = self
+                    .floating
+                    .iter_mut()
+                    .find(|f| Some(&f.tabs.panels[0]) == neighbor.as_ref())
+                {
+                    f.tabs.add(panel);
+                }
+            }
+            DropTarget::NewFloating(origin) => self.float(panel, origin, DEFAULT_FLOATING_SIZE),
+        }
+    }
+
+    /// Visible regions, bottom to top.
+    fn regions(&self, sz: LocalSize) -> Vec<(Slot, LocalRect)> {
+        let show = |tabs: &DockTabs| !tabs.panels.is_empty();
+        let bottom = if show(&self.bottom) {
+            self.bottom_height.min(sz.height - TAB_BAR_HEIGHT).max(0.0)
+        } else {
+            0.0
+        };
+        let left = if show(&self.left) {
+            self.left_width
+        } else {
+            0.0
+        };
+        let right = if show(&self.right) {
+            self.right_width.min(sz.width - left).max(0.0)
+        } else {
+            0.0
+        };
+        let height = sz.height - bottom;
+
+        let mut regions = vec![];
+        if show(&self.bottom) {
+            regions.push((
+                Slot::Dock(DockArea::Bottom),
+                LocalRect::new([0.0, 0.0].into(), [sz.width, bottom].into()),
+            ));
+        }
+        if show(&self.left) {
+            regions.push((
+                Slot::Dock(DockArea::Left),
+                LocalRect::new([0.0, bottom].into(), [left, height].into()),
+            ));
+        }
+        if show(&self.right) {
+            regions.push((
+                Slot::Dock(DockArea::Right),
+                LocalRect::new([sz.width - right, bottom].into(), [right, height].into()),
+            ));
+        }
+        regions.push((
+            Slot::Dock(DockArea::Center),
+            LocalRect::new(
+                [left, bottom].into(),
+                [(sz.width - left - right).max(0.0), height].into(),
+            ),
+        ));
+        for (i, floating) in self.floating.iter().enumerate() {
+            regions.push((Slot::Floating(i), floating.rect()));
+        }
+        regions
+    }
+
+    /// Splitters between docks and the center, with the line they're drawn on.
+    fn splitters(&self, sz: LocalSize) -> Vec<(DockArea, LocalRect)> {
+        let mut splitters = vec![];
+        for (slot, rect) in self.regions(sz) {
+            let line = match slot {
+                Slot::Dock(DockArea::Left) => LocalRect::new(
+                    [rect.max_x(), rect.min_y()].into(),
+                    [0.0, rect.height()].into(),
+                ),
+                Slot::Dock(DockArea::Right) => LocalRect::new(
+                    [rect.min_x(), rect.min_y()].into(),
+                    [0.0, rect.height()].into(),
+                ),
+                Slot::Dock(DockArea::Bottom) => {
+                    LocalRect::new([0.0, rect.max_y()].into(), [rect.width(), 0.0].into())
+                }
+                _ => continue,
+            };
+            splitters.push((slot.area().unwrap(), line));
+        }
+        splitters
+    }
+}
+
+/// A set of tabbed panels within a `dock_space`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    Dock(DockArea),
+    Floating(usize),
+}
+
+impl Slot {
+    fn area(&self) -> Option<DockArea> {
+        match self {
+            Slot::Dock(area) => Some(*area),
+            Slot::Floating(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DropTarget {
+    Dock(DockArea),
+    Floating(usize),
+    NewFloating([f32; 2]),
+}
+
+fn tab_bar(rect: LocalRect) -> LocalRect {
+    LocalRect::new(
+        [rect.min_x(), rect.max_y() - TAB_BAR_HEIGHT].into(),
+        [rect.width(), TAB_BAR_HEIGHT].into(),
+    )
+}
+
+fn content(rect: LocalRect) -> LocalRect {
+    LocalRect::new(
+        rect.origin,
+        [rect.width(), (rect.height() - TAB_BAR_HEIGHT).max(0.0)].into(),
+    )
+}
+
+fn tab_rect(rect: LocalRect, index: usize) -> LocalRect {
+    let bar = tab_bar(rect);
+    LocalRect::new(
+        [bar.min_x() + index as f32 * TAB_WIDTH, bar.min_y()].into(),
+        [TAB_WIDTH, TAB_BAR_HEIGHT].into(),
+    )
+}
+
+fn grab_rect(line: LocalRect) -> LocalRect {
+    line.inflate(SPLITTER_GRAB, SPLITTER_GRAB)
+}
+
+#[derive(Clone, Debug)]
+enum Gesture {
+    Tab {
+        panel: String,
+        from: Slot,
+        index: usize,
+        moved: bool,
+    },
+    Splitter(DockArea),
+    MoveFloating {
+        index: usize,
+        origin: [f32; 2],
+    },
+    ResizeFloating {
+        index: usize,
+        origin: [f32; 2],
+        size: [f32; 2],
+    },
+}
+
+#[derive(Clone, Debug, Default)]
+struct DockState {
+    size: LocalSize,
+    gesture: Option<Gesture>,
+    start: LocalPoint,
+    position: LocalPoint,
+}
+
+/// Struct for `dock_space`.
+pub struct DockSpace<B, F> {
+    layout: B,
+    func: F,
+    key: Option<String>,
+}
+
+impl<B, V, F> DockSpace<B, F>
+where
+    B: Binding<DockLayout>,
+    V: View,
+    F: Fn(&str) -> V + 'static,
+{
+    /// Saves and restores the layout with the workspace, under a stable `key`.
+    /// See `Context::save_workspace`.
+    pub fn workspace_key(self, key: &str) -> Self {
+        Self {
+            key: Some(key.into()),
+            ..self
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<DockState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &DockState::default);
+        StateHandle::new(id)
+    }
+
+    /// The panels being shown, bottom to top, with the area they're shown in.
+    fn visible(&self, cx: &Context, sz: LocalSize) -> Vec<(String, LocalRect)> {
+        let layout = self.layout.get(cx);
+        layout
+            .regions(sz)
+            .into_iter()
+            .filter_map(|(slot, rect)| {
+                layout
+                    .tabs(slot)
+                    .active_panel()
+                    .map(|p| (p.to_string(), content(rect)))
+            })
+            .collect()
+    }
+
+    fn drop_target(&self, cx: &Context, sz: LocalSize, pt: LocalPoint, from: Slot) -> DropTarget {
+        let layout = self.layout.get(cx);
+        for (slot, rect) in layout.regions(sz).into_iter().rev() {
+            if !rect.contains(pt) {
+                continue;
+            }
+            return match slot {
+                Slot::Floating(_) if slot == from => continue,
+                Slot::Floating(i) => DropTarget::Floating(i),
+                Slot::Dock(DockArea::Center) => {
+                    if tab_bar(rect).contains(pt) {
+                        DropTarget::Dock(DockArea::Center)
+                    } else if pt.x < rect.min_x() + DROP_EDGE {
+                        DropTarget::Dock(DockArea::Left)
+                    } else if pt.x > rect.max_x() - DROP_EDGE {
+                        DropTarget::Dock(DockArea::Right)
+                    } else if pt.y < rect.min_y() + DROP_EDGE {
+                        DropTarget::Dock(DockArea::Bottom)
+                    } else {
+                        DropTarget::NewFloating([
+                            pt.x - TAB_WIDTH / 2.0,
+                            pt.y + TAB_BAR_HEIGHT / 2.0 - DEFAULT_FLOATING_SIZE[1],
+                        ])
+                    }
+                }
+                Slot::Dock(area) => DropTarget::Dock(area),
+            };
+        }
+        DropTarget::Dock(DockArea::Center)
+    }
+
+    /// Where a drop would put a panel, for highlighting.
+    fn drop_rect(&self, cx: &Context, sz: LocalSize, target: DropTarget) -> LocalRect {
+        let layout = self.layout.get(cx);
+        let regions = layout.regions(sz);
+        let find = |slot: Slot| regions.iter().find(|(s, _)| *s == slot).map(|(_, r)| *r);
+        let center = find(Slot::Dock(DockArea::Center)).unwrap_or_default();
+        match target {
+            DropTarget::Dock(area) => find(Slot::Dock(area)).unwrap_or_else(|| match area {
+                DockArea::Left => LocalRect::new(
+                    center.origin,
+                    [layout.left_width.min(center.width()), center.height()].into(),
+                ),
+                DockArea::Right => {
+                    let width = layout.right_width.min(center.width());
+                    LocalRect::new(
+                        [center.max_x() - width, center.min_y()].into(),
+                        [width, center.height()].into(),
+                    )
+                }
+                DockArea::Bottom => LocalRect::new(
+                    [0.0, 0.0].into(),
+                    [sz.width, layout.bottom_height.min(sz.height)].into(),
+                ),
+                DockArea::Center => center,
+            }),
+            DropTarget::Floating(i) => layout.floating[i].rect(),
+            DropTarget::NewFloating(origin) => {
+                LocalRect::new(origin.into(), DEFAULT_FLOATING_SIZE.into())
+            }
+        }
+    }
+
+    /// Starts a gesture if `pt` is on the dock chrome rather than a panel.
+    fn begin_gesture(&self, cx: &Context, sz: LocalSize, pt: LocalPoint) -> Option<Gesture> {
+        let layout = self.layout.get(cx);
+        let regions = layout.regions(sz);
+
+        for (slot, rect) in regions.iter().rev() {
+            if let Slot::Floating(index) = slot {
+                if !rect.contains(pt) {
+                    continue;
+                }
+                let corner = LocalRect::new(
+                    [rect.max_x() - RESIZE_CORNER, rect.min_y()].into(),
+                    [RESIZE_CORNER, RESIZE_CORNER].into(),
+                );
+                let floating = &layout.floating[*index];
+                if corner.contains(pt) {
+                    return Some(Gesture::ResizeFloating {
+                        index: *index,
+                        origin: floating.origin,
+                        size: floating.size,
+                    });
+                }
+                if tab_bar(*rect).contains(pt) {
+                    return Some(self.tab_gesture(layout, *slot, *rect, pt).unwrap_or(
+                        Gesture::MoveFloating {
+                            index: *index,
+                            origin: floating.origin,
+                        },
+                    ));
+                }
+                // On the panel's content.
+                return None;
+            }
+        }
+
+        for (area, line) in layout.splitters(sz) {
+            if grab_rect(line).contains(pt) {
+                return Some(Gesture::Splitter(area));
+            }
+        }
+
+        for (slot, rect) in regions {
+            if tab_bar(rect).contains(pt) {
+                return self.tab_gesture(layout, slot, rect, pt);
+            }
+        }
+
+        None
+    }
+
+    fn tab_gesture(
+        &self,
+        layout: &DockLayout,
+        slot: Slot,
+        rect: LocalRect,
+        pt: LocalPoint,
+    ) -> Option<Gesture> {
+        let tabs = layout.tabs(slot);
+        (0..tabs.panels.len())
+            .find(|i| tab_rect(rect, *i).contains(pt))
+            .map(|index| Gesture::Tab {
+                panel: tabs.panels[index].clone(),
+                from: slot,
+                index,
+                moved: false,
+            })
+    }
+
+    fn update_gesture(&self, cx: &mut Context, s: StateHandle<DockState>) {
+        let state = cx[s].clone();
+        let delta = state.position - state.start;
+        let sz = state.size;
+        match state.gesture {
+            Some(Gesture::Tab { moved: false, .. }) if delta.length() > DRAG_THRESHOLD => {
+                if let Some(Gesture::Tab { moved, .. }) = &mut cx[s].gesture {
+                    *moved = true;
+                }
+            }
+            Some(Gesture::Splitter(area)) => {
+                let pt = state.position;
+                let layout = self.layout.get_mut(cx);
+                let max_width = (sz.width - MIN_DOCK_SIZE).max(MIN_DOCK_SIZE);
+                let max_height = (sz.height - TAB_BAR_HEIGHT).max(MIN_DOCK_SIZE);
+                match area {
+                    DockArea::Left => layout.left_width = pt.x.clamp(MIN_DOCK_SIZE, max_width),
+                    DockArea::Right => {
+                        layout.right_width = (sz.width - pt.x).clamp(MIN_DOCK_SIZE, max_width)
+                    }
+                    DockArea::Bottom => {
+                        layout.bottom_height = pt.y.clamp(MIN_DOCK_SIZE, max_height)
+                    }
+                    DockArea::Center => (),
+                }
+            }
+            Some(Gesture::MoveFloating { index, origin }) => {
+                self.layout.get_mut(cx).floating[index].origin =
+                    [origin[0] + delta.x, origin[1] + delta.y];
+            }
+            Some(Gesture::ResizeFloating {
+                index,
+                origin,
+                size,
+            }) => {
+                // The corner is bottom-right, so the top edge stays put.
+                let width = (size[0] + delta.x).max(MIN_DOCK_SIZE);
+                let height = (size[1] - delta.y).max(MIN_DOCK_SIZE);
+                let floating = &mut self.layout.get_mut(cx).floating[index];
+                floating.size = [width, height];
+                floating.origin = [origin[0], origin[1] + size[1] - height];
+            }
+            _ => (),
+        }
+    }
+
+    fn end_gesture(&self, cx: &mut Context, s: StateHandle<DockState>) {
+        let state = cx[s].clone();
+        cx[s].gesture = None;
+        if let Some(Gesture::Tab {
+            panel,
+            from,
+            index,
+            moved,
+        }) = state.gesture
+        {
+            if moved {
+                let target = self.drop_target(cx, state.size, state.position, from);
+                self.layout.get_mut(cx).drop_panel(&panel, target);
+            } else {
+                self.layout.get_mut(cx).tabs_mut(from).active = index;
+            }
+        }
+    }
+
+    fn draw_tabs(&self, vger: &mut Vger, tabs: &DockTabs, rect: LocalRect) {
+        let bar = tab_bar(rect);
+        let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        vger.fill_rect(bar, 0.0, paint);
+
+        for (i, panel) in tabs.panels.iter().enumerate() {
+            let tab = tab_rect(rect, i);
+            if i == tabs.active {
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.fill_rect(tab, 0.0, paint);
+            }
+            vger.save();
+            vger.scissor(tab);
+            let origin = vger.text_bounds(panel, TAB_FONT_SIZE, None).origin;
+            vger.translate([tab.min_x() + 8.0 - origin.x, tab.min_y() + 7.0 - origin.y]);
+            let color = if i == tabs.active {
+                TEXT_COLOR
+            } else {
+                MEDIUM_GRAY
+            };
+            vger.text(panel, TAB_FONT_SIZE, color, None);
+            vger.restore();
+        }
+    }
+}
+
+impl<B, V, F> View for DockSpace<B, F>
+where
+    B: Binding<DockLayout>,
+    V: View,
+    F: Fn(&str) -> V + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        let s = self.state(path, cx);
+        let sz = cx[s].size;
+
+        match event {
+            Event::TouchBegin { id, position } => {
+                if cx.touches[*id].is_default() {
+                    if let Some(gesture) = self.begin_gesture(cx, sz, *position) {
+                        cx.touches[*id] = vid;
+                        cx[s].gesture = Some(gesture);
+                        cx[s].start = *position;
+                        cx[s].position = *position;
+                        return;
+                    }
+                }
+
+                // Only the topmost panel under the touch gets it.
+                let visible = self.visible(cx, sz);
+                if let Some((panel, _)) = visible.iter().rev().find(|(_, r)| r.contains(*position))
+                {
+                    path.push(hh(panel));
+                    let offset = cx.get_layout(path).offset;
+                    ((self.func)(panel)).process(&event.offset(-offset), path, cx, actions);
+                    path.pop();
+                }
+                return;
+            }
+            Event::TouchMove { id, position, .. } => {
+                if cx.touches[*id] == vid {
+                    cx[s].position = *position;
+                    self.update_gesture(cx, s);
+                    return;
+                }
+            }
+            Event::TouchEnd { id, position } => {
+                if cx.touches[*id] == vid {
+                    cx.touches[*id] = ViewId::default();
+                    cx[s].position = *position;
+                    self.end_gesture(cx, s);
+                    return;
+                }
+            }
+            _ => (),
+        }
+
+        for (panel, _) in self.visible(cx, sz).iter().rev() {
+            path.push(hh(panel));
+            let offset = cx.get_layout(path).offset;
+            ((self.func)(panel)).process(&event.offset(-offset), path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let s = self.state(path, args.cx);
+        let state = args.cx[s].clone();
+        let layout = self.layout.get(args.cx).clone();
+
+        for (slot, rect) in layout.regions(state.size) {
+            let tabs = layout.tabs(slot);
+
+            let paint = args.vger.color_paint(BLACK);
+            args.vger.fill_rect(rect, 0.0, paint);
+
+            if let Some(panel) = tabs.active_panel() {
+                let area = content(rect);
+                path.push(hh(&panel));
+                let offset = args.cx.get_layout(path).offset;
+                args.vger.save();
+                args.vger.scissor(area);
+                args.vger.translate(offset);
+                ((self.func)(panel)).draw(path, args);
+                args.vger.restore();
+                path.pop();
+            }
+
+            self.draw_tabs(args.vger, tabs, rect);
+
+            if let Slot::Floating(_) = slot {
+                let paint = args.vger.color_paint(MEDIUM_GRAY);
+                args.vger
+                    .stroke_rect(rect.min(), rect.max(), 0.0, 1.0, paint);
+            }
+        }
+
+        for (_, line) in layout.splitters(state.size) {
+            let paint = args.vger.color_paint(GROOVES);
+            args.vger.fill_rect(line.inflate(0.5, 0.5), 0.0, paint);
+        }
+
+        if let Some(Gesture::Tab {
+            panel,
+            from,
+            moved: true,
+            ..
+        }) = &state.gesture
+        {
+            let target = self.drop_target(args.cx, state.size, state.position, *from);
+            let rect = self.drop_rect(args.cx, state.size, target);
+            let paint = args.vger.color_paint(AZURE_HIGHLIGHT.alpha(0.2));
+            args.vger.fill_rect(rect, 0.0, paint);
+            let paint = args.vger.color_paint(AZURE_HIGHLIGHT);
+            args.vger
+                .stroke_rect(rect.min(), rect.max(), 0.0, 2.0, paint);
+
+            // The dragged tab follows the pointer.
+            let region = layout
+                .regions(state.size)
+                .into_iter()
+                .find(|(slot, _)| slot == from)
+                .map(|(_, rect)| rect)
+                .unwrap_or_default();
+            let index = layout.tabs(*from).panels.iter().position(|p| p == panel);
+            let tab = tab_rect(region, index.unwrap_or(0));
+            let ghost = DockTabs {
+                panels: vec![panel.clone()],
+                active: 0,
+            };
+            args.vger.save();
+            args.vger.translate(state.position - state.start);
+            self.draw_tabs(args.vger, &ghost, tab);
+            args.vger.restore();
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+        args.cx[s].size = args.sz;
+
+        if let Some(key) = &self.key {
+            args.cx.sync_workspace_dock(key, self.layout);
+        }
+
+        for (panel, area) in self.visible(args.cx, args.sz) {
+            path.push(hh(&panel));
+            let child_size = ((self.func)(&panel)).layout(path, &mut args.size(area.size));
+
+            // Align to the top left of the area.
+            let offset = LocalOffset::new(area.min_x(), area.max_y() - child_size.height);
+            args.cx.set_layout_offset(path, offset);
+            path.pop();
+        }
+
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let s = self.state(path, cx);
+        let sz = cx[s].size;
+        for (panel, _) in self.visible(cx, sz) {
+            path.push(hh(&panel));
+            let offset = cx.get_layout(path).offset;
+            let xf = xform.pre_translate(offset);
+            ((self.func)(&panel)).dirty(path, xf, cx);
+            path.pop();
+        }
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let s = self.state(path, cx);
+        let sz = cx[s].size;
+
+        if self.begin_gesture(cx, sz, pt).is_some() {
+            return Some(cx.view_id(path));
+        }
+
+        let visible = self.visible(cx, sz);
+        if let Some((panel, _)) = visible.iter().rev().find(|(_, r)| r.contains(pt)) {
+            path.push(hh(panel));
+            let offset = cx.get_layout(path).offset;
+            let hit = ((self.func)(panel)).hittest(path, pt - offset, cx);
+            path.pop();
+            return hit;
+        }
+
+        None
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        let panels: Vec<String> = self.layout.get(cx).panels().cloned().collect();
+        for panel in panels {
+            path.push(hh(&panel));
+            ((self.func)(&panel)).commands(path, cx, cmds);
+            path.pop();
+        }
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+
+        // Keep state for panels in background tabs too.
+        let panels: Vec<String> = self.layout.get(cx).panels().cloned().collect();
+        for panel in panels {
+            path.push(hh(&panel));
+            map.push(cx.view_id(path));
+            ((self.func)(&panel)).gc(path, cx, map);
+            path.pop();
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        let s = self.state(path, cx);
+        let sz = cx[s].size;
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Group);
+
+        let children: Vec<accesskit::NodeId> = self
+            .visible(cx, sz)
+            .iter()
+            .filter_map(|(panel, _)| {
+                path.push(hh(panel));
+                let node_id = ((self.func)(panel)).access(path, cx, nodes);
+                path.pop();
+                node_id
+            })
+            .collect();
+
+        builder.set_children(children);
+        nodes.push((
+            cx.view_id(path).access_id(),
+            builder.build(&mut cx.access_node_classes),
+        ));
+        Some(cx.view_id(path).access_id())
+    }
+}
+
+impl<B, F> private::Sealed for DockSpace<B, F> {}
+
+/// Panels in docks along the left, right and bottom edges, around a center
+/// area, plus floating panels on top. Drag tabs to rearrange panels, and drag
+/// the edges of docks to resize them. See `examples/dock.rs`.
+///
+/// `layout` holds the arrangement, and `f` makes the view for a panel id.
+pub fn dock_space<B: Binding<DockLayout>, V: View, F: Fn(&str) -> V + 'static>(
+    layout: B,
+    f: F,
+) -> DockSpace<B, F> {
+    DockSpace {
+        layout,
+        func: f,
+        key: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_dock_layout() {
+        let mut layout = DockLayout::new();
+        layout.dock("files", DockArea::Left);
+        layout.dock("outline", DockArea::Left);
+        layout.dock("editor", DockArea::Center);
+        layout.dock("console", DockArea::Bottom);
+        assert_eq!(layout.left.active_panel(), Some("outline"));
+
+        layout.dock("outline", DockArea::Right);
+        assert_eq!(layout.left.active_panel(), Some("files"));
+        assert_eq!(layout.area_of("outline"), Some(DockArea::Right));
+
+        layout.float("console", [10.0, 10.0], [100.0, 100.0]);
+        assert!(layout.bottom.panels.is_empty());
+        assert_eq!(layout.floating.len(), 1);
+
+        // Tabbing into the floating panel, then out again.
+        layout.drop_panel("files", DropTarget::Floating(0));
+        assert_eq!(layout.floating[0].tabs.panels, vec!["console", "files"]);
+        layout.drop_panel("console", DropTarget::Dock(DockArea::Bottom));
+        layout.drop_panel("files", DropTarget::Dock(DockArea::Left));
+        assert!(layout.floating.is_empty());
+
+        let regions = layout.regions([1000.0, 800.0].into());
+        let center = regions
+            .iter()
+            .find(|(slot, _)| *slot == Slot::Dock(DockArea::Center))
+            .unwrap()
+            .1;
+        assert_eq!(center.min_x(), 240.0);
+        assert_eq!(center.width(), 520.0);
+        assert_eq!(center.min_y(), 200.0);
+    }
+
+    #[test]
+    fn test_dock_drag_tab() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || {
+                let mut layout = DockLayout::new();
+                layout.dock("a", DockArea::Center);
+                layout.dock("b", DockArea::Center);
+                layout
+            },
+            |layout, _| dock_space(layout, text),
+        );
+        let sz = [1000.0, 800.0].into();
+        let mut path = vec![0];
+
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::new(LocalPoint::zero(), [90.0, 20.0].into()),
+            },
+        );
+
+        let s = StateHandle::<DockLayout>::new(cx.view_id(&path));
+        assert_eq!(cx[s].center.active_panel(), Some("b"));
+
+        // Tap the first tab.
+        let tab = [10.0, 790.0].into();
+        let mut actions = vec![];
+        ui.process(
+            &Event::TouchBegin {
+                id: 0,
+                position: tab,
+            },
+            &mut path,
+            &mut cx,
+            &mut actions,
+        );
+        ui.process(
+            &Event::TouchEnd {
+                id: 0,
+                position: tab,
+            },
+            &mut path,
+            &mut cx,
+            &mut actions,
+        );
+        assert_eq!(cx[s].center.active_panel(), Some("a"));
+
+        // Drag it to the left edge to dock it there.
+        let events = [
+            Event::TouchBegin {
+                id: 0,
+                position: tab,
+            },
+            Event::TouchMove {
+                id: 0,
+                position: [20.0, 400.0].into(),
+                delta: [10.0, -390.0].into(),
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: [20.0, 400.0].into(),
+            },
+        ];
+        for event in &events {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+        assert_eq!(cx[s].area_of("a"), Some(DockArea::Left));
+        assert_eq!(cx[s].center.panels, vec!["b"]);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::*;
+use std::any::Any;
+use std::marker::PhantomData;
+
+/// How far the pointer must move before a drag between views begins.
+const DRAG_THRESHOLD: f32 = 4.0;
+
+/// A drag from a `draggable` view, which `drop_target` views can accept.
+pub(crate) struct ActiveDrag {
+    /// Which touch is dragging.
+    pub touch: usize,
+    pub payload: Box<dyn Any>,
+    /// Size of the dragged view, for the preview.
+    size: LocalSize,
+    /// Where the view was grabbed, relative to its origin.
+    grab: LocalOffset,
+    /// Where the pointer is, relative to the root view.
+    pub position: LocalPoint,
+}
+
+impl ActiveDrag {
+    /// Where the preview follows the pointer, relative to the root view.
+    pub fn preview_rect(&self) -> LocalRect {
+        LocalRect::new(self.position - self.grab, self.size)
+    }
+}
+
+/// Draws a placeholder for the dragged view, above everything else.
+pub(crate) fn draw_drag_preview(vger: &mut Vger, rect: LocalRect, colors: &Palette) {
+    let fill = vger.color_paint(colors.accent_background.alpha(0.6));
+    vger.fill_rect(rect, 4.0, fill);
+    let stroke = vger.color_paint(colors.accent);
+    vger.stroke_rect(rect.min(), rect.max(), 4.0, 1.0, stroke);
+}
+
+/// Struct for the `draggable` modifier.
+pub struct Draggable<V, T> {
+    child: V,
+    payload: T,
+}
+
+impl<V, T> Draggable<V, T>
+where
+    V: View,
+    T: Clone + 'static,
+{
+    pub fn new(v: V, payload: T) -> Self {
+        Self { child: v, payload }
+    }
+}
+
+impl<V, T> View for Draggable<V, T>
+where
+    V: View,
+    T: Clone + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        // Controls inside get the first chance at a touch.
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+
+        let vid = cx.view_id(path);
+        match &event {
+            Event::TouchBegin { id, position } => {
+                if cx.touches[*id].is_default() && self.hittest(path, *position, cx).is_some() {
+                    cx.touches[*id] = vid;
+                    cx.starts[*id] = *position;
+                }
+            }
+            Event::TouchMove { id, position, .. } => {
+                if cx.touches[*id] == vid
+                    && cx.drag.is_none()
+                    && (*position - cx.starts[*id]).length() > DRAG_THRESHOLD
+                {
+                    let rect = cx.get_layout(path).rect;
+                    cx.drag = Some(ActiveDrag {
+                        touch: *id,
+                        payload: Box::new(self.payload.clone()),
+                        size: rect.size,
+                        grab: cx.starts[*id] - rect.origin,
+                        // Context::process fills this in.
+                        position: LocalPoint::zero(),
+                    });
+                }
+            }
+            Event::TouchEnd { id, .. } => {
+                if cx.touches[*id] == vid {
+                    cx.touches[*id] = ViewId::default();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, T> private::Sealed for Draggable<V, T> {}
+
+/// Struct for the `drop_target` modifier.
+pub struct DropTarget<V, F, T> {
+    child: V,
+    func: F,
+    phantom: PhantomData<T>,
+}
+
+impl<V, F, T, A> DropTarget<V, F, T>
+where
+    V: View,
+    F: Fn(&mut Context, T) -> A + 'static,
+    T: Clone + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self {
+            child: v,
+            func: f,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, F, T, A> View for DropTarget<V, F, T>
+where
+    V: View,
+    F: Fn(&mut Context, T) -> A + 'static,
+    T: Clone + 'static,
+    A: 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        // Whether a payload we take is being dragged over the view.
+        let vid = cx.view_id(path);
+        let targeted = StateHandle::<bool>::new(vid);
+        cx.init_state(vid, &|| false);
+
+        let dragging = cx
+            .drag
+            .as_ref()
+            .filter(|drag| drag.payload.is::<T>())
+            .map(|drag| drag.touch);
+        let inside = match event {
+            Event::TouchMove { id, position, .. } if dragging == Some(*id) => {
+                self.hittest(path, *position, cx).is_some()
+            }
+            Event::TouchEnd { id, position } if dragging == Some(*id) => {
+                if self.hittest(path, *position, cx).is_some() {
+                    let payload = cx
+                        .drag
+                        .as_ref()
+                        .and_then(|drag| drag.payload.downcast_ref::<T>())
+                        .cloned();
+                    if let Some(payload) = payload {
+                        actions.push(Box::new((self.func)(cx, payload)));
+                    }
+                }
+                false
+            }
+            _ if dragging.is_none() => false,
+            _ => cx[targeted],
+        };
+        if cx[targeted] != inside {
+            cx[targeted] = inside;
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        let vid = args.cx.view_id(path);
+        args.cx.init_state(vid, &|| false);
+        if *StateHandle::<bool>::new(vid).get(args.cx) {
+            draw_drop_outline(path, args);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F, T> private::Sealed for DropTarget<V, F, T> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_drag_between_views() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |s, _| {
+                hstack((
+                    rectangle().size([50.0, 50.0]).draggable(7),
+                    rectangle()
+                        .size([50.0, 50.0])
+                        .drop_target(move |cx, n: i32| cx[s] = n),
+                ))
+            },
+        );
+        let sz = [100.0, 50.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<i32>::new(cx.view_id(&path));
+
+        cx.process(
+            &ui,
+            &Event::TouchBegin {
+                id: 0,
+                position: [25.0, 25.0].into(),
+            },
+        );
+        cx.process(
+            &ui,
+            &Event::TouchMove {
+                id: 0,
+                position: [60.0, 25.0].into(),
+                delta: [35.0, 0.0].into(),
+            },
+        );
+        assert!(cx.drag.is_some());
+        assert_eq!(
+            cx.drag.as_ref().unwrap().preview_rect().origin,
+            [35.0, 0.0].into()
+        );
+
+        cx.process(
+            &ui,
+            &Event::TouchEnd {
+                id: 0,
+                position: [75.0, 25.0].into(),
+            },
+        );
+        assert!(cx.drag.is_none());
+        assert_eq!(*s.get(&cx), 7);
+    }
+}
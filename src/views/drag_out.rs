@@ -0,0 +1,210 @@
+use crate::*;
+use std::any::Any;
+use std::path::PathBuf;
+
+/// How far the pointer must move before a drag out of the window begins.
+const DRAG_OUT_THRESHOLD: f32 = 4.0;
+
+/// Preview shown under the cursor when none is given.
+const DEFAULT_PREVIEW: &[u8] = include_bytes!("drag_preview.png");
+
+/// Content dragged from a `drag_out` view to another application.
+#[derive(Clone, Debug)]
+pub enum DragPayload {
+    /// Files, given as absolute paths.
+    Files(Vec<PathBuf>),
+    /// Plain text.
+    Text(String),
+    /// A PNG image.
+    Image(Vec<u8>),
+}
+
+/// A drag session waiting to be started by the event loop.
+#[cfg_attr(not(feature = "drag-out"), allow(dead_code))]
+pub(crate) struct DragOutRequest {
+    pub payload: DragPayload,
+    /// Encoded preview image.
+    pub preview: Vec<u8>,
+}
+
+/// Struct for the `drag_out` modifier.
+pub struct DragOut<V, F> {
+    child: V,
+    func: F,
+    preview: Option<Vec<u8>>,
+}
+
+impl<V, F> DragOut<V, F>
+where
+    V: View,
+    F: Fn(&mut Context) -> DragPayload + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self {
+            child: v,
+            func: f,
+            preview: None,
+        }
+    }
+
+    /// Image shown under the cursor during the drag, encoded as PNG.
+    pub fn preview(self, png: impl Into<Vec<u8>>) -> Self {
+        Self {
+            child: self.child,
+            func: self.func,
+            preview: Some(png.into()),
+        }
+    }
+}
+
+impl<V, F> View for DragOut<V, F>
+where
+    V: View,
+    F: Fn(&mut Context) -> DragPayload + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        match &event {
+            Event::TouchBegin { id, position } => {
+                if cx.touches[*id].is_default() && self.hittest(path, *position, cx).is_some() {
+                    cx.touches[*id] = vid;
+                    cx.starts[*id] = *position;
+                }
+            }
+            Event::TouchMove { id, position, .. } => {
+                if cx.touches[*id] == vid
+                    && (*position - cx.starts[*id]).length() > DRAG_OUT_THRESHOLD
+                {
+                    // The OS owns the drag from here on.
+                    cx.touches[*id] = ViewId::default();
+
+                    let payload = (self.func)(cx);
+                    let preview = match (&self.preview, &payload) {
+                        (Some(preview), _) => preview.clone(),
+                        (None, DragPayload::Image(image)) => image.clone(),
+                        (None, _) => DEFAULT_PREVIEW.to_vec(),
+                    };
+                    cx.drag_out = Some(DragOutRequest { payload, preview });
+                }
+            }
+            Event::TouchEnd { id, .. } => {
+                if cx.touches[*id] == vid {
+                    cx.touches[*id] = ViewId::default();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for DragOut<V, F> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_drag_out() {
+        let mut cx = Context::new();
+
+        let ui = rectangle().drag_out(|_| DragPayload::Text("hello".into()));
+        let sz = [100.0, 100.0].into();
+        let mut path = vec![0];
+
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::new(LocalPoint::zero(), [90.0, 90.0].into()),
+            },
+        );
+
+        let mut actions = vec![];
+        let begin = Event::TouchBegin {
+            id: 0,
+            position: [50.0, 50.0].into(),
+        };
+        ui.process(&begin, &mut path, &mut cx, &mut actions);
+
+        // Small movements don't start a drag.
+        let nudge = Event::TouchMove {
+            id: 0,
+            position: [51.0, 50.0].into(),
+            delta: [1.0, 0.0].into(),
+        };
+        ui.process(&nudge, &mut path, &mut cx, &mut actions);
+        assert!(cx.drag_out.is_none());
+
+        let moved = Event::TouchMove {
+            id: 0,
+            position: [60.0, 50.0].into(),
+            delta: [9.0, 0.0].into(),
+        };
+        ui.process(&moved, &mut path, &mut cx, &mut actions);
+        let request = cx.drag_out.take().unwrap();
+        assert!(matches!(request.payload, DragPayload::Text(ref s) if s == "hello"));
+        assert_eq!(request.preview, DEFAULT_PREVIEW);
+
+        // The OS owns the drag now, so further moves are ignored.
+        ui.process(&moved, &mut path, &mut cx, &mut actions);
+        assert!(cx.drag_out.is_none());
+    }
+}
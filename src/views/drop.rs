@@ -0,0 +1,179 @@
+use crate::*;
+use std::any::Any;
+use std::path::PathBuf;
+
+/// Width of the outline around a view while files are dragged over it.
+const DROP_OUTLINE_WIDTH: f32 = 2.0;
+
+/// Outlines the view at `path` in the accent color, while something is
+/// dragged over it.
+pub(crate) fn draw_drop_outline(path: &mut IdPath, args: &mut DrawArgs) {
+    let inset = DROP_OUTLINE_WIDTH / 2.0;
+    let rect = args.cx.get_layout(path).rect.inflate(-inset, -inset);
+    let paint = args.vger.color_paint(args.cx.theme().colors.accent);
+    args.vger
+        .stroke_rect(rect.min(), rect.max(), 0.0, DROP_OUTLINE_WIDTH, paint);
+}
+
+/// Struct for the `on_drop` modifier.
+pub struct OnDrop<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F, A> OnDrop<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, Vec<PathBuf>) -> A + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        Self { child: v, func: f }
+    }
+}
+
+impl<V, F, A> View for OnDrop<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, Vec<PathBuf>) -> A + 'static,
+    A: 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        // Whether files are being dragged over the view.
+        let vid = cx.view_id(path);
+        let targeted = StateHandle::<bool>::new(vid);
+        cx.init_state(vid, &|| false);
+
+        let inside = match event {
+            Event::DropHover { position, .. } => self.hittest(path, *position, cx).is_some(),
+            Event::DropFiles { position, paths } => {
+                if self.hittest(path, *position, cx).is_some() {
+                    actions.push(Box::new((self.func)(cx, paths.clone())));
+                }
+                false
+            }
+            Event::DropHoverLeave => false,
+            _ => cx[targeted],
+        };
+        if cx[targeted] != inside {
+            cx[targeted] = inside;
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        let vid = args.cx.view_id(path);
+        args.cx.init_state(vid, &|| false);
+        if *StateHandle::<bool>::new(vid).get(args.cx) {
+            draw_drop_outline(path, args);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for OnDrop<V, F> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_on_drop() {
+        let mut cx = Context::new();
+
+        let ui = state(Vec::<PathBuf>::new, |s, _| {
+            rectangle()
+                .size([100.0, 100.0])
+                .on_drop(move |cx, paths| cx[s] = paths)
+        });
+        let sz = [200.0, 200.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<Vec<PathBuf>>::new(cx.view_id(&path));
+
+        let paths = vec![PathBuf::from("/tmp/a.txt")];
+        let drop = |x, y| Event::DropFiles {
+            position: [x, y].into(),
+            paths: paths.clone(),
+        };
+        let mut actions = vec![];
+        ui.process(&drop(150.0, 150.0), &mut path, &mut cx, &mut actions);
+        assert!(s.get(&cx).is_empty());
+
+        ui.process(&drop(50.0, 50.0), &mut path, &mut cx, &mut actions);
+        assert_eq!(*s.get(&cx), paths);
+    }
+}
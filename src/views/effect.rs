@@ -0,0 +1,125 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `blur`, `brightness`, `saturation` and `shader`
+/// modifiers.
+pub struct EffectView<V> {
+    child: V,
+    effect: Effect,
+}
+
+impl<V> EffectView<V>
+where
+    V: View,
+{
+    pub(crate) fn new(child: V, effect: Effect) -> Self {
+        Self { child, effect }
+    }
+}
+
+impl<V> View for EffectView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        path.push(0);
+        draw_layer(args.cx, args.vger, rect, self.effect.clone(), |cx, vger| {
+            self.child.draw(path, &mut DrawArgs { cx, vger })
+        });
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for EffectView<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_effect_view() {
+        let mut cx = Context::new();
+        let ui = rectangle().size([40.0, 30.0]).blur(4.0);
+
+        let mut path = vec![0];
+        let sz = ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        assert_eq!(sz, LocalSize::new(40.0, 30.0));
+        assert_eq!(
+            cx.get_layout(&path).rect,
+            LocalRect::new(LocalPoint::zero(), sz)
+        );
+        assert_eq!(ui.effect, Effect::blur(4.0));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::*;
+
+/// Find and replace, for `text_editor`.
+#[derive(Clone, Default)]
+pub(crate) struct FindPanelState {
+    pub open: bool,
+    pub find: Find,
+    /// Which match Next, Previous and Replace are on.
+    pub current: usize,
+}
+
+make_lens!(FindLens, FindPanelState, Find, find);
+make_lens!(PatternLens, Find, String, pattern);
+make_lens!(ReplacementLens, Find, String, replacement);
+make_lens!(RegexLens, Find, bool, regex);
+make_lens!(CaseLens, Find, bool, case_sensitive);
+
+impl FindPanelState {
+    /// Matches to highlight in `text`, and which is current.
+    pub fn highlights(&self, text: &str) -> (Vec<std::ops::Range<usize>>, usize) {
+        match self.find.matches(text) {
+            Ok(matches) if self.open && !matches.is_empty() => {
+                let current = self.current % matches.len();
+                (matches, current)
+            }
+            _ => (vec![], 0),
+        }
+    }
+
+    /// Match count, or what's wrong with the pattern.
+    fn status(&self, text: &str) -> String {
+        match self.find.matches(text) {
+            Ok(matches) if matches.is_empty() => "No matches".into(),
+            Ok(matches) => format!("{} of {}", self.current % matches.len() + 1, matches.len()),
+            Err(err) => err,
+        }
+    }
+}
+
+/// Moves to the next or previous match.
+fn step(
+    cx: &mut Context,
+    text: impl Binding<String>,
+    panel: StateHandle<FindPanelState>,
+    by: isize,
+) {
+    let count = cx[panel].find.matches(text.get(cx)).map_or(0, |m| m.len());
+    if count > 0 {
+        let current = (cx[panel].current % count) as isize;
+        cx[panel].current = (current + by).rem_euclid(count as isize) as usize;
+    }
+}
+
+fn replace_current(
+    cx: &mut Context,
+    text: impl Binding<String>,
+    panel: StateHandle<FindPanelState>,
+) {
+    let (matches, current) = cx[panel].highlights(text.get(cx));
+    if let Some(range) = matches.get(current) {
+        if let Ok(t) = cx[panel].find.replace(text.get(cx), range.clone()) {
+            // The next match takes its place.
+            text.with_mut(cx, |text| *text = t);
+        }
+    }
+}
+
+fn replace_all(cx: &mut Context, text: impl Binding<String>, panel: StateHandle<FindPanelState>) {
+    if let Ok((t, count)) = cx[panel].find.replace_all(text.get(cx)) {
+        if count > 0 {
+            text.with_mut(cx, |text| *text = t);
+        }
+    }
+}
+
+/// The find and replace bar shown above a `text_editor`.
+pub(crate) fn find_panel(
+    text: impl Binding<String>,
+    panel: StateHandle<FindPanelState>,
+    cx: &Context,
+) -> impl View {
+    let find = bind(panel, FindLens);
+    let status = cx[panel].status(text.get(cx));
+    vstack((
+        hstack((
+            text_field(bind(find, PatternLens))
+                .placeholder("Find")
+                .on_submit(move |cx, _| step(cx, text, panel, 1))
+                .flex(),
+            text(&status).padding(Auto),
+            button("Previous", move |cx| step(cx, text, panel, -1)),
+            button("Next", move |cx| step(cx, text, panel, 1)),
+        )),
+        hstack((
+            text_field(bind(find, ReplacementLens))
+                .placeholder("Replace")
+                .on_submit(move |cx, _| replace_current(cx, text, panel))
+                .flex(),
+            button("Replace", move |cx| replace_current(cx, text, panel)),
+            button("All", move |cx| replace_all(cx, text, panel)),
+        )),
+        hstack((
+            toggle(bind(find, CaseLens)),
+            text("Match case").padding(Auto),
+            toggle(bind(find, RegexLens)),
+            text("Regular expression").padding(Auto),
+            spacer(),
+            button("Done", move |cx| cx[panel].open = false),
+        )),
+    ))
+    .padding(Auto)
+}
@@ -26,7 +26,9 @@ where
                     cx.set_dirty();
                 }
             }
-            Event::Key(Key::Escape) => {
+            Event::Key {
+                key: Key::Escape, ..
+            } => {
                 if cx.focused_id == Some(vid) {
                     cx.focused_id = None;
                     cx.set_dirty();
@@ -0,0 +1,670 @@
+use crate::*;
+use std::rc::Rc;
+
+/// A bar on a `timeline`, from `start` for `duration`, in whatever unit of
+/// time the timeline is in, like seconds, beats or days.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimelineItem {
+    pub lane: usize,
+    pub start: f64,
+    pub duration: f64,
+    pub label: String,
+    pub color: Color,
+}
+
+impl TimelineItem {
+    pub fn new(lane: usize, start: f64, duration: f64, label: &str) -> Self {
+        Self {
+            lane,
+            start,
+            duration,
+            label: label.into(),
+            color: AZURE_HIGHLIGHT_DARK,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn end(&self) -> f64 {
+        self.start + self.duration
+    }
+}
+
+const RULER_HEIGHT: f32 = 24.0;
+const LANE_HEIGHT: f32 = 32.0;
+const LABEL_WIDTH: f32 = 100.0;
+const BAR_INSET: f32 = 3.0;
+/// How near an edge of a bar a drag resizes it, rather than moving it.
+const EDGE: f32 = 6.0;
+const MIN_BAR_WIDTH: f32 = 4.0;
+const MIN_TICK_SPACING: f32 = 60.0;
+const FONT_SIZE: u32 = 12;
+
+/// Maps time to x, for the part of a timeline right of the lane labels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct TimeAxis {
+    /// The time at `left`.
+    origin: f64,
+    /// Points per unit of time.
+    scale: f32,
+    left: f32,
+}
+
+impl TimeAxis {
+    fn x(&self, time: f64) -> f32 {
+        self.left + ((time - self.origin) * self.scale as f64) as f32
+    }
+
+    fn time(&self, x: f32) -> f64 {
+        self.origin + ((x - self.left) / self.scale) as f64
+    }
+
+    /// Zooms by `factor`, keeping the time at `x` in place.
+    fn zoom(&mut self, x: f32, factor: f32) {
+        let time = self.time(x);
+        self.scale = (self.scale * factor).clamp(1e-3, 1e4);
+        self.origin = time - ((x - self.left) / self.scale) as f64;
+    }
+
+    /// Time between ticks on the ruler: 1, 2 or 5 times a power of ten,
+    /// as little as leaves room for the labels.
+    fn tick_step(&self) -> f64 {
+        let min = (MIN_TICK_SPACING / self.scale) as f64;
+        let mut step = 10f64.powf(min.log10().floor());
+        for k in [1.0, 2.0, 5.0, 10.0] {
+            if step * k >= min {
+                step *= k;
+                break;
+            }
+        }
+        step
+    }
+}
+
+impl Default for TimeAxis {
+    fn default() -> Self {
+        Self {
+            origin: 0.0,
+            scale: 40.0,
+            left: 0.0,
+        }
+    }
+}
+
+/// Rounds `time` to the nearest multiple of `step`, if there's a step.
+fn snap(time: f64, step: f64) -> f64 {
+    if step > 0.0 {
+        (time / step).round() * step
+    } else {
+        time
+    }
+}
+
+/// A label for a tick `step` apart from the next, with as many decimals as
+/// the step needs.
+fn tick_label(time: f64, step: f64) -> String {
+    let decimals = (-step.log10()).ceil().max(0.0) as usize;
+    format!("{:.*}", decimals, time)
+}
+
+/// Which part of a bar a drag holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BarPart {
+    Start,
+    Middle,
+    End,
+}
+
+/// Where `original` goes when its `part` is dragged by `dt`, into `lane`,
+/// keeping at least `min` long.
+fn drag_bar(
+    original: &TimelineItem,
+    part: BarPart,
+    dt: f64,
+    lane: usize,
+    step: f64,
+    min: f64,
+) -> TimelineItem {
+    let mut item = original.clone();
+    match part {
+        BarPart::Middle => {
+            item.start = snap(original.start + dt, step);
+            item.lane = lane;
+        }
+        BarPart::Start => {
+            let end = original.end();
+            item.start = snap(original.start + dt, step).min(end - min);
+            item.duration = end - item.start;
+        }
+        BarPart::End => {
+            let end = snap(original.end() + dt, step).max(original.start + min);
+            item.duration = end - original.start;
+        }
+    }
+    item
+}
+
+#[derive(Clone, Debug)]
+enum TimelineDrag {
+    Playhead,
+    Bar {
+        index: usize,
+        part: BarPart,
+        /// The time under the pointer when the drag began.
+        from: f64,
+        original: TimelineItem,
+    },
+    Pan(LocalPoint),
+}
+
+#[derive(Clone, Debug)]
+struct TimelineState {
+    axis: TimeAxis,
+    /// How far the lanes are scrolled down.
+    scroll_y: f32,
+    size: LocalSize,
+    drag: Option<TimelineDrag>,
+    /// The scale when a pinch began.
+    pinch_scale: f32,
+}
+
+impl Default for TimelineState {
+    fn default() -> Self {
+        Self {
+            axis: TimeAxis::default(),
+            scroll_y: 0.0,
+            size: LocalSize::zero(),
+            drag: None,
+            pinch_scale: 1.0,
+        }
+    }
+}
+
+impl TimelineState {
+    /// The lane at `y`, if it's below the ruler.
+    fn lane_at(&self, y: f32) -> Option<usize> {
+        let from_top = self.size.height - RULER_HEIGHT - y + self.scroll_y;
+        (from_top >= 0.0 && y < self.size.height - RULER_HEIGHT)
+            .then(|| (from_top / LANE_HEIGHT) as usize)
+    }
+
+    /// The top of `lane`.
+    fn lane_top(&self, lane: usize) -> f32 {
+        self.size.height - RULER_HEIGHT - lane as f32 * LANE_HEIGHT + self.scroll_y
+    }
+
+    /// The bar drawn at `p`, drawn last if they overlap, and which part.
+    fn hit(&self, items: &[TimelineItem], p: LocalPoint) -> Option<(usize, BarPart)> {
+        let lane = self.lane_at(p.y)?;
+        items.iter().enumerate().rev().find_map(|(i, item)| {
+            let (x0, x1) = (self.axis.x(item.start), self.axis.x(item.end()));
+            if item.lane != lane || p.x < x0 - EDGE / 2.0 || p.x > x1 + EDGE / 2.0 {
+                return None;
+            }
+            let part = if x1 - x0 < 3.0 * EDGE {
+                BarPart::Middle
+            } else if p.x - x0 < EDGE {
+                BarPart::Start
+            } else if x1 - p.x < EDGE {
+                BarPart::End
+            } else {
+                BarPart::Middle
+            };
+            Some((i, part))
+        })
+    }
+
+    /// Where zooming centers, in the middle of the lanes.
+    fn zoom_x(&self) -> f32 {
+        (self.axis.left + self.size.width) / 2.0
+    }
+
+    fn scroll_by(&mut self, dy: f32, lanes: usize) {
+        let max = (lanes as f32 * LANE_HEIGHT - (self.size.height - RULER_HEIGHT)).max(0.0);
+        self.scroll_y = (self.scroll_y + dy).clamp(0.0, max);
+    }
+}
+
+/// Reads and writes the time a `timeline`'s playhead is at.
+#[derive(Clone)]
+struct PlayheadBinding {
+    get: Rc<dyn Fn(&Context) -> f64>,
+    set: Rc<dyn Fn(&mut Context, f64)>,
+}
+
+/// Options for `timeline`. Set them with `TimelineMods`.
+#[derive(Clone, Default)]
+pub struct TimelineOptions {
+    lanes: Vec<String>,
+    snap: f64,
+    playhead: Option<PlayheadBinding>,
+}
+
+pub trait TimelineMods: View + Sized {
+    /// Names the lanes, in a column down the left.
+    fn lanes(self, names: &[&str]) -> Self;
+
+    /// Snaps bars, as they're dragged, and the playhead to multiples of
+    /// `step`.
+    fn snap(self, step: f64) -> Self;
+
+    /// Shows a playhead at the time in `time`, which dragging along the
+    /// ruler moves.
+    fn playhead(self, time: impl Binding<f64>) -> Self;
+}
+
+impl<F> TimelineMods for ModView<TimelineOptions, F>
+where
+    ModView<TimelineOptions, F>: View,
+{
+    fn lanes(self, names: &[&str]) -> Self {
+        let mut opts = self.value;
+        opts.lanes = names.iter().map(|s| s.to_string()).collect();
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn snap(self, step: f64) -> Self {
+        let mut opts = self.value;
+        opts.snap = step;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn playhead(self, time: impl Binding<f64>) -> Self {
+        let mut opts = self.value;
+        opts.playhead = Some(PlayheadBinding {
+            get: Rc::new(move |cx| *time.get(cx)),
+            set: Rc::new(move |cx, t| *time.get_mut(cx) = t),
+        });
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+/// Draws `s` at `x`, centered in a band `height` tall with its top at `top`.
+fn draw_label(vger: &mut Vger, s: &str, x: f32, top: f32, height: f32, color: Color) {
+    vger.save();
+    vger.translate([x, top - (height - FONT_SIZE as f32) / 2.0 - 2.0]);
+    vger.text(s, FONT_SIZE, color, None);
+    vger.restore();
+}
+
+fn timeline_canvas(
+    items: impl Binding<Vec<TimelineItem>>,
+    state: StateHandle<TimelineState>,
+    opts: TimelineOptions,
+) -> impl View {
+    let (drag_opts, scroll_opts) = (opts.clone(), opts.clone());
+    canvas(move |cx, rect, vger| {
+        let left = if opts.lanes.is_empty() {
+            0.0
+        } else {
+            LABEL_WIDTH
+        };
+        cx[state].size = rect.size;
+        cx[state].axis.left = left;
+        let playhead = opts.playhead.as_ref().map(|p| (p.get)(cx));
+        let s = cx[state].clone();
+        let items = items.get(cx);
+        let axis = s.axis;
+        let lanes = items
+            .iter()
+            .map(|i| i.lane + 1)
+            .max()
+            .unwrap_or(0)
+            .max(opts.lanes.len());
+        let body = LocalRect::new(
+            [left, 0.0].into(),
+            [
+                (rect.width() - left).max(0.0),
+                (rect.height() - RULER_HEIGHT).max(0.0),
+            ]
+            .into(),
+        );
+
+        // Lanes, striped, and the lines under the ticks.
+        let step = axis.tick_step();
+        let first_tick = (axis.time(left) / step).ceil() as i64;
+        let last_tick = (axis.time(rect.width()) / step).floor() as i64;
+        let stripe = vger.color_paint(CONTROL_BACKGROUND);
+        let grid = vger.color_paint(GROOVES);
+        vger.save();
+        vger.scissor(body);
+        for lane in (0..lanes).step_by(2) {
+            let top = s.lane_top(lane);
+            vger.fill_rect(
+                LocalRect::new(
+                    [left, top - LANE_HEIGHT].into(),
+                    [body.width(), LANE_HEIGHT].into(),
+                ),
+                0.0,
+                stripe,
+            );
+        }
+        for tick in first_tick..=last_tick {
+            let x = axis.x(tick as f64 * step);
+            vger.fill_rect(
+                LocalRect::new([x, 0.0].into(), [1.0, body.height()].into()),
+                0.0,
+                grid,
+            );
+        }
+
+        // Bars.
+        let dragged = match &s.drag {
+            Some(TimelineDrag::Bar { index, .. }) => Some(*index),
+            _ => None,
+        };
+        for (i, item) in items.iter().enumerate() {
+            let top = s.lane_top(item.lane);
+            let (x0, x1) = (axis.x(item.start), axis.x(item.end()));
+            let bar = LocalRect::new(
+                [x0, top - LANE_HEIGHT + BAR_INSET].into(),
+                [(x1 - x0).max(MIN_BAR_WIDTH), LANE_HEIGHT - 2.0 * BAR_INSET].into(),
+            );
+            if !bar.intersects(&body) {
+                continue;
+            }
+            let paint = vger.color_paint(item.color);
+            vger.fill_rect(bar, 4.0, paint);
+            if dragged == Some(i) {
+                let paint = vger.color_paint(TEXT_COLOR);
+                vger.stroke_rect(bar.min(), bar.max(), 4.0, 1.0, paint);
+            }
+            vger.save();
+            vger.scissor(bar.inflate(-4.0, 0.0));
+            draw_label(
+                vger,
+                &item.label,
+                x0.max(left) + 6.0,
+                bar.max_y(),
+                bar.height(),
+                TEXT_COLOR,
+            );
+            vger.restore();
+        }
+        vger.restore();
+
+        // Lane names.
+        if left > 0.0 {
+            let background = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+            vger.fill_rect(
+                LocalRect::new(LocalPoint::zero(), [left, body.height()].into()),
+                0.0,
+                background,
+            );
+            vger.save();
+            vger.scissor(LocalRect::new(
+                LocalPoint::zero(),
+                [left, body.height()].into(),
+            ));
+            for (lane, name) in opts.lanes.iter().enumerate() {
+                draw_label(vger, name, 8.0, s.lane_top(lane), LANE_HEIGHT, TEXT_COLOR);
+            }
+            vger.restore();
+        }
+
+        // The ruler.
+        let ruler = LocalRect::new(
+            [0.0, rect.height() - RULER_HEIGHT].into(),
+            [rect.width(), RULER_HEIGHT].into(),
+        );
+        let background = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        vger.fill_rect(ruler, 0.0, background);
+        vger.save();
+        vger.scissor(LocalRect::new(
+            [left, ruler.min_y()].into(),
+            [body.width(), RULER_HEIGHT].into(),
+        ));
+        for tick in first_tick..=last_tick {
+            let time = tick as f64 * step;
+            let x = axis.x(time);
+            vger.fill_rect(
+                LocalRect::new([x, ruler.min_y()].into(), [1.0, 6.0].into()),
+                0.0,
+                grid,
+            );
+            draw_label(
+                vger,
+                &tick_label(time, step),
+                x + 3.0,
+                rect.height(),
+                RULER_HEIGHT,
+                MEDIUM_GRAY,
+            );
+        }
+        vger.restore();
+
+        // The playhead, over everything.
+        if let Some(time) = playhead {
+            let x = axis.x(time);
+            if x >= left && x <= rect.width() {
+                let paint = vger.color_paint(RED_HIGHLIGHT);
+                vger.fill_rect(
+                    LocalRect::new([x - 0.5, 0.0].into(), [1.0, rect.height()].into()),
+                    0.0,
+                    paint,
+                );
+                vger.fill_rect(
+                    LocalRect::new([x - 5.0, ruler.min_y()].into(), [10.0, 8.0].into()),
+                    2.0,
+                    paint,
+                );
+            }
+        }
+    })
+    .clip()
+    .drag_p(move |cx, p, gesture, _| {
+        let opts = &drag_opts;
+        let s = cx[state].clone();
+        let lanes = items
+            .with(cx, |items| {
+                items.iter().map(|i| i.lane + 1).max().unwrap_or(0)
+            })
+            .max(opts.lanes.len())
+            .max(1);
+
+        if gesture == GestureState::Began {
+            let drag = if p.y >= s.size.height - RULER_HEIGHT {
+                opts.playhead.as_ref().map(|_| TimelineDrag::Playhead)
+            } else if p.x < s.axis.left {
+                None
+            } else {
+                let hit = items.with(cx, |items| {
+                    s.hit(items, p)
+                        .map(|(index, part)| (index, part, items[index].clone()))
+                });
+                Some(match hit {
+                    Some((index, part, original)) => TimelineDrag::Bar {
+                        index,
+                        part,
+                        from: s.axis.time(p.x),
+                        original,
+                    },
+                    None => TimelineDrag::Pan(p),
+                })
+            };
+            cx[state].drag = drag;
+        }
+
+        match cx[state].drag.clone() {
+            Some(TimelineDrag::Playhead) => {
+                if let Some(playhead) = &opts.playhead {
+                    (playhead.set)(cx, snap(s.axis.time(p.x), opts.snap));
+                }
+            }
+            Some(TimelineDrag::Bar {
+                index,
+                part,
+                from,
+                original,
+            }) => {
+                let dt = s.axis.time(p.x) - from;
+                let lane = s
+                    .lane_at(p.y.min(s.size.height - RULER_HEIGHT - 1.0))
+                    .unwrap_or(original.lane)
+                    .min(lanes - 1);
+                let min = opts.snap.max((MIN_BAR_WIDTH / s.axis.scale) as f64);
+                let moved = drag_bar(&original, part, dt, lane, opts.snap, min);
+                items.with_mut(cx, |items| {
+                    if let Some(item) = items.get_mut(index) {
+                        *item = moved;
+                    }
+                });
+            }
+            Some(TimelineDrag::Pan(last)) => {
+                let s = &mut cx[state];
+                s.axis.origin -= ((p.x - last.x) / s.axis.scale) as f64;
+                s.scroll_by(p.y - last.y, lanes);
+                s.drag = Some(TimelineDrag::Pan(p));
+            }
+            None => (),
+        }
+
+        if gesture == GestureState::Ended {
+            cx[state].drag = None;
+        }
+    })
+    .scroll(move |cx, delta| {
+        let lanes = items
+            .with(cx, |items| {
+                items.iter().map(|i| i.lane + 1).max().unwrap_or(0)
+            })
+            .max(scroll_opts.lanes.len());
+        let zoom = cx.key_mods.shortcut();
+        let s = &mut cx[state];
+        if zoom {
+            let x = s.zoom_x();
+            s.axis.zoom(x, (1.0 + delta.y * 0.01).clamp(0.5, 2.0));
+        } else {
+            s.axis.origin += (delta.x / s.axis.scale) as f64;
+            s.scroll_by(delta.y, lanes);
+        }
+    })
+    .on_pinch(move |cx, scale, _, gesture| {
+        let s = &mut cx[state];
+        if gesture == GestureState::Began {
+            s.pinch_scale = s.axis.scale;
+        }
+        let x = s.zoom_x();
+        let factor = s.pinch_scale * scale / s.axis.scale;
+        s.axis.zoom(x, factor);
+    })
+}
+
+/// Shows `items` as bars along a time axis, in lanes, like a Gantt chart
+/// or the arrangement in a video editor or DAW. Dragging a bar moves it,
+/// to other lanes too, and dragging its ends resizes it, snapping with
+/// `TimelineMods::snap`. Dragging elsewhere, or scrolling, pans, and
+/// scrolling with the shortcut key, or pinching, zooms.
+///
+/// ```no_run
+/// # use rui::*;
+/// let items = vec![
+///     TimelineItem::new(0, 0.0, 4.0, "Intro"),
+///     TimelineItem::new(1, 2.0, 8.0, "Drums").color(GREEN_HIGHLIGHT),
+/// ];
+/// state(
+///     || 0.0,
+///     move |playhead, _| {
+///         let items = items.clone();
+///         state(
+///             move || items.clone(),
+///             move |items, _| {
+///                 timeline(items)
+///                     .lanes(&["Video", "Audio"])
+///                     .snap(0.25)
+///                     .playhead(playhead)
+///             },
+///         )
+///     },
+/// );
+/// ```
+pub fn timeline(items: impl Binding<Vec<TimelineItem>>) -> impl TimelineMods {
+    modview(move |opts: TimelineOptions, _| {
+        state(TimelineState::default, move |state, _| {
+            timeline_canvas(items, state, opts.clone())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_time_axis() {
+        let mut axis = TimeAxis {
+            origin: 10.0,
+            scale: 20.0,
+            left: 100.0,
+        };
+        assert_eq!(axis.x(12.0), 140.0);
+        assert_eq!(axis.time(140.0), 12.0);
+
+        axis.zoom(140.0, 2.0);
+        assert_eq!(axis.scale, 40.0);
+        assert_eq!(axis.x(12.0), 140.0);
+
+        // At least 60 points apart: 1.5 units, so 2.
+        assert_eq!(axis.tick_step(), 2.0);
+        axis.scale = 1000.0;
+        assert!((axis.tick_step() - 0.1).abs() < 1e-12);
+        assert_eq!(tick_label(0.30000000000000004, 0.1), "0.3");
+        assert_eq!(tick_label(20.0, 10.0), "20");
+    }
+
+    #[test]
+    fn test_drag_bar() {
+        let item = TimelineItem::new(0, 2.0, 4.0, "a");
+
+        let moved = drag_bar(&item, BarPart::Middle, 1.1, 2, 0.5, 0.5);
+        assert_eq!((moved.lane, moved.start, moved.duration), (2, 3.0, 4.0));
+
+        let start = drag_bar(&item, BarPart::Start, 10.0, 0, 0.0, 0.5);
+        assert_eq!((start.start, start.end()), (5.5, 6.0));
+
+        let end = drag_bar(&item, BarPart::End, -1.2, 0, 0.5, 0.5);
+        assert_eq!((end.start, end.duration), (2.0, 3.0));
+    }
+
+    #[test]
+    fn test_timeline_hit() {
+        let mut s = TimelineState {
+            size: [400.0, RULER_HEIGHT + 2.0 * LANE_HEIGHT].into(),
+            ..Default::default()
+        };
+        s.axis.scale = 10.0;
+        let items = vec![
+            TimelineItem::new(0, 0.0, 10.0, "a"),
+            TimelineItem::new(1, 5.0, 10.0, "b"),
+        ];
+        let lane1 = LANE_HEIGHT / 2.0;
+        assert_eq!(s.lane_at(lane1), Some(1));
+        assert_eq!(s.lane_at(s.size.height - 1.0), None);
+        assert_eq!(
+            s.hit(&items, [100.0, lane1].into()),
+            Some((1, BarPart::Middle))
+        );
+        assert_eq!(
+            s.hit(&items, [52.0, lane1].into()),
+            Some((1, BarPart::Start))
+        );
+        assert_eq!(
+            s.hit(&items, [149.0, lane1].into()),
+            Some((1, BarPart::End))
+        );
+        assert_eq!(s.hit(&items, [20.0, lane1].into()), None);
+    }
+}
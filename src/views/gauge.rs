@@ -0,0 +1,298 @@
+use crate::*;
+use std::f32::consts::PI;
+use std::ops::Range;
+
+/// Width of the arc and its zones.
+const ARC_WIDTH: f32 = 6.0;
+const MAJOR_TICK: f32 = 10.0;
+const MINOR_TICK: f32 = 5.0;
+const LABEL_SIZE: u32 = 11;
+
+/// Options for `gauge`. Set them with `GaugeMods`.
+#[derive(Clone)]
+pub struct GaugeOptions {
+    start_angle: f32,
+    end_angle: f32,
+    major_ticks: usize,
+    minor_ticks: usize,
+    zones: Vec<(Range<f32>, Color)>,
+    spring: Spring,
+}
+
+impl Default for GaugeOptions {
+    fn default() -> Self {
+        Self {
+            start_angle: 1.25 * PI,
+            end_angle: -0.25 * PI,
+            major_ticks: 10,
+            minor_ticks: 4,
+            zones: vec![],
+            spring: Spring::default(),
+        }
+    }
+}
+
+impl GaugeOptions {
+    /// The angle `value` is at, clamped to `range`.
+    fn angle(&self, range: &Range<f32>, value: f32) -> f32 {
+        let span = range.end - range.start;
+        let t = if span == 0.0 {
+            0.0
+        } else {
+            ((value - range.start) / span).clamp(0.0, 1.0)
+        };
+        self.start_angle + t * (self.end_angle - self.start_angle)
+    }
+
+    /// The values ticks go at, and whether each is a major one.
+    fn ticks(&self, range: &Range<f32>) -> Vec<(f32, bool)> {
+        let n = self.major_ticks * (self.minor_ticks + 1);
+        if n == 0 {
+            return vec![];
+        }
+        let step = (range.end - range.start) / n as f32;
+        (0..=n)
+            .map(|i| {
+                (
+                    range.start + step * i as f32,
+                    i % (self.minor_ticks + 1) == 0,
+                )
+            })
+            .collect()
+    }
+}
+
+/// The label for a major tick, with a decimal place when ticks are closer
+/// than a whole unit apart.
+fn tick_label(value: f32, step: f32) -> String {
+    if step.abs() >= 1.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+pub trait GaugeMods: View + Sized {
+    /// Sweeps from `start` at the bottom of the range to `end` at the
+    /// top, in radians counterclockwise from pointing right. The default
+    /// goes clockwise over the top, from the lower left to the lower right.
+    fn angles(self, start: f32, end: f32) -> Self;
+
+    /// Divides the range with `major` labeled ticks, and `minor` smaller
+    /// ones between each.
+    fn ticks(self, major: usize, minor: usize) -> Self;
+
+    /// Colors the part of the arc over `range`, for marking where values
+    /// are fine or not. Later zones draw over earlier ones.
+    fn zone(self, range: Range<f32>, color: Color) -> Self;
+
+    /// How the needle springs to a new value.
+    fn spring(self, spring: Spring) -> Self;
+}
+
+impl<F> GaugeMods for ModView<GaugeOptions, F>
+where
+    ModView<GaugeOptions, F>: View,
+{
+    fn angles(self, start: f32, end: f32) -> Self {
+        let mut opts = self.value;
+        opts.start_angle = start;
+        opts.end_angle = end;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn ticks(self, major: usize, minor: usize) -> Self {
+        let mut opts = self.value;
+        opts.major_ticks = major;
+        opts.minor_ticks = minor;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn zone(self, range: Range<f32>, color: Color) -> Self {
+        let mut opts = self.value;
+        opts.zones.push((range, color));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn spring(self, spring: Spring) -> Self {
+        let mut opts = self.value;
+        opts.spring = spring;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+fn draw_gauge(
+    vger: &mut Vger,
+    rect: LocalRect,
+    opts: &GaugeOptions,
+    range: &Range<f32>,
+    value: f32,
+) {
+    let c = rect.center();
+    let r = rect.width().min(rect.height()) / 2.0 - ARC_WIDTH;
+    if r <= 0.0 {
+        return;
+    }
+    let at = |angle: f32, r: f32| c + LocalVector::new(angle.cos(), angle.sin()) * r;
+
+    let paint = vger.color_paint(CONTROL_BACKGROUND);
+    let track = CanvasPath::arc(c, r, opts.start_angle, opts.end_angle);
+    vger.stroke_path(&track, ARC_WIDTH, paint);
+
+    for (zone, color) in &opts.zones {
+        let paint = vger.color_paint(*color);
+        let zone = CanvasPath::arc(
+            c,
+            r,
+            opts.angle(range, zone.start),
+            opts.angle(range, zone.end),
+        );
+        vger.stroke_path(&zone, ARC_WIDTH, paint);
+    }
+
+    let ticks = opts.ticks(range);
+    let step = (range.end - range.start) / opts.major_ticks.max(1) as f32;
+    let inner = r - ARC_WIDTH;
+    for (tick, major) in ticks {
+        let a = opts.angle(range, tick);
+        let (length, width, color) = if major {
+            (MAJOR_TICK, 1.5, TEXT_COLOR)
+        } else {
+            (MINOR_TICK, 1.0, MEDIUM_GRAY)
+        };
+        let paint = vger.color_paint(color);
+        vger.stroke_segment(at(a, inner), at(a, inner - length), width, paint);
+
+        if major {
+            let label = tick_label(tick, step);
+            let bounds = vger.text_bounds(&label, LABEL_SIZE, None);
+            let p = at(a, inner - MAJOR_TICK - 4.0 - bounds.width() / 2.0);
+            vger.text_at(
+                &label,
+                p - bounds.center().to_vector(),
+                LABEL_SIZE,
+                TEXT_COLOR,
+            );
+        }
+    }
+
+    let paint = vger.color_paint(RED_HIGHLIGHT);
+    let a = opts.angle(range, value);
+    vger.stroke_segment(c, at(a, inner - MINOR_TICK), 2.0, paint);
+    vger.fill_circle(c, 4.0, paint);
+}
+
+/// A dial with a needle pointing at `value` in `range`, with tick marks
+/// and optional colored zones, for showing readings like levels, speeds
+/// or temperatures. The needle springs to new values. Unlike `knob`, it
+/// can't be dragged.
+///
+/// ```no_run
+/// # use rui::*;
+/// state(
+///     || 72.0,
+///     |temperature, _| {
+///         gauge(temperature, 0.0..100.0)
+///             .ticks(5, 4)
+///             .zone(80.0..100.0, RED_HIGHLIGHT)
+///     },
+/// );
+/// ```
+pub fn gauge(value: impl Binding<f32>, range: Range<f32>) -> impl GaugeMods {
+    modview(move |opts: GaugeOptions, cx| {
+        let range = range.clone();
+        let spring = opts.spring;
+        animated(*value.get(cx), spring, move |v, _| {
+            let opts = opts.clone();
+            let range = range.clone();
+            canvas(move |_, rect, vger| draw_gauge(vger, rect, &opts, &range, v))
+                .role(accesskit::Role::ProgressIndicator)
+        })
+    })
+}
+
+/// A ring filling clockwise from the top as `value` goes from 0 to 1,
+/// with the percentage in the middle, for progress that's known, like
+/// downloads or exports.
+///
+/// ```no_run
+/// # use rui::*;
+/// state(|| 0.3, |progress, _| radial_progress(progress).size([64.0, 64.0]));
+/// ```
+pub fn radial_progress(value: impl Binding<f32>) -> impl View {
+    canvas(move |cx, rect, vger| {
+        let value = (*value.get(cx)).clamp(0.0, 1.0);
+        let c = rect.center();
+        let r = rect.width().min(rect.height()) / 2.0 - ARC_WIDTH / 2.0;
+        if r <= 0.0 {
+            return;
+        }
+
+        let paint = vger.color_paint(CONTROL_BACKGROUND);
+        vger.stroke_path(&CanvasPath::circle(c, r), ARC_WIDTH, paint);
+
+        if value > 0.0 {
+            let paint = vger.color_paint(AZURE_HIGHLIGHT);
+            let arc = CanvasPath::arc(c, r, PI / 2.0, PI / 2.0 - 2.0 * PI * value);
+            vger.stroke_path(&arc, ARC_WIDTH, paint);
+        }
+
+        let label = format!("{:.0}%", value * 100.0);
+        let size = (r / 2.0).clamp(8.0, 24.0) as u32;
+        let bounds = vger.text_bounds(&label, size, None);
+        vger.text_at(&label, c - bounds.center().to_vector(), size, TEXT_COLOR);
+    })
+    .role(accesskit::Role::ProgressIndicator)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_gauge_angle() {
+        let opts = GaugeOptions::default();
+        let range = 0.0..100.0;
+        assert_eq!(opts.angle(&range, 0.0), 1.25 * PI);
+        assert_eq!(opts.angle(&range, 100.0), -0.25 * PI);
+        assert!((opts.angle(&range, 50.0) - PI / 2.0).abs() < 1e-5);
+        // Clamped to the range.
+        assert_eq!(opts.angle(&range, 150.0), -0.25 * PI);
+        assert_eq!(opts.angle(&range, -10.0), 1.25 * PI);
+        assert_eq!(opts.angle(&(1.0..1.0), 5.0), 1.25 * PI);
+    }
+
+    #[test]
+    fn test_gauge_ticks() {
+        let opts = GaugeOptions {
+            major_ticks: 2,
+            minor_ticks: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            opts.ticks(&(0.0..10.0)),
+            vec![
+                (0.0, true),
+                (2.5, false),
+                (5.0, true),
+                (7.5, false),
+                (10.0, true)
+            ]
+        );
+        assert_eq!(tick_label(5.0, 5.0), "5");
+        assert_eq!(tick_label(0.5, 0.25), "0.5");
+    }
+}
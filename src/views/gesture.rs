@@ -0,0 +1,396 @@
+use crate::*;
+use std::any::Any;
+use std::time::Duration;
+
+/// What a gesture view remembers between events.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GestureViewState {
+    /// Touches which began on the view, as bits.
+    touches: u16,
+    /// Has the long press for the current touch been reported?
+    long_pressed: bool,
+    /// The touch being panned.
+    pan: Option<usize>,
+    /// The two touches being pinched, and how far apart and at what angle
+    /// they started.
+    pinch: Option<(usize, usize, f32, f32)>,
+}
+
+impl GestureViewState {
+    fn owns(&self, id: usize) -> bool {
+        self.touches & (1 << id) != 0
+    }
+
+    /// Touches on the view which are still down.
+    fn down(&self, cx: &Context) -> Vec<usize> {
+        (0..16)
+            .filter(|id| self.owns(*id))
+            .filter(|id| cx.gestures.touch(*id).map_or(false, |t| t.down))
+            .collect()
+    }
+}
+
+/// Recognizes a gesture from the touches which began on a view.
+pub trait Recognizer {
+    fn process(
+        &self,
+        event: &Event,
+        state: StateHandle<GestureViewState>,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    );
+}
+
+/// Recognizes `count` taps in a row. See `on_tap`.
+pub struct TapRecognizer<F> {
+    count: usize,
+    f: F,
+}
+
+impl<A: 'static, F: Fn(&mut Context) -> A> Recognizer for TapRecognizer<F> {
+    fn process(
+        &self,
+        event: &Event,
+        state: StateHandle<GestureViewState>,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::TouchEnd { id, .. } = event {
+            let taps = cx.tap_count();
+            if state.get(cx).owns(*id) && taps > 0 && taps % self.count == 0 {
+                actions.push(Box::new((self.f)(cx)));
+            }
+        }
+    }
+}
+
+/// Recognizes a touch held still. See `on_long_press`.
+pub struct LongPressRecognizer<F> {
+    duration: Duration,
+    f: F,
+}
+
+impl<A: 'static, F: Fn(&mut Context) -> A> Recognizer for LongPressRecognizer<F> {
+    fn process(
+        &self,
+        event: &Event,
+        state: StateHandle<GestureViewState>,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        match event {
+            Event::TouchBegin { id, .. } if state.get(cx).owns(*id) => {
+                cx[state].long_pressed = false;
+                // Wake up to check on it.
+                cx.request_redraw_at(Instant::now() + self.duration);
+            }
+            Event::Anim | Event::TouchMove { .. } if !state.get(cx).long_pressed => {
+                let held = state.get(cx).down(cx).into_iter().any(|id| {
+                    let touch = cx.gestures.touch(id).unwrap();
+                    !touch.moved && touch.start.elapsed() >= self.duration
+                });
+                if held {
+                    cx[state].long_pressed = true;
+                    actions.push(Box::new((self.f)(cx)));
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Recognizes a single touch dragging. See `on_pan`.
+pub struct PanRecognizer<F> {
+    f: F,
+}
+
+impl<A: 'static, F: Fn(&mut Context, LocalOffset, LocalOffset, GestureState) -> A> Recognizer
+    for PanRecognizer<F>
+{
+    fn process(
+        &self,
+        event: &Event,
+        state: StateHandle<GestureViewState>,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let pan = state.get(cx).pan;
+        match event {
+            Event::TouchMove { id, delta, .. } if state.get(cx).owns(*id) => {
+                let touch = *cx.gestures.touch(*id).unwrap();
+                if pan == Some(*id) {
+                    actions.push(Box::new((self.f)(
+                        cx,
+                        *delta,
+                        touch.velocity,
+                        GestureState::Changed,
+                    )));
+                } else if pan.is_none() && touch.moved {
+                    // Catch up on the movement within the slop.
+                    cx[state].pan = Some(*id);
+                    actions.push(Box::new((self.f)(
+                        cx,
+                        touch.position - touch.origin,
+                        touch.velocity,
+                        GestureState::Began,
+                    )));
+                }
+            }
+            Event::TouchEnd { id, .. } if pan == Some(*id) => {
+                let velocity = cx.gestures.touch(*id).unwrap().velocity;
+                cx[state].pan = None;
+                actions.push(Box::new((self.f)(
+                    cx,
+                    LocalOffset::zero(),
+                    velocity,
+                    GestureState::Ended,
+                )));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Recognizes two touches pinching and rotating. See `on_pinch`.
+pub struct PinchRecognizer<F> {
+    f: F,
+}
+
+impl<F> PinchRecognizer<F> {
+    /// Distance and angle between two touches.
+    fn span(cx: &Context, a: usize, b: usize) -> (f32, f32) {
+        let a = cx.gestures.touch(a).unwrap().position;
+        let b = cx.gestures.touch(b).unwrap().position;
+        let v = b - a;
+        (v.length(), v.y.atan2(v.x))
+    }
+}
+
+impl<A: 'static, F: Fn(&mut Context, f32, f32, GestureState) -> A> Recognizer
+    for PinchRecognizer<F>
+{
+    fn process(
+        &self,
+        event: &Event,
+        state: StateHandle<GestureViewState>,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        match (event, state.get(cx).pinch) {
+            (Event::TouchBegin { .. }, None) => {
+                let down = state.get(cx).down(cx);
+                if let [a, b, ..] = down[..] {
+                    let (distance, angle) = Self::span(cx, a, b);
+                    if distance > 0.0 {
+                        cx[state].pinch = Some((a, b, distance, angle));
+                        actions.push(Box::new((self.f)(cx, 1.0, 0.0, GestureState::Began)));
+                    }
+                }
+            }
+            (Event::TouchMove { id, .. }, Some((a, b, distance, angle)))
+            | (Event::TouchEnd { id, .. }, Some((a, b, distance, angle)))
+                if *id == a || *id == b =>
+            {
+                let (d, theta) = Self::span(cx, a, b);
+                let gesture = if matches!(event, Event::TouchEnd { .. }) {
+                    cx[state].pinch = None;
+                    GestureState::Ended
+                } else {
+                    GestureState::Changed
+                };
+                actions.push(Box::new((self.f)(cx, d / distance, theta - angle, gesture)));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Struct for the gesture modifiers: `on_tap`, `on_double_tap`,
+/// `on_long_press`, `on_pan` and `on_pinch`. Unlike `tap` and `drag`, these
+/// don't take the touch for themselves, so several can recognize gestures
+/// on the same view.
+pub struct Gesture<V, R> {
+    child: V,
+    recognizer: R,
+}
+
+impl<V, R> Gesture<V, R>
+where
+    V: View,
+    R: Recognizer + 'static,
+{
+    pub fn new(v: V, recognizer: R) -> Self {
+        Self {
+            child: v,
+            recognizer,
+        }
+    }
+}
+
+impl<V, R> View for Gesture<V, R>
+where
+    V: View,
+    R: Recognizer + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        cx.init_state(vid, &GestureViewState::default);
+        let s = StateHandle::<GestureViewState>::new(vid);
+
+        if let Event::TouchBegin { id, position } = event {
+            if self.hittest(path, *position, cx).is_some() {
+                cx[s].touches |= 1 << id;
+            }
+        }
+
+        self.recognizer.process(event, s, cx, actions);
+
+        if let Event::TouchEnd { id, .. } = event {
+            if s.get(cx).owns(*id) {
+                cx[s].touches &= !(1 << id);
+            }
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, R> private::Sealed for Gesture<V, R> {}
+
+impl<F> TapRecognizer<F> {
+    pub fn new(count: usize, f: F) -> Self {
+        Self {
+            count: count.max(1),
+            f,
+        }
+    }
+}
+
+impl<F> LongPressRecognizer<F> {
+    pub fn new(duration: Duration, f: F) -> Self {
+        Self { duration, f }
+    }
+}
+
+impl<F> PanRecognizer<F> {
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> PinchRecognizer<F> {
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_pinch() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 1.0,
+            |s, _| {
+                rectangle()
+                    .size([200.0, 200.0])
+                    .on_pinch(move |cx, scale, _, _| cx[s] = scale)
+            },
+        );
+        let sz = [200.0, 200.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<f32>::new(cx.view_id(&path));
+
+        let events = [
+            Event::TouchBegin {
+                id: 1,
+                position: [50.0, 100.0].into(),
+            },
+            Event::TouchBegin {
+                id: 2,
+                position: [150.0, 100.0].into(),
+            },
+            Event::TouchMove {
+                id: 2,
+                position: [250.0, 100.0].into(),
+                delta: [100.0, 0.0].into(),
+            },
+        ];
+        for event in &events {
+            cx.process(&ui, event);
+        }
+        assert_eq!(*s.get(&cx), 2.0);
+    }
+}
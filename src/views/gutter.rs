@@ -0,0 +1,160 @@
+use crate::*;
+
+/// An icon in `text_editor`'s gutter. See `TextEditorMods::gutter_icon`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GutterIcon {
+    Breakpoint,
+    Bookmark,
+}
+
+/// How a line differs from the text it's compared with, for the change
+/// markers in `text_editor`'s gutter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineChange {
+    Added,
+    Modified,
+    /// Lines were deleted before this one.
+    Deleted,
+}
+
+/// Says which lines of the text in a `text_editor` have changed, such as
+/// since it was last saved or committed. See `BaseTextDiff`.
+pub trait DiffProvider {
+    /// Changed lines of `text`, numbered from 0.
+    fn line_changes(&self, text: &str) -> Vec<(usize, LineChange)>;
+}
+
+/// Compares text with a base version of it, line by line.
+pub struct BaseTextDiff {
+    base: String,
+}
+
+impl BaseTextDiff {
+    pub fn new(base: impl Into<String>) -> Self {
+        Self { base: base.into() }
+    }
+}
+
+impl DiffProvider for BaseTextDiff {
+    fn line_changes(&self, text: &str) -> Vec<(usize, LineChange)> {
+        diff_lines(&self.base, text)
+    }
+}
+
+/// Finds changed lines. Where lines were both removed and added, the
+/// added ones count as modified.
+fn diff_lines(base: &str, text: &str) -> Vec<(usize, LineChange)> {
+    let a: Vec<&str> = base.lines().collect();
+    let b: Vec<&str> = text.lines().collect();
+    let mut changes = vec![];
+    for block in diff_blocks(&a, &b).into_iter().filter(|b| b.changed) {
+        if block.new.is_empty() {
+            changes.push((block.new.start, LineChange::Deleted));
+        }
+        for line in block.new.clone() {
+            let change = if line - block.new.start < block.old.len() {
+                LineChange::Modified
+            } else {
+                LineChange::Added
+            };
+            changes.push((line, change));
+        }
+    }
+    changes
+}
+
+pub(crate) const GUTTER_ICON_SIZE: f32 = 18.0;
+pub(crate) const CHANGE_MARKER_WIDTH: f32 = 3.0;
+const LINE_NUMBER_SIZE: u32 = 14;
+
+/// Draws `icon` centered in `rect`.
+pub(crate) fn draw_gutter_icon(vger: &mut Vger, icon: GutterIcon, rect: LocalRect) {
+    let c = rect.center();
+    match icon {
+        GutterIcon::Breakpoint => {
+            let paint = vger.color_paint(RED_HIGHLIGHT);
+            vger.fill_circle(c, 5.0, paint);
+        }
+        GutterIcon::Bookmark => {
+            let paint = vger.color_paint(AZURE_HIGHLIGHT);
+            vger.fill_rect(
+                LocalRect::new([c.x - 4.0, c.y - 5.0].into(), [8.0, 10.0].into()),
+                1.0,
+                paint,
+            );
+        }
+    }
+}
+
+/// Draws a change marker at the right of the gutter for a line from
+/// `top` down `height` points.
+pub(crate) fn draw_change_marker(
+    vger: &mut Vger,
+    change: LineChange,
+    x: f32,
+    top: f32,
+    height: f32,
+) {
+    match change {
+        LineChange::Added | LineChange::Modified => {
+            let color = if change == LineChange::Added {
+                GREEN_HIGHLIGHT
+            } else {
+                AZURE_HIGHLIGHT
+            };
+            let paint = vger.color_paint(color);
+            vger.fill_rect(
+                LocalRect::new(
+                    [x, top - height].into(),
+                    [CHANGE_MARKER_WIDTH, height].into(),
+                ),
+                0.0,
+                paint,
+            );
+        }
+        // A notch where the lines were.
+        LineChange::Deleted => {
+            let paint = vger.color_paint(RED_HIGHLIGHT);
+            vger.fill_rect(
+                LocalRect::new(
+                    [x, top - 2.0].into(),
+                    [CHANGE_MARKER_WIDTH * 2.0, 4.0].into(),
+                ),
+                0.0,
+                paint,
+            );
+        }
+    }
+}
+
+/// Draws a line number right-aligned to `right`, with its top at `top`.
+pub(crate) fn draw_line_number(vger: &mut Vger, number: usize, right: f32, top: f32) {
+    let s = number.to_string();
+    let bounds = vger.text_bounds(&s, LINE_NUMBER_SIZE, None);
+    vger.save();
+    vger.translate([right - bounds.width(), top - 2.0]);
+    vger.text(&s, LINE_NUMBER_SIZE, MEDIUM_GRAY, None);
+    vger.restore();
+}
+
+/// How wide line numbers up to `lines` need to be.
+pub(crate) fn line_number_width(lines: usize) -> f32 {
+    lines.max(1).to_string().len() as f32 * 9.0 + 8.0
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let base = "a\nb\nc\nd";
+        assert_eq!(diff_lines(base, base), vec![]);
+        assert_eq!(
+            diff_lines(base, "a\nB\nc\nnew\nd"),
+            vec![(1, LineChange::Modified), (3, LineChange::Added)]
+        );
+        assert_eq!(diff_lines(base, "a\nd"), vec![(1, LineChange::Deleted)]);
+    }
+}
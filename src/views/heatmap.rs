@@ -0,0 +1,398 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Width of the row labels, when there are some.
+const ROW_LABEL_WIDTH: f32 = 60.0;
+/// Height of the column labels, when there are some.
+const COLUMN_LABEL_HEIGHT: f32 = 20.0;
+const LABEL_SIZE: u32 = 11;
+const TOOLTIP_SIZE: u32 = 12;
+
+/// Colors for values, from the lowest to the highest, for `heatmap`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorScale {
+    colors: Vec<Color>,
+}
+
+impl ColorScale {
+    /// Blends between `colors`, spaced evenly from the lowest value to
+    /// the highest.
+    pub fn new(colors: impl Into<Vec<Color>>) -> Self {
+        Self {
+            colors: colors.into(),
+        }
+    }
+
+    /// The color `t` of the way up the scale, from 0 to 1.
+    pub fn color(&self, t: f32) -> Color {
+        let n = self.colors.len();
+        if n < 2 {
+            return self.colors.first().copied().unwrap_or(CLEAR_COLOR);
+        }
+        let x = t.clamp(0.0, 1.0) * (n - 1) as f32;
+        let i = (x.floor() as usize).min(n - 2);
+        let (a, b, f) = (self.colors[i], self.colors[i + 1], x - i as f32);
+        Color::new(
+            a.r + (b.r - a.r) * f,
+            a.g + (b.g - a.g) * f,
+            a.b + (b.b - a.b) * f,
+            a.a + (b.a - a.a) * f,
+        )
+    }
+}
+
+impl Default for ColorScale {
+    /// From the control background, through azure, to red.
+    fn default() -> Self {
+        Self::new(vec![CONTROL_BACKGROUND, AZURE_HIGHLIGHT, RED_HIGHLIGHT])
+    }
+}
+
+/// Options for `heatmap`. Set them with `HeatmapMods`.
+#[derive(Clone, Default)]
+pub struct HeatmapOptions {
+    row_labels: Vec<String>,
+    column_labels: Vec<String>,
+    range: Option<Range<f32>>,
+}
+
+impl HeatmapOptions {
+    /// Everything drawing depends on, for `cached_canvas`.
+    fn key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.row_labels.hash(&mut hasher);
+        self.column_labels.hash(&mut hasher);
+        self.range
+            .as_ref()
+            .map(|r| (r.start.to_bits(), r.end.to_bits()))
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+pub trait HeatmapMods: View + Sized {
+    /// Labels the rows, from the top, down the left side.
+    fn row_labels(self, labels: impl IntoIterator<Item = impl ToString>) -> Self;
+
+    /// Labels the columns, from the left, along the top.
+    fn column_labels(self, labels: impl IntoIterator<Item = impl ToString>) -> Self;
+
+    /// Maps `range` to the ends of the color scale, rather than the
+    /// lowest and highest values. Values outside it get the end colors.
+    fn range(self, range: Range<f32>) -> Self;
+}
+
+impl<F> HeatmapMods for ModView<HeatmapOptions, F>
+where
+    ModView<HeatmapOptions, F>: View,
+{
+    fn row_labels(self, labels: impl IntoIterator<Item = impl ToString>) -> Self {
+        let mut opts = self.value;
+        opts.row_labels = labels.into_iter().map(|l| l.to_string()).collect();
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn column_labels(self, labels: impl IntoIterator<Item = impl ToString>) -> Self {
+        let mut opts = self.value;
+        opts.column_labels = labels.into_iter().map(|l| l.to_string()).collect();
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn range(self, range: Range<f32>) -> Self {
+        let mut opts = self.value;
+        opts.range = Some(range);
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+/// Where the cells of a heatmap go.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct HeatmapGeometry {
+    grid: LocalRect,
+    rows: usize,
+    columns: usize,
+}
+
+impl HeatmapGeometry {
+    fn new(rect: LocalRect, matrix: &[Vec<f32>], opts: &HeatmapOptions) -> Self {
+        let left = if opts.row_labels.is_empty() {
+            0.0
+        } else {
+            ROW_LABEL_WIDTH
+        };
+        let top = if opts.column_labels.is_empty() {
+            0.0
+        } else {
+            COLUMN_LABEL_HEIGHT
+        };
+        Self {
+            grid: LocalRect::new(
+                [rect.min_x() + left, rect.min_y()].into(),
+                [
+                    (rect.width() - left).max(0.0),
+                    (rect.height() - top).max(0.0),
+                ]
+                .into(),
+            ),
+            rows: matrix.len(),
+            columns: matrix.iter().map(|row| row.len()).max().unwrap_or(0),
+        }
+    }
+
+    fn cell_size(&self) -> LocalSize {
+        LocalSize::new(
+            self.grid.width() / self.columns.max(1) as f32,
+            self.grid.height() / self.rows.max(1) as f32,
+        )
+    }
+
+    /// The cell at `row` and `column`, counting from the top left.
+    fn cell(&self, row: usize, column: usize) -> LocalRect {
+        let size = self.cell_size();
+        LocalRect::new(
+            [
+                self.grid.min_x() + column as f32 * size.width,
+                self.grid.max_y() - (row + 1) as f32 * size.height,
+            ]
+            .into(),
+            size,
+        )
+    }
+
+    /// The row and column at `p`.
+    fn hit(&self, p: LocalPoint) -> Option<(usize, usize)> {
+        if !self.grid.contains(p) || self.rows == 0 || self.columns == 0 {
+            return None;
+        }
+        let size = self.cell_size();
+        let column = ((p.x - self.grid.min_x()) / size.width) as usize;
+        let row = ((self.grid.max_y() - p.y) / size.height) as usize;
+        Some((row.min(self.rows - 1), column.min(self.columns - 1)))
+    }
+}
+
+/// The range of the finite values, or `0..1` if there aren't any.
+fn value_range(matrix: &[Vec<f32>]) -> Range<f32> {
+    let finite = matrix.iter().flatten().filter(|v| v.is_finite());
+    let min = finite.clone().fold(f32::INFINITY, |a, b| a.min(*b));
+    let max = finite.fold(f32::NEG_INFINITY, |a, b| a.max(*b));
+    if min > max {
+        0.0..1.0
+    } else {
+        min..max
+    }
+}
+
+fn draw_cells(
+    matrix: &[Vec<f32>],
+    scale: &ColorScale,
+    rect: LocalRect,
+    opts: &HeatmapOptions,
+    list: &mut DrawList,
+) {
+    let geom = HeatmapGeometry::new(rect, matrix, opts);
+    let range = opts.range.clone().unwrap_or_else(|| value_range(matrix));
+    let span = range.end - range.start;
+    for (r, row) in matrix.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            if !value.is_finite() {
+                continue;
+            }
+            let t = if span == 0.0 {
+                0.5
+            } else {
+                (value - range.start) / span
+            };
+            list.fill_rect(geom.cell(r, c), 0.0, scale.color(t));
+        }
+    }
+
+    for (r, label) in opts.row_labels.iter().enumerate().take(geom.rows) {
+        let cell = geom.cell(r, 0);
+        list.text(
+            label,
+            [
+                rect.min_x() + 2.0,
+                cell.center().y - LABEL_SIZE as f32 / 3.0,
+            ],
+            LABEL_SIZE,
+            TEXT_COLOR,
+        );
+    }
+    for (c, label) in opts.column_labels.iter().enumerate().take(geom.columns) {
+        let cell = geom.cell(0, c);
+        list.text(
+            label,
+            [cell.min_x() + 2.0, rect.max_y() - COLUMN_LABEL_HEIGHT + 6.0],
+            LABEL_SIZE,
+            TEXT_COLOR,
+        );
+    }
+}
+
+/// What the tooltip says for a cell.
+fn tooltip(
+    matrix: &[Vec<f32>],
+    opts: &HeatmapOptions,
+    row: usize,
+    column: usize,
+) -> Option<String> {
+    let value = *matrix.get(row)?.get(column)?;
+    let row = opts
+        .row_labels
+        .get(row)
+        .cloned()
+        .unwrap_or_else(|| row.to_string());
+    let column = opts
+        .column_labels
+        .get(column)
+        .cloned()
+        .unwrap_or_else(|| column.to_string());
+    Some(format!("{}, {}: {}", row, column, value))
+}
+
+#[derive(Default)]
+struct HeatmapState {
+    hover: Option<LocalPoint>,
+}
+
+/// Shows `matrix` as a grid of cells colored by `color_scale`, for seeing
+/// patterns in dense data like correlations, activity by hour or
+/// histograms in two dimensions. Rows go down from the top. Hovering a
+/// cell shows its value. Each cell is one rectangle in vger's batch, and
+/// the cells are only rebuilt when the data, options or size change, so
+/// thousands of them stay cheap.
+///
+/// ```no_run
+/// # use rui::*;
+/// let matrix: Vec<Vec<f32>> = (0..7)
+///     .map(|day| (0..24).map(|hour| ((day * hour) % 10) as f32).collect())
+///     .collect();
+/// heatmap(matrix, ColorScale::default())
+///     .row_labels(["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"])
+///     .column_labels((0..24).map(|h| if h % 6 == 0 { h.to_string() } else { String::new() }));
+/// ```
+pub fn heatmap(matrix: Vec<Vec<f32>>, color_scale: ColorScale) -> impl HeatmapMods {
+    let mut hasher = DefaultHasher::new();
+    for row in &matrix {
+        row.len().hash(&mut hasher);
+        for v in row {
+            v.to_bits().hash(&mut hasher);
+        }
+    }
+    for c in &color_scale.colors {
+        [c.r, c.g, c.b, c.a].map(f32::to_bits).hash(&mut hasher);
+    }
+    let data = hasher.finish();
+    let matrix = Rc::new(matrix);
+    let scale = Rc::new(color_scale);
+
+    modview(move |opts: HeatmapOptions, _| {
+        let (matrix, scale) = (matrix.clone(), scale.clone());
+        state(HeatmapState::default, move |s, _| {
+            let (cells, opts_cells, scale) = (matrix.clone(), opts.clone(), scale.clone());
+            let (hovered, opts) = (matrix.clone(), opts.clone());
+            zstack((
+                cached_canvas((data, opts_cells.key()), move |_, rect, list| {
+                    draw_cells(&cells, &scale, rect, &opts_cells, list)
+                }),
+                canvas(move |cx, rect, vger| {
+                    let p = match cx[s].hover {
+                        Some(p) => p,
+                        None => return,
+                    };
+                    let geom = HeatmapGeometry::new(rect, &hovered, &opts);
+                    let (row, column) = match geom.hit(p) {
+                        Some(hit) => hit,
+                        None => return,
+                    };
+                    let text = match tooltip(&hovered, &opts, row, column) {
+                        Some(text) => text,
+                        None => return,
+                    };
+
+                    let cell = geom.cell(row, column);
+                    let paint = vger.color_paint(TEXT_COLOR);
+                    vger.stroke_rect(cell.min(), cell.max(), 0.0, 1.0, paint);
+
+                    // Above and to the right of the mouse, kept inside.
+                    let bounds = vger.text_bounds(&text, TOOLTIP_SIZE, None);
+                    let size = bounds.size + LocalSize::new(12.0, 8.0);
+                    let x = (p.x + 8.0).min(rect.max_x() - size.width).max(rect.min_x());
+                    let y = (p.y + 8.0)
+                        .min(rect.max_y() - size.height)
+                        .max(rect.min_y());
+                    let tip = LocalRect::new([x, y].into(), size);
+                    let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+                    vger.fill_rect(tip, 4.0, paint);
+                    vger.text_at(
+                        &text,
+                        tip.min() + LocalVector::new(6.0, 4.0) - bounds.min().to_vector(),
+                        TOOLTIP_SIZE,
+                        TEXT_COLOR,
+                    );
+                }),
+            ))
+            .hover_p(move |cx, p| cx[s].hover = Some(p))
+            .hover(move |cx, inside| {
+                if !inside {
+                    cx[s].hover = None;
+                }
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_color_scale() {
+        let scale = ColorScale::new(vec![BLACK, Color::WHITE]);
+        assert_eq!(scale.color(0.0), BLACK);
+        assert_eq!(scale.color(1.0), Color::WHITE);
+        assert_eq!(scale.color(2.0), Color::WHITE);
+        let mid = scale.color(0.5);
+        assert!((mid.r - 0.5).abs() < 1e-6);
+        assert_eq!(ColorScale::new(vec![]).color(0.5), CLEAR_COLOR);
+    }
+
+    #[test]
+    fn test_heatmap_geometry() {
+        let matrix = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0]];
+        let opts = HeatmapOptions {
+            row_labels: vec!["a".into(), "b".into()],
+            ..Default::default()
+        };
+        let rect = LocalRect::new(LocalPoint::zero(), [360.0, 100.0].into());
+        let geom = HeatmapGeometry::new(rect, &matrix, &opts);
+        assert_eq!((geom.rows, geom.columns), (2, 3));
+        assert_eq!(
+            geom.cell(0, 0),
+            LocalRect::new([60.0, 50.0].into(), [100.0, 50.0].into())
+        );
+        assert_eq!(geom.hit([65.0, 90.0].into()), Some((0, 0)));
+        assert_eq!(geom.hit([350.0, 10.0].into()), Some((1, 2)));
+        assert_eq!(geom.hit([10.0, 10.0].into()), None);
+
+        assert_eq!(tooltip(&matrix, &opts, 1, 0), Some("b, 0: 4".into()));
+        // Rows can be short.
+        assert_eq!(tooltip(&matrix, &opts, 1, 2), None);
+        assert_eq!(value_range(&matrix), 1.0..5.0);
+        assert_eq!(value_range(&[vec![f32::NAN]]), 0.0..1.0);
+    }
+}
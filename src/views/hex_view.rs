@@ -0,0 +1,713 @@
+use crate::*;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// Bytes shown by `hex_view`. Only the rows on screen are read, so data
+/// can be paged in from a file or device of any size.
+pub trait HexData {
+    fn len(&self) -> u64;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Reads bytes starting at `offset` into `buf`, returning how many were
+    /// read.
+    fn read(&self, offset: u64, buf: &mut [u8]) -> usize;
+}
+
+impl HexData for [u8] {
+    fn len(&self) -> u64 {
+        <[u8]>::len(self) as u64
+    }
+
+    fn read(&self, offset: u64, buf: &mut [u8]) -> usize {
+        let start = (offset.min(HexData::len(self))) as usize;
+        let n = buf.len().min(<[u8]>::len(self) - start);
+        buf[..n].copy_from_slice(&self[start..start + n]);
+        n
+    }
+}
+
+impl HexData for Vec<u8> {
+    fn len(&self) -> u64 {
+        HexData::len(self.as_slice())
+    }
+
+    fn read(&self, offset: u64, buf: &mut [u8]) -> usize {
+        HexData::read(self.as_slice(), offset, buf)
+    }
+}
+
+impl HexData for &'static [u8] {
+    fn len(&self) -> u64 {
+        HexData::len(*self)
+    }
+
+    fn read(&self, offset: u64, buf: &mut [u8]) -> usize {
+        HexData::read(*self, offset, buf)
+    }
+}
+
+/// A file read on demand for `hex_view`, so it needn't fit in memory.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct HexFile {
+    file: std::cell::RefCell<std::fs::File>,
+    len: u64,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HexFile {
+    pub fn open(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            file: std::cell::RefCell::new(file),
+            len,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HexData for HexFile {
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    fn read(&self, offset: u64, buf: &mut [u8]) -> usize {
+        use std::io::{Read, Seek, SeekFrom};
+        let mut file = self.file.borrow_mut();
+        if file.seek(SeekFrom::Start(offset)).is_err() {
+            return 0;
+        }
+        let mut read = 0;
+        while read < buf.len() {
+            match file.read(&mut buf[read..]) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => read += n,
+            }
+        }
+        read
+    }
+}
+
+const BYTES_PER_ROW: u64 = 16;
+const ROW_HEIGHT: f32 = 20.0;
+const FONT_SIZE: u32 = 14;
+const DIGIT_WIDTH: f32 = 9.0;
+const HEX_CELL: f32 = 24.0;
+/// Between the two groups of eight bytes.
+const GROUP_GAP: f32 = 8.0;
+const ASCII_CELL: f32 = 10.0;
+const COLUMN_GAP: f32 = 16.0;
+/// Copying more than this copies only this much.
+const MAX_COPY: u64 = 1 << 20;
+
+/// The column of a `hex_view` a selection was made in, which decides how
+/// it's copied and what typing does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HexColumn {
+    Hex,
+    Ascii,
+}
+
+/// Where the columns start, for data of `len` bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct HexLayout {
+    offset_digits: usize,
+    hex_x: f32,
+    ascii_x: f32,
+}
+
+impl HexLayout {
+    fn new(len: u64) -> Self {
+        let offset_digits = if len > u32::MAX as u64 { 16 } else { 8 };
+        let hex_x = offset_digits as f32 * DIGIT_WIDTH + COLUMN_GAP;
+        let ascii_x = hex_x + BYTES_PER_ROW as f32 * HEX_CELL + GROUP_GAP + COLUMN_GAP;
+        Self {
+            offset_digits,
+            hex_x,
+            ascii_x,
+        }
+    }
+
+    fn hex_cell_x(&self, i: u64) -> f32 {
+        let gap = if i >= BYTES_PER_ROW / 2 {
+            GROUP_GAP
+        } else {
+            0.0
+        };
+        self.hex_x + i as f32 * HEX_CELL + gap
+    }
+
+    fn ascii_cell_x(&self, i: u64) -> f32 {
+        self.ascii_x + i as f32 * ASCII_CELL
+    }
+
+    /// The column and byte within a row at `x`, or the nearest byte.
+    fn cell_at(&self, x: f32) -> (HexColumn, u64) {
+        let last = BYTES_PER_ROW - 1;
+        if x >= self.ascii_x - COLUMN_GAP / 2.0 {
+            let i = ((x - self.ascii_x) / ASCII_CELL).max(0.0) as u64;
+            (HexColumn::Ascii, i.min(last))
+        } else {
+            let i = (0..BYTES_PER_ROW)
+                .rev()
+                .find(|&i| x >= self.hex_cell_x(i))
+                .unwrap_or(0);
+            (HexColumn::Hex, i)
+        }
+    }
+}
+
+/// The byte as shown in the ASCII column.
+fn ascii(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+/// Options for `hex_view`. Set them with `HexViewMods`.
+#[derive(Clone, Default)]
+pub struct HexViewOptions {
+    editable: bool,
+    on_edit: Option<Rc<dyn Fn(&mut Context, u64, u8)>>,
+}
+
+pub trait HexViewMods: View + Sized {
+    /// Lets bytes be typed over, in hex or as ASCII, depending on the
+    /// column.
+    fn editable(self, editable: bool) -> Self;
+    /// Calls `f` with the offset and new value of each byte changed, and
+    /// changed back by undo.
+    fn on_edit(self, f: impl Fn(&mut Context, u64, u8) + 'static) -> Self;
+}
+
+impl<F> HexViewMods for ModView<HexViewOptions, F>
+where
+    ModView<HexViewOptions, F>: View,
+{
+    fn editable(self, editable: bool) -> Self {
+        let mut opts = self.value;
+        opts.editable = editable;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_edit(self, f: impl Fn(&mut Context, u64, u8) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_edit = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct HexViewState {
+    /// The first row shown. Rows, rather than points, so scrolling stays
+    /// exact through gigabytes.
+    top: u64,
+    /// Scrolling not yet amounting to a row.
+    scroll_frac: f32,
+    /// Rows which fit, as of the last draw.
+    rows_shown: u64,
+    height: f32,
+    anchor: u64,
+    cursor: u64,
+    column: HexColumn,
+    /// The high nibble typed at the cursor, waiting for the low one.
+    nibble: Option<u8>,
+    /// Bytes as edited, over the data.
+    edits: BTreeMap<u64, u8>,
+    /// Each edit, and what the byte was before it, if edited.
+    undo: Vec<(u64, Option<u8>)>,
+}
+
+impl Default for HexViewState {
+    fn default() -> Self {
+        Self {
+            top: 0,
+            scroll_frac: 0.0,
+            rows_shown: 1,
+            height: 0.0,
+            anchor: 0,
+            cursor: 0,
+            column: HexColumn::Hex,
+            nibble: None,
+            edits: BTreeMap::new(),
+            undo: vec![],
+        }
+    }
+}
+
+impl HexViewState {
+    fn selection(&self) -> std::ops::Range<u64> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor) + 1
+    }
+
+    /// Reads from the data with the edits applied.
+    fn read(&self, data: &dyn HexData, offset: u64, buf: &mut [u8]) -> usize {
+        let n = data.read(offset, buf);
+        for (&at, &byte) in self.edits.range(offset..offset + n as u64) {
+            buf[(at - offset) as usize] = byte;
+        }
+        n
+    }
+
+    fn rows(len: u64) -> u64 {
+        len.div_ceil(BYTES_PER_ROW)
+    }
+
+    fn scroll_by(&mut self, rows: i64, len: u64) {
+        let max = Self::rows(len).saturating_sub(self.rows_shown);
+        self.top = (self.top as i64 + rows).clamp(0, max as i64) as u64;
+    }
+
+    /// Scrolls so the cursor's row shows.
+    fn reveal(&mut self) {
+        let row = self.cursor / BYTES_PER_ROW;
+        if row < self.top {
+            self.top = row;
+        } else if row >= self.top + self.rows_shown {
+            self.top = row + 1 - self.rows_shown;
+        }
+    }
+
+    fn move_to(&mut self, at: u64, select: bool) {
+        self.cursor = at;
+        if !select {
+            self.anchor = at;
+        }
+        self.nibble = None;
+        self.reveal();
+    }
+
+    /// Moves the cursor by `by` bytes, staying within the data.
+    fn step(&mut self, by: i64, select: bool, len: u64) {
+        let at = (self.cursor as i64 + by).clamp(0, len.saturating_sub(1) as i64);
+        self.move_to(at as u64, select);
+    }
+
+    /// Changes the byte at `at`, returning the edit to report.
+    fn set(&mut self, data: &dyn HexData, at: u64, byte: u8) -> (u64, u8) {
+        let before = self.edits.insert(at, byte);
+        self.undo.push((at, before));
+        let mut original = [0];
+        data.read(at, &mut original);
+        if original[0] == byte {
+            self.edits.remove(&at);
+        }
+        (at, byte)
+    }
+
+    /// Types `c` over the byte at the cursor: a hex digit in the hex
+    /// column, taking two to make a byte, or a character in the ASCII
+    /// column.
+    fn type_char(&mut self, data: &dyn HexData, c: char) -> Option<(u64, u8)> {
+        let at = self.cursor;
+        if at >= data.len() {
+            return None;
+        }
+        let mut current = [0];
+        self.read(data, at, &mut current);
+        match self.column {
+            HexColumn::Hex => {
+                let digit = c.to_digit(16)? as u8;
+                match self.nibble.take() {
+                    None => {
+                        self.nibble = Some(digit);
+                        Some(self.set(data, at, (digit << 4) | (current[0] & 0x0F)))
+                    }
+                    Some(high) => {
+                        let edit = self.set(data, at, (high << 4) | digit);
+                        self.step(1, false, data.len());
+                        Some(edit)
+                    }
+                }
+            }
+            HexColumn::Ascii => {
+                if !c.is_ascii() || c.is_ascii_control() {
+                    return None;
+                }
+                let edit = self.set(data, at, c as u8);
+                self.step(1, false, data.len());
+                Some(edit)
+            }
+        }
+    }
+
+    /// Takes back the last edit, returning the byte as it's now shown.
+    fn undo(&mut self, data: &dyn HexData) -> Option<(u64, u8)> {
+        let (at, before) = self.undo.pop()?;
+        match before {
+            Some(byte) => self.edits.insert(at, byte),
+            None => self.edits.remove(&at),
+        };
+        self.nibble = None;
+        self.move_to(at, false);
+        let mut byte = [0];
+        self.read(data, at, &mut byte);
+        Some((at, byte[0]))
+    }
+
+    /// The selection as text: hex pairs, or characters from the ASCII
+    /// column.
+    fn copy_text(&self, data: &dyn HexData) -> String {
+        let range = self.selection();
+        let len = (range.end.min(data.len()) - range.start).min(MAX_COPY);
+        let mut bytes = vec![0; len as usize];
+        let n = self.read(data, range.start, &mut bytes);
+        bytes.truncate(n);
+        match self.column {
+            HexColumn::Hex => bytes
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect::<Vec<_>>()
+                .join(" "),
+            HexColumn::Ascii => bytes.iter().map(|&b| ascii(b)).collect(),
+        }
+    }
+}
+
+/// Draws `s` at `x`, with the top of the row at `top`.
+fn draw_text(vger: &mut Vger, s: &str, color: Color, x: f32, top: f32) {
+    vger.save();
+    vger.translate([x, top - 2.0]);
+    vger.text(s, FONT_SIZE, color, None);
+    vger.restore();
+}
+
+fn hex_canvas(
+    data: Rc<dyn HexData>,
+    state: StateHandle<HexViewState>,
+    has_focus: bool,
+) -> impl View {
+    let (drag_data, scroll_data) = (data.clone(), data.clone());
+    canvas(move |cx, rect, vger| {
+        let len = data.len();
+        let layout = HexLayout::new(len);
+        let rows_shown = ((rect.height() / ROW_HEIGHT).floor() as u64).max(1);
+        cx[state].rows_shown = rows_shown;
+        cx[state].height = rect.height();
+        // Keep the last rows at the bottom after resizing.
+        cx[state].scroll_by(0, len);
+
+        let s = &cx[state];
+        let selection = s.selection();
+        let mut buf = [0; BYTES_PER_ROW as usize];
+        let last = (s.top + rows_shown + 1).min(HexViewState::rows(len));
+        for row in s.top..last {
+            let top = rect.height() - (row - s.top) as f32 * ROW_HEIGHT;
+            let offset = row * BYTES_PER_ROW;
+            let n = s.read(&*data, offset, &mut buf) as u64;
+
+            let label = format!("{:0width$X}", offset, width = layout.offset_digits);
+            draw_text(vger, &label, MEDIUM_GRAY, 0.0, top);
+
+            for i in 0..n {
+                let at = offset + i;
+                let byte = buf[i as usize];
+                let hex_x = layout.hex_cell_x(i);
+                let ascii_x = layout.ascii_cell_x(i);
+                if selection.contains(&at) {
+                    let color = if has_focus {
+                        AZURE_HIGHLIGHT_BACKGROUND
+                    } else {
+                        CONTROL_BACKGROUND
+                    };
+                    let paint = vger.color_paint(color);
+                    let cell = |x: f32, width: f32| {
+                        LocalRect::new(
+                            [x - 2.0, top - ROW_HEIGHT].into(),
+                            [width, ROW_HEIGHT].into(),
+                        )
+                    };
+                    vger.fill_rect(cell(hex_x, HEX_CELL), 0.0, paint);
+                    vger.fill_rect(cell(ascii_x, ASCII_CELL), 0.0, paint);
+                }
+                let color = if s.edits.contains_key(&at) {
+                    AZURE_HIGHLIGHT
+                } else {
+                    TEXT_COLOR
+                };
+                draw_text(vger, &format!("{:02X}", byte), color, hex_x, top);
+                let mut c = [0; 4];
+                draw_text(vger, ascii(byte).encode_utf8(&mut c), color, ascii_x, top);
+            }
+
+            // The cursor, in the column typing goes to.
+            if has_focus && (offset..offset + BYTES_PER_ROW).contains(&s.cursor) {
+                let i = s.cursor - offset;
+                let (x, width) = match s.column {
+                    HexColumn::Hex => (layout.hex_cell_x(i), HEX_CELL),
+                    HexColumn::Ascii => (layout.ascii_cell_x(i), ASCII_CELL),
+                };
+                let paint = vger.color_paint(AZURE_HIGHLIGHT);
+                let underline =
+                    LocalRect::new([x - 2.0, top - ROW_HEIGHT].into(), [width, 2.0].into());
+                vger.fill_rect(underline, 0.0, paint);
+            }
+        }
+
+        let paint = vger.color_paint(GROOVES);
+        for x in [layout.hex_x, layout.ascii_x] {
+            let x = x - COLUMN_GAP / 2.0;
+            vger.fill_rect(
+                LocalRect::new([x, 0.0].into(), [1.0, rect.height()].into()),
+                0.0,
+                paint,
+            );
+        }
+    })
+    .clip()
+    .drag_p(move |cx, p, gesture, _| {
+        let len = drag_data.len();
+        if len == 0 {
+            return;
+        }
+        let layout = HexLayout::new(len);
+        // Rows go down from the top of the canvas.
+        let row = cx[state].top + ((cx[state].height - p.y) / ROW_HEIGHT).max(0.0) as u64;
+        let (column, i) = layout.cell_at(p.x);
+        let at = (row * BYTES_PER_ROW + i).min(len - 1);
+        let select = gesture != GestureState::Began || cx.key_mods.shift;
+        if gesture == GestureState::Began {
+            cx[state].column = column;
+        }
+        cx[state].move_to(at, select);
+    })
+    .scroll(move |cx, delta| {
+        let s = &mut cx[state];
+        s.scroll_frac += delta.y / ROW_HEIGHT;
+        let rows = s.scroll_frac.trunc();
+        s.scroll_frac -= rows;
+        s.scroll_by(rows as i64, scroll_data.len());
+    })
+}
+
+/// Shows bytes in rows of sixteen, with their offset, in hex, and as
+/// ASCII. Only the rows on screen are read from the `HexData`, so it
+/// scrolls through files of many gigabytes, such as with `HexFile`.
+///
+/// Click and drag, or shift and the arrow keys, to select, and the
+/// shortcut for copy copies the selection as hex or text, depending on
+/// the column clicked. With `HexViewMods::editable`, typing overwrites
+/// bytes. Changed bytes are highlighted and reported to
+/// `HexViewMods::on_edit`, and undo takes them back. The data itself
+/// isn't written.
+///
+/// ```no_run
+/// # use rui::*;
+/// hex_view(b"Hello, world!\n".to_vec())
+///     .editable(true)
+///     .on_edit(|_, offset, byte| println!("{:X}: {:02X}", offset, byte));
+/// ```
+pub fn hex_view(data: impl HexData + 'static) -> impl HexViewMods {
+    let data: Rc<dyn HexData> = Rc::new(data);
+    modview(move |opts: HexViewOptions, _| {
+        let data = data.clone();
+        focus(move |has_focus| {
+            let data = data.clone();
+            let opts = opts.clone();
+            state(HexViewState::default, move |state, _| {
+                let key_data = data.clone();
+                let opts = opts.clone();
+                hex_canvas(data.clone(), state, has_focus).key(move |cx, k| {
+                    if !has_focus {
+                        return;
+                    }
+                    let data = &*key_data;
+                    let len = data.len();
+                    let select = cx.key_mods.shift;
+                    let page = cx[state].rows_shown as i64 * BYTES_PER_ROW as i64;
+                    let hotkey = match &k {
+                        Key::Character(c) if cx.key_mods.shortcut() => HotKey::from_char(*c),
+                        _ => None,
+                    };
+                    let edit = match (hotkey, &k) {
+                        (Some(HotKey::KeyC), _) => {
+                            let s = cx[state].copy_text(data);
+                            cx.clipboard().set_text(&s);
+                            None
+                        }
+                        (Some(HotKey::KeyA), _) => {
+                            cx[state].anchor = 0;
+                            cx[state].cursor = len.saturating_sub(1);
+                            None
+                        }
+                        (Some(HotKey::KeyZ), _) if opts.editable => cx[state].undo(data),
+                        (Some(_), _) => None,
+                        (None, Key::ArrowLeft) => {
+                            cx[state].step(-1, select, len);
+                            None
+                        }
+                        (None, Key::ArrowRight) => {
+                            cx[state].step(1, select, len);
+                            None
+                        }
+                        (None, Key::ArrowUp) => {
+                            cx[state].step(-(BYTES_PER_ROW as i64), select, len);
+                            None
+                        }
+                        (None, Key::ArrowDown) => {
+                            cx[state].step(BYTES_PER_ROW as i64, select, len);
+                            None
+                        }
+                        (None, Key::PageUp) => {
+                            cx[state].step(-page, select, len);
+                            None
+                        }
+                        (None, Key::PageDown) => {
+                            cx[state].step(page, select, len);
+                            None
+                        }
+                        (None, Key::Home) => {
+                            cx[state].move_to(0, select);
+                            None
+                        }
+                        (None, Key::End) => {
+                            cx[state].move_to(len.saturating_sub(1), select);
+                            None
+                        }
+                        (None, Key::Tab) => {
+                            cx[state].column = match cx[state].column {
+                                HexColumn::Hex => HexColumn::Ascii,
+                                HexColumn::Ascii => HexColumn::Hex,
+                            };
+                            cx[state].nibble = None;
+                            None
+                        }
+                        (None, Key::Character(c)) if opts.editable => cx[state].type_char(data, *c),
+                        (None, Key::Space) if opts.editable => cx[state].type_char(data, ' '),
+                        _ => None,
+                    };
+                    if let (Some((at, byte)), Some(f)) = (edit, &opts.on_edit) {
+                        f(cx, at, byte);
+                    }
+                })
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_hex_layout() {
+        let layout = HexLayout::new(100);
+        assert_eq!(layout.offset_digits, 8);
+        assert_eq!(HexLayout::new(1 << 33).offset_digits, 16);
+
+        assert_eq!(layout.cell_at(0.0), (HexColumn::Hex, 0));
+        assert_eq!(
+            layout.cell_at(layout.hex_cell_x(9) + 1.0),
+            (HexColumn::Hex, 9)
+        );
+        assert_eq!(
+            layout.cell_at(layout.ascii_cell_x(3) + 1.0),
+            (HexColumn::Ascii, 3)
+        );
+        assert_eq!(
+            layout.cell_at(layout.ascii_cell_x(20)),
+            (HexColumn::Ascii, 15)
+        );
+    }
+
+    #[test]
+    fn test_hex_edit() {
+        let data = b"abcd".to_vec();
+        let mut state = HexViewState::default();
+        state.move_to(1, false);
+
+        assert_eq!(state.type_char(&data, 'f'), Some((1, 0xF2)));
+        assert_eq!(state.cursor, 1);
+        assert_eq!(state.type_char(&data, '0'), Some((1, 0xF0)));
+        assert_eq!(state.cursor, 2);
+        assert_eq!(state.type_char(&data, 'x'), None);
+
+        state.column = HexColumn::Ascii;
+        assert_eq!(state.type_char(&data, 'Z'), Some((2, b'Z')));
+
+        let mut buf = [0; 4];
+        state.read(&data, 0, &mut buf);
+        assert_eq!(&buf, b"a\xF0Zd");
+        assert_eq!(state.edits.len(), 2);
+
+        assert_eq!(state.undo(&data), Some((2, b'c')));
+        assert_eq!(state.undo(&data), Some((1, 0xF2)));
+        assert_eq!(state.undo(&data), Some((1, b'b')));
+        assert!(state.edits.is_empty());
+    }
+
+    #[test]
+    fn test_hex_copy() {
+        let data = b"Hi\x00!".to_vec();
+        let mut state = HexViewState::default();
+        state.move_to(0, false);
+        state.move_to(2, true);
+        assert_eq!(state.copy_text(&data), "48 69 00");
+        state.column = HexColumn::Ascii;
+        assert_eq!(state.copy_text(&data), "Hi.");
+    }
+
+    #[test]
+    fn test_hex_events() {
+        let mut cx = Context::new();
+        let ui = hex_view(vec![0u8; 64]);
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [600.0, 40.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        // Drawing would record what fits: two of the four rows.
+        let hex = StateHandle::<HexViewState>::new(cx.view_id(&vec![0, 0, 0]));
+        cx[hex].height = 40.0;
+        cx[hex].rows_shown = 2;
+
+        // From the second byte of the first row to the third of the second.
+        let layout = HexLayout::new(64);
+        let from = LocalPoint::new(layout.hex_cell_x(1) + 1.0, 30.0);
+        let to = LocalPoint::new(layout.hex_cell_x(2) + 1.0, 10.0);
+        let scroll = |dy: f32| Event::Scroll {
+            position: [100.0, 20.0].into(),
+            delta: [0.0, dy].into(),
+        };
+        let events = [
+            Event::TouchBegin {
+                id: 0,
+                position: from,
+            },
+            Event::TouchMove {
+                id: 0,
+                position: to,
+                delta: to - from,
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: to,
+            },
+            scroll(ROW_HEIGHT),
+            scroll(ROW_HEIGHT * 2.5),
+        ];
+        let mut actions = vec![];
+        for event in &events {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+        assert_eq!(cx[hex].selection(), 1..19);
+        // Scrolled a row, then as far as the last rows.
+        assert_eq!(cx[hex].top, 2);
+    }
+}
@@ -2,7 +2,16 @@ use crate::*;
 use std::any::Any;
 
 pub trait HoverFn {
-    fn call(&self, cx: &mut Context, pt: LocalPoint, inside: bool, actions: &mut Vec<Box<dyn Any>>);
+    /// Called as the mouse moves, with `changed` set when it has just
+    /// entered or left the view.
+    fn call(
+        &self,
+        cx: &mut Context,
+        pt: LocalPoint,
+        inside: bool,
+        changed: bool,
+        actions: &mut Vec<Box<dyn Any>>,
+    );
 }
 
 pub struct HoverFuncP<F> {
@@ -15,6 +24,7 @@ impl<A: 'static, F: Fn(&mut Context, LocalPoint) -> A> HoverFn for HoverFuncP<F>
         cx: &mut Context,
         pt: LocalPoint,
         inside: bool,
+        _changed: bool,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
         if inside {
@@ -33,9 +43,12 @@ impl<A: 'static, F: Fn(&mut Context, bool) -> A> HoverFn for HoverFunc<F> {
         cx: &mut Context,
         _pt: LocalPoint,
         inside: bool,
+        changed: bool,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        actions.push(Box::new((self.f)(cx, inside)))
+        if changed {
+            actions.push(Box::new((self.f)(cx, inside)))
+        }
     }
 }
 
@@ -67,11 +80,25 @@ where
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if let Event::TouchMove { position, .. } = &event {
-            if cx.mouse_button.is_none() {
-                let inside = self.hittest(path, *position, cx).is_some();
-                self.func.call(cx, *position, inside, actions);
+        // Whether the mouse was over the view.
+        let vid = cx.view_id(path);
+        let was_inside = StateHandle::<bool>::new(vid);
+        cx.init_state(vid, &|| false);
+
+        let hover = match event {
+            // Moves with a button held are drags, not hovers.
+            Event::Hover { position } if cx.mouse_button.is_none() => {
+                Some((*position, self.hittest(path, *position, cx).is_some()))
             }
+            Event::HoverLeave => Some((LocalPoint::zero(), false)),
+            _ => None,
+        };
+        if let Some((position, inside)) = hover {
+            let changed = cx[was_inside] != inside;
+            if changed {
+                cx[was_inside] = inside;
+            }
+            self.func.call(cx, position, inside, changed, actions);
         }
         path.push(0);
         self.child.process(event, path, cx, actions);
@@ -111,6 +138,7 @@ where
     }
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
         path.push(0);
         self.child.gc(path, cx, map);
         path.pop();
@@ -130,3 +158,84 @@ where
 }
 
 impl<V, F> private::Sealed for Hover<V, F> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_hover() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |s, _| {
+                rectangle()
+                    .size([100.0, 100.0])
+                    .hover(move |cx, inside| cx[s] += if inside { 1 } else { 10 })
+            },
+        );
+        let sz = [200.0, 200.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<i32>::new(cx.view_id(&path));
+
+        let hover = |x, y| Event::Hover {
+            position: [x, y].into(),
+        };
+        let events = [
+            hover(150.0, 150.0),
+            hover(50.0, 50.0),
+            hover(60.0, 60.0),
+            Event::HoverLeave,
+        ];
+        let mut actions = vec![];
+        for event in &events {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+        // Entering and leaving, but not moving around inside.
+        assert_eq!(*s.get(&cx), 11);
+    }
+
+    #[test]
+    fn test_hover_while_dragging() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0,
+            |s, _| {
+                rectangle()
+                    .size([100.0, 100.0])
+                    .hover(move |cx, _| cx[s] += 1)
+            },
+        );
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [200.0, 200.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<i32>::new(cx.view_id(&path));
+
+        cx.mouse_button = Some(MouseButton::Left);
+        let event = Event::Hover {
+            position: [50.0, 50.0].into(),
+        };
+        let mut actions = vec![];
+        ui.process(&event, &mut path, &mut cx, &mut actions);
+        assert_eq!(*s.get(&cx), 0);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::*;
+
+/// A glyph from the icon set bundled with the `icons` feature, for `icon`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Icon {
+    ChevronLeft,
+    ChevronRight,
+    ChevronUp,
+    ChevronDown,
+    Close,
+    Search,
+    Plus,
+    Minus,
+    Check,
+    Menu,
+    More,
+    Info,
+    Warning,
+    Trash,
+    Edit,
+    Copy,
+    Home,
+    Refresh,
+    Play,
+    Pause,
+    Folder,
+    File,
+}
+
+impl Icon {
+    /// Every icon, for pickers and galleries.
+    pub const ALL: &'static [Icon] = &[
+        Icon::ChevronLeft,
+        Icon::ChevronRight,
+        Icon::ChevronUp,
+        Icon::ChevronDown,
+        Icon::Close,
+        Icon::Search,
+        Icon::Plus,
+        Icon::Minus,
+        Icon::Check,
+        Icon::Menu,
+        Icon::More,
+        Icon::Info,
+        Icon::Warning,
+        Icon::Trash,
+        Icon::Edit,
+        Icon::Copy,
+        Icon::Home,
+        Icon::Refresh,
+        Icon::Play,
+        Icon::Pause,
+        Icon::Folder,
+        Icon::File,
+    ];
+
+    /// The shapes inside the icon's `<svg>`, on a 24 point grid. Outlines
+    /// are stroked, and `fill="black"` marks filled parts.
+    fn body(self) -> &'static str {
+        match self {
+            Icon::ChevronLeft => r#"<path d="M15 18l-6-6 6-6"/>"#,
+            Icon::ChevronRight => r#"<path d="M9 18l6-6-6-6"/>"#,
+            Icon::ChevronUp => r#"<path d="M18 15l-6-6-6 6"/>"#,
+            Icon::ChevronDown => r#"<path d="M6 9l6 6 6-6"/>"#,
+            Icon::Close => r#"<path d="M18 6L6 18M6 6l12 12"/>"#,
+            Icon::Search => r#"<circle cx="11" cy="11" r="7"/><path d="M21 21l-5-5"/>"#,
+            Icon::Plus => r#"<path d="M12 5v14M5 12h14"/>"#,
+            Icon::Minus => r#"<path d="M5 12h14"/>"#,
+            Icon::Check => r#"<path d="M5 12l5 5 9-10"/>"#,
+            Icon::Menu => r#"<path d="M4 6h16M4 12h16M4 18h16"/>"#,
+            Icon::More => {
+                r#"<g fill="black" stroke="none"><circle cx="5" cy="12" r="1.5"/><circle cx="12" cy="12" r="1.5"/><circle cx="19" cy="12" r="1.5"/></g>"#
+            }
+            Icon::Info => r#"<circle cx="12" cy="12" r="9"/><path d="M12 16v-5M12 8v.01"/>"#,
+            Icon::Warning => r#"<path d="M12 3L2 20h20z"/><path d="M12 9v5M12 17v.01"/>"#,
+            Icon::Trash => r#"<path d="M3 6h18M9 6V3h6v3M5 6l1 15h12l1-15M10 11v6M14 11v6"/>"#,
+            Icon::Edit => r#"<path d="M4 20h4L19 9l-4-4L4 16z"/><path d="M13 7l4 4"/>"#,
+            Icon::Copy => {
+                r#"<rect x="9" y="9" width="12" height="12" rx="2"/><path d="M5 15H4a1 1 0 0 1-1-1V4a1 1 0 0 1 1-1h10a1 1 0 0 1 1 1v1"/>"#
+            }
+            Icon::Home => r#"<path d="M3 11l9-8 9 8M5 10v10h5v-6h4v6h5V10"/>"#,
+            Icon::Refresh => r#"<path d="M20 12a8 8 0 1 1-2.34-5.66M20 4v4h-4"/>"#,
+            Icon::Play => r#"<path d="M7 4l13 8-13 8z" fill="black"/>"#,
+            Icon::Pause => {
+                r#"<g fill="black" stroke="none"><rect x="6" y="4" width="4" height="16" rx="1"/><rect x="14" y="4" width="4" height="16" rx="1"/></g>"#
+            }
+            Icon::Folder => {
+                r#"<path d="M3 6a1 1 0 0 1 1-1h5l2 2h9a1 1 0 0 1 1 1v10a1 1 0 0 1-1 1H4a1 1 0 0 1-1-1z"/>"#
+            }
+            Icon::File => r#"<path d="M6 3h8l5 5v13H6z"/><path d="M14 3v5h5"/>"#,
+        }
+    }
+
+    /// The icon as an SVG document.
+    pub fn svg(self) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="none" stroke="black" stroke-width="2" stroke-linecap="round" stroke-linejoin="round">{}</svg>"#,
+            self.body()
+        )
+    }
+}
+
+/// Struct for `icon`.
+pub struct IconView {
+    icon: Icon,
+    svg: Svg,
+    size: f32,
+}
+
+impl IconView {
+    pub const DEFAULT_SIZE: f32 = 16.0;
+
+    /// Draws the icon in `color`. The default is `TEXT_COLOR`.
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            svg: self.svg.tint(color),
+            ..self
+        }
+    }
+
+    /// Sets the width and height of the icon, in points.
+    pub fn icon_size(self, size: f32) -> Self {
+        Self { size, ..self }
+    }
+}
+
+impl View for IconView {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        self.svg.draw(path, args);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let size = LocalSize::new(self.size, self.size);
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = cx.view_id(path).access_id();
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Image);
+        builder.set_name(format!("{:?}", self.icon));
+        nodes.push((aid, builder.build(&mut cx.access_node_classes)));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for IconView {}
+
+/// Shows an icon from the set bundled with the `icons` feature, the size of
+/// body text and in the text color unless set otherwise, so common glyphs
+/// don't have to be drawn in a canvas.
+///
+/// ```no_run
+/// # use rui::*;
+/// hstack((
+///     icon(Icon::Search).color(MEDIUM_GRAY),
+///     text("Search"),
+///     icon(Icon::Close).icon_size(12.0),
+/// ));
+/// ```
+pub fn icon(icon: Icon) -> IconView {
+    IconView {
+        icon,
+        svg: svg(icon.svg()).tint(TEXT_COLOR),
+        size: IconView::DEFAULT_SIZE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_icons_parse() {
+        for icon in Icon::ALL {
+            let svg = SvgImage::parse(icon.svg().as_bytes()).unwrap();
+            assert_eq!(svg.size(), LocalSize::new(24.0, 24.0), "{:?}", icon);
+            assert!(!svg.is_blank(), "{:?}", icon);
+        }
+    }
+}
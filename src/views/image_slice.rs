@@ -0,0 +1,232 @@
+use crate::*;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// An image as 8-bit RGBA pixels, from the top row down.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: usize,
+    pub height: usize,
+    pub rgba: Vec<u8>,
+}
+
+impl From<PasteboardImage> for RgbaImage {
+    fn from(image: PasteboardImage) -> Self {
+        Self {
+            width: image.width,
+            height: image.height,
+            rgba: image.rgba,
+        }
+    }
+}
+
+/// How many pixels in from each edge of an image stay the same size when
+/// it's stretched. The corners are fixed, the edges stretch along their
+/// length and the center stretches both ways.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SliceInsets {
+    pub top: usize,
+    pub left: usize,
+    pub bottom: usize,
+    pub right: usize,
+}
+
+impl SliceInsets {
+    pub fn new(top: usize, left: usize, bottom: usize, right: usize) -> Self {
+        Self {
+            top,
+            left,
+            bottom,
+            right,
+        }
+    }
+
+    pub fn uniform(inset: usize) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+}
+
+/// Where pixel boundary `x` of an axis `n` pixels long, with fixed `start`
+/// and `end` insets, lands when the axis is stretched to `length` points.
+/// Insets which don't fit shrink in proportion.
+fn stretch(x: usize, n: usize, start: usize, end: usize, length: f32) -> f32 {
+    let (x, n, start, end) = (x as f32, n as f32, start as f32, end as f32);
+    let fixed = start + end;
+    if length < fixed {
+        let k = length / fixed;
+        if x <= start {
+            x * k
+        } else if x >= n - end {
+            length - (n - x) * k
+        } else {
+            start * k
+        }
+    } else if x <= start {
+        x
+    } else if x >= n - end {
+        length - (n - x)
+    } else {
+        start + (x - start) * (length - fixed) / (n - fixed)
+    }
+}
+
+/// A horizontal run of same-colored pixels, within one slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Run {
+    row: usize,
+    start: usize,
+    end: usize,
+    rgba: [u8; 4],
+}
+
+/// Splits the rows of `image` into runs which don't cross the insets, so
+/// each run stretches into a single rectangle. Clear pixels are skipped.
+fn runs(image: &RgbaImage, insets: SliceInsets) -> Vec<Run> {
+    let mut runs = vec![];
+    let breaks = [insets.left, image.width.saturating_sub(insets.right)];
+    for row in 0..image.height {
+        let pixel = |x: usize| {
+            let i = (row * image.width + x) * 4;
+            [
+                image.rgba[i],
+                image.rgba[i + 1],
+                image.rgba[i + 2],
+                image.rgba[i + 3],
+            ]
+        };
+        let mut start = 0;
+        for x in 1..=image.width {
+            if x == image.width || breaks.contains(&x) || pixel(x) != pixel(start) {
+                let rgba = pixel(start);
+                if rgba[3] != 0 {
+                    runs.push(Run {
+                        row,
+                        start,
+                        end: x,
+                        rgba,
+                    });
+                }
+                start = x;
+            }
+        }
+    }
+    runs
+}
+
+/// Struct for `image_slice`.
+pub struct ImageSlice {
+    image: Rc<RgbaImage>,
+    insets: SliceInsets,
+    runs: Vec<Run>,
+}
+
+impl View for ImageSlice {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let (w, h) = (self.image.width, self.image.height);
+        let insets = self.insets;
+
+        // vger can't draw textures, so each run is a filled rectangle.
+        let mut paints = HashMap::new();
+        for run in &self.runs {
+            let paint = *paints.entry(run.rgba).or_insert_with(|| {
+                let [r, g, b, a] = run.rgba;
+                args.vger.color_paint(vger::Color::new(
+                    r as f32 / 255.0,
+                    g as f32 / 255.0,
+                    b as f32 / 255.0,
+                    a as f32 / 255.0,
+                ))
+            });
+            let x0 = stretch(run.start, w, insets.left, insets.right, rect.width());
+            let x1 = stretch(run.end, w, insets.left, insets.right, rect.width());
+            // Rows go down from the top.
+            let y0 = stretch(run.row, h, insets.top, insets.bottom, rect.height());
+            let y1 = stretch(run.row + 1, h, insets.top, insets.bottom, rect.height());
+            args.vger.fill_rect(
+                LocalRect::new(
+                    [rect.min_x() + x0, rect.max_y() - y1].into(),
+                    [x1 - x0, y1 - y0].into(),
+                ),
+                0.0,
+                paint,
+            );
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+}
+
+impl private::Sealed for ImageSlice {}
+
+/// Stretches an image to fill the space offered, keeping its corners the
+/// same size, for skinned buttons and chat bubbles. A pixel of the image is
+/// a point. Usually used with `background_image_slice`.
+pub fn image_slice(image: impl Into<Rc<RgbaImage>>, insets: SliceInsets) -> ImageSlice {
+    let image = image.into();
+    let runs = runs(&image, insets);
+    ImageSlice {
+        image,
+        insets,
+        runs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_stretch() {
+        // 10 pixels with 2 fixed at each end, stretched to 20 points.
+        assert_eq!(stretch(0, 10, 2, 2, 20.0), 0.0);
+        assert_eq!(stretch(2, 10, 2, 2, 20.0), 2.0);
+        assert_eq!(stretch(5, 10, 2, 2, 20.0), 10.0);
+        assert_eq!(stretch(8, 10, 2, 2, 20.0), 18.0);
+        assert_eq!(stretch(10, 10, 2, 2, 20.0), 20.0);
+
+        // Too small for the insets.
+        assert_eq!(stretch(2, 10, 2, 2, 2.0), 1.0);
+        assert_eq!(stretch(9, 10, 2, 2, 2.0), 1.5);
+    }
+
+    #[test]
+    fn test_runs() {
+        let red = [255, 0, 0, 255];
+        let clear = [0, 0, 0, 0];
+        let image = RgbaImage {
+            width: 4,
+            height: 1,
+            rgba: [red, red, red, clear].concat(),
+        };
+        let runs = runs(&image, SliceInsets::new(0, 1, 0, 1));
+        // Split at the left inset, and the clear pixel skipped.
+        assert_eq!(
+            runs,
+            [
+                Run {
+                    row: 0,
+                    start: 0,
+                    end: 1,
+                    rgba: red
+                },
+                Run {
+                    row: 0,
+                    start: 1,
+                    end: 3,
+                    rgba: red
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Where an `image` comes from.
+#[derive(Clone)]
+pub enum ImageSource {
+    /// Pixels already decoded.
+    Pixels(Arc<RgbaImage>),
+    /// PNG or JPEG data. Needs the `images` feature.
+    #[cfg(feature = "images")]
+    Data(Arc<[u8]>),
+    /// A PNG or JPEG file from the `Assets` in the environment. Needs the
+    /// `images` feature.
+    #[cfg(feature = "images")]
+    Asset(String),
+}
+
+impl ImageSource {
+    /// A file from the `Assets` in the environment, like "icons/open.png".
+    #[cfg(feature = "images")]
+    pub fn asset(path: &str) -> Self {
+        ImageSource::Asset(path.into())
+    }
+
+    /// Identifies the image in the `Context`'s cache.
+    fn key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            ImageSource::Pixels(image) => {
+                (0, image.width, image.height).hash(&mut hasher);
+                image.rgba.hash(&mut hasher);
+            }
+            #[cfg(feature = "images")]
+            ImageSource::Data(data) => (1, data).hash(&mut hasher),
+            #[cfg(feature = "images")]
+            ImageSource::Asset(path) => (2, path).hash(&mut hasher),
+        }
+        hasher.finish()
+    }
+
+    /// The decoded image, if it's ready. Data and assets decode in the
+    /// background, and the view updates when they're done.
+    fn load(&self, cx: &mut Context, key: u64) -> Option<Arc<RgbaImage>> {
+        if let Some(image) = cx.images.get(key) {
+            return Some(image);
+        }
+        let image = match self {
+            ImageSource::Pixels(image) => image.clone(),
+            #[cfg(feature = "images")]
+            ImageSource::Data(data) => {
+                let data = data.clone();
+                decoded(cx, key, move || decode_image(&data))?
+            }
+            #[cfg(feature = "images")]
+            ImageSource::Asset(path) => {
+                let (assets, path) = (cx.assets(), path.clone());
+                decoded(cx, key, move || decode_image(&assets.read(&path)?))?
+            }
+        };
+        cx.images.insert(key, image.clone());
+        Some(image)
+    }
+}
+
+/// Decodes PNG or JPEG data.
+#[cfg(feature = "images")]
+pub(crate) fn decode_image(data: &[u8]) -> Result<RgbaImage, String> {
+    let image = image::load_from_memory(data)
+        .map_err(|err| err.to_string())?
+        .to_rgba8();
+    Ok(RgbaImage {
+        width: image.width() as usize,
+        height: image.height() as usize,
+        rgba: image.into_raw(),
+    })
+}
+
+/// Decodes on a background thread where there are threads. An image which
+/// fails to decode shows nothing.
+#[cfg(feature = "images")]
+fn decoded(
+    cx: &mut Context,
+    key: u64,
+    decode: impl FnOnce() -> Result<RgbaImage, String> + Send + 'static,
+) -> Option<Arc<RgbaImage>> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        match cx.load_asset(&format!("rui image {:016x}", key), decode) {
+            Asset::Ready(image) => Some(image),
+            Asset::Loading | Asset::Failed(_) => None,
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let image = Arc::new(decode().unwrap_or(RgbaImage {
+            width: 0,
+            height: 0,
+            rgba: vec![],
+        }));
+        cx.images.insert(key, image.clone());
+        Some(image)
+    }
+}
+
+impl From<RgbaImage> for ImageSource {
+    fn from(image: RgbaImage) -> Self {
+        ImageSource::Pixels(Arc::new(image))
+    }
+}
+
+impl From<Arc<RgbaImage>> for ImageSource {
+    fn from(image: Arc<RgbaImage>) -> Self {
+        ImageSource::Pixels(image)
+    }
+}
+
+#[cfg(feature = "images")]
+impl From<&'static [u8]> for ImageSource {
+    fn from(data: &'static [u8]) -> Self {
+        ImageSource::Data(data.into())
+    }
+}
+
+#[cfg(feature = "images")]
+impl<const N: usize> From<&'static [u8; N]> for ImageSource {
+    fn from(data: &'static [u8; N]) -> Self {
+        ImageSource::Data(data[..].into())
+    }
+}
+
+#[cfg(feature = "images")]
+impl From<Vec<u8>> for ImageSource {
+    fn from(data: Vec<u8>) -> Self {
+        ImageSource::Data(data.into())
+    }
+}
+
+/// How an `image` fits the space it's offered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentMode {
+    /// Scales to fit inside, keeping its shape, and centers.
+    #[default]
+    Fit,
+    /// Scales to cover it all, keeping its shape, and crops what's over.
+    Fill,
+    /// Scales to fill it exactly.
+    Stretch,
+}
+
+/// Where an image `size` pixels goes in `rect` for `mode`, and the part of
+/// it shown there, from 0 to 1, from the top left.
+fn place(mode: ContentMode, size: LocalSize, rect: LocalRect) -> Option<(LocalRect, LocalRect)> {
+    if size.is_empty() || rect.is_empty() {
+        return None;
+    }
+    let all = LocalRect::new(LocalPoint::zero(), [1.0, 1.0].into());
+    let (sx, sy) = (rect.width() / size.width, rect.height() / size.height);
+    Some(match mode {
+        ContentMode::Stretch => (rect, all),
+        ContentMode::Fit => {
+            let scaled = size * sx.min(sy);
+            let origin = rect.center() - scaled.to_vector() / 2.0;
+            (LocalRect::new(origin, scaled), all)
+        }
+        ContentMode::Fill => {
+            let scaled = size * sx.max(sy);
+            let shown = LocalSize::new(rect.width() / scaled.width, rect.height() / scaled.height);
+            let origin = LocalPoint::new((1.0 - shown.width) / 2.0, (1.0 - shown.height) / 2.0);
+            (rect, LocalRect::new(origin, shown))
+        }
+    })
+}
+
+/// Struct for `image`.
+pub struct Image {
+    source: ImageSource,
+    key: u64,
+    mode: ContentMode,
+    radius: f32,
+}
+
+impl Image {
+    /// Sets how the image fits the space it's offered. The default is
+    /// `ContentMode::Fit`.
+    pub fn content_mode(mut self, mode: ContentMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Rounds the corners of the image, where it's drawn.
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+}
+
+impl View for Image {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let image = match self.source.load(args.cx, self.key) {
+            Some(image) => image,
+            None => return,
+        };
+        let size = LocalSize::new(image.width as f32, image.height as f32);
+        if let Some((placed, uv)) = place(self.mode, size, rect) {
+            let world = args
+                .vger
+                .current_transform()
+                .outer_transformed_rect(&placed);
+            let (clip, layer) = (args.cx.clip_rect, args.cx.layer);
+            args.cx
+                .images
+                .draw(self.key, world, uv, self.radius, clip, layer);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+}
+
+impl private::Sealed for Image {}
+
+/// Shows a bitmap, taking all the space it's offered, from pixels or, with
+/// the `images` feature, from PNG or JPEG data or assets. Images decode in
+/// the background and are uploaded to the GPU once, then kept until they
+/// haven't been drawn for a while.
+///
+/// Images are drawn after the rest of the window, so they show above
+/// views drawn over them, like menus, though they're clipped by
+/// `clip` and `scroll_view`.
+///
+/// ```no_run
+/// # use rui::*;
+/// let pixels = RgbaImage {
+///     width: 2,
+///     height: 1,
+///     rgba: vec![255, 0, 0, 255, 0, 0, 255, 255],
+/// };
+/// image(pixels)
+///     .content_mode(ContentMode::Fill)
+///     .corner_radius(8.0)
+///     .padding(Auto);
+/// ```
+pub fn image(source: impl Into<ImageSource>) -> Image {
+    let source = source.into();
+    Image {
+        key: source.key(),
+        source,
+        mode: ContentMode::Fit,
+        radius: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> LocalRect {
+        LocalRect::new([x, y].into(), [w, h].into())
+    }
+
+    #[test]
+    fn test_image_place() {
+        let size = LocalSize::new(200.0, 100.0);
+        let area = rect(0.0, 0.0, 100.0, 100.0);
+        let all = rect(0.0, 0.0, 1.0, 1.0);
+
+        assert_eq!(place(ContentMode::Stretch, size, area), Some((area, all)));
+        assert_eq!(
+            place(ContentMode::Fit, size, area),
+            Some((rect(0.0, 25.0, 100.0, 50.0), all))
+        );
+        assert_eq!(
+            place(ContentMode::Fill, size, area),
+            Some((area, rect(0.25, 0.0, 0.5, 1.0)))
+        );
+        assert_eq!(place(ContentMode::Fit, LocalSize::zero(), area), None);
+    }
+
+    #[test]
+    fn test_image_cache() {
+        let mut cx = Context::new();
+        let pixels = RgbaImage {
+            width: 1,
+            height: 1,
+            rgba: vec![1, 2, 3, 4],
+        };
+        let a = image(pixels.clone());
+        assert_eq!(a.key, image(pixels).key);
+
+        let loaded = a.source.load(&mut cx, a.key).unwrap();
+        assert_eq!(loaded.rgba, [1, 2, 3, 4]);
+        assert!(cx.images.get(a.key).is_some());
+    }
+}
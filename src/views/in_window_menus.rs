@@ -0,0 +1,609 @@
+use crate::*;
+use std::any::Any;
+
+const MENU_BAR_HEIGHT: f32 = 24.0;
+const MENU_FONT_SIZE: u32 = 13;
+const MENU_PADDING: f32 = 10.0;
+const MENU_ITEM_HEIGHT: f32 = 22.0;
+const MENU_SEPARATOR_HEIGHT: f32 = 9.0;
+const MENU_MIN_WIDTH: f32 = 160.0;
+const SUBMENU_INDENT: f32 = 12.0;
+const SHORTCUT_GAP: f32 = 24.0;
+
+/// A line of a dropdown. Submenus are shown inline, under a heading.
+struct Row {
+    title: String,
+    shortcut: Option<String>,
+    /// What choosing the row does, if it can be chosen.
+    role: Option<MenuItemRole>,
+    depth: usize,
+}
+
+impl Row {
+    fn is_separator(&self) -> bool {
+        self.role == Some(MenuItemRole::Separator)
+    }
+
+    fn selectable(&self) -> bool {
+        matches!(
+            self.role,
+            Some(MenuItemRole::Command(_))
+                | Some(MenuItemRole::About(_))
+                | Some(MenuItemRole::Preferences(_))
+                | Some(MenuItemRole::Quit)
+        )
+    }
+
+    fn height(&self) -> f32 {
+        if self.is_separator() {
+            MENU_SEPARATOR_HEIGHT
+        } else {
+            MENU_ITEM_HEIGHT
+        }
+    }
+}
+
+fn shortcut_label(key: HotKey) -> String {
+    let name = format!("{:?}", key);
+    format!("Ctrl+{}", name.strip_prefix("Key").unwrap_or(&name))
+}
+
+fn add_rows(rows: &mut Vec<Row>, items: &[MenuItem], depth: usize) {
+    for item in items {
+        match &item.role {
+            MenuItemRole::Submenu => {
+                rows.push(Row {
+                    title: item.title.clone(),
+                    shortcut: None,
+                    role: None,
+                    depth,
+                });
+                add_rows(rows, &item.submenu, depth + 1);
+            }
+            role => rows.push(Row {
+                title: item.title.clone(),
+                shortcut: item.key.map(shortcut_label),
+                role: Some(role.clone()),
+                depth,
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct MenuBarState {
+    /// The menu showing its dropdown.
+    open: Option<usize>,
+    /// The row chosen with the keyboard.
+    highlighted: Option<usize>,
+    /// Where each menu's title is in the bar.
+    titles: Vec<LocalRect>,
+    /// Width of each menu's dropdown.
+    widths: Vec<f32>,
+    /// Where the content is below the bar.
+    child_offset: LocalOffset,
+}
+
+/// Struct for `in_window_menus`.
+pub struct InWindowMenus<V> {
+    child: V,
+    menus: Vec<(String, Vec<Row>)>,
+}
+
+impl<V> InWindowMenus<V>
+where
+    V: View,
+{
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<MenuBarState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &MenuBarState::default);
+        StateHandle::new(id)
+    }
+
+    /// Where the rows of a menu's dropdown are.
+    fn row_rects(&self, state: &MenuBarState, menu: usize) -> Vec<LocalRect> {
+        let title = state.titles[menu];
+        let mut top = title.min_y() - MENU_PADDING / 2.0;
+        self.menus[menu]
+            .1
+            .iter()
+            .map(|row| {
+                top -= row.height();
+                LocalRect::new(
+                    [title.min_x(), top].into(),
+                    [state.widths[menu], row.height()].into(),
+                )
+            })
+            .collect()
+    }
+
+    fn dropdown_frame(&self, state: &MenuBarState, menu: usize) -> LocalRect {
+        let rows = self.row_rects(state, menu);
+        let height = rows.iter().map(|r| r.height()).sum::<f32>() + MENU_PADDING;
+        let title = state.titles[menu];
+        LocalRect::new(
+            [title.min_x(), title.min_y() - height].into(),
+            [state.widths[menu], height].into(),
+        )
+    }
+
+    /// The next row which can be chosen after `from`, going `step` rows at a
+    /// time.
+    fn next_selectable(&self, menu: usize, from: Option<usize>, step: isize) -> Option<usize> {
+        let rows = &self.menus[menu].1;
+        let n = rows.len() as isize;
+        let mut i = match from {
+            Some(i) => i as isize,
+            None if step > 0 => -1,
+            None => n,
+        };
+        for _ in 0..n {
+            i = (i + step).rem_euclid(n);
+            if rows[i as usize].selectable() {
+                return Some(i as usize);
+            }
+        }
+        None
+    }
+
+    fn open(&self, cx: &mut Context, s: StateHandle<MenuBarState>, menu: Option<usize>) {
+        let highlighted = menu.and_then(|m| self.next_selectable(m, None, 1));
+        if cx[s].open != menu || cx[s].highlighted != highlighted {
+            cx[s].open = menu;
+            cx[s].highlighted = highlighted;
+        }
+    }
+
+    fn choose(
+        &self,
+        menu: usize,
+        row: usize,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        self.open(cx, s, None);
+        match &self.menus[menu].1[row].role {
+            Some(MenuItemRole::Command(name))
+            | Some(MenuItemRole::About(name))
+            | Some(MenuItemRole::Preferences(name)) => {
+                path.push(0);
+                self.child
+                    .process(&Event::Command(name.clone()), path, cx, actions);
+                path.pop();
+            }
+            Some(MenuItemRole::Quit) => cx.close_window(),
+            _ => (),
+        }
+    }
+
+    /// Handles keys while a menu is open or to open one, returning whether
+    /// the key was used.
+    fn process_key(
+        &self,
+        key: &Key,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) -> bool {
+        let s = self.state(path, cx);
+        let state = s.get(cx).clone();
+        let n = self.menus.len();
+
+        let menu = match state.open {
+            Some(menu) => menu,
+            None => {
+                if matches!(key, Key::F10) {
+                    self.open(cx, s, Some(0));
+                    return true;
+                }
+                return false;
+            }
+        };
+
+        match key {
+            Key::Escape | Key::F10 => self.open(cx, s, None),
+            Key::ArrowLeft => self.open(cx, s, Some((menu + n - 1) % n)),
+            Key::ArrowRight => self.open(cx, s, Some((menu + 1) % n)),
+            Key::ArrowUp => cx[s].highlighted = self.next_selectable(menu, state.highlighted, -1),
+            Key::ArrowDown => cx[s].highlighted = self.next_selectable(menu, state.highlighted, 1),
+            Key::Enter | Key::Space => {
+                if let Some(row) = state.highlighted {
+                    self.choose(menu, row, path, cx, actions);
+                }
+            }
+            _ => (),
+        }
+        // The menu has the keyboard while it's open.
+        true
+    }
+
+    /// Handles clicks on the bar and open menus, returning whether the click
+    /// was used.
+    fn process_touch(
+        &self,
+        position: LocalPoint,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) -> bool {
+        let s = self.state(path, cx);
+        let state = s.get(cx).clone();
+
+        if let Some(menu) = state.titles.iter().position(|r| r.contains(position)) {
+            let menu = if state.open == Some(menu) {
+                None
+            } else {
+                Some(menu)
+            };
+            self.open(cx, s, menu);
+            return true;
+        }
+
+        if let Some(menu) = state.open {
+            let rows = self.row_rects(&state, menu);
+            match rows.iter().position(|r| r.contains(position)) {
+                Some(row) if self.menus[menu].1[row].selectable() => {
+                    self.choose(menu, row, path, cx, actions)
+                }
+                Some(_) => (),
+                None if self.dropdown_frame(&state, menu).contains(position) => (),
+                // Clicking elsewhere just closes the menu.
+                None => self.open(cx, s, None),
+            }
+            return true;
+        }
+
+        // The rest of the bar.
+        position.y >= state.titles.first().map_or(f32::MAX, |r| r.min_y())
+    }
+
+    fn draw_text(vger: &mut Vger, text: &str, rect: LocalRect, x: f32, color: Color) {
+        let bounds = vger.text_bounds(text, MENU_FONT_SIZE, None);
+        vger.save();
+        vger.translate([x - bounds.min_x(), rect.center().y - bounds.center().y]);
+        vger.text(text, MENU_FONT_SIZE, color, None);
+        vger.restore();
+    }
+
+    fn draw_menus(&self, rect: LocalRect, state: &MenuBarState, vger: &mut Vger) {
+        let bar = LocalRect::new(
+            [rect.min_x(), rect.max_y() - MENU_BAR_HEIGHT].into(),
+            [rect.width(), MENU_BAR_HEIGHT].into(),
+        );
+        let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        vger.fill_rect(bar, 0.0, paint);
+
+        for (i, (title, _)) in self.menus.iter().enumerate() {
+            let title_rect = state.titles[i];
+            if state.open == Some(i) {
+                let paint = vger.color_paint(BUTTON_HOVER_COLOR);
+                vger.fill_rect(title_rect, 0.0, paint);
+            }
+            Self::draw_text(
+                vger,
+                title,
+                title_rect,
+                title_rect.min_x() + MENU_PADDING,
+                TEXT_COLOR,
+            );
+        }
+
+        let menu = match state.open {
+            Some(menu) => menu,
+            None => return,
+        };
+        let frame = self.dropdown_frame(state, menu);
+        let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        vger.fill_rect(frame, 4.0, paint);
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        vger.stroke_rect(frame.min(), frame.max(), 4.0, 1.0, paint);
+
+        let rows = &self.menus[menu].1;
+        for (i, (row, row_rect)) in rows.iter().zip(self.row_rects(state, menu)).enumerate() {
+            if row.is_separator() {
+                let y = row_rect.center().y;
+                vger.fill_rect(
+                    euclid::rect(
+                        row_rect.min_x() + MENU_PADDING,
+                        y,
+                        row_rect.width() - 2.0 * MENU_PADDING,
+                        1.0,
+                    ),
+                    0.0,
+                    paint,
+                );
+                continue;
+            }
+            if state.highlighted == Some(i) {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT_DARK);
+                vger.fill_rect(row_rect.inflate(-2.0, 0.0), 4.0, paint);
+            }
+            let color = if row.selectable() {
+                TEXT_COLOR
+            } else {
+                MEDIUM_GRAY
+            };
+            let x = row_rect.min_x() + MENU_PADDING + row.depth as f32 * SUBMENU_INDENT;
+            Self::draw_text(vger, &row.title, row_rect, x, color);
+            if let Some(shortcut) = &row.shortcut {
+                let width = vger.text_bounds(shortcut, MENU_FONT_SIZE, None).width();
+                let x = row_rect.max_x() - MENU_PADDING - width;
+                Self::draw_text(vger, shortcut, row_rect, x, MEDIUM_GRAY);
+            }
+        }
+    }
+}
+
+impl<V> View for InWindowMenus<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if !self.menus.is_empty() {
+            let used = match event {
+                Event::Key { key, .. } => self.process_key(key, path, cx, actions),
+                Event::TouchBegin { position, .. } => {
+                    self.process_touch(*position, path, cx, actions)
+                }
+                Event::TouchMove { .. }
+                | Event::TouchEnd { .. }
+                | Event::Hover { .. }
+                | Event::Scroll { .. } => {
+                    let s = self.state(path, cx);
+                    s.get(cx).open.is_some()
+                }
+                _ => false,
+            };
+            if used {
+                return;
+            }
+        }
+
+        let s = self.state(path, cx);
+        let offset = s.get(cx).child_offset;
+        path.push(0);
+        self.child
+            .process(&event.offset(-offset), path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let s = self.state(path, args.cx);
+        let state = s.get(args.cx).clone();
+
+        args.vger.save();
+        args.vger.translate(state.child_offset);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+
+        if !self.menus.is_empty() {
+            let rect = args.cx.get_layout(path).rect;
+            self.draw_menus(rect, &state, args.vger);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+
+        if self.menus.is_empty() {
+            path.push(0);
+            let sz = self.child.layout(path, args);
+            path.pop();
+            args.cx.update_layout(
+                path,
+                LayoutBox {
+                    rect: LocalRect::new(LocalPoint::zero(), sz),
+                    offset: LocalOffset::zero(),
+                },
+            );
+            return sz;
+        }
+
+        let mut titles = vec![];
+        let mut widths = vec![];
+        let mut x = 0.0;
+        let top = args.sz.height;
+        for (title, rows) in &self.menus {
+            let width =
+                (args.text_bounds)(title, MENU_FONT_SIZE, None).width() + 2.0 * MENU_PADDING;
+            titles.push(LocalRect::new(
+                [x, top - MENU_BAR_HEIGHT].into(),
+                [width, MENU_BAR_HEIGHT].into(),
+            ));
+            x += width;
+
+            let mut dropdown_width = MENU_MIN_WIDTH;
+            for row in rows {
+                let mut w = (args.text_bounds)(&row.title, MENU_FONT_SIZE, None).width()
+                    + row.depth as f32 * SUBMENU_INDENT;
+                if let Some(shortcut) = &row.shortcut {
+                    w += SHORTCUT_GAP + (args.text_bounds)(shortcut, MENU_FONT_SIZE, None).width();
+                }
+                dropdown_width = dropdown_width.max(w + 2.0 * MENU_PADDING);
+            }
+            widths.push(dropdown_width);
+        }
+
+        // The content goes below the bar, centered like the root view.
+        let area = LocalSize::new(args.sz.width, (args.sz.height - MENU_BAR_HEIGHT).max(0.0));
+        path.push(0);
+        let sz = self.child.layout(path, &mut args.size(area));
+        path.pop();
+        let child_offset = ((area - sz) / 2.0).to_vector();
+
+        let state = s.get(args.cx);
+        if state.titles != titles || state.widths != widths || state.child_offset != child_offset {
+            args.cx[s].titles = titles;
+            args.cx[s].widths = widths;
+            args.cx[s].child_offset = child_offset;
+        }
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let offset = self.state(path, cx).get(cx).child_offset;
+        path.push(0);
+        self.child.dirty(path, xform.pre_translate(offset), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let s = self.state(path, cx);
+        let state = s.get(cx).clone();
+        let on_menus = state.titles.iter().any(|r| r.contains(pt))
+            || state
+                .open
+                .map_or(false, |menu| self.dropdown_frame(&state, menu).contains(pt));
+        if on_menus {
+            return Some(cx.view_id(path));
+        }
+
+        path.push(0);
+        let id = self.child.hittest(path, pt - state.child_offset, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for InWindowMenus<V> {}
+
+/// Draws `menus`, usually from `menu_bar`, as a menu bar across the top of
+/// the window, above `content`. Clicking a title opens its menu, and F10
+/// opens the first one, after which the arrow keys move around, Enter
+/// chooses and Escape closes. Choosing a command sends `Event::Command` to
+/// `content`.
+///
+/// `rui_with` does this for you where there's no native menu bar; see
+/// `MenuBarMode`.
+pub fn in_window_menus<V: View>(menus: Vec<Menu>, content: V) -> InWindowMenus<V> {
+    InWindowMenus {
+        child: content,
+        menus: menus
+            .into_iter()
+            .map(|menu| {
+                let mut rows = vec![];
+                add_rows(&mut rows, &menu.items, 0);
+                (menu.title, rows)
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_in_window_menus() {
+        let mut cx = Context::new();
+
+        let commands = [
+            CommandInfo {
+                path: "File:New".into(),
+                key: Some(HotKey::KeyN),
+            },
+            CommandInfo {
+                path: "File:Open".into(),
+                key: None,
+            },
+        ];
+        let menus = menu_bar("Demo", &commands, MenuPlatform::Linux);
+        let ui = in_window_menus(
+            menus,
+            state(
+                || None,
+                |s, _| {
+                    rectangle()
+                        .command("File:New", Some(HotKey::KeyN), move |cx| {
+                            cx[s] = Some("new")
+                        })
+                        .command("File:Open", None, move |cx| cx[s] = Some("open"))
+                },
+            ),
+        );
+        let sz = [400.0, 300.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        path.push(0);
+        let s = StateHandle::<Option<&str>>::new(cx.view_id(&path));
+        path.pop();
+
+        let mut actions = vec![];
+        let mut press = |key, cx: &mut Context| {
+            let event = Event::Key {
+                key,
+                mods: KeyboardModifiers::default(),
+                repeat: false,
+            };
+            ui.process(&event, &mut path, cx, &mut actions);
+        };
+        // Arrow keys go to the content until a menu is open.
+        press(Key::ArrowDown, &mut cx);
+        press(Key::F10, &mut cx);
+        press(Key::ArrowDown, &mut cx);
+        press(Key::Enter, &mut cx);
+        assert_eq!(*s.get(&cx), Some("open"));
+
+        // Clicking the title opens the menu, and the first row is New.
+        let click = |x, y| Event::TouchBegin {
+            id: 0,
+            position: [x, y].into(),
+        };
+        let mut path = vec![0];
+        ui.process(&click(5.0, 290.0), &mut path, &mut cx, &mut actions);
+        ui.process(&click(5.0, 260.0), &mut path, &mut cx, &mut actions);
+        assert_eq!(*s.get(&cx), Some("new"));
+    }
+}
@@ -0,0 +1,299 @@
+use crate::*;
+use std::any::Any;
+
+/// How much a pressed view shrinks.
+const PRESS_SCALE: f32 = 0.95;
+
+/// How long a shake lasts, in seconds.
+const SHAKE_DURATION: f32 = 0.4;
+
+/// How far a shake moves the view, and how many times a second it goes
+/// back and forth.
+const SHAKE_AMPLITUDE: f32 = 6.0;
+const SHAKE_FREQUENCY: f32 = 12.0;
+
+/// Where a shake started `t` seconds ago has moved the view to.
+fn shake_offset(t: f32) -> f32 {
+    if t >= SHAKE_DURATION {
+        0.0
+    } else {
+        let decay = 1.0 - t / SHAKE_DURATION;
+        SHAKE_AMPLITUDE * decay * (2.0 * std::f32::consts::PI * SHAKE_FREQUENCY * t).sin()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Preset {
+    Press,
+    Spring(LocalOffset),
+    Shake(u64),
+    Checkmark(bool),
+}
+
+/// Where an interaction is in its animation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Motion {
+    pressed: bool,
+    position: LocalOffset,
+    velocity: LocalOffset,
+    /// The shake trigger last seen, and the time since it changed.
+    trigger: u64,
+    elapsed: f32,
+}
+
+/// Struct for the micro-interaction modifiers: `press_scale`,
+/// `spring_offset`, `shake` and `success_checkmark`.
+pub struct Interaction<V> {
+    child: V,
+    preset: Preset,
+}
+
+impl<V> Interaction<V>
+where
+    V: View,
+{
+    fn new(child: V, preset: Preset) -> Self {
+        Self { child, preset }
+    }
+
+    pub fn press(child: V) -> Self {
+        Self::new(child, Preset::Press)
+    }
+
+    pub fn spring(child: V, offset: LocalOffset) -> Self {
+        Self::new(child, Preset::Spring(offset))
+    }
+
+    pub fn shake(child: V, trigger: u64) -> Self {
+        Self::new(child, Preset::Shake(trigger))
+    }
+
+    pub fn checkmark(child: V, shown: bool) -> Self {
+        Self::new(child, Preset::Checkmark(shown))
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<Motion> {
+        let vid = cx.view_id(path);
+        let preset = self.preset;
+        // Start out where we're going, so nothing animates on appearing.
+        cx.init_state(vid, &move || match preset {
+            Preset::Spring(offset) => Motion {
+                position: offset,
+                ..Default::default()
+            },
+            Preset::Shake(trigger) => Motion {
+                trigger,
+                elapsed: SHAKE_DURATION,
+                ..Default::default()
+            },
+            Preset::Checkmark(shown) => Motion {
+                position: [if shown { 1.0 } else { 0.0 }, 0.0].into(),
+                ..Default::default()
+            },
+            Preset::Press => Motion::default(),
+        });
+        StateHandle::new(vid)
+    }
+
+    /// Steps the animation.
+    fn animate(&self, motion: Motion, dt: f32) -> Motion {
+        let mut next = motion;
+        let target = match self.preset {
+            Preset::Press => [if motion.pressed { 1.0 } else { 0.0 }, 0.0].into(),
+            Preset::Spring(offset) => offset,
+            Preset::Checkmark(shown) => [if shown { 1.0 } else { 0.0 }, 0.0].into(),
+            Preset::Shake(trigger) => {
+                if trigger != motion.trigger {
+                    next.trigger = trigger;
+                    next.elapsed = 0.0;
+                } else if motion.elapsed < SHAKE_DURATION {
+                    next.elapsed = (motion.elapsed + dt).min(SHAKE_DURATION);
+                }
+                return next;
+            }
+        };
+        let (position, velocity) =
+            Spring::default().step(motion.position, motion.velocity, target, dt);
+        next.position = position;
+        next.velocity = velocity;
+        next
+    }
+
+    /// How far the child is moved.
+    fn offset(&self, motion: &Motion) -> LocalOffset {
+        match self.preset {
+            Preset::Spring(_) => motion.position,
+            Preset::Shake(_) => [shake_offset(motion.elapsed), 0.0].into(),
+            _ => LocalOffset::zero(),
+        }
+    }
+}
+
+/// Strokes a checkmark over `rect`, `progress` of the way along.
+fn draw_checkmark(rect: LocalRect, progress: f32, vger: &mut Vger, color: Color) {
+    let s = rect.width().min(rect.height()) * 0.5;
+    let c = rect.center();
+    let points: [LocalPoint; 3] = [
+        c + LocalOffset::new(-0.5, 0.0) * s,
+        c + LocalOffset::new(-0.15, -0.35) * s,
+        c + LocalOffset::new(0.5, 0.4) * s,
+    ];
+    let lengths = [
+        (points[1] - points[0]).length(),
+        (points[2] - points[1]).length(),
+    ];
+    let paint = vger.color_paint(color);
+    let width = (s * 0.15).max(1.5);
+    let mut remaining = progress.clamp(0.0, 1.0) * (lengths[0] + lengths[1]);
+    for i in 0..2 {
+        if remaining <= 0.0 {
+            break;
+        }
+        let t = (remaining / lengths[i]).min(1.0);
+        let end = points[i].lerp(points[i + 1], t);
+        vger.stroke_segment(points[i], end, width, paint);
+        remaining -= lengths[i];
+    }
+}
+
+impl<V> View for Interaction<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        let motion = *s.get(cx);
+
+        let next = match event {
+            Event::Anim => self.animate(motion, cx.anim_dt),
+            Event::TouchBegin { position, .. } if matches!(self.preset, Preset::Press) => {
+                let pressed = self.hittest(path, *position, cx).is_some();
+                Motion {
+                    pressed: motion.pressed || pressed,
+                    ..motion
+                }
+            }
+            Event::TouchEnd { .. } => Motion {
+                pressed: false,
+                ..motion
+            },
+            _ => motion,
+        };
+        // Writing the state keeps the animation ticking, so only do it
+        // until things settle.
+        if next != motion {
+            cx[s] = next;
+            if let Event::Anim = event {
+                cx.note_animation("interaction", None);
+            }
+        }
+
+        path.push(0);
+        self.child
+            .process(&event.offset(-self.offset(&motion)), path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let motion = *self.state(path, args.cx).get(args.cx);
+        let rect = args.cx.get_layout(path).rect;
+
+        args.vger.save();
+        args.vger.translate(self.offset(&motion));
+        if let Preset::Press = self.preset {
+            let scale = 1.0 + (PRESS_SCALE - 1.0) * motion.position.x;
+            let center = rect.center().to_vector();
+            args.vger.translate(center);
+            args.vger.scale([scale, scale]);
+            args.vger.translate(-center);
+        }
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+
+        if let Preset::Checkmark(_) = self.preset {
+            if motion.position.x > 0.0 {
+                let color = args.cx.theme().colors.accent;
+                draw_checkmark(rect, motion.position.x, args.vger, color);
+            }
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let motion = *self.state(path, cx).get(cx);
+        path.push(0);
+        self.child
+            .dirty(path, xform.pre_translate(self.offset(&motion)), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let motion = *self.state(path, cx).get(cx);
+        path.push(0);
+        let id = self.child.hittest(path, pt - self.offset(&motion), cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for Interaction<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_shake() {
+        assert_eq!(shake_offset(0.0), 0.0);
+        assert!(shake_offset(0.02).abs() > 1.0);
+        assert_eq!(shake_offset(SHAKE_DURATION), 0.0);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::*;
+use std::any::Any;
+
+#[derive(Clone, Default)]
+struct IsolateState {
+    /// State inside, which only invalidates the inside.
+    deps: Vec<ViewId>,
+    /// Size given at the last layout.
+    size: LocalSize,
+    /// Lay out the inside before drawing.
+    needs_layout: bool,
+}
+
+/// Gets the state without marking it changed, which would invalidate
+/// views outside.
+fn isolate_state(cx: &mut Context, id: ViewId) -> &mut IsolateState {
+    cx.init_state(id, &IsolateState::default);
+    cx.state_map
+        .get_mut(&id)
+        .unwrap()
+        .state
+        .downcast_mut()
+        .unwrap()
+}
+
+/// Struct for the `isolate` modifier.
+pub struct Isolate<V> {
+    child: V,
+}
+
+impl<V> Isolate<V>
+where
+    V: View,
+{
+    pub fn new(child: V) -> Self {
+        Self { child }
+    }
+
+    fn layout_child(
+        &self,
+        path: &mut IdPath,
+        id: ViewId,
+        sz: LocalSize,
+        cx: &mut Context,
+        text_bounds: &mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect,
+    ) {
+        path.push(0);
+        self.child.layout(
+            path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut *cx,
+                text_bounds,
+            },
+        );
+        let mut deps = vec![];
+        self.child.gc(path, cx, &mut deps);
+        path.pop();
+
+        let state = isolate_state(cx, id);
+        state.deps = deps;
+        state.size = sz;
+        state.needs_layout = false;
+    }
+}
+
+impl<V> View for Isolate<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let id = args.cx.view_id(path);
+
+        // Views outside skipped layout, so lay out the inside here.
+        let state = isolate_state(args.cx, id);
+        if state.needs_layout {
+            let sz = state.size;
+            let vger = &mut *args.vger;
+            self.layout_child(path, id, sz, args.cx, &mut |str, size, max_width| {
+                vger.text_bounds(str, size, max_width)
+            });
+        }
+
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let id = args.cx.view_id(path);
+        self.layout_child(path, id, args.sz, args.cx, args.text_bounds);
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let id = cx.view_id(path);
+        let deps = std::mem::take(&mut isolate_state(cx, id).deps);
+        let changed = deps
+            .iter()
+            .any(|dep| cx.state_map.get(dep).map_or(false, |holder| holder.dirty));
+        isolate_state(cx, id).deps = deps;
+
+        if changed {
+            isolate_state(cx, id).needs_layout = true;
+
+            // The inside may be laid out differently, so redraw all of it.
+            let rect = cx.get_layout(path).rect;
+            let pts: [LocalPoint; 4] = [
+                rect.min(),
+                [rect.max_x(), rect.min_y()].into(),
+                [rect.min_x(), rect.max_y()].into(),
+                rect.max(),
+            ];
+            let world_pts = pts.map(|p| xform.transform_point(p));
+            cx.dirty_region.add_rect(WorldRect::from_points(world_pts));
+        } else {
+            path.push(0);
+            self.child.dirty(path, xform, cx);
+            path.pop();
+        }
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.child.hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+
+        // Hide the inside from the dependencies of `state` views outside.
+        if cx.collecting_deps {
+            return;
+        }
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+
+    fn is_flexible(&self) -> bool {
+        true
+    }
+}
+
+impl<V> private::Sealed for Isolate<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_isolate() {
+        let mut cx = Context::new();
+        let ui = state(|| 0, |_, _| state(|| 0, |_, _| rectangle()).isolate());
+        let sz = [100.0, 100.0].into();
+        let mut path = vec![0];
+        assert_eq!(
+            ui.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz,
+                    cx: &mut cx,
+                    text_bounds: &mut |_, _, _| LocalRect::zero(),
+                },
+            ),
+            sz
+        );
+
+        // The outer state doesn't depend on the inner one.
+        let outer = cx.view_id(&vec![0]);
+        let inner = cx.view_id(&vec![0, 0, 0]);
+        assert!(cx.state_map.contains_key(&inner));
+        assert!(!cx.deps[&outer].contains(&inner));
+    }
+}
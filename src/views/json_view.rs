@@ -0,0 +1,569 @@
+use crate::*;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// A step into a JSON value.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Seg {
+    Key(String),
+    Index(usize),
+}
+
+type JsonPath = Vec<Seg>;
+
+/// Writes `path` like `$.users[0]["first name"]`.
+fn path_string(path: &[Seg]) -> String {
+    let mut s = String::from("$");
+    for seg in path {
+        match seg {
+            Seg::Key(key)
+                if key.starts_with(|c: char| c.is_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+            {
+                s.push('.');
+                s.push_str(key);
+            }
+            Seg::Key(key) => s.push_str(&format!("[{}]", Value::from(key.as_str()))),
+            Seg::Index(i) => s.push_str(&format!("[{}]", i)),
+        }
+    }
+    s
+}
+
+fn lookup<'a>(value: &'a Value, path: &[Seg]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(value, |value, seg| match (value, seg) {
+            (Value::Object(map), Seg::Key(key)) => map.get(key),
+            (Value::Array(items), Seg::Index(i)) => items.get(*i),
+            _ => None,
+        })
+}
+
+/// A row of a `json_view`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum JsonRow {
+    /// A value, under its key or index.
+    Value { path: JsonPath, depth: usize },
+    /// Children of the container at `path` which aren't shown yet.
+    More {
+        path: JsonPath,
+        depth: usize,
+        hidden: usize,
+    },
+}
+
+/// Children shown at first, and each time "Show more" is tapped.
+const PAGE: usize = 100;
+
+/// Search stops after this many matches.
+const MAX_MATCHES: usize = 1000;
+
+/// Adds rows for `value` and, if it's expanded, its children. Collapsed
+/// containers aren't looked into, so huge documents cost only what's
+/// open.
+fn push_rows(value: &Value, path: &mut JsonPath, state: &JsonViewState, rows: &mut Vec<JsonRow>) {
+    let depth = path.len();
+    rows.push(JsonRow::Value {
+        path: path.clone(),
+        depth,
+    });
+    if !state.expanded.contains(path.as_slice()) {
+        return;
+    }
+    let limit = state.shown.get(path.as_slice()).copied().unwrap_or(PAGE);
+    let count = match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter().take(limit) {
+                path.push(Seg::Key(key.clone()));
+                push_rows(child, path, state, rows);
+                path.pop();
+            }
+            map.len()
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate().take(limit) {
+                path.push(Seg::Index(i));
+                push_rows(child, path, state, rows);
+                path.pop();
+            }
+            items.len()
+        }
+        _ => return,
+    };
+    if count > limit {
+        rows.push(JsonRow::More {
+            path: path.clone(),
+            depth: depth + 1,
+            hidden: count - limit,
+        });
+    }
+}
+
+/// Is `value` a scalar containing `query`, which is lowercase?
+fn scalar_matches(value: &Value, query: &str) -> bool {
+    match value {
+        Value::String(s) => s.to_lowercase().contains(query),
+        Value::Object(_) | Value::Array(_) => false,
+        other => other.to_string().contains(query),
+    }
+}
+
+/// Paths of values whose key or scalar value contains `query`, ignoring
+/// case, in document order.
+fn search(value: &Value, query: &str) -> Vec<JsonPath> {
+    fn visit(value: &Value, path: &mut JsonPath, query: &str, found: &mut Vec<JsonPath>) {
+        if found.len() >= MAX_MATCHES {
+            return;
+        }
+        let key_matches = match path.last() {
+            Some(Seg::Key(key)) => key.to_lowercase().contains(query),
+            _ => false,
+        };
+        if key_matches || scalar_matches(value, query) {
+            found.push(path.clone());
+        }
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    path.push(Seg::Key(key.clone()));
+                    visit(child, path, query, found);
+                    path.pop();
+                }
+            }
+            Value::Array(items) => {
+                for (i, child) in items.iter().enumerate() {
+                    path.push(Seg::Index(i));
+                    visit(child, path, query, found);
+                    path.pop();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut found = vec![];
+    if !query.is_empty() {
+        visit(value, &mut vec![], &query.to_lowercase(), &mut found);
+    }
+    found
+}
+
+/// View-model for `json_view`.
+#[derive(Default)]
+struct JsonViewState {
+    expanded: HashSet<JsonPath>,
+    /// How many children of a container are shown, once "Show more" has
+    /// been tapped.
+    shown: HashMap<JsonPath, usize>,
+    selected: Option<JsonPath>,
+    /// The right-click menu, for the row at the path, opened at the point.
+    menu: Option<(JsonPath, LocalPoint)>,
+
+    query: String,
+    /// The last search, and the value and query it was for.
+    matches: Option<(Rc<Value>, String, Vec<JsonPath>)>,
+    /// Which match Next and Previous are on.
+    current: usize,
+    /// Scroll to the current match on the next draw.
+    reveal: bool,
+
+    /// How far the rows are scrolled up, in points.
+    scroll: f32,
+    /// Of the canvas, and of all the rows, as last drawn.
+    height: f32,
+    content_height: f32,
+}
+
+make_lens!(QueryLens, JsonViewState, String, query);
+
+impl JsonViewState {
+    fn new() -> Self {
+        let mut state = Self::default();
+        // The top level starts open.
+        state.expanded.insert(vec![]);
+        state
+    }
+
+    fn rows(&self, value: &Value) -> Vec<JsonRow> {
+        let mut rows = vec![];
+        push_rows(value, &mut vec![], self, &mut rows);
+        rows
+    }
+
+    fn matches(&self) -> &[JsonPath] {
+        match &self.matches {
+            Some((_, _, matches)) => matches,
+            None => &[],
+        }
+    }
+
+    /// Searches again if the query or value changed, opening what's
+    /// needed to show the matches.
+    fn update_search(&mut self, value: &Rc<Value>) {
+        if let Some((v, q, _)) = &self.matches {
+            if Rc::ptr_eq(v, value) && *q == self.query {
+                return;
+            }
+        }
+        let found = search(value, &self.query);
+        for path in &found {
+            for len in 0..path.len() {
+                self.expanded.insert(path[..len].to_vec());
+            }
+        }
+        self.matches = Some((value.clone(), self.query.clone(), found));
+        self.current = 0;
+        self.reveal = true;
+    }
+
+    /// Makes sure the current match isn't past "Show more".
+    fn show_current(&mut self, value: &Value) {
+        let path = match self.matches().get(self.current) {
+            Some(path) => path.clone(),
+            None => return,
+        };
+        for len in 0..path.len() {
+            let parent = &path[..len];
+            let index = match (lookup(value, parent), &path[len]) {
+                (Some(Value::Object(map)), Seg::Key(key)) => map.keys().position(|k| k == key),
+                (_, Seg::Index(i)) => Some(*i),
+                _ => None,
+            };
+            if let Some(index) = index {
+                let shown = self.shown.entry(parent.to_vec()).or_insert(PAGE);
+                while *shown <= index {
+                    *shown += PAGE;
+                }
+            }
+        }
+    }
+
+    /// Which match we're on, and how many there are.
+    fn status(&self) -> String {
+        match self.matches().len() {
+            _ if self.query.is_empty() => String::new(),
+            0 => "No matches".into(),
+            n => format!("{} of {}", self.current % n + 1, n),
+        }
+    }
+
+    fn toggle(&mut self, path: &[Seg]) {
+        if !self.expanded.remove(path) {
+            self.expanded.insert(path.to_vec());
+        }
+    }
+}
+
+const ROW_HEIGHT: f32 = 20.0;
+const FONT_SIZE: u32 = 14;
+const INDENT: f32 = 16.0;
+const MENU_ITEMS: [&str; 2] = ["Copy path", "Copy value"];
+const MENU_WIDTH: f32 = 120.0;
+const MENU_ITEM_HEIGHT: f32 = 22.0;
+
+/// Color for a value of its type.
+fn value_color(value: &Value) -> Color {
+    match value {
+        Value::String(_) => GREEN_HIGHLIGHT,
+        Value::Number(_) => AZURE_HIGHLIGHT,
+        Value::Bool(_) => RED_HIGHLIGHT,
+        Value::Null | Value::Object(_) | Value::Array(_) => MEDIUM_GRAY,
+    }
+}
+
+/// A value as shown on its row. Containers are summarized.
+fn value_text(value: &Value) -> String {
+    let plural = |n: usize, what: &str| format!("{} {}{}", n, what, if n == 1 { "" } else { "s" });
+    match value {
+        Value::Object(map) => format!("{{ {} }}", plural(map.len(), "key")),
+        Value::Array(items) => format!("[ {} ]", plural(items.len(), "item")),
+        Value::String(s) if s.chars().count() > 200 => {
+            let s: String = s.chars().take(200).collect();
+            format!("{}…", Value::from(s))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Draws `s` at `x`, with the top of the row at `top`, returning where
+/// it ends.
+fn draw_text(vger: &mut Vger, s: &str, color: Color, x: f32, top: f32) -> f32 {
+    vger.save();
+    vger.translate([x, top - 2.0]);
+    vger.text(s, FONT_SIZE, color, None);
+    vger.restore();
+    x + vger.text_bounds(s, FONT_SIZE, None).width()
+}
+
+/// Moves to the next or previous match, and scrolls to it.
+fn step(cx: &mut Context, state: StateHandle<JsonViewState>, value: &Rc<Value>, by: isize) {
+    cx[state].update_search(value);
+    let count = cx[state].matches().len() as isize;
+    if count > 0 {
+        let current = (cx[state].current as isize + by).rem_euclid(count);
+        cx[state].current = current as usize;
+        cx[state].reveal = true;
+    }
+}
+
+/// Copies the path or the value of the row at `path`.
+fn copy(cx: &mut Context, value: &Value, path: &[Seg], item: usize) {
+    let s = match item {
+        0 => path_string(path),
+        _ => match lookup(value, path) {
+            Some(v) => serde_json::to_string_pretty(v).unwrap_or_default(),
+            None => return,
+        },
+    };
+    cx.clipboard().set_text(&s);
+}
+
+fn json_canvas(value: Rc<Value>, state: StateHandle<JsonViewState>) -> impl View {
+    let tap_value = value.clone();
+    canvas(move |cx, rect, vger| {
+        cx[state].update_search(&value);
+        if cx[state].reveal {
+            cx[state].show_current(&value);
+        }
+        let rows = cx[state].rows(&value);
+
+        let content_height = rows.len() as f32 * ROW_HEIGHT;
+        let current = cx[state].matches().get(cx[state].current).cloned();
+        if cx[state].reveal {
+            cx[state].reveal = false;
+            let row = rows.iter().position(|r| match (r, &current) {
+                (JsonRow::Value { path, .. }, Some(c)) => path == c,
+                _ => false,
+            });
+            if let Some(row) = row {
+                // A couple of rows down from the top.
+                let y = row.saturating_sub(2) as f32 * ROW_HEIGHT;
+                cx[state].scroll = y;
+            }
+        }
+        let scroll = cx[state]
+            .scroll
+            .min(content_height - rect.height())
+            .max(0.0);
+        if cx[state].scroll != scroll {
+            cx[state].scroll = scroll;
+        }
+        cx[state].height = rect.height();
+        cx[state].content_height = content_height;
+
+        let matched: HashSet<&JsonPath> = cx[state].matches().iter().collect();
+        let selected = cx[state].selected.clone();
+        let first = (scroll / ROW_HEIGHT) as usize;
+        let last = (((scroll + rect.height()) / ROW_HEIGHT).ceil() as usize).min(rows.len());
+        for (i, row) in rows.iter().enumerate().take(last).skip(first) {
+            let top = rect.height() + scroll - i as f32 * ROW_HEIGHT;
+            let row_rect = LocalRect::new(
+                [0.0, top - ROW_HEIGHT].into(),
+                [rect.width(), ROW_HEIGHT].into(),
+            );
+
+            let (path, depth) = match row {
+                JsonRow::Value { path, depth } => (path, *depth),
+                JsonRow::More { depth, hidden, .. } => {
+                    let label = format!("Show {} more of {}", PAGE.min(*hidden), hidden);
+                    draw_text(
+                        vger,
+                        &label,
+                        AZURE_HIGHLIGHT,
+                        *depth as f32 * INDENT + 14.0,
+                        top,
+                    );
+                    continue;
+                }
+            };
+
+            if selected.as_ref() == Some(path) {
+                let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.4));
+                vger.fill_rect(row_rect, 0.0, paint);
+            } else if matched.contains(path) {
+                let alpha = if current.as_ref() == Some(path) {
+                    0.6
+                } else {
+                    0.25
+                };
+                let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(alpha));
+                vger.fill_rect(row_rect, 0.0, paint);
+            }
+
+            let v = match lookup(&value, path) {
+                Some(v) => v,
+                None => continue,
+            };
+            let x = depth as f32 * INDENT;
+
+            // A disclosure triangle for containers.
+            if matches!(v, Value::Object(_) | Value::Array(_)) {
+                let c = LocalPoint::new(x + 6.0, top - ROW_HEIGHT / 2.0);
+                let paint = vger.color_paint(TEXT_COLOR);
+                if cx[state].expanded.contains(path) {
+                    vger.stroke_segment([c.x - 4.0, c.y + 2.0], [c.x, c.y - 2.0], 1.5, paint);
+                    vger.stroke_segment([c.x, c.y - 2.0], [c.x + 4.0, c.y + 2.0], 1.5, paint);
+                } else {
+                    vger.stroke_segment([c.x - 2.0, c.y + 4.0], [c.x + 2.0, c.y], 1.5, paint);
+                    vger.stroke_segment([c.x + 2.0, c.y], [c.x - 2.0, c.y - 4.0], 1.5, paint);
+                }
+            }
+
+            let mut x = x + 14.0;
+            match path.last() {
+                Some(Seg::Key(key)) => {
+                    let label = format!("{}:", Value::from(key.as_str()));
+                    x = draw_text(vger, &label, TEXT_COLOR, x, top) + 6.0;
+                }
+                Some(Seg::Index(i)) => {
+                    x = draw_text(vger, &format!("{}:", i), MEDIUM_GRAY, x, top) + 6.0;
+                }
+                None => (),
+            }
+            draw_text(vger, &value_text(v), value_color(v), x, top);
+        }
+
+        if let Some((_, p)) = &cx[state].menu {
+            let menu = LocalRect::new(
+                [p.x, p.y - MENU_ITEM_HEIGHT * MENU_ITEMS.len() as f32].into(),
+                [MENU_WIDTH, MENU_ITEM_HEIGHT * MENU_ITEMS.len() as f32].into(),
+            );
+            let paint = vger.color_paint(CONTROL_BACKGROUND);
+            vger.fill_rect(menu, 4.0, paint);
+            for (i, item) in MENU_ITEMS.iter().enumerate() {
+                let top = p.y - i as f32 * MENU_ITEM_HEIGHT;
+                draw_text(vger, item, TEXT_COLOR, p.x + 8.0, top);
+            }
+        }
+    })
+    .clip()
+    .tap_p(move |cx, p, button| {
+        let value = &tap_value;
+
+        // A tap on the menu chooses from it, and anywhere else closes it.
+        if let Some((path, at)) = cx[state].menu.take() {
+            let item = ((at.y - p.y) / MENU_ITEM_HEIGHT).floor();
+            if (at.x..at.x + MENU_WIDTH).contains(&p.x)
+                && (0.0..MENU_ITEMS.len() as f32).contains(&item)
+            {
+                copy(cx, value, &path, item as usize);
+            }
+            return;
+        }
+
+        // Rows go down from the top of the canvas.
+        let row = ((cx[state].height + cx[state].scroll - p.y) / ROW_HEIGHT) as usize;
+        let rows = cx[state].rows(value);
+        match rows.get(row) {
+            Some(JsonRow::Value { path, .. }) => {
+                cx[state].selected = Some(path.clone());
+                if matches!(button, Some(MouseButton::Right)) {
+                    cx[state].menu = Some((path.clone(), p));
+                } else if matches!(
+                    lookup(value, path),
+                    Some(Value::Object(_) | Value::Array(_))
+                ) {
+                    cx[state].toggle(path);
+                }
+            }
+            Some(JsonRow::More { path, hidden, .. }) => {
+                let shown = cx[state].shown.entry(path.clone()).or_insert(PAGE);
+                *shown += PAGE.min(*hidden);
+            }
+            None => (),
+        }
+    })
+    .scroll(move |cx, delta| {
+        let max = (cx[state].content_height - cx[state].height).max(0.0);
+        cx[state].scroll = (cx[state].scroll + delta.y).clamp(0.0, max);
+    })
+}
+
+/// Shows a JSON value as a tree. Objects and arrays open and close when
+/// tapped, and only what's open is laid out, a page of children at a
+/// time, so huge documents stay quick. Values are colored by type.
+///
+/// Right-clicking a row offers to copy its path, like
+/// `$.users[0].name`, or its value. The search field above finds keys
+/// and values, opening what's needed to show them.
+///
+/// Needs the `json-view` feature.
+///
+/// ```no_run
+/// # use rui::*;
+/// json_view(serde_json::json!({ "name": "rui", "tags": ["ui", "gpu"] }));
+/// ```
+pub fn json_view(value: impl Into<Rc<Value>>) -> impl View {
+    let value: Rc<Value> = value.into();
+    state(JsonViewState::new, move |state, cx| {
+        let (prev, next, submit) = (value.clone(), value.clone(), value.clone());
+        vstack((
+            hstack((
+                text_field(bind(state, QueryLens))
+                    .placeholder("Search")
+                    .on_submit(move |cx, _| step(cx, state, &submit, 1))
+                    .flex(),
+                text(&cx[state].status()).padding(Auto),
+                button("Previous", move |cx| step(cx, state, &prev, -1)),
+                button("Next", move |cx| step(cx, state, &next, 1)),
+            )),
+            json_canvas(value.clone(), state),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_path_string() {
+        let path = vec![
+            Seg::Key("users".into()),
+            Seg::Index(0),
+            Seg::Key("first name".into()),
+        ];
+        assert_eq!(path_string(&path), "$.users[0][\"first name\"]");
+        assert_eq!(path_string(&[]), "$");
+    }
+
+    #[test]
+    fn test_json_rows() {
+        let value = json!({ "a": [1, 2, 3], "b": { "c": null } });
+        let mut state = JsonViewState::new();
+        let paths = |rows: Vec<JsonRow>| -> Vec<String> {
+            rows.iter()
+                .map(|r| match r {
+                    JsonRow::Value { path, .. } => path_string(path),
+                    JsonRow::More { hidden, .. } => format!("{} more", hidden),
+                })
+                .collect()
+        };
+        assert_eq!(paths(state.rows(&value)), vec!["$", "$.a", "$.b"]);
+
+        state.toggle(&[Seg::Key("a".into())]);
+        state.shown.insert(vec![Seg::Key("a".into())], 2);
+        assert_eq!(
+            paths(state.rows(&value)),
+            vec!["$", "$.a", "$.a[0]", "$.a[1]", "1 more", "$.b"]
+        );
+    }
+
+    #[test]
+    fn test_json_search() {
+        let value = Rc::new(json!({ "name": "Rui", "items": [{ "label": "rust" }] }));
+        let mut state = JsonViewState::new();
+        state.query = "RU".into();
+        state.update_search(&value);
+        let found: Vec<String> = state.matches().iter().map(|p| path_string(p)).collect();
+        assert_eq!(found, vec!["$.items[0].label", "$.name"]);
+        // Opened to show the matches.
+        assert!(state
+            .expanded
+            .contains(&vec![Seg::Key("items".into()), Seg::Index(0)]));
+        assert_eq!(state.status(), "1 of 2");
+    }
+}
@@ -1,10 +1,13 @@
 use crate::*;
 use std::any::Any;
 
-/// Struct for the `key` modifier.
+/// Struct for the `key` and `key_up` modifiers. Key events stop here;
+/// everything else is passed on to the child.
 pub struct KeyView<V, F> {
     child: V,
     func: F,
+    /// Responds to releases rather than presses.
+    up: bool,
 }
 
 impl<V, F, A> KeyView<V, F>
@@ -13,7 +16,19 @@ where
     F: Fn(&mut Context, Key) -> A + 'static,
 {
     pub fn new(v: V, f: F) -> Self {
-        KeyView { child: v, func: f }
+        KeyView {
+            child: v,
+            func: f,
+            up: false,
+        }
+    }
+
+    pub fn new_up(v: V, f: F) -> Self {
+        KeyView {
+            child: v,
+            func: f,
+            up: true,
+        }
     }
 }
 
@@ -26,12 +41,27 @@ where
     fn process(
         &self,
         event: &Event,
-        _path: &mut IdPath,
+        path: &mut IdPath,
         cx: &mut Context,
         actions: &mut Vec<Box<dyn Any>>,
     ) {
-        if let Event::Key(key) = &event {
-            actions.push(Box::new((self.func)(cx, key.clone())));
+        match event {
+            Event::Key { key, .. } => {
+                if !self.up {
+                    actions.push(Box::new((self.func)(cx, *key)));
+                }
+            }
+            Event::KeyUp { key, .. } => {
+                if self.up {
+                    actions.push(Box::new((self.func)(cx, *key)));
+                }
+            }
+            // Keys stop here, but taps and the like reach the child.
+            _ => {
+                path.push(0);
+                self.child.process(event, path, cx, actions);
+                path.pop();
+            }
         }
     }
 
@@ -87,3 +117,58 @@ where
 }
 
 impl<V, F> private::Sealed for KeyView<V, F> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_key_forwarding() {
+        let mut cx = Context::new();
+        let inner = Rc::new(Cell::new(0));
+        let outer = Rc::new(Cell::new(0));
+        let taps = Rc::new(Cell::new(0));
+        let (i, o, t) = (inner.clone(), outer.clone(), taps.clone());
+        let ui = rectangle()
+            .tap(move |_| t.set(t.get() + 1))
+            .key(move |_, _| i.set(i.get() + 1))
+            .key(move |_, _| o.set(o.get() + 1));
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let events = [
+            Event::Key {
+                key: Key::Enter,
+                mods: KeyboardModifiers::default(),
+                repeat: false,
+            },
+            Event::TouchBegin {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+        ];
+        let mut actions = vec![];
+        for event in &events {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+
+        // The outer handler has the key to itself, but the tap gets through.
+        assert_eq!(outer.get(), 1);
+        assert_eq!(inner.get(), 0);
+        assert_eq!(taps.get(), 1);
+    }
+}
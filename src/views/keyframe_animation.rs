@@ -0,0 +1,316 @@
+use crate::*;
+use std::any::Any;
+
+/// Where a keyframe animation is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Playhead {
+    /// The trigger which last started the animation.
+    trigger: u64,
+    /// Seconds since it started, or `None` if it hasn't.
+    elapsed: Option<f32>,
+}
+
+/// Struct for `keyframe_animation`.
+pub struct KeyframeAnimation<T, F> {
+    trigger: u64,
+    keyframes: Keyframes<T>,
+    func: F,
+}
+
+impl<T, V, F> KeyframeAnimation<T, F>
+where
+    T: Animatable,
+    V: View,
+    F: Fn(T, &mut Context) -> V + 'static,
+{
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<Playhead> {
+        let vid = cx.view_id(path);
+        let trigger = self.trigger;
+        // Repeating animations play from the start.
+        let elapsed = if self.keyframes.repeats() {
+            Some(0.0)
+        } else {
+            None
+        };
+        cx.init_state(vid, &move || Playhead { trigger, elapsed });
+        StateHandle::new(vid)
+    }
+
+    fn body(&self, path: &IdPath, cx: &mut Context) -> V {
+        let elapsed = self.state(path, cx).get(cx).elapsed;
+        let value = self.keyframes.value_at(elapsed.unwrap_or(0.0));
+        (self.func)(value, cx)
+    }
+}
+
+impl<T, V, F> View for KeyframeAnimation<T, F>
+where
+    T: Animatable,
+    V: View,
+    F: Fn(T, &mut Context) -> V + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            let s = self.state(path, cx);
+            let playhead = *s.get(cx);
+            let duration = self.keyframes.period();
+            let next = if playhead.trigger != self.trigger {
+                Playhead {
+                    trigger: self.trigger,
+                    elapsed: Some(0.0),
+                }
+            } else if let (Some(_), Some(t)) = (playhead.elapsed, cx.anim_scrub()) {
+                Playhead {
+                    elapsed: Some(t * duration),
+                    ..playhead
+                }
+            } else {
+                match playhead.elapsed {
+                    Some(t) if self.keyframes.repeats() => Playhead {
+                        elapsed: Some((t + cx.anim_dt) % duration.max(f32::EPSILON)),
+                        ..playhead
+                    },
+                    Some(t) if t < duration => Playhead {
+                        elapsed: Some((t + cx.anim_dt).min(duration)),
+                        ..playhead
+                    },
+                    _ => playhead,
+                }
+            };
+            // Writing keeps animation frames coming, so stop at the end.
+            if next != playhead {
+                cx[s] = next;
+            }
+            match next.elapsed {
+                Some(t) if self.keyframes.repeats() || t < duration => {
+                    cx.note_animation("keyframes", Some(t / duration.max(f32::EPSILON)));
+                }
+                _ => (),
+            }
+        }
+
+        path.push(0);
+        self.body(path, cx).process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.body(path, args.cx).draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.body(path, args.cx).layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.body(path, cx).dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.body(path, cx).hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.body(path, cx).commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.body(path, cx).gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.body(path, cx).access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<T, F> private::Sealed for KeyframeAnimation<T, F> {}
+
+/// Plays `keyframes` each time `trigger` changes, building a view from the
+/// value as it goes. Bump a counter to play it. Repeating keyframes play
+/// all the time.
+pub fn keyframe_animation<T: Animatable, V: View, F: Fn(T, &mut Context) -> V + 'static>(
+    trigger: u64,
+    keyframes: Keyframes<T>,
+    f: F,
+) -> KeyframeAnimation<T, F> {
+    KeyframeAnimation {
+        trigger,
+        keyframes,
+        func: f,
+    }
+}
+
+/// Struct for the `keyframes` modifier.
+pub struct KeyframeDriver<V, B, T> {
+    child: V,
+    binding: B,
+    keyframes: Keyframes<T>,
+}
+
+impl<V, B, T> KeyframeDriver<V, B, T>
+where
+    V: View,
+    B: Binding<T>,
+    T: Animatable,
+{
+    pub fn new(child: V, binding: B, keyframes: Keyframes<T>) -> Self {
+        Self {
+            child,
+            binding,
+            keyframes,
+        }
+    }
+}
+
+impl<V, B, T> View for KeyframeDriver<V, B, T>
+where
+    V: View,
+    B: Binding<T>,
+    T: Animatable,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            // Seconds since the view appeared.
+            let vid = cx.view_id(path);
+            cx.init_state(vid, &|| 0.0_f32);
+            let s = StateHandle::<f32>::new(vid);
+            let elapsed = *s.get(cx);
+
+            let period = self.keyframes.period();
+            if self.keyframes.repeats() || elapsed < period {
+                let elapsed = match cx.anim_scrub() {
+                    Some(t) => t * period,
+                    None => elapsed + cx.anim_dt,
+                };
+                cx.note_animation("keyframes", Some(elapsed / period.max(f32::EPSILON)));
+                let value = self.keyframes.value_at(elapsed);
+                if *self.binding.get(cx) != value {
+                    *self.binding.get_mut(cx) = value;
+                }
+                cx[s] = elapsed;
+            }
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.child.hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B, T> private::Sealed for KeyframeDriver<V, B, T> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_keyframe_driver() {
+        let mut cx = Context::new();
+        let ui = state(
+            || 0.0_f32,
+            |s, _| {
+                rectangle().keyframes(s, Keyframes::new().at(0.0, 0.0).at(1.0, 10.0).duration(0.5))
+            },
+        );
+
+        let mut path = vec![0];
+        let mut actions = vec![];
+        cx.anim_dt = 0.25;
+        ui.process(&Event::Anim, &mut path, &mut cx, &mut actions);
+        let s = StateHandle::<f32>::new(cx.view_id(&path));
+        assert_eq!(*s.get(&cx), 5.0);
+
+        // Stops at the end.
+        for _ in 0..4 {
+            ui.process(&Event::Anim, &mut path, &mut cx, &mut actions);
+        }
+        assert_eq!(*s.get(&cx), 10.0);
+    }
+}
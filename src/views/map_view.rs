@@ -0,0 +1,551 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::f64::consts::PI;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Width and height of a map tile, in pixels.
+const TILE_SIZE: f64 = 256.0;
+/// Furthest north or south Web Mercator goes.
+const MAX_LATITUDE: f64 = 85.051_128_78;
+/// How many zoom levels out to look for a tile to stand in while one loads.
+const FALLBACK_LEVELS: u32 = 3;
+const MARKER_SIZE: f32 = 18.0;
+/// How far a drag can move and still count as a tap on a marker.
+const TAP_SLOP: f32 = 4.0;
+
+/// A place on Earth, in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LatLng {
+    pub lat: f64,
+    pub lng: f64,
+}
+
+impl LatLng {
+    pub fn new(lat: f64, lng: f64) -> Self {
+        Self { lat, lng }
+    }
+}
+
+/// Pixels from the top left of the world at `zoom`, in Web Mercator.
+fn world_point(p: LatLng, zoom: u32) -> (f64, f64) {
+    let size = TILE_SIZE * (1u64 << zoom) as f64;
+    let lat = p.lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = (p.lng + 180.0) / 360.0;
+    let y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0;
+    (x * size, y * size)
+}
+
+fn from_world(x: f64, y: f64, zoom: u32) -> LatLng {
+    let size = TILE_SIZE * (1u64 << zoom) as f64;
+    let lng = (x / size * 360.0).rem_euclid(360.0) - 180.0;
+    let lat = (PI * (1.0 - 2.0 * y / size)).sinh().atan().to_degrees();
+    LatLng::new(lat.clamp(-MAX_LATITUDE, MAX_LATITUDE), lng)
+}
+
+/// A tile to draw, where it goes, and which part of it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Tile {
+    x: u32,
+    y: u32,
+    z: u32,
+    rect: LocalRect,
+}
+
+/// What a `map_view` of `size` shows, centered on `center` at a
+/// fractional `zoom`, using tiles from the nearest whole zoom.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Viewport {
+    /// The tile zoom.
+    z: u32,
+    /// The center, in pixels at `z`.
+    center: (f64, f64),
+    /// Points per pixel at `z`.
+    scale: f64,
+    size: LocalSize,
+}
+
+impl Viewport {
+    fn new(center: LatLng, zoom: f64, size: LocalSize) -> Self {
+        let z = zoom.round().max(0.0) as u32;
+        Self {
+            z,
+            center: world_point(center, z),
+            scale: 2f64.powf(zoom - z as f64),
+            size,
+        }
+    }
+
+    /// Where `p` is in the view, with y going up.
+    fn local(&self, p: LatLng) -> LocalPoint {
+        let (x, y) = world_point(p, self.z);
+        let world = TILE_SIZE * (1u64 << self.z) as f64;
+        // The nearest copy of the world, across the date line.
+        let dx = (x - self.center.0 + world / 2.0).rem_euclid(world) - world / 2.0;
+        LocalPoint::new(
+            self.size.width / 2.0 + (dx * self.scale) as f32,
+            self.size.height / 2.0 - ((y - self.center.1) * self.scale) as f32,
+        )
+    }
+
+    fn lat_lng(&self, p: LocalPoint) -> LatLng {
+        let x = self.center.0 + (p.x - self.size.width / 2.0) as f64 / self.scale;
+        let y = self.center.1 - (p.y - self.size.height / 2.0) as f64 / self.scale;
+        from_world(x, y, self.z)
+    }
+
+    /// The center after dragging the map by `delta` points.
+    fn panned(&self, delta: LocalVector) -> LatLng {
+        let center = LocalPoint::new(self.size.width / 2.0, self.size.height / 2.0);
+        self.lat_lng(center - delta)
+    }
+
+    /// The tiles covering the view, wrapping around east to west.
+    fn tiles(&self) -> Vec<Tile> {
+        let count = 1i64 << self.z;
+        let span = |c: f64, half: f32| {
+            let half = half as f64 / self.scale;
+            (
+                ((c - half) / TILE_SIZE).floor() as i64,
+                ((c + half) / TILE_SIZE).ceil() as i64 - 1,
+            )
+        };
+        let (x0, x1) = span(self.center.0, self.size.width / 2.0);
+        let (y0, y1) = span(self.center.1, self.size.height / 2.0);
+        let size = (TILE_SIZE * self.scale) as f32;
+        let mut tiles = vec![];
+        for ty in y0.max(0)..=y1.min(count - 1) {
+            for tx in x0..=x1 {
+                let left = (tx as f64 * TILE_SIZE - self.center.0) * self.scale;
+                let top = (ty as f64 * TILE_SIZE - self.center.1) * self.scale;
+                let x = self.size.width / 2.0 + left as f32;
+                let y = self.size.height / 2.0 - top as f32 - size;
+                tiles.push(Tile {
+                    x: tx.rem_euclid(count) as u32,
+                    y: ty as u32,
+                    z: self.z,
+                    rect: LocalRect::new([x, y].into(), [size, size].into()),
+                });
+            }
+        }
+        tiles
+    }
+}
+
+/// A pin on a `map_view`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapMarker {
+    pub position: LatLng,
+    pub title: String,
+    pub color: Color,
+}
+
+impl MapMarker {
+    pub fn new(position: LatLng, title: &str) -> Self {
+        Self {
+            position,
+            title: title.into(),
+            color: RED_HIGHLIGHT,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// A round pin in `color` with a white edge, at twice its size in points.
+fn marker_image(color: Color) -> RgbaImage {
+    let size = (MARKER_SIZE * 2.0) as usize;
+    let (c, r) = (size as f32 / 2.0, size as f32 / 2.0 - 1.0);
+    let mut rgba = Vec::with_capacity(size * size * 4);
+    for y in 0..size {
+        for x in 0..size {
+            let d = ((x as f32 + 0.5 - c).powi(2) + (y as f32 + 0.5 - c).powi(2)).sqrt();
+            let coverage = (r - d + 0.5).clamp(0.0, 1.0);
+            let edge = (d - (r - 4.0) + 0.5).clamp(0.0, 1.0);
+            let channel = |v: f32| ((v * (1.0 - edge) + edge) * 255.0).round() as u8;
+            rgba.extend_from_slice(&[
+                channel(color.r),
+                channel(color.g),
+                channel(color.b),
+                (coverage * 255.0).round() as u8,
+            ]);
+        }
+    }
+    RgbaImage {
+        width: size,
+        height: size,
+        rgba,
+    }
+}
+
+/// Options for `map_view`. Set them with `MapViewMods`.
+#[derive(Clone)]
+pub struct MapViewOptions {
+    tile_url: Rc<str>,
+    attribution: Rc<str>,
+    max_zoom: f64,
+    markers: Rc<Vec<MapMarker>>,
+    on_marker: Option<Rc<dyn Fn(&mut Context, usize)>>,
+}
+
+impl Default for MapViewOptions {
+    fn default() -> Self {
+        Self {
+            tile_url: "https://tile.openstreetmap.org/{z}/{x}/{y}.png".into(),
+            attribution: "© OpenStreetMap contributors".into(),
+            max_zoom: 19.0,
+            markers: Default::default(),
+            on_marker: None,
+        }
+    }
+}
+
+impl MapViewOptions {
+    fn url(&self, x: u32, y: u32, z: u32) -> String {
+        self.tile_url
+            .replace("{z}", &z.to_string())
+            .replace("{x}", &x.to_string())
+            .replace("{y}", &y.to_string())
+    }
+}
+
+pub trait MapViewMods: View + Sized {
+    /// Fetches tiles from `url`, with `{z}`, `{x}` and `{y}` in it, and
+    /// credits them with `attribution`, under the map. The default is
+    /// OpenStreetMap's tile server, whose usage policy suits light use.
+    fn tiles(self, url: &str, attribution: &str) -> Self;
+
+    /// Stops zooming in at `zoom`. The default is 19.
+    fn max_zoom(self, zoom: f64) -> Self;
+
+    /// Shows pins at `markers`.
+    fn markers(self, markers: Vec<MapMarker>) -> Self;
+
+    /// Calls `f` with the index of a marker when it's tapped.
+    fn on_marker(self, f: impl Fn(&mut Context, usize) + 'static) -> Self;
+}
+
+impl<F> MapViewMods for ModView<MapViewOptions, F>
+where
+    ModView<MapViewOptions, F>: View,
+{
+    fn tiles(self, url: &str, attribution: &str) -> Self {
+        let mut opts = self.value;
+        opts.tile_url = url.into();
+        opts.attribution = attribution.into();
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn max_zoom(self, zoom: f64) -> Self {
+        let mut opts = self.value;
+        opts.max_zoom = zoom;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn markers(self, markers: Vec<MapMarker>) -> Self {
+        let mut opts = self.value;
+        opts.markers = Rc::new(markers);
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_marker(self, f: impl Fn(&mut Context, usize) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_marker = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct MapViewState {
+    size: LocalSize,
+    /// Where a drag started, and where it's got to.
+    drag: Option<(LocalPoint, LocalPoint)>,
+    pinch_zoom: f64,
+}
+
+fn image_key(name: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn fetch_tile(url: &str) -> Result<RgbaImage, String> {
+    let response = ureq::get(url)
+        .set("User-Agent", concat!("rui/", env!("CARGO_PKG_VERSION")))
+        .call()
+        .map_err(|err| format!("{}: {}", url, err))?;
+    let mut data = vec![];
+    std::io::Read::read_to_end(&mut response.into_reader(), &mut data)
+        .map_err(|err| format!("{}: {}", url, err))?;
+    decode_image(&data)
+}
+
+/// The key of the tile at `url` in the `Context`'s image cache, once it's
+/// there. Tiles are fetched in the background, then moved from the asset
+/// cache to the image cache, which lets them go when they haven't been
+/// drawn for a while.
+fn load_tile(cx: &mut Context, url: String, fetch: bool) -> Option<u64> {
+    let key = image_key(&url);
+    if cx.images.get(key).is_some() {
+        return Some(key);
+    }
+    if !fetch {
+        return None;
+    }
+    let asset = format!("rui map tile {}", url);
+    match cx.load_asset(&asset, move || fetch_tile(&url)) {
+        Asset::Ready(image) => {
+            cx.images.insert(key, image);
+            cx.evict_asset(&asset);
+            Some(key)
+        }
+        Asset::Loading | Asset::Failed(_) => None,
+    }
+}
+
+/// The key of the pin image for `color`, made on first use.
+fn marker_key(cx: &mut Context, color: Color) -> u64 {
+    let key = image_key(&format!("rui map marker {:?}", color));
+    if cx.images.get(key).is_none() {
+        cx.images.insert(key, Arc::new(marker_image(color)));
+    }
+    key
+}
+
+/// The marker at `p`, topmost first.
+fn marker_at(view: &Viewport, markers: &[MapMarker], p: LocalPoint) -> Option<usize> {
+    markers
+        .iter()
+        .rposition(|m| (view.local(m.position) - p).length() <= MARKER_SIZE / 2.0)
+}
+
+fn map_canvas(
+    center: impl Binding<LatLng>,
+    zoom: impl Binding<f64>,
+    state: StateHandle<MapViewState>,
+    opts: MapViewOptions,
+) -> impl View {
+    let (drag_opts, scroll_opts, pinch_opts) = (opts.clone(), opts.clone(), opts.clone());
+    canvas(move |cx, rect, vger| {
+        cx[state].size = rect.size;
+        let view = Viewport::new(*center.get(cx), *zoom.get(cx), rect.size);
+        let paint = vger.color_paint(CONTROL_BACKGROUND);
+        vger.fill_rect(rect, 0.0, paint);
+
+        let xform = vger.current_transform();
+        let (clip, layer) = (cx.clip_rect, cx.layer);
+        for tile in view.tiles() {
+            // While a tile loads, a part of one further out stands in.
+            let found = (0..=FALLBACK_LEVELS.min(tile.z)).find_map(|up| {
+                let (x, y, z) = (tile.x >> up, tile.y >> up, tile.z - up);
+                let key = load_tile(cx, opts.url(x, y, z), up == 0)?;
+                let n = (1u32 << up) as f32;
+                let uv = LocalRect::new(
+                    [
+                        (tile.x % (1 << up)) as f32 / n,
+                        (tile.y % (1 << up)) as f32 / n,
+                    ]
+                    .into(),
+                    [1.0 / n, 1.0 / n].into(),
+                );
+                Some((key, uv))
+            });
+            if let Some((key, uv)) = found {
+                let world = xform.outer_transformed_rect(&tile.rect);
+                cx.images.draw(key, world, uv, 0.0, clip, layer);
+            }
+        }
+
+        // Markers are images too, so they show over the tiles.
+        let all = LocalRect::new(LocalPoint::zero(), [1.0, 1.0].into());
+        for marker in opts.markers.iter() {
+            let p = view.local(marker.position);
+            let pin = LocalRect::new(
+                p - LocalVector::new(MARKER_SIZE, MARKER_SIZE) / 2.0,
+                [MARKER_SIZE, MARKER_SIZE].into(),
+            );
+            if pin.intersects(&rect) {
+                let key = marker_key(cx, marker.color);
+                let world = xform.outer_transformed_rect(&pin);
+                cx.images.draw(key, world, all, 0.0, clip, layer);
+            }
+        }
+    })
+    .clip()
+    .drag_p(move |cx, p, gesture, _| {
+        let size = cx[state].size;
+        let view = Viewport::new(*center.get(cx), *zoom.get(cx), size);
+        match (gesture, cx[state].drag) {
+            (GestureState::Began, _) => cx[state].drag = Some((p, p)),
+            (GestureState::Changed, Some((start, last))) => {
+                center.with_mut(cx, |c| *c = view.panned(p - last));
+                cx[state].drag = Some((start, p));
+            }
+            (GestureState::Ended, Some((start, _))) => {
+                cx[state].drag = None;
+                if (p - start).length() <= TAP_SLOP {
+                    let opts = &drag_opts;
+                    if let (Some(f), Some(i)) =
+                        (&opts.on_marker, marker_at(&view, &opts.markers, p))
+                    {
+                        f(cx, i);
+                    }
+                }
+            }
+            _ => (),
+        }
+    })
+    .scroll(move |cx, delta| {
+        if cx.key_mods.shortcut() {
+            let max = scroll_opts.max_zoom;
+            zoom.with_mut(cx, |z| *z = (*z + (delta.y * 0.01) as f64).clamp(0.0, max));
+        } else {
+            let view = Viewport::new(*center.get(cx), *zoom.get(cx), cx[state].size);
+            center.with_mut(cx, |c| *c = view.panned(delta));
+        }
+    })
+    .on_pinch(move |cx, scale, _, gesture| {
+        if gesture == GestureState::Began {
+            cx[state].pinch_zoom = *zoom.get(cx);
+        }
+        let (from, max) = (cx[state].pinch_zoom, pinch_opts.max_zoom);
+        zoom.with_mut(cx, |z| *z = (from + (scale as f64).log2()).clamp(0.0, max));
+    })
+}
+
+/// Shows a map of slippy-map raster tiles, centered on `center` at
+/// `zoom`, from 0 for the whole world to around 19 for streets. Dragging
+/// or scrolling pans it, and pinching, or scrolling with the shortcut key,
+/// zooms, updating the bindings. Tiles are fetched and decoded in the
+/// background, standing in parts of tiles further out until they arrive,
+/// and kept until they haven't been drawn for a while.
+///
+/// Needs the `maps` feature.
+///
+/// ```no_run
+/// # use rui::*;
+/// state(
+///     || LatLng::new(51.5072, -0.1276),
+///     |center, _| {
+///         state(
+///             || 12.0,
+///             move |zoom, _| {
+///                 let tower = LatLng::new(51.5081, -0.0759);
+///                 map_view(center, zoom)
+///                     .markers(vec![MapMarker::new(tower, "Tower of London")])
+///                     .on_marker(|_, i| println!("tapped marker {}", i))
+///             },
+///         )
+///     },
+/// );
+/// ```
+pub fn map_view(center: impl Binding<LatLng>, zoom: impl Binding<f64>) -> impl MapViewMods {
+    modview(move |opts: MapViewOptions, _| {
+        state(MapViewState::default, move |state, _| {
+            vstack((
+                map_canvas(center, zoom, state, opts.clone()),
+                text(&opts.attribution).font_size(10).color(MEDIUM_GRAY),
+            ))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn close(a: LatLng, b: LatLng) -> bool {
+        (a.lat - b.lat).abs() < 1e-9 && (a.lng - b.lng).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_projection() {
+        assert_eq!(world_point(LatLng::new(0.0, 0.0), 0), (128.0, 128.0));
+        assert_eq!(world_point(LatLng::new(0.0, -180.0), 1), (0.0, 256.0));
+        let (_, top) = world_point(LatLng::new(90.0, 0.0), 0);
+        assert!(top.abs() < 1e-6);
+
+        let london = LatLng::new(51.5072, -0.1276);
+        let (x, y) = world_point(london, 12);
+        assert!(close(from_world(x, y, 12), london));
+    }
+
+    #[test]
+    fn test_viewport() {
+        let size = LocalSize::new(512.0, 256.0);
+        let view = Viewport::new(LatLng::new(0.0, 0.0), 1.0, size);
+        assert_eq!(
+            view.local(LatLng::new(0.0, 0.0)),
+            LocalPoint::new(256.0, 128.0)
+        );
+        assert!(close(
+            view.lat_lng(LocalPoint::new(256.0, 128.0)),
+            LatLng::new(0.0, 0.0)
+        ));
+
+        // The whole world, two tiles across at zoom 1, with the top half
+        // above the center.
+        let tiles = view.tiles();
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(
+            tiles[0].rect,
+            LocalRect::new([0.0, 128.0].into(), [256.0, 256.0].into())
+        );
+        assert_eq!((tiles[0].x, tiles[0].y), (0, 0));
+
+        // Dragging right moves the center west.
+        assert!(view.panned(LocalVector::new(10.0, 0.0)).lng < 0.0);
+
+        // Half way between zooms, tiles from the nearer are scaled.
+        let view = Viewport::new(LatLng::new(0.0, 0.0), 1.4, size);
+        assert_eq!(view.z, 1);
+        assert!((view.scale - 2f64.powf(0.4)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_viewport_wraps() {
+        let view = Viewport::new(LatLng::new(0.0, 179.0), 2.0, LocalSize::new(512.0, 256.0));
+        let columns: Vec<u32> = view.tiles().iter().map(|t| t.x).collect();
+        assert!(columns.contains(&3) && columns.contains(&0));
+        assert!(view.local(LatLng::new(0.0, -179.0)).x > 256.0);
+    }
+
+    #[test]
+    fn test_marker_at() {
+        let view = Viewport::new(LatLng::new(0.0, 0.0), 3.0, LocalSize::new(200.0, 200.0));
+        let markers = vec![
+            MapMarker::new(LatLng::new(0.0, 0.0), "a"),
+            MapMarker::new(LatLng::new(0.0, 0.0), "b"),
+        ];
+        assert_eq!(
+            marker_at(&view, &markers, LocalPoint::new(103.0, 98.0)),
+            Some(1)
+        );
+        assert_eq!(
+            marker_at(&view, &markers, LocalPoint::new(150.0, 100.0)),
+            None
+        );
+
+        let image = marker_image(RED_HIGHLIGHT);
+        assert_eq!(image.rgba.len(), image.width * image.height * 4);
+        // Clear in the corners.
+        assert_eq!(image.rgba[3], 0);
+    }
+}
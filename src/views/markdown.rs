@@ -0,0 +1,344 @@
+use crate::*;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::rc::Rc;
+
+/// A block of a `markdown` document.
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    /// A paragraph, heading or list item, `indent` levels into lists and
+    /// quotes.
+    Text {
+        text: AttributedText,
+        indent: usize,
+    },
+    Code(String),
+    Rule,
+}
+
+const HEADING_SIZES: [u32; 3] = [32, 26, 22];
+const CODE_SIZE: u32 = 14;
+const CODE_COLOR: Color = GREEN_HIGHLIGHT;
+/// vger has no italic face, so emphasis is brighter instead.
+const EMPHASIS_COLOR: Color = Color::hex_const("#FFFFFF");
+const INDENT: f32 = 20.0;
+
+/// Turns CommonMark events into blocks of attributed text.
+#[derive(Default)]
+struct BlockBuilder {
+    blocks: Vec<Block>,
+    text: AttributedText,
+    /// Of headings, or else the default.
+    size: Option<u32>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: usize,
+    link: Option<String>,
+    /// Each open list, and the number of its next item if it's ordered.
+    lists: Vec<Option<u64>>,
+    quotes: usize,
+    code: Option<String>,
+}
+
+impl BlockBuilder {
+    /// Ends the block of text so far.
+    fn flush(&mut self) {
+        if !self.text.text().is_empty() {
+            self.blocks.push(Block::Text {
+                text: std::mem::take(&mut self.text),
+                indent: self.lists.len() + self.quotes,
+            });
+        }
+    }
+
+    fn push_text(&mut self, s: &str, code: bool) {
+        if let Some(block) = &mut self.code {
+            block.push_str(s);
+            return;
+        }
+        let all = 0..s.len();
+        let size = if code {
+            CODE_SIZE
+        } else {
+            self.size.unwrap_or(Text::DEFAULT_SIZE)
+        };
+        let mut t = AttributedText::new(s).size(all.clone(), size);
+        if self.strong > 0 {
+            t = t.weight(all.clone(), FontWeight::Bold);
+        }
+        if self.quotes > 0 {
+            t = t.color(all.clone(), MEDIUM_GRAY);
+        }
+        if self.emphasis > 0 {
+            t = t.color(all.clone(), EMPHASIS_COLOR);
+        }
+        if code {
+            t = t.color(all.clone(), CODE_COLOR);
+        }
+        if self.strikethrough > 0 {
+            t = t.strikethrough(all.clone());
+        }
+        if let Some(url) = &self.link {
+            t = t.link(all, url.clone());
+        }
+        self.text = std::mem::take(&mut self.text).append(t);
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(s) => self.push_text(&s, false),
+            Event::Code(s) => self.push_text(&s, true),
+            Event::SoftBreak => self.push_text(" ", false),
+            Event::HardBreak => self.push_text("\n", false),
+            Event::Rule => {
+                self.flush();
+                self.blocks.push(Block::Rule);
+            }
+            Event::TaskListMarker(done) => self.push_text(if done { "☑ " } else { "☐ " }, false),
+            Event::Html(_) | Event::FootnoteReference(_) => (),
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading(level, ..) => {
+                self.flush();
+                self.size = Some(
+                    HEADING_SIZES
+                        .get(level as usize - 1)
+                        .copied()
+                        .unwrap_or(Text::DEFAULT_SIZE),
+                );
+                self.strong += 1;
+            }
+            Tag::BlockQuote => {
+                self.flush();
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.flush();
+                self.code = Some(String::new());
+            }
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "• ".into(),
+                };
+                self.push_text(&marker, false);
+            }
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough += 1,
+            Tag::Link(_, url, _) => self.link = Some(url.to_string()),
+            _ => (),
+        }
+    }
+
+    fn end(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph | Tag::Item => self.flush(),
+            Tag::Heading(..) => {
+                self.flush();
+                self.size = None;
+                self.strong -= 1;
+            }
+            Tag::BlockQuote => {
+                self.flush();
+                self.quotes -= 1;
+            }
+            Tag::CodeBlock(_) => {
+                if let Some(code) = self.code.take() {
+                    self.blocks
+                        .push(Block::Code(code.trim_end_matches('\n').to_string()));
+                }
+            }
+            Tag::List(_) => {
+                self.flush();
+                self.lists.pop();
+            }
+            Tag::Emphasis => self.emphasis -= 1,
+            Tag::Strong => self.strong -= 1,
+            Tag::Strikethrough => self.strikethrough -= 1,
+            Tag::Link(..) => self.link = None,
+            _ => (),
+        }
+    }
+}
+
+fn parse_blocks(source: &str) -> Vec<Block> {
+    let mut builder = BlockBuilder::default();
+    let options = Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(source, options) {
+        builder.event(event);
+    }
+    builder.flush();
+    builder.blocks
+}
+
+/// A line across the width it's offered, for `---`.
+struct Rule;
+
+impl View for Rule {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let paint = args.vger.color_paint(MEDIUM_GRAY);
+        let line = LocalRect::new([0.0, rect.center().y].into(), [rect.width(), 1.0].into());
+        args.vger.fill_rect(line, 0.0, paint);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let size = LocalSize::new(args.sz.width, 17.0);
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+}
+
+impl private::Sealed for Rule {}
+
+/// Options for `markdown`. Set them with `MarkdownMods`.
+#[derive(Clone, Default)]
+pub struct MarkdownOptions {
+    on_link: Option<Rc<dyn Fn(&mut Context, &str)>>,
+}
+
+pub trait MarkdownMods: View + Sized {
+    /// Calls `f` with the URL when a link is tapped.
+    fn on_link(self, f: impl Fn(&mut Context, &str) + 'static) -> Self;
+}
+
+impl<F> MarkdownMods for ModView<MarkdownOptions, F>
+where
+    ModView<MarkdownOptions, F>: View,
+{
+    fn on_link(self, f: impl Fn(&mut Context, &str) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_link = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+fn block_view(block: &Block, opts: &MarkdownOptions) -> AnyView {
+    let with_links = |t: RichText| match &opts.on_link {
+        Some(f) => {
+            let f = f.clone();
+            t.on_link(move |cx, url| f(cx, url))
+        }
+        None => t,
+    };
+    match block {
+        Block::Text { text, indent } => any_view(
+            with_links(rich_text(text.clone()))
+                .indent(*indent as f32 * INDENT)
+                .fill_width()
+                .padding(Auto),
+        ),
+        Block::Code(code) => {
+            let t = AttributedText::new(code.as_str())
+                .size(0..code.len(), CODE_SIZE)
+                .color(0..code.len(), CODE_COLOR);
+            any_view(
+                rich_text(t)
+                    .fill_width()
+                    .padding(Auto)
+                    .background(rectangle().color(CONTROL_BACKGROUND).corner_radius(4.0))
+                    .padding(Auto),
+            )
+        }
+        Block::Rule => any_view(Rule.padding(Auto)),
+    }
+}
+
+/// Renders CommonMark: headings, paragraphs, emphasis, strikethrough,
+/// lists, block quotes, code and links, as a stack of `rich_text`s.
+/// Tapped links go to `MarkdownMods::on_link`. Emphasis is brighter,
+/// rather than italic. Images show their alt text, and HTML is left out.
+///
+/// Needs the `markdown` feature.
+///
+/// ```no_run
+/// # use rui::*;
+/// scroll_view(markdown("# Help\n\nSee [the docs](https://docs.rs/rui)."))
+///     .on_link(|_, url| println!("open {}", url));
+/// ```
+pub fn markdown(source: &str) -> impl MarkdownMods {
+    let blocks = Rc::new(parse_blocks(source));
+    modview(move |opts: MarkdownOptions, _| {
+        let blocks = blocks.clone();
+        list((0..blocks.len()).collect(), move |i| {
+            block_view(&blocks[*i], &opts)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn texts(blocks: &[Block]) -> Vec<(String, usize)> {
+        blocks
+            .iter()
+            .map(|b| match b {
+                Block::Text { text, indent } => (text.text().to_string(), *indent),
+                Block::Code(code) => (format!("code: {}", code), 0),
+                Block::Rule => ("rule".into(), 0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_markdown_blocks() {
+        let source = "# Title\n\nSome *text*\nhere.\n\n- one\n- two\n  1. nested\n\n---\n\n```\nlet x = 1;\n```\n";
+        let blocks = parse_blocks(source);
+        assert_eq!(
+            texts(&blocks),
+            vec![
+                ("Title".to_string(), 0),
+                ("Some text here.".to_string(), 0),
+                ("• one".to_string(), 1),
+                ("• two".to_string(), 1),
+                ("1. nested".to_string(), 2),
+                ("rule".to_string(), 0),
+                ("code: let x = 1;".to_string(), 0),
+            ]
+        );
+
+        let title = match &blocks[0] {
+            Block::Text { text, .. } => text.runs(),
+            _ => unreachable!(),
+        };
+        assert_eq!(title[0].1.size, 32);
+        assert_eq!(title[0].1.weight, FontWeight::Bold);
+    }
+
+    #[test]
+    fn test_markdown_links() {
+        let blocks = parse_blocks("Go [**there**](rui://there) now");
+        let runs = match &blocks[0] {
+            Block::Text { text, .. } => text.runs(),
+            _ => unreachable!(),
+        };
+        let link = runs.iter().find(|(_, a)| a.link.is_some()).unwrap();
+        assert_eq!(link.0, 3..8);
+        assert_eq!(link.1.link.as_deref(), Some("rui://there"));
+        assert_eq!(link.1.weight, FontWeight::Bold);
+    }
+}
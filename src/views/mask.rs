@@ -0,0 +1,153 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `mask` modifier.
+pub struct MaskView<V, M> {
+    child: V,
+    mask: M,
+}
+
+impl<V, M> MaskView<V, M>
+where
+    V: View,
+    M: View,
+{
+    pub fn new(child: V, mask: M) -> Self {
+        Self { child, mask }
+    }
+}
+
+impl<V, M> View for MaskView<V, M>
+where
+    V: View,
+    M: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let world = match layer_rect(args.cx, args.vger, rect, 0.0) {
+            Some(world) => world,
+            None => {
+                path.push(0);
+                self.child.draw(path, args);
+                path.pop();
+                return;
+            }
+        };
+        path.push(0);
+        let effect = Some(Effect::identity());
+        let layer = draw_into(args.cx, args.vger, world, effect, |cx, vger| {
+            self.child.draw(path, &mut DrawArgs { cx, vger })
+        });
+        path.pop();
+        path.push(1);
+        draw_mask(args.cx, args.vger, layer, |cx, vger| {
+            self.mask.draw(path, &mut DrawArgs { cx, vger })
+        });
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        path.push(1);
+        self.mask.layout(path, &mut args.size(sz));
+        path.pop();
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+        path.push(1);
+        self.mask.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        path.push(1);
+        self.mask.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, M> private::Sealed for MaskView<V, M> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_mask_view() {
+        let mut cx = Context::new();
+        let ui = rectangle().mask(|| rectangle().size([20.0, 20.0]));
+
+        let mut path = vec![0];
+        let sz = ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        assert_eq!(sz, LocalSize::new(100.0, 100.0));
+        assert_eq!(
+            cx.get_layout(&vec![0, 1, 0]).rect.size,
+            LocalSize::new(20.0, 20.0)
+        );
+
+        // Only what's masked is hit, not the mask.
+        let id = ui.hittest(&mut path, [50.0, 50.0].into(), &mut cx);
+        assert_eq!(id, Some(cx.view_id(&vec![0, 0])));
+    }
+}
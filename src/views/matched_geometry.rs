@@ -0,0 +1,271 @@
+use crate::*;
+use std::any::Any;
+
+/// Where the view with a `matched_geometry` id is, and where it's moving
+/// from if another view with the id just went away. Corner radii move
+/// along with the rects.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MatchedFrame {
+    owner: ViewId,
+    rect: WorldRect,
+    radius: f32,
+    from: Option<(WorldRect, f32)>,
+    progress: f32,
+    /// Frame the owner was last drawn.
+    drawn: u64,
+}
+
+impl MatchedFrame {
+    fn new(owner: ViewId, rect: WorldRect, radius: f32, frame: u64) -> Self {
+        Self {
+            owner,
+            rect,
+            radius,
+            from: None,
+            progress: 1.0,
+            drawn: frame,
+        }
+    }
+
+    /// Where the view is drawn, and its corner radius.
+    fn current(&self) -> (WorldRect, f32) {
+        match self.from {
+            Some((rect, radius)) => {
+                let t = ease_out(self.progress);
+                (rect.lerp(self.rect, t), radius + (self.radius - radius) * t)
+            }
+            None => (self.rect, self.radius),
+        }
+    }
+
+    /// Notes that `vid` is being drawn at `rect` with `radius`, and returns
+    /// where and how round to draw it instead.
+    fn update(
+        &mut self,
+        vid: ViewId,
+        rect: WorldRect,
+        radius: f32,
+        frame: u64,
+    ) -> (WorldRect, f32) {
+        if self.owner != vid {
+            if self.drawn == frame {
+                // Both views are showing, so leave the other be.
+                return (rect, radius);
+            }
+            // Only move from the other view if it just went away.
+            let just_gone = self.drawn + 1 == frame;
+            let from = self.current();
+            *self = Self::new(vid, rect, radius, frame);
+            if just_gone && from != (rect, radius) {
+                self.from = Some(from);
+                self.progress = 0.0;
+            }
+        }
+        self.rect = rect;
+        self.radius = radius;
+        self.drawn = frame;
+        self.current()
+    }
+}
+
+/// Struct for the `matched_geometry` modifier.
+pub struct MatchedGeometry<V> {
+    child: V,
+    id: String,
+}
+
+impl<V> MatchedGeometry<V>
+where
+    V: View,
+{
+    pub fn new(child: V, id: &str) -> Self {
+        Self {
+            child,
+            id: id.into(),
+        }
+    }
+}
+
+impl<V> View for MatchedGeometry<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            let vid = cx.view_id(path);
+            let dt = cx.anim_dt;
+            let scrub = cx.anim_scrub();
+            let mut moving = None;
+            if let Some(m) = cx.matched_geometry.get_mut(&self.id) {
+                if m.owner == vid && m.from.is_some() {
+                    m.progress = match scrub {
+                        Some(t) => t.min(0.99),
+                        None => (m.progress + dt / TRANSITION_DURATION).min(1.0),
+                    };
+                    if m.progress >= 1.0 {
+                        m.from = None;
+                    }
+                    moving = Some(m.progress);
+                }
+            }
+            if let Some(progress) = moving {
+                cx.note_animation("matched geometry", Some(progress));
+                cx.set_dirty();
+            }
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let vid = args.cx.view_id(path);
+        let rect = args.cx.get_layout(path).rect;
+        let xform = args.vger.current_transform();
+        let world = xform.outer_transformed_rect(&rect);
+        let frame = args.cx.frame();
+        let radius = args.cx.init_env(&CornerRadius::default).0;
+
+        let (shown, shown_radius) = args
+            .cx
+            .matched_geometry
+            .entry(self.id.clone())
+            .or_insert_with(|| MatchedFrame::new(vid, world, radius, frame))
+            .update(vid, world, radius, frame);
+
+        let target = match xform.inverse() {
+            Some(inverse) if (shown, shown_radius) != (world, radius) && !rect.is_empty() => {
+                inverse.outer_transformed_rect(&shown)
+            }
+            _ => {
+                path.push(0);
+                self.child.draw(path, args);
+                path.pop();
+                return;
+            }
+        };
+
+        // Map our rect onto where we're moving through.
+        let scale = [
+            target.width() / rect.width(),
+            target.height() / rect.height(),
+        ];
+        args.vger.save();
+        args.vger.translate(target.origin.to_vector());
+        args.vger.scale(scale);
+        args.vger.translate(-rect.origin.to_vector());
+        // Undo the scale, so the corners come out at the eased radius.
+        let old = args
+            .cx
+            .set_env(&CornerRadius(shown_radius / scale[0].min(scale[1])));
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.cx.set_env(&old.unwrap_or_default());
+        args.vger.restore();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for MatchedGeometry<V> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_matched_frame() {
+        let thumbnail = ViewId { id: 1 };
+        let detail = ViewId { id: 2 };
+        let small = WorldRect::new([10.0, 10.0].into(), [20.0, 20.0].into());
+        let big = WorldRect::new([0.0, 0.0].into(), [200.0, 200.0].into());
+
+        let mut m = MatchedFrame::new(thumbnail, small, 0.0, 1);
+        assert_eq!(m.update(thumbnail, small, 0.0, 2), (small, 0.0));
+
+        // The detail replaces the thumbnail, so starts where it was.
+        assert_eq!(m.update(detail, big, 0.0, 3), (small, 0.0));
+        m.progress = 1.0;
+        assert_eq!(m.update(detail, big, 0.0, 4), (big, 0.0));
+
+        // Both showing: each stays put.
+        assert_eq!(m.update(thumbnail, small, 0.0, 4), (small, 0.0));
+        assert_eq!(m.update(detail, big, 0.0, 5), (big, 0.0));
+
+        // Back after a while, so there's nothing to move from.
+        assert_eq!(m.update(thumbnail, small, 0.0, 9), (small, 0.0));
+    }
+
+    #[test]
+    fn test_matched_radius() {
+        let thumbnail = ViewId { id: 1 };
+        let detail = ViewId { id: 2 };
+        let rect = WorldRect::new([0.0, 0.0].into(), [100.0, 100.0].into());
+
+        let mut m = MatchedFrame::new(thumbnail, rect, 20.0, 1);
+        assert_eq!(m.update(detail, rect, 0.0, 2), (rect, 20.0));
+
+        // Halfway through, eased.
+        m.progress = 0.5;
+        assert_eq!(m.update(detail, rect, 0.0, 3), (rect, 5.0));
+    }
+}
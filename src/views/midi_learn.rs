@@ -0,0 +1,203 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `midi_learn` modifier.
+pub struct MidiLearn<V, B> {
+    child: V,
+    value: B,
+}
+
+impl<V, B> MidiLearn<V, B>
+where
+    V: View,
+    B: Binding<f32>,
+{
+    pub fn new(child: V, value: B) -> Self {
+        Self { child, value }
+    }
+}
+
+impl<V, B> View for MidiLearn<V, B>
+where
+    V: View,
+    B: Binding<f32>,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        // The channel and controller mapped to this control.
+        let mapping = StateHandle::<Option<(u8, u8)>>::new(vid);
+        cx.init_state(vid, &|| None);
+
+        match event {
+            Event::TouchBegin { position, .. } if cx.midi_learning => {
+                if self.hittest(path, *position, cx).is_some() {
+                    cx.midi_learn_target = Some(vid);
+                    cx.set_dirty();
+                }
+            }
+            Event::Midi(MidiMessage::ControlChange {
+                channel,
+                controller,
+                value,
+            }) => {
+                let cc = Some((*channel, *controller));
+                if cx.midi_learn_target == Some(vid) {
+                    cx[mapping] = cc;
+                    cx.midi_learn_target = None;
+                } else if cx[mapping] == cc {
+                    self.value.with_mut(cx, |v| *v = *value as f32 / 127.0);
+                }
+            }
+            _ => (),
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+
+        if args.cx.midi_learning {
+            let vid = args.cx.view_id(path);
+            let rect = args.cx.get_layout(path).rect;
+            let (color, width) = if args.cx.midi_learn_target == Some(vid) {
+                (AZURE_HIGHLIGHT, 2.0)
+            } else {
+                (CONTROL_BACKGROUND, 1.0)
+            };
+            let paint = args.vger.color_paint(color);
+            args.vger
+                .stroke_rect(rect.min(), rect.max(), 4.0, width, paint);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.start_midi();
+
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B> private::Sealed for MidiLearn<V, B> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_midi_learn() {
+        let mut cx = Context::new();
+
+        let ui = state(|| 0.0, |s, _| knob(s).midi_learn(s));
+        let sz = [100.0, 100.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<f32>::new(cx.view_id(&path));
+
+        let cc = |value| {
+            Event::Midi(MidiMessage::ControlChange {
+                channel: 0,
+                controller: 7,
+                value,
+            })
+        };
+        let events = [
+            // Not mapped yet.
+            cc(127),
+            Event::TouchBegin {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+        ];
+
+        let mut actions = vec![];
+        for event in &events {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+        assert_eq!(*s.get(&cx), 0.0);
+
+        cx.set_midi_learn(true);
+        for event in &events[1..] {
+            ui.process(event, &mut path, &mut cx, &mut actions);
+        }
+        // The first CC after touching the knob maps it.
+        ui.process(&cc(0), &mut path, &mut cx, &mut actions);
+        cx.set_midi_learn(false);
+        assert_eq!(*s.get(&cx), 0.0);
+
+        ui.process(&cc(127), &mut path, &mut cx, &mut actions);
+        assert_eq!(*s.get(&cx), 1.0);
+    }
+}
@@ -1,51 +1,133 @@
 mod anim;
 pub use anim::*;
+mod animated;
+pub use animated::*;
 mod anyview;
 pub use anyview::*;
 mod background;
 pub use background::*;
 mod button;
 pub use button::*;
+mod calendar;
+pub use calendar::*;
 mod canvas;
 pub use canvas::*;
+mod chrome;
+pub use chrome::*;
 mod clip;
 pub use clip::*;
+mod isolate;
+pub use isolate::*;
+mod midi_learn;
+pub use midi_learn::*;
+mod param;
+pub use param::*;
+mod scroll;
+pub use scroll::*;
+mod scroll_view;
+pub use scroll_view::*;
 mod command;
 pub use command::*;
 mod cond;
 pub use cond::*;
+mod data_grid;
+pub use data_grid::*;
+mod diff_view;
+pub use diff_view::*;
+mod dock;
+pub use dock::*;
 mod drag;
 pub use drag::*;
+mod divider;
+pub use divider::*;
+mod drop;
+pub use drop::*;
+mod drag_drop;
+pub use drag_drop::*;
+mod drag_out;
+pub use drag_out::*;
+mod effect;
+pub use effect::*;
 mod emptyview;
 pub use emptyview::*;
 mod env;
 pub use env::*;
+mod find_panel;
+pub(crate) use find_panel::*;
 mod flex;
 pub use flex::*;
 mod focus;
 pub use focus::*;
+mod gantt;
+pub use gantt::*;
+mod gauge;
+pub use gauge::*;
+mod gesture;
+pub use gesture::*;
 mod geom;
 pub use geom::*;
+mod gutter;
+pub use gutter::*;
 mod handle;
 pub use handle::*;
+mod heatmap;
+pub use heatmap::*;
+mod hex_view;
+pub use hex_view::*;
 mod hover;
 pub use hover::*;
+#[cfg(all(feature = "icons", not(target_arch = "wasm32")))]
+mod icons;
+#[cfg(all(feature = "icons", not(target_arch = "wasm32")))]
+pub use icons::*;
+mod image_slice;
+pub use image_slice::*;
+mod image_view;
+pub use image_view::*;
+mod in_window_menus;
+pub use in_window_menus::*;
+mod interactions;
+pub use interactions::*;
+#[cfg(all(feature = "json-view", not(target_arch = "wasm32")))]
+mod json_view;
+#[cfg(all(feature = "json-view", not(target_arch = "wasm32")))]
+pub use json_view::*;
 mod key;
 pub use key::*;
+mod keyframe_animation;
+pub use keyframe_animation::*;
 mod knob;
 pub use knob::*;
 mod list;
 pub use list::*;
 mod map;
 pub use map::*;
+mod mask;
+pub use mask::*;
+#[cfg(all(feature = "maps", not(target_arch = "wasm32")))]
+mod map_view;
+#[cfg(all(feature = "maps", not(target_arch = "wasm32")))]
+pub use map_view::*;
+#[cfg(all(feature = "markdown", not(target_arch = "wasm32")))]
+mod markdown;
+#[cfg(all(feature = "markdown", not(target_arch = "wasm32")))]
+pub use markdown::*;
+mod matched_geometry;
+pub use matched_geometry::*;
 mod modview;
 pub use modview::*;
+mod numeric_field;
+pub use numeric_field::*;
 mod offset;
 pub use offset::*;
 mod padding;
 pub use padding::*;
+mod preferences;
+pub use preferences::*;
 mod redux;
 pub use redux::*;
+mod rich_text;
+pub use rich_text::*;
 mod role;
 pub use role::*;
 mod shapes;
@@ -54,6 +136,8 @@ mod size;
 pub use size::*;
 mod slider;
 pub use slider::*;
+mod sparkline;
+pub use sparkline::*;
 mod spacer;
 pub use spacer::*;
 mod stack;
@@ -61,13 +145,33 @@ pub use stack::*;
 mod stack_layout;
 mod state;
 pub use state::*;
+#[cfg(all(feature = "svg", not(target_arch = "wasm32")))]
+mod svg;
+#[cfg(all(feature = "svg", not(target_arch = "wasm32")))]
+pub use svg::*;
 mod tap;
 pub use tap::*;
+mod titlebar;
+pub use titlebar::*;
 mod text_editor;
 pub use text_editor::*;
+mod text_field;
+pub use text_field::*;
 mod text;
 pub use text::*;
+mod text_input;
+pub use text_input::*;
+mod tick;
+pub use tick::*;
+mod timeline_view;
+pub use timeline_view::*;
 mod toggle;
 pub use toggle::*;
+mod transition;
+pub use transition::*;
+mod wgpu_view;
+pub use wgpu_view::*;
 mod window;
 pub use window::*;
+mod workspace_state;
+pub use workspace_state::*;
@@ -0,0 +1,316 @@
+use crate::*;
+
+/// How numbers are written, for `numeric_field`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+}
+
+/// Languages which write "1,5" for one and a half.
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "bg", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it", "lt", "lv", "nb",
+    "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk", "vi",
+];
+
+impl NumberFormat {
+    pub fn new(decimal_separator: char) -> Self {
+        Self { decimal_separator }
+    }
+
+    /// The user's, going by the `LC_ALL`, `LC_NUMERIC` and `LANG`
+    /// environment variables, or else with a decimal point.
+    pub fn current() -> Self {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .unwrap_or_default();
+        Self::for_locale(&locale)
+    }
+
+    /// For a locale such as `"de_DE.UTF-8"`.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale.split(|c| c == '_' || c == '-' || c == '.').next();
+        match language {
+            Some(language) if DECIMAL_COMMA_LANGUAGES.contains(&language) => Self::new(','),
+            _ => Self::new('.'),
+        }
+    }
+
+    /// Reads a number such as `"-1.5"`.
+    pub fn parse(&self, s: &str) -> Option<f64> {
+        if !self.is_partial(s, true, None) {
+            return None;
+        }
+        s.replace(self.decimal_separator, ".").parse().ok()
+    }
+
+    /// Writes `value` with `decimals` decimal places, or else up to 9,
+    /// without trailing zeros.
+    pub fn format(&self, value: f64, decimals: Option<usize>) -> String {
+        let mut s = match decimals {
+            Some(decimals) => format!("{:.*}", decimals, value),
+            None => {
+                let s = format!("{:.9}", value);
+                s.trim_end_matches('0').trim_end_matches('.').to_string()
+            }
+        };
+        if s.trim_start_matches('-')
+            .chars()
+            .all(|c| c == '0' || c == '.')
+        {
+            s = s.trim_start_matches('-').to_string();
+        }
+        s.replace('.', &self.decimal_separator.to_string())
+    }
+
+    /// Could `s` be typed on the way to a number? Allows a minus sign if
+    /// `negative`, and at most `decimals` decimal places.
+    fn is_partial(&self, s: &str, negative: bool, decimals: Option<usize>) -> bool {
+        let s = match s.strip_prefix('-') {
+            Some(rest) if negative => rest,
+            _ => s,
+        };
+        let mut parts = s.splitn(2, self.decimal_separator);
+        let whole = parts.next().unwrap_or_default();
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        digits(whole)
+            && match parts.next() {
+                Some(fraction) => {
+                    decimals != Some(0)
+                        && digits(fraction)
+                        && decimals.map_or(true, |d| fraction.len() <= d)
+                }
+                None => true,
+            }
+    }
+}
+
+/// Options for `numeric_field`. Set them with `NumericFieldMods`.
+#[derive(Clone, Copy)]
+pub struct NumericFieldOptions {
+    min: f64,
+    max: f64,
+    step: f64,
+    decimals: Option<usize>,
+    format: NumberFormat,
+}
+
+impl Default for NumericFieldOptions {
+    fn default() -> Self {
+        Self {
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            step: 1.0,
+            decimals: None,
+            format: NumberFormat::current(),
+        }
+    }
+}
+
+impl NumericFieldOptions {
+    fn clamp(&self, value: f64) -> f64 {
+        value.max(self.min).min(self.max)
+    }
+
+    fn accepts(&self, text: &str) -> bool {
+        self.format.is_partial(text, self.min < 0.0, self.decimals)
+    }
+}
+
+pub trait NumericFieldMods: View + Sized {
+    /// Keeps the value within `range`.
+    fn range(self, range: std::ops::RangeInclusive<f64>) -> Self;
+
+    /// How much the arrows change the value by. It's 1 by default.
+    fn step(self, step: f64) -> Self;
+
+    /// Shows and allows this many decimal places.
+    fn decimals(self, decimals: usize) -> Self;
+
+    /// Writes numbers in `format` instead of the user's.
+    fn number_format(self, format: NumberFormat) -> Self;
+}
+
+impl<F> NumericFieldMods for ModView<NumericFieldOptions, F>
+where
+    ModView<NumericFieldOptions, F>: View,
+{
+    fn range(self, range: std::ops::RangeInclusive<f64>) -> Self {
+        let mut opts = self.value;
+        opts.min = *range.start();
+        opts.max = *range.end();
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn step(self, step: f64) -> Self {
+        let mut opts = self.value;
+        opts.step = step;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn decimals(self, decimals: usize) -> Self {
+        let mut opts = self.value;
+        opts.decimals = Some(decimals);
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn number_format(self, format: NumberFormat) -> Self {
+        let mut opts = self.value;
+        opts.format = format;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+/// View-model for `numeric_field`.
+#[derive(Default)]
+struct NumericFieldState {
+    /// What's in the field.
+    text: String,
+    editing: bool,
+}
+
+make_lens!(NumericTextLens, NumericFieldState, String, text);
+
+const STEP_ARROW_WIDTH: f32 = 16.0;
+const STEP_ARROW_HEIGHT: f32 = 12.0;
+
+/// An arrow which steps the value up or down.
+fn step_arrow_view(
+    value: impl Binding<f64>,
+    state: StateHandle<NumericFieldState>,
+    opts: NumericFieldOptions,
+    up: bool,
+) -> impl View {
+    canvas(move |_, rect, vger| {
+        let c = rect.center();
+        let d = if up { 3.0 } else { -3.0 };
+        let paint = vger.color_paint(TEXT_COLOR);
+        vger.stroke_segment([c.x - 4.0, c.y - d], [c.x, c.y + d], 1.5, paint);
+        vger.stroke_segment([c.x, c.y + d], [c.x + 4.0, c.y - d], 1.5, paint);
+    })
+    .size([STEP_ARROW_WIDTH, STEP_ARROW_HEIGHT])
+    .tap(move |cx| {
+        let step = if up { opts.step } else { -opts.step };
+        let v = opts.clamp(*value.get(cx) + step);
+        value.with_mut(cx, |value| *value = v);
+        cx[state].text = opts.format.format(v, opts.decimals);
+    })
+}
+
+/// Parses what's been typed into the value, clamping it, and shows the
+/// result.
+fn commit(
+    cx: &mut Context,
+    value: impl Binding<f64>,
+    state: StateHandle<NumericFieldState>,
+    opts: NumericFieldOptions,
+) {
+    if let Some(v) = opts.format.parse(&cx[state].text) {
+        let v = opts.clamp(v);
+        value.with_mut(cx, |value| *value = v);
+    }
+    let v = *value.get(cx);
+    cx[state].text = opts.format.format(v, opts.decimals);
+}
+
+/// A field for entering a number, with arrows to step it up and down.
+/// Keystrokes which can't be part of a number are rejected, and the
+/// decimal separator follows the user's locale.
+///
+/// The value changes as you type, while what's typed is within the
+/// range set with `NumericFieldMods::range`. It's clamped to the range
+/// when you press Enter or leave the field.
+pub fn numeric_field(value: impl Binding<f64>) -> impl NumericFieldMods {
+    modview(move |opts: NumericFieldOptions, _| {
+        state(NumericFieldState::default, move |state, _| {
+            hstack((
+                text_field(bind(state, NumericTextLens))
+                    .validate(move |text| opts.accepts(text))
+                    .on_submit(move |cx, _| commit(cx, value, state, opts))
+                    .on_editing_changed(move |cx, editing| {
+                        cx[state].editing = editing;
+                        if !editing {
+                            commit(cx, value, state, opts);
+                        }
+                    }),
+                vstack((
+                    step_arrow_view(value, state, opts, true),
+                    step_arrow_view(value, state, opts, false),
+                )),
+            ))
+            .anim(move |cx, _| {
+                let v = *value.get(cx);
+                if cx[state].editing {
+                    // Follow the typing, within bounds.
+                    let typed = opts.format.parse(&cx[state].text);
+                    if let Some(t) = typed.filter(|t| *t == opts.clamp(*t) && *t != v) {
+                        value.with_mut(cx, |value| *value = t);
+                    }
+                } else {
+                    // Show changes made elsewhere.
+                    let text = opts.format.format(v, opts.decimals);
+                    if cx[state].text != text {
+                        cx[state].text = text;
+                    }
+                }
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_number_format() {
+        let point = NumberFormat::for_locale("en_US.UTF-8");
+        let comma = NumberFormat::for_locale("de_DE.UTF-8");
+        assert_eq!(point, NumberFormat::new('.'));
+        assert_eq!(comma, NumberFormat::new(','));
+        assert_eq!(NumberFormat::for_locale("C"), point);
+
+        assert_eq!(point.parse("-1.5"), Some(-1.5));
+        assert_eq!(comma.parse("-1,5"), Some(-1.5));
+        assert_eq!(comma.parse("1.5"), None);
+        assert_eq!(point.parse("1e5"), None);
+        assert_eq!(point.parse("-"), None);
+
+        assert_eq!(point.format(0.1 + 0.2, None), "0.3");
+        assert_eq!(comma.format(2.5, Some(2)), "2,50");
+        assert_eq!(point.format(3.0, None), "3");
+        assert_eq!(point.format(-0.0, Some(1)), "0.0");
+    }
+
+    #[test]
+    fn test_numeric_field_accepts() {
+        let opts = NumericFieldOptions {
+            min: 0.0,
+            max: 10.0,
+            decimals: Some(1),
+            format: NumberFormat::new('.'),
+            ..Default::default()
+        };
+        assert!(opts.accepts(""));
+        assert!(opts.accepts("9."));
+        assert!(opts.accepts("9.5"));
+        assert!(!opts.accepts("9.55"));
+        assert!(!opts.accepts("-1"));
+        assert!(!opts.accepts("1a"));
+        assert_eq!(opts.clamp(12.0), 10.0);
+    }
+}
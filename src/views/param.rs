@@ -0,0 +1,164 @@
+use crate::*;
+use std::any::Any;
+use std::rc::Rc;
+
+/// A value which can be set by address.
+pub(crate) trait ParamBinding {
+    fn value(&self, cx: &Context) -> f32;
+    fn set_value(&self, cx: &mut Context, value: f32);
+}
+
+struct BoundParam<B>(B);
+
+impl<B: Binding<f32>> ParamBinding for BoundParam<B> {
+    fn value(&self, cx: &Context) -> f32 {
+        *self.0.get(cx)
+    }
+    fn set_value(&self, cx: &mut Context, value: f32) {
+        *self.0.get_mut(cx) = value;
+    }
+}
+
+/// Struct for the `param` modifier.
+pub struct ParamView<V, B> {
+    child: V,
+    address: String,
+    value: B,
+}
+
+impl<V, B> ParamView<V, B>
+where
+    V: View,
+    B: Binding<f32>,
+{
+    pub fn new(child: V, address: &str, value: B) -> Self {
+        Self {
+            child,
+            address: address.into(),
+            value,
+        }
+    }
+}
+
+impl<V, B> View for ParamView<V, B>
+where
+    V: View,
+    B: Binding<f32>,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        // The registry is rebuilt each update, so it only has bindings to
+        // state which is still around.
+        cx.params
+            .insert(self.address.clone(), Rc::new(BoundParam(self.value)));
+
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B> private::Sealed for ParamView<V, B> {}
+
+impl Context {
+    /// Sets the value registered at `address` with the `param` modifier.
+    /// Returns false if nothing is registered there.
+    pub fn set_param(&mut self, address: &str, value: f32) -> bool {
+        match self.params.get(address).cloned() {
+            Some(param) => {
+                param.set_value(self, value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The addresses registered with the `param` modifier and their values.
+    pub fn params(&self) -> Vec<(String, f32)> {
+        let mut params: Vec<_> = self
+            .params
+            .iter()
+            .map(|(address, param)| (address.clone(), param.value(self)))
+            .collect();
+        params.sort_by(|a, b| a.0.cmp(&b.0));
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_params() {
+        let mut cx = Context::new();
+
+        let ui = state(|| 0.0, |s, _| knob(s).param("/synth/cutoff", s));
+        let mut path = vec![0];
+        let mut keep = vec![];
+        ui.gc(&mut path, &mut cx, &mut keep);
+
+        assert_eq!(cx.params(), [("/synth/cutoff".to_string(), 0.0)]);
+        assert!(cx.set_param("/synth/cutoff", 0.5));
+        assert!(!cx.set_param("/synth/resonance", 0.5));
+
+        let s = StateHandle::<f32>::new(cx.view_id(&path));
+        assert_eq!(*s.get(&cx), 0.5);
+    }
+}
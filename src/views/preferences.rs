@@ -0,0 +1,397 @@
+use crate::*;
+use std::any::Any;
+
+/// The command which opens preferences. `menu_bar` puts it where the
+/// platform expects a Preferences item.
+pub const PREFERENCES_COMMAND: &str = "App:Preferences";
+
+/// Workspace key for the last open tab. See `Context::save_workspace`.
+const TAB_KEY: &str = "preferences.tab";
+
+const TOOLBAR_HEIGHT: f32 = 36.0;
+const TAB_PADDING: f32 = 14.0;
+const TAB_FONT_SIZE: u32 = 13;
+const CLOSE_RADIUS: f32 = 6.0;
+
+#[derive(Clone, Default)]
+struct PreferencesState {
+    open: bool,
+    /// Title of the active tab.
+    tab: String,
+    size: LocalSize,
+    tab_widths: Vec<f32>,
+    content: LocalSize,
+}
+
+make_lens!(TabLens, PreferencesState, String, tab);
+
+impl PreferencesState {
+    /// The window, centered in the view.
+    fn frame(&self) -> LocalRect {
+        let tabs_width: f32 = self.tab_widths.iter().sum();
+        let size = LocalSize::new(
+            self.content.width.max(tabs_width + 4.0 * CLOSE_RADIUS),
+            self.content.height + TOOLBAR_HEIGHT,
+        );
+        LocalRect::new(((self.size - size) / 2.0).to_vector().to_point(), size)
+    }
+
+    fn toolbar(&self) -> LocalRect {
+        let frame = self.frame();
+        LocalRect::new(
+            [frame.min_x(), frame.max_y() - TOOLBAR_HEIGHT].into(),
+            [frame.width(), TOOLBAR_HEIGHT].into(),
+        )
+    }
+
+    fn tab_rects(&self) -> Vec<LocalRect> {
+        let toolbar = self.toolbar();
+        let tabs_width: f32 = self.tab_widths.iter().sum();
+        let mut x = toolbar.center().x - tabs_width / 2.0;
+        self.tab_widths
+            .iter()
+            .map(|w| {
+                let rect = LocalRect::new([x, toolbar.min_y()].into(), [*w, TOOLBAR_HEIGHT].into());
+                x += w;
+                rect
+            })
+            .collect()
+    }
+
+    fn close_button(&self) -> LocalPoint {
+        let toolbar = self.toolbar();
+        [toolbar.min_x() + 2.0 * CLOSE_RADIUS, toolbar.center().y].into()
+    }
+
+    /// Offset of the active tab's view, centered below the toolbar.
+    fn content_offset(&self) -> LocalOffset {
+        let frame = self.frame();
+        [
+            frame.min_x() + (frame.width() - self.content.width) / 2.0,
+            frame.min_y(),
+        ]
+        .into()
+    }
+}
+
+/// Struct for `preferences_window`.
+pub struct PreferencesWindow {
+    tabs: Vec<(String, AnyView)>,
+}
+
+impl PreferencesWindow {
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<PreferencesState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &PreferencesState::default);
+        StateHandle::new(id)
+    }
+
+    fn active_index(&self, cx: &Context, s: StateHandle<PreferencesState>) -> usize {
+        self.tabs
+            .iter()
+            .position(|(title, _)| *title == cx[s].tab)
+            .unwrap_or(0)
+    }
+
+    /// The active tab, if the window is open.
+    fn active(&self, cx: &Context, s: StateHandle<PreferencesState>) -> Option<&(String, AnyView)> {
+        if cx[s].open {
+            self.tabs.get(self.active_index(cx, s))
+        } else {
+            None
+        }
+    }
+}
+
+impl View for PreferencesWindow {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+
+        if let Event::Command(name) = event {
+            if name == PREFERENCES_COMMAND {
+                cx[s].open = true;
+                return;
+            }
+        }
+
+        if !cx[s].open {
+            return;
+        }
+
+        match event {
+            Event::Key {
+                key: Key::Escape, ..
+            } => {
+                cx[s].open = false;
+                return;
+            }
+            Event::TouchBegin { position, .. } => {
+                if cx[s].close_button().distance_to(*position) < 2.0 * CLOSE_RADIUS {
+                    cx[s].open = false;
+                    return;
+                }
+                let tab_rects = cx[s].tab_rects();
+                if let Some(i) = tab_rects.iter().position(|r| r.contains(*position)) {
+                    cx[s].tab = self.tabs[i].0.clone();
+                    return;
+                }
+                // Nothing behind the window gets touches while it's open.
+                if !cx[s].frame().contains(*position) {
+                    return;
+                }
+            }
+            _ => (),
+        }
+
+        if let Some((title, view)) = self.active(cx, s) {
+            path.push(hh(title));
+            let offset = cx.get_layout(path).offset;
+            view.process(&event.offset(-offset), path, cx, actions);
+            path.pop();
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let s = self.state(path, args.cx);
+        let (title, view) = match self.active(args.cx, s) {
+            Some(tab) => tab,
+            None => return,
+        };
+        let active = self.active_index(args.cx, s);
+        let state = args.cx[s].clone();
+        let vger = &mut args.vger;
+
+        // Dim what's behind.
+        let paint = vger.color_paint(BLACK.alpha(0.4));
+        vger.fill_rect(LocalRect::from_size(state.size), 0.0, paint);
+
+        let frame = state.frame();
+        let paint = vger.color_paint(BLACK);
+        vger.fill_rect(frame, 6.0, paint);
+
+        let toolbar = state.toolbar();
+        let paint = vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        vger.fill_rect(toolbar, 0.0, paint);
+
+        for (i, (rect, (title, _))) in state.tab_rects().iter().zip(&self.tabs).enumerate() {
+            if i == active {
+                let paint = vger.color_paint(CONTROL_BACKGROUND);
+                vger.fill_rect(*rect, 0.0, paint);
+            }
+            let bounds = vger.text_bounds(title, TAB_FONT_SIZE, None);
+            vger.save();
+            vger.translate(rect.center() - bounds.center());
+            let color = if i == active { TEXT_COLOR } else { MEDIUM_GRAY };
+            vger.text(title, TAB_FONT_SIZE, color, None);
+            vger.restore();
+        }
+
+        let close = state.close_button();
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        vger.fill_rect(
+            LocalRect::new(close, LocalSize::zero()).inflate(CLOSE_RADIUS, CLOSE_RADIUS),
+            CLOSE_RADIUS,
+            paint,
+        );
+
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        vger.stroke_rect(frame.min(), frame.max(), 6.0, 1.0, paint);
+
+        path.push(hh(title));
+        let offset = args.cx.get_layout(path).offset;
+        args.vger.save();
+        args.vger.translate(offset);
+        view.draw(path, args);
+        args.vger.restore();
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+        args.cx[s].size = args.sz;
+
+        // Restore the last open tab, and remember the current one.
+        if args.cx[s].tab.is_empty() {
+            if let Some((title, _)) = self.tabs.first() {
+                args.cx[s].tab = title.clone();
+            }
+        }
+        args.cx.sync_workspace_panel(TAB_KEY, bind(s, TabLens {}));
+
+        if let Some((title, view)) = self.active(args.cx, s) {
+            let tab_widths = self
+                .tabs
+                .iter()
+                .map(|(title, _)| {
+                    (args.text_bounds)(title, TAB_FONT_SIZE, None).width() + 2.0 * TAB_PADDING
+                })
+                .collect();
+
+            // The window takes the size of the active tab's view.
+            path.push(hh(title));
+            let content = view.layout(path, &mut args.size(args.sz - [0.0, TOOLBAR_HEIGHT].into()));
+            args.cx[s].tab_widths = tab_widths;
+            args.cx[s].content = content;
+            let offset = args.cx[s].content_offset();
+            args.cx.set_layout_offset(path, offset);
+            path.pop();
+        }
+
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let s = self.state(path, cx);
+        if let Some((title, view)) = self.active(cx, s) {
+            path.push(hh(title));
+            let offset = cx.get_layout(path).offset;
+            view.dirty(path, xform.pre_translate(offset), cx);
+            path.pop();
+        }
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let s = self.state(path, cx);
+        let (title, view) = self.active(cx, s)?;
+
+        path.push(hh(title));
+        let offset = cx.get_layout(path).offset;
+        let hit = view.hittest(path, pt - offset, cx);
+        path.pop();
+
+        // The window covers everything while it's open.
+        hit.or_else(|| Some(cx.view_id(path)))
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        for (title, view) in &self.tabs {
+            path.push(hh(title));
+            view.commands(path, cx, cmds);
+            path.pop();
+        }
+        cmds.push(CommandInfo {
+            path: PREFERENCES_COMMAND.into(),
+            key: Some(HotKey::Comma),
+        });
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+
+        // Keep state for the other tabs too.
+        for (title, view) in &self.tabs {
+            path.push(hh(title));
+            map.push(cx.view_id(path));
+            view.gc(path, cx, map);
+            path.pop();
+        }
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        let s = self.state(path, cx);
+        let (title, view) = self.active(cx, s)?;
+
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::Window);
+        builder.set_name(title.clone());
+        path.push(hh(title));
+        let child = view.access(path, cx, nodes);
+        path.pop();
+        builder.set_children(child.into_iter().collect::<Vec<_>>());
+
+        let node_id = cx.view_id(path).access_id();
+        nodes.push((node_id, builder.build(&mut cx.access_node_classes)));
+        Some(node_id)
+    }
+}
+
+impl private::Sealed for PreferencesWindow {}
+
+/// A settings window with a tab for each of `tabs`, shown over the rest of
+/// the UI. Put it in a `zstack` on top of the app's views.
+///
+/// It opens on `PREFERENCES_COMMAND` (Cmd+, on macOS, Ctrl+, elsewhere, and
+/// the Preferences menu item), closes with Escape or its close button, and
+/// sizes itself to the active tab's view. The last open tab is saved with
+/// the workspace.
+pub fn preferences_window(tabs: Vec<(&str, AnyView)>) -> PreferencesWindow {
+    PreferencesWindow {
+        tabs: tabs
+            .into_iter()
+            .map(|(title, view)| (title.to_string(), view))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn layout(ui: &impl View, cx: &mut Context) {
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [400.0, 300.0].into(),
+                cx,
+                text_bounds: &mut |_, _, _| LocalRect::new(LocalPoint::zero(), [40.0, 10.0].into()),
+            },
+        );
+    }
+
+    #[test]
+    fn test_preferences_window() {
+        let mut cx = Context::new();
+        let ui = preferences_window(vec![
+            ("General", any_view(rectangle().size([100.0, 50.0]))),
+            ("Advanced", any_view(rectangle().size([200.0, 80.0]))),
+        ]);
+        let s = StateHandle::<PreferencesState>::new(cx.view_id(&vec![0]));
+
+        let mut path = vec![0];
+        let mut actions = vec![];
+        ui.process(
+            &Event::Command(PREFERENCES_COMMAND.into()),
+            &mut path,
+            &mut cx,
+            &mut actions,
+        );
+        layout(&ui, &mut cx);
+        assert!(cx[s].open);
+        assert_eq!(cx[s].frame().size, [160.0, 50.0 + TOOLBAR_HEIGHT].into());
+
+        // Switch to the second tab, and the window resizes to fit it.
+        let tab = cx[s].tab_rects()[1].center();
+        ui.process(
+            &Event::TouchBegin {
+                id: 0,
+                position: tab,
+            },
+            &mut path,
+            &mut cx,
+            &mut actions,
+        );
+        layout(&ui, &mut cx);
+        assert_eq!(cx[s].frame().size, [200.0, 80.0 + TOOLBAR_HEIGHT].into());
+        assert_eq!(cx.save_workspace().panels[TAB_KEY], "Advanced");
+
+        let escape = Event::Key {
+            key: Key::Escape,
+            mods: KeyboardModifiers::default(),
+            repeat: false,
+        };
+        ui.process(&escape, &mut path, &mut cx, &mut actions);
+        assert!(!cx[s].open);
+    }
+}
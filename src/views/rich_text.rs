@@ -0,0 +1,403 @@
+use crate::*;
+use std::any::Any;
+use std::ops::Range;
+use std::rc::Rc;
+
+/// Lines are this many times the largest font size on them high, with
+/// the baseline this far down.
+const LINE_HEIGHT: f32 = 1.25;
+const ASCENT: f32 = 0.95;
+
+/// A word, or the space between words, in one run, placed on a line.
+#[derive(Clone, Debug, PartialEq)]
+struct Piece {
+    range: Range<usize>,
+    /// Index into the runs.
+    run: usize,
+    x: f32,
+    width: f32,
+    line: usize,
+}
+
+/// Where the runs of a `rich_text` went. Distances are down from the top
+/// left.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct RichTextLayout {
+    runs: Vec<(Range<usize>, TextAttributes)>,
+    pieces: Vec<Piece>,
+    /// Top, baseline and bottom of each line.
+    lines: Vec<(f32, f32, f32)>,
+    size: LocalSize,
+}
+
+impl RichTextLayout {
+    /// The link under `pt`, in the view's coordinates.
+    fn link_at(&self, pt: LocalPoint) -> Option<&str> {
+        let y = self.size.height - pt.y;
+        self.pieces
+            .iter()
+            .find(|p| {
+                let (top, _, bottom) = self.lines[p.line];
+                (p.x..p.x + p.width).contains(&pt.x) && (top..bottom).contains(&y)
+            })
+            .and_then(|p| self.runs[p.run].1.link.as_deref())
+    }
+}
+
+/// Lays out `runs` of `text` in lines no wider than `max_width`, starting
+/// `indent` in and breaking between words. `measure` says how far a
+/// string advances at a size.
+fn layout_runs(
+    text: &str,
+    runs: Vec<(Range<usize>, TextAttributes)>,
+    max_width: f32,
+    indent: f32,
+    measure: &mut dyn FnMut(&str, u32) -> f32,
+) -> RichTextLayout {
+    let mut pieces: Vec<Piece> = vec![];
+    let mut line_sizes = vec![0];
+    let (mut x, mut line) = (indent, 0);
+    // First piece of the word being placed, which may span runs.
+    let mut word: Option<usize> = None;
+    // Spaces where a line wrapped aren't shown.
+    let mut wrapped = false;
+
+    for (run, (range, attrs)) in runs.iter().enumerate() {
+        let mut start = range.start;
+        while start < range.end {
+            let s = &text[start..range.end];
+            let first = s.chars().next().unwrap();
+            let len = if first == '\n' {
+                1
+            } else {
+                let space = first.is_whitespace();
+                s.find(|c: char| c == '\n' || c.is_whitespace() != space)
+                    .unwrap_or(s.len())
+            };
+            let piece = start..start + len;
+            start += len;
+
+            if first == '\n' {
+                // Empty lines are as tall as their newline.
+                line_sizes[line] = line_sizes[line].max(attrs.size);
+                line += 1;
+                line_sizes.push(0);
+                x = indent;
+                word = None;
+                wrapped = false;
+                continue;
+            }
+
+            let width = measure(&text[piece.clone()], attrs.size);
+            if first.is_whitespace() {
+                word = None;
+                if wrapped && x == indent {
+                    continue;
+                }
+            } else {
+                let from = *word.get_or_insert(pieces.len());
+                let word_x = pieces.get(from).map_or(x, |p| p.x);
+                if x + width > max_width && word_x > indent {
+                    // Move the whole word down.
+                    line += 1;
+                    line_sizes.push(0);
+                    wrapped = true;
+                    for p in &mut pieces[from..] {
+                        p.x -= word_x - indent;
+                        p.line = line;
+                    }
+                    x -= word_x - indent;
+                }
+            }
+            pieces.push(Piece {
+                range: piece,
+                run,
+                x,
+                width,
+                line,
+            });
+            x += width;
+        }
+    }
+
+    for p in &pieces {
+        line_sizes[p.line] = line_sizes[p.line].max(runs[p.run].1.size);
+    }
+    let mut lines = vec![];
+    let mut top = 0.0;
+    for size in line_sizes {
+        let size = size as f32;
+        lines.push((top, top + size * ASCENT, top + size * LINE_HEIGHT));
+        top += size * LINE_HEIGHT;
+    }
+    let width = pieces.iter().map(|p| p.x + p.width).fold(0.0, f32::max);
+    RichTextLayout {
+        runs,
+        pieces,
+        lines,
+        size: [width, top].into(),
+    }
+}
+
+/// Struct for `rich_text`.
+pub struct RichText {
+    text: AttributedText,
+    on_link: Option<Rc<dyn Fn(&mut Context, &str)>>,
+    indent: f32,
+    fill_width: bool,
+}
+
+impl RichText {
+    /// Calls `f` with the link when one is tapped.
+    pub fn on_link(self, f: impl Fn(&mut Context, &str) + 'static) -> Self {
+        Self {
+            on_link: Some(Rc::new(f)),
+            ..self
+        }
+    }
+
+    /// Starts every line `indent` points in, such as for list items.
+    pub fn indent(self, indent: f32) -> Self {
+        Self { indent, ..self }
+    }
+
+    /// Takes all the width it's offered, with the text at the left,
+    /// rather than just what the text needs, so paragraphs in a stack
+    /// line up.
+    pub fn fill_width(self) -> Self {
+        Self {
+            fill_width: true,
+            ..self
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<RichTextLayout> {
+        let vid = cx.view_id(path);
+        cx.init_state(vid, &RichTextLayout::default);
+        StateHandle::new(vid)
+    }
+}
+
+impl View for RichText {
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        _actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let vid = cx.view_id(path);
+        match &event {
+            Event::TouchBegin { id, position } => {
+                if self.hittest(path, *position, cx).is_some() {
+                    cx.touches[*id] = vid;
+                }
+            }
+            Event::TouchEnd { id, position } => {
+                if cx.touches[*id] == vid {
+                    cx.touches[*id] = ViewId::default();
+                    let s = self.state(path, cx);
+                    let link = s.get(cx).link_at(*position).map(String::from);
+                    if let (Some(link), Some(f)) = (link, &self.on_link) {
+                        f(cx, &link);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let s = self.state(path, args.cx);
+        let layout = s.get(args.cx);
+        let vger = &mut args.vger;
+        let height = layout.size.height;
+
+        for piece in &layout.pieces {
+            let attrs = &layout.runs[piece.run].1;
+            let size = attrs.size as f32;
+            let baseline = height - layout.lines[piece.line].1;
+            let paint = vger.color_paint(attrs.color);
+
+            let s = &self.text.text()[piece.range.clone()];
+            if !s.trim().is_empty() {
+                // Where the baseline is relative to where text is drawn.
+                let x_bottom = vger.text_bounds("x", attrs.size, None).min_y();
+                vger.save();
+                vger.translate([piece.x, baseline - x_bottom]);
+                vger.text(s, attrs.size, attrs.color, None);
+                // vger has one weight, so bold is drawn twice, a little
+                // apart.
+                if attrs.weight == FontWeight::Bold {
+                    vger.translate([(size / 24.0).max(0.5), 0.0]);
+                    vger.text(s, attrs.size, attrs.color, None);
+                }
+                vger.restore();
+            }
+
+            let thickness = (size / 16.0).max(1.0);
+            let mut rule = |y: f32| {
+                let r = LocalRect::new([piece.x, y].into(), [piece.width, thickness].into());
+                vger.fill_rect(r, 0.0, paint);
+            };
+            if attrs.underline {
+                rule(baseline - size * 0.15);
+            }
+            if attrs.strikethrough {
+                rule(baseline + size * 0.3);
+            }
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let text_bounds = &mut args.text_bounds;
+        // Bracketed, so spaces and side bearings count.
+        let mut measure = |s: &str, size: u32| {
+            (text_bounds)(&format!("|{}|", s), size, None).width()
+                - (text_bounds)("||", size, None).width()
+        };
+        let mut layout = layout_runs(
+            self.text.text(),
+            self.text.runs(),
+            args.sz.width,
+            self.indent,
+            &mut measure,
+        );
+        if self.fill_width && args.sz.width.is_finite() {
+            layout.size.width = layout.size.width.max(args.sz.width);
+        }
+        let size = layout.size;
+
+        let s = self.state(path, args.cx);
+        if *s.get(args.cx) != layout {
+            args.cx[s] = layout;
+        }
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), size),
+                offset: LocalOffset::zero(),
+            },
+        );
+        size
+    }
+
+    /// Only links can be hit, so taps elsewhere go to what's behind.
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let s = self.state(path, cx);
+        s.get(cx).link_at(pt)?;
+        Some(cx.view_id(path))
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        let aid = cx.view_id(path).access_id();
+        let mut builder = accesskit::NodeBuilder::new(accesskit::Role::LabelText);
+        builder.set_name(self.text.text().to_string());
+        nodes.push((aid, builder.build(&mut cx.access_node_classes)));
+        Some(aid)
+    }
+}
+
+impl private::Sealed for RichText {}
+
+/// Shows text whose size, weight, color, underline, strikethrough and
+/// links vary along it, wrapping between words to fit the width it's
+/// offered. See `AttributedText`.
+///
+/// ```no_run
+/// # use rui::*;
+/// rich_text(
+///     AttributedText::new("See the docs")
+///         .weight(0..3, FontWeight::Bold)
+///         .link(8..12, "https://docs.rs/rui"),
+/// )
+/// .on_link(|_, url| println!("open {}", url));
+/// ```
+pub fn rich_text(text: impl Into<AttributedText>) -> RichText {
+    RichText {
+        text: text.into(),
+        on_link: None,
+        indent: 0.0,
+        fill_width: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn layout(text: &AttributedText, max_width: f32) -> RichTextLayout {
+        // Every character is as wide as the font size.
+        let mut measure = |s: &str, size: u32| (s.chars().count() as u32 * size) as f32;
+        layout_runs(text.text(), text.runs(), max_width, 0.0, &mut measure)
+    }
+
+    #[test]
+    fn test_rich_text_wraps_words() {
+        let t = AttributedText::new("aa bb\ncc").size(0..2, 20);
+        let l = layout(&t, f32::INFINITY);
+        assert_eq!(l.lines.len(), 2);
+        assert_eq!(l.size.width, 20.0 * 2.0 + 18.0 * 3.0);
+        // The first line is as tall as its largest text.
+        assert_eq!(l.lines[0], (0.0, 19.0, 25.0));
+        assert_eq!(l.lines[1].0, 25.0);
+
+        // "bb" moves down, without the space before it.
+        let l = layout(&t, 60.0);
+        let placed: Vec<_> = l
+            .pieces
+            .iter()
+            .map(|p| (p.range.clone(), p.x, p.line))
+            .collect();
+        assert_eq!(
+            placed,
+            vec![
+                (0..2, 0.0, 0),
+                (2..3, 40.0, 0),
+                (3..5, 0.0, 1),
+                (6..8, 0.0, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rich_text_word_across_runs() {
+        // "bold" and "er" wrap together.
+        let t = AttributedText::new("a bolder").weight(2..6, FontWeight::Bold);
+        let l = layout(&t, 110.0);
+        let lines: Vec<_> = l.pieces.iter().map(|p| (p.range.clone(), p.line)).collect();
+        assert_eq!(lines, vec![(0..1, 0), (1..2, 0), (2..6, 1), (6..8, 1)]);
+        assert_eq!(l.pieces[3].x, 18.0 * 4.0);
+    }
+
+    #[test]
+    fn test_rich_text_indent() {
+        let t = AttributedText::new("aa bb");
+        let mut measure = |s: &str, size: u32| (s.chars().count() as u32 * size) as f32;
+        let l = layout_runs(t.text(), t.runs(), 60.0, 10.0, &mut measure);
+        let placed: Vec<_> = l.pieces.iter().map(|p| (p.x, p.line)).collect();
+        assert_eq!(placed, vec![(10.0, 0), (46.0, 0), (10.0, 1)]);
+    }
+
+    #[test]
+    fn test_link_at() {
+        let t = AttributedText::new("go here").link(3..7, "rui://here");
+        let l = layout(&t, f32::INFINITY);
+        let height = l.size.height;
+        assert_eq!(
+            l.link_at([4.0 * 18.0, height - 5.0].into()),
+            Some("rui://here")
+        );
+        assert_eq!(l.link_at([5.0, height - 5.0].into()), None);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::*;
+use std::any::Any;
+
+/// Struct for the `scroll` modifier.
+pub struct ScrollGesture<V, F> {
+    child: V,
+    func: F,
+}
+
+impl<V, F, A> ScrollGesture<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, LocalOffset) -> A + 'static,
+{
+    pub fn new(v: V, f: F) -> Self {
+        ScrollGesture { child: v, func: f }
+    }
+}
+
+impl<V, F, A> View for ScrollGesture<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, LocalOffset) -> A + 'static,
+    A: 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Scroll { position, delta } = &event {
+            if self.hittest(path, *position, cx).is_some() {
+                actions.push(Box::new((self.func)(cx, *delta)));
+            }
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for ScrollGesture<V, F> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_scroll() {
+        let mut cx = Context::new();
+
+        let ui = state(
+            || 0.0,
+            |s, _| {
+                rectangle()
+                    .size([100.0, 100.0])
+                    .scroll(move |cx, delta| cx[s] += delta.y)
+            },
+        );
+        let sz = [200.0, 200.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<f32>::new(cx.view_id(&path));
+
+        let mut actions = vec![];
+        for position in [[50.0, 50.0], [150.0, 150.0]] {
+            let event = Event::Scroll {
+                position: position.into(),
+                delta: [0.0, 10.0].into(),
+            };
+            ui.process(&event, &mut path, &mut cx, &mut actions);
+        }
+        // Only the scroll over the view counts.
+        assert_eq!(*s.get(&cx), 10.0);
+    }
+}
@@ -0,0 +1,482 @@
+use crate::*;
+use std::any::Any;
+
+const SCROLLBAR_WIDTH: f32 = 4.0;
+const SCROLLBAR_MIN_LENGTH: f32 = 20.0;
+
+/// Fraction of momentum kept after a second.
+const SCROLL_DECAY: f32 = 0.002;
+
+/// Speed in points per second at which momentum stops.
+const SCROLL_MIN_SPEED: f32 = 10.0;
+
+/// How close to an edge dragging starts scrolling, in points.
+const AUTO_SCROLL_EDGE: f32 = 40.0;
+
+/// How fast dragging right at an edge, or past it, scrolls, in points per
+/// second.
+const AUTO_SCROLL_SPEED: f32 = 800.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct ScrollState {
+    /// How far the content is scrolled from its top left, if the position
+    /// isn't bound.
+    offset: LocalOffset,
+    /// Momentum, in points per second.
+    velocity: LocalOffset,
+    viewport: LocalSize,
+    content: LocalSize,
+    /// The touch which began inside the view.
+    touch: Option<usize>,
+    /// Where that touch is, once it's dragging something.
+    drag_point: Option<LocalPoint>,
+}
+
+impl ScrollState {
+    fn clamp(&self, offset: LocalOffset) -> LocalOffset {
+        let max = LocalOffset::new(
+            (self.content.width - self.viewport.width).max(0.0),
+            (self.content.height - self.viewport.height).max(0.0),
+        );
+        offset.max(LocalOffset::zero()).min(max)
+    }
+
+    /// Where to draw the content for a scroll position, so the top of the
+    /// content is at the top of the viewport at zero.
+    fn translation(&self, offset: LocalOffset) -> LocalOffset {
+        LocalOffset::new(
+            -offset.x,
+            self.viewport.height - self.content.height + offset.y,
+        )
+    }
+}
+
+/// How fast to scroll while dragging at `pt`, faster the closer it is to
+/// the edges of `rect`.
+fn auto_scroll_velocity(rect: LocalRect, pt: LocalPoint) -> LocalOffset {
+    let speed =
+        |distance: f32| AUTO_SCROLL_SPEED * (1.0 - distance / AUTO_SCROLL_EDGE).clamp(0.0, 1.0);
+    LocalOffset::new(
+        speed(rect.max_x() - pt.x) - speed(pt.x - rect.min_x()),
+        // Towards the top is towards zero.
+        speed(pt.y - rect.min_y()) - speed(rect.max_y() - pt.y),
+    )
+}
+
+/// Struct for `scroll_view`.
+pub struct ScrollView<V, B> {
+    child: V,
+    position: Option<B>,
+}
+
+impl<V, B> ScrollView<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    /// Reads and writes the scroll position, how far the content is
+    /// scrolled from its top left in points, with a binding, so it can be
+    /// scrolled from code.
+    pub fn scroll_position<B2: Binding<LocalOffset>>(self, position: B2) -> ScrollView<V, B2> {
+        ScrollView {
+            child: self.child,
+            position: Some(position),
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<ScrollState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &ScrollState::default);
+        StateHandle::new(id)
+    }
+
+    fn position(&self, cx: &Context, state: &ScrollState) -> LocalOffset {
+        let offset = match &self.position {
+            Some(position) => *position.get(cx),
+            None => state.offset,
+        };
+        state.clamp(offset)
+    }
+
+    /// Scrolls to `offset`, returning where it ended up.
+    fn scroll_to(
+        &self,
+        cx: &mut Context,
+        s: StateHandle<ScrollState>,
+        offset: LocalOffset,
+    ) -> LocalOffset {
+        let state = *s.get(cx);
+        let offset = state.clamp(offset);
+        if offset != self.position(cx, &state) {
+            match &self.position {
+                Some(position) => *position.get_mut(cx) = offset,
+                None => cx[s].offset = offset,
+            }
+        }
+        offset
+    }
+
+    fn draw_scrollbars(
+        &self,
+        rect: LocalRect,
+        state: &ScrollState,
+        offset: LocalOffset,
+        vger: &mut Vger,
+    ) {
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        let viewport = state.viewport;
+        let content = state.content;
+
+        if content.height > viewport.height {
+            let length =
+                (viewport.height * viewport.height / content.height).max(SCROLLBAR_MIN_LENGTH);
+            let t = offset.y / (content.height - viewport.height);
+            let top = rect.max_y() - t * (viewport.height - length);
+            vger.fill_rect(
+                euclid::rect(
+                    rect.max_x() - SCROLLBAR_WIDTH,
+                    top - length,
+                    SCROLLBAR_WIDTH,
+                    length,
+                ),
+                SCROLLBAR_WIDTH / 2.0,
+                paint,
+            );
+        }
+
+        if content.width > viewport.width {
+            let length =
+                (viewport.width * viewport.width / content.width).max(SCROLLBAR_MIN_LENGTH);
+            let t = offset.x / (content.width - viewport.width);
+            let left = rect.min_x() + t * (viewport.width - length);
+            vger.fill_rect(
+                euclid::rect(left, rect.min_y(), length, SCROLLBAR_WIDTH),
+                SCROLLBAR_WIDTH / 2.0,
+                paint,
+            );
+        }
+    }
+}
+
+impl<V, B> View for ScrollView<V, B>
+where
+    V: View,
+    B: Binding<LocalOffset>,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        let rect = cx.get_layout(path).rect;
+        // A drag to keep up to date as the content scrolls under it.
+        let mut follow = None;
+
+        match event {
+            Event::Scroll { position, delta } if rect.contains(*position) => {
+                let state = *s.get(cx);
+                let offset = self.position(cx, &state);
+                self.scroll_to(cx, s, offset + LocalOffset::new(-delta.x, delta.y));
+                // Keep going at the speed of the last scroll once it stops.
+                cx[s].velocity = LocalOffset::new(-delta.x, delta.y) / cx.anim_dt;
+            }
+            Event::Anim => {
+                let state = *s.get(cx);
+                let speed = state.velocity.length();
+                if speed > 0.0 {
+                    let dt = cx.anim_dt;
+                    let offset = self.position(cx, &state);
+                    let velocity = if speed < SCROLL_MIN_SPEED {
+                        LocalOffset::zero()
+                    } else if self.scroll_to(cx, s, offset + state.velocity * dt) == offset {
+                        // Stopped at the edge.
+                        LocalOffset::zero()
+                    } else {
+                        state.velocity * SCROLL_DECAY.powf(dt)
+                    };
+                    cx[s].velocity = velocity;
+                }
+
+                // Scroll when dragging near the edges.
+                if let (Some(id), Some(pt)) = (state.touch, state.drag_point) {
+                    let velocity = auto_scroll_velocity(rect, pt);
+                    if velocity != LocalOffset::zero() {
+                        let offset = self.position(cx, &state);
+                        let moved = self.scroll_to(cx, s, offset + velocity * cx.anim_dt) - offset;
+                        if moved != LocalOffset::zero() {
+                            follow = Some(Event::TouchMove {
+                                id,
+                                position: pt,
+                                delta: LocalOffset::new(moved.x, -moved.y),
+                            });
+                        }
+                    }
+                }
+            }
+            Event::TouchBegin { id, position } if rect.contains(*position) => {
+                cx[s].touch = Some(*id);
+            }
+            Event::TouchMove { id, position, .. } if s.get(cx).touch == Some(*id) => {
+                let drag_point = if cx.is_dragging(*id) {
+                    Some(*position)
+                } else {
+                    None
+                };
+                if drag_point != s.get(cx).drag_point {
+                    cx[s].drag_point = drag_point;
+                }
+            }
+            Event::TouchEnd { id, .. } if s.get(cx).touch == Some(*id) => {
+                cx[s].touch = None;
+                cx[s].drag_point = None;
+            }
+            _ => (),
+        }
+
+        let state = *s.get(cx);
+        let translation = state.translation(self.position(cx, &state));
+        path.push(0);
+        self.child
+            .process(&event.offset(-translation), path, cx, actions);
+        // The touch hasn't moved, but the content has.
+        if let Some(event) = follow {
+            self.child
+                .process(&event.offset(-translation), path, cx, actions);
+        }
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let state = *self.state(path, args.cx).get(args.cx);
+        let offset = self.position(args.cx, &state);
+
+        args.vger.save();
+        args.vger.scissor(rect);
+        let world = args.vger.current_transform().outer_transformed_rect(&rect);
+        let clip = args.cx.clip_to(world);
+        args.vger.translate(state.translation(offset));
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.cx.clip_rect = clip;
+        args.vger.restore();
+
+        self.draw_scrollbars(rect, &state, offset, args.vger);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+
+        // The content can be as tall as it likes.
+        path.push(0);
+        let sz = self
+            .child
+            .layout(path, &mut args.size([args.sz.width, f32::INFINITY].into()));
+        path.pop();
+
+        // Views which fill the space they're offered fill the viewport.
+        let content = LocalSize::new(
+            if sz.width.is_finite() {
+                sz.width
+            } else {
+                args.sz.width
+            },
+            if sz.height.is_finite() {
+                sz.height
+            } else {
+                args.sz.height
+            },
+        );
+        let state = *s.get(args.cx);
+        if state.viewport != args.sz || state.content != content {
+            args.cx[s].viewport = args.sz;
+            args.cx[s].content = content;
+        }
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let state = *self.state(path, cx).get(cx);
+        let translation = state.translation(self.position(cx, &state));
+        path.push(0);
+        self.child.dirty(path, xform.pre_translate(translation), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let rect = cx.get_layout(path).rect;
+
+        if rect.contains(pt) {
+            let state = *self.state(path, cx).get(cx);
+            let translation = state.translation(self.position(cx, &state));
+            path.push(0);
+            let vid = self.child.hittest(path, pt - translation, cx);
+            path.pop();
+            vid
+        } else {
+            None
+        }
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+
+    fn is_flexible(&self) -> bool {
+        true
+    }
+}
+
+impl<V, B> private::Sealed for ScrollView<V, B> {}
+
+/// Scrolls content which is larger than the view, with the mouse wheel or
+/// trackpad. The content is offered unlimited height, so stacks are as tall
+/// as their contents, and it scrolls sideways too if it's wider. Dragging
+/// something near an edge scrolls that way, faster the closer it gets.
+///
+/// ```no_run
+/// # use rui::*;
+/// scroll_view(list(
+///     (0..100).collect::<Vec<usize>>(),
+///     |i| text(&format!("row {}", i)).padding(Auto),
+/// ));
+/// ```
+pub fn scroll_view<V: View>(content: V) -> ScrollView<V, StateHandle<LocalOffset>> {
+    ScrollView {
+        child: content,
+        position: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_scroll_view() {
+        let mut cx = Context::new();
+
+        let ui = state(LocalOffset::zero, |s, _| {
+            scroll_view(rectangle().size([100.0, 300.0])).scroll_position(s)
+        });
+        let sz = [100.0, 100.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<LocalOffset>::new(cx.view_id(&path));
+
+        let scroll = |dy| Event::Scroll {
+            position: [50.0, 50.0].into(),
+            delta: [0.0, dy].into(),
+        };
+        let mut actions = vec![];
+        ui.process(&scroll(30.0), &mut path, &mut cx, &mut actions);
+        assert_eq!(s.get(&cx).y, 30.0);
+
+        // Momentum carries it further.
+        ui.process(&Event::Anim, &mut path, &mut cx, &mut actions);
+        assert!(s.get(&cx).y > 30.0);
+
+        // Only as far as the end of the content.
+        ui.process(&scroll(1000.0), &mut path, &mut cx, &mut actions);
+        assert_eq!(s.get(&cx).y, 200.0);
+    }
+
+    #[test]
+    fn test_auto_scroll() {
+        let mut cx = Context::new();
+
+        let ui = state(LocalOffset::zero, |s, _| {
+            scroll_view(rectangle().size([100.0, 300.0])).scroll_position(s)
+        });
+        let sz = [100.0, 100.0].into();
+
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz,
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let s = StateHandle::<LocalOffset>::new(cx.view_id(&path));
+
+        let mut actions = vec![];
+        let mut send = |cx: &mut Context, event: Event| {
+            cx.gestures.track(&event);
+            ui.process(&event, &mut path.clone(), cx, &mut actions);
+        };
+        send(
+            &mut cx,
+            Event::TouchBegin {
+                id: 0,
+                position: [50.0, 50.0].into(),
+            },
+        );
+        // Drag down to the bottom edge.
+        send(
+            &mut cx,
+            Event::TouchMove {
+                id: 0,
+                position: [50.0, 10.0].into(),
+                delta: [0.0, -40.0].into(),
+            },
+        );
+        cx.anim_dt = 0.1;
+        send(&mut cx, Event::Anim);
+        assert_eq!(s.get(&cx).y, 60.0);
+
+        // Not after letting go.
+        send(
+            &mut cx,
+            Event::TouchEnd {
+                id: 0,
+                position: [50.0, 10.0].into(),
+            },
+        );
+        send(&mut cx, Event::Anim);
+        assert_eq!(s.get(&cx).y, 60.0);
+    }
+}
@@ -67,7 +67,8 @@ pub fn circle() -> Circle {
 /// Struct for `rectangle`.
 #[derive(Clone)]
 pub struct Rectangle {
-    corner_radius: f32,
+    /// Uses `CornerRadius` from the environment if not set.
+    corner_radius: Option<f32>,
     paint: Paint,
 }
 
@@ -87,7 +88,7 @@ impl Rectangle {
     /// Sets the rectangle's corner radius.
     pub fn corner_radius(self, radius: f32) -> Rectangle {
         Rectangle {
-            corner_radius: radius,
+            corner_radius: Some(radius),
             paint: self.paint,
         }
     }
@@ -97,9 +98,14 @@ impl View for Rectangle {
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let rect = self.geom(path, args.cx);
 
+        let radius = match self.corner_radius {
+            Some(radius) => radius,
+            None => args.cx.init_env(&CornerRadius::default).0,
+        };
+
         let vger = &mut args.vger;
         let paint = self.paint.vger_paint(vger);
-        vger.fill_rect(rect, self.corner_radius, paint);
+        vger.fill_rect(rect, radius, paint);
     }
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
@@ -133,7 +139,7 @@ impl private::Sealed for Rectangle {}
 /// Renders a rectangle which expands to fill available space.
 pub fn rectangle() -> Rectangle {
     Rectangle {
-        corner_radius: 0.0,
+        corner_radius: None,
         paint: Paint::Color(Color::CYAN),
     }
 }
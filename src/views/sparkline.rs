@@ -0,0 +1,255 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+const LINE_WIDTH: f32 = 1.5;
+const DOT_RADIUS: f32 = 2.0;
+
+/// Options for `sparkline`. Set them with `SparklineMods`.
+#[derive(Clone)]
+pub struct SparklineOptions {
+    color: Color,
+    area: bool,
+    min_max: bool,
+    last_dot: bool,
+}
+
+impl Default for SparklineOptions {
+    fn default() -> Self {
+        Self {
+            color: AZURE_HIGHLIGHT,
+            area: false,
+            min_max: false,
+            last_dot: true,
+        }
+    }
+}
+
+impl SparklineOptions {
+    /// Everything drawing depends on, for `cached_canvas`.
+    fn key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let c = self.color;
+        [c.r, c.g, c.b, c.a].map(f32::to_bits).hash(&mut hasher);
+        (self.area, self.min_max, self.last_dot).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+pub trait SparklineMods: View + Sized {
+    /// Color of the line, the area under it and the last value's dot. The
+    /// default is `AZURE_HIGHLIGHT`.
+    fn line_color(self, color: Color) -> Self;
+
+    /// Shades the area under the line. Off by default.
+    fn area(self, show: bool) -> Self;
+
+    /// Marks the lowest value in red and the highest in green. Off by
+    /// default.
+    fn min_max(self, show: bool) -> Self;
+
+    /// Puts a dot on the last value. On by default.
+    fn last_dot(self, show: bool) -> Self;
+}
+
+impl<F> SparklineMods for ModView<SparklineOptions, F>
+where
+    ModView<SparklineOptions, F>: View,
+{
+    fn line_color(self, color: Color) -> Self {
+        let mut opts = self.value;
+        opts.color = color;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn area(self, show: bool) -> Self {
+        let mut opts = self.value;
+        opts.area = show;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn min_max(self, show: bool) -> Self {
+        let mut opts = self.value;
+        opts.min_max = show;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn last_dot(self, show: bool) -> Self {
+        let mut opts = self.value;
+        opts.last_dot = show;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+/// Where each value goes in `rect`, inset so dots fit. Values which
+/// aren't finite have no point, which breaks the line.
+fn sparkline_points(values: &[f32], rect: LocalRect) -> Vec<Option<LocalPoint>> {
+    let finite = values.iter().filter(|v| v.is_finite());
+    let min = finite.clone().fold(f32::INFINITY, |a, b| a.min(*b));
+    let max = finite.fold(f32::NEG_INFINITY, |a, b| a.max(*b));
+    let inner = rect.inflate(-DOT_RADIUS, -DOT_RADIUS);
+    let dx = if values.len() > 1 {
+        inner.width() / (values.len() - 1) as f32
+    } else {
+        0.0
+    };
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            if !v.is_finite() {
+                return None;
+            }
+            // Flat data goes through the middle.
+            let t = if max > min {
+                (v - min) / (max - min)
+            } else {
+                0.5
+            };
+            Some(LocalPoint::new(
+                inner.min_x() + dx * i as f32,
+                inner.min_y() + t * inner.height(),
+            ))
+        })
+        .collect()
+}
+
+/// The indices of the first lowest and highest finite values.
+fn extremes(values: &[f32]) -> Option<(usize, usize)> {
+    let mut finite = values.iter().enumerate().filter(|(_, v)| v.is_finite());
+    let first = finite.next()?;
+    let (min, max) = finite.fold((first, first), |(min, max), x| {
+        (
+            if x.1 < min.1 { x } else { min },
+            if x.1 > max.1 { x } else { max },
+        )
+    });
+    Some((min.0, max.0))
+}
+
+fn draw_sparkline(values: &[f32], rect: LocalRect, opts: &SparklineOptions, list: &mut DrawList) {
+    let points = sparkline_points(values, rect);
+    let mut line = CanvasPath::new();
+    let mut area = CanvasPath::new();
+    for run in points.split(|p| p.is_none()) {
+        let run: Vec<LocalPoint> = run.iter().flatten().copied().collect();
+        let (first, last) = match (run.first(), run.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => continue,
+        };
+        line.move_to(first);
+        for p in &run[1..] {
+            line.line_to(*p);
+        }
+        area.move_to([first.x, rect.min_y()]);
+        for p in &run {
+            area.line_to(*p);
+        }
+        area.line_to([last.x, rect.min_y()]).close();
+    }
+
+    let c = opts.color;
+    if opts.area && !area.is_empty() {
+        list.fill_path(
+            &area,
+            CanvasPaint::LinearGradient {
+                start: [rect.min_x(), rect.max_y()].into(),
+                end: rect.min(),
+                inner: Color::new(c.r, c.g, c.b, c.a * 0.35),
+                outer: Color::new(c.r, c.g, c.b, 0.0),
+            },
+        );
+    }
+    list.stroke_path(&line, LINE_WIDTH, c);
+
+    if opts.min_max {
+        if let Some((min, max)) = extremes(values) {
+            if let (Some(min), Some(max)) = (points[min], points[max]) {
+                list.fill_circle(min, DOT_RADIUS, RED_HIGHLIGHT);
+                list.fill_circle(max, DOT_RADIUS, GREEN_HIGHLIGHT);
+            }
+        }
+    }
+    if opts.last_dot {
+        if let Some(Some(last)) = points.last() {
+            list.fill_circle(*last, DOT_RADIUS, c);
+        }
+    }
+}
+
+/// A small line chart of `values` without axes, for showing a trend at a
+/// glance in table cells, lists and status bars. It takes the space it's
+/// offered, so give it a size. The line is only rebuilt when the values,
+/// options or size change.
+///
+/// ```no_run
+/// # use rui::*;
+/// hstack((
+///     text("CPU"),
+///     sparkline(vec![12.0, 30.0, 22.0, 48.0, 41.0, 65.0])
+///         .area(true)
+///         .min_max(true)
+///         .size([80.0, 16.0]),
+/// ));
+/// ```
+pub fn sparkline(values: impl Into<Vec<f32>>) -> impl SparklineMods {
+    let values = Rc::new(values.into());
+    let mut hasher = DefaultHasher::new();
+    for v in values.iter() {
+        v.to_bits().hash(&mut hasher);
+    }
+    let data = hasher.finish();
+    modview(move |opts: SparklineOptions, _| {
+        let values = values.clone();
+        cached_canvas((data, opts.key()), move |_, rect, list| {
+            draw_sparkline(&values, rect, &opts, list)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_sparkline_points() {
+        let rect = LocalRect::new(LocalPoint::zero(), [104.0, 24.0].into());
+        let points = sparkline_points(&[0.0, 10.0, 5.0], rect);
+        assert_eq!(
+            points,
+            vec![
+                Some(LocalPoint::new(2.0, 2.0)),
+                Some(LocalPoint::new(52.0, 22.0)),
+                Some(LocalPoint::new(102.0, 12.0)),
+            ]
+        );
+
+        // Flat, with a gap.
+        let points = sparkline_points(&[3.0, f32::NAN, 3.0], rect);
+        assert_eq!(points[0], Some(LocalPoint::new(2.0, 12.0)));
+        assert_eq!(points[1], None);
+        assert_eq!(points[2], Some(LocalPoint::new(102.0, 12.0)));
+    }
+
+    #[test]
+    fn test_sparkline_extremes() {
+        assert_eq!(extremes(&[]), None);
+        assert_eq!(extremes(&[f32::NAN]), None);
+        assert_eq!(extremes(&[4.0, f32::NAN, 1.0, 9.0, 1.0]), Some((2, 3)));
+        assert_eq!(extremes(&[2.0]), Some((0, 0)));
+    }
+}
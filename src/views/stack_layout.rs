@@ -7,7 +7,8 @@ pub enum StackItem {
 ///
 /// Returns length used to express the layout. If there are any
 /// flexible items, will return `total`, since the flexible items
-/// will expand to fill the available space.
+/// will expand to fill the available space, unless `total` is infinite,
+/// as it is in a `scroll_view`, when they have no length.
 pub fn stack_layout(
     total: f32,
     sizes: &[StackItem],
@@ -27,7 +28,11 @@ pub fn stack_layout(
     }
 
     // length of flexible items is remaining size divided equally
-    *flex_length = (total - sizes_sum) / (flex_count as f32);
+    *flex_length = if total.is_finite() {
+        (total - sizes_sum) / (flex_count as f32)
+    } else {
+        0.0
+    };
 
     let mut x = 0.0;
     for i in 0..sizes.len() {
@@ -87,5 +92,17 @@ mod tests {
             assert_eq!(length, 4.0);
             println!("intervals: {:?}", intervals);
         }
+
+        {
+            let sizes = [Fixed(1.0), Flexible, Fixed(1.0)];
+            let mut intervals = [(0.0, 0.0); 3];
+
+            let mut flex_length = 0.0;
+            let length = stack_layout(f32::INFINITY, &sizes, &mut intervals, &mut flex_length);
+
+            assert_eq!(flex_length, 0.0);
+            assert_eq!(length, 2.0);
+            println!("intervals: {:?}", intervals);
+        }
     }
 }
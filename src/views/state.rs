@@ -45,6 +45,20 @@ impl<S: 'static> Binding<S> for StateHandle<S> {
 struct StateView<D, F> {
     default: D,
     func: F,
+    name: Option<&'static str>,
+}
+
+impl<S, D, F> StateView<D, F>
+where
+    S: 'static,
+    D: Fn() -> S + 'static,
+{
+    fn init(&self, id: ViewId, cx: &mut Context) {
+        cx.init_state(id, &self.default);
+        if self.name.is_some() {
+            cx.state_map.get_mut(&id).unwrap().name = self.name;
+        }
+    }
 }
 
 impl<S, V, D, F> View for StateView<D, F>
@@ -62,7 +76,7 @@ where
         actions: &mut Vec<Box<dyn Any>>,
     ) {
         let id = cx.view_id(path);
-        cx.init_state(id, &self.default);
+        self.init(id, cx);
         path.push(0);
         (self.func)(StateHandle::new(id), cx).process(event, path, cx, actions);
         path.pop();
@@ -70,7 +84,7 @@ where
 
     fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
         let id = args.cx.view_id(path);
-        args.cx.init_state(id, &self.default);
+        self.init(id, args.cx);
         path.push(0);
         (self.func)(StateHandle::new(id), args.cx).draw(path, args);
         path.pop();
@@ -78,7 +92,7 @@ where
 
     fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
         let id = args.cx.view_id(path);
-        args.cx.init_state(id, &self.default);
+        self.init(id, args.cx);
 
         // Do we need to recompute layout?
         let mut compute_layout = true;
@@ -98,6 +112,9 @@ where
         }
 
         if compute_layout {
+            if args.cx.explain_updates {
+                args.cx.relaid_out.push(id);
+            }
             args.cx.id_stack.push(id);
 
             let view = (self.func)(StateHandle::new(id), args.cx);
@@ -108,7 +125,10 @@ where
             // Compute layout dependencies.
             let mut deps = vec![];
             deps.append(&mut args.cx.id_stack.clone());
+            let collecting_deps = args.cx.collecting_deps;
+            args.cx.collecting_deps = true;
             view.gc(path, args.cx, &mut deps);
+            args.cx.collecting_deps = collecting_deps;
 
             path.pop();
 
@@ -127,14 +147,9 @@ where
     }
 
     fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
-        let default = &self.default;
         let id = cx.view_id(path);
-        let holder = cx.state_map.entry(id).or_insert_with(|| StateHolder {
-            state: Box::new((default)()),
-            dirty: false,
-        });
-
-        if holder.dirty {
+        self.init(id, cx);
+        if cx.state_map[&id].dirty {
             // Add a region.
             let rect = cx.get_layout(path).rect;
             let pts: [LocalPoint; 4] = [
@@ -154,7 +169,7 @@ where
 
     fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
         let id = cx.view_id(path);
-        cx.init_state(id, &self.default);
+        self.init(id, cx);
         path.push(0);
         let hit_id = (self.func)(StateHandle::new(id), cx).hittest(path, pt, cx);
         path.pop();
@@ -163,7 +178,7 @@ where
 
     fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
         let id = cx.view_id(path);
-        cx.init_state(id, &self.default);
+        self.init(id, cx);
         path.push(0);
         (self.func)(StateHandle::new(id), cx).commands(path, cx, cmds);
         path.pop();
@@ -171,7 +186,7 @@ where
 
     fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
         let id = cx.view_id(path);
-        cx.init_state(id, &self.default);
+        self.init(id, cx);
         map.push(id);
         path.push(0);
         (self.func)(StateHandle::new(id), cx).gc(path, cx, map);
@@ -185,7 +200,7 @@ where
         nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
     ) -> Option<accesskit::NodeId> {
         let id = cx.view_id(path);
-        cx.init_state(id, &self.default);
+        self.init(id, cx);
         path.push(0);
         let node_id = (self.func)(StateHandle::new(id), cx).access(path, cx, nodes);
         path.pop();
@@ -214,6 +229,25 @@ pub fn state<
     StateView {
         default: initial,
         func: f,
+        name: None,
+    }
+}
+
+/// Like `state`, with a name for the state in `Context::dump_state`.
+pub fn named_state<
+    S: 'static,
+    V: View,
+    D: Fn() -> S + 'static,
+    F: Fn(StateHandle<S>, &Context) -> V + 'static,
+>(
+    name: &'static str,
+    initial: D,
+    f: F,
+) -> impl View {
+    StateView {
+        default: initial,
+        func: f,
+        name: Some(name),
     }
 }
 
@@ -0,0 +1,434 @@
+use crate::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use usvg::tiny_skia_path::PathSegment;
+use usvg::TreeParsing;
+
+/// Most sizes to keep an SVG's scaled shapes for.
+const MAX_SCALED_SIZES: usize = 8;
+
+/// `paint` in `tint` if there is one, keeping its alpha. Gradients take
+/// their start alpha.
+fn tinted(paint: CanvasPaint, tint: Option<Color>) -> CanvasPaint {
+    let alpha = match paint {
+        CanvasPaint::Color(color) | CanvasPaint::LinearGradient { inner: color, .. } => color.a,
+    };
+    match tint {
+        Some(tint) => CanvasPaint::Color(Color::new(tint.r, tint.g, tint.b, tint.a * alpha)),
+        None => paint,
+    }
+}
+
+/// How a shape in an SVG is painted. vger gradients have two colors, so
+/// SVG gradients are drawn with their first and last stops, and radial
+/// ones in their first color.
+#[derive(Clone, Debug, PartialEq)]
+struct SvgShape {
+    path: CanvasPath,
+    fill: Option<CanvasPaint>,
+    stroke: Option<(CanvasPaint, f32)>,
+}
+
+impl SvgShape {
+    fn transformed(&self, xform: &Transform2D<f32, LocalSpace, LocalSpace>, scale: f32) -> Self {
+        let paint = |paint: CanvasPaint| match paint {
+            CanvasPaint::LinearGradient {
+                start,
+                end,
+                inner,
+                outer,
+            } => CanvasPaint::LinearGradient {
+                start: xform.transform_point(start),
+                end: xform.transform_point(end),
+                inner,
+                outer,
+            },
+            color => color,
+        };
+        Self {
+            path: self.path.transformed(xform),
+            fill: self.fill.map(paint),
+            stroke: self.stroke.map(|(p, width)| (paint(p), width * scale)),
+        }
+    }
+}
+
+/// An SVG parsed into shapes vger can draw. Text, images, patterns,
+/// clips, masks and filters are left out, which suits icons.
+pub struct SvgImage {
+    size: LocalSize,
+    /// In SVG units, from the top left.
+    shapes: Vec<SvgShape>,
+    /// The shapes scaled to sizes they've been drawn at, keyed on the
+    /// bits of the size, so drawing at the same size doesn't redo it.
+    scaled: Mutex<HashMap<(u32, u32), Arc<Vec<SvgShape>>>>,
+}
+
+fn svg_color(c: usvg::Color, alpha: f32) -> Color {
+    Color::new(
+        c.red as f32 / 255.0,
+        c.green as f32 / 255.0,
+        c.blue as f32 / 255.0,
+        alpha,
+    )
+}
+
+fn apply(t: usvg::Transform, x: f32, y: f32) -> LocalPoint {
+    LocalPoint::new(t.sx * x + t.kx * y + t.tx, t.ky * x + t.sy * y + t.ty)
+}
+
+fn svg_paint(
+    paint: &usvg::Paint,
+    opacity: f32,
+    bounds: usvg::tiny_skia_path::Rect,
+    t: usvg::Transform,
+) -> Option<CanvasPaint> {
+    let stop = |s: Option<&usvg::Stop>| {
+        s.map(|s| svg_color(s.color, s.opacity.get() * opacity))
+            .unwrap_or(CLEAR_COLOR)
+    };
+    match paint {
+        usvg::Paint::Color(c) => Some(CanvasPaint::Color(svg_color(*c, opacity))),
+        usvg::Paint::LinearGradient(g) => {
+            let point = |x: f32, y: f32| {
+                let p = apply(g.transform, x, y);
+                let p = if g.units == usvg::Units::ObjectBoundingBox {
+                    LocalPoint::new(
+                        bounds.x() + p.x * bounds.width(),
+                        bounds.y() + p.y * bounds.height(),
+                    )
+                } else {
+                    p
+                };
+                apply(t, p.x, p.y)
+            };
+            Some(CanvasPaint::LinearGradient {
+                start: point(g.x1, g.y1),
+                end: point(g.x2, g.y2),
+                inner: stop(g.stops.first()),
+                outer: stop(g.stops.last()),
+            })
+        }
+        usvg::Paint::RadialGradient(g) => Some(CanvasPaint::Color(stop(g.stops.first()))),
+        usvg::Paint::Pattern(_) => None,
+    }
+}
+
+fn svg_shape(path: &usvg::Path, t: usvg::Transform, opacity: f32) -> SvgShape {
+    let point = |p: usvg::tiny_skia_path::Point| apply(t, p.x, p.y);
+    let mut shape = CanvasPath::new();
+    for segment in path.data.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => shape.move_to(point(p)),
+            PathSegment::LineTo(p) => shape.line_to(point(p)),
+            PathSegment::QuadTo(c, p) => shape.quad_to(point(c), point(p)),
+            PathSegment::CubicTo(c1, c2, p) => shape.cubic_to(point(c1), point(c2), point(p)),
+            PathSegment::Close => shape.close(),
+        };
+    }
+
+    let bounds = path.data.bounds();
+    // How much the transform scales stroke widths, on average.
+    let scale = (t.sx * t.sy - t.kx * t.ky).abs().sqrt();
+    SvgShape {
+        path: shape,
+        fill: path
+            .fill
+            .as_ref()
+            .and_then(|f| svg_paint(&f.paint, f.opacity.get() * opacity, bounds, t)),
+        stroke: path.stroke.as_ref().and_then(|s| {
+            svg_paint(&s.paint, s.opacity.get() * opacity, bounds, t)
+                .map(|paint| (paint, s.width.get() * scale))
+        }),
+    }
+}
+
+fn add_shapes(node: &usvg::Node, t: usvg::Transform, opacity: f32, shapes: &mut Vec<SvgShape>) {
+    match *node.borrow() {
+        usvg::NodeKind::Group(ref group) => {
+            let (t, opacity) = (t.pre_concat(group.transform), opacity * group.opacity.get());
+            for child in node.children() {
+                add_shapes(&child, t, opacity, shapes);
+            }
+        }
+        usvg::NodeKind::Path(ref path) if path.visibility == usvg::Visibility::Visible => {
+            shapes.push(svg_shape(path, t.pre_concat(path.transform), opacity));
+        }
+        _ => (),
+    }
+}
+
+impl SvgImage {
+    pub fn parse(data: &[u8]) -> Result<Self, String> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+            .map_err(|err| err.to_string())?;
+        let size = LocalSize::new(tree.size.width(), tree.size.height());
+
+        // From the view box to the size.
+        let view_box = tree.view_box.rect;
+        let (sx, sy) = (
+            size.width / view_box.width(),
+            size.height / view_box.height(),
+        );
+        let t = usvg::Transform::from_row(sx, 0.0, 0.0, sy, -view_box.x() * sx, -view_box.y() * sy);
+
+        let mut shapes = vec![];
+        add_shapes(&tree.root, t, 1.0, &mut shapes);
+        Ok(Self {
+            size,
+            shapes,
+            scaled: Default::default(),
+        })
+    }
+
+    /// The width and height the SVG gives.
+    pub fn size(&self) -> LocalSize {
+        self.size
+    }
+
+    /// Whether there's nothing to draw.
+    pub(crate) fn is_blank(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// The shapes scaled to `size`, with y going up.
+    fn scaled(&self, size: LocalSize) -> Arc<Vec<SvgShape>> {
+        let key = (size.width.to_bits(), size.height.to_bits());
+        let mut scaled = self.scaled.lock().unwrap();
+        if let Some(shapes) = scaled.get(&key) {
+            return shapes.clone();
+        }
+        if scaled.len() >= MAX_SCALED_SIZES {
+            scaled.clear();
+        }
+        let (sx, sy) = (size.width / self.size.width, size.height / self.size.height);
+        // Flipped, so y goes up.
+        let flip = Transform2D::new(sx, 0.0, 0.0, -sy, 0.0, size.height);
+        let shapes: Arc<Vec<SvgShape>> = Arc::new(
+            self.shapes
+                .iter()
+                .map(|shape| shape.transformed(&flip, (sx * sy).sqrt()))
+                .collect(),
+        );
+        scaled.insert(key, shapes.clone());
+        shapes
+    }
+
+    /// Draws the SVG stretched over `rect`, in `tint` if there is one.
+    fn draw(&self, vger: &mut Vger, rect: LocalRect, tint: Option<Color>) {
+        if self.size.is_empty() || rect.is_empty() {
+            return;
+        }
+        let shapes = self.scaled(rect.size);
+        vger.save();
+        vger.translate(rect.origin.to_vector());
+        for shape in shapes.iter() {
+            if let Some(fill) = shape.fill {
+                let paint = tinted(fill, tint).paint(vger);
+                vger.fill_path(&shape.path, paint);
+            }
+            if let Some((stroke, width)) = shape.stroke {
+                let paint = tinted(stroke, tint).paint(vger);
+                vger.stroke_path(&shape.path, width, paint);
+            }
+        }
+        vger.restore();
+    }
+}
+
+/// Where an SVG `size` goes in `rect` for `mode`. When filling, it's
+/// bigger than `rect`.
+fn svg_rect(mode: ContentMode, size: LocalSize, rect: LocalRect) -> Option<LocalRect> {
+    if size.is_empty() || rect.is_empty() {
+        return None;
+    }
+    let (sx, sy) = (rect.width() / size.width, rect.height() / size.height);
+    let scaled = match mode {
+        ContentMode::Stretch => return Some(rect),
+        ContentMode::Fit => size * sx.min(sy),
+        ContentMode::Fill => size * sx.max(sy),
+    };
+    Some(LocalRect::new(
+        rect.center() - scaled.to_vector() / 2.0,
+        scaled,
+    ))
+}
+
+#[derive(Clone)]
+enum SvgSource {
+    Data(Arc<[u8]>),
+    Asset(String),
+}
+
+/// Struct for `svg` and `svg_file`.
+pub struct Svg {
+    source: SvgSource,
+    key: String,
+    mode: ContentMode,
+    tint: Option<Color>,
+}
+
+impl Svg {
+    /// Sets how the SVG fits the space it's offered. The default is
+    /// `ContentMode::Fit`.
+    pub fn content_mode(mut self, mode: ContentMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Draws everything in `color`, keeping the SVG's opacity, as for
+    /// icons which follow the text color.
+    pub fn tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+
+    /// The parsed SVG, once it's ready. An SVG which fails to parse shows
+    /// nothing.
+    fn load(&self, cx: &mut Context) -> Option<Arc<SvgImage>> {
+        let asset = match &self.source {
+            SvgSource::Data(data) => {
+                let data = data.clone();
+                cx.load_asset(&self.key, move || SvgImage::parse(&data))
+            }
+            SvgSource::Asset(path) => {
+                let (assets, path) = (cx.assets(), path.clone());
+                cx.load_asset(&self.key, move || SvgImage::parse(&assets.read(&path)?))
+            }
+        };
+        match asset {
+            Asset::Ready(svg) => Some(svg),
+            Asset::Loading | Asset::Failed(_) => None,
+        }
+    }
+}
+
+impl View for Svg {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let svg = match self.load(args.cx) {
+            Some(svg) => svg,
+            None => return,
+        };
+        if let Some(placed) = svg_rect(self.mode, svg.size(), rect) {
+            args.vger.save();
+            if self.mode == ContentMode::Fill {
+                args.vger.scissor(rect);
+            }
+            svg.draw(args.vger, placed, self.tint);
+            args.vger.restore();
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+}
+
+impl private::Sealed for Svg {}
+
+/// Shows an SVG, taking all the space it's offered, so icons can be vector
+/// assets. It's parsed in the background, and the shapes are kept for
+/// each size it's drawn at.
+///
+/// ```no_run
+/// # use rui::*;
+/// let icon = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24">
+///     <circle cx="12" cy="12" r="10" fill="black"/>
+/// </svg>"#;
+/// svg(icon).tint(AZURE_HIGHLIGHT).size([24.0, 24.0]);
+/// ```
+pub fn svg(data: impl AsRef<[u8]>) -> Svg {
+    let data: Arc<[u8]> = data.as_ref().into();
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    Svg {
+        key: format!("rui svg {:016x}", hasher.finish()),
+        source: SvgSource::Data(data),
+        mode: ContentMode::Fit,
+        tint: None,
+    }
+}
+
+/// Shows an SVG file from the `Assets` in the environment, like
+/// "icons/open.svg". See `svg`.
+pub fn svg_file(path: &str) -> Svg {
+    Svg {
+        key: format!("rui svg {}", path),
+        source: SvgSource::Asset(path.into()),
+        mode: ContentMode::Fit,
+        tint: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const SQUARE: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10" viewBox="0 0 40 20">
+        <rect width="40" height="20" fill="#ff0000"/>
+    </svg>"##;
+
+    #[test]
+    fn test_svg_parse() {
+        let svg = SvgImage::parse(SQUARE.as_bytes()).unwrap();
+        assert_eq!(svg.size(), LocalSize::new(20.0, 10.0));
+        assert_eq!(svg.shapes.len(), 1);
+
+        let shape = &svg.shapes[0];
+        assert_eq!(
+            shape.fill,
+            Some(CanvasPaint::Color(Color::new(1.0, 0.0, 0.0, 1.0)))
+        );
+        assert_eq!(shape.stroke, None);
+        assert_eq!(
+            shape.path.segments[0],
+            Segment::Move(LocalPoint::new(0.0, 0.0))
+        );
+        assert!(shape
+            .path
+            .segments
+            .contains(&Segment::Line(LocalPoint::new(20.0, 10.0))));
+
+        assert!(SvgImage::parse(b"not svg").is_err());
+    }
+
+    #[test]
+    fn test_svg_scaled() {
+        let svg = SvgImage::parse(SQUARE.as_bytes()).unwrap();
+        let a = svg.scaled(LocalSize::new(40.0, 20.0));
+        assert!(Arc::ptr_eq(&a, &svg.scaled(LocalSize::new(40.0, 20.0))));
+
+        // Flipped, so the top left is at the top.
+        assert_eq!(
+            a[0].path.segments[0],
+            Segment::Move(LocalPoint::new(0.0, 20.0))
+        );
+        assert!(!Arc::ptr_eq(&a, &svg.scaled(LocalSize::new(20.0, 10.0))));
+    }
+
+    #[test]
+    fn test_svg_rect() {
+        let size = LocalSize::new(20.0, 10.0);
+        let rect = LocalRect::new(LocalPoint::zero(), [40.0, 40.0].into());
+        assert_eq!(
+            svg_rect(ContentMode::Fit, size, rect),
+            Some(LocalRect::new([0.0, 10.0].into(), [40.0, 20.0].into()))
+        );
+        assert_eq!(
+            svg_rect(ContentMode::Fill, size, rect),
+            Some(LocalRect::new([-20.0, 0.0].into(), [80.0, 40.0].into()))
+        );
+        assert_eq!(svg_rect(ContentMode::Stretch, size, rect), Some(rect));
+    }
+}
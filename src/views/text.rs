@@ -3,39 +3,213 @@ use crate::*;
 pub trait TextModifiers: View + Sized {
     fn font_size(self, size: u32) -> Text;
     fn color(self, color: Color) -> Text;
+    fn foreground(self, paint: Paint) -> Text;
 }
 
 /// Struct for `text`.
 #[derive(Clone)]
 pub struct Text {
     text: String,
-    size: u32,
+    /// Set with `font_size`, overriding the `font` modifier's size.
+    size: Option<u32>,
     color: Color,
+    /// Overrides `color`, for gradient text.
+    foreground: Option<Paint>,
 }
 
 impl Text {
     pub const DEFAULT_SIZE: u32 = 18;
     pub fn color(self, color: Color) -> Text {
         Text {
-            text: self.text,
-            size: self.size,
             color,
+            foreground: None,
+            ..self
         }
     }
-}
 
-impl View for Text {
-    fn draw(&self, _path: &mut IdPath, args: &mut DrawArgs) {
-        let vger = &mut args.vger;
-        let origin = vger.text_bounds(self.text.as_str(), self.size, None).origin;
+    /// The font from the `font` modifier, and the size to draw at.
+    fn font(&self, cx: &mut Context) -> (Font, u32) {
+        let font = cx.init_env(&Font::default);
+        let size = self.size.or(font.size).unwrap_or(Text::DEFAULT_SIZE);
+        (font, size)
+    }
+
+    fn draw_once(
+        &self,
+        cx: &mut Context,
+        runs: &[FontRun],
+        size: u32,
+        color: Color,
+        vger: &mut Vger,
+    ) {
+        match runs {
+            [] => (),
+            [FontRun {
+                face: Some(face), ..
+            }] => {
+                let origin = face.text_bounds(self.text.as_str(), size).origin;
+                vger.translate([-origin.x, -origin.y]);
+                let paint = vger.color_paint(color);
+                face.draw_text(self.text.as_str(), size, paint, color, vger);
+                face.draw_bitmaps(self.text.as_str(), size, cx, vger);
+            }
+            [FontRun { face: None, .. }] => {
+                let origin = vger.text_bounds(self.text.as_str(), size, None).origin;
+                vger.translate([-origin.x, -origin.y]);
+                vger.text(self.text.as_str(), size, color, None);
+            }
+            _ => {
+                let (xs, bounds) = measure_runs(runs, size, &mut |s, size, max_width| {
+                    vger.text_bounds(s, size, max_width)
+                });
+                let baseline = vger.text_bounds("x", size, None).min_y();
+                vger.translate([-bounds.origin.x, -bounds.origin.y]);
+                for (run, x) in runs.iter().zip(xs) {
+                    vger.save();
+                    match &run.face {
+                        Some(face) => {
+                            vger.translate([x, 0.0]);
+                            let paint = vger.color_paint(color);
+                            face.draw_text(&run.text, size, paint, color, vger);
+                            face.draw_bitmaps(&run.text, size, cx, vger);
+                        }
+                        None => {
+                            vger.translate([x, -baseline]);
+                            vger.text(&run.text, size, color, None);
+                        }
+                    }
+                    vger.restore();
+                }
+            }
+        }
+    }
 
+    /// Draws the text in `color`, and again `bold` points over for faux
+    /// bold. Bitmap glyphs go to the image cache in `cx`.
+    fn draw_glyphs(
+        &self,
+        cx: &mut Context,
+        runs: &[FontRun],
+        size: u32,
+        bold: Option<f32>,
+        color: Color,
+        vger: &mut Vger,
+    ) {
         vger.save();
-        vger.translate([-origin.x, -origin.y]);
-        vger.text(self.text.as_str(), self.size, self.color, None);
+        self.draw_once(cx, runs, size, color, vger);
         vger.restore();
+
+        if let Some(offset) = bold {
+            vger.save();
+            vger.translate([offset, 0.0]);
+            self.draw_once(cx, runs, size, color, vger);
+            vger.restore();
+        }
     }
-    fn layout(&self, _path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
-        (args.text_bounds)(self.text.as_str(), self.size, None).size
+}
+
+/// Places runs in different fonts one after another on a shared baseline,
+/// returning where each starts, and the bounds of them all, with the
+/// baseline at zero.
+fn measure_runs(
+    runs: &[FontRun],
+    size: u32,
+    text_bounds: &mut dyn FnMut(&str, u32, Option<f32>) -> LocalRect,
+) -> (Vec<f32>, LocalRect) {
+    let baseline = text_bounds("x", size, None).min_y();
+    let mut xs = vec![];
+    let mut bounds: Option<LocalRect> = None;
+    let mut x = 0.0;
+    for run in runs {
+        let (rect, advance) = match &run.face {
+            Some(face) => {
+                let rect = face.text_bounds(&run.text, size);
+                (rect, rect.width())
+            }
+            None => {
+                // Bounds are of the ink, so measure the advance between bars.
+                let bars = text_bounds("||", size, None).width();
+                let advance = text_bounds(&format!("|{}|", run.text), size, None).width() - bars;
+                let rect = text_bounds(&run.text, size, None);
+                (rect.translate([0.0, -baseline].into()), advance)
+            }
+        };
+        let rect = rect.translate([x, 0.0].into());
+        bounds = Some(bounds.map_or(rect, |b| b.union(&rect)));
+        xs.push(x);
+        x += advance;
+    }
+    (xs, bounds.unwrap_or_else(LocalRect::zero))
+}
+
+/// Bold without a bold face is drawn a second time, this far over.
+fn faux_bold_offset(font: &Font, face: Option<&FontFace>, size: u32) -> Option<f32> {
+    let has_bold = face.map_or(false, |face| face.weight() == FontWeight::Bold);
+    if font.weight == FontWeight::Bold && !has_bold {
+        Some(size as f32 / 24.0)
+    } else {
+        None
+    }
+}
+
+impl View for Text {
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let (font, size) = self.font(args.cx);
+        let face = args.cx.fonts.face(&font).cloned();
+        let runs = args.cx.fonts.runs(&self.text, &font);
+        let bold = faux_bold_offset(&font, face.as_ref(), size);
+        let rect = args.cx.get_layout(path).rect;
+
+        let paint = match &self.foreground {
+            Some(Paint::Color(color)) => {
+                self.draw_glyphs(args.cx, &runs, size, bold, *color, args.vger);
+                return;
+            }
+            Some(paint) => paint,
+            None => {
+                self.draw_glyphs(args.cx, &runs, size, bold, self.color, args.vger);
+                return;
+            }
+        };
+        // vger fills glyphs with a single color, so the paint fills the
+        // text's bounds, masked by the text.
+        match layer_rect(args.cx, args.vger, rect, 0.0) {
+            Some(world) => {
+                let effect = Some(Effect::identity());
+                let layer = draw_into(args.cx, args.vger, world, effect, |_, vger| {
+                    let paint = paint.vger_paint(vger);
+                    vger.fill_rect(rect, 0.0, paint);
+                });
+                draw_mask(args.cx, args.vger, layer, |cx, vger| {
+                    self.draw_glyphs(cx, &runs, size, bold, Color::WHITE, vger)
+                });
+            }
+            None => {
+                let color = paint.color_at(rect.center());
+                self.draw_glyphs(args.cx, &runs, size, bold, color, args.vger);
+            }
+        }
+    }
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let (font, size) = self.font(args.cx);
+        let runs = args.cx.fonts.runs(&self.text, &font);
+        let mut sz = match runs.as_slice() {
+            [FontRun {
+                face: Some(face), ..
+            }] => face.text_bounds(self.text.as_str(), size).size,
+            [_, _, ..] => measure_runs(&runs, size, args.text_bounds).1.size,
+            _ => (args.text_bounds)(self.text.as_str(), size, None).size,
+        };
+        let face = args.cx.fonts.face(&font);
+        sz.width += faux_bold_offset(&font, face, size).unwrap_or(0.0);
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
     }
     fn hittest(&self, _path: &mut IdPath, _pt: LocalPoint, _cx: &mut Context) -> Option<ViewId> {
         None
@@ -58,16 +232,17 @@ impl View for Text {
 impl TextModifiers for Text {
     fn font_size(self, size: u32) -> Self {
         Self {
-            text: self.text,
-            color: self.color,
-            size,
+            size: Some(size),
+            ..self
         }
     }
     fn color(self, color: Color) -> Text {
+        Text::color(self, color)
+    }
+    fn foreground(self, paint: Paint) -> Text {
         Text {
-            text: self.text,
-            size: self.size,
-            color,
+            foreground: Some(paint),
+            ..self
         }
     }
 }
@@ -78,8 +253,9 @@ impl private::Sealed for Text {}
 pub fn text(name: &str) -> Text {
     Text {
         text: String::from(name),
-        size: Text::DEFAULT_SIZE,
+        size: None,
         color: TEXT_COLOR,
+        foreground: None,
     }
 }
 
@@ -121,18 +297,13 @@ where
     V: std::fmt::Display + std::fmt::Debug + 'static,
 {
     fn font_size(self, size: u32) -> Text {
-        Text {
-            text: format!("{}", self),
-            size,
-            color: TEXT_COLOR,
-        }
+        text(&format!("{}", self)).font_size(size)
     }
     fn color(self, color: Color) -> Text {
-        Text {
-            text: format!("{}", self),
-            size: Text::DEFAULT_SIZE,
-            color,
-        }
+        text(&format!("{}", self)).color(color)
+    }
+    fn foreground(self, paint: Paint) -> Text {
+        text(&format!("{}", self)).foreground(paint)
     }
 }
 
@@ -1,29 +1,154 @@
 use crate::*;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How long the caret stays on (and off) while blinking.
+const CARET_BLINK_INTERVAL: Duration = Duration::from_millis(530);
+
+/// Caret width in points, before snapping to device pixels.
+const CARET_WIDTH: f32 = 2.0;
+
+/// How quickly the caret glides to its new position.
+pub(crate) const CARET_SPEED: f32 = 30.0;
+
+/// A caret, with the text between it and `anchor` selected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Caret {
+    anchor: usize,
+    cursor: usize,
+}
+
+impl Caret {
+    fn at(cursor: usize) -> Self {
+        Self {
+            anchor: cursor,
+            cursor,
+        }
+    }
+
+    fn range(&self) -> std::ops::Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor)
+    }
+}
 
 /// View-model for `text_editor`.
 struct TextEditorState {
-    cursor: usize,
+    /// Where edits go. Cmd+click and Cmd+D add carets, and the last is
+    /// the primary one, where input methods compose.
+    carets: Vec<Caret>,
     glyph_rects: Vec<LocalRect>,
     lines: Vec<LineMetrics>,
+    /// Of the canvas, since glyphs are drawn down from its top.
+    height: f32,
+    width: f32,
+    /// Of all the text, as last laid out.
+    content_height: f32,
+    content_width: f32,
+    /// How far the text is scrolled up, in points.
+    scroll: f32,
+    /// How far it's scrolled left, when lines don't wrap.
+    scroll_x: f32,
+    /// Were lines wrapped when last drawn? Switching keeps the caret in
+    /// view.
+    wrapped: bool,
+    /// Scroll to the primary caret on the next draw, after it moves.
+    reveal_caret: bool,
+
+    /// Width of the gutter left of the text, if it has one.
+    gutter_width: f32,
+    /// Where each line starts, going down, for taps in the gutter.
+    line_tops: Vec<f32>,
+    /// Did the current drag start in the gutter? It doesn't select.
+    gutter_drag: bool,
+    /// The last changes from the `DiffProvider`, and what they were
+    /// found for, so they aren't found again every frame.
+    line_changes: Option<(Rc<dyn DiffProvider>, String, Vec<(usize, LineChange)>)>,
+
+    /// Where the caret is drawn, animating towards `caret_target`.
+    caret: Option<LocalPoint>,
+    caret_target: Option<LocalPoint>,
+
+    /// When the text was last edited or the cursor moved. The caret
+    /// stays solid while typing.
+    last_edit: Instant,
+
+    /// Text an input method is composing at the cursor, not yet part
+    /// of the bound text.
+    composition: String,
+
+    undo: TextUndo,
 }
 
 impl TextEditorState {
-    fn fwd(&mut self, len: usize) {
-        self.cursor += 1;
-        if self.cursor > len {
-            self.cursor = len;
-        }
+    /// Where the primary caret is.
+    fn cursor(&self) -> usize {
+        self.carets.last().unwrap().cursor
+    }
+
+    /// Puts a single caret at `cursor`.
+    fn set_cursor(&mut self, cursor: usize) {
+        self.carets = vec![Caret::at(cursor)];
     }
-    fn back(&mut self) {
-        if self.cursor > 0 {
-            self.cursor -= 1;
+
+    /// Makes a caret at `cursor` the primary one, adding it if need be.
+    fn add_caret(&mut self, cursor: usize) {
+        self.carets.push(Caret::at(cursor));
+        self.merge_carets();
+    }
+
+    /// Removes carets which ended up in the same place, keeping the later
+    /// one so the primary caret stays last.
+    fn merge_carets(&mut self) {
+        let mut carets: Vec<Caret> = vec![];
+        for caret in self.carets.drain(..).rev() {
+            if !carets.iter().any(|c| c.cursor == caret.cursor) {
+                carets.push(caret);
+            }
         }
+        carets.reverse();
+        self.carets = carets;
+    }
+
+    /// Moves the primary caret to `cursor`, selecting from its anchor.
+    fn select_to(&mut self, cursor: usize) {
+        self.carets.last_mut().unwrap().cursor = cursor;
+        self.merge_carets();
+    }
+
+    /// Moves every caret to where `f` says, deselecting, or selecting
+    /// what it moves over if `select`.
+    fn move_carets(&mut self, select: bool, f: impl Fn(&Self, Caret) -> usize) {
+        self.carets = self
+            .carets
+            .iter()
+            .map(|c| {
+                let cursor = f(self, *c);
+                Caret {
+                    anchor: if select { c.anchor } else { cursor },
+                    cursor,
+                }
+            })
+            .collect();
+        self.merge_carets();
+    }
+
+    /// The selected text, one line per caret which selects something.
+    fn selected_text(&self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut ranges: Vec<_> = self.carets.iter().map(|c| c.range()).collect();
+        ranges.sort_by_key(|r| r.start);
+        ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| chars[r].iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
-    fn find_line(&self) -> usize {
+    fn find_line(&self, cursor: usize) -> usize {
         let mut i = 0;
         for line in &self.lines {
-            if self.cursor >= line.glyph_start && self.cursor < line.glyph_end {
+            if cursor >= line.glyph_start && cursor < line.glyph_end {
                 break;
             }
             i += 1;
@@ -49,111 +174,544 @@ impl TextEditorState {
         closest
     }
 
-    fn down(&mut self) {
-        let p = self.glyph_rects[self.cursor].center();
+    fn down(&self, cursor: usize) -> usize {
+        let p = match self
+            .glyph_rects
+            .get(cursor)
+            .or_else(|| self.glyph_rects.last())
+        {
+            Some(r) => r.center(),
+            None => return cursor,
+        };
 
-        let line = self.find_line() + 1;
+        let line = self.find_line(cursor) + 1;
         if line < self.lines.len() {
             let metrics = self.lines[line];
-            self.cursor =
-                self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects);
+            self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects)
+        } else {
+            cursor
         }
     }
 
-    fn up(&mut self) {
-        let p = self.glyph_rects[self.cursor].center();
+    fn up(&self, cursor: usize) -> usize {
+        let p = match self
+            .glyph_rects
+            .get(cursor)
+            .or_else(|| self.glyph_rects.last())
+        {
+            Some(r) => r.center(),
+            None => return cursor,
+        };
 
-        let line = self.find_line();
+        let line = self.find_line(cursor);
         if line > 0 {
             let metrics = self.lines[line - 1];
-            self.cursor =
-                self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects);
+            self.closest_in_range(p, metrics.glyph_start..metrics.glyph_end, &self.glyph_rects)
+        } else {
+            cursor
+        }
+    }
+
+    /// Where a caret goes for a tap at `p`, in glyph coordinates.
+    fn position_at(&self, p: LocalPoint) -> usize {
+        let i = self.closest_in_range(p, 0..self.glyph_rects.len(), &self.glyph_rects);
+        match self.glyph_rects.get(i) {
+            Some(r) if p.x > r.center().x => i + 1,
+            Some(_) => i,
+            None => 0,
         }
     }
 
-    fn key(&mut self, k: &Key, text: String) -> String {
+    /// Handles a key, with shift held if `select`, following `language`'s
+    /// rules for brackets and indentation if there is one.
+    fn key(
+        &mut self,
+        k: &Key,
+        select: bool,
+        language: Option<&dyn EditorLanguage>,
+        text: String,
+    ) -> String {
+        let len = text.chars().count();
         match k {
             Key::ArrowLeft => {
-                self.back();
+                self.move_carets(select, |_, c| {
+                    if c.anchor != c.cursor && !select {
+                        c.range().start
+                    } else {
+                        c.cursor.saturating_sub(1)
+                    }
+                });
                 text
             }
             Key::ArrowRight => {
-                self.fwd(text.len());
+                self.move_carets(select, |_, c| {
+                    if c.anchor != c.cursor && !select {
+                        c.range().end
+                    } else {
+                        (c.cursor + 1).min(len)
+                    }
+                });
                 text
             }
             Key::ArrowUp => {
-                self.up();
+                self.move_carets(select, |s, c| s.up(c.cursor));
                 text
             }
             Key::ArrowDown => {
-                self.down();
+                self.move_carets(select, |s, c| s.down(c.cursor));
                 text
             }
-            Key::Backspace => {
-                if self.cursor > 0 {
-                    let mut t = text;
-                    t.remove(self.cursor - 1);
-                    self.back();
-                    t
-                } else {
-                    text
-                }
-            }
-            Key::Character(c) => {
-                let mut t = text;
-                t.insert_str(self.cursor, &format!("{}", c));
-                self.cursor += 1;
-                t
-            }
-            Key::Space => {
-                let mut t = text;
-                t.insert(self.cursor, ' ');
-                self.cursor += 1;
-                t
-            }
+            Key::Backspace => self.backspace(language, &text),
+            Key::Character(c) => match language {
+                Some(language) => self.type_char(*c, language, &text),
+                None => self.insert(&c.to_string(), text),
+            },
+            Key::Space => self.insert(" ", text),
+            Key::Enter => match language {
+                Some(language) => self.new_line(language, &text),
+                None => self.insert("\n", text),
+            },
             Key::Home => {
-                self.cursor = 0;
+                self.move_carets(select, |_, _| 0);
                 text
             }
             Key::End => {
-                self.cursor = text.len();
+                self.move_carets(select, |_, _| len);
                 text
             }
             _ => text,
         }
     }
+
+    /// Inserts `s` at every caret, replacing what they select, and moves
+    /// them past it.
+    fn insert(&mut self, s: &str, text: String) -> String {
+        self.edit(&text, |_, c| (c.range(), s.into(), 0))
+    }
+
+    /// Deletes what each caret selects, or the character before it. In
+    /// code, an empty pair of brackets goes together.
+    fn backspace(&mut self, language: Option<&dyn EditorLanguage>, text: &str) -> String {
+        self.edit(text, |chars, c| {
+            let mut range = c.range();
+            if range.is_empty() && range.start > 0 {
+                range.start -= 1;
+                let pair = language.and_then(|l| closing_bracket(l, chars[range.start]));
+                if pair.is_some() && chars.get(range.end) == pair.as_ref() {
+                    range.end += 1;
+                }
+            }
+            (range, String::new(), 0)
+        })
+    }
+
+    /// Types `c` in code: opening brackets and quotes close themselves,
+    /// and typing a closer which is already there steps over it.
+    fn type_char(&mut self, ch: char, language: &dyn EditorLanguage, text: &str) -> String {
+        self.edit(text, |chars, c| {
+            let range = c.range();
+            let closes = language.brackets().iter().any(|(_, close)| *close == ch)
+                || language.quotes().contains(&ch);
+            if range.is_empty() && closes && chars.get(range.end) == Some(&ch) {
+                return (range.start..range.end + 1, ch.to_string(), 0);
+            }
+
+            let after_word = range.start > 0 && chars[range.start - 1].is_alphanumeric();
+            let close = closing_bracket(language, ch)
+                .or_else(|| Some(ch).filter(|ch| language.quotes().contains(ch) && !after_word));
+            match close {
+                // Wrap the selection, or leave the caret between the pair.
+                Some(close) => {
+                    let selected: String = chars[range.clone()].iter().collect();
+                    (range, format!("{}{}{}", ch, selected, close), 1)
+                }
+                None => (range, ch.to_string(), 0),
+            }
+        })
+    }
+
+    /// Starts a new line indented like `language` says. Between a pair
+    /// of brackets, the closing one goes on a line of its own.
+    fn new_line(&mut self, language: &dyn EditorLanguage, text: &str) -> String {
+        self.edit(text, |chars, c| {
+            let range = c.range();
+            let line_start = chars[..range.start]
+                .iter()
+                .rposition(|c| *c == '\n')
+                .map_or(0, |i| i + 1);
+            let line: String = chars[line_start..range.start].iter().collect();
+            let indent = language.indent_after(&line);
+
+            let pair = range
+                .start
+                .checked_sub(1)
+                .and_then(|i| closing_bracket(language, chars[i]));
+            if pair.is_some() && chars.get(range.end) == pair.as_ref() {
+                let outer: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+                let back = 1 + outer.chars().count();
+                (range, format!("\n{}\n{}", indent, outer), back)
+            } else {
+                (range, format!("\n{}", indent), 0)
+            }
+        })
+    }
+
+    /// Edits at each caret. `f` is given the text and a caret, and says
+    /// what to replace, with what, and how far before the end of the
+    /// replacement the caret goes. Carets are edited first to last so each
+    /// stays in place as text before it changes.
+    fn edit(
+        &mut self,
+        text: &str,
+        f: impl Fn(&[char], Caret) -> (std::ops::Range<usize>, String, usize),
+    ) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut order: Vec<usize> = (0..self.carets.len()).collect();
+        order.sort_by_key(|&i| self.carets[i].range().start);
+
+        let mut edited = String::new();
+        let mut edited_len = 0;
+        // How much of `text` has been copied to `edited`.
+        let mut copied = 0;
+        for i in order {
+            let (mut range, s, back) = f(&chars, self.carets[i]);
+            // Don't delete text twice where carets overlap.
+            range.start = range.start.max(copied);
+            range.end = range.end.max(range.start);
+
+            edited.extend(&chars[copied..range.start]);
+            edited.push_str(&s);
+            edited_len += range.start - copied + s.chars().count();
+            self.carets[i] = Caret::at(edited_len - back);
+            copied = range.end;
+        }
+        edited.extend(&chars[copied..]);
+        self.merge_carets();
+        edited
+    }
+
+    /// Selects the word at the primary caret, or adds a caret selecting
+    /// the next occurrence of what it selects, like code editors' Cmd+D.
+    fn select_next(&mut self, text: &str) {
+        let chars: Vec<char> = text.chars().collect();
+        let primary = *self.carets.last().unwrap();
+
+        if primary.anchor == primary.cursor {
+            let is_word = |i: usize| {
+                chars
+                    .get(i)
+                    .map_or(false, |c| c.is_alphanumeric() || *c == '_')
+            };
+            let mut start = primary.cursor;
+            while start > 0 && is_word(start - 1) {
+                start -= 1;
+            }
+            let mut end = primary.cursor;
+            while is_word(end) {
+                end += 1;
+            }
+            if start < end {
+                *self.carets.last_mut().unwrap() = Caret {
+                    anchor: start,
+                    cursor: end,
+                };
+            }
+            return;
+        }
+
+        let needle = &chars[primary.range()];
+        let from = primary.range().end;
+        // Search after the primary caret, wrapping around to the start.
+        let found = (from..chars.len())
+            .chain(0..from)
+            .filter(|&i| chars.get(i..i + needle.len()) == Some(needle))
+            .find(|&i| !self.carets.iter().any(|c| c.range().start == i));
+        if let Some(i) = found {
+            self.carets.push(Caret {
+                anchor: i,
+                cursor: i + needle.len(),
+            });
+        }
+    }
+
+    fn text_input(&mut self, input: TextInput, text: String) -> String {
+        match input {
+            TextInput::Compose { text: composed, .. } => {
+                self.composition = composed;
+                text
+            }
+            TextInput::Commit(s) => {
+                self.composition.clear();
+                self.insert(&s, text)
+            }
+        }
+    }
 }
 
 impl TextEditorState {
     fn new() -> Self {
         Self {
-            cursor: 0,
+            carets: vec![Caret::at(0)],
             glyph_rects: vec![],
             lines: vec![],
+            height: 0.0,
+            width: 0.0,
+            content_height: 0.0,
+            content_width: 0.0,
+            scroll: 0.0,
+            scroll_x: 0.0,
+            wrapped: true,
+            reveal_caret: false,
+            gutter_width: 0.0,
+            line_tops: vec![],
+            gutter_drag: false,
+            line_changes: None,
+            caret: None,
+            caret_target: None,
+            last_edit: Instant::now(),
+            composition: String::new(),
+            undo: TextUndo::default(),
+        }
+    }
+
+    /// Moves the drawn caret towards where it should be.
+    fn animate_caret(&self, dt: f32) -> Option<LocalPoint> {
+        match (self.caret, self.caret_target) {
+            (Some(caret), Some(target)) if caret != target => {
+                if caret.distance_to(target) < 0.5 {
+                    Some(target)
+                } else {
+                    Some(caret.lerp(target, (dt * CARET_SPEED).min(1.0)))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Changed lines of `text`, finding them again only if it or the
+    /// provider changed.
+    fn line_changes(&mut self, diff: &Rc<dyn DiffProvider>, text: &str) -> &[(usize, LineChange)] {
+        let stale = match &self.line_changes {
+            Some((d, t, _)) => !Rc::ptr_eq(d, diff) || t != text,
+            None => true,
+        };
+        if stale {
+            let changes = diff.line_changes(text);
+            self.line_changes = Some((diff.clone(), text.to_string(), changes));
+        }
+        &self.line_changes.as_ref().unwrap().2
+    }
+}
+
+/// Draws a blinking caret at `p`, and schedules a redraw for the next blink.
+pub(crate) fn draw_caret(
+    cx: &mut Context,
+    vger: &mut Vger,
+    p: LocalPoint,
+    height: f32,
+    last_edit: Instant,
+) {
+    let elapsed = Instant::now().duration_since(last_edit);
+    let phase = (elapsed.as_secs_f32() / CARET_BLINK_INTERVAL.as_secs_f32()) as u32;
+
+    // Tell input methods where to put their candidate windows.
+    let caret_rect = LocalRect::new(p, [CARET_WIDTH, height].into());
+    cx.text_input_area = Some(vger.current_transform().outer_transformed_rect(&caret_rect));
+
+    if phase % 2 == 0 {
+        // Keep the caret crisp by covering whole device pixels.
+        let scale = cx.scale_factor();
+        let width = (CARET_WIDTH * scale).round().max(1.0) / scale;
+        let x = (p.x * scale).round() / scale;
+        let paint = vger.color_paint(vger::Color::MAGENTA);
+        vger.fill_rect(
+            LocalRect::new([x, p.y].into(), [width, height].into()),
+            0.0,
+            paint,
+        );
+    }
+
+    cx.request_redraw_at(last_edit + CARET_BLINK_INTERVAL * (phase + 1));
+}
+
+/// Options for `text_editor`. Set them with `TextEditorMods`.
+#[derive(Clone, Default)]
+pub struct TextEditorOptions {
+    language: Option<Rc<dyn EditorLanguage>>,
+    no_wrap: bool,
+    line_numbers: bool,
+    gutter_icon: Option<Rc<dyn Fn(&Context, usize) -> Option<GutterIcon>>>,
+    on_gutter_tap: Option<Rc<dyn Fn(&mut Context, usize)>>,
+    diff: Option<Rc<dyn DiffProvider>>,
+}
+
+impl TextEditorOptions {
+    /// How wide the gutter is for text with `lines` lines.
+    fn gutter_width(&self, lines: usize) -> f32 {
+        let mut width = 0.0;
+        if self.line_numbers {
+            width += line_number_width(lines);
+        }
+        if self.gutter_icon.is_some() || self.on_gutter_tap.is_some() {
+            width += GUTTER_ICON_SIZE;
+        }
+        if self.diff.is_some() {
+            width += CHANGE_MARKER_WIDTH + 4.0;
+        }
+        width
+    }
+}
+
+pub trait TextEditorMods: View + Sized {
+    /// Edits as code in `language`: brackets and quotes close
+    /// themselves, the bracket matching the one at the caret is outlined,
+    /// and new lines keep the indentation. See `CodeLanguage`.
+    fn language(self, language: impl EditorLanguage + 'static) -> Self;
+
+    /// Wraps long lines to the width of the editor, which it does by
+    /// default, or scrolls sideways to show them.
+    fn wrap(self, wrap: bool) -> Self;
+
+    /// Numbers lines in a gutter on the left. Wrapped lines share their
+    /// number.
+    fn line_numbers(self, show: bool) -> Self;
+
+    /// Shows the icon `f` returns for each line, numbered from 0, in the
+    /// gutter, such as for breakpoints.
+    fn gutter_icon(self, f: impl Fn(&Context, usize) -> Option<GutterIcon> + 'static) -> Self;
+
+    /// Calls `f` with the line, numbered from 0, when the gutter is
+    /// clicked next to it, such as to toggle a breakpoint.
+    fn on_gutter_tap(self, f: impl Fn(&mut Context, usize) + 'static) -> Self;
+
+    /// Marks added, modified and deleted lines in the gutter, as
+    /// `provider` finds them. See `BaseTextDiff`.
+    fn line_changes(self, provider: impl DiffProvider + 'static) -> Self;
+}
+
+impl<F> TextEditorMods for ModView<TextEditorOptions, F>
+where
+    ModView<TextEditorOptions, F>: View,
+{
+    fn language(self, language: impl EditorLanguage + 'static) -> Self {
+        let mut opts = self.value;
+        opts.language = Some(Rc::new(language));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn wrap(self, wrap: bool) -> Self {
+        let mut opts = self.value;
+        opts.no_wrap = !wrap;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn line_numbers(self, show: bool) -> Self {
+        let mut opts = self.value;
+        opts.line_numbers = show;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn gutter_icon(self, f: impl Fn(&Context, usize) -> Option<GutterIcon> + 'static) -> Self {
+        let mut opts = self.value;
+        opts.gutter_icon = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_gutter_tap(self, f: impl Fn(&mut Context, usize) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_gutter_tap = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn line_changes(self, provider: impl DiffProvider + 'static) -> Self {
+        let mut opts = self.value;
+        opts.diff = Some(Rc::new(provider));
+        ModView {
+            func: self.func,
+            value: opts,
         }
     }
 }
 
-/// A multi-line text editor.
+/// A multi-line text editor. Cmd+F shows a find and replace panel above
+/// it, and highlights the matches while it's open. Cmd+click adds a caret
+/// and Cmd+D selects the next occurrence of the selection, so typing
+/// edits at every caret. Shift with the arrows or a drag selects, and
+/// it scrolls to keep the caret in view. Long lines wrap unless
+/// `TextEditorMods::wrap` says not to. A gutter can show line numbers,
+/// icons and changed lines.
 ///
 /// This shows how a complex View with internal
 /// state can be created from more atomic Views.
-pub fn text_editor(text: impl Binding<String>) -> impl View {
+pub fn text_editor(text: impl Binding<String>) -> impl TextEditorMods {
+    modview(move |opts: TextEditorOptions, _| {
+        state(FindPanelState::default, move |panel, cx| {
+            vstack((
+                cond(cx[panel].open, find_panel(text, panel, cx), EmptyView {}),
+                editor(text, panel, opts.clone()),
+            ))
+        })
+    })
+}
+
+fn editor(
+    text: impl Binding<String>,
+    panel: StateHandle<FindPanelState>,
+    opts: TextEditorOptions,
+) -> impl View {
+    let wrap = !opts.no_wrap;
     focus(move |has_focus| {
+        let opts = opts.clone();
         state(TextEditorState::new, move |state, cx| {
-            let cursor = cx[state].cursor;
+            let key_language = opts.language.clone();
+            let language = opts.language.clone();
+            let gutter_opts = opts.clone();
+            let on_gutter_tap = opts.on_gutter_tap.clone();
+            let cursor = cx[state].cursor();
+            let carets = cx[state].carets.clone();
+            let caret = cx[state].caret;
+            let scroll = cx[state].scroll;
+            let scroll_x = cx[state].scroll_x;
+            let last_edit = cx[state].last_edit;
+            let composition = cx[state].composition.clone();
             canvas(move |cx, rect, vger| {
-                vger.translate([0.0, rect.height()]);
-                let font_size = 18;
-                let break_width = Some(rect.width());
+                let line_count = text.get(cx).matches('\n').count() + 1;
+                let gutter = gutter_opts.gutter_width(line_count);
+                cx[state].gutter_width = gutter;
 
-                vger.text(text.get(cx), font_size, TEXT_COLOR, break_width);
+                // Keep scrolled text out of the gutter.
+                vger.save();
+                vger.scissor(LocalRect::new(
+                    [gutter, 0.0].into(),
+                    [rect.width() - gutter, rect.height()].into(),
+                ));
+                vger.translate([gutter - scroll_x, rect.height() + scroll]);
+                let font_size = 18;
+                let text_width = rect.width() - gutter;
+                let break_width = if wrap { Some(text_width) } else { None };
 
-                if has_focus {
-                    let rects = vger.glyph_positions(text.get(cx), font_size, break_width);
-                    let lines = vger.line_metrics(text.get(cx), font_size, break_width);
-                    let glyph_rect_paint = vger.color_paint(vger::Color::MAGENTA);
-                    let p = if cursor == rects.len() {
+                // Show any composition in place, as if it were typed.
+                let mut t = text.get(cx).clone();
+                t.insert_str(byte_index(&t, cursor), &composition);
+                let composed = cursor..cursor + composition.chars().count();
+                let rects = vger.glyph_positions(&t, font_size, break_width);
+                let caret_position = |cursor: usize| -> LocalPoint {
+                    if cursor == rects.len() {
                         if let Some(r) = rects.last() {
                             [r.origin.x + r.size.width, r.origin.y].into()
                         } else {
@@ -161,20 +719,534 @@ pub fn text_editor(text: impl Binding<String>) -> impl View {
                         }
                     } else {
                         rects[cursor].origin
+                    }
+                };
+
+                // Where text is drawn, after the composition.
+                let shift = |i: usize| {
+                    if i >= cursor {
+                        i + composed.len()
+                    } else {
+                        i
+                    }
+                };
+
+                let (matches, current) = cx[panel].highlights(text.get(cx));
+                for (i, range) in matches.iter().enumerate() {
+                    let color = if i == current {
+                        AZURE_HIGHLIGHT.alpha(0.6)
+                    } else {
+                        AZURE_HIGHLIGHT.alpha(0.25)
                     };
-                    vger.fill_rect(LocalRect::new(p, [2.0, 20.0].into()), 0.0, glyph_rect_paint);
+                    let paint = vger.color_paint(color);
+                    for r in rects
+                        .get(shift(range.start)..shift(range.end))
+                        .unwrap_or(&[])
+                    {
+                        vger.fill_rect(*r, 0.0, paint);
+                    }
+                }
+
+                if has_focus {
+                    let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.4));
+                    for c in &carets {
+                        let range = c.range();
+                        for r in rects
+                            .get(shift(range.start)..shift(range.end))
+                            .unwrap_or(&[])
+                        {
+                            vger.fill_rect(*r, 0.0, paint);
+                        }
+                    }
+                }
+
+                // Outline the bracket at the caret, or just before it, and
+                // the one it matches.
+                if let (true, Some(language)) = (has_focus, &language) {
+                    let chars: Vec<char> = text.get(cx).chars().collect();
+                    let pair = [cursor, cursor.wrapping_sub(1)].iter().find_map(|&i| {
+                        matching_bracket(language.as_ref(), &chars, i).map(|j| [i, j])
+                    });
+                    let paint = vger.color_paint(TEXT_COLOR.alpha(0.5));
+                    for i in pair.iter().flatten() {
+                        if let Some(r) = rects.get(shift(*i)) {
+                            vger.stroke_rect(r.min(), r.max(), 2.0, 1.0, paint);
+                        }
+                    }
+                }
+
+                vger.text(&t, font_size, TEXT_COLOR, break_width);
+
+                cx[state].height = rect.height();
+                cx[state].width = text_width;
+                cx[state].content_height = rects.iter().map(|r| -r.min_y()).fold(0.0, f32::max);
+                cx[state].content_width = rects.iter().map(|r| r.max_x()).fold(0.0, f32::max);
+                if cx[state].wrapped != wrap {
+                    cx[state].wrapped = wrap;
+                    cx[state].reveal_caret = true;
+                }
+
+                if has_focus {
+                    let lines = vger.line_metrics(&t, font_size, break_width);
+
+                    // Underline the composition, like native text views do.
+                    let paint = vger.color_paint(TEXT_COLOR);
+                    for r in rects.get(composed.clone()).unwrap_or(&[]) {
+                        vger.fill_rect(
+                            LocalRect::new(
+                                [r.min_x(), r.min_y() - 2.0].into(),
+                                [r.width(), 1.0].into(),
+                            ),
+                            0.0,
+                            paint,
+                        );
+                    }
+
+                    // The other carets, then the primary one, which glides.
+                    for c in &carets[..carets.len() - 1] {
+                        let p = caret_position(shift(c.cursor));
+                        draw_caret(cx, vger, p, 20.0, last_edit);
+                    }
+                    let p = caret_position(composed.end);
+                    draw_caret(cx, vger, caret.unwrap_or(p), 20.0, last_edit);
+
+                    // Scroll just far enough to show the caret.
+                    if cx[state].reveal_caret {
+                        cx[state].reveal_caret = false;
+                        cx[state].scroll =
+                            scroll.min(-p.y - 20.0).max(-p.y - rect.height()).max(0.0);
+                        cx[state].scroll_x = if wrap {
+                            0.0
+                        } else {
+                            scroll_x
+                                .min(p.x)
+                                .max(p.x + CARET_WIDTH - text_width)
+                                .max(0.0)
+                        };
+                    }
 
-                    cx[state].glyph_rects = rects;
                     cx[state].lines = lines;
+                    cx[state].caret_target = Some(p);
+                    if caret.is_none() {
+                        cx[state].caret = Some(p);
+                    }
+                }
+                vger.restore();
+
+                // Logical lines start at the text's start and after each
+                // newline, so wrapped lines share a number.
+                let line_tops: Vec<f32> = std::iter::once(0)
+                    .chain(
+                        t.chars()
+                            .enumerate()
+                            .filter(|(_, c)| *c == '\n')
+                            .map(|(i, _)| i + 1),
+                    )
+                    .map(|i| caret_position(i).y + 20.0)
+                    .collect();
+
+                if gutter > 0.0 {
+                    vger.save();
+                    vger.translate([0.0, rect.height() + scroll]);
+                    let mut x = 0.0;
+                    if gutter_opts.line_numbers {
+                        x += line_number_width(line_count);
+                        for (line, top) in line_tops.iter().enumerate() {
+                            draw_line_number(vger, line + 1, x - 8.0, *top);
+                        }
+                    }
+                    if let Some(icon) = &gutter_opts.gutter_icon {
+                        for (line, top) in line_tops.iter().enumerate() {
+                            if let Some(icon) = icon(cx, line) {
+                                let r = LocalRect::new(
+                                    [x, top - 20.0].into(),
+                                    [GUTTER_ICON_SIZE, 20.0].into(),
+                                );
+                                draw_gutter_icon(vger, icon, r);
+                            }
+                        }
+                    }
+                    if let Some(diff) = &gutter_opts.diff {
+                        let x = gutter - CHANGE_MARKER_WIDTH - 2.0;
+                        let current = text.get(cx).clone();
+                        let changes = cx[state].line_changes(diff, &current).to_vec();
+                        for (line, change) in changes {
+                            let top = match line_tops.get(line) {
+                                Some(top) => *top,
+                                // Deleted at the end.
+                                None => match line_tops.last() {
+                                    Some(top) => top - 20.0,
+                                    None => continue,
+                                },
+                            };
+                            let height = match line_tops.get(line + 1) {
+                                Some(next) => top - next,
+                                None => 20.0,
+                            };
+                            draw_change_marker(vger, change, x, top, height);
+                        }
+                    }
+                    vger.restore();
+                }
+
+                cx[state].line_tops = line_tops;
+                if has_focus {
+                    cx[state].glyph_rects = rects;
+                }
+            })
+            .clip()
+            .drag_p(move |cx, p, gesture, _| {
+                let gutter = cx[state].gutter_width;
+                // Glyphs are drawn down from the top of the scrolled text.
+                let p = LocalPoint::new(
+                    p.x - gutter + cx[state].scroll_x,
+                    p.y - cx[state].height - cx[state].scroll,
+                );
+                // Clicks in the gutter are for it, and don't move the caret.
+                if gesture == GestureState::Began {
+                    cx[state].gutter_drag = p.x < cx[state].scroll_x;
+                    if cx[state].gutter_drag {
+                        let line = cx[state].line_tops.iter().rposition(|&top| top >= p.y);
+                        if let (Some(f), Some(line)) = (&on_gutter_tap, line) {
+                            f(cx, line);
+                        }
+                    }
+                }
+                if cx[state].gutter_drag {
+                    return;
+                }
+                let at = cx[state].position_at(p);
+                match gesture {
+                    GestureState::Began if cx.key_mods.shortcut() => cx[state].add_caret(at),
+                    GestureState::Began if !cx.key_mods.shift => cx[state].set_cursor(at),
+                    // Dragging and shift-clicking select.
+                    _ => cx[state].select_to(at),
+                }
+                cx[state].last_edit = Instant::now();
+                cx[state].reveal_caret = true;
+            })
+            .scroll(move |cx, delta| {
+                let max = (cx[state].content_height - cx[state].height).max(0.0);
+                cx[state].scroll = (cx[state].scroll + delta.y).clamp(0.0, max);
+                if !wrap {
+                    let max = (cx[state].content_width - cx[state].width).max(0.0);
+                    cx[state].scroll_x = (cx[state].scroll_x - delta.x).clamp(0.0, max);
                 }
             })
             .key(move |cx, k| {
                 if has_focus {
                     let t = text.with(cx, |t| t.clone());
-                    let new_t = cx[state].key(&k, t);
+                    let cursor = cx[state].cursor();
+                    let hotkey = match &k {
+                        Key::Character(c) if cx.key_mods.shortcut() => HotKey::from_char(*c),
+                        _ => None,
+                    };
+                    if let Some(action) = undo_shortcut(&k, &cx.key_mods) {
+                        if let Some((new_t, c)) = cx[state].undo.apply(action, &t, cursor) {
+                            cx[state].set_cursor(c);
+                            text.with_mut(cx, |t| *t = new_t);
+                        }
+                    } else {
+                        let select = cx.key_mods.shift;
+                        let new_t = match hotkey {
+                            Some(HotKey::KeyF) => {
+                                cx[panel].open = true;
+                                t.clone()
+                            }
+                            Some(HotKey::KeyD) => {
+                                cx[state].select_next(&t);
+                                t.clone()
+                            }
+                            Some(HotKey::KeyA) => {
+                                cx[state].carets = vec![Caret {
+                                    anchor: 0,
+                                    cursor: t.chars().count(),
+                                }];
+                                t.clone()
+                            }
+                            Some(HotKey::KeyC) | Some(HotKey::KeyX) => {
+                                let selected = cx[state].selected_text(&t);
+                                if !selected.is_empty() {
+                                    cx.clipboard().set_text(&selected);
+                                }
+                                if hotkey == Some(HotKey::KeyX) {
+                                    // Only what's selected goes.
+                                    cx[state].insert("", t.clone())
+                                } else {
+                                    t.clone()
+                                }
+                            }
+                            Some(HotKey::KeyV) => match cx.clipboard().text() {
+                                Some(s) => cx[state].insert(&s, t.clone()),
+                                None => t.clone(),
+                            },
+                            _ => cx[state].key(&k, select, key_language.as_deref(), t.clone()),
+                        };
+                        if new_t != t {
+                            let kind = if hotkey.is_some() {
+                                EditKind::Other
+                            } else {
+                                EditKind::for_key(&k)
+                            };
+                            let new_cursor = cx[state].cursor();
+                            cx[state].undo.record(kind, &t, cursor, new_cursor);
+                        }
+                        text.with_mut(cx, |t| *t = new_t);
+                    }
+                    cx[state].last_edit = Instant::now();
+                    cx[state].reveal_caret = true;
+                }
+            })
+            .text_input(move |cx, input| {
+                if has_focus {
+                    let t = text.with(cx, |t| t.clone());
+                    let cursor = cx[state].cursor();
+                    let new_t = cx[state].text_input(input, t.clone());
+                    if new_t != t {
+                        let new_cursor = cx[state].cursor();
+                        cx[state]
+                            .undo
+                            .record(EditKind::Typing, &t, cursor, new_cursor);
+                    }
                     text.with_mut(cx, |t| *t = new_t);
+                    cx[state].last_edit = Instant::now();
+                    cx[state].reveal_caret = true;
+                }
+            })
+            .anim(move |cx, dt| {
+                if let Some(p) = cx[state].animate_caret(dt) {
+                    cx[state].caret = Some(p);
                 }
             })
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_multiple_carets() {
+        let mut state = TextEditorState::new();
+        let text = "let a = a + a;".to_string();
+
+        // Cmd+D on "a" selects it, then the next two.
+        state.set_cursor(4);
+        state.select_next(&text);
+        assert_eq!(
+            state.carets,
+            vec![Caret {
+                anchor: 4,
+                cursor: 5
+            }]
+        );
+        state.select_next(&text);
+        state.select_next(&text);
+        assert_eq!(state.carets.len(), 3);
+
+        let text = state.insert("b", text);
+        assert_eq!(text, "let b = b + b;");
+        assert_eq!(
+            state.carets.iter().map(|c| c.cursor).collect::<Vec<_>>(),
+            vec![5, 9, 13]
+        );
+
+        let text = state.key(&Key::Backspace, false, None, text);
+        assert_eq!(text, "let  =  + ;");
+        assert_eq!(state.cursor(), 10);
+
+        // Adding a caret where there is one makes it the primary.
+        state.add_caret(4);
+        assert_eq!(state.carets.len(), 3);
+        assert_eq!(state.cursor(), 4);
+
+        state.key(&Key::Home, false, None, text);
+        assert_eq!(state.carets, vec![Caret::at(0)]);
+    }
+
+    #[test]
+    fn test_select_and_cut() {
+        let mut state = TextEditorState::new();
+        let text = "one two".to_string();
+
+        state.set_cursor(4);
+        let text = state.key(&Key::End, true, None, text);
+        assert_eq!(state.selected_text(&text), "two");
+
+        // Left without shift goes to the start of the selection.
+        let text = state.key(&Key::ArrowLeft, false, None, text);
+        assert_eq!(state.carets, vec![Caret::at(4)]);
+
+        state.select_to(0);
+        let text = state.insert("", text);
+        assert_eq!(text, "two");
+        assert_eq!(state.cursor(), 0);
+
+        let text = state.key(&Key::Enter, false, None, text);
+        assert_eq!(text, "\ntwo");
+    }
+
+    #[test]
+    fn test_code_editing() {
+        let mut state = TextEditorState::new();
+        let code = Some(&CodeLanguage as &dyn EditorLanguage);
+        let mut text = String::new();
+        for c in "if (x) {".chars() {
+            text = state.key(&Key::Character(c), false, code, text);
+        }
+        // The bracket closed itself, and typing it stepped over it.
+        assert_eq!(text, "if (x) {}");
+        assert_eq!(state.cursor(), 8);
+
+        let text = state.key(&Key::Enter, false, code, text);
+        assert_eq!(text, "if (x) {\n    \n}");
+        assert_eq!(state.cursor(), 13);
+
+        state.set_cursor(4);
+        let text = state.key(&Key::Backspace, false, code, text);
+        assert_eq!(text, "if x) {\n    \n}");
+    }
+
+    /// Lays out `ui` at 200 by 200 and sends it `events`, which are
+    /// positioned relative to the editor.
+    fn send(ui: &impl View, cx: &mut Context, events: &[Event]) {
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [200.0, 200.0].into(),
+                cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        // Below the find panel, in the stack.
+        let offset = cx.get_layout(&vec![0, 0, 0, 0, 1]).offset;
+        let mut actions = vec![];
+        for event in events {
+            ui.process(&event.offset(offset), &mut path, cx, &mut actions);
+        }
+    }
+
+    /// Stands in for drawing, which would place the glyphs: ten points
+    /// per character, on one line, in an editor 100 points tall.
+    fn place_glyphs(cx: &mut Context, n: usize) -> StateHandle<TextEditorState> {
+        let editor = StateHandle::<TextEditorState>::new(cx.view_id(&vec![0, 0, 0, 0, 1, 0]));
+        cx[editor].glyph_rects = (0..n)
+            .map(|i| LocalRect::new([i as f32 * 10.0, -20.0].into(), [10.0, 20.0].into()))
+            .collect();
+        cx[editor].height = 100.0;
+        cx[editor].line_tops = vec![0.0];
+        editor
+    }
+
+    fn drag(from: LocalPoint, to: LocalPoint) -> [Event; 3] {
+        [
+            Event::TouchBegin {
+                id: 0,
+                position: from,
+            },
+            Event::TouchMove {
+                id: 0,
+                position: to,
+                delta: to - from,
+            },
+            Event::TouchEnd {
+                id: 0,
+                position: to,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_editor_drag_and_scroll() {
+        let mut cx = Context::new();
+        let ui = state(|| String::from("hello world"), |s, _| text_editor(s));
+        send(&ui, &mut cx, &[]);
+        let editor = place_glyphs(&mut cx, 11);
+
+        // Across "ell", in the middle of the line.
+        send(
+            &ui,
+            &mut cx,
+            &drag([12.0, 90.0].into(), [38.0, 90.0].into()),
+        );
+        assert_eq!(
+            cx[editor].carets,
+            vec![Caret {
+                anchor: 1,
+                cursor: 4
+            }]
+        );
+        assert_eq!(cx[editor].selected_text("hello world"), "ell");
+
+        cx[editor].content_height = 300.0;
+        let scroll = |dy: f32| Event::Scroll {
+            position: [50.0, 50.0].into(),
+            delta: [0.0, dy].into(),
+        };
+        send(&ui, &mut cx, &[scroll(30.0)]);
+        assert_eq!(cx[editor].scroll, 30.0);
+
+        // No further than the end of the text.
+        send(&ui, &mut cx, &[scroll(500.0)]);
+        assert_eq!(cx[editor].scroll, 200.0);
+    }
+
+    #[test]
+    fn test_editor_horizontal_scroll() {
+        let mut cx = Context::new();
+        let ui = state(|| "long line ".repeat(5), |s, _| text_editor(s).wrap(false));
+        send(&ui, &mut cx, &[]);
+        let editor = place_glyphs(&mut cx, 50);
+        cx[editor].width = 200.0;
+        cx[editor].content_width = 500.0;
+
+        let scroll = |dx: f32| Event::Scroll {
+            position: [50.0, 50.0].into(),
+            delta: [dx, 0.0].into(),
+        };
+        send(&ui, &mut cx, &[scroll(-40.0)]);
+        assert_eq!(cx[editor].scroll_x, 40.0);
+
+        // Taps land in the scrolled text.
+        send(&ui, &mut cx, &drag([2.0, 90.0].into(), [2.0, 90.0].into()));
+        assert_eq!(cx[editor].cursor(), 4);
+
+        send(&ui, &mut cx, &[scroll(-1000.0)]);
+        assert_eq!(cx[editor].scroll_x, 300.0);
+    }
+
+    #[test]
+    fn test_editor_gutter_tap() {
+        use std::cell::Cell;
+
+        let mut cx = Context::new();
+        let tapped = Rc::new(Cell::new(None));
+        let on_tap = tapped.clone();
+        let ui = state(
+            || String::from("one\ntwo"),
+            move |s, _| {
+                let on_tap = on_tap.clone();
+                text_editor(s)
+                    .line_numbers(true)
+                    .on_gutter_tap(move |_, line| on_tap.set(Some(line)))
+            },
+        );
+        send(&ui, &mut cx, &[]);
+        let editor = place_glyphs(&mut cx, 7);
+        cx[editor].gutter_width = 30.0;
+        cx[editor].line_tops = vec![0.0, -20.0];
+        cx[editor].set_cursor(2);
+
+        // In the gutter, beside the second line.
+        send(
+            &ui,
+            &mut cx,
+            &drag([10.0, 70.0].into(), [10.0, 70.0].into()),
+        );
+        assert_eq!(tapped.get(), Some(1));
+        assert_eq!(cx[editor].cursor(), 2);
+    }
+}
@@ -0,0 +1,930 @@
+use crate::*;
+use std::rc::Rc;
+
+/// Options for `text_field`. Set them with `TextFieldMods`.
+#[derive(Clone, Default)]
+pub struct TextFieldOptions {
+    placeholder: String,
+    clear_button: bool,
+    max_length: Option<usize>,
+    mask: Option<InputMask>,
+    secure: bool,
+    validate: Option<Rc<dyn Fn(&str) -> bool>>,
+    on_submit: Option<Rc<dyn Fn(&mut Context, &str)>>,
+    on_editing_changed: Option<Rc<dyn Fn(&mut Context, bool)>>,
+}
+
+pub trait TextFieldMods: View + Sized {
+    /// Ghost text shown while the field is empty.
+    fn placeholder(self, text: &str) -> Self;
+
+    /// Shows a button to clear the field while it has text.
+    fn clear_button(self, show: bool) -> Self;
+
+    /// Limits the number of characters that can be entered.
+    fn max_length(self, n: usize) -> Self;
+
+    /// Restricts input to a pattern, such as `"(###) ###-####"`. See `InputMask`.
+    fn input_mask(self, mask: impl Into<InputMask>) -> Self;
+
+    /// Shows bullets instead of the text, for passwords, with a button to
+    /// reveal it. The text can't be copied or cut.
+    fn secure(self, secure: bool) -> Self;
+
+    /// Rejects edits which would leave text that `f` returns false for,
+    /// such as keystrokes which can't be part of a number.
+    fn validate(self, f: impl Fn(&str) -> bool + 'static) -> Self;
+
+    /// Called with the text when the user presses Enter.
+    fn on_submit(self, f: impl Fn(&mut Context, &str) + 'static) -> Self;
+
+    /// Called when the field gains (true) or loses (false) focus.
+    fn on_editing_changed(self, f: impl Fn(&mut Context, bool) + 'static) -> Self;
+}
+
+impl<F> TextFieldMods for ModView<TextFieldOptions, F>
+where
+    ModView<TextFieldOptions, F>: View,
+{
+    fn placeholder(self, text: &str) -> Self {
+        let mut opts = self.value;
+        opts.placeholder = text.into();
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn clear_button(self, show: bool) -> Self {
+        let mut opts = self.value;
+        opts.clear_button = show;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn max_length(self, n: usize) -> Self {
+        let mut opts = self.value;
+        opts.max_length = Some(n);
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn input_mask(self, mask: impl Into<InputMask>) -> Self {
+        let mut opts = self.value;
+        opts.mask = Some(mask.into());
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn secure(self, secure: bool) -> Self {
+        let mut opts = self.value;
+        opts.secure = secure;
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn validate(self, f: impl Fn(&str) -> bool + 'static) -> Self {
+        let mut opts = self.value;
+        opts.validate = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_submit(self, f: impl Fn(&mut Context, &str) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_submit = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+
+    fn on_editing_changed(self, f: impl Fn(&mut Context, bool) + 'static) -> Self {
+        let mut opts = self.value;
+        opts.on_editing_changed = Some(Rc::new(f));
+        ModView {
+            func: self.func,
+            value: opts,
+        }
+    }
+}
+
+/// View-model for `text_field`.
+struct TextFieldState {
+    /// Cursor position, in chars.
+    cursor: usize,
+    /// Where the selection started. The text between it and the cursor
+    /// is selected.
+    anchor: usize,
+
+    /// Did we have focus the last time we checked?
+    editing: bool,
+
+    /// Is a secure field showing its text?
+    revealed: bool,
+
+    /// Where the displayed characters are, for placing the cursor.
+    glyph_rects: Vec<LocalRect>,
+    /// Where the text is drawn from.
+    text_origin: LocalVector,
+
+    caret: Option<LocalPoint>,
+    caret_target: Option<LocalPoint>,
+    last_edit: Instant,
+
+    /// Text an input method is composing at the cursor, shown in place
+    /// but not yet part of the bound text.
+    composition: String,
+
+    undo: TextUndo,
+}
+
+/// Where a word starts, moving left from `i`.
+fn word_start(chars: &[char], i: usize) -> usize {
+    let mut i = i;
+    while i > 0 && !chars[i - 1].is_alphanumeric() {
+        i -= 1;
+    }
+    while i > 0 && chars[i - 1].is_alphanumeric() {
+        i -= 1;
+    }
+    i
+}
+
+/// Where a word ends, moving right from `i`.
+fn word_end(chars: &[char], i: usize) -> usize {
+    let mut i = i;
+    while i < chars.len() && !chars[i].is_alphanumeric() {
+        i += 1;
+    }
+    while i < chars.len() && chars[i].is_alphanumeric() {
+        i += 1;
+    }
+    i
+}
+
+impl TextFieldState {
+    fn new() -> Self {
+        Self {
+            cursor: 0,
+            anchor: 0,
+            editing: false,
+            revealed: false,
+            glyph_rects: vec![],
+            text_origin: LocalVector::zero(),
+            caret: None,
+            caret_target: None,
+            last_edit: Instant::now(),
+            composition: String::new(),
+            undo: TextUndo::default(),
+        }
+    }
+
+    fn selection(&self) -> std::ops::Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor)
+    }
+
+    /// Moves the cursor, selecting from where it was if `select`.
+    fn move_to(&mut self, cursor: usize, select: bool) {
+        self.cursor = cursor;
+        if !select {
+            self.anchor = cursor;
+        }
+    }
+
+    fn selected_text(&self, text: &str) -> String {
+        text.chars()
+            .skip(self.selection().start)
+            .take(self.selection().len())
+            .collect()
+    }
+
+    /// Removes the selected text. Returns true if there was any.
+    fn delete_selection(&mut self, text: &mut String) -> bool {
+        let range = self.selection();
+        if range.is_empty() {
+            return false;
+        }
+        text.replace_range(
+            byte_index(text, range.start)..byte_index(text, range.end),
+            "",
+        );
+        self.cursor = range.start;
+        self.anchor = range.start;
+        true
+    }
+
+    /// Applies a key press to `text`. Returns true if the text changed.
+    fn key(
+        &mut self,
+        k: &Key,
+        mods: KeyboardModifiers,
+        text: &mut String,
+        max_length: Option<usize>,
+        mask: Option<&InputMask>,
+    ) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        let len = chars.len();
+        self.cursor = self.cursor.min(len);
+        self.anchor = self.anchor.min(len);
+        let selection = self.selection();
+        match k {
+            Key::ArrowLeft => {
+                let cursor = if mods.word() {
+                    word_start(&chars, self.cursor)
+                } else if mods.command {
+                    0
+                } else if !selection.is_empty() && !mods.shift {
+                    selection.start
+                } else {
+                    self.cursor.saturating_sub(1)
+                };
+                self.move_to(cursor, mods.shift);
+                false
+            }
+            Key::ArrowRight => {
+                let cursor = if mods.word() {
+                    word_end(&chars, self.cursor)
+                } else if mods.command {
+                    len
+                } else if !selection.is_empty() && !mods.shift {
+                    selection.end
+                } else {
+                    (self.cursor + 1).min(len)
+                };
+                self.move_to(cursor, mods.shift);
+                false
+            }
+            Key::Home | Key::ArrowUp => {
+                self.move_to(0, mods.shift);
+                false
+            }
+            Key::End | Key::ArrowDown => {
+                self.move_to(len, mods.shift);
+                false
+            }
+            Key::Backspace => {
+                if !selection.is_empty() {
+                    self.delete_selection(text)
+                } else if self.cursor > 0 {
+                    self.anchor = if mods.word() {
+                        word_start(&chars, self.cursor)
+                    } else {
+                        self.cursor - 1
+                    };
+                    self.delete_selection(text)
+                } else {
+                    false
+                }
+            }
+            Key::Delete => {
+                if !selection.is_empty() {
+                    self.delete_selection(text)
+                } else if self.cursor < len {
+                    self.anchor = if mods.word() {
+                        word_end(&chars, self.cursor)
+                    } else {
+                        self.cursor + 1
+                    };
+                    self.delete_selection(text)
+                } else {
+                    false
+                }
+            }
+            Key::Character(c) => self.insert(*c, text, max_length, mask),
+            Key::Space => self.insert(' ', text, max_length, mask),
+            _ => false,
+        }
+    }
+
+    /// Inserts `c` at the cursor, replacing the selection.
+    fn insert(
+        &mut self,
+        c: char,
+        text: &mut String,
+        max_length: Option<usize>,
+        mask: Option<&InputMask>,
+    ) -> bool {
+        let mut t = text.clone();
+        let (anchor, cursor) = (self.anchor, self.cursor);
+        self.delete_selection(&mut t);
+        t.insert(byte_index(&t, self.cursor), c);
+        let too_long = max_length.map_or(false, |max| t.chars().count() > max);
+        // Everything after the cursor shifts over, so check it all.
+        let unmasked = mask.map_or(false, |mask| {
+            mask.filter(&t) != t || t.chars().count() > mask.capacity()
+        });
+        if too_long || unmasked {
+            self.anchor = anchor;
+            self.cursor = cursor;
+            return false;
+        }
+        *text = t;
+        self.cursor += 1;
+        self.anchor = self.cursor;
+        true
+    }
+
+    /// Applies input method text to `text`. Committed text is inserted a
+    /// character at a time so the length limit and mask apply. Returns
+    /// true if the text changed.
+    fn text_input(
+        &mut self,
+        input: TextInput,
+        text: &mut String,
+        max_length: Option<usize>,
+        mask: Option<&InputMask>,
+    ) -> bool {
+        match input {
+            TextInput::Compose { text: composed, .. } => {
+                self.composition = composed;
+                false
+            }
+            TextInput::Commit(s) => {
+                self.composition.clear();
+                self.cursor = self.cursor.min(text.chars().count());
+                self.anchor = self.anchor.min(text.chars().count());
+                let mut changed = self.delete_selection(text);
+                for c in s.chars() {
+                    changed |= self.insert(c, text, max_length, mask);
+                }
+                changed
+            }
+        }
+    }
+
+    /// Where the cursor goes for a touch at `p`, given the mask if any.
+    fn position_at(&self, p: LocalPoint, len: usize, mask: Option<&InputMask>) -> usize {
+        let x = p.x - self.text_origin.x;
+        let display = self
+            .glyph_rects
+            .iter()
+            .position(|r| x < r.center().x)
+            .unwrap_or(self.glyph_rects.len());
+        // The last character shown at or before that.
+        match mask {
+            Some(mask) => (0..=len)
+                .rev()
+                .find(|&i| mask.display_index(i) <= display)
+                .unwrap_or(0),
+            None => display.min(len),
+        }
+    }
+}
+
+/// Byte offset of the char at `index`.
+pub(crate) fn byte_index(s: &str, index: usize) -> usize {
+    s.char_indices()
+        .nth(index)
+        .map(|(i, _)| i)
+        .unwrap_or(s.len())
+}
+
+const TEXT_FIELD_FONT_SIZE: u32 = 18;
+const TEXT_FIELD_PADDING: f32 = 4.0;
+const CLEAR_BUTTON_SIZE: f32 = 20.0;
+
+fn clear_button_view(text: impl Binding<String>, state: StateHandle<TextFieldState>) -> impl View {
+    canvas(|_, rect, vger| {
+        let c = rect.center();
+        let r = rect.width().min(rect.height()) / 2.0 - 2.0;
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        vger.fill_circle(c, r, paint);
+
+        let d = r * 0.4;
+        let paint = vger.color_paint(BLACK);
+        vger.stroke_segment([c.x - d, c.y - d], [c.x + d, c.y + d], 1.5, paint);
+        vger.stroke_segment([c.x - d, c.y + d], [c.x + d, c.y - d], 1.5, paint);
+    })
+    .size([CLEAR_BUTTON_SIZE, CLEAR_BUTTON_SIZE])
+    .tap(move |cx| {
+        let cursor = cx[state].cursor;
+        let t = text.get(cx).clone();
+        cx[state].undo.record(EditKind::Other, &t, cursor, 0);
+        text.with_mut(cx, |t| t.clear());
+        cx[state].cursor = 0;
+        cx[state].anchor = 0;
+        cx[state].last_edit = Instant::now();
+    })
+}
+
+/// Shows or hides the text of a secure field.
+fn reveal_button_view(state: StateHandle<TextFieldState>) -> impl View {
+    canvas(move |cx, rect, vger| {
+        let c = rect.center();
+        let r = rect.width().min(rect.height()) / 2.0 - 4.0;
+        let paint = vger.color_paint(MEDIUM_GRAY);
+        vger.fill_circle(c, r, paint);
+        let paint = vger.color_paint(BLACK);
+        vger.fill_circle(c, r * 0.45, paint);
+
+        // Crossed out while the text is hidden.
+        if !cx[state].revealed {
+            let d = r + 2.0;
+            let paint = vger.color_paint(TEXT_COLOR);
+            vger.stroke_segment([c.x - d, c.y - d], [c.x + d, c.y + d], 1.5, paint);
+        }
+    })
+    .size([CLEAR_BUTTON_SIZE, CLEAR_BUTTON_SIZE])
+    .tap(move |cx| {
+        let revealed = cx[state].revealed;
+        cx[state].revealed = !revealed;
+    })
+}
+
+/// A single-line text field. Click to place the cursor, and drag, shift-
+/// click or use shift with the arrows to select. Option (Control off
+/// macOS) moves and deletes by words.
+///
+/// Use `TextFieldMods` to add a placeholder, a clear button, a
+/// length limit and callbacks, or to make it `secure` for passwords.
+pub fn text_field(text: impl Binding<String>) -> impl TextFieldMods {
+    modview(move |opts: TextFieldOptions, _| {
+        focus(move |has_focus| {
+            let opts = opts.clone();
+            state(TextFieldState::new, move |state, cx| {
+                let cursor = cx[state].cursor;
+                let selection = cx[state].selection();
+                let caret = cx[state].caret;
+                let last_edit = cx[state].last_edit;
+                let composition = cx[state].composition.clone();
+                let show_clear = opts.clear_button && !text.get(cx).is_empty();
+                let secure = opts.secure;
+                let hidden = secure && !cx[state].revealed;
+                let placeholder = opts.placeholder.clone();
+                let max_length = opts.max_length;
+                let mask = opts.mask.clone();
+                let key_mask = opts.mask.clone();
+                let input_mask = opts.mask.clone();
+                let drag_mask = opts.mask.clone();
+                let key_validate = opts.validate.clone();
+                let input_validate = opts.validate.clone();
+                let on_submit = opts.on_submit.clone();
+                let on_editing_changed = opts.on_editing_changed.clone();
+
+                hstack((
+                    canvas(move |cx, rect, vger| {
+                        let font_size = TEXT_FIELD_FONT_SIZE;
+
+                        // Center a line of text vertically.
+                        let line = vger.text_bounds("X", font_size, None);
+                        let origin = LocalVector::new(
+                            TEXT_FIELD_PADDING,
+                            (rect.height() - line.height()) / 2.0 - line.origin.y,
+                        );
+                        vger.translate(origin);
+
+                        let mut t = match &mask {
+                            Some(mask) => mask.format(text.get(cx)),
+                            None => text.get(cx).clone(),
+                        };
+                        let display = |i: usize| {
+                            match &mask {
+                                Some(mask) => mask.display_index(i),
+                                None => i,
+                            }
+                            .min(t.chars().count())
+                        };
+                        let cursor = display(cursor);
+                        let selected = display(selection.start)..display(selection.end);
+
+                        // Show any composition in place, as if it were typed.
+                        t.insert_str(byte_index(&t, cursor), &composition);
+                        let composed = cursor..cursor + composition.chars().count();
+                        if hidden {
+                            t = t.chars().map(|_| '\u{2022}').collect();
+                        }
+
+                        let rects = vger.glyph_positions(&t, font_size, None);
+                        if has_focus && !selected.is_empty() {
+                            let paint = vger.color_paint(AZURE_HIGHLIGHT.alpha(0.4));
+                            for r in rects.get(selected.clone()).unwrap_or(&[]) {
+                                vger.fill_rect(
+                                    LocalRect::new(
+                                        [r.min_x(), line.origin.y].into(),
+                                        [r.width(), line.height()].into(),
+                                    ),
+                                    0.0,
+                                    paint,
+                                );
+                            }
+                        }
+
+                        if t.is_empty() {
+                            let placeholder = match &mask {
+                                Some(mask) if placeholder.is_empty() => mask.template(),
+                                _ => placeholder.clone(),
+                            };
+                            vger.text(&placeholder, font_size, TEXT_COLOR.alpha(0.4), None);
+                        } else {
+                            vger.text(&t, font_size, TEXT_COLOR, None);
+                        }
+
+                        if has_focus {
+                            // Underline the composition, like native fields do.
+                            let paint = vger.color_paint(TEXT_COLOR);
+                            for r in rects.get(composed.clone()).unwrap_or(&[]) {
+                                vger.fill_rect(
+                                    LocalRect::new(
+                                        [r.min_x(), line.origin.y - 2.0].into(),
+                                        [r.width(), 1.0].into(),
+                                    ),
+                                    0.0,
+                                    paint,
+                                );
+                            }
+
+                            let cursor = composed.end;
+                            let x = if cursor == 0 {
+                                0.0
+                            } else if let Some(r) = rects.get(cursor - 1) {
+                                r.max_x()
+                            } else {
+                                rects.last().map(|r| r.max_x()).unwrap_or(0.0)
+                            };
+                            let p = LocalPoint::new(x, line.origin.y);
+                            // Selected text is highlighted instead.
+                            if selected.is_empty() {
+                                draw_caret(cx, vger, caret.unwrap_or(p), line.height(), last_edit);
+                            }
+                            cx[state].caret_target = Some(p);
+                            if caret.is_none() {
+                                cx[state].caret = Some(p);
+                            }
+                        }
+                        cx[state].glyph_rects = rects;
+                        cx[state].text_origin = origin;
+                    })
+                    .drag_p(move |cx, p, gesture, _| {
+                        let len = text.get(cx).chars().count();
+                        let at = cx[state].position_at(p, len, drag_mask.as_ref());
+                        // Shift-clicking and dragging select.
+                        let select = gesture != GestureState::Began || cx.key_mods.shift;
+                        cx[state].move_to(at, select);
+                        cx[state].last_edit = Instant::now();
+                    })
+                    .flex(),
+                    cond(show_clear, clear_button_view(text, state), EmptyView {}),
+                    cond(secure, reveal_button_view(state), EmptyView {}),
+                ))
+                .key(move |cx, k| {
+                    if has_focus {
+                        if matches!(k, Key::Enter) {
+                            if let Some(f) = &on_submit {
+                                let t = text.get(cx).clone();
+                                f(cx, &t);
+                            }
+                            return;
+                        }
+                        let before = text.get(cx).clone();
+                        let cursor = cx[state].cursor.min(before.chars().count());
+                        if let Some(action) = undo_shortcut(&k, &cx.key_mods) {
+                            if let Some((t, c)) = cx[state].undo.apply(action, &before, cursor) {
+                                cx[state].move_to(c, false);
+                                text.with_mut(cx, |text| *text = t);
+                            }
+                            cx[state].last_edit = Instant::now();
+                            return;
+                        }
+                        let mut t = before.clone();
+                        let anchor = cx[state].anchor;
+                        let mods = cx.key_mods;
+                        let hotkey = match k {
+                            Key::Character(c) if mods.shortcut() => HotKey::from_char(c),
+                            _ => None,
+                        };
+                        let kind = if hotkey.is_some() {
+                            EditKind::Other
+                        } else {
+                            EditKind::for_key(&k)
+                        };
+                        let changed = match hotkey {
+                            Some(HotKey::KeyA) => {
+                                cx[state].anchor = 0;
+                                cx[state].cursor = t.chars().count();
+                                false
+                            }
+                            // Passwords stay out of the clipboard.
+                            Some(HotKey::KeyC) | Some(HotKey::KeyX) if secure => false,
+                            Some(HotKey::KeyC) | Some(HotKey::KeyX) => {
+                                let selected = cx[state].selected_text(&t);
+                                if !selected.is_empty() {
+                                    cx.clipboard().set_text(&selected);
+                                }
+                                hotkey == Some(HotKey::KeyX) && cx[state].delete_selection(&mut t)
+                            }
+                            Some(HotKey::KeyV) => match cx.clipboard().text() {
+                                // Single line, so paste just the first.
+                                Some(s) => {
+                                    let line = s.lines().next().unwrap_or_default().into();
+                                    cx[state].text_input(
+                                        TextInput::Commit(line),
+                                        &mut t,
+                                        max_length,
+                                        key_mask.as_ref(),
+                                    )
+                                }
+                                None => false,
+                            },
+                            Some(_) => false,
+                            None => cx[state].key(&k, mods, &mut t, max_length, key_mask.as_ref()),
+                        };
+                        if changed && !key_validate.as_ref().map_or(true, |f| f(&t)) {
+                            cx[state].anchor = anchor;
+                            cx[state].cursor = cursor;
+                        } else if changed {
+                            let new_cursor = cx[state].cursor;
+                            cx[state].undo.record(kind, &before, cursor, new_cursor);
+                            text.with_mut(cx, |text| *text = t);
+                        }
+                        cx[state].last_edit = Instant::now();
+                    }
+                })
+                .text_input(move |cx, input| {
+                    if !has_focus {
+                        return;
+                    }
+                    let before = text.get(cx).clone();
+                    let cursor = cx[state].cursor.min(before.chars().count());
+                    let mut t = before.clone();
+                    let anchor = cx[state].anchor;
+                    let changed =
+                        cx[state].text_input(input, &mut t, max_length, input_mask.as_ref());
+                    if changed && !input_validate.as_ref().map_or(true, |f| f(&t)) {
+                        cx[state].anchor = anchor;
+                        cx[state].cursor = cursor;
+                    } else if changed {
+                        let new_cursor = cx[state].cursor;
+                        cx[state]
+                            .undo
+                            .record(EditKind::Typing, &before, cursor, new_cursor);
+                        text.with_mut(cx, |text| *text = t);
+                    }
+                    cx[state].last_edit = Instant::now();
+                })
+                .anim(move |cx, dt| {
+                    if cx[state].editing != has_focus {
+                        cx[state].editing = has_focus;
+                        if !has_focus {
+                            // Input methods drop compositions on blur.
+                            cx[state].composition.clear();
+                        }
+                        if let Some(f) = &on_editing_changed {
+                            f(cx, has_focus);
+                        }
+                    }
+                    let s = &cx[state];
+                    if let (Some(caret), Some(target)) = (s.caret, s.caret_target) {
+                        if caret != target {
+                            cx[state].caret = Some(if caret.distance_to(target) < 0.5 {
+                                target
+                            } else {
+                                caret.lerp(target, (dt * CARET_SPEED).min(1.0))
+                            });
+                        }
+                    }
+                })
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_text_field_editing() {
+        let mut s = TextFieldState::new();
+        let mut t = String::new();
+        let mods = KeyboardModifiers::default();
+
+        assert!(s.key(&Key::Character('h'), mods, &mut t, None, None));
+        assert!(s.key(&Key::Character('i'), mods, &mut t, None, None));
+        assert_eq!(t, "hi");
+        assert_eq!(s.cursor, 2);
+
+        s.key(&Key::Home, mods, &mut t, None, None);
+        s.key(&Key::Character('é'), mods, &mut t, None, None);
+        assert_eq!(t, "éhi");
+
+        s.key(&Key::Delete, mods, &mut t, None, None);
+        assert_eq!(t, "éi");
+
+        s.key(&Key::Backspace, mods, &mut t, None, None);
+        assert_eq!(t, "i");
+        assert_eq!(s.cursor, 0);
+    }
+
+    #[test]
+    fn test_text_field_max_length() {
+        let mut s = TextFieldState::new();
+        let mut t = String::new();
+        let mods = KeyboardModifiers::default();
+        for c in "abcdef".chars() {
+            s.key(&Key::Character(c), mods, &mut t, Some(3), None);
+        }
+        assert_eq!(t, "abc");
+        assert!(!s.key(&Key::Space, mods, &mut t, Some(3), None));
+    }
+
+    #[test]
+    fn test_text_field_mask() {
+        let mask = InputMask::new("##-AA");
+        let mut s = TextFieldState::new();
+        let mut t = String::new();
+        let mods = KeyboardModifiers::default();
+        for c in "1x2ab3".chars() {
+            s.key(&Key::Character(c), mods, &mut t, None, Some(&mask));
+        }
+        assert_eq!(t, "12ab");
+        assert_eq!(mask.format(&t), "12-ab");
+    }
+
+    #[test]
+    fn test_text_field_composition() {
+        let mut s = TextFieldState::new();
+        let mut t = String::from("ab");
+        s.move_to(1, false);
+
+        let compose = |text: &str| TextInput::Compose {
+            text: text.into(),
+            selection: None,
+        };
+        assert!(!s.text_input(compose("n"), &mut t, None, None));
+        assert!(!s.text_input(compose("に"), &mut t, None, None));
+        assert_eq!(s.composition, "に");
+        assert_eq!(t, "ab");
+
+        assert!(s.text_input(TextInput::Commit("日本".into()), &mut t, Some(3), None));
+        assert_eq!(t, "a日b");
+        assert_eq!(s.cursor, 2);
+        assert!(s.composition.is_empty());
+    }
+
+    #[test]
+    fn test_text_field_selection() {
+        let mut s = TextFieldState::new();
+        let mut t = String::from("hello big world");
+        let shift = KeyboardModifiers {
+            shift: true,
+            ..Default::default()
+        };
+        let word = if cfg!(target_os = "macos") {
+            KeyboardModifiers {
+                alt: true,
+                ..Default::default()
+            }
+        } else {
+            KeyboardModifiers {
+                control: true,
+                ..Default::default()
+            }
+        };
+
+        s.key(&Key::End, KeyboardModifiers::default(), &mut t, None, None);
+        s.key(&Key::ArrowLeft, word, &mut t, None, None);
+        assert_eq!(s.cursor, 10);
+        s.key(&Key::ArrowLeft, shift, &mut t, None, None);
+        s.key(&Key::ArrowLeft, shift, &mut t, None, None);
+        assert_eq!(s.selected_text(&t), "g ");
+
+        // Typing replaces the selection.
+        assert!(s.key(&Key::Character('_'), shift, &mut t, None, None));
+        assert_eq!(t, "hello bi_world");
+        assert_eq!(s.selection(), 9..9);
+
+        s.key(&Key::Backspace, word, &mut t, None, None);
+        assert_eq!(t, "hello world");
+    }
+
+    /// Lays out `ui` as a 200 by 30 field and sends it `events`.
+    fn send(ui: &impl View, cx: &mut Context, events: &[Event]) {
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [200.0, 30.0].into(),
+                cx,
+                text_bounds: &mut |_, _, _| LocalRect::zero(),
+            },
+        );
+        let mut actions = vec![];
+        for event in events {
+            ui.process(event, &mut path, cx, &mut actions);
+        }
+    }
+
+    fn key(c: char) -> Event {
+        Event::Key {
+            key: Key::Character(c),
+            mods: KeyboardModifiers::default(),
+            repeat: false,
+        }
+    }
+
+    fn tap(x: f32) -> [Event; 2] {
+        let position = LocalPoint::new(x, 15.0);
+        [
+            Event::TouchBegin { id: 0, position },
+            Event::TouchEnd { id: 0, position },
+        ]
+    }
+
+    #[test]
+    fn test_text_field_events() {
+        let mut cx = Context::new();
+        let ui = state(String::new, |s, _| text_field(s));
+        let s = StateHandle::<String>::new(cx.view_id(&vec![0]));
+
+        // Keys are ignored until a tap focuses the field.
+        send(&ui, &mut cx, &[key('x')]);
+        assert_eq!(cx[s], "");
+
+        send(&ui, &mut cx, &tap(50.0));
+        send(&ui, &mut cx, &[key('h'), key('i')]);
+        assert_eq!(cx[s], "hi");
+
+        send(
+            &ui,
+            &mut cx,
+            &[Event::TextInput(TextInput::Commit("!".into()))],
+        );
+        assert_eq!(cx[s], "hi!");
+    }
+
+    #[test]
+    fn test_text_field_clear_button() {
+        let mut cx = Context::new();
+        let ui = state(
+            || String::from("hello"),
+            |s, _| text_field(s).clear_button(true),
+        );
+        let s = StateHandle::<String>::new(cx.view_id(&vec![0]));
+
+        // The button sits at the right end of the field.
+        send(&ui, &mut cx, &tap(190.0));
+        assert_eq!(cx[s], "");
+    }
+
+    #[test]
+    fn test_text_field_drag_selection() {
+        let mut cx = Context::new();
+        let ui = state(|| String::from("hello"), |s, _| text_field(s));
+        send(&ui, &mut cx, &[]);
+
+        // Drawing would place the glyphs, so stand in for it: ten
+        // points per character.
+        let field = StateHandle::<TextFieldState>::new(cx.view_id(&vec![0, 0, 0, 0]));
+        cx[field].glyph_rects = (0..5)
+            .map(|i| LocalRect::new([i as f32 * 10.0, 0.0].into(), [10.0, 20.0].into()))
+            .collect();
+
+        send(
+            &ui,
+            &mut cx,
+            &[
+                Event::TouchBegin {
+                    id: 0,
+                    position: [12.0, 15.0].into(),
+                },
+                Event::TouchMove {
+                    id: 0,
+                    position: [38.0, 15.0].into(),
+                    delta: [26.0, 0.0].into(),
+                },
+                Event::TouchEnd {
+                    id: 0,
+                    position: [38.0, 15.0].into(),
+                },
+            ],
+        );
+        assert_eq!(cx[field].selection(), 1..4);
+        assert_eq!(cx[field].selected_text("hello"), "ell");
+    }
+
+    #[test]
+    fn test_text_field_reveal_button() {
+        let mut cx = Context::new();
+        let ui = state(|| String::from("secret"), |s, _| text_field(s).secure(true));
+        send(&ui, &mut cx, &[]);
+        let field = StateHandle::<TextFieldState>::new(cx.view_id(&vec![0, 0, 0, 0]));
+        assert!(!cx[field].revealed);
+
+        send(&ui, &mut cx, &tap(190.0));
+        assert!(cx[field].revealed);
+        send(&ui, &mut cx, &tap(190.0));
+        assert!(!cx[field].revealed);
+    }
+}
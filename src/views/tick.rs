@@ -0,0 +1,123 @@
+use crate::*;
+use std::any::Any;
+use std::time::Duration;
+
+/// Struct for the `tick` modifier.
+pub struct TickView<V, F> {
+    child: V,
+    interval: Duration,
+    func: F,
+}
+
+impl<V, F> TickView<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, f32) + 'static,
+{
+    pub fn new(child: V, interval: Duration, func: F) -> Self {
+        Self {
+            child,
+            interval,
+            func,
+        }
+    }
+}
+
+impl<V, F> View for TickView<V, F>
+where
+    V: View,
+    F: Fn(&mut Context, f32) + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            // When it last ticked.
+            let vid = cx.view_id(path);
+            cx.init_state(vid, &|| None::<Instant>);
+            let s = StateHandle::<Option<Instant>>::new(vid);
+
+            let now = Instant::now();
+            let next = match *s.get(cx) {
+                Some(last) => {
+                    let elapsed = now.duration_since(last);
+                    if elapsed >= self.interval {
+                        (self.func)(cx, elapsed.as_secs_f32());
+                        cx[s] = Some(now);
+                        now + self.interval
+                    } else {
+                        last + self.interval
+                    }
+                }
+                None => {
+                    // The first tick is right away.
+                    (self.func)(cx, self.interval.as_secs_f32());
+                    cx[s] = Some(now);
+                    now + self.interval
+                }
+            };
+            cx.request_redraw_at(next);
+        }
+
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.child.hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, F> private::Sealed for TickView<V, F> {}
@@ -0,0 +1,146 @@
+use crate::*;
+use std::any::Any;
+use std::time::Duration;
+
+/// Struct for `timeline_view`.
+pub struct TimelineView<F> {
+    interval: Duration,
+    func: F,
+}
+
+/// When the timeline started, and which tick it's on.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Ticks {
+    start: Instant,
+    tick: u64,
+}
+
+impl<V, F> TimelineView<F>
+where
+    V: View,
+    F: Fn(Instant, &mut Context) -> V + 'static,
+{
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<Ticks> {
+        let vid = cx.view_id(path);
+        cx.init_state(vid, &|| Ticks {
+            start: Instant::now(),
+            tick: 0,
+        });
+        StateHandle::new(vid)
+    }
+
+    /// Time of a tick, so everything built during it agrees.
+    fn time(&self, ticks: &Ticks) -> Instant {
+        if self.interval.is_zero() {
+            return Instant::now();
+        }
+        ticks.start + Duration::from_secs_f64(self.interval.as_secs_f64() * ticks.tick as f64)
+    }
+
+    fn body(&self, path: &IdPath, cx: &mut Context) -> V {
+        let ticks = *self.state(path, cx).get(cx);
+        (self.func)(self.time(&ticks), cx)
+    }
+}
+
+impl<V, F> View for TimelineView<F>
+where
+    V: View,
+    F: Fn(Instant, &mut Context) -> V + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        if let Event::Anim = event {
+            let s = self.state(path, cx);
+            let ticks = *s.get(cx);
+            let elapsed = ticks.start.elapsed();
+            let tick = if self.interval.is_zero() {
+                // Every animation frame.
+                ticks.tick + 1
+            } else {
+                (elapsed.as_secs_f64() / self.interval.as_secs_f64()) as u64
+            };
+            if tick != ticks.tick {
+                cx[s].tick = tick;
+            }
+            if !self.interval.is_zero() {
+                cx.request_redraw_at(self.time(&Ticks {
+                    tick: tick + 1,
+                    ..ticks
+                }));
+            }
+        }
+
+        path.push(0);
+        self.body(path, cx).process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.body(path, args.cx).draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.body(path, args.cx).layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.body(path, cx).dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let vid = self.body(path, cx).hittest(path, pt, cx);
+        path.pop();
+        vid
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.body(path, cx).commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.body(path, cx).gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.body(path, cx).access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<F> private::Sealed for TimelineView<F> {}
+
+/// Rebuilds a view every `interval`, passing the time, for clocks,
+/// countdowns and progress indicators. A zero interval rebuilds it every
+/// animation frame.
+pub fn timeline_view<V: View, F: Fn(Instant, &mut Context) -> V + 'static>(
+    interval: Duration,
+    f: F,
+) -> TimelineView<F> {
+    TimelineView { interval, func: f }
+}
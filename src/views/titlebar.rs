@@ -0,0 +1,391 @@
+use crate::*;
+use instant::{Duration, Instant};
+use std::any::Any;
+
+/// Longest time between the clicks of a double click.
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+const TRAFFIC_LIGHT_DIAMETER: f32 = 12.0;
+const TRAFFIC_LIGHT_SPACING: f32 = 8.0;
+const TRAFFIC_LIGHT_MARGIN: f32 = 12.0;
+const TRAFFIC_LIGHT_COLORS: [Color; 3] = [
+    Color::hex_const("#FF5F57"),
+    Color::hex_const("#FEBC2E"),
+    Color::hex_const("#28C840"),
+];
+
+const CAPTION_BUTTON_WIDTH: f32 = 46.0;
+const CAPTION_GLYPH_SIZE: f32 = 10.0;
+const CLOSE_HOVER_COLOR: Color = Color::hex_const("#C42B1C");
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CaptionButton {
+    Close,
+    Minimize,
+    Zoom,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+struct TitlebarState {
+    /// The button under a touch which hasn't ended.
+    pressed: Option<CaptionButton>,
+    /// When the bar was last clicked, to spot double clicks.
+    last_click: Option<Instant>,
+    title_offset: LocalOffset,
+    child_offset: LocalOffset,
+}
+
+/// Struct for the `titlebar` modifier.
+pub struct Titlebar<V, T> {
+    child: V,
+    title: T,
+    platform: MenuPlatform,
+}
+
+impl<V, T> Titlebar<V, T>
+where
+    V: View,
+    T: View,
+{
+    pub fn new(child: V, title: T) -> Self {
+        Self {
+            child,
+            title,
+            platform: MenuPlatform::current(),
+        }
+    }
+
+    fn height(&self) -> f32 {
+        match self.platform {
+            MenuPlatform::MacOS => 28.0,
+            MenuPlatform::Windows => 32.0,
+            MenuPlatform::Linux => 36.0,
+        }
+    }
+
+    fn state(&self, path: &IdPath, cx: &mut Context) -> StateHandle<TitlebarState> {
+        let id = cx.view_id(path);
+        cx.init_state(id, &TitlebarState::default);
+        StateHandle::new(id)
+    }
+
+    fn bar(&self, rect: LocalRect) -> LocalRect {
+        LocalRect::new(
+            [rect.min_x(), rect.max_y() - self.height()].into(),
+            [rect.width(), self.height()].into(),
+        )
+    }
+
+    /// Where the buttons go: traffic lights on the left on macOS, caption
+    /// buttons on the right elsewhere.
+    fn buttons(&self, rect: LocalRect) -> [(CaptionButton, LocalRect); 3] {
+        let bar = self.bar(rect);
+        let order = match self.platform {
+            MenuPlatform::MacOS => [
+                CaptionButton::Close,
+                CaptionButton::Minimize,
+                CaptionButton::Zoom,
+            ],
+            _ => [
+                CaptionButton::Minimize,
+                CaptionButton::Zoom,
+                CaptionButton::Close,
+            ],
+        };
+        let mut i = 0.0;
+        order.map(|button| {
+            let r = match self.platform {
+                MenuPlatform::MacOS => LocalRect::new(
+                    [
+                        bar.min_x()
+                            + TRAFFIC_LIGHT_MARGIN
+                            + i * (TRAFFIC_LIGHT_DIAMETER + TRAFFIC_LIGHT_SPACING),
+                        bar.center().y - TRAFFIC_LIGHT_DIAMETER / 2.0,
+                    ]
+                    .into(),
+                    [TRAFFIC_LIGHT_DIAMETER, TRAFFIC_LIGHT_DIAMETER].into(),
+                ),
+                _ => LocalRect::new(
+                    [bar.max_x() - (3.0 - i) * CAPTION_BUTTON_WIDTH, bar.min_y()].into(),
+                    [CAPTION_BUTTON_WIDTH, bar.height()].into(),
+                ),
+            };
+            i += 1.0;
+            (button, r)
+        })
+    }
+
+    fn button_at(&self, rect: LocalRect, pt: LocalPoint) -> Option<CaptionButton> {
+        self.buttons(rect)
+            .iter()
+            .find(|(_, r)| r.contains(pt))
+            .map(|(button, _)| *button)
+    }
+
+    fn draw_buttons(&self, rect: LocalRect, pressed: Option<CaptionButton>, vger: &mut Vger) {
+        for (i, (button, r)) in self.buttons(rect).iter().enumerate() {
+            let down = pressed == Some(*button);
+            if self.platform == MenuPlatform::MacOS {
+                let color = TRAFFIC_LIGHT_COLORS[i];
+                let paint = vger.color_paint(if down { color.darken(0.1) } else { color });
+                vger.fill_circle(r.center(), TRAFFIC_LIGHT_DIAMETER / 2.0, paint);
+                continue;
+            }
+
+            if down {
+                let color = if *button == CaptionButton::Close {
+                    CLOSE_HOVER_COLOR
+                } else {
+                    BUTTON_HOVER_COLOR
+                };
+                let paint = vger.color_paint(color);
+                vger.fill_rect(*r, 0.0, paint);
+            }
+
+            let c = r.center();
+            let h = CAPTION_GLYPH_SIZE / 2.0;
+            let paint = vger.color_paint(TEXT_COLOR);
+            match button {
+                CaptionButton::Minimize => {
+                    vger.stroke_segment([c.x - h, c.y], [c.x + h, c.y], 1.0, paint)
+                }
+                CaptionButton::Zoom => vger.stroke_rect(
+                    [c.x - h, c.y - h].into(),
+                    [c.x + h, c.y + h].into(),
+                    0.0,
+                    1.0,
+                    paint,
+                ),
+                CaptionButton::Close => {
+                    vger.stroke_segment([c.x - h, c.y - h], [c.x + h, c.y + h], 1.0, paint);
+                    vger.stroke_segment([c.x - h, c.y + h], [c.x + h, c.y - h], 1.0, paint);
+                }
+            }
+        }
+    }
+}
+
+impl<V, T> View for Titlebar<V, T>
+where
+    V: View,
+    T: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        let s = self.state(path, cx);
+        let state = *s.get(cx);
+        let rect = cx.get_layout(path).rect;
+
+        match event {
+            Event::TouchBegin { position, .. } if self.bar(rect).contains(*position) => {
+                if let Some(button) = self.button_at(rect, *position) {
+                    cx[s].pressed = Some(button);
+                } else {
+                    let now = Instant::now();
+                    match state.last_click {
+                        Some(last) if now - last < DOUBLE_CLICK_TIME => {
+                            cx.toggle_window_maximized();
+                            cx[s].last_click = None;
+                        }
+                        _ => {
+                            cx.drag_window();
+                            cx[s].last_click = Some(now);
+                        }
+                    }
+                }
+                return;
+            }
+            Event::TouchMove { .. } if state.pressed.is_some() => return,
+            Event::TouchEnd { position, .. } if state.pressed.is_some() => {
+                cx[s].pressed = None;
+                if self.button_at(rect, *position) == state.pressed {
+                    match state.pressed {
+                        Some(CaptionButton::Close) => cx.close_window(),
+                        Some(CaptionButton::Minimize) => cx.minimize_window(),
+                        Some(CaptionButton::Zoom) => cx.toggle_window_maximized(),
+                        None => (),
+                    }
+                }
+                return;
+            }
+            _ => (),
+        }
+
+        path.push(0);
+        self.child
+            .process(&event.offset(-state.child_offset), path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        let state = *self.state(path, args.cx).get(args.cx);
+
+        args.vger.save();
+        args.vger.translate(state.child_offset);
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+
+        let paint = args.vger.color_paint(BUTTON_BACKGROUND_COLOR);
+        args.vger.fill_rect(self.bar(rect), 0.0, paint);
+
+        args.vger.save();
+        args.vger.translate(state.title_offset);
+        path.push(1);
+        self.title.draw(path, args);
+        path.pop();
+        args.vger.restore();
+
+        self.draw_buttons(rect, state.pressed, args.vger);
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        let s = self.state(path, args.cx);
+        let bar = self.bar(LocalRect::from_size(args.sz));
+
+        path.push(1);
+        let title_size = self.title.layout(path, &mut args.size(bar.size));
+        path.pop();
+        let title_offset = (bar.center() - title_size.to_vector() / 2.0).to_vector();
+
+        // The content goes below the bar, centered like the root view.
+        let area = LocalSize::new(args.sz.width, bar.min_y().max(0.0));
+        path.push(0);
+        let child_size = self.child.layout(path, &mut args.size(area));
+        path.pop();
+        let child_offset = ((area - child_size) / 2.0).to_vector();
+
+        let state = *s.get(args.cx);
+        if state.title_offset != title_offset || state.child_offset != child_offset {
+            args.cx[s].title_offset = title_offset;
+            args.cx[s].child_offset = child_offset;
+        }
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        let offset = self.state(path, cx).get(cx).child_offset;
+        path.push(0);
+        self.child.dirty(path, xform.pre_translate(offset), cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let rect = cx.get_layout(path).rect;
+        if self.bar(rect).contains(pt) {
+            return Some(cx.view_id(path));
+        }
+
+        let offset = self.state(path, cx).get(cx).child_offset;
+        path.push(0);
+        let id = self.child.hittest(path, pt - offset, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        map.push(cx.view_id(path));
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+        path.push(1);
+        self.title.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, T> private::Sealed for Titlebar<V, T> {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_titlebar() {
+        for platform in [
+            MenuPlatform::MacOS,
+            MenuPlatform::Windows,
+            MenuPlatform::Linux,
+        ] {
+            let mut cx = Context::new();
+            let mut ui = rectangle().titlebar(|| text("Demo"));
+            ui.platform = platform;
+            let sz = [400.0, 300.0].into();
+
+            let mut path = vec![0];
+            ui.layout(
+                &mut path,
+                &mut LayoutArgs {
+                    sz,
+                    cx: &mut cx,
+                    text_bounds: &mut |_, _, _| LocalRect::zero(),
+                },
+            );
+
+            let rect = LocalRect::from_size(sz);
+            let mut actions = vec![];
+            for (button, r) in ui.buttons(rect) {
+                for event in [
+                    Event::TouchBegin {
+                        id: 0,
+                        position: r.center(),
+                    },
+                    Event::TouchEnd {
+                        id: 0,
+                        position: r.center(),
+                    },
+                ] {
+                    ui.process(&event, &mut path, &mut cx, &mut actions);
+                }
+                let change = match button {
+                    CaptionButton::Close => WindowChange::Close,
+                    CaptionButton::Minimize => WindowChange::Minimize,
+                    CaptionButton::Zoom => WindowChange::ToggleMaximized,
+                };
+                assert_eq!(cx.window_changes.pop(), Some(change));
+            }
+
+            // Dragging the rest of the bar moves the window.
+            let event = Event::TouchBegin {
+                id: 0,
+                position: [200.0, 290.0].into(),
+            };
+            ui.process(&event, &mut path, &mut cx, &mut actions);
+            assert_eq!(cx.window_changes, [WindowChange::Drag]);
+        }
+    }
+}
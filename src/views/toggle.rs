@@ -19,7 +19,7 @@ pub fn toggle(on: impl Binding<bool>) -> impl View {
                 circle()
                     .color(if b { AZURE_HIGHLIGHT } else { MEDIUM_GRAY })
                     .size([10.0, 10.0])
-                    .offset([if b { 25.0 } else { 5.0 }, 5.0]),
+                    .spring_offset([if b { 25.0 } else { 5.0 }, 5.0]),
             ))
         },
     )
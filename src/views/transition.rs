@@ -0,0 +1,156 @@
+use crate::*;
+use std::any::Any;
+
+/// How long views take to appear and disappear, in seconds.
+pub const TRANSITION_DURATION: f32 = 0.25;
+
+/// How a view appears and disappears when `cond` switches to or away
+/// from it. See the `transition` modifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transition {
+    /// Fades in and out. vger can't draw views translucent, so this fades
+    /// the view from the theme's background color, which only looks right
+    /// over that background.
+    Fade,
+    /// Slides in from the left, and back out.
+    Slide,
+    /// Grows from the center, and shrinks back.
+    Scale,
+}
+
+/// How much of a view `cond` is showing while switching, from 0 (gone) to 1,
+/// or `None` when it isn't switching.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct TransitionPhase(pub Option<f32>);
+
+/// Eases out, so views settle gently.
+pub(crate) fn ease_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+/// Struct for the `transition` modifier.
+pub struct TransitionView<V> {
+    child: V,
+    transition: Transition,
+}
+
+impl<V> TransitionView<V>
+where
+    V: View,
+{
+    pub fn new(child: V, transition: Transition) -> Self {
+        Self { child, transition }
+    }
+}
+
+impl<V> View for TransitionView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        // Let the `cond` above us know it should animate.
+        if let Event::Anim = event {
+            cx.transition_seen = true;
+        }
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let shown = match args.cx.init_env(&TransitionPhase::default).0 {
+            Some(shown) => ease_out(shown),
+            None => {
+                path.push(0);
+                self.child.draw(path, args);
+                path.pop();
+                return;
+            }
+        };
+        let rect = args.cx.get_layout(path).rect;
+
+        args.vger.save();
+        match self.transition {
+            Transition::Slide => args.vger.translate([-(1.0 - shown) * rect.max_x(), 0.0]),
+            Transition::Scale => {
+                let center = rect.center().to_vector();
+                let scale = shown.max(0.001);
+                args.vger.translate(center);
+                args.vger.scale([scale, scale]);
+                args.vger.translate(-center);
+            }
+            Transition::Fade => (),
+        }
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.vger.restore();
+
+        if let Transition::Fade = self.transition {
+            let color = args.cx.theme().colors.background.alpha(1.0 - shown);
+            let paint = args.vger.color_paint(color);
+            args.vger.fill_rect(rect, 0.0, paint);
+        }
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for TransitionView<V> {}
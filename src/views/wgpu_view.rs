@@ -0,0 +1,108 @@
+use crate::*;
+use std::rc::Rc;
+
+/// Renders a `wgpu_view` into its texture.
+pub(crate) type RenderFn =
+    Rc<dyn Fn(&wgpu::Device, &wgpu::Queue, &wgpu::TextureView, &WgpuViewport)>;
+
+/// Where a `wgpu_view` is rendering this frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WgpuViewport {
+    /// Where the texture goes in the window, or the layer of an effect
+    /// it's in, in points from the bottom left.
+    pub rect: WorldRect,
+    /// Size of the texture, in pixels.
+    pub width: u32,
+    pub height: u32,
+    /// Format of the texture, which is the window's, so pipelines made
+    /// for one work with the other.
+    pub format: wgpu::TextureFormat,
+    /// Pixels per point.
+    pub scale: f32,
+}
+
+/// Struct for `wgpu_view`.
+pub struct WgpuView<F> {
+    func: Rc<F>,
+}
+
+impl<F> View for WgpuView<F>
+where
+    F: Fn(&wgpu::Device, &wgpu::Queue, &wgpu::TextureView, &WgpuViewport) + 'static,
+{
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        let rect = args.cx.get_layout(path).rect;
+        if rect.is_empty() {
+            return;
+        }
+        let world = args.vger.current_transform().outer_transformed_rect(&rect);
+        let key = args.cx.view_id(path).id;
+        let (clip, layer) = (args.cx.clip_rect, args.cx.layer);
+        args.cx
+            .images
+            .draw_surface(key, world, clip, layer, self.func.clone());
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.update_layout(
+            path,
+            LayoutBox {
+                rect: LocalRect::new(LocalPoint::zero(), args.sz),
+                offset: LocalOffset::zero(),
+            },
+        );
+        args.sz
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        let rect = cx.get_layout(path).rect;
+
+        if rect.contains(pt) {
+            Some(cx.view_id(path))
+        } else {
+            None
+        }
+    }
+}
+
+impl<F> private::Sealed for WgpuView<F> {}
+
+/// Renders with wgpu directly, for 3D scenes, custom shaders or plotting
+/// engines which vger can't draw. Each frame the view's in, `f` gets the
+/// device and queue, and a texture the size of the view in pixels to
+/// render into with its own command encoders. What's rendered is blended
+/// over the window by its alpha, and stays in the texture for the next
+/// frame unless it's cleared.
+///
+/// Like images, it's drawn after the rest of the window, so it shows above
+/// views drawn over it, but it's clipped by `clip` and `scroll_view`. The
+/// window only redraws when something changes, so for animation, redraw
+/// with the `tick` modifier.
+///
+/// ```no_run
+/// # use rui::*;
+/// wgpu_view(|device, queue, view, _viewport| {
+///     let mut encoder = device.create_command_encoder(&Default::default());
+///     encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+///         label: None,
+///         color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+///             view,
+///             resolve_target: None,
+///             ops: wgpu::Operations {
+///                 load: wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+///                 store: wgpu::StoreOp::Store,
+///             },
+///         })],
+///         ..Default::default()
+///     });
+///     queue.submit(Some(encoder.finish()));
+/// })
+/// .padding(Auto);
+/// ```
+pub fn wgpu_view<
+    F: Fn(&wgpu::Device, &wgpu::Queue, &wgpu::TextureView, &WgpuViewport) + 'static,
+>(
+    f: F,
+) -> WgpuView<F> {
+    WgpuView { func: Rc::new(f) }
+}
@@ -175,3 +175,138 @@ where
 }
 
 impl<V> private::Sealed for FullscreenView<V> {}
+
+/// What's drawn behind the UI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowBackground {
+    /// Clear the window to a color.
+    Color(Color),
+
+    /// See-through window. Views draw directly over the desktop.
+    Transparent,
+
+    /// Translucent system material: vibrancy on macOS, acrylic on Windows.
+    /// Falls back to `Transparent` where unsupported or without the
+    /// `vibrancy` feature.
+    Blur,
+}
+
+impl Default for WindowBackground {
+    fn default() -> Self {
+        WindowBackground::Color(BLACK)
+    }
+}
+
+impl WindowBackground {
+    /// Does the window need to be composited with what's behind it?
+    pub fn is_transparent(&self) -> bool {
+        match self {
+            WindowBackground::Color(color) => color.a < 1.0,
+            _ => true,
+        }
+    }
+
+    /// Color for clearing the frame, premultiplied.
+    pub(crate) fn clear_color(&self) -> wgpu::Color {
+        match self {
+            WindowBackground::Color(c) => {
+                let a = c.a as f64;
+                wgpu::Color {
+                    r: c.r as f64 * a,
+                    g: c.g as f64 * a,
+                    b: c.b as f64 * a,
+                    a,
+                }
+            }
+            _ => wgpu::Color::TRANSPARENT,
+        }
+    }
+}
+
+/// Struct for the `window_background` modifier.
+pub struct WindowBackgroundView<V> {
+    child: V,
+    background: WindowBackground,
+}
+
+impl<V> WindowBackgroundView<V>
+where
+    V: View,
+{
+    pub fn new(v: V, background: WindowBackground) -> Self {
+        Self {
+            child: v,
+            background,
+        }
+    }
+}
+
+impl<V> View for WindowBackgroundView<V>
+where
+    V: View,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+        args.cx.window_background = self.background;
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V> private::Sealed for WindowBackgroundView<V> {}
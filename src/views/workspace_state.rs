@@ -0,0 +1,101 @@
+use crate::*;
+use std::any::Any;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Struct for the `workspace_state` modifier.
+pub struct WorkspaceState<V, B, T> {
+    child: V,
+    key: String,
+    binding: B,
+    phantom: PhantomData<T>,
+}
+
+impl<V, B, T> WorkspaceState<V, B, T>
+where
+    V: View,
+    B: Binding<T>,
+    T: FromStr + Display + 'static,
+{
+    pub fn new(v: V, key: &str, binding: B) -> Self {
+        Self {
+            child: v,
+            key: key.into(),
+            binding,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, B, T> View for WorkspaceState<V, B, T>
+where
+    V: View,
+    B: Binding<T>,
+    T: FromStr + Display + 'static,
+{
+    fn process(
+        &self,
+        event: &Event,
+        path: &mut IdPath,
+        cx: &mut Context,
+        actions: &mut Vec<Box<dyn Any>>,
+    ) {
+        path.push(0);
+        self.child.process(event, path, cx, actions);
+        path.pop();
+    }
+
+    fn draw(&self, path: &mut IdPath, args: &mut DrawArgs) {
+        path.push(0);
+        self.child.draw(path, args);
+        path.pop();
+    }
+
+    fn layout(&self, path: &mut IdPath, args: &mut LayoutArgs) -> LocalSize {
+        args.cx.sync_workspace_panel(&self.key, self.binding);
+        path.push(0);
+        let sz = self.child.layout(path, args);
+        path.pop();
+        sz
+    }
+
+    fn dirty(&self, path: &mut IdPath, xform: LocalToWorld, cx: &mut Context) {
+        path.push(0);
+        self.child.dirty(path, xform, cx);
+        path.pop();
+    }
+
+    fn hittest(&self, path: &mut IdPath, pt: LocalPoint, cx: &mut Context) -> Option<ViewId> {
+        path.push(0);
+        let id = self.child.hittest(path, pt, cx);
+        path.pop();
+        id
+    }
+
+    fn commands(&self, path: &mut IdPath, cx: &mut Context, cmds: &mut Vec<CommandInfo>) {
+        path.push(0);
+        self.child.commands(path, cx, cmds);
+        path.pop();
+    }
+
+    fn gc(&self, path: &mut IdPath, cx: &mut Context, map: &mut Vec<ViewId>) {
+        path.push(0);
+        self.child.gc(path, cx, map);
+        path.pop();
+    }
+
+    fn access(
+        &self,
+        path: &mut IdPath,
+        cx: &mut Context,
+        nodes: &mut Vec<(accesskit::NodeId, accesskit::Node)>,
+    ) -> Option<accesskit::NodeId> {
+        path.push(0);
+        let node_id = self.child.access(path, cx, nodes);
+        path.pop();
+        node_id
+    }
+}
+
+impl<V, B, T> private::Sealed for WorkspaceState<V, B, T> {}
@@ -0,0 +1,370 @@
+use crate::*;
+
+/// How the window starts out, for `rui_with`. Sizes and positions are in
+/// points.
+///
+/// ```no_run
+/// # use rui::*;
+/// rui_with(
+///     WindowConfig::new()
+///         .title("Synth")
+///         .size(800.0, 600.0)
+///         .min_size(400.0, 300.0),
+///     text("hello"),
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowConfig {
+    pub title: String,
+    /// Size of the window's content. The platform decides if `None`.
+    pub size: Option<[f32; 2]>,
+    pub min_size: Option<[f32; 2]>,
+    pub max_size: Option<[f32; 2]>,
+    pub resizable: bool,
+    /// Position of the window's outer frame. The platform decides if `None`.
+    pub position: Option<[f32; 2]>,
+    /// Whether the platform draws a title bar and border. Borderless
+    /// windows can draw their own with the `titlebar` modifier.
+    pub decorations: bool,
+    pub menu_bar: MenuBarMode,
+    /// Exclusive fullscreen for installations and point-of-sale. See
+    /// `WindowConfig::kiosk`.
+    pub kiosk: bool,
+    /// Which monitor kiosk mode takes over, in the order the platform lists
+    /// them. The primary monitor if `None`.
+    pub monitor: Option<usize>,
+    /// The video mode kiosk mode switches to. The monitor's largest if
+    /// `None`.
+    pub video_mode: Option<VideoMode>,
+    /// Whether the window can be see-through, for a `Transparent` or `Blur`
+    /// `window_background`. Some platforms can't switch later, so ask up
+    /// front if the app starts out that way.
+    pub transparent: bool,
+}
+
+/// A display resolution, in pixels, and refresh rate, in hertz.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct VideoMode {
+    pub width: u32,
+    pub height: u32,
+    /// Any rate if `None`, preferring the fastest.
+    pub refresh_rate: Option<u32>,
+}
+
+impl VideoMode {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            refresh_rate: None,
+        }
+    }
+
+    pub fn refresh_rate(mut self, hz: u32) -> Self {
+        self.refresh_rate = Some(hz);
+        self
+    }
+}
+
+/// Whether to draw the app's commands as a menu bar in the window.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MenuBarMode {
+    /// Where there's no native menu bar, which is everywhere but macOS, if
+    /// the app has commands.
+    Auto,
+    InWindow,
+    Off,
+}
+
+impl MenuBarMode {
+    /// Whether `rui_with` puts the app's commands in the window.
+    #[cfg_attr(not(feature = "winit"), allow(dead_code))]
+    pub(crate) fn in_window(self, commands: &[CommandInfo]) -> bool {
+        match self {
+            MenuBarMode::Auto => {
+                MenuPlatform::current() != MenuPlatform::MacOS && !commands.is_empty()
+            }
+            MenuBarMode::InWindow => true,
+            MenuBarMode::Off => false,
+        }
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "rui".into(),
+            size: None,
+            min_size: None,
+            max_size: None,
+            resizable: true,
+            position: None,
+            decorations: true,
+            menu_bar: MenuBarMode::Auto,
+            kiosk: false,
+            monitor: None,
+            video_mode: None,
+            transparent: false,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.size = Some([width, height]);
+        self
+    }
+
+    pub fn min_size(mut self, width: f32, height: f32) -> Self {
+        self.min_size = Some([width, height]);
+        self
+    }
+
+    pub fn max_size(mut self, width: f32, height: f32) -> Self {
+        self.max_size = Some([width, height]);
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn position(mut self, x: f32, y: f32) -> Self {
+        self.position = Some([x, y]);
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn menu_bar(mut self, mode: MenuBarMode) -> Self {
+        self.menu_bar = mode;
+        self
+    }
+
+    /// Takes over a monitor with exclusive fullscreen, confines the cursor
+    /// to it, and keeps the screen saver and display sleep away while the
+    /// app runs. Falls back to borderless fullscreen if the video mode isn't
+    /// available. Screen saver inhibition works on macOS and Windows.
+    ///
+    /// ```no_run
+    /// # use rui::*;
+    /// rui_with(
+    ///     WindowConfig::new()
+    ///         .kiosk(true)
+    ///         .monitor(1)
+    ///         .video_mode(VideoMode::new(1920, 1080).refresh_rate(60)),
+    ///     text("Welcome"),
+    /// );
+    /// ```
+    pub fn kiosk(mut self, kiosk: bool) -> Self {
+        self.kiosk = kiosk;
+        self
+    }
+
+    pub fn monitor(mut self, index: usize) -> Self {
+        self.monitor = Some(index);
+        self
+    }
+
+    pub fn video_mode(mut self, mode: VideoMode) -> Self {
+        self.video_mode = Some(mode);
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+}
+
+/// How close to the edge of a borderless window the mouse resizes it, in
+/// points.
+#[cfg_attr(not(feature = "winit"), allow(dead_code))]
+pub(crate) const RESIZE_BORDER: f32 = 6.0;
+
+/// An edge or corner of the window, to resize it from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WindowEdge {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl WindowEdge {
+    /// The edge of a window of `size` which `position` is near, if any.
+    #[cfg_attr(not(feature = "winit"), allow(dead_code))]
+    pub(crate) fn at(position: LocalPoint, size: LocalSize) -> Option<WindowEdge> {
+        let west = position.x < RESIZE_BORDER;
+        let east = position.x > size.width - RESIZE_BORDER;
+        let south = position.y < RESIZE_BORDER;
+        let north = position.y > size.height - RESIZE_BORDER;
+        match (north, south, east, west) {
+            (true, _, true, _) => Some(WindowEdge::NorthEast),
+            (true, _, _, true) => Some(WindowEdge::NorthWest),
+            (_, true, true, _) => Some(WindowEdge::SouthEast),
+            (_, true, _, true) => Some(WindowEdge::SouthWest),
+            (true, ..) => Some(WindowEdge::North),
+            (_, true, ..) => Some(WindowEdge::South),
+            (_, _, true, _) => Some(WindowEdge::East),
+            (.., true) => Some(WindowEdge::West),
+            _ => None,
+        }
+    }
+}
+
+/// Where to put the window on its screen.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WindowSnap {
+    LeftHalf,
+    RightHalf,
+    Maximized,
+    /// Back to where it was before it was snapped or maximized, or
+    /// minimized if it wasn't.
+    Restore,
+}
+
+impl WindowSnap {
+    /// The position and size for snapping to half of a screen, given the
+    /// screen's position and size.
+    #[cfg_attr(not(feature = "winit"), allow(dead_code))]
+    pub(crate) fn frame(self, origin: [f32; 2], size: [f32; 2]) -> Option<([f32; 2], [f32; 2])> {
+        let half = [size[0] / 2.0, size[1]];
+        match self {
+            WindowSnap::LeftHalf => Some((origin, half)),
+            WindowSnap::RightHalf => Some(([origin[0] + half[0], origin[1]], half)),
+            WindowSnap::Maximized | WindowSnap::Restore => None,
+        }
+    }
+}
+
+/// A change to the window, for the event loop to make.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum WindowChange {
+    Size([f32; 2]),
+    MinSize(Option<[f32; 2]>),
+    MaxSize(Option<[f32; 2]>),
+    Resizable(bool),
+    Position([f32; 2]),
+    Close,
+    Minimize,
+    ToggleMaximized,
+    /// Moves the window with the mouse, while the button is down.
+    Drag,
+    DragResize(WindowEdge),
+    Snap(WindowSnap),
+    ContentProtection(bool),
+}
+
+impl Context {
+    pub fn set_window_title(&mut self, title: &str) {
+        self.window_title = title.into();
+    }
+
+    /// Resizes the window's content, in points.
+    pub fn set_window_size(&mut self, width: f32, height: f32) {
+        self.window_changes
+            .push(WindowChange::Size([width, height]));
+    }
+
+    pub fn set_window_min_size(&mut self, size: Option<[f32; 2]>) {
+        self.window_changes.push(WindowChange::MinSize(size));
+    }
+
+    pub fn set_window_max_size(&mut self, size: Option<[f32; 2]>) {
+        self.window_changes.push(WindowChange::MaxSize(size));
+    }
+
+    pub fn set_window_resizable(&mut self, resizable: bool) {
+        self.window_changes.push(WindowChange::Resizable(resizable));
+    }
+
+    /// Moves the window's outer frame, in points.
+    pub fn set_window_position(&mut self, x: f32, y: f32) {
+        self.window_changes.push(WindowChange::Position([x, y]));
+    }
+
+    /// Closes the window, which quits the app.
+    pub fn close_window(&mut self) {
+        self.window_changes.push(WindowChange::Close);
+    }
+
+    pub fn minimize_window(&mut self) {
+        self.window_changes.push(WindowChange::Minimize);
+    }
+
+    /// Maximizes the window, or restores it if it's maximized.
+    pub fn toggle_window_maximized(&mut self) {
+        self.window_changes.push(WindowChange::ToggleMaximized);
+    }
+
+    /// Lets the user move the window by dragging, for custom title bars.
+    /// Call it when the mouse button goes down.
+    pub fn drag_window(&mut self) {
+        self.window_changes.push(WindowChange::Drag);
+    }
+
+    /// Lets the user resize the window by dragging `edge`. Borderless
+    /// windows do this near their edges already.
+    pub fn drag_resize_window(&mut self, edge: WindowEdge) {
+        self.window_changes.push(WindowChange::DragResize(edge));
+    }
+
+    /// Snaps the window to half of the screen, maximizes it or restores it.
+    /// Borderless windows do this with the Windows key and the arrow keys,
+    /// except on macOS.
+    pub fn snap_window(&mut self, snap: WindowSnap) {
+        self.window_changes.push(WindowChange::Snap(snap));
+    }
+
+    /// Keeps the window out of screenshots, recordings and screen sharing,
+    /// for passwords and protected media. Works on macOS and Windows 10
+    /// 2004 or later, and does nothing elsewhere.
+    pub fn set_content_protection(&mut self, protected: bool) {
+        self.window_changes
+            .push(WindowChange::ContentProtection(protected));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_window_edge() {
+        let size = LocalSize::new(200.0, 100.0);
+        let edge = |x, y| WindowEdge::at(LocalPoint::new(x, y), size);
+        assert_eq!(edge(100.0, 50.0), None);
+        assert_eq!(edge(100.0, 98.0), Some(WindowEdge::North));
+        assert_eq!(edge(2.0, 2.0), Some(WindowEdge::SouthWest));
+        assert_eq!(edge(199.0, 50.0), Some(WindowEdge::East));
+    }
+
+    #[test]
+    fn test_window_snap() {
+        let screen = ([0.0, 25.0], [1000.0, 800.0]);
+        assert_eq!(
+            WindowSnap::RightHalf.frame(screen.0, screen.1),
+            Some(([500.0, 25.0], [500.0, 800.0]))
+        );
+        assert_eq!(WindowSnap::Maximized.frame(screen.0, screen.1), None);
+    }
+}
@@ -1,29 +1,30 @@
 use crate::*;
 
-use futures::executor::block_on;
+use euclid::Size2D;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 
 use winit::{
-    dpi::PhysicalSize,
+    dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize},
     event::{
-        ElementState, Event as WEvent, MouseButton as WMouseButton, Touch, TouchPhase,
-        VirtualKeyCode, WindowEvent,
+        ElementState, Event as WEvent, Ime, MouseButton as WMouseButton, MouseScrollDelta,
+        StartCause, Touch, TouchPhase, VirtualKeyCode, WindowEvent,
     },
     event_loop::{ControlFlow, EventLoop, EventLoopProxy},
-    window::{Window, WindowBuilder},
+    window::{CursorIcon, Fullscreen, ResizeDirection, Window, WindowBuilder},
 };
 
-type WorkQueue = VecDeque<Box<dyn FnOnce(&mut Context) + Send>>;
+pub(crate) type WorkQueue = VecDeque<Box<dyn FnOnce(&mut Context) + Send>>;
 
 #[cfg(not(target_arch = "wasm32"))]
 lazy_static! {
     /// Allows us to wake the event loop whenever we want.
     static ref GLOBAL_EVENT_LOOP_PROXY: Mutex<Option<EventLoopProxy<()>>> = Mutex::new(None);
 
-    static ref GLOBAL_WORK_QUEUE: Mutex<WorkQueue> = Mutex::new(WorkQueue::new());
+    pub(crate) static ref GLOBAL_WORK_QUEUE: Mutex<WorkQueue> = Mutex::new(WorkQueue::new());
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -39,6 +40,17 @@ pub fn on_main(f: impl FnOnce(&mut Context) + Send + 'static) {
     }
 }
 
+/// Sends files and URLs the app was asked to open to the UI.
+fn process_opens(cx: &mut Context, view: &impl View, window: &Window) {
+    if !cx.open_files.is_empty() {
+        let paths = std::mem::take(&mut cx.open_files);
+        process_event(cx, view, &Event::OpenFiles(paths), window);
+    }
+    for url in std::mem::take(&mut cx.open_urls) {
+        process_event(cx, view, &Event::OpenUrl(url), window);
+    }
+}
+
 struct Setup {
     size: PhysicalSize<u32>,
     surface: wgpu::Surface,
@@ -132,17 +144,283 @@ fn process_event(cx: &mut Context, view: &impl View, event: &Event, window: &Win
     }
 
     cx.prev_grab_cursor = cx.grab_cursor;
+
+    if let Some(request) = cx.drag_out.take() {
+        start_drag_out(window, request);
+    }
+}
+
+/// Hands a `drag_out` over to the OS. Winit windows on Linux can't
+/// start native drags, so there the request is dropped.
+#[allow(unused_variables)]
+fn start_drag_out(window: &Window, request: DragOutRequest) {
+    #[cfg(all(feature = "drag-out", any(target_os = "macos", target_os = "windows")))]
+    {
+        let item = match request.payload {
+            DragPayload::Files(paths) => drag::DragItem::Files(paths),
+            #[cfg(target_os = "macos")]
+            DragPayload::Text(text) => drag::DragItem::Data {
+                provider: Box::new(move |_| Some(text.clone().into_bytes())),
+                types: vec!["public.utf8-plain-text".into()],
+            },
+            #[cfg(target_os = "macos")]
+            DragPayload::Image(png) => drag::DragItem::Data {
+                provider: Box::new(move |_| Some(png.clone())),
+                types: vec!["public.png".into()],
+            },
+            // Drags from Windows can only carry files, so write one.
+            #[cfg(target_os = "windows")]
+            DragPayload::Text(text) => match drag_out_file("Dragged Text.txt", text.as_bytes()) {
+                Ok(path) => drag::DragItem::Files(vec![path]),
+                Err(err) => {
+                    println!("unable to write dragged text: {}", err);
+                    return;
+                }
+            },
+            #[cfg(target_os = "windows")]
+            DragPayload::Image(png) => match drag_out_file("Dragged Image.png", &png) {
+                Ok(path) => drag::DragItem::Files(vec![path]),
+                Err(err) => {
+                    println!("unable to write dragged image: {}", err);
+                    return;
+                }
+            },
+        };
+
+        if let Err(err) = drag::start_drag(window, item, drag::Image::Raw(request.preview), |_| ())
+        {
+            println!("unable to start drag: {}", err);
+        }
+    }
+}
+
+#[cfg(all(feature = "drag-out", target_os = "windows"))]
+fn drag_out_file(name: &str, contents: &[u8]) -> std::io::Result<std::path::PathBuf> {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Picks a compositing mode for the surface.
+fn alpha_mode(modes: &[wgpu::CompositeAlphaMode], transparent: bool) -> wgpu::CompositeAlphaMode {
+    if transparent {
+        for mode in [
+            wgpu::CompositeAlphaMode::PreMultiplied,
+            wgpu::CompositeAlphaMode::PostMultiplied,
+            wgpu::CompositeAlphaMode::Inherit,
+        ] {
+            if modes.contains(&mode) {
+                return mode;
+            }
+        }
+    }
+    wgpu::CompositeAlphaMode::Auto
+}
+
+fn apply_window_background(window: &Window, background: WindowBackground) {
+    window.set_transparent(background.is_transparent());
+
+    #[cfg(all(feature = "vibrancy", not(target_arch = "wasm32")))]
+    {
+        let result = if background == WindowBackground::Blur {
+            #[cfg(target_os = "macos")]
+            {
+                window_vibrancy::apply_vibrancy(
+                    window,
+                    window_vibrancy::NSVisualEffectMaterial::HudWindow,
+                    None,
+                    None,
+                )
+            }
+            #[cfg(target_os = "windows")]
+            {
+                window_vibrancy::apply_acrylic(window, None)
+                    .or_else(|_| window_vibrancy::apply_blur(window, None))
+            }
+            #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+            {
+                Ok(())
+            }
+        } else {
+            #[cfg(target_os = "windows")]
+            {
+                window_vibrancy::clear_acrylic(window)
+                    .or_else(|_| window_vibrancy::clear_blur(window))
+            }
+            #[cfg(not(target_os = "windows"))]
+            {
+                Ok(())
+            }
+        };
+
+        if let Err(err) = result {
+            println!("unable to set window background: {}", err);
+        }
+    }
+}
+
+/// Window size in points, and the scale factor.
+fn logical_size(window: &Window) -> (Size2D<f32, WorldSpace>, f32) {
+    let window_size = window.inner_size();
+    let scale = window.scale_factor() as f32;
+    // println!("window_size: {:?}", window_size);
+    let width = window_size.width as f32 / scale;
+    let height = window_size.height as f32 / scale;
+    ([width, height].into(), scale)
+}
+
+/// Should a resize wait for the GPU to finish the frame drawn for it?
+/// macOS and Wayland show a window at its new size along with the next
+/// frame it presents, so returning to them before that frame is done
+/// shows the old contents stretched, or black borders. Elsewhere waiting
+/// would only hold up the event loop.
+#[cfg(target_os = "macos")]
+fn resize_waits_for_frame(_window: &Window) -> bool {
+    true
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn resize_waits_for_frame(window: &Window) -> bool {
+    use winit::platform::wayland::WindowExtWayland;
+    window.wayland_surface().is_some()
+}
+
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn resize_waits_for_frame(_window: &Window) -> bool {
+    false
+}
+
+fn resize_direction(edge: WindowEdge) -> ResizeDirection {
+    match edge {
+        WindowEdge::North => ResizeDirection::North,
+        WindowEdge::South => ResizeDirection::South,
+        WindowEdge::East => ResizeDirection::East,
+        WindowEdge::West => ResizeDirection::West,
+        WindowEdge::NorthEast => ResizeDirection::NorthEast,
+        WindowEdge::NorthWest => ResizeDirection::NorthWest,
+        WindowEdge::SouthEast => ResizeDirection::SouthEast,
+        WindowEdge::SouthWest => ResizeDirection::SouthWest,
+    }
+}
+
+fn drag_resize(window: &Window, edge: WindowEdge) {
+    if let Err(err) = window.drag_resize_window(resize_direction(edge)) {
+        println!("unable to resize window: {}", err);
+    }
+}
+
+/// Where the window was before it was snapped.
+type Unsnapped = Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>;
+
+fn snap_window(window: &Window, snap: WindowSnap, unsnapped: &mut Unsnapped) {
+    let frame = (
+        window.outer_position().unwrap_or_default(),
+        window.inner_size(),
+    );
+    match snap {
+        WindowSnap::Maximized => {
+            if unsnapped.is_none() && !window.is_maximized() {
+                *unsnapped = Some(frame);
+            }
+            window.set_maximized(true);
+        }
+        WindowSnap::Restore => {
+            if window.is_maximized() {
+                window.set_maximized(false);
+            } else if let Some((position, size)) = unsnapped.take() {
+                window.set_outer_position(position);
+                window.set_inner_size(size);
+            } else {
+                window.set_minimized(true);
+            }
+        }
+        WindowSnap::LeftHalf | WindowSnap::RightHalf => {
+            let monitor = match window.current_monitor() {
+                Some(monitor) => monitor,
+                None => return,
+            };
+            let origin = monitor.position();
+            let size = monitor.size();
+            let snapped = snap.frame(
+                [origin.x as f32, origin.y as f32],
+                [size.width as f32, size.height as f32],
+            );
+            if let Some((origin, size)) = snapped {
+                if unsnapped.is_none() && !window.is_maximized() {
+                    *unsnapped = Some(frame);
+                }
+                window.set_maximized(false);
+                window
+                    .set_outer_position(PhysicalPosition::new(origin[0] as i32, origin[1] as i32));
+                window.set_inner_size(PhysicalSize::new(size[0] as u32, size[1] as u32));
+            }
+        }
+    }
 }
 
 /// Call this function to run your UI.
 pub fn rui(view: impl View) {
+    rui_with(WindowConfig::default(), view)
+}
+
+/// Runs your UI in a window set up by `config`.
+pub fn rui_with(config: WindowConfig, view: impl View) {
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    crate::tokio_runtime::enter();
+
     let event_loop = EventLoop::new();
 
-    let mut window_title = String::from("rui");
-    let builder = WindowBuilder::new().with_title(&window_title);
+    let mut window_title = config.title.clone();
+    #[cfg(target_os = "macos")]
+    let mut document_edited = false;
+    let menu_bar_mode = config.menu_bar;
+    let kiosk = config.kiosk;
+    let decorations = config.decorations && !config.kiosk;
+    let mut builder = WindowBuilder::new()
+        .with_title(&window_title)
+        .with_resizable(config.resizable && !config.kiosk)
+        .with_decorations(decorations);
+    if let Some([width, height]) = config.size {
+        builder = builder.with_inner_size(LogicalSize::new(width, height));
+    }
+    if let Some([width, height]) = config.min_size {
+        builder = builder.with_min_inner_size(LogicalSize::new(width, height));
+    }
+    if let Some([width, height]) = config.max_size {
+        builder = builder.with_max_inner_size(LogicalSize::new(width, height));
+    }
+    if let Some([x, y]) = config.position {
+        builder = builder.with_position(LogicalPosition::new(x, y));
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    if config.kiosk {
+        builder = builder.with_fullscreen(Some(crate::kiosk::fullscreen(&event_loop, &config)));
+    }
+    let builder = builder.with_transparent(config.transparent);
     let window = builder.build(&event_loop).unwrap();
+    #[cfg(not(target_arch = "wasm32"))]
+    if config.kiosk {
+        crate::kiosk::lock_down(&window);
+    }
 
-    let setup = block_on(setup(&window));
+    // Don't stall Tokio if we're started from one of its threads.
+    #[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+    let setup = crate::tokio_runtime::block_on(setup(&window));
+    #[cfg(not(all(feature = "tokio", not(target_arch = "wasm32"))))]
+    let setup = futures::executor::block_on(setup(&window));
     let surface = setup.surface;
     let device = Arc::new(setup.device);
     let size = setup.size;
@@ -163,39 +441,81 @@ pub fn rui(view: impl View) {
     #[cfg(not(target_arch = "wasm32"))]
     {
         *GLOBAL_EVENT_LOOP_PROXY.lock().unwrap() = Some(event_loop.create_proxy());
+
+        // Run anything queued before the event loop existed.
+        if !GLOBAL_WORK_QUEUE.lock().unwrap().is_empty() {
+            let _ = event_loop.create_proxy().send_event(());
+        }
     }
 
     let mut vger = Vger::new(device.clone(), queue.clone(), config.format);
     let mut cx = Context::new();
+    cx.effects
+        .set_device(device.clone(), queue.clone(), config.format);
+    cx.window_title = window_title.clone();
     let mut mouse_position = LocalPoint::zero();
 
+    // winit reports dragged files one at a time, so we gather them up and
+    // send them together once the events have been handled.
+    let mut hovered_files: Vec<PathBuf> = vec![];
+    let mut dropped_files: Vec<PathBuf> = vec![];
+    let mut files_hover_changed = false;
+
+    #[cfg(all(feature = "global-hotkeys", not(target_arch = "wasm32")))]
+    let mut global_hotkeys = crate::global_hotkeys::GlobalHotKeys::default();
+
     let mut commands: Vec<CommandInfo> = Vec::new();
     let mut command_map = HashMap::new();
     cx.commands(&view, &mut commands);
 
+    let menus = if menu_bar_mode.in_window(&commands) {
+        menu_bar(&window_title, &commands, MenuPlatform::current())
+    } else {
+        vec![]
+    };
+    let view = in_window_menus(menus, view);
+
     {
         // So we can infer a type for CommandMap when winit is enabled.
         command_map.insert("", "");
     }
 
     let mut access_nodes = vec![];
+    let mut minimized = false;
+    let resize_waits = resize_waits_for_frame(&window);
+    let mut held_keys = HashSet::new();
+    let mut unsnapped: Unsnapped = None;
+    let mut resize_cursor = None;
+    let mut window_background = cx.window_background;
+    let mut text_input_area = None;
 
-    event_loop.run(move |event, _, control_flow| {
-        // ControlFlow::Poll continuously runs the event loop, even if the OS hasn't
-        // dispatched any events. This is ideal for games and similar applications.
-        // *control_flow = ControlFlow::Poll;
-
-        // ControlFlow::Wait pauses the event loop if no events are available to process.
-        // This is ideal for non-game applications that only update in response to user
-        // input, and uses significantly less power/CPU time than ControlFlow::Poll.
-        *control_flow = ControlFlow::Wait;
+    // Windows and Linux pass documents and links as arguments.
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let cwd = std::env::current_dir().unwrap_or_default();
+        let (files, urls) = command_line_opens(&cwd, args.iter().map(|a| a.as_str()));
+        cx.open_files = files;
+        cx.open_urls = urls;
+    }
 
+    event_loop.run(move |event, _, control_flow| {
         match event {
+            WEvent::NewEvents(StartCause::Init) => {
+                #[cfg(target_os = "macos")]
+                crate::open::apple_events::install();
+
+                process_opens(&mut cx, &view, &window);
+            }
             WEvent::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
             } => {
-                println!("The close button was pressed; stopping");
+                #[cfg(not(target_arch = "wasm32"))]
+                if !cx.close_requested() {
+                    window.request_redraw();
+                    return;
+                }
                 *control_flow = ControlFlow::Exit
             }
             WEvent::WindowEvent {
@@ -208,10 +528,64 @@ pub fn rui(view: impl View) {
                 ..
             } => {
                 // println!("Resizing to {:?}", size);
+
+                // Some platforms report minimizing as a resize to zero.
+                let is_minimized = size.width == 0 || size.height == 0;
+                if is_minimized != minimized {
+                    minimized = is_minimized;
+                    cx.set_window_occluded(minimized);
+                }
+
                 config.width = size.width.max(1);
                 config.height = size.height.max(1);
                 surface.configure(&device, &config);
-                window.request_redraw();
+
+                if minimized {
+                    return;
+                }
+
+                // Draw at the new size right away rather than waiting for RedrawRequested,
+                // so the frame reaches the compositor along with the resize. Otherwise live
+                // resizing on macOS and Wayland shows stretched content or black borders.
+                let (window_size, scale) = logical_size(&window);
+                cx.update(&view, &mut vger, &mut access_nodes, window_size);
+                cx.render(
+                    RenderInfo {
+                        device: &device,
+                        surface: &surface,
+                        config: &config,
+                        queue: &queue,
+                    },
+                    &view,
+                    &mut vger,
+                    window_size,
+                    scale,
+                );
+
+                // Don't return to the compositor until the GPU has finished the frame,
+                // where it matters. See resize_waits_for_frame.
+                if resize_waits {
+                    device.poll(wgpu::Maintain::Wait);
+                }
+            }
+            WEvent::WindowEvent {
+                event: WindowEvent::Moved(position),
+                ..
+            } => {
+                let position = position.to_logical::<f32>(window.scale_factor());
+                cx.window_position = Some([position.x, position.y]);
+            }
+            WEvent::WindowEvent {
+                event: WindowEvent::Focused(focused),
+                ..
+            } => {
+                cx.set_window_focused(focused);
+            }
+            WEvent::WindowEvent {
+                event: WindowEvent::Occluded(occluded),
+                ..
+            } => {
+                cx.set_window_occluded(occluded);
             }
             WEvent::UserEvent(_) => {
                 // println!("received user event");
@@ -223,30 +597,117 @@ pub fn rui(view: impl View) {
                         f(&mut cx);
                     }
                 }
+
+                process_opens(&mut cx, &view, &window);
+
+                for message in std::mem::take(&mut cx.midi_events) {
+                    process_event(&mut cx, &view, &Event::Midi(message), &window);
+                }
             }
             WEvent::MainEventsCleared => {
                 // Application update code.
 
+                if !dropped_files.is_empty() {
+                    hovered_files.clear();
+                    let event = Event::DropFiles {
+                        position: mouse_position,
+                        paths: std::mem::take(&mut dropped_files),
+                    };
+                    process_event(&mut cx, &view, &event, &window);
+                } else if files_hover_changed && !hovered_files.is_empty() {
+                    let event = Event::DropHover {
+                        position: mouse_position,
+                        paths: hovered_files.clone(),
+                    };
+                    process_event(&mut cx, &view, &event, &window);
+                }
+                files_hover_changed = false;
+
                 // Queue a RedrawRequested event.
                 //
                 // You only need to call this if you've determined that you need to redraw, in
                 // applications which do not always need to. Applications that redraw continuously
                 // can just render here instead.
 
-                let window_size = window.inner_size();
-                let scale = window.scale_factor() as f32;
-                // println!("window_size: {:?}", window_size);
-                let width = window_size.width as f32 / scale;
-                let height = window_size.height as f32 / scale;
+                let (window_size, _) = logical_size(&window);
+                let changed = cx.update(&view, &mut vger, &mut access_nodes, window_size);
 
-                if cx.update(&view, &mut vger, &mut access_nodes, [width, height].into()) {
+                #[cfg(all(feature = "global-hotkeys", not(target_arch = "wasm32")))]
+                global_hotkeys.update(&mut cx);
+
+                if cx.should_redraw(changed) {
                     window.request_redraw();
                 }
 
+                // ControlFlow::Wait pauses the event loop if no events are available to process.
+                // This is ideal for non-game applications that only update in response to user
+                // input, and uses significantly less power/CPU time than ControlFlow::Poll.
+                // We wake up early for animations, timers and idle callbacks.
+                *control_flow = match cx.next_deadline() {
+                    Some(deadline) => ControlFlow::WaitUntil(deadline),
+                    None => ControlFlow::Wait,
+                };
+
                 if cx.window_title != window_title {
                     window_title = cx.window_title.clone();
                     window.set_title(&cx.window_title);
                 }
+
+                #[cfg(target_os = "macos")]
+                if cx.edited_document.is_some() != document_edited {
+                    use winit::platform::macos::WindowExtMacOS;
+                    document_edited = cx.edited_document.is_some();
+                    window.set_document_edited(document_edited);
+                }
+
+                if let Some(spec) = cx.pending_window.take() {
+                    window.set_inner_size(LogicalSize::new(spec.size[0], spec.size[1]));
+                    if let Some([x, y]) = spec.position {
+                        window.set_outer_position(LogicalPosition::new(x, y));
+                    }
+                    // Kiosk mode keeps its own fullscreen.
+                    if !kiosk {
+                        window
+                            .set_fullscreen(spec.fullscreen.then(|| Fullscreen::Borderless(None)));
+                        cx.fullscreen = spec.fullscreen;
+                    }
+                    window.set_title(&spec.title);
+                    cx.window_title = spec.title.clone();
+                    window_title = spec.title;
+                }
+
+                for change in std::mem::take(&mut cx.window_changes) {
+                    match change {
+                        WindowChange::Size([width, height]) => {
+                            window.set_inner_size(LogicalSize::new(width, height))
+                        }
+                        WindowChange::MinSize(size) => window.set_min_inner_size(
+                            size.map(|[width, height]| LogicalSize::new(width, height)),
+                        ),
+                        WindowChange::MaxSize(size) => window.set_max_inner_size(
+                            size.map(|[width, height]| LogicalSize::new(width, height)),
+                        ),
+                        WindowChange::Resizable(resizable) => window.set_resizable(resizable),
+                        WindowChange::Position([x, y]) => {
+                            window.set_outer_position(LogicalPosition::new(x, y))
+                        }
+                        WindowChange::Close => *control_flow = ControlFlow::Exit,
+                        WindowChange::Minimize => window.set_minimized(true),
+                        WindowChange::ToggleMaximized => {
+                            window.set_maximized(!window.is_maximized())
+                        }
+                        WindowChange::Drag => {
+                            if let Err(err) = window.drag_window() {
+                                println!("unable to drag window: {}", err);
+                            }
+                        }
+                        WindowChange::DragResize(edge) => drag_resize(&window, edge),
+                        WindowChange::Snap(snap) => snap_window(&window, snap, &mut unsnapped),
+                        WindowChange::ContentProtection(protected) => {
+                            window.set_content_protected(protected)
+                        }
+                    }
+                }
             }
             WEvent::RedrawRequested(_) => {
                 // Redraw the application.
@@ -255,11 +716,7 @@ pub fn rui(view: impl View) {
                 // this event rather than in MainEventsCleared, since rendering in here allows
                 // the program to gracefully handle redraws requested by the OS.
 
-                let window_size = window.inner_size();
-                let scale = window.scale_factor() as f32;
-                // println!("window_size: {:?}", window_size);
-                let width = window_size.width as f32 / scale;
-                let height = window_size.height as f32 / scale;
+                let (window_size, scale) = logical_size(&window);
 
                 // println!("RedrawRequested");
                 cx.render(
@@ -271,9 +728,36 @@ pub fn rui(view: impl View) {
                     },
                     &view,
                     &mut vger,
-                    [width, height].into(),
+                    window_size,
                     scale,
                 );
+
+                // The window background is set while drawing.
+                if cx.window_background != window_background {
+                    window_background = cx.window_background;
+                    apply_window_background(&window, window_background);
+                    config.alpha_mode = alpha_mode(
+                        &surface.get_capabilities(&adapter).alpha_modes,
+                        window_background.is_transparent(),
+                    );
+                    surface.configure(&device, &config);
+                    window.request_redraw();
+                }
+
+                // Input methods only run while a text view has focus, and
+                // follow its caret.
+                if cx.text_input_area != text_input_area {
+                    if text_input_area.is_some() != cx.text_input_area.is_some() {
+                        window.set_ime_allowed(cx.text_input_area.is_some());
+                    }
+                    text_input_area = cx.text_input_area;
+                    if let Some(rect) = text_input_area {
+                        window.set_ime_position(LogicalPosition::new(
+                            rect.min_x(),
+                            window_size.height - rect.min_y(),
+                        ));
+                    }
+                }
             }
             WEvent::WindowEvent {
                 event: WindowEvent::MouseInput { state, button, .. },
@@ -281,6 +765,17 @@ pub fn rui(view: impl View) {
             } => {
                 match state {
                     ElementState::Pressed => {
+                        // Borderless windows resize from their edges.
+                        if !decorations && button == WMouseButton::Left && window.is_resizable() {
+                            let (window_size, _) = logical_size(&window);
+                            if let Some(edge) =
+                                WindowEdge::at(mouse_position, window_size.cast_unit())
+                            {
+                                drag_resize(&window, edge);
+                                return;
+                            }
+                        }
+
                         cx.mouse_button = match button {
                             WMouseButton::Left => Some(MouseButton::Left),
                             WMouseButton::Right => Some(MouseButton::Right),
@@ -303,11 +798,33 @@ pub fn rui(view: impl View) {
                     }
                 };
             }
+            WEvent::WindowEvent {
+                event: WindowEvent::MouseWheel { delta, .. },
+                ..
+            } => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => {
+                        [x * SCROLL_LINE_HEIGHT, -y * SCROLL_LINE_HEIGHT]
+                    }
+                    MouseScrollDelta::PixelDelta(position) => {
+                        let scale = window.scale_factor() as f32;
+                        [position.x as f32 / scale, -position.y as f32 / scale]
+                    }
+                };
+                let event = Event::Scroll {
+                    position: mouse_position,
+                    delta: delta.into(),
+                };
+                process_event(&mut cx, &view, &event, &window)
+            }
             WEvent::WindowEvent {
                 window_id,
                 event:
                     WindowEvent::Touch(Touch {
-                        phase, location, ..
+                        phase,
+                        location,
+                        id: touch_id,
+                        ..
                     }),
                 ..
             } => {
@@ -323,18 +840,25 @@ pub fn rui(view: impl View) {
                 ]
                 .into();
 
-                let delta = position - cx.previous_position[0];
+                let id = match cx.gestures.touch_id(touch_id, phase == TouchPhase::Started) {
+                    Some(id) => id,
+                    // Too many fingers, or one we never saw begin.
+                    None => return,
+                };
+
+                let delta = position - cx.previous_position[id];
+                cx.previous_position[id] = position;
 
-                // TODO: Multi-Touch management
                 let event = match phase {
-                    TouchPhase::Started => Some(Event::TouchBegin { id: 0, position }),
+                    TouchPhase::Started => Some(Event::TouchBegin { id, position }),
                     TouchPhase::Moved => Some(Event::TouchMove {
-                        id: 0,
+                        id,
                         position,
                         delta,
                     }),
                     TouchPhase::Ended | TouchPhase::Cancelled => {
-                        Some(Event::TouchEnd { id: 0, position })
+                        cx.gestures.release_touch_id(id);
+                        Some(Event::TouchEnd { id, position })
                     }
                 };
 
@@ -352,190 +876,291 @@ pub fn rui(view: impl View) {
                     (config.height as f32 - position.y as f32) / scale,
                 ]
                 .into();
-                // let event = Event::TouchMove {
-                //     id: 0,
-                //     position: mouse_position,
-                // };
-                // process_event(&mut cx, &view, &event, &window)
+
+                if !decorations && window.is_resizable() {
+                    let (window_size, _) = logical_size(&window);
+                    let edge = WindowEdge::at(mouse_position, window_size.cast_unit());
+                    if edge != resize_cursor {
+                        resize_cursor = edge;
+                        window.set_cursor_icon(match edge {
+                            Some(edge) => resize_direction(edge).into(),
+                            None => CursorIcon::Default,
+                        });
+                    }
+                }
+
+                // Some platforms report the cursor during a drag.
+                files_hover_changed |= !hovered_files.is_empty();
+
+                let event = Event::Hover {
+                    position: mouse_position,
+                };
+                process_event(&mut cx, &view, &event, &window)
+            }
+            WEvent::WindowEvent {
+                event: WindowEvent::HoveredFile(path),
+                ..
+            } => {
+                hovered_files.push(path);
+                files_hover_changed = true;
+            }
+            WEvent::WindowEvent {
+                event: WindowEvent::HoveredFileCancelled,
+                ..
+            } => {
+                hovered_files.clear();
+                files_hover_changed = false;
+                process_event(&mut cx, &view, &Event::DropHoverLeave, &window)
+            }
+            WEvent::WindowEvent {
+                event: WindowEvent::DroppedFile(path),
+                ..
+            } => dropped_files.push(path),
+            WEvent::WindowEvent {
+                event: WindowEvent::CursorEntered { .. },
+                ..
+            } => {
+                // Nothing to do until the CursorMoved which follows, since
+                // this doesn't say where the cursor is.
             }
+            WEvent::WindowEvent {
+                event: WindowEvent::CursorLeft { .. },
+                ..
+            } => process_event(&mut cx, &view, &Event::HoverLeave, &window),
 
             WEvent::WindowEvent {
                 event: WindowEvent::KeyboardInput { input, .. },
                 ..
             } => {
-                if input.state == ElementState::Pressed {
+                let key = input.virtual_keycode.and_then(|code| match code {
+                    // VirtualKeyCode::Character(c) => Some(Key::Character(c)),
+                    VirtualKeyCode::Key1 => {
+                        Some(Key::Character(if cx.key_mods.shift { '!' } else { '1' }))
+                    }
+                    VirtualKeyCode::Key2 => {
+                        Some(Key::Character(if cx.key_mods.shift { '@' } else { '2' }))
+                    }
+                    VirtualKeyCode::Key3 => {
+                        Some(Key::Character(if cx.key_mods.shift { '#' } else { '3' }))
+                    }
+                    VirtualKeyCode::Key4 => {
+                        Some(Key::Character(if cx.key_mods.shift { '$' } else { '4' }))
+                    }
+                    VirtualKeyCode::Key5 => {
+                        Some(Key::Character(if cx.key_mods.shift { '%' } else { '5' }))
+                    }
+                    VirtualKeyCode::Key6 => {
+                        Some(Key::Character(if cx.key_mods.shift { '^' } else { '6' }))
+                    }
+                    VirtualKeyCode::Key7 => {
+                        Some(Key::Character(if cx.key_mods.shift { '&' } else { '7' }))
+                    }
+                    VirtualKeyCode::Key8 => {
+                        Some(Key::Character(if cx.key_mods.shift { '*' } else { '8' }))
+                    }
+                    VirtualKeyCode::Key9 => {
+                        Some(Key::Character(if cx.key_mods.shift { '(' } else { '9' }))
+                    }
+                    VirtualKeyCode::Key0 => {
+                        Some(Key::Character(if cx.key_mods.shift { ')' } else { '0' }))
+                    }
+                    VirtualKeyCode::A => {
+                        Some(Key::Character(if cx.key_mods.shift { 'A' } else { 'a' }))
+                    }
+                    VirtualKeyCode::B => {
+                        Some(Key::Character(if cx.key_mods.shift { 'B' } else { 'b' }))
+                    }
+                    VirtualKeyCode::C => {
+                        Some(Key::Character(if cx.key_mods.shift { 'C' } else { 'c' }))
+                    }
+                    VirtualKeyCode::D => {
+                        Some(Key::Character(if cx.key_mods.shift { 'D' } else { 'd' }))
+                    }
+                    VirtualKeyCode::E => {
+                        Some(Key::Character(if cx.key_mods.shift { 'E' } else { 'e' }))
+                    }
+                    VirtualKeyCode::F => {
+                        Some(Key::Character(if cx.key_mods.shift { 'F' } else { 'f' }))
+                    }
+                    VirtualKeyCode::G => {
+                        Some(Key::Character(if cx.key_mods.shift { 'G' } else { 'g' }))
+                    }
+                    VirtualKeyCode::H => {
+                        Some(Key::Character(if cx.key_mods.shift { 'H' } else { 'h' }))
+                    }
+                    VirtualKeyCode::I => {
+                        Some(Key::Character(if cx.key_mods.shift { 'I' } else { 'i' }))
+                    }
+                    VirtualKeyCode::J => {
+                        Some(Key::Character(if cx.key_mods.shift { 'J' } else { 'j' }))
+                    }
+                    VirtualKeyCode::K => {
+                        Some(Key::Character(if cx.key_mods.shift { 'K' } else { 'k' }))
+                    }
+                    VirtualKeyCode::L => {
+                        Some(Key::Character(if cx.key_mods.shift { 'L' } else { 'l' }))
+                    }
+                    VirtualKeyCode::M => {
+                        Some(Key::Character(if cx.key_mods.shift { 'M' } else { 'm' }))
+                    }
+                    VirtualKeyCode::N => {
+                        Some(Key::Character(if cx.key_mods.shift { 'N' } else { 'n' }))
+                    }
+                    VirtualKeyCode::O => {
+                        Some(Key::Character(if cx.key_mods.shift { 'O' } else { 'o' }))
+                    }
+                    VirtualKeyCode::P => {
+                        Some(Key::Character(if cx.key_mods.shift { 'P' } else { 'p' }))
+                    }
+                    VirtualKeyCode::Q => {
+                        Some(Key::Character(if cx.key_mods.shift { 'Q' } else { 'q' }))
+                    }
+                    VirtualKeyCode::R => {
+                        Some(Key::Character(if cx.key_mods.shift { 'R' } else { 'r' }))
+                    }
+                    VirtualKeyCode::S => {
+                        Some(Key::Character(if cx.key_mods.shift { 'S' } else { 's' }))
+                    }
+                    VirtualKeyCode::T => {
+                        Some(Key::Character(if cx.key_mods.shift { 'T' } else { 't' }))
+                    }
+                    VirtualKeyCode::U => {
+                        Some(Key::Character(if cx.key_mods.shift { 'U' } else { 'u' }))
+                    }
+                    VirtualKeyCode::V => {
+                        Some(Key::Character(if cx.key_mods.shift { 'V' } else { 'v' }))
+                    }
+                    VirtualKeyCode::W => {
+                        Some(Key::Character(if cx.key_mods.shift { 'W' } else { 'w' }))
+                    }
+                    VirtualKeyCode::X => {
+                        Some(Key::Character(if cx.key_mods.shift { 'X' } else { 'x' }))
+                    }
+                    VirtualKeyCode::Y => {
+                        Some(Key::Character(if cx.key_mods.shift { 'Y' } else { 'y' }))
+                    }
+                    VirtualKeyCode::Z => {
+                        Some(Key::Character(if cx.key_mods.shift { 'Z' } else { 'z' }))
+                    }
+                    VirtualKeyCode::Semicolon => {
+                        Some(Key::Character(if cx.key_mods.shift { ':' } else { ';' }))
+                    }
+                    VirtualKeyCode::Colon => Some(Key::Character(':')),
+                    VirtualKeyCode::Caret => Some(Key::Character('^')),
+                    VirtualKeyCode::Asterisk => Some(Key::Character('*')),
+                    VirtualKeyCode::Period => {
+                        Some(Key::Character(if cx.key_mods.shift { '>' } else { '.' }))
+                    }
+                    VirtualKeyCode::Comma => {
+                        Some(Key::Character(if cx.key_mods.shift { '<' } else { ',' }))
+                    }
+                    VirtualKeyCode::Equals | VirtualKeyCode::NumpadEquals => {
+                        Some(Key::Character('='))
+                    }
+                    VirtualKeyCode::Plus | VirtualKeyCode::NumpadAdd => Some(Key::Character('+')),
+                    VirtualKeyCode::Minus | VirtualKeyCode::NumpadSubtract => {
+                        Some(Key::Character(if cx.key_mods.shift { '_' } else { '-' }))
+                    }
+                    VirtualKeyCode::Slash | VirtualKeyCode::NumpadDivide => {
+                        Some(Key::Character(if cx.key_mods.shift { '?' } else { '/' }))
+                    }
+                    VirtualKeyCode::Grave => {
+                        Some(Key::Character(if cx.key_mods.shift { '~' } else { '`' }))
+                    }
+                    VirtualKeyCode::Return => Some(Key::Enter),
+                    VirtualKeyCode::Tab => Some(Key::Tab),
+                    VirtualKeyCode::Space => Some(Key::Space),
+                    VirtualKeyCode::Down => Some(Key::ArrowDown),
+                    VirtualKeyCode::Left => Some(Key::ArrowLeft),
+                    VirtualKeyCode::Right => Some(Key::ArrowRight),
+                    VirtualKeyCode::Up => Some(Key::ArrowUp),
+                    VirtualKeyCode::End => Some(Key::End),
+                    VirtualKeyCode::Home => Some(Key::Home),
+                    VirtualKeyCode::PageDown => Some(Key::PageDown),
+                    VirtualKeyCode::PageUp => Some(Key::PageUp),
+                    VirtualKeyCode::Back => Some(Key::Backspace),
+                    VirtualKeyCode::Delete => Some(Key::Delete),
+                    VirtualKeyCode::Escape => Some(Key::Escape),
+                    VirtualKeyCode::F1 => Some(Key::F1),
+                    VirtualKeyCode::F2 => Some(Key::F2),
+                    VirtualKeyCode::F3 => Some(Key::F3),
+                    VirtualKeyCode::F4 => Some(Key::F4),
+                    VirtualKeyCode::F5 => Some(Key::F5),
+                    VirtualKeyCode::F6 => Some(Key::F6),
+                    VirtualKeyCode::F7 => Some(Key::F7),
+                    VirtualKeyCode::F8 => Some(Key::F8),
+                    VirtualKeyCode::F9 => Some(Key::F9),
+                    VirtualKeyCode::F10 => Some(Key::F10),
+                    VirtualKeyCode::F11 => Some(Key::F11),
+                    VirtualKeyCode::F12 => Some(Key::F12),
+                    _ => None,
+                });
+
+                if input.state == ElementState::Released {
                     if let Some(code) = input.virtual_keycode {
-                        let key = match code {
-                            // VirtualKeyCode::Character(c) => Some(Key::Character(c)),
-                            VirtualKeyCode::Key1 => {
-                                Some(Key::Character(if cx.key_mods.shift { '!' } else { '1' }))
-                            }
-                            VirtualKeyCode::Key2 => {
-                                Some(Key::Character(if cx.key_mods.shift { '@' } else { '2' }))
-                            }
-                            VirtualKeyCode::Key3 => {
-                                Some(Key::Character(if cx.key_mods.shift { '#' } else { '3' }))
-                            }
-                            VirtualKeyCode::Key4 => {
-                                Some(Key::Character(if cx.key_mods.shift { '$' } else { '4' }))
-                            }
-                            VirtualKeyCode::Key5 => {
-                                Some(Key::Character(if cx.key_mods.shift { '%' } else { '5' }))
-                            }
-                            VirtualKeyCode::Key6 => {
-                                Some(Key::Character(if cx.key_mods.shift { '^' } else { '6' }))
-                            }
-                            VirtualKeyCode::Key7 => {
-                                Some(Key::Character(if cx.key_mods.shift { '&' } else { '7' }))
-                            }
-                            VirtualKeyCode::Key8 => {
-                                Some(Key::Character(if cx.key_mods.shift { '*' } else { '8' }))
-                            }
-                            VirtualKeyCode::Key9 => {
-                                Some(Key::Character(if cx.key_mods.shift { '(' } else { '9' }))
-                            }
-                            VirtualKeyCode::Key0 => {
-                                Some(Key::Character(if cx.key_mods.shift { ')' } else { '0' }))
-                            }
-                            VirtualKeyCode::A => {
-                                Some(Key::Character(if cx.key_mods.shift { 'A' } else { 'a' }))
-                            }
-                            VirtualKeyCode::B => {
-                                Some(Key::Character(if cx.key_mods.shift { 'B' } else { 'b' }))
-                            }
-                            VirtualKeyCode::C => {
-                                Some(Key::Character(if cx.key_mods.shift { 'C' } else { 'c' }))
-                            }
-                            VirtualKeyCode::D => {
-                                Some(Key::Character(if cx.key_mods.shift { 'D' } else { 'd' }))
-                            }
-                            VirtualKeyCode::E => {
-                                Some(Key::Character(if cx.key_mods.shift { 'E' } else { 'e' }))
-                            }
-                            VirtualKeyCode::F => {
-                                Some(Key::Character(if cx.key_mods.shift { 'F' } else { 'f' }))
-                            }
-                            VirtualKeyCode::G => {
-                                Some(Key::Character(if cx.key_mods.shift { 'G' } else { 'g' }))
-                            }
-                            VirtualKeyCode::H => {
-                                Some(Key::Character(if cx.key_mods.shift { 'H' } else { 'h' }))
-                            }
-                            VirtualKeyCode::I => {
-                                Some(Key::Character(if cx.key_mods.shift { 'I' } else { 'i' }))
-                            }
-                            VirtualKeyCode::J => {
-                                Some(Key::Character(if cx.key_mods.shift { 'J' } else { 'j' }))
-                            }
-                            VirtualKeyCode::K => {
-                                Some(Key::Character(if cx.key_mods.shift { 'K' } else { 'k' }))
-                            }
-                            VirtualKeyCode::L => {
-                                Some(Key::Character(if cx.key_mods.shift { 'L' } else { 'l' }))
-                            }
-                            VirtualKeyCode::M => {
-                                Some(Key::Character(if cx.key_mods.shift { 'M' } else { 'm' }))
-                            }
-                            VirtualKeyCode::N => {
-                                Some(Key::Character(if cx.key_mods.shift { 'N' } else { 'n' }))
-                            }
-                            VirtualKeyCode::O => {
-                                Some(Key::Character(if cx.key_mods.shift { 'O' } else { 'o' }))
-                            }
-                            VirtualKeyCode::P => {
-                                Some(Key::Character(if cx.key_mods.shift { 'P' } else { 'p' }))
-                            }
-                            VirtualKeyCode::Q => {
-                                Some(Key::Character(if cx.key_mods.shift { 'Q' } else { 'q' }))
-                            }
-                            VirtualKeyCode::R => {
-                                Some(Key::Character(if cx.key_mods.shift { 'R' } else { 'r' }))
-                            }
-                            VirtualKeyCode::S => {
-                                Some(Key::Character(if cx.key_mods.shift { 'S' } else { 's' }))
-                            }
-                            VirtualKeyCode::T => {
-                                Some(Key::Character(if cx.key_mods.shift { 'T' } else { 't' }))
-                            }
-                            VirtualKeyCode::U => {
-                                Some(Key::Character(if cx.key_mods.shift { 'U' } else { 'u' }))
-                            }
-                            VirtualKeyCode::V => {
-                                Some(Key::Character(if cx.key_mods.shift { 'V' } else { 'v' }))
-                            }
-                            VirtualKeyCode::W => {
-                                Some(Key::Character(if cx.key_mods.shift { 'W' } else { 'w' }))
-                            }
-                            VirtualKeyCode::X => {
-                                Some(Key::Character(if cx.key_mods.shift { 'X' } else { 'x' }))
-                            }
-                            VirtualKeyCode::Y => {
-                                Some(Key::Character(if cx.key_mods.shift { 'Y' } else { 'y' }))
-                            }
-                            VirtualKeyCode::Z => {
-                                Some(Key::Character(if cx.key_mods.shift { 'Z' } else { 'z' }))
-                            }
-                            VirtualKeyCode::Semicolon => {
-                                Some(Key::Character(if cx.key_mods.shift { ':' } else { ';' }))
-                            }
-                            VirtualKeyCode::Colon => Some(Key::Character(':')),
-                            VirtualKeyCode::Caret => Some(Key::Character('^')),
-                            VirtualKeyCode::Asterisk => Some(Key::Character('*')),
-                            VirtualKeyCode::Period => {
-                                Some(Key::Character(if cx.key_mods.shift { '>' } else { '.' }))
-                            }
-                            VirtualKeyCode::Comma => {
-                                Some(Key::Character(if cx.key_mods.shift { '<' } else { ',' }))
-                            }
-                            VirtualKeyCode::Equals | VirtualKeyCode::NumpadEquals => {
-                                Some(Key::Character('='))
-                            }
-                            VirtualKeyCode::Plus | VirtualKeyCode::NumpadAdd => {
-                                Some(Key::Character('+'))
-                            }
-                            VirtualKeyCode::Minus | VirtualKeyCode::NumpadSubtract => {
-                                Some(Key::Character(if cx.key_mods.shift { '_' } else { '-' }))
-                            }
-                            VirtualKeyCode::Slash | VirtualKeyCode::NumpadDivide => {
-                                Some(Key::Character(if cx.key_mods.shift { '?' } else { '/' }))
-                            }
-                            VirtualKeyCode::Grave => {
-                                Some(Key::Character(if cx.key_mods.shift { '~' } else { '`' }))
-                            }
-                            VirtualKeyCode::Return => Some(Key::Enter),
-                            VirtualKeyCode::Tab => Some(Key::Tab),
-                            VirtualKeyCode::Space => Some(Key::Space),
-                            VirtualKeyCode::Down => Some(Key::ArrowDown),
-                            VirtualKeyCode::Left => Some(Key::ArrowLeft),
-                            VirtualKeyCode::Right => Some(Key::ArrowRight),
-                            VirtualKeyCode::Up => Some(Key::ArrowUp),
-                            VirtualKeyCode::End => Some(Key::End),
-                            VirtualKeyCode::Home => Some(Key::Home),
-                            VirtualKeyCode::PageDown => Some(Key::PageDown),
-                            VirtualKeyCode::PageUp => Some(Key::PageUp),
-                            VirtualKeyCode::Back => Some(Key::Backspace),
-                            VirtualKeyCode::Delete => Some(Key::Delete),
-                            VirtualKeyCode::Escape => Some(Key::Escape),
-                            VirtualKeyCode::F1 => Some(Key::F1),
-                            VirtualKeyCode::F2 => Some(Key::F2),
-                            VirtualKeyCode::F3 => Some(Key::F3),
-                            VirtualKeyCode::F4 => Some(Key::F4),
-                            VirtualKeyCode::F5 => Some(Key::F5),
-                            VirtualKeyCode::F6 => Some(Key::F6),
-                            VirtualKeyCode::F7 => Some(Key::F7),
-                            VirtualKeyCode::F8 => Some(Key::F8),
-                            VirtualKeyCode::F9 => Some(Key::F9),
-                            VirtualKeyCode::F10 => Some(Key::F10),
-                            VirtualKeyCode::F11 => Some(Key::F11),
-                            VirtualKeyCode::F12 => Some(Key::F12),
-                            _ => None,
+                        held_keys.remove(&code);
+                    }
+                    if let Some(key) = key {
+                        let event = Event::KeyUp {
+                            key,
+                            mods: cx.key_mods,
                         };
+                        cx.process(&view, &event);
+                    }
+                    return;
+                }
+                // winit doesn't say which presses are repeats.
+                let repeat = input
+                    .virtual_keycode
+                    .map_or(false, |code| !held_keys.insert(code));
 
-                        if let Some(key) = key {
-                            cx.process(&view, &Event::Key(key))
-                        }
+                // Borderless windows don't get the system's snapping
+                // shortcuts on Windows, or any on Linux.
+                if !decorations
+                    && !cfg!(target_os = "macos")
+                    && cx.key_mods.command
+                    && window.is_resizable()
+                {
+                    let snap = match key {
+                        Some(Key::ArrowLeft) => Some(WindowSnap::LeftHalf),
+                        Some(Key::ArrowRight) => Some(WindowSnap::RightHalf),
+                        Some(Key::ArrowUp) => Some(WindowSnap::Maximized),
+                        Some(Key::ArrowDown) => Some(WindowSnap::Restore),
+                        _ => None,
+                    };
+                    if let Some(snap) = snap {
+                        snap_window(&window, snap, &mut unsnapped);
+                        return;
                     }
                 }
+
+                // While editing text, undo and redo are the text's own.
+                let text_undo = cx.is_editing_text()
+                    && key
+                        .as_ref()
+                        .map_or(false, |key| undo_shortcut(key, &cx.key_mods).is_some());
+
+                // Command hotkeys take precedence over key events.
+                if cx.key_mods.shortcut() && !text_undo {
+                    if let Some(cmd) = find_shortcut(
+                        &commands,
+                        cx.shortcut_matching,
+                        key.as_ref(),
+                        HotKey::from_scancode(input.scancode),
+                    ) {
+                        cx.process(&view, &Event::Command(cmd.path.clone()));
+                        return;
+                    }
+                }
+
+                if let Some(key) = key {
+                    let event = Event::Key {
+                        key,
+                        mods: cx.key_mods,
+                        repeat,
+                    };
+                    cx.process(&view, &event)
+                }
             }
 
             WEvent::WindowEvent {
@@ -550,6 +1175,22 @@ pub fn rui(view: impl View) {
                 };
             }
 
+            // Note that winit only reports text committed after a composition,
+            // so dictation and text replacements that insert directly are
+            // not seen here. Those need our own `insertText:replacementRange:`
+            // on macOS, and the field's text and selection shown to AppKit.
+            WEvent::WindowEvent {
+                event: WindowEvent::Ime(ime),
+                ..
+            } => {
+                let input = match ime {
+                    Ime::Preedit(text, selection) => TextInput::Compose { text, selection },
+                    Ime::Commit(text) => TextInput::Commit(text),
+                    Ime::Enabled | Ime::Disabled => return,
+                };
+                process_event(&mut cx, &view, &Event::TextInput(input), &window);
+            }
+
             WEvent::DeviceEvent {
                 event: winit::event::DeviceEvent::MouseMotion { delta },
                 ..
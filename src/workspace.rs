@@ -0,0 +1,72 @@
+use crate::*;
+use std::collections::BTreeMap;
+
+/// A window in a saved workspace.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowSpec {
+    pub title: String,
+    /// Position of the window's outer frame in points, if known.
+    pub position: Option<[f32; 2]>,
+    /// Size of the window's content in points.
+    pub size: [f32; 2],
+    pub fullscreen: bool,
+}
+
+/// Windows, dock layouts and panel state, from `Context::save_workspace`.
+///
+/// Dock layouts and panel state are keyed by the identifiers given to
+/// `DockSpace::workspace_key` and the `workspace_state` modifier.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkspaceSpec {
+    /// rui apps have a single window for now.
+    pub windows: Vec<WindowSpec>,
+    pub docks: BTreeMap<String, DockLayout>,
+    pub panels: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_workspace() {
+        let mut cx = Context::new();
+
+        let mut spec = WorkspaceSpec::default();
+        spec.panels.insert("zoom".into(), "2.5".into());
+        cx.restore_workspace(spec);
+
+        let ui = state(
+            || 1.0_f32,
+            |zoom, _| rectangle().workspace_state("zoom", zoom),
+        );
+        let mut path = vec![0];
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::new(LocalPoint::zero(), [90.0, 90.0].into()),
+            },
+        );
+
+        let zoom = StateHandle::<f32>::new(cx.view_id(&path));
+        assert_eq!(cx[zoom], 2.5);
+
+        cx[zoom] = 3.0;
+        ui.layout(
+            &mut path,
+            &mut LayoutArgs {
+                sz: [100.0, 100.0].into(),
+                cx: &mut cx,
+                text_bounds: &mut |_, _, _| LocalRect::new(LocalPoint::zero(), [90.0, 90.0].into()),
+            },
+        );
+        let saved = cx.save_workspace();
+        assert_eq!(saved.panels["zoom"], "3");
+        assert_eq!(saved.windows.len(), 1);
+    }
+}